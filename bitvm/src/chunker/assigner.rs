@@ -5,7 +5,8 @@ use super::{
     chunk_groth16_verifier::groth16_verify_to_segments,
     common::{self, *},
     disprove_execution::RawProof,
-    elements::ElementTrait,
+    elements::{ElementError, ElementTrait},
+    segment::Segment,
 };
 use crate::{
     signatures::signing_winternitz::{
@@ -16,7 +17,10 @@ use crate::{
     execute_script_with_inputs,
     treepp::*,
 };
-use std::{collections::BTreeMap, rc::Rc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 /// Implement `BCAssinger` to adapt with bridge.
 #[allow(clippy::borrowed_box)]
@@ -31,7 +35,7 @@ pub trait BCAssigner: Default {
     /// output witness for all elements, used by assert transaction
     fn all_intermediate_witnesses(
         &self,
-        elements: BTreeMap<String, Rc<Box<dyn ElementTrait>>>,
+        elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>>,
     ) -> Vec<Vec<RawWitness>>;
     /// recover hashes from witnesses
     fn recover_from_witness(
@@ -39,6 +43,10 @@ pub trait BCAssigner: Default {
         witnesses: Vec<Vec<RawWitness>>,
         vk: VerifyingKey<ark_bn254::Bn254>,
     ) -> (BTreeMap<String, BLAKE3HASH>, RawProof);
+    /// whether the elements created by this assigner reject invalid points
+    fn checks_points(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Default)]
@@ -64,7 +72,7 @@ impl BCAssigner for DummyAssigner {
             element.to_witness().unwrap()
         } else {
             // else use the hash of element
-            element.to_hash_witness().unwrap()
+            element.to_hash_witness().unwrap().unwrap()
         }
     }
 
@@ -103,7 +111,7 @@ impl BCAssigner for DummyAssigner {
 
     fn all_intermediate_witnesses(
         &self,
-        elements: BTreeMap<String, Rc<Box<dyn ElementTrait>>>,
+        elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>>,
     ) -> Vec<Vec<RawWitness>> {
         for (key, _) in self.bc_map.iter() {
             if !elements.contains_key(key) {
@@ -118,6 +126,49 @@ impl BCAssigner for DummyAssigner {
     }
 }
 
+/// Assigner for the segments of a committed proof, its points are not checked.
+/// A malicious operator may commit points that are not valid group elements, the
+/// segments still have to be rebuilt for them to find the one to disprove.
+#[derive(Default)]
+pub struct UncheckedPointsAssigner<A: BCAssigner>(pub A);
+
+impl<A: BCAssigner> BCAssigner for UncheckedPointsAssigner<A> {
+    fn create_hash(&mut self, id: &str) {
+        self.0.create_hash(id)
+    }
+
+    fn locking_script<T: ElementTrait + ?Sized>(&self, element: &Box<T>) -> Script {
+        self.0.locking_script(element)
+    }
+
+    fn get_witness<T: ElementTrait + ?Sized>(&self, element: &Box<T>) -> RawWitness {
+        self.0.get_witness(element)
+    }
+
+    fn all_intermediate_scripts(&self) -> Vec<Vec<Script>> {
+        self.0.all_intermediate_scripts()
+    }
+
+    fn all_intermediate_witnesses(
+        &self,
+        elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>>,
+    ) -> Vec<Vec<RawWitness>> {
+        self.0.all_intermediate_witnesses(elements)
+    }
+
+    fn recover_from_witness(
+        &mut self,
+        witnesses: Vec<Vec<RawWitness>>,
+        vk: VerifyingKey<ark_bn254::Bn254>,
+    ) -> (BTreeMap<String, BLAKE3HASH>, RawProof) {
+        self.0.recover_from_witness(witnesses, vk)
+    }
+
+    fn checks_points(&self) -> bool {
+        false
+    }
+}
+
 /// This assigner records all intermediate values messages.
 /// It run the entire chunker with a default proof. A git-commit-related cache may reduce the time.
 #[derive(Default)]
@@ -217,7 +268,7 @@ impl BCAssigner for BridgeAssigner {
         } else {
             // else use the hash of element
             WinternitzSigningInputs {
-                message: &element.to_hash().unwrap().to_vec(),
+                message: &element.to_hash().unwrap().unwrap().to_vec(),
                 signing_key: secret_key,
             }
         };
@@ -231,7 +282,7 @@ impl BCAssigner for BridgeAssigner {
 
     fn all_intermediate_witnesses(
         &self,
-        elements: BTreeMap<String, Rc<Box<dyn ElementTrait>>>,
+        elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>>,
     ) -> Vec<Vec<RawWitness>> {
         todo!()
     }
@@ -291,9 +342,166 @@ impl BCAssigner for BridgeAssigner {
     }
 }
 
+/// An intermediate value whose hash differs from the cached baseline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangedElement {
+    pub id: String,
+    pub hash: BLAKE3HASH,
+}
+
+/// Opt-in assigner mode that caches the element hashes of a baseline proof.
+/// The dependencies between elements are taken from the segments: every result of a
+/// segment depends on all of its parameters. Elements that are never a result (the
+/// proof itself and free hints) are the inputs of the dependency graph.
+/// A proof equal to the baseline is answered from the cache without running the
+/// chunker. Otherwise the chunker evaluates the segments again, inputs are compared
+/// by their witness and only the elements that depend on a changed input are hashed.
+pub struct IncrementalAssigner {
+    baseline: RawProof,
+    hashes: BTreeMap<String, BLAKE3HASH>,
+    input_witnesses: BTreeMap<String, Option<RawWitness>>,
+    dependents: BTreeMap<String, BTreeSet<String>>,
+    recomputed: BTreeSet<String>,
+}
+
+impl IncrementalAssigner {
+    pub fn new(baseline: &RawProof) -> Result<Self, ElementError> {
+        let segments = Self::segments(baseline)?;
+
+        let mut input_witnesses = BTreeMap::new();
+        let mut dependents: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let results: BTreeSet<&str> = segments
+            .iter()
+            .flat_map(|segment| segment.result_list.iter().map(|result| result.id()))
+            .collect();
+        for segment in segments.iter() {
+            for param in segment.parameter_list.iter() {
+                if !results.contains(param.id()) {
+                    input_witnesses.insert(param.id().to_string(), param.to_witness());
+                }
+                dependents
+                    .entry(param.id().to_string())
+                    .or_default()
+                    .extend(
+                        segment
+                            .result_list
+                            .iter()
+                            .map(|result| result.id().to_string()),
+                    );
+            }
+        }
+
+        let hashes = Self::element_hashes(&segments);
+        Ok(Self {
+            baseline: baseline.clone(),
+            recomputed: hashes.keys().cloned().collect(),
+            hashes,
+            input_witnesses,
+            dependents,
+        })
+    }
+
+    /// Hashes of all intermediate values of the current baseline.
+    pub fn hashes(&self) -> &BTreeMap<String, BLAKE3HASH> { &self.hashes }
+
+    /// Elements whose hash was computed by the last assignment, all others were
+    /// taken from the cache.
+    pub fn recomputed(&self) -> &BTreeSet<String> { &self.recomputed }
+
+    /// Hashes of all intermediate values of `proof`, computed from scratch.
+    pub fn assign_full(proof: &RawProof) -> Result<BTreeMap<String, BLAKE3HASH>, ElementError> {
+        Ok(Self::element_hashes(&Self::segments(proof)?))
+    }
+
+    /// Recomputes the hashes of the elements that depend on a changed input and makes
+    /// `new_proof` the new baseline. Returns the elements whose hash changed.
+    pub fn assign_incremental(
+        &mut self,
+        new_proof: &RawProof,
+    ) -> Result<Vec<ChangedElement>, ElementError> {
+        if *new_proof == self.baseline {
+            self.recomputed.clear();
+            return Ok(vec![]);
+        }
+
+        // a different circuit has a different structure, start over
+        if new_proof.vk != self.baseline.vk {
+            let old_hashes = std::mem::take(&mut self.hashes);
+            *self = Self::new(new_proof)?;
+            return Ok(self
+                .hashes
+                .iter()
+                .filter(|(id, hash)| old_hashes.get(*id) != Some(hash))
+                .map(|(id, hash)| ChangedElement {
+                    id: id.clone(),
+                    hash: *hash,
+                })
+                .collect());
+        }
+
+        let segments = Self::segments(new_proof)?;
+        let elements: BTreeMap<&str, &Arc<Box<dyn ElementTrait>>> = segments
+            .iter()
+            .flat_map(|segment| segment.parameter_list.iter().chain(&segment.result_list))
+            .map(|element| (element.id(), element))
+            .collect();
+
+        // collect the dependents of all changed inputs
+        let mut dirty = BTreeSet::new();
+        let mut stack = vec![];
+        for (id, witness) in self.input_witnesses.iter_mut() {
+            let new_witness = elements[id.as_str()].to_witness();
+            if *witness != new_witness {
+                *witness = new_witness;
+                stack.push(id.clone());
+            }
+        }
+        while let Some(id) = stack.pop() {
+            if dirty.insert(id.clone()) {
+                if let Some(dependents) = self.dependents.get(&id) {
+                    stack.extend(dependents.iter().cloned());
+                }
+            }
+        }
+
+        let mut changed = vec![];
+        for id in dirty.iter() {
+            let hash = elements[id.as_str()].to_hash().unwrap().unwrap();
+            if self.hashes.get(id) != Some(&hash) {
+                self.hashes.insert(id.clone(), hash);
+                changed.push(ChangedElement {
+                    id: id.clone(),
+                    hash,
+                });
+            }
+        }
+        self.baseline = new_proof.clone();
+        self.recomputed = dirty;
+
+        Ok(changed)
+    }
+
+    fn segments(proof: &RawProof) -> Result<Vec<Segment>, ElementError> {
+        groth16_verify_to_segments(
+            &mut DummyAssigner::default(),
+            &proof.public,
+            &proof.proof,
+            &proof.vk,
+        )
+    }
+
+    fn element_hashes(segments: &[Segment]) -> BTreeMap<String, BLAKE3HASH> {
+        segments
+            .iter()
+            .flat_map(|segment| segment.parameter_list.iter().chain(&segment.result_list))
+            .map(|element| (element.id().to_string(), element.to_hash().unwrap().unwrap()))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::DummyAssigner;
+    use super::{ChangedElement, DummyAssigner, IncrementalAssigner};
     use crate::chunker::common::witness_size;
     use crate::execute_script_with_inputs;
     use crate::treepp::{script, Script};
@@ -309,6 +517,52 @@ mod tests {
         },
         signatures::utils::digits_to_number,
     };
+    use ark_ff::Field as _;
+
+    #[test]
+    fn test_incremental_assignment_agrees_with_full() {
+        let baseline = RawProof::default();
+        let mut assigner = IncrementalAssigner::new(&baseline).unwrap();
+        assert_eq!(
+            assigner.hashes(),
+            &IncrementalAssigner::assign_full(&baseline).unwrap()
+        );
+
+        // same circuit, new public input
+        let mut new_proof = baseline.clone();
+        new_proof.public[0] += ark_bn254::Fr::ONE;
+
+        let old_hashes = assigner.hashes().clone();
+        let changed = assigner.assign_incremental(&new_proof).unwrap();
+        let full_hashes = IncrementalAssigner::assign_full(&new_proof).unwrap();
+
+        assert_eq!(assigner.hashes(), &full_hashes);
+        assert!(!changed.is_empty());
+        assert_eq!(
+            changed,
+            full_hashes
+                .iter()
+                .filter(|(id, hash)| old_hashes.get(*id) != Some(hash))
+                .map(|(id, hash)| ChangedElement {
+                    id: id.clone(),
+                    hash: *hash
+                })
+                .collect::<Vec<_>>()
+        );
+
+        // only the dependents of the changed public input are hashed again
+        let recomputed = assigner.recomputed().clone();
+        assert!(recomputed.len() < full_hashes.len());
+        assert!(changed.iter().all(|element| recomputed.contains(&element.id)));
+        assert!(full_hashes
+            .iter()
+            .filter(|(id, _)| !recomputed.contains(*id))
+            .all(|(id, hash)| old_hashes.get(id) == Some(hash)));
+
+        // nothing changes when the proof is assigned again
+        assert!(assigner.assign_incremental(&new_proof).unwrap().is_empty());
+        assert!(assigner.recomputed().is_empty());
+    }
 
     #[test]
     fn test_variable_names() {
@@ -323,7 +577,9 @@ mod tests {
         let q4 = proof.proof.b;
 
         let mut q4_input = G2PointType::new(&mut dummy_assigner, "q4");
-        q4_input.fill_with_data(crate::chunker::elements::DataType::G2PointData(q4));
+        q4_input
+            .fill_with_data(crate::chunker::elements::DataType::G2PointData(q4))
+            .unwrap();
 
         let witness = q4_input.to_witness().unwrap();
 
@@ -26,7 +26,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
     c_inv: ark_bn254::Fq12,
     wi: ark_bn254::Fq12,
     p_lst: Vec<ark_bn254::G1Affine>,
-) -> (Vec<Segment>, Fq12Type, ark_bn254::Fq12) {
+) -> Result<(Vec<Segment>, Fq12Type, ark_bn254::Fq12), ElementError> {
     let mut segments = vec![];
 
     assert_eq!(constants.len(), 4);
@@ -38,13 +38,13 @@ pub fn chunk_accumulator<T: BCAssigner>(
     let mut f = c_inv;
 
     let mut param_c_inv = Fq12Type::new(assigner, "c_inv_init");
-    param_c_inv.fill_with_data(Fq12Data(c_inv));
+    param_c_inv.fill_with_data(Fq12Data(c_inv))?;
     let mut param_c = Fq12Type::new(assigner, "c_init");
-    param_c.fill_with_data(Fq12Data(c));
+    param_c.fill_with_data(Fq12Data(c))?;
     let mut param_wi = Fq12Type::new(assigner, "wi_init");
-    param_wi.fill_with_data(Fq12Data(wi));
+    param_wi.fill_with_data(Fq12Data(wi))?;
     let mut param_f = Fq12Type::new(assigner, "f_init");
-    param_f.fill_with_data(Fq12Data(f));
+    param_f.fill_with_data(Fq12Data(f))?;
 
     // ATE_LOOP_COUNT = 65
     for i in (1..ark_bn254::Config::ATE_LOOP_COUNT.len()).rev() {
@@ -57,7 +57,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
             fx,
             hinted_script.clone(),
             hint.clone(),
-        );
+        )?;
         segments.extend(s);
         param_f = r;
         f = fx;
@@ -71,7 +71,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
                 param_c_inv.clone(),
                 f,
                 c_inv,
-            );
+            )?;
             segments.extend(s);
             param_f = r;
             f = fx;
@@ -84,7 +84,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
                 param_c.clone(),
                 f,
                 c,
-            );
+            )?;
             segments.extend(s);
             param_f = r;
             f = fx;
@@ -118,7 +118,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
                 p.y.inverse().unwrap(),
                 coeffs,
                 coeffs_4,
-            );
+            )?;
             
             segments.extend(s);
             param_f = r;
@@ -154,7 +154,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
                     p.y.inverse().unwrap(),
                     coeffs,
                     coeffs_4,
-                );
+                )?;
 
                 segments.extend(s);
                 param_f = r;
@@ -172,7 +172,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
         c_inv_p,
         hinted_script.clone(),
         hint.clone(),
-    );
+    )?;
     segments.extend(s);
     let param_c_inv_p = r;
 
@@ -184,7 +184,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
         param_c_inv_p.clone(),
         f,
         c_inv_p,
-    );
+    )?;
 
     segments.extend(s);
     param_f = r;
@@ -199,7 +199,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
         c_p2,
         hinted_script.clone(),
         hint.clone(),
-    );
+    )?;
 
     segments.extend(s);
     let param_c_p2 = r;
@@ -212,7 +212,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
         param_c_p2.clone(),
         f,
         c_p2,
-    );
+    )?;
 
     segments.extend(s);
     param_f = r;
@@ -227,7 +227,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
         c_inv_p3,
         hinted_script.clone(),
         hint.clone(),
-    );
+    )?;
     segments.extend(s);
     let param_c_inv_p3 = r;
 
@@ -239,7 +239,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
         param_c_inv_p3.clone(),
         f,
         c_inv_p3,
-    );
+    )?;
 
     segments.extend(s);
     param_f = r;
@@ -253,7 +253,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
         param_wi.clone(),
         f,
         wi,
-    );
+    )?;
 
     segments.extend(s);
     param_f = r;
@@ -287,7 +287,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
             p.y.inverse().unwrap(),
             coeffs,
                 coeffs_4,
-            );
+            )?;
 
         segments.extend(s);
         param_f = r;
@@ -321,7 +321,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
             p.y.inverse().unwrap(),
             coeffs,
                 coeffs_4,
-            );
+            )?;
 
         segments.extend(s);
         param_f = r;
@@ -329,7 +329,7 @@ pub fn chunk_accumulator<T: BCAssigner>(
     }
 
     // evaluate L3
-    (segments, param_f, f)
+    Ok((segments, param_f, f))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -340,13 +340,13 @@ pub fn make_chunk_mul<T: BCAssigner>(
     param_b: Fq12Type,
     a: ark_bn254::Fq12,
     b: ark_bn254::Fq12,
-) -> (Vec<Segment>, Fq12Type) {
+) -> Result<(Vec<Segment>, Fq12Type), ElementError> {
     let mut segments = vec![];
 
-    let (segments_mul, c) = fq12_mul_wrapper(assigner, &fn_name, param_a, param_b, a, b);
+    let (segments_mul, c) = fq12_mul_wrapper(assigner, &fn_name, param_a, param_b, a, b)?;
     segments.extend(segments_mul);
 
-    (segments, c)
+    Ok((segments, c))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -357,10 +357,10 @@ pub fn make_chunk_square<T: BCAssigner>(
     fx: ark_bn254::Fq12,
     script: Script,
     hint: Vec<Hint>,
-) -> (Vec<Segment>, Fq12Type) {
+) -> Result<(Vec<Segment>, Fq12Type), ElementError> {
     let mut segments = vec![];
     let mut c = Fq12Type::new(assigner, &format!("{}_o_a", fn_name));
-    c.fill_with_data(Fq12Data(fx));
+    c.fill_with_data(Fq12Data(fx))?;
     segments.push(
         Segment::new_with_name(fn_name, script)
             .add_parameter(&param_f)
@@ -368,7 +368,7 @@ pub fn make_chunk_square<T: BCAssigner>(
             .add_hint(hint),
     );
 
-    (segments, c)
+    Ok((segments, c))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -382,11 +382,11 @@ pub fn make_chunk_ell<T: BCAssigner>(
     y: ark_bn254::Fq,
     constant: &EllCoeff,
     constant_4:Option<Fq6Type>,
-) -> (Vec<Segment>, Fq12Type) {
+) -> Result<(Vec<Segment>, Fq12Type), ElementError> {
     let mut segments = vec![];
-    let (segments_mul, c) = chunk_evaluate_line(assigner, &fn_name, pf, pxy, f, x, y, constant,constant_4);
+    let (segments_mul, c) = chunk_evaluate_line(assigner, &fn_name, pf, pxy, f, x, y, constant,constant_4)?;
     segments.extend(segments_mul);
-    (segments, c)
+    Ok((segments, c))
 }
 
 
@@ -397,11 +397,11 @@ pub fn make_chunk_frobenius_map<T: BCAssigner>(
     fx: ark_bn254::Fq12,
     script: Script,
     hint: Vec<Hint>,
-) -> (Vec<Segment>, Fq12Type) {
+) -> Result<(Vec<Segment>, Fq12Type), ElementError> {
     let mut segments = vec![];
 
     let mut c = Fq12Type::new(assigner, &format!("{}_o_a", fn_name));
-    c.fill_with_data(Fq12Data(fx));
+    c.fill_with_data(Fq12Data(fx))?;
     segments.push(
         Segment::new_with_name(fn_name, script)
             .add_parameter(&a)
@@ -409,7 +409,7 @@ pub fn make_chunk_frobenius_map<T: BCAssigner>(
             .add_hint(hint),
     );
 
-    (segments, c)
+    Ok((segments, c))
 }
 
 #[cfg(test)]
@@ -458,7 +458,7 @@ mod test {
         println!("chunk:");
         let mut assigner = DummyAssigner::default();
         let mut pa = Fq12Type::new(&mut assigner, &format!("i_a"));
-        pa.fill_with_data(Fq12Data(b));
+        pa.fill_with_data(Fq12Data(b)).unwrap();
         let (segments, _) = make_chunk_square(
             &mut assigner,
             "test".to_owned(),
@@ -466,7 +466,7 @@ mod test {
             b,
             hinted_square.clone(),
             hints.clone(),
-        );
+        ).unwrap();
         {
             let script0 = segments[0].script(&mut assigner);
             let witness0 = segments[0].witness(&mut assigner);
@@ -1,11 +1,11 @@
 use super::elements::{
-    DataType::Fq12Data, DataType::Fq2Data, DataType::Fq6Data, ElementTrait,
-    Fq12Type, Fq2Type, Fq6Type,
+    sparse_fq12, DataType::Fq12Data, DataType::Fq12SparseData, DataType::Fq2Data, ElementError,
+    ElementTrait, Fq12SparseType, Fq12Type, Fq2Type, Fq6Type,
 };
 use super::{assigner::BCAssigner, segment::Segment};
 use crate::bn254::{ell_coeffs::EllCoeff, fp254impl::Fp254Impl, fq::Fq, fq12::Fq12, fq2::Fq2};
 use crate::treepp::*;
-use ark_ff::{AdditiveGroup, Field};
+use ark_ff::Field;
 
 #[allow(clippy::too_many_arguments)]
 pub fn chunk_evaluate_line_wrapper<T: BCAssigner>(
@@ -15,11 +15,11 @@ pub fn chunk_evaluate_line_wrapper<T: BCAssigner>(
     x: ark_bn254::Fq,
     y: ark_bn254::Fq,
     constant: &EllCoeff,
-) -> (Vec<Segment>, Fq12Type) {
+) -> Result<(Vec<Segment>, Fq12Type), ElementError> {
     let mut pf = Fq12Type::new(assigner, &format!("{}{}", prefix, "f"));
-    pf.fill_with_data(Fq12Data(f));
+    pf.fill_with_data(Fq12Data(f))?;
     let mut pxy = Fq2Type::new(assigner, &format!("{}{}", prefix, "xy"));
-    pxy.fill_with_data(Fq2Data(ark_bn254::Fq2::new(x, y)));
+    pxy.fill_with_data(Fq2Data(ark_bn254::Fq2::new(x, y)))?;
 
     chunk_evaluate_line(assigner, prefix, pf, pxy, f, x, y, constant,None)
 }
@@ -35,7 +35,7 @@ pub fn chunk_evaluate_line<T: BCAssigner>(
     y: ark_bn254::Fq,
     constant: &EllCoeff,
     constant_4:Option<Fq6Type>,
-) -> (Vec<Segment>, Fq12Type) {
+) -> Result<(Vec<Segment>, Fq12Type), ElementError> {
     assert_eq!(constant.0, ark_bn254::Fq2::ONE);
 
     let (hinted_script1, hint1) = if constant_4.is_some() {
@@ -129,14 +129,18 @@ pub fn chunk_evaluate_line<T: BCAssigner>(
     hints_0.extend(hint3);
     hints_0.extend(hint4);
 
-    let mut tr0 = Fq6Type::new(assigner, &format!("{}{}", prefix, "c0"));
-    tr0.fill_with_data(Fq6Data(ark_bn254::Fq6::new(c1, c2, ark_bn254::Fq2::ZERO)));
+    // the line function, sparse with c0 = 1, c3 = c1' and c4 = c2'
+    let mut tr0 = Fq12SparseType::new(assigner, &format!("{}{}", prefix, "c0"));
+    tr0.fill_with_data(Fq12SparseData(sparse_fq12(constant.0, c1, c2)))?;
 
     let mut segment0 = Segment::new_with_name(
         format!("{}seg1", prefix),
         script! {
             {script_0}
-            {Fq2::push_zero()}
+            // [c1', c2'] -> [1, c1', c2']
+            {Fq2::push_one()}
+            {Fq2::roll(4)}
+            {Fq2::roll(4)}
         },
     );
     if constant_4.is_some() {
@@ -150,7 +154,7 @@ pub fn chunk_evaluate_line<T: BCAssigner>(
     f1.mul_by_034(&constant.0, &c1, &c2);
     let c = f1;
     let mut tc = Fq12Type::new(assigner, &format!("{}{}", prefix, "c"));
-    tc.fill_with_data(Fq12Data(c));
+    tc.fill_with_data(Fq12Data(c))?;
 
     let (script_1, hint_1) = Fq12::hinted_mul_by_34(f, c1, c2);
     //  // compute the new f with c1'(c3) and c2'(c4), where c1 is trival value 1
@@ -160,6 +164,8 @@ pub fn chunk_evaluate_line<T: BCAssigner>(
     let segment1 = Segment::new_with_name(
         format!("{}seg2", prefix),
         script! {
+            // [f, 1, c1', c2'] -> [f, c1', c2']
+            {Fq2::roll(4)}
             {Fq2::drop()}
             {script_1}
         },
@@ -169,7 +175,7 @@ pub fn chunk_evaluate_line<T: BCAssigner>(
     .add_result(&tc)
     .add_hint(hint_1);
 
-    (vec![segment0, segment1], tc)
+    Ok((vec![segment0, segment1], tc))
 }
 
 #[cfg(test)]
@@ -263,7 +269,7 @@ mod test {
                 -p.x / p.y,
                 p.y.inverse().unwrap(),
                 &coeffs.ell_coeffs[0],
-            );
+            ).unwrap();
         segments.extend(segments_mul);
 
         for segment in segments {
@@ -1,4 +1,6 @@
-use super::elements::{DataType::Fq12Data, DataType::Fq6Data, ElementTrait, Fq12Type, Fq6Type};
+use super::elements::{
+    DataType::Fq12Data, DataType::Fq6Data, ElementError, ElementTrait, Fq12Type, Fq6Type,
+};
 use super::{assigner::BCAssigner, segment::Segment};
 use crate::bn254::fq12::Fq12;
 use crate::bn254::fq6::Fq6;
@@ -13,7 +15,7 @@ pub fn fq12_mul_wrapper<T: BCAssigner>(
     pb: Fq12Type,
     a: ark_bn254::Fq12,
     b: ark_bn254::Fq12,
-) -> (Vec<Segment>, Fq12Type) {
+) -> Result<(Vec<Segment>, Fq12Type), ElementError> {
     chunk_fq12_multiplication(assigner, prefix, pa, pb, a, b)
 }
 
@@ -25,7 +27,7 @@ pub fn chunk_fq12_multiplication<T: BCAssigner>(
     pb: Fq12Type,
     a: ark_bn254::Fq12,
     b: ark_bn254::Fq12,
-) -> (Vec<Segment>, Fq12Type) {
+) -> Result<(Vec<Segment>, Fq12Type), ElementError> {
     let c = a.mul(b);
     let (hinted_script1, hint1) = Fq6::hinted_mul(6, a.c0, 0, b.c0);
     let (hinted_script2, hint2) = Fq6::hinted_mul(6, a.c1, 0, b.c1);
@@ -38,11 +40,11 @@ pub fn chunk_fq12_multiplication<T: BCAssigner>(
     // let mut b0_b1 = Fq6Type::new(assigner, &format!("{}{}", prefix, "b0_b1")); // means b0+b1
     let mut ab = Fq6Type::new(assigner, &format!("{}{}", prefix, "a0_a1 * b0_b1"));
 
-    a0b0.fill_with_data(Fq6Data(a.c0.mul(b.c0)));
-    a1b1.fill_with_data(Fq6Data(a.c1.mul(b.c1)));
+    a0b0.fill_with_data(Fq6Data(a.c0.mul(b.c0)))?;
+    a1b1.fill_with_data(Fq6Data(a.c1.mul(b.c1)))?;
     // a0_a1.fill_with_data(Fq6Data(a.c0.add(a.c1)));
     // b0_b1.fill_with_data(Fq6Data(b.c0.add(b.c1)));
-    ab.fill_with_data(Fq6Data(a.c0.add(a.c1).mul(b.c0.add(b.c1))));
+    ab.fill_with_data(Fq6Data(a.c0.add(a.c1).mul(b.c0.add(b.c1))))?;
 
     // final states
     // let mut c0 = Fq6Type::new(assigner, &format!("{}{}", prefix, "c0"));
@@ -104,7 +106,7 @@ pub fn chunk_fq12_multiplication<T: BCAssigner>(
     .add_hint(hint3);
 
     let mut tc = Fq12Type::new(assigner, &format!("{}{}", prefix, "c"));
-    tc.fill_with_data(Fq12Data(c));
+    tc.fill_with_data(Fq12Data(c))?;
 
     let segment6 = Segment::new_with_name(
         format!("{}{}", prefix, "fq6_to_fq12"),
@@ -132,13 +134,13 @@ pub fn chunk_fq12_multiplication<T: BCAssigner>(
     .add_parameter(&a1b1)
     .add_result(&tc);
 
-    (
+    Ok((
         vec![
             segment1, segment2, /*segment3,*/ segment4,
             /*segment5,*/ segment6, /*segment7,*/
         ],
         tc,
-    )
+    ))
 }
 
 #[cfg(test)]
@@ -170,11 +172,11 @@ mod test {
         let _c = a.mul(&b);
 
         // Output segment with data
-        a_type.fill_with_data(Fq12Data(a));
-        b_type.fill_with_data(Fq12Data(b));
+        a_type.fill_with_data(Fq12Data(a)).unwrap();
+        b_type.fill_with_data(Fq12Data(b)).unwrap();
 
         let (filled_segments, _): (Vec<Segment>, Fq12Type) =
-            fq12_mul_wrapper(&mut assigner, "test_", a_type, b_type, a, b);
+            fq12_mul_wrapper(&mut assigner, "test_", a_type, b_type, a, b).unwrap();
 
         println!("segements num {}", filled_segments.len());
 
@@ -1,5 +1,5 @@
 use super::elements::DataType::{Fq2Data, G1PointData};
-use super::elements::{Fq2Type, G1PointType};
+use super::elements::{ElementError, Fq2Type, G1PointType};
 
 use crate::bn254::fp254impl::Fp254Impl;
 use crate::bn254::fq::Fq;
@@ -19,21 +19,21 @@ pub fn g1_points<T: BCAssigner>(
     g1a: ark_bn254::G1Affine,
     proof: &Proof<Bn254>,
     vk: &VerifyingKey<Bn254>,
-) -> (Vec<Segment>, Vec<Fq2Type>) {
+) -> Result<(Vec<Segment>, Vec<Fq2Type>), ElementError> {
     let mut segments = vec![];
 
     let (p2, p3, p4) = (proof.c, vk.alpha_g1, proof.a);
     let mut g2p = G1PointType::new(assigner, "F_p2_init");
-    g2p.fill_with_data(G1PointData(p2));
+    g2p.fill_with_data(G1PointData(p2))?;
     let mut g3p = G1PointType::new(assigner, "F_p3_init");
-    g3p.fill_with_data(G1PointData(p3));
+    g3p.fill_with_data(G1PointData(p3))?;
     let mut g4p = G1PointType::new(assigner, "F_p4_init");
-    g4p.fill_with_data(G1PointData(p4));
+    g4p.fill_with_data(G1PointData(p4))?;
 
-    let (s1, a1) = make_p(assigner, "F_p1_im".to_owned(), g1p, g1a);
-    let (s2, a2) = make_p(assigner, "F_p2_im".to_owned(), g2p, p2);
-    let (s3, a3) = make_p(assigner, "F_p3_im".to_owned(), g3p, p3);
-    let (s4, a4) = make_p(assigner, "F_p4_im".to_owned(), g4p, p4);
+    let (s1, a1) = make_p(assigner, "F_p1_im".to_owned(), g1p, g1a)?;
+    let (s2, a2) = make_p(assigner, "F_p2_im".to_owned(), g2p, p2)?;
+    let (s3, a3) = make_p(assigner, "F_p3_im".to_owned(), g3p, p3)?;
+    let (s4, a4) = make_p(assigner, "F_p4_im".to_owned(), g4p, p4)?;
 
     segments.extend(s1);
     segments.extend(s2);
@@ -41,7 +41,7 @@ pub fn g1_points<T: BCAssigner>(
     segments.extend(s4);
 
     let im_var_p = vec![a1, a2, a3, a4];
-    (segments, im_var_p)
+    Ok((segments, im_var_p))
 }
 
 fn make_p<T: BCAssigner>(
@@ -49,7 +49,7 @@ fn make_p<T: BCAssigner>(
     prefix: String,
     g1p: G1PointType,
     g1a: ark_bn254::G1Affine,
-) -> (Vec<Segment>, Fq2Type) {
+) -> Result<(Vec<Segment>, Fq2Type), ElementError> {
     let mut segments = vec![];
 
     let p1 = g1a;
@@ -70,10 +70,11 @@ fn make_p<T: BCAssigner>(
     hints_p1.extend(hint2);
 
     let mut result_p = Fq2Type::new(assigner, &format!("{}_o_a", prefix));
-    result_p.fill_with_data(Fq2Data(ark_bn254::Fq2::new(
-        -p1.x / p1.y,
-        p1.y.inverse().unwrap(),
-    )));
+    result_p
+        .fill_with_data(Fq2Data(ark_bn254::Fq2::new(
+            -p1.x / p1.y,
+            p1.y.inverse().unwrap(),
+        )))?;
 
     segments.push(
         Segment::new_with_name(prefix, script_p1)
@@ -82,7 +83,7 @@ fn make_p<T: BCAssigner>(
             .add_hint(hints_p1),
     );
 
-    (segments, result_p)
+    Ok((segments, result_p))
 }
 
 #[cfg(test)]
@@ -134,8 +135,8 @@ mod test {
         let mut assigner = DummyAssigner::default();
         let g1a = expect;
         let mut g1p = G1PointType::new(&mut assigner, "test");
-        g1p.fill_with_data(G1PointData(g1a));
-        let (segments, _) = make_p(&mut assigner, "test".to_owned(), g1p, g1a);
+        g1p.fill_with_data(G1PointData(g1a)).unwrap();
+        let (segments, _) = make_p(&mut assigner, "test".to_owned(), g1p, g1a).unwrap();
 
         for segment in segments {
             let witness = segment.witness(&assigner);
@@ -3,7 +3,7 @@ use crate::bn254::g2::collect_line_coeffs;
 use crate::chunker::chunk_g1_points::g1_points;
 use crate::chunker::chunk_msm::chunk_hinted_msm_with_constant_bases_affine;
 use crate::chunker::chunk_non_fixed_point::chunk_q4;
-use crate::chunker::elements::{ElementTrait, DataType::Fq6Data,Fq6Type, FrType, G2PointType};
+use crate::chunker::elements::{ElementError, ElementTrait, DataType::Fq6Data,Fq6Type, FrType, G2PointType};
 use crate::chunker::{chunk_accumulator, chunk_hinted_accumulator};
 use crate::groth16::offchain_checker::compute_c_wi;
 use ark_bn254::{Bn254, G1Projective};
@@ -19,12 +19,13 @@ use super::segment::Segment;
 /// This function outputs a vector segment, which is equivalent to the plain groth16 verifier.
 /// Each segment will generate script and witness for each branch of disprove transaction.
 /// Bitcommitments are collected into assinger.
+/// Fails if a computed value does not fit the element it is filled into.
 pub fn groth16_verify_to_segments<T: BCAssigner>(
     assigner: &mut T,
     public_inputs: &[<Bn254 as ark_Pairing>::ScalarField],
     proof: &Proof<Bn254>,
     vk: &VerifyingKey<Bn254>,
-) -> Vec<Segment> {
+) -> Result<Vec<Segment>, ElementError> {
     let scalars = [
         vec![<Bn254 as ark_Pairing>::ScalarField::ONE],
         public_inputs.to_owned(),
@@ -61,7 +62,8 @@ pub fn groth16_verify_to_segments<T: BCAssigner>(
     let mut scalar_types = vec![FrType::new_dummy("scalar_0")];
     for (idx, scalar) in scalars.iter().enumerate().skip(1) {
         let mut scalar_type = FrType::new(assigner, &format!("scalar_{}", idx));
-        scalar_type.fill_with_data(crate::chunker::elements::DataType::FrData(*scalar));
+        scalar_type
+            .fill_with_data(crate::chunker::elements::DataType::FrData(*scalar))?;
         scalar_types.push(scalar_type);
     }
 
@@ -71,11 +73,11 @@ pub fn groth16_verify_to_segments<T: BCAssigner>(
         &vk.gamma_abc_g1,
         &scalars,
         &scalar_types,
-    );
+    )?;
 
     segments.extend(segment);
 
-    let (segment, tp_lst) = g1_points(assigner, p1_type, p1, proof, vk);
+    let (segment, tp_lst) = g1_points(assigner, p1_type, p1, proof, vk)?;
     segments.extend(segment);
 
     let constants = q_prepared.to_vec();
@@ -91,7 +93,7 @@ pub fn groth16_verify_to_segments<T: BCAssigner>(
             let coeff = &line_coeff[num_line_groups-1][j];
             let mut fq6 = Fq6Type::new(assigner, &format!("line_coeffs_4_{i}{j}"));
             let data = ark_bn254::Fq6::new(coeff.0,coeff.1,coeff.2);
-            fq6.fill_with_data(Fq6Data(data));
+            fq6.fill_with_data(Fq6Data(data))?;
             line_coeff_4.push(fq6);
         }
         line_coeffs_4.push(line_coeff_4);
@@ -113,12 +115,13 @@ pub fn groth16_verify_to_segments<T: BCAssigner>(
     segments.extend(segment);
 
     let mut q4_input = G2PointType::new(assigner, "q4");
-    q4_input.fill_with_data(crate::chunker::elements::DataType::G2PointData(q4));
-    let segment = chunk_q4(q_prepared.to_vec(),&line_coeffs_4, q4, q4_input, assigner);
+    q4_input
+        .fill_with_data(crate::chunker::elements::DataType::G2PointData(q4))?;
+    let segment = chunk_q4(q_prepared.to_vec(),&line_coeffs_4, q4, q4_input, assigner)?;
 
     segments.extend(segment);
 
-    segments
+    Ok(segments)
 }
 
 #[cfg(test)]
@@ -183,7 +186,7 @@ mod test {
 
         fn all_intermediate_witnesses(
             &self,
-            elements: std::collections::BTreeMap<String, std::rc::Rc<Box<dyn ElementTrait>>>,
+            elements: std::collections::BTreeMap<String, std::sync::Arc<Box<dyn ElementTrait>>>,
         ) -> Vec<Vec<RawWitness>> {
             self.dummy_assigner.all_intermediate_witnesses(elements)
         }
@@ -261,7 +264,7 @@ mod test {
 
         let mut assigner = StatisticAssinger::new();
 
-        let segments = groth16_verify_to_segments(&mut assigner, &vec![c], &proof, &vk);
+        let segments = groth16_verify_to_segments(&mut assigner, &vec![c], &proof, &vk).unwrap();
 
         let mut small_segment_size = 0;
         let mut min_segment = 4_000_000;
@@ -342,7 +345,7 @@ mod test {
         // let mut assigner = DummyAssinger {};
         let mut assigner = StatisticAssinger::new();
 
-        let segments = groth16_verify_to_segments(&mut assigner, &vec![c], &proof, &vk);
+        let segments = groth16_verify_to_segments(&mut assigner, &vec![c], &proof, &vk).unwrap();
 
         let mut small_segment_size = 0;
 
@@ -443,7 +446,7 @@ mod test {
         let proof = Groth16::<E>::prove(pk, circuit, rng).unwrap();
 
         let mut assigner = DummyAssigner::default();
-        let segments = groth16_verify_to_segments(&mut assigner, &vec![c], &proof, &vk);
+        let segments = groth16_verify_to_segments(&mut assigner, &vec![c], &proof, &vk).unwrap();
 
         println!("segments number: {}", segments.len());
 
@@ -113,7 +113,7 @@ mod test {
 
         let g1a = msm_g1.into_affine();
         let mut g1p = G1PointType::new(assigner, "test");
-        g1p.fill_with_data(G1PointData(g1a));
+        g1p.fill_with_data(G1PointData(g1a)).unwrap();
 
         (g1a, g1p)
     }
@@ -216,7 +216,7 @@ mod test {
 
         // let (hinted_groth16_verifier, hints) = Verifier::hinted_verify(&vec![c], &proof, &vk);
         let (g1a, g1p) = generate_p1(&mut assigner, &vec![c], &vk);
-        let (segments, _) = g1_points(&mut assigner, g1p, g1a, &proof, &vk);
+        let (segments, _) = g1_points(&mut assigner, g1p, g1a, &proof, &vk).unwrap();
 
         println!("segments len {}", segments.len());
         for segment in segments {
@@ -265,14 +265,14 @@ mod test {
         let mut prng: ChaCha20Rng = ChaCha20Rng::seed_from_u64(0);
         let rc = ark_bn254::Fq12::rand(&mut prng);
         let mut tc = Fq12Type::new(&mut assigner, &format!("{}{}", "test".to_owned(), "c"));
-        tc.fill_with_data(Fq12Data(rc));
+        tc.fill_with_data(Fq12Data(rc)).unwrap();
         let tf = generate_f(&vec![c], &proof, &vk);
         let mut tc = Fq12Type::new(&mut assigner, &format!("{}{}", "test".to_owned(), "c1"));
-        tc.fill_with_data(Fq12Data(tf));
+        tc.fill_with_data(Fq12Data(tf)).unwrap();
 
         // let (hinted_groth16_verifier, hints) = Verifier::hinted_verify(&vec![c], &proof, &vk);
         let (g1a, g1p) = generate_p1(&mut assigner, &vec![c], &vk);
-        let (_, tp_lst) = g1_points(&mut assigner, g1p, g1a, &proof, &vk);
+        let (_, tp_lst) = g1_points(&mut assigner, g1p, g1a, &proof, &vk).unwrap();
 
         let (constants, c, c_inv, wi, p_lst, _) = generate_f_arg(&vec![c], &proof, &vk);
         
@@ -290,14 +290,14 @@ mod test {
                 let coeff = &line_coeff[num_line_groups-1][j];
                 let mut fq6 = Fq6Type::new(&mut assigner, &format!("line_coeffs_4_{i}{j}"));
                 let data = ark_bn254::Fq6::new(coeff.0,coeff.1,coeff.2);
-                fq6.fill_with_data(Fq6Data(data));
+                fq6.fill_with_data(Fq6Data(data)).unwrap();
                 line_coeff_4.push(fq6);
             }
             line_coeffs_4.push(line_coeff_4);
         }
         
         let (segments, fs, f) =
-            chunk_accumulator(&mut assigner, tp_lst, constants, &line_coeffs_4,c, c_inv, wi, p_lst);
+            chunk_accumulator(&mut assigner, tp_lst, constants, &line_coeffs_4,c, c_inv, wi, p_lst).unwrap();
         println!("tf: {} \n f: {}", tf, f);
         println!("tc: {:?} \n fs: {:?}", tc, fs);
 
@@ -348,10 +348,10 @@ mod test {
         let mut prng: ChaCha20Rng = ChaCha20Rng::seed_from_u64(0);
         let rc = ark_bn254::Fq12::rand(&mut prng);
         let mut tc = Fq12Type::new(&mut assigner, &format!("{}{}", "test".to_owned(), "c"));
-        tc.fill_with_data(Fq12Data(rc));
+        tc.fill_with_data(Fq12Data(rc)).unwrap();
         let f = generate_f(&vec![c], &proof, &vk);
         let mut tc1 = Fq12Type::new(&mut assigner, &format!("{}{}", "test".to_owned(), "c1"));
-        tc1.fill_with_data(Fq12Data(f));
+        tc1.fill_with_data(Fq12Data(f)).unwrap();
 
         let segments = verify_accumulator(tc1);
         println!("segments len {}", segments.len());
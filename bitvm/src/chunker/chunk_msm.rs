@@ -6,7 +6,7 @@ use ark_ff::Field;
 use bitcoin_script::script;
 
 use super::assigner::BCAssigner;
-use super::elements::FrType;
+use super::elements::{ElementError, FrType};
 use super::segment::Segment;
 use crate::{
     bn254::g1::G1Affine,
@@ -20,7 +20,7 @@ pub fn chunk_hinted_msm_with_constant_bases_affine<T: BCAssigner>(
     bases: &[ark_bn254::G1Affine],
     scalars: &[ark_bn254::Fr],
     scalar_types: &[FrType],
-) -> (Vec<Segment>, G1PointType) {
+) -> Result<(Vec<Segment>, G1PointType), ElementError> {
     println!("use hinted_msm_with_constant_bases_affine");
     assert_eq!(bases.len(), scalars.len());
     assert_eq!(scalar_types.len(), scalars.len());
@@ -33,7 +33,7 @@ pub fn chunk_hinted_msm_with_constant_bases_affine<T: BCAssigner>(
     let (inner_coeffs, outer_coeffs) = prepare_msm_input(bases, scalars, i_step);
 
     let mut type_acc = G1PointType::new(assigner, "msm_init");
-    type_acc.fill_with_data(G1PointData(bases[0]));
+    type_acc.fill_with_data(G1PointData(bases[0]))?;
 
     // 1. init the sum=0;
     let mut p = (bases[0] * scalars[0]).into_affine();
@@ -62,7 +62,7 @@ pub fn chunk_hinted_msm_with_constant_bases_affine<T: BCAssigner>(
             inner_coeffs[i].0.clone(),
             inner_coeffs[i].1.clone(),
             inner_coeffs[i].2.clone(),
-        );
+        )?;
         segments.extend(segment);
 
         // check coeffs before using
@@ -72,7 +72,7 @@ pub fn chunk_hinted_msm_with_constant_bases_affine<T: BCAssigner>(
         p = (p + c).into_affine();
 
         let mut update = G1PointType::new(assigner, &format!("msm_update_{}", i));
-        update.fill_with_data(G1PointData(p));
+        update.fill_with_data(G1PointData(p))?;
         let segment = Segment::new_with_name(
             format!("msm_add_{}", i),
             script! {
@@ -88,7 +88,7 @@ pub fn chunk_hinted_msm_with_constant_bases_affine<T: BCAssigner>(
 
         type_acc = update;
     }
-    (segments, type_acc)
+    Ok((segments, type_acc))
 }
 
 #[cfg(test)]
@@ -127,7 +127,9 @@ mod tests {
         let mut scalar_types = vec![];
         for (idx, scalar) in scalars.iter().enumerate() {
             let mut scalar_type = FrType::new(&mut assigner, &format!("scalar_{}", idx));
-            scalar_type.fill_with_data(crate::chunker::elements::DataType::FrData(*scalar));
+            scalar_type
+                .fill_with_data(crate::chunker::elements::DataType::FrData(*scalar))
+                .unwrap();
             scalar_types.push(scalar_type);
         }
 
@@ -136,7 +138,7 @@ mod tests {
             &bases,
             &scalars,
             &scalar_types,
-        );
+        ).unwrap();
 
         println!("segments number {}", segments.len());
 
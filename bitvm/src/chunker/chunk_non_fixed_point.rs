@@ -13,7 +13,7 @@ use num_traits::One;
 use std::{ops::Neg, str::FromStr};
 
 use super::assigner::BCAssigner;
-use super::elements::{Fq6Type, G2PointType};
+use super::elements::{ElementError, Fq6Type, G2PointType};
 use super::segment::Segment;
 
 pub fn chunk_q4<T: BCAssigner>(
@@ -22,7 +22,7 @@ pub fn chunk_q4<T: BCAssigner>(
     q4: ark_bn254::G2Affine,
     q4_input: G2PointType,
     assigner: &mut T,
-) -> Vec<Segment> {
+) -> Result<Vec<Segment>, ElementError> {
     assert_eq!(constants.len(), 4);
     let num_line_groups = constants.len();
     let num_constant = 3;
@@ -35,7 +35,8 @@ pub fn chunk_q4<T: BCAssigner>(
     let mut t4 = q4;
 
     let mut t4_acc = G2PointType::new(assigner, "t4_init");
-    t4_acc.fill_with_data(crate::chunker::elements::DataType::G2PointData(t4));
+    t4_acc
+        .fill_with_data(crate::chunker::elements::DataType::G2PointData(t4))?;
     let segment = Segment::new_with_name("copy_q4_to_t4".into(), script! {})
         .add_parameter(&q4_input)
         .add_result(&t4_acc);
@@ -72,7 +73,8 @@ pub fn chunk_q4<T: BCAssigner>(
                 hints.extend(hint);
 
                 let mut t4_update = G2PointType::new(assigner, &format!("T4_{}_double", i));
-                t4_update.fill_with_data(crate::chunker::elements::DataType::G2PointData(t4x));
+                t4_update
+                    .fill_with_data(crate::chunker::elements::DataType::G2PointData(t4x))?;
                 let segment = Segment::new_with_name(
                     format!("check and double_{}", i),
                     script! {
@@ -172,7 +174,8 @@ pub fn chunk_q4<T: BCAssigner>(
                     );
 
                     let mut t4_update = G2PointType::new(assigner, &format!("T4_{}_add", i));
-                    t4_update.fill_with_data(crate::chunker::elements::DataType::G2PointData(t4x));
+                    t4_update
+                        .fill_with_data(crate::chunker::elements::DataType::G2PointData(t4x))?;
                     let segment = Segment::new_with_name(format!("check and add{}", i), script)
                         .add_parameter(&line_coeffs_4[num_lines - (i + 2)][1])
                         .add_parameter(&t4_acc)
@@ -251,7 +254,8 @@ pub fn chunk_q4<T: BCAssigner>(
             };
 
             let mut t4_update = G2PointType::new(assigner, "T4_final_add");
-            t4_update.fill_with_data(crate::chunker::elements::DataType::G2PointData(t4x));
+            t4_update
+                .fill_with_data(crate::chunker::elements::DataType::G2PointData(t4x))?;
             let segment = Segment::new_with_name("final check and add".into(), script)
                 .add_parameter(&line_coeffs_4[num_lines - 2][0])
                 .add_parameter(&t4_acc)
@@ -305,7 +309,7 @@ pub fn chunk_q4<T: BCAssigner>(
         }
     }
 
-    segments
+    Ok(segments)
 }
 
 #[cfg(test)]
@@ -337,7 +341,9 @@ mod tests {
         let q4_prepared = G2Prepared::from_affine(q4);
 
         let mut q4_input = G2PointType::new(&mut assigner, "q4");
-        q4_input.fill_with_data(crate::chunker::elements::DataType::G2PointData(q4));
+        q4_input
+            .fill_with_data(crate::chunker::elements::DataType::G2PointData(q4))
+            .unwrap();
 
         let constants = [q1_prepared.clone(), q2_prepared.clone(), q3_prepared.clone(), q4_prepared.clone()].to_vec();
         assert_eq!(constants.len(), 4);
@@ -352,7 +358,7 @@ mod tests {
                 let coeff = &line_coeff[num_line_groups-1][j];
                 let mut fq6 = Fq6Type::new(&mut assigner, &format!("line_coeffs_4_{i}{j}"));
                 let data = ark_bn254::Fq6::new(coeff.0,coeff.1,coeff.2);
-                fq6.fill_with_data(Fq6Data(data));
+                fq6.fill_with_data(Fq6Data(data)).unwrap();
                 line_coeff_4.push(fq6);
             }
             line_coeffs_4.push(line_coeff_4);
@@ -364,7 +370,7 @@ mod tests {
             q4,
             q4_input,
             &mut assigner,
-        );
+        ).unwrap();
 
         println!("segments number :{}", segments.len());
 
@@ -5,7 +5,7 @@ use ark_ff::{BigInteger, PrimeField};
 use bitcoin_script::script;
 
 use super::assigner::BCAssigner;
-use super::elements::FrType;
+use super::elements::{ElementError, FrType};
 use super::segment::Segment;
 use crate::{
     bn254::{g1::G1Affine, fp254impl::Fp254Impl, fr::Fr},
@@ -24,7 +24,7 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
     coeff: Vec<(ark_bn254::Fq, ark_bn254::Fq)>,
     step_p: Vec<ark_bn254::G1Affine>,
     trace: Vec<ark_bn254::G1Affine>,
-) -> (Vec<Segment>, G1PointType) {
+) -> Result<(Vec<Segment>, G1PointType), ElementError> {
     let mut segments = vec![];
     let mut type_acc = G1PointType::new(assigner, &format!("{}_{}", prefix, 0));
 
@@ -72,7 +72,8 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
                 loop_scripts.clear();
 
                 let mut update = G1PointType::new(assigner, &format!("{}_{}_piece_1", prefix, i));
-                update.fill_with_data(crate::chunker::elements::DataType::G1PointData(c));
+                update
+                    .fill_with_data(crate::chunker::elements::DataType::G1PointData(c))?;
                 let segment = Segment::new_with_name(
                     format!("{}_loop_{}_piece_1", prefix, i),
                     segment_script,
@@ -105,7 +106,8 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
                 loop_scripts.clear();
 
                 let mut update = G1PointType::new(assigner, &format!("{}_{}_piece_2", prefix, i));
-                update.fill_with_data(crate::chunker::elements::DataType::G1PointData(c));
+                update
+                    .fill_with_data(crate::chunker::elements::DataType::G1PointData(c))?;
                 let segment = Segment::new_with_name(
                     format!("{}_loop_{}_piece_2", prefix, i),
                     segment_script,
@@ -138,7 +140,8 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
                 loop_scripts.clear();
 
                 let mut update = G1PointType::new(assigner, &format!("{}_{}_piece_3", prefix, i));
-                update.fill_with_data(crate::chunker::elements::DataType::G1PointData(c));
+                update
+                    .fill_with_data(crate::chunker::elements::DataType::G1PointData(c))?;
                 let segment = Segment::new_with_name(
                     format!("{}_loop_{}_piece_3", prefix, i),
                     segment_script,
@@ -171,7 +174,8 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
                 loop_scripts.clear();
 
                 let mut update = G1PointType::new(assigner, &format!("{}_{}_piece_1", prefix, i));
-                update.fill_with_data(crate::chunker::elements::DataType::G1PointData(c));
+                update
+                    .fill_with_data(crate::chunker::elements::DataType::G1PointData(c))?;
                 let segment = Segment::new_with_name(
                     format!("{}_loop_{}_piece_1", prefix, i),
                     segment_script,
@@ -204,7 +208,8 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
                 loop_scripts.clear();
 
                 let mut update = G1PointType::new(assigner, &format!("{}_{}_piece_2", prefix, i));
-                update.fill_with_data(crate::chunker::elements::DataType::G1PointData(c));
+                update
+                    .fill_with_data(crate::chunker::elements::DataType::G1PointData(c))?;
                 let segment = Segment::new_with_name(
                     format!("{}_loop_{}_piece_2", prefix, i),
                     segment_script,
@@ -237,7 +242,8 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
                 loop_scripts.clear();
 
                 let mut update = G1PointType::new(assigner, &format!("{}_{}_piece_3", prefix, i));
-                update.fill_with_data(crate::chunker::elements::DataType::G1PointData(c));
+                update
+                    .fill_with_data(crate::chunker::elements::DataType::G1PointData(c))?;
                 let segment = Segment::new_with_name(
                     format!("{}_loop_{}_piece_3", prefix, i),
                     segment_script,
@@ -308,7 +314,8 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
         loop_scripts.clear();
 
         if i == 0 {
-            type_acc.fill_with_data(crate::chunker::elements::DataType::G1PointData(c));
+            type_acc
+                .fill_with_data(crate::chunker::elements::DataType::G1PointData(c))?;
             let segment = Segment::new_with_name(format!("{}_loop_{}", prefix, i), segment_script)
                 .add_parameter(&scalar_type)
                 .add_result(&type_acc)
@@ -317,7 +324,8 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
             segments.push(segment);
         } else {
             let mut update = G1PointType::new(assigner, &format!("{}_{}", prefix, i));
-            update.fill_with_data(crate::chunker::elements::DataType::G1PointData(c));
+            update
+                .fill_with_data(crate::chunker::elements::DataType::G1PointData(c))?;
             let segment = Segment::new_with_name(format!("{}_loop_{}", prefix, i), segment_script)
                 .add_parameter(&type_acc)
                 .add_parameter(&scalar_type)
@@ -338,7 +346,7 @@ pub fn chunk_hinted_scalar_mul_by_constant<T: BCAssigner>(
     println!("debug: c:{:?}", c);
     *p = c;
 
-    (segments, type_acc)
+    Ok((segments, type_acc))
 }
 
 #[cfg(test)]
@@ -377,7 +385,9 @@ mod tests {
         println!("debug: expected res:{:?}", q);
         let (inner_coeffs, _) = prepare_msm_input(&bases, &scalars, 12);
         let mut scalar_type = FrType::new(&mut assigner, "init_run1");
-        scalar_type.fill_with_data(crate::chunker::elements::DataType::FrData(scalars[0]));
+        scalar_type
+            .fill_with_data(crate::chunker::elements::DataType::FrData(scalars[0]))
+            .unwrap();
 
         let (segments1, _) = chunk_hinted_scalar_mul_by_constant(
             &mut assigner,
@@ -388,7 +398,7 @@ mod tests {
             inner_coeffs[0].0.clone(),
             inner_coeffs[0].1.clone(),
             inner_coeffs[0].2.clone(),
-        );
+        ).unwrap();
 
         // second run
         let mut bases2 = bases.clone();
@@ -397,7 +407,9 @@ mod tests {
         println!("debug: expected res:{:?}", q);
         let (inner_coeffs, _) = prepare_msm_input(&bases, &scalars, 12);
         let mut scalar_type = FrType::new(&mut assigner, "init_run2");
-        scalar_type.fill_with_data(crate::chunker::elements::DataType::FrData(scalars[0]));
+        scalar_type
+            .fill_with_data(crate::chunker::elements::DataType::FrData(scalars[0]))
+            .unwrap();
 
         let (segments2, _) = chunk_hinted_scalar_mul_by_constant(
             &mut assigner,
@@ -408,7 +420,7 @@ mod tests {
             inner_coeffs[0].0.clone(),
             inner_coeffs[0].1.clone(),
             inner_coeffs[0].2.clone(),
-        );
+        ).unwrap();
 
         assert_eq!(segments1.len(), segments2.len());
         for (seg1, seg2) in segments1.into_iter().zip(segments2) {
@@ -468,7 +480,9 @@ mod tests {
         let (inner_coeffs, _) = prepare_msm_input(&bases, &scalars, 12);
 
         let mut scalar_type = FrType::new(&mut assigner, "init");
-        scalar_type.fill_with_data(crate::chunker::elements::DataType::FrData(scalars[0]));
+        scalar_type
+            .fill_with_data(crate::chunker::elements::DataType::FrData(scalars[0]))
+            .unwrap();
 
         let (segments, _) = chunk_hinted_scalar_mul_by_constant(
             &mut assigner,
@@ -479,7 +493,7 @@ mod tests {
             inner_coeffs[0].0.clone(),
             inner_coeffs[0].1.clone(),
             inner_coeffs[0].2.clone(),
-        );
+        ).unwrap();
 
         println!("segments count: {}", segments.len());
 
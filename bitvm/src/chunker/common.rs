@@ -5,7 +5,9 @@ use crate::{
         fq::Fq,
         fr::Fr,
     },
+    hash::sha256::sha256,
     treepp::*,
+    u32::u32_std::u32_uncompress,
     ExecuteInfo,
 };
 use ark_ec::bn::Bn;
@@ -13,12 +15,13 @@ use ark_groth16::{Proof, VerifyingKey};
 use bitcoin::script::{read_scriptint, write_scriptint};
 use num_bigint::BigUint;
 use regex::Regex;
+use std::fmt::Debug;
 
 /// Define Witness
 pub type RawWitness = Vec<Vec<u8>>;
 
 /// Should use u32 version's blake3 hash for fq element
-pub use crate::hash::blake3_u32::blake3_var_length;
+pub use crate::hash::blake3_u32::{blake3_var_length, blake3_var_length_with_padding, Blake3Padding};
 
 use super::disprove_execution::RawProof;
 
@@ -27,6 +30,67 @@ pub const BLAKE3_HASH_LENGTH: usize =
     crate::hash::blake3_u32::N_DIGEST_U32_LIMBS as usize * 4;
 pub type BLAKE3HASH = [u8; BLAKE3_HASH_LENGTH];
 
+/// The depth of a sha256 hash
+pub const SHA256_HASH_LENGTH: usize = 32;
+
+/// Hash committed for the witness of an element, computed by a script over the
+/// u32 limbs of the witness.
+pub trait CommitmentHash: Debug + Clone + Default + Send + Sync + 'static {
+    /// Length of the hash in bytes, each byte being one element of its witness
+    fn hash_length() -> usize;
+    /// Script hashing `num_u32` limbs on top of the stack into `hash_length` bytes
+    fn hash_script(&self, num_u32: usize) -> Script;
+}
+
+/// Blake3 truncated to `BLAKE3_HASH_LENGTH` bytes, the hash verified by the segments.
+/// The padding only changes the script size, the default `Blake3Padding::Roll` is
+/// the layout of the scripts already committed to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Blake3Hasher {
+    pub padding: Blake3Padding,
+}
+
+impl Blake3Hasher {
+    /// Pads with the smaller layout for `num_u32` limbs, see
+    /// `Blake3Padding::smallest_for_length`
+    pub fn for_length(num_u32: usize) -> Self {
+        Self {
+            padding: Blake3Padding::smallest_for_length(num_u32),
+        }
+    }
+}
+
+impl CommitmentHash for Blake3Hasher {
+    fn hash_length() -> usize {
+        BLAKE3_HASH_LENGTH
+    }
+
+    fn hash_script(&self, num_u32: usize) -> Script {
+        blake3_var_length_with_padding(num_u32, self.padding)
+    }
+}
+
+/// SHA256 of the limbs as little endian u32, in witness order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sha256Hasher;
+
+impl CommitmentHash for Sha256Hasher {
+    fn hash_length() -> usize {
+        SHA256_HASH_LENGTH
+    }
+
+    fn hash_script(&self, num_u32: usize) -> Script {
+        script! {
+            // the top limb is split first, which leaves the bytes of the first limb on top
+            for i in 0..num_u32 {
+                { 4 * i } OP_ROLL
+                { u32_uncompress() }
+            }
+            { sha256(num_u32 * 4) }
+        }
+    }
+}
+
 /// Commit the original proof, listing all the variable name of original proof.
 /// [proof.a, proof.b, proof.c, public_input0, public_input1, public_input2, public_input3]
 pub const PROOF_NAMES: [&str; 10] = [
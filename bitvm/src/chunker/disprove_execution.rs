@@ -1,6 +1,8 @@
 use super::{
-    assigner::BCAssigner, chunk_groth16_verifier::groth16_verify_to_segments, common::RawWitness,
-    elements::dummy_element,
+    assigner::{BCAssigner, UncheckedPointsAssigner},
+    chunk_groth16_verifier::groth16_verify_to_segments,
+    common::{RawWitness, BLAKE3HASH},
+    elements::{dummy_element, ElementError, ElementTrait},
 };
 use crate::chunker::common;
 use crate::groth16::{constants::LAMBDA, offchain_checker::compute_c_wi};
@@ -11,8 +13,10 @@ use ark_ec::{AffineRepr as _, CurveGroup as _, VariableBaseMSM as _};
 use ark_ff::Field as _;
 use ark_groth16::{Proof, VerifyingKey};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
 use std::ops::Neg;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone, Debug, PartialEq, CanonicalDeserialize, CanonicalSerialize)]
 pub struct RawProof {
@@ -32,6 +36,16 @@ impl Default for RawProof {
 
 impl RawProof {
     pub fn valid_proof(&self) -> bool {
+        // the pairing of points outside of the groups says nothing about the proof
+        let (a, b, c) = (self.proof.a, self.proof.b, self.proof.c);
+        if ![a, c]
+            .iter()
+            .all(|p| p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve())
+            || !(b.is_on_curve() && b.is_in_correct_subgroup_assuming_on_curve())
+        {
+            return false;
+        }
+
         let scalars = [
             vec![<Bn254 as ark_Pairing>::ScalarField::ONE],
             self.public.clone(),
@@ -64,69 +78,114 @@ impl RawProof {
     }
 }
 
+/// Find a segment of the verifier that can be disproved for the asserted
+/// intermediate values, and the witness to disprove it with. Segments are
+/// checked in parallel, the segment with the lowest index is returned.
+/// Fails if the segments cannot be rebuilt from the asserted proof.
 pub fn disprove_exec<A: BCAssigner>(
     assigner: &mut A,
     assert_witness: Vec<Vec<RawWitness>>,
     vk: VerifyingKey<ark_bn254::Bn254>,
-) -> Option<(usize, RawWitness)> {
+) -> Result<Option<(usize, RawWitness)>, ElementError> {
+    disprove_exec_with(assigner, assert_witness, vk, true)
+}
+
+/// Same as `disprove_exec`, checking the segments on the current thread only.
+pub fn disprove_exec_sequential<A: BCAssigner>(
+    assigner: &mut A,
+    assert_witness: Vec<Vec<RawWitness>>,
+    vk: VerifyingKey<ark_bn254::Bn254>,
+) -> Result<Option<(usize, RawWitness)>, ElementError> {
+    disprove_exec_with(assigner, assert_witness, vk, false)
+}
+
+fn disprove_exec_with<A: BCAssigner>(
+    assigner: &mut A,
+    assert_witness: Vec<Vec<RawWitness>>,
+    vk: VerifyingKey<ark_bn254::Bn254>,
+    parallel: bool,
+) -> Result<Option<(usize, RawWitness)>, ElementError> {
     // 0. recover assigner from witness
     let (hash_map, wrong_proof) = assigner.recover_from_witness(assert_witness, vk);
 
     // 1. if 'wrong_proof' is correct, return none
     if wrong_proof.valid_proof() {
-        return None;
+        return Ok(None);
     }
 
-    // 2. derive assigner from wrong proof
-    let mut wrong_proof_assigner = A::default();
+    // 2. derive assigner from wrong proof, its points may be invalid and are not checked
+    let mut wrong_proof_assigner = UncheckedPointsAssigner::<A>::default();
     let mut segments = groth16_verify_to_segments(
         &mut wrong_proof_assigner,
         &wrong_proof.public,
         &wrong_proof.proof,
         &wrong_proof.vk,
-    );
-    let _segment_length = segments.len();
+    )?;
 
     // 3. find which chunk is unconsistent
-    for (idx, segment) in segments.iter_mut().enumerate() {
-        let mut is_param_equal = true;
-        for param in segment.parameter_list.iter() {
-            // skip when the param is in proof
-            if common::PROOF_NAMES.contains(&param.id()) {
-                continue;
-            }
-            if param.to_hash().unwrap() != *hash_map.get(param.id()).unwrap() {
-                is_param_equal = false;
-            }
-        }
-        let mut is_result_equal = true;
+    // only the elements are shared between threads, the rest of a segment stays here
+    let element_lists: Vec<_> = segments
+        .iter()
+        .map(|segment| (&segment.parameter_list, &segment.result_list))
+        .collect();
+    let inconsistent_idx = if parallel {
+        element_lists
+            .par_iter()
+            .position_first(|(parameter_list, result_list)| {
+                is_inconsistent(parameter_list, result_list, &hash_map)
+            })
+    } else {
+        element_lists
+            .iter()
+            .position(|(parameter_list, result_list)| {
+                is_inconsistent(parameter_list, result_list, &hash_map)
+            })
+    };
+
+    if let Some(idx) = inconsistent_idx {
+        let segment = &mut segments[idx];
         for result in segment.result_list.iter_mut() {
-            if result.to_hash().unwrap() != *hash_map.get(result.id()).unwrap() {
-                is_result_equal = false;
+            if result.to_hash().unwrap().unwrap() != *hash_map.get(result.id()).unwrap() {
                 // replace the result to hash_map
-                *result = Rc::new(Box::new(dummy_element(
+                *result = Arc::new(Box::new(dummy_element(
                     result.id(),
                     *hash_map.get(result.id()).unwrap(),
                 )));
             }
         }
-
-        if is_param_equal && !is_result_equal {
-            let disprove_witness = segment.witness(assigner);
-            return Some((idx, disprove_witness));
-        }
+        let disprove_witness = segment.witness(assigner);
+        return Ok(Some((idx, disprove_witness)));
     }
 
     // if all intermediate values is identical, then return the final chunk
     for (idx, segment) in segments.iter().enumerate() {
         if segment.is_final() {
             let disprove_witness = segment.witness(assigner);
-            return Some((idx, disprove_witness));
+            return Ok(Some((idx, disprove_witness)));
         }
     }
 
     println!("Shouldn't happend, some chunk must can be available with a wrong proof");
-    None
+    Ok(None)
+}
+
+/// A segment is inconsistent if its parameters are the asserted values but its
+/// results are not.
+fn is_inconsistent(
+    parameter_list: &[Arc<Box<dyn ElementTrait>>],
+    result_list: &[Arc<Box<dyn ElementTrait>>],
+    hash_map: &BTreeMap<String, BLAKE3HASH>,
+) -> bool {
+    let is_param_equal = parameter_list
+        .iter()
+        // skip when the param is in proof
+        .filter(|param| !common::PROOF_NAMES.contains(&param.id()))
+        .all(|param| param.to_hash().unwrap().unwrap() == *hash_map.get(param.id()).unwrap());
+
+    is_param_equal
+        && !result_list
+            .iter()
+            .all(|result| result.to_hash().unwrap().unwrap() == *hash_map.get(result.id()).unwrap())
 }
 
 #[cfg(test)]
@@ -134,12 +193,13 @@ mod tests {
     use crate::chunker::assigner::*;
     use crate::chunker::chunk_groth16_verifier::groth16_verify_to_segments;
     use crate::chunker::disprove_execution::RawProof;
+    use crate::chunker::elements::ElementError;
     use crate::chunker::elements::ElementTrait;
     use crate::chunker::elements::Fq12Type;
     use crate::execute_script_with_inputs;
 
     use ark_bn254::g1::G1Affine;
-    use ark_bn254::{Bn254, Fq12};
+    use ark_bn254::{Bn254, Fq, Fq12};
     use ark_crypto_primitives::snark::{CircuitSpecificSetupSNARK, SNARK};
     use ark_ec::pairing::Pairing;
     use ark_ff::{Field, PrimeField};
@@ -150,9 +210,9 @@ mod tests {
     use ark_std::{test_rng, UniformRand};
     use rand::{RngCore, SeedableRng};
     use std::collections::BTreeMap;
-    use std::rc::Rc;
+    use std::sync::Arc;
 
-    use super::disprove_exec;
+    use super::{disprove_exec, disprove_exec_sequential};
 
     #[derive(Copy)]
     struct DummyCircuit<F: PrimeField> {
@@ -256,12 +316,13 @@ mod tests {
             &wrong_proof.public,
             &wrong_proof.proof,
             &wrong_proof.vk,
-        );
+        )
+        .unwrap();
 
         println!("segments length: {}", segments.len());
 
         // get all elements
-        let mut elements: BTreeMap<String, std::rc::Rc<Box<dyn ElementTrait>>> = BTreeMap::new();
+        let mut elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>> = BTreeMap::new();
         for segment in segments.iter() {
             for parameter in segment.parameter_list.iter() {
                 elements.insert(parameter.id().to_owned(), parameter.clone());
@@ -275,8 +336,9 @@ mod tests {
         let assert_witnesses = assigner.all_intermediate_witnesses(elements);
 
         // must find some avalible chunk
-        let (id, witness) =
-            disprove_exec(&mut assigner, assert_witnesses, wrong_proof.vk.clone()).unwrap();
+        let (id, witness) = disprove_exec(&mut assigner, assert_witnesses, wrong_proof.vk.clone())
+            .unwrap()
+            .unwrap();
 
         // println!("segment: {:?}", segments[id].parameter_list);
         let script = segments[id].script(&assigner);
@@ -302,12 +364,13 @@ mod tests {
             &wrong_proof.public,
             &wrong_proof.proof,
             &wrong_proof.vk,
-        );
+        )
+        .unwrap();
 
         println!("segments length: {}", segments.len());
 
         // get all elements
-        let mut elements: BTreeMap<String, std::rc::Rc<Box<dyn ElementTrait>>> = BTreeMap::new();
+        let mut elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>> = BTreeMap::new();
         for segment in segments.iter() {
             for parameter in segment.parameter_list.iter() {
                 elements.insert(parameter.id().to_owned(), parameter.clone());
@@ -322,14 +385,18 @@ mod tests {
         assert!(elements.contains_key(modify_id));
         let mut mock_assigner = DummyAssigner::default();
         let mut new_element = Fq12Type::new(&mut mock_assigner, modify_id);
-        new_element.fill_with_data(crate::chunker::elements::DataType::Fq12Data(Fq12::ONE));
-        elements.insert(modify_id.to_string(), Rc::new(Box::new(new_element)));
+        new_element
+            .fill_with_data(crate::chunker::elements::DataType::Fq12Data(Fq12::ONE))
+            .unwrap();
+        elements.insert(modify_id.to_string(), Arc::new(Box::new(new_element)));
 
         // get all witnesses
         let assert_witnesses = assigner.all_intermediate_witnesses(elements);
 
         // must find some avalible chunk
-        let (id, witness) = disprove_exec(&mut assigner, assert_witnesses, wrong_proof.vk).unwrap();
+        let (id, witness) = disprove_exec(&mut assigner, assert_witnesses, wrong_proof.vk)
+            .unwrap()
+            .unwrap();
 
         // println!("segment: {:?}", segments[id].parameter_list);
         let script = segments[id].script(&assigner);
@@ -337,6 +404,121 @@ mod tests {
         assert!(res.success);
     }
 
+    /// parallel and sequential search must pick the same segment and witness
+    #[test]
+    fn test_disprove_exec_parallel_matches_sequential() {
+        let mut right_proof = gen_right_proof();
+
+        // make it wrong
+        let mut rng = ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+        right_proof.proof.a = G1Affine::rand(&mut rng);
+        let wrong_proof = right_proof;
+
+        // assert witness
+        let mut assigner = DummyAssigner::default();
+        let segments = groth16_verify_to_segments(
+            &mut assigner,
+            &wrong_proof.public,
+            &wrong_proof.proof,
+            &wrong_proof.vk,
+        )
+        .unwrap();
+
+        // get all elements
+        let mut elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>> = BTreeMap::new();
+        for segment in segments.iter() {
+            for parameter in segment.parameter_list.iter() {
+                elements.insert(parameter.id().to_owned(), parameter.clone());
+            }
+            for result in segment.result_list.iter() {
+                elements.insert(result.id().to_owned(), result.clone());
+            }
+        }
+
+        // more than one segment is inconsistent, the first one must be picked
+        let modify_id = "F_final_2p3c";
+        let mut mock_assigner = DummyAssigner::default();
+        let mut new_element = Fq12Type::new(&mut mock_assigner, modify_id);
+        new_element
+            .fill_with_data(crate::chunker::elements::DataType::Fq12Data(Fq12::ONE))
+            .unwrap();
+        elements.insert(modify_id.to_string(), Arc::new(Box::new(new_element)));
+
+        // get all witnesses
+        let assert_witnesses = assigner.all_intermediate_witnesses(elements);
+
+        let parallel = disprove_exec(
+            &mut assigner,
+            assert_witnesses.clone(),
+            wrong_proof.vk.clone(),
+        )
+        .unwrap();
+        let sequential =
+            disprove_exec_sequential(&mut assigner, assert_witnesses, wrong_proof.vk).unwrap();
+        assert!(parallel.is_some());
+        assert_eq!(parallel, sequential);
+    }
+
+    /// a malicious operator commits a proof point that is not on the curve,
+    /// the segments are rebuilt for it all the same and one of them disproves it
+    #[test]
+    fn test_off_curve_proof_point() {
+        let mut wrong_proof = gen_right_proof();
+
+        // y^2 = x^3 + 3 does not hold for (1, 1)
+        wrong_proof.proof.a = G1Affine::new_unchecked(Fq::from(1u64), Fq::from(1u64));
+        assert!(!wrong_proof.proof.a.is_on_curve());
+        assert!(!wrong_proof.valid_proof());
+
+        // an honest assignment rejects the point
+        assert_eq!(
+            groth16_verify_to_segments(
+                &mut DummyAssigner::default(),
+                &wrong_proof.public,
+                &wrong_proof.proof,
+                &wrong_proof.vk,
+            )
+            .err(),
+            Some(ElementError::InvalidPoint {
+                id: "F_p4_init".to_owned()
+            })
+        );
+
+        // assert witness, committed without checking the point
+        let mut assigner = UncheckedPointsAssigner::<DummyAssigner>::default();
+        let segments = groth16_verify_to_segments(
+            &mut assigner,
+            &wrong_proof.public,
+            &wrong_proof.proof,
+            &wrong_proof.vk,
+        )
+        .unwrap();
+        let mut assigner = assigner.0;
+
+        // get all elements
+        let mut elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>> = BTreeMap::new();
+        for segment in segments.iter() {
+            for parameter in segment.parameter_list.iter() {
+                elements.insert(parameter.id().to_owned(), parameter.clone());
+            }
+            for result in segment.result_list.iter() {
+                elements.insert(result.id().to_owned(), result.clone());
+            }
+        }
+
+        // get all witnesses
+        let assert_witnesses = assigner.all_intermediate_witnesses(elements);
+
+        // must find some avalible chunk
+        let (id, witness) = disprove_exec(&mut assigner, assert_witnesses, wrong_proof.vk)
+            .unwrap()
+            .unwrap();
+
+        let script = segments[id].script(&assigner);
+        let res = execute_script_with_inputs(script, witness);
+        assert!(res.success, "{:?}, {:?}", res.error, res.final_stack);
+    }
+
     #[test]
     fn offchain_check_wrong_proof() {
         let mut right_proof = gen_right_proof();
@@ -366,12 +548,13 @@ mod tests {
             &wrong_proof.public,
             &wrong_proof.proof,
             &wrong_proof.vk,
-        );
+        )
+        .unwrap();
 
         println!("segments length: {}", segments.len());
 
         // get all elements
-        let mut elements: BTreeMap<String, std::rc::Rc<Box<dyn ElementTrait>>> = BTreeMap::new();
+        let mut elements: BTreeMap<String, Arc<Box<dyn ElementTrait>>> = BTreeMap::new();
         for segment in segments.iter() {
             for parameter in segment.parameter_list.iter() {
                 elements.insert(parameter.id().to_owned(), parameter.clone());
@@ -1,4 +1,5 @@
 use super::common::*;
+use crate::bn254::fp254impl::Fp254Impl;
 use crate::bn254::fq::Fq;
 use crate::bn254::fq12::Fq12;
 use crate::bn254::fq2::Fq2;
@@ -6,9 +7,15 @@ use crate::bn254::fq6::Fq6;
 use crate::bn254::fr::Fr;
 use crate::bn254::{g1::G1Affine, g2::G2Affine};
 use crate::treepp::*;
-use crate::{chunker::assigner::BCAssigner, execute_script_with_inputs};
-use std::any::Any;
+use crate::{
+    chunker::assigner::BCAssigner, execute_script_with_inputs, execute_script_without_stack_limit,
+};
+use ark_ff::AdditiveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use bitcoin::opcodes::Opcode;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::sync::OnceLock;
 
 /// FqElements are used in the chunker, representing muliple Fq.
 #[derive(Debug, Clone)]
@@ -17,6 +24,11 @@ pub struct FqElement {
     pub size: usize,
     pub witness_data: Option<RawWitness>,
     pub data: Option<DataType>,
+    /// Hash of `witness_data` as a witness, computed on the first `to_hash`
+    /// or `to_hash_witness` and reset whenever the witness changes.
+    hash_witness: OnceLock<RawWitness>,
+    /// Whether points are checked to be valid group elements when filled
+    check_points: bool,
 }
 
 /// Achieve witness depth, `9` is the witness depth of `U254`
@@ -24,33 +36,274 @@ impl FqElement {
     fn witness_size(&self) -> usize {
         self.size * 9
     }
+
+    fn check_witness_size(&self, witness: &RawWitness) -> Result<(), ElementError> {
+        if witness.len() != self.witness_size() {
+            return Err(ElementError::WitnessSizeMismatch {
+                id: self.identity.clone(),
+                expected: self.witness_size(),
+                got: witness.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn set_witness_data(&mut self, witness: RawWitness, data: Option<DataType>) {
+        self.witness_data = Some(witness);
+        self.data = data;
+        self.hash_witness = OnceLock::new();
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of times the blake3 script ran on this thread, to check the caching
+    static HASH_SCRIPT_RUNS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
 }
 
 /// Define all data types
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DataType {
     FqData(ark_bn254::Fq),
     FrData(ark_bn254::Fr),
     Fq2Data(ark_bn254::Fq2),
     Fq6Data(ark_bn254::Fq6),
     Fq12Data(ark_bn254::Fq12),
+    /// Fq12 whose only nonzero Fq2 coefficients are c0, c3 and c4, the form of the
+    /// line functions the pairing multiplies in by `mul_by_034`
+    Fq12SparseData(ark_bn254::Fq12),
     G1PointData(ark_bn254::G1Affine),
     G2PointData(ark_bn254::G2Affine),
 }
 
+/// Variant of a `DataType`, needed to decode its canonical bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataTypeVariant {
+    Fq,
+    Fr,
+    Fq2,
+    Fq6,
+    Fq12,
+    Fq12Sparse,
+    G1Point,
+    G2Point,
+}
+
+impl DataTypeVariant {
+    /// Length of the compressed canonical encoding in bytes
+    pub fn canonical_size(&self) -> usize {
+        match self {
+            DataTypeVariant::Fq | DataTypeVariant::Fr => 32,
+            DataTypeVariant::Fq2 => 64,
+            DataTypeVariant::Fq6 | DataTypeVariant::Fq12Sparse => 192,
+            DataTypeVariant::Fq12 => 384,
+            DataTypeVariant::G1Point => 32,
+            DataTypeVariant::G2Point => 64,
+        }
+    }
+}
+
+/// Compact off-chain encoding of the intermediate values, used by the remote data
+/// store. The witness encoding is still what gets committed on-chain.
+impl DataType {
+    pub fn variant(&self) -> DataTypeVariant {
+        match self {
+            DataType::FqData(_) => DataTypeVariant::Fq,
+            DataType::FrData(_) => DataTypeVariant::Fr,
+            DataType::Fq2Data(_) => DataTypeVariant::Fq2,
+            DataType::Fq6Data(_) => DataTypeVariant::Fq6,
+            DataType::Fq12Data(_) => DataTypeVariant::Fq12,
+            DataType::Fq12SparseData(_) => DataTypeVariant::Fq12Sparse,
+            DataType::G1PointData(_) => DataTypeVariant::G1Point,
+            DataType::G2PointData(_) => DataTypeVariant::G2Point,
+        }
+    }
+
+    /// Fails for a sparse Fq12 with nonzero coefficients besides c0, c3 and c4, it
+    /// has no sparse encoding.
+    pub fn to_canonical_bytes(&self) -> Result<Vec<u8>, SerializationError> {
+        let mut bytes = Vec::with_capacity(self.variant().canonical_size());
+        match self {
+            DataType::FqData(x) => x.serialize_compressed(&mut bytes),
+            DataType::FrData(x) => x.serialize_compressed(&mut bytes),
+            DataType::Fq2Data(x) => x.serialize_compressed(&mut bytes),
+            DataType::Fq6Data(x) => x.serialize_compressed(&mut bytes),
+            DataType::Fq12Data(x) => x.serialize_compressed(&mut bytes),
+            DataType::Fq12SparseData(x) => sparse_coefficients(*x)
+                .ok_or(SerializationError::InvalidData)?
+                .serialize_compressed(&mut bytes),
+            DataType::G1PointData(x) => x.serialize_compressed(&mut bytes),
+            DataType::G2PointData(x) => x.serialize_compressed(&mut bytes),
+        }
+        .expect("serializing into a vector cannot fail");
+        Ok(bytes)
+    }
+
+    pub fn from_canonical_bytes(
+        variant: DataTypeVariant,
+        bytes: &[u8],
+    ) -> Result<Self, SerializationError> {
+        if bytes.len() != variant.canonical_size() {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(match variant {
+            DataTypeVariant::Fq => {
+                DataType::FqData(CanonicalDeserialize::deserialize_compressed(bytes)?)
+            }
+            DataTypeVariant::Fr => {
+                DataType::FrData(CanonicalDeserialize::deserialize_compressed(bytes)?)
+            }
+            DataTypeVariant::Fq2 => {
+                DataType::Fq2Data(CanonicalDeserialize::deserialize_compressed(bytes)?)
+            }
+            DataTypeVariant::Fq6 => {
+                DataType::Fq6Data(CanonicalDeserialize::deserialize_compressed(bytes)?)
+            }
+            DataTypeVariant::Fq12 => {
+                DataType::Fq12Data(CanonicalDeserialize::deserialize_compressed(bytes)?)
+            }
+            DataTypeVariant::Fq12Sparse => {
+                let (c0, c3, c4) = CanonicalDeserialize::deserialize_compressed(bytes)?;
+                DataType::Fq12SparseData(sparse_fq12(c0, c3, c4))
+            }
+            DataTypeVariant::G1Point => {
+                DataType::G1PointData(CanonicalDeserialize::deserialize_compressed(bytes)?)
+            }
+            DataTypeVariant::G2Point => {
+                DataType::G2PointData(CanonicalDeserialize::deserialize_compressed(bytes)?)
+            }
+        })
+    }
+}
+
+/// Sparse Fq12 with the coefficients c0, c3 and c4, all others being zero
+pub fn sparse_fq12(c0: ark_bn254::Fq2, c3: ark_bn254::Fq2, c4: ark_bn254::Fq2) -> ark_bn254::Fq12 {
+    ark_bn254::Fq12::new(
+        ark_bn254::Fq6::new(c0, ark_bn254::Fq2::ZERO, ark_bn254::Fq2::ZERO),
+        ark_bn254::Fq6::new(c3, c4, ark_bn254::Fq2::ZERO),
+    )
+}
+
+/// Coefficients c0, c3 and c4 of a sparse Fq12, none if any other is nonzero
+fn sparse_coefficients(
+    x: ark_bn254::Fq12,
+) -> Option<(ark_bn254::Fq2, ark_bn254::Fq2, ark_bn254::Fq2)> {
+    let (c0, c3, c4) = (x.c0.c0, x.c1.c0, x.c1.c1);
+    (x == sparse_fq12(c0, c3, c4)).then_some((c0, c3, c4))
+}
+
+/// Push only the nonzero coefficients of a sparse Fq12, as c0, c3, c4
+fn push_fq12_sparse(x: ark_bn254::Fq12) -> Script {
+    let (c0, c3, c4) = (x.c0.c0, x.c1.c0, x.c1.c1);
+    script! {
+        { Fq2::push(c0) }
+        { Fq2::push(c3) }
+        { Fq2::push(c4) }
+    }
+}
+
+/// Errors for values that are loaded into an element from outside of the chunker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ElementError {
+    /// The blake3 script did not run through on the witness, e.g. because the witness
+    /// is malformed, or the script pushing the values of `fill_many` did not. `error`
+    /// is empty if it ran but did not leave a hash on the stack.
+    ScriptFailed {
+        error: Option<String>,
+        last_opcode: Option<Opcode>,
+        final_stack_len: usize,
+    },
+    /// A hash of `actual` bytes asked for as a `BLAKE3HASH`
+    HashLengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// A witness of `got` elements set or filled into the element `id` instead of
+    /// `expected`, e.g. because it is the witness or data of another type
+    WitnessSizeMismatch {
+        id: String,
+        expected: usize,
+        got: usize,
+    },
+    /// Data of the `got` variant filled into the element `id`, which holds
+    /// another type of the same witness size
+    WrongDataType {
+        id: String,
+        got: DataTypeVariant,
+    },
+    /// A point filled into the element `id` is not on the curve or not in the
+    /// prime order subgroup
+    InvalidPoint {
+        id: String,
+    },
+    /// A sparse Fq12 filled into the element `id` has nonzero coefficients besides
+    /// c0, c3 and c4
+    NotSparse {
+        id: String,
+    },
+}
+
+/// Check that a point is a valid group element before it is committed, the segments
+/// verifying it would fail on anything else only when it is disproved
+fn check_point(id: &str, x: &DataType) -> Result<(), ElementError> {
+    let is_valid = match x {
+        DataType::G1PointData(p) => p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve(),
+        DataType::G2PointData(p) => p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve(),
+        _ => true,
+    };
+    if !is_valid {
+        return Err(ElementError::InvalidPoint { id: id.to_owned() });
+    }
+
+    Ok(())
+}
+
+/// Hash a witness of `witness_size` elements by `hasher`, return witness of Hash
+fn hash_witness<H: CommitmentHash>(
+    hasher: &H,
+    witness: RawWitness,
+    witness_size: usize,
+) -> Result<RawWitness, ElementError> {
+    #[cfg(test)]
+    HASH_SCRIPT_RUNS.with(|runs| runs.set(runs.get() + 1));
+
+    let res = execute_script_with_inputs(
+        script! {
+            {hasher.hash_script(witness_size)}
+        },
+        witness,
+    );
+    if res.error.is_some() || res.final_stack.len() != H::hash_length() {
+        return Err(ElementError::ScriptFailed {
+            error: res.error.as_ref().map(|error| format!("{:?}", error)),
+            last_opcode: res.last_opcode,
+            final_stack_len: res.final_stack.len(),
+        });
+    }
+
+    Ok(extract_witness_from_stack(res))
+}
+
 /// This trait defines the intermediate values
-pub trait ElementTrait: Debug {
+pub trait ElementTrait: Debug + Send + Sync {
     /// Fill data by a specific value
-    fn fill_with_data(&mut self, x: DataType);
+    fn fill_with_data(&mut self, x: DataType) -> Result<(), ElementError>;
     /// Convert the intermediate values to witness
     fn to_witness(&self) -> Option<RawWitness>;
     /// Convert the intermediate values from witness.
     /// If witness is none, return none.
     fn to_data(&self) -> Option<DataType>;
-    /// Hash witness by blake3, return Hash
-    fn to_hash(&self) -> Option<BLAKE3HASH>;
-    /// Hash witness by blake3, return witness of Hash
-    fn to_hash_witness(&self) -> Option<RawWitness>;
+    /// Hash witness by blake3, return Hash.
+    /// If witness is none, return none. Fails for elements hashed by another hasher.
+    fn to_hash(&self) -> Result<Option<BLAKE3HASH>, ElementError>;
+    /// Hash witness by the hasher of the element, return witness of Hash
+    fn to_hash_witness(&self) -> Result<Option<RawWitness>, ElementError>;
+    /// Script hashing the witness on top of the stack like `to_hash_witness`
+    fn hash_script(&self) -> Script;
+    /// Length of the hash by bytes
+    fn hash_length(&self) -> usize;
     /// Size of element by Fq
     fn size(&self) -> usize;
     /// Witness size of element by u32
@@ -60,52 +313,92 @@ pub trait ElementTrait: Debug {
 }
 
 macro_rules! impl_element_trait {
-    ($element_type:ident, $data_type:ident, $size:expr, $push_method:expr) => {
+    ($element_type:ident, $data_type:ident, $size:expr) => {
+        /// Hashed by `H` for its commitment, blake3 unless created with a hasher
         #[derive(Clone, Debug)]
-        pub struct $element_type(FqElement);
+        pub struct $element_type<H: CommitmentHash = Blake3Hasher>(FqElement, H);
 
         impl $element_type {
             /// Create a new element by using bitcommitment assigner
             pub fn new<F: BCAssigner>(assigner: &mut F, id: &str) -> Self {
-                assigner.create_hash(id);
-                Self {
-                    0: FqElement {
-                        identity: id.to_owned(),
-                        size: $size,
-                        witness_data: None,
-                        data: None,
-                    },
-                }
+                Self::new_with_hasher(assigner, id)
             }
 
             pub fn new_dummy(id: &str) -> Self {
-                Self {
-                    0: FqElement {
+                Self::new_dummy_with_hasher(id)
+            }
+        }
+
+        impl<H: CommitmentHash> $element_type<H> {
+            /// Create a new element hashed by `H` by using bitcommitment assigner
+            pub fn new_with_hasher<F: BCAssigner>(assigner: &mut F, id: &str) -> Self {
+                assigner.create_hash(id);
+                let mut element = Self::new_dummy_with_hasher(id);
+                element.0.check_points = assigner.checks_points();
+                element
+            }
+
+            pub fn new_dummy_with_hasher(id: &str) -> Self {
+                Self(
+                    FqElement {
                         identity: id.to_owned(),
                         size: $size,
                         witness_data: None,
                         data: None,
+                        hash_witness: OnceLock::new(),
+                        check_points: true,
                     },
+                    H::default(),
+                )
+            }
+
+            /// Hash by `hasher` instead of the default one of `H`, e.g. with another
+            /// blake3 padding
+            pub fn with_hasher(mut self, hasher: H) -> Self {
+                self.0.hash_witness = OnceLock::new();
+                self.1 = hasher;
+                self
+            }
+
+            /// Set a witness obtained externally, e.g. from an assert-commit transaction.
+            /// The data is left empty, as it is not derived from the witness.
+            pub fn set_witness(&mut self, witness: RawWitness) -> Result<(), ElementError> {
+                self.0.check_witness_size(&witness)?;
+                self.0.set_witness_data(witness, None);
+                Ok(())
+            }
+
+            /// Set data together with the witness it was pushed as. Every way of filling
+            /// data goes through here, so points are always checked before they are
+            /// committed, unless the element was created by an assigner that skips it.
+            fn fill_with_witness(
+                &mut self,
+                x: DataType,
+                witness: RawWitness,
+            ) -> Result<(), ElementError> {
+                self.0.check_witness_size(&witness)?;
+                if !matches!(x, DataType::$data_type(_)) {
+                    return Err(ElementError::WrongDataType {
+                        id: self.0.identity.clone(),
+                        got: x.variant(),
+                    });
                 }
+                if self.0.check_points {
+                    check_point(&self.0.identity, &x)?;
+                }
+
+                self.0.set_witness_data(witness, Some(x));
+                Ok(())
             }
         }
 
         /// impl element for Fq6
-        impl ElementTrait for $element_type {
-            fn fill_with_data(&mut self, x: DataType) {
-                match x {
-                    DataType::$data_type(fq6_data) => {
-                        let res = execute_script(script! {
-                            {$push_method(fq6_data)}
-                        });
-                        let witness = extract_witness_from_stack(res);
-                        assert_eq!(witness.len(), self.0.witness_size());
-
-                        self.0.witness_data = Some(witness);
-                        self.0.data = Some(x)
-                    }
-                    _ => panic!("fill wrong data {:?}", x.type_id()),
-                }
+        impl<H: CommitmentHash> ElementTrait for $element_type<H> {
+            fn fill_with_data(&mut self, x: DataType) -> Result<(), ElementError> {
+                x.check_sparse(&self.0.identity)?;
+                let res = execute_script(x.push());
+                let witness = extract_witness_from_stack(res);
+                self.fill_with_witness(x, witness)
             }
 
             fn to_witness(&self) -> Option<RawWitness> {
@@ -116,36 +409,35 @@ macro_rules! impl_element_trait {
                 self.0.data.clone()
             }
 
-            fn to_hash(&self) -> Option<BLAKE3HASH> {
-                match self.0.witness_data.clone() {
-                    None => None,
-                    Some(witness) => {
-                        let res = execute_script_with_inputs(
-                            script! {
-                                {blake3_var_length(self.0.witness_size())}
-                            },
-                            witness,
-                        );
-                        let hash = witness_to_array(extract_witness_from_stack(res));
-                        Some(hash)
-                    }
+            fn to_hash(&self) -> Result<Option<BLAKE3HASH>, ElementError> {
+                if H::hash_length() != BLAKE3_HASH_LENGTH {
+                    return Err(ElementError::HashLengthMismatch {
+                        expected: BLAKE3_HASH_LENGTH,
+                        actual: H::hash_length(),
+                    });
                 }
+                Ok(self.to_hash_witness()?.map(witness_to_array))
             }
 
-            fn to_hash_witness(&self) -> Option<RawWitness> {
-                match self.0.witness_data.clone() {
-                    None => None,
-                    Some(witness) => {
-                        let res = execute_script_with_inputs(
-                            script! {
-                                {blake3_var_length(self.0.witness_size())}
-                            },
-                            witness,
-                        );
-                        let witness = extract_witness_from_stack(res);
-                        Some(witness)
-                    }
+            fn to_hash_witness(&self) -> Result<Option<RawWitness>, ElementError> {
+                let Some(witness) = &self.0.witness_data else {
+                    return Ok(None);
+                };
+                if let Some(cached) = self.0.hash_witness.get() {
+                    return Ok(Some(cached.clone()));
                 }
+
+                // failures are not cached, they are returned again on every call
+                let hash = hash_witness(&self.1, witness.clone(), self.0.witness_size())?;
+                Ok(Some(self.0.hash_witness.get_or_init(|| hash).clone()))
+            }
+
+            fn hash_script(&self) -> Script {
+                self.1.hash_script(self.0.witness_size())
+            }
+
+            fn hash_length(&self) -> usize {
+                H::hash_length()
             }
 
             fn size(&self) -> usize {
@@ -164,19 +456,126 @@ macro_rules! impl_element_trait {
 }
 
 // (Fq)
-impl_element_trait!(FqType, FqData, 1, Fq::push);
+impl_element_trait!(FqType, FqData, 1);
 // (Fr)
-impl_element_trait!(FrType, FrData, 1, Fr::push);
+impl_element_trait!(FrType, FrData, 1);
 // (Fq2)
-impl_element_trait!(Fq2Type, Fq2Data, 2, Fq2::push);
+impl_element_trait!(Fq2Type, Fq2Data, 2);
 // (Fq6)
-impl_element_trait!(Fq6Type, Fq6Data, 6, Fq6::push);
+impl_element_trait!(Fq6Type, Fq6Data, 6);
 // (Fq12)
-impl_element_trait!(Fq12Type, Fq12Data, 12, Fq12::push);
+impl_element_trait!(Fq12Type, Fq12Data, 12);
+// (c0: Fq2, c3: Fq2, c4: Fq2)
+impl_element_trait!(Fq12SparseType, Fq12SparseData, 6);
 // (x: Fq, y: Fq)
-impl_element_trait!(G1PointType, G1PointData, 2, G1Affine::push);
+impl_element_trait!(G1PointType, G1PointData, 2);
 // (x: Fq, y: Fq2)
-impl_element_trait!(G2PointType, G2PointData, 4, G2Affine::push);
+impl_element_trait!(G2PointType, G2PointData, 4);
+
+impl<H: CommitmentHash> Fq12SparseType<H> {
+    /// The same value as a full Fq12 element, taken from the data or else decoded
+    /// from the witness. It is not created by an assigner, so it is not committed.
+    pub fn to_full(&self) -> Result<Fq12Type<H>, ElementError> {
+        let mut full =
+            Fq12Type::<H>::new_dummy_with_hasher(&self.0.identity).with_hasher(self.1.clone());
+        let x = match (&self.0.data, &self.0.witness_data) {
+            (Some(DataType::Fq12SparseData(x)), _) => Some(*x),
+            // the witness length is checked when it is set
+            (_, Some(witness)) => {
+                let limbs = Fq::N_LIMBS as usize * 2;
+                let coefficient =
+                    |i: usize| Fq2::read_from_stack(witness[i * limbs..(i + 1) * limbs].to_vec());
+                Some(sparse_fq12(coefficient(0), coefficient(1), coefficient(2)))
+            }
+            _ => None,
+        };
+        if let Some(x) = x {
+            full.fill_with_data(DataType::Fq12Data(x))?;
+        }
+        Ok(full)
+    }
+}
+
+impl DataType {
+    /// A sparse Fq12 must be checked before it is pushed, only its nonzero
+    /// coefficients are
+    fn check_sparse(&self, id: &str) -> Result<(), ElementError> {
+        if let DataType::Fq12SparseData(x) = self {
+            if sparse_coefficients(*x).is_none() {
+                return Err(ElementError::NotSparse { id: id.to_owned() });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Script pushing the value as the witness of its element
+    fn push(&self) -> Script {
+        match self {
+            DataType::FqData(x) => Fq::push(*x),
+            DataType::FrData(x) => Fr::push(*x),
+            DataType::Fq2Data(x) => Fq2::push(*x),
+            DataType::Fq6Data(x) => Fq6::push(*x),
+            DataType::Fq12Data(x) => Fq12::push(*x),
+            DataType::Fq12SparseData(x) => push_fq12_sparse(*x),
+            DataType::G1PointData(x) => G1Affine::push(*x),
+            DataType::G2PointData(x) => G2Affine::push(*x),
+        }
+    }
+}
+
+/// Create and fill the elements of `items` at once. All values are pushed in a
+/// single script execution, the resulting stack is split into the witnesses of the
+/// elements by their witness size. The elements are the same as if created and
+/// filled one by one with `fill_with_data`.
+pub fn fill_many<F: BCAssigner>(
+    assigner: &mut F,
+    items: &[(&str, DataType)],
+) -> Result<Vec<Box<dyn ElementTrait>>, ElementError> {
+    for (id, x) in items {
+        x.check_sparse(id)?;
+    }
+    // the script only materializes the witnesses off-chain, so a batch may well
+    // leave more than the 1000 elements a script is limited to on the stack
+    let res = execute_script_without_stack_limit(script! {
+        for (_, x) in items {
+            {x.push()}
+        }
+    });
+    if res.error.is_some() {
+        return Err(ElementError::ScriptFailed {
+            error: res.error.as_ref().map(|error| format!("{:?}", error)),
+            last_opcode: res.last_opcode,
+            final_stack_len: res.final_stack.len(),
+        });
+    }
+    let mut stack = extract_witness_from_stack(res).into_iter();
+
+    items
+        .iter()
+        .map(|(id, x)| {
+            macro_rules! fill {
+                ($element_type:ident) => {{
+                    let mut element = $element_type::new(assigner, id);
+                    let witness = stack.by_ref().take(element.witness_size()).collect();
+                    element.fill_with_witness(x.clone(), witness)?;
+                    Ok(Box::new(element) as Box<dyn ElementTrait>)
+                }};
+            }
+
+            match x {
+                DataType::FqData(_) => fill!(FqType),
+                DataType::FrData(_) => fill!(FrType),
+                DataType::Fq2Data(_) => fill!(Fq2Type),
+                DataType::Fq6Data(_) => fill!(Fq6Type),
+                DataType::Fq12Data(_) => fill!(Fq12Type),
+                DataType::Fq12SparseData(_) => fill!(Fq12SparseType),
+                DataType::G1PointData(_) => fill!(G1PointType),
+                DataType::G2PointData(_) => fill!(G2PointType),
+            }
+        })
+        .collect()
+}
 
 #[derive(Debug, Clone)]
 pub struct DummyElement {
@@ -185,7 +584,9 @@ pub struct DummyElement {
 }
 
 impl ElementTrait for DummyElement {
-    fn fill_with_data(&mut self, _: DataType) {}
+    fn fill_with_data(&mut self, _: DataType) -> Result<(), ElementError> {
+        Ok(())
+    }
 
     fn to_witness(&self) -> Option<RawWitness> {
         None
@@ -195,12 +596,20 @@ impl ElementTrait for DummyElement {
         None
     }
 
-    fn to_hash(&self) -> Option<BLAKE3HASH> {
-        Some(self.hash)
+    fn to_hash(&self) -> Result<Option<BLAKE3HASH>, ElementError> {
+        Ok(Some(self.hash))
+    }
+
+    fn to_hash_witness(&self) -> Result<Option<RawWitness>, ElementError> {
+        Ok(Some(array_to_witness(self.hash)))
+    }
+
+    fn hash_script(&self) -> Script {
+        blake3_var_length(0)
     }
 
-    fn to_hash_witness(&self) -> Option<RawWitness> {
-        Some(array_to_witness(self.hash))
+    fn hash_length(&self) -> usize {
+        BLAKE3_HASH_LENGTH
     }
 
     fn size(&self) -> usize {
@@ -222,3 +631,541 @@ pub fn dummy_element(id: &str, hash: BLAKE3HASH) -> DummyElement {
         hash,
     }
 }
+
+/// Check the hash committed for every element against the blake3 hash of its
+/// witness, matched by element identity.
+/// Returns the identities whose commitment is missing, whose hash cannot be
+/// computed or whose hash differs from the committed one.
+pub fn verify_commitment_chain(
+    elements: &[&dyn ElementTrait],
+    committed: &BTreeMap<String, BLAKE3HASH>,
+) -> Result<(), Vec<String>> {
+    let mismatches: Vec<String> = elements
+        .iter()
+        .filter(
+            |element| match (element.to_hash(), committed.get(element.id())) {
+                (Ok(Some(hash)), Some(committed_hash)) => hash != *committed_hash,
+                _ => true,
+            },
+        )
+        .map(|element| element.id().to_owned())
+        .collect();
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        dummy_element, fill_many, sparse_fq12, verify_commitment_chain, DataType, DataTypeVariant,
+        ElementError, ElementTrait, Fq12SparseType, Fq12Type, Fq2Type, Fq6Type, FqType, FrType,
+        G1PointType, G2PointType, BLAKE3_HASH_LENGTH, HASH_SCRIPT_RUNS,
+    };
+    use crate::chunker::{
+        assigner::DummyAssigner,
+        common::{
+            blake3_var_length, Blake3Hasher, Blake3Padding, Sha256Hasher, SHA256_HASH_LENGTH,
+        },
+    };
+    use ark_std::{test_rng, UniformRand};
+    use bitcoin::script::read_scriptint;
+    use sha2::{Digest, Sha256};
+    use std::{collections::BTreeMap, time::Instant};
+
+    fn random_items() -> Vec<(String, DataType)> {
+        let mut prng = test_rng();
+        (0..4)
+            .flat_map(|i| {
+                vec![
+                    (
+                        format!("fq_{i}"),
+                        DataType::FqData(ark_bn254::Fq::rand(&mut prng)),
+                    ),
+                    (
+                        format!("fr_{i}"),
+                        DataType::FrData(ark_bn254::Fr::rand(&mut prng)),
+                    ),
+                    (
+                        format!("fq2_{i}"),
+                        DataType::Fq2Data(ark_bn254::Fq2::rand(&mut prng)),
+                    ),
+                    (
+                        format!("fq6_{i}"),
+                        DataType::Fq6Data(ark_bn254::Fq6::rand(&mut prng)),
+                    ),
+                    (
+                        format!("fq12_{i}"),
+                        DataType::Fq12Data(ark_bn254::Fq12::rand(&mut prng)),
+                    ),
+                    (
+                        format!("fq12_sparse_{i}"),
+                        DataType::Fq12SparseData(sparse_fq12(
+                            ark_bn254::Fq2::rand(&mut prng),
+                            ark_bn254::Fq2::rand(&mut prng),
+                            ark_bn254::Fq2::rand(&mut prng),
+                        )),
+                    ),
+                    (
+                        format!("g1_{i}"),
+                        DataType::G1PointData(ark_bn254::G1Affine::rand(&mut prng)),
+                    ),
+                    (
+                        format!("g2_{i}"),
+                        DataType::G2PointData(ark_bn254::G2Affine::rand(&mut prng)),
+                    ),
+                ]
+            })
+            .collect()
+    }
+
+    fn fill_one(id: &str, x: DataType) -> Box<dyn ElementTrait> {
+        let mut element: Box<dyn ElementTrait> = match x {
+            DataType::FqData(_) => Box::new(FqType::new_dummy(id)),
+            DataType::FrData(_) => Box::new(FrType::new_dummy(id)),
+            DataType::Fq2Data(_) => Box::new(Fq2Type::new_dummy(id)),
+            DataType::Fq6Data(_) => Box::new(Fq6Type::new_dummy(id)),
+            DataType::Fq12Data(_) => Box::new(Fq12Type::new_dummy(id)),
+            DataType::Fq12SparseData(_) => Box::new(Fq12SparseType::new_dummy(id)),
+            DataType::G1PointData(_) => Box::new(G1PointType::new_dummy(id)),
+            DataType::G2PointData(_) => Box::new(G2PointType::new_dummy(id)),
+        };
+        element.fill_with_data(x).unwrap();
+        element
+    }
+
+    #[test]
+    fn test_fill_many() {
+        let items = random_items();
+        let items: Vec<(&str, DataType)> = items
+            .iter()
+            .map(|(id, x)| (id.as_str(), x.clone()))
+            .collect();
+
+        let elements = fill_many(&mut DummyAssigner::default(), &items).unwrap();
+        assert_eq!(elements.len(), items.len());
+        // more than fit on the stack of a script at once
+        assert!(elements.iter().map(|element| element.witness_size()).sum::<usize>() > 1000);
+        for (element, (id, x)) in elements.iter().zip(&items) {
+            let expected = fill_one(id, x.clone());
+            assert_eq!(element.id(), *id);
+            assert_eq!(element.to_witness(), expected.to_witness());
+            assert_eq!(element.to_data(), Some(x.clone()));
+        }
+
+        assert!(fill_many(&mut DummyAssigner::default(), &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_fill_many() {
+        let items = random_items();
+        let items: Vec<(&str, DataType)> = items
+            .iter()
+            .map(|(id, x)| (id.as_str(), x.clone()))
+            .collect();
+
+        let start = Instant::now();
+        for (id, x) in &items {
+            fill_one(id, x.clone());
+        }
+        println!(
+            "{} elements filled one by one in {:?}",
+            items.len(),
+            start.elapsed()
+        );
+
+        let start = Instant::now();
+        fill_many(&mut DummyAssigner::default(), &items).unwrap();
+        println!(
+            "{} elements filled at once in {:?}",
+            items.len(),
+            start.elapsed()
+        );
+    }
+
+    #[test]
+    fn test_set_witness_length() {
+        let mut fq12 = Fq12Type::new_dummy("fq12");
+        fq12.fill_with_data(DataType::Fq12Data(ark_bn254::Fq12::rand(&mut test_rng())))
+            .unwrap();
+        let witness = fq12.to_witness().unwrap();
+
+        let mut element = Fq12Type::new_dummy("fq12");
+        assert_eq!(element.set_witness(witness.clone()), Ok(()));
+        assert_eq!(element.to_witness(), Some(witness.clone()));
+        assert_eq!(element.to_hash(), fq12.to_hash());
+
+        // an Fq12 witness does not fit a G1 point
+        let mut element = G1PointType::new_dummy("g1");
+        assert_eq!(
+            element.set_witness(witness.clone()),
+            Err(ElementError::WitnessSizeMismatch {
+                id: "g1".to_owned(),
+                expected: element.witness_size(),
+                got: witness.len(),
+            })
+        );
+        assert_eq!(element.to_witness(), None);
+
+        let mut element = Fq12Type::new_dummy("fq12");
+        assert!(element.set_witness(witness[1..].to_vec()).is_err());
+        assert_eq!(element.to_witness(), None);
+    }
+
+    #[test]
+    fn test_fill_with_data_of_wrong_size() {
+        let x = DataType::Fq12Data(ark_bn254::Fq12::rand(&mut test_rng()));
+        let fq12_witness_size = Fq12Type::new_dummy("fq12").witness_size();
+
+        // an Fq12 does not fit an Fq6, the error names the element
+        let mut element = Fq6Type::new_dummy("T4_0_double");
+        assert_eq!(
+            element.fill_with_data(x),
+            Err(ElementError::WitnessSizeMismatch {
+                id: "T4_0_double".to_owned(),
+                expected: element.witness_size(),
+                got: fq12_witness_size,
+            })
+        );
+        assert_eq!(element.to_witness(), None);
+        assert_eq!(element.to_data(), None);
+    }
+
+    #[test]
+    fn test_fill_with_data_of_wrong_type() {
+        let mut prng = test_rng();
+
+        // same witness size, but another type
+        let mut element = Fq2Type::new_dummy("fq2");
+        assert_eq!(
+            element.fill_with_data(DataType::G1PointData(ark_bn254::G1Affine::rand(&mut prng))),
+            Err(ElementError::WrongDataType {
+                id: "fq2".to_owned(),
+                got: DataTypeVariant::G1Point,
+            })
+        );
+        assert_eq!(element.to_witness(), None);
+
+        let mut element = Fq12SparseType::new_dummy("line");
+        assert_eq!(
+            element.fill_with_data(DataType::Fq6Data(ark_bn254::Fq6::rand(&mut prng))),
+            Err(ElementError::WrongDataType {
+                id: "line".to_owned(),
+                got: DataTypeVariant::Fq6,
+            })
+        );
+        assert_eq!(element.to_data(), None);
+    }
+
+    #[test]
+    fn test_fill_with_invalid_point() {
+        // y^2 = x^3 + 3 does not hold for (1, 1)
+        let off_curve = ark_bn254::G1Affine::new_unchecked(
+            ark_bn254::Fq::from(1u64),
+            ark_bn254::Fq::from(1u64),
+        );
+        let mut element = G1PointType::new_dummy("g1");
+        assert_eq!(
+            element.fill_with_data(DataType::G1PointData(off_curve)),
+            Err(ElementError::InvalidPoint {
+                id: "g1".to_owned()
+            })
+        );
+        assert_eq!(element.to_witness(), None);
+
+        // the twist has a cofactor, a point for a random x is almost never in the subgroup
+        let mut prng = test_rng();
+        let off_subgroup = loop {
+            let x = ark_bn254::Fq2::rand(&mut prng);
+            if let Some(point) = ark_bn254::G2Affine::get_point_from_x_unchecked(x, false) {
+                if !point.is_in_correct_subgroup_assuming_on_curve() {
+                    break point;
+                }
+            }
+        };
+        assert!(off_subgroup.is_on_curve());
+        let mut element = G2PointType::new_dummy("g2");
+        assert_eq!(
+            element.fill_with_data(DataType::G2PointData(off_subgroup)),
+            Err(ElementError::InvalidPoint {
+                id: "g2".to_owned()
+            })
+        );
+        assert_eq!(element.to_witness(), None);
+
+        let mut element = G2PointType::new_dummy("g2");
+        let point = ark_bn254::G2Affine::rand(&mut prng);
+        assert_eq!(element.fill_with_data(DataType::G2PointData(point)), Ok(()));
+
+        // filled in bulk, the same points are rejected
+        let items = [
+            ("g2", DataType::G2PointData(point)),
+            ("g1", DataType::G1PointData(off_curve)),
+        ];
+        assert_eq!(
+            fill_many(&mut DummyAssigner::default(), &items).err(),
+            Some(ElementError::InvalidPoint {
+                id: "g1".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_hash_cached() {
+        let hash_script_runs = || HASH_SCRIPT_RUNS.with(|runs| runs.get());
+        let mut prng = test_rng();
+        let mut fq6 = Fq6Type::new_dummy("fq6");
+        fq6.fill_with_data(DataType::Fq6Data(ark_bn254::Fq6::rand(&mut prng)))
+            .unwrap();
+
+        let runs = hash_script_runs();
+        let hash = fq6.to_hash().unwrap().unwrap();
+        for _ in 0..3 {
+            assert_eq!(fq6.to_hash(), Ok(Some(hash)));
+            assert!(fq6.to_hash_witness().unwrap().is_some());
+        }
+        assert_eq!(hash_script_runs(), runs + 1);
+
+        // new data makes the cached hash stale
+        fq6.fill_with_data(DataType::Fq6Data(ark_bn254::Fq6::rand(&mut prng)))
+            .unwrap();
+        let new_hash = fq6.to_hash().unwrap().unwrap();
+        assert_ne!(new_hash, hash);
+        assert_eq!(hash_script_runs(), runs + 2);
+
+        let mut element = Fq6Type::new_dummy("fq6");
+        element.set_witness(fq6.to_witness().unwrap()).unwrap();
+        assert_eq!(element.to_hash(), Ok(Some(new_hash)));
+        assert_eq!(hash_script_runs(), runs + 3);
+    }
+
+    #[test]
+    fn test_sha256_hasher() {
+        let x = DataType::Fq2Data(ark_bn254::Fq2::rand(&mut test_rng()));
+        let mut element = Fq2Type::<Sha256Hasher>::new_dummy_with_hasher("fq2");
+        element.fill_with_data(x.clone()).unwrap();
+        let witness = element.to_witness().unwrap();
+        let hash_witness = element.to_hash_witness().unwrap().unwrap();
+        assert_eq!(element.hash_length(), SHA256_HASH_LENGTH);
+        assert_eq!(hash_witness.len(), SHA256_HASH_LENGTH);
+
+        // the digest is left with its first byte on top of the stack
+        let message: Vec<u8> = witness
+            .iter()
+            .flat_map(|limb| (read_scriptint(limb).unwrap() as u32).to_le_bytes())
+            .collect();
+        let hash: Vec<u8> = hash_witness
+            .iter()
+            .rev()
+            .map(|byte| byte.first().copied().unwrap_or(0))
+            .collect();
+        assert_eq!(hash, Sha256::digest(&message).to_vec());
+
+        let mut committed = Fq2Type::<Sha256Hasher>::new_dummy_with_hasher("fq2");
+        assert_eq!(committed.set_witness(witness), Ok(()));
+        assert_eq!(committed.to_hash_witness(), Ok(Some(hash_witness)));
+        assert_eq!(
+            committed.to_hash(),
+            Err(ElementError::HashLengthMismatch {
+                expected: BLAKE3_HASH_LENGTH,
+                actual: SHA256_HASH_LENGTH,
+            })
+        );
+
+        let mut blake3 = Fq2Type::new_dummy("fq2");
+        blake3.fill_with_data(x).unwrap();
+        assert_eq!(blake3.hash_length(), BLAKE3_HASH_LENGTH);
+        assert_eq!(
+            blake3.to_hash_witness().unwrap().unwrap().len(),
+            BLAKE3_HASH_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_blake3_padding_of_elements() {
+        let mut prng = test_rng();
+
+        // the padding tuned to the witness size hashes to the same commitment, by a
+        // script that is smaller for every witness spanning several blocks
+        macro_rules! check_padding {
+            ($element_type:ident, $x:expr, $is_smaller:expr) => {{
+                let x = $x;
+                let mut roll = $element_type::new_dummy("roll");
+                roll.fill_with_data(x.clone()).unwrap();
+                let hasher = Blake3Hasher::for_length(roll.witness_size());
+                let mut tuned = $element_type::new_dummy("tuned").with_hasher(hasher);
+                tuned.fill_with_data(x).unwrap();
+
+                assert_eq!(roll.to_hash_witness().unwrap().unwrap().len(), BLAKE3_HASH_LENGTH);
+                assert_eq!(tuned.to_hash(), roll.to_hash());
+                assert_eq!(hasher.padding == Blake3Padding::AltStack, $is_smaller);
+                if $is_smaller {
+                    assert!(tuned.hash_script().len() < roll.hash_script().len());
+                } else {
+                    assert_eq!(tuned.hash_script().len(), roll.hash_script().len());
+                }
+            }};
+        }
+
+        check_padding!(FqType, DataType::FqData(ark_bn254::Fq::rand(&mut prng)), false);
+        check_padding!(Fq6Type, DataType::Fq6Data(ark_bn254::Fq6::rand(&mut prng)), true);
+        check_padding!(Fq12Type, DataType::Fq12Data(ark_bn254::Fq12::rand(&mut prng)), true);
+
+        // the default keeps the layout of the scripts already committed to
+        let fq12 = Fq12Type::new_dummy("fq12");
+        assert_eq!(
+            fq12.hash_script().len(),
+            blake3_var_length(fq12.witness_size()).len()
+        );
+    }
+
+    #[test]
+    fn test_to_hash_malformed_witness() {
+        assert_eq!(FqType::new_dummy("empty").to_hash(), Ok(None));
+
+        // limbs wider than a script number make the blake3 script fail
+        let mut element = FqType::new_dummy("fq");
+        let witness = vec![vec![0xff; 5]; element.witness_size()];
+        assert_eq!(element.set_witness(witness), Ok(()));
+        assert!(matches!(
+            element.to_hash(),
+            Err(ElementError::ScriptFailed { error: Some(_), .. })
+        ));
+        assert!(element.to_hash_witness().is_err());
+    }
+
+    #[test]
+    fn test_data_type_canonical_bytes_roundtrip() {
+        let mut prng = test_rng();
+        let data = vec![
+            DataType::FqData(ark_bn254::Fq::rand(&mut prng)),
+            DataType::FrData(ark_bn254::Fr::rand(&mut prng)),
+            DataType::Fq2Data(ark_bn254::Fq2::rand(&mut prng)),
+            DataType::Fq6Data(ark_bn254::Fq6::rand(&mut prng)),
+            DataType::Fq12Data(ark_bn254::Fq12::rand(&mut prng)),
+            DataType::Fq12SparseData(sparse_fq12(
+                ark_bn254::Fq2::rand(&mut prng),
+                ark_bn254::Fq2::rand(&mut prng),
+                ark_bn254::Fq2::rand(&mut prng),
+            )),
+            DataType::G1PointData(ark_bn254::G1Affine::rand(&mut prng)),
+            DataType::G2PointData(ark_bn254::G2Affine::rand(&mut prng)),
+        ];
+
+        for x in data {
+            let bytes = x.to_canonical_bytes().unwrap();
+            assert_eq!(bytes.len(), x.variant().canonical_size());
+            assert_eq!(
+                DataType::from_canonical_bytes(x.variant(), &bytes).unwrap(),
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn test_data_type_canonical_bytes_size() {
+        let fq12 = DataType::Fq12Data(ark_bn254::Fq12::rand(&mut test_rng()));
+        assert_eq!(fq12.to_canonical_bytes().unwrap().len(), 384);
+
+        // a truncated encoding is rejected instead of being read as another variant
+        let bytes = fq12.to_canonical_bytes().unwrap();
+        assert!(DataType::from_canonical_bytes(DataTypeVariant::Fq12, &bytes[..383]).is_err());
+        assert!(DataType::from_canonical_bytes(DataTypeVariant::Fq6, &bytes).is_err());
+    }
+
+    #[test]
+    fn test_fq12_sparse_roundtrip() {
+        let mut prng = test_rng();
+        let f = ark_bn254::Fq12::rand(&mut prng);
+        let (c0, c3, c4) = (
+            ark_bn254::Fq2::rand(&mut prng),
+            ark_bn254::Fq2::rand(&mut prng),
+            ark_bn254::Fq2::rand(&mut prng),
+        );
+        let line = sparse_fq12(c0, c3, c4);
+        let mut f1 = f;
+        f1.mul_by_034(&c0, &c3, &c4);
+        assert_eq!(f1, f * line);
+
+        let mut sparse = Fq12SparseType::new_dummy("line");
+        sparse
+            .fill_with_data(DataType::Fq12SparseData(line))
+            .unwrap();
+        let mut full = Fq12Type::new_dummy("line");
+        full.fill_with_data(DataType::Fq12Data(line)).unwrap();
+        assert_eq!(sparse.witness_size() * 2, full.witness_size());
+
+        // the committed witness alone gives back the same field element
+        let mut committed = Fq12SparseType::new_dummy("line");
+        committed.set_witness(sparse.to_witness().unwrap()).unwrap();
+        assert_eq!(committed.to_hash(), sparse.to_hash());
+        for element in [sparse.to_full().unwrap(), committed.to_full().unwrap()] {
+            assert_eq!(element.to_data(), Some(DataType::Fq12Data(line)));
+            assert_eq!(element.to_witness(), full.to_witness());
+        }
+        assert_eq!(
+            Fq12SparseType::new_dummy("empty")
+                .to_full()
+                .unwrap()
+                .to_data(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fill_with_non_sparse_fq12() {
+        let x = DataType::Fq12SparseData(ark_bn254::Fq12::rand(&mut test_rng()));
+        let expected = ElementError::NotSparse {
+            id: "line".to_owned(),
+        };
+
+        // a full Fq12 does not fit the sparse witness, however it is filled
+        let mut element = Fq12SparseType::new_dummy("line");
+        assert_eq!(element.fill_with_data(x.clone()), Err(expected.clone()));
+        assert_eq!(element.to_witness(), None);
+        assert_eq!(
+            fill_many(&mut DummyAssigner::default(), &[("line", x.clone())]).err(),
+            Some(expected)
+        );
+        assert!(x.to_canonical_bytes().is_err());
+    }
+
+    #[test]
+    fn test_verify_commitment_chain() {
+        let mut fq = FqType::new_dummy("fq");
+        fq.fill_with_data(DataType::FqData(ark_bn254::Fq::rand(&mut test_rng())))
+            .unwrap();
+        let a = dummy_element("a", [1; BLAKE3_HASH_LENGTH]);
+        let b = dummy_element("b", [2; BLAKE3_HASH_LENGTH]);
+        let elements: Vec<&dyn ElementTrait> = vec![&fq, &a, &b];
+
+        let mut committed = BTreeMap::from([
+            ("fq".to_owned(), fq.to_hash().unwrap().unwrap()),
+            ("a".to_owned(), [1; BLAKE3_HASH_LENGTH]),
+            ("b".to_owned(), [2; BLAKE3_HASH_LENGTH]),
+        ]);
+        assert_eq!(verify_commitment_chain(&elements, &committed), Ok(()));
+
+        // a wrong hash and a missing commitment are both reported by identity
+        committed.insert("fq".to_owned(), [0; BLAKE3_HASH_LENGTH]);
+        committed.remove("b");
+        assert_eq!(
+            verify_commitment_chain(&elements, &committed),
+            Err(vec!["fq".to_owned(), "b".to_owned()])
+        );
+
+        // an element without a witness cannot match any commitment
+        let empty = FqType::new_dummy("empty");
+        committed.insert("empty".to_owned(), [0; BLAKE3_HASH_LENGTH]);
+        assert_eq!(
+            verify_commitment_chain(&[&empty], &committed),
+            Err(vec!["empty".to_owned()])
+        );
+    }
+}
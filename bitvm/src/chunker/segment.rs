@@ -5,15 +5,15 @@ use super::elements::ElementTrait;
 use crate::bn254::utils::Hint;
 use crate::execute_script;
 use crate::treepp::*;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// Each segment is a branch in the taproot of disprove transaction.
 #[derive(Debug)]
 pub struct Segment {
     pub name: String,
     pub script: Script,
-    pub parameter_list: Vec<Rc<Box<dyn ElementTrait>>>,
-    pub result_list: Vec<Rc<Box<dyn ElementTrait>>>,
+    pub parameter_list: Vec<Arc<Box<dyn ElementTrait>>>,
+    pub result_list: Vec<Arc<Box<dyn ElementTrait>>>,
     pub hints: Vec<Hint>,
     pub final_segment: bool,
 }
@@ -49,12 +49,12 @@ impl Segment {
     }
 
     pub fn add_parameter<T: ElementTrait + 'static + Clone>(mut self, x: &T) -> Self {
-        self.parameter_list.push(Rc::new(Box::new(x.clone())));
+        self.parameter_list.push(Arc::new(Box::new(x.clone())));
         self
     }
 
     pub fn add_result<T: ElementTrait + 'static + Clone>(mut self, x: &T) -> Self {
-        self.result_list.push(Rc::new(Box::new(x.clone())));
+        self.result_list.push(Arc::new(Box::new(x.clone())));
         self
     }
 
@@ -125,7 +125,7 @@ impl Segment {
                         for _ in 0..parameter_length {
                             {base + parameter_length - 1} OP_PICK
                         }
-                        {blake3_var_length(parameter_length)}
+                        {parameter.hash_script()}
                         for _ in 0..BLAKE3_HASH_LENGTH {
                             OP_FROMALTSTACK
                         }
@@ -146,7 +146,7 @@ impl Segment {
 
                 // 4. result of blake3
                 for result in self.result_list.iter().rev() {
-                    {blake3_var_length(result.as_ref().witness_size())}
+                    {result.hash_script()}
                     for _ in 0..BLAKE3_HASH_LENGTH {
                         OP_TOALTSTACK
                     }
@@ -219,7 +219,7 @@ mod tests {
         let mut assigner = DummyAssigner::default();
 
         let mut a0 = Fq6Type::new(&mut assigner, "a0");
-        a0.fill_with_data(Fq6Data(ark_bn254::Fq6::from(1)));
+        a0.fill_with_data(Fq6Data(ark_bn254::Fq6::from(1))).unwrap();
 
         let segment = Segment::new(script! {}).add_parameter(&a0).add_result(&a0);
 
@@ -229,7 +229,7 @@ mod tests {
         println!("witnesss needs stack {}", witness.len());
         println!(
             "element witnesss needs stack {}",
-            a0.to_hash_witness().unwrap().len()
+            a0.to_hash_witness().unwrap().unwrap().len()
         );
 
         let res = execute_script_with_inputs(script, witness);
@@ -246,10 +246,11 @@ mod tests {
         let mut assigner = DummyAssigner::default();
 
         let mut a0 = Fq6Type::new(&mut assigner, "scalar_1");
-        a0.fill_with_data(Fq6Data(ark_bn254::Fq6::from(1)));
+        a0.fill_with_data(Fq6Data(ark_bn254::Fq6::from(1))).unwrap();
 
         let mut a1 = G1PointType::new(&mut assigner, "a0");
-        a1.fill_with_data(G1PointData(ark_bn254::G1Affine::rand(&mut rng)));
+        a1.fill_with_data(G1PointData(ark_bn254::G1Affine::rand(&mut rng)))
+            .unwrap();
 
         let segment = Segment::new(script! {
             for _ in 0..54 {
@@ -266,7 +267,7 @@ mod tests {
         println!("witnesss needs stack {}", witness.len());
         println!(
             "element witnesss needs stack {}",
-            a0.to_hash_witness().unwrap().len()
+            a0.to_hash_witness().unwrap().unwrap().len()
         );
 
         let res = execute_script_with_inputs(script, witness);
@@ -284,10 +285,10 @@ mod tests {
         let x = ark_bn254::G2Affine::rand(&mut rng);
 
         let mut q4 = G2PointType::new(&mut assigner, "q4");
-        q4.fill_with_data(G2PointData(x));
+        q4.fill_with_data(G2PointData(x)).unwrap();
 
         let mut t4 = G2PointType::new(&mut assigner, "t4_init");
-        t4.fill_with_data(G2PointData(x));
+        t4.fill_with_data(G2PointData(x)).unwrap();
 
         let segment = Segment::new_with_name("copy_q4_to_t4".into(), script! {})
             .add_parameter(&q4)
@@ -241,7 +241,67 @@ pub fn blake3() -> Script {
     }
 }
 
+/// Layout used to pad the message to a whole number of 64-byte blocks. Blake3
+/// fixes the block size, so the layouts only differ in script size and hash
+/// the message to the same digest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Blake3Padding {
+    /// Push the padding on top and roll every message u32 above it
+    #[default]
+    Roll,
+    /// Park the message on the altstack while the padding is pushed below it
+    AltStack,
+}
+
+impl Blake3Padding {
+    /// The smaller layout for a message of `num_u32` u32s. Within a single block
+    /// every roll depth is pushed by one opcode, beyond that the altstack round
+    /// trip is smaller.
+    pub fn smallest_for_length(num_u32: usize) -> Self {
+        if num_u32 <= 16 {
+            Blake3Padding::Roll
+        } else {
+            Blake3Padding::AltStack
+        }
+    }
+
+    fn script(&self, num_u32: usize, num_padding_u32: usize) -> Script {
+        if num_padding_u32 == 0 {
+            return script! {};
+        }
+
+        match self {
+            Blake3Padding::Roll => script! {
+                for _ in 0..num_padding_u32 {
+                    {0}
+                }
+                for _ in 0..num_u32 {
+                    { num_u32 + num_padding_u32 - 1 } OP_ROLL
+                }
+            },
+            Blake3Padding::AltStack => script! {
+                for _ in 0..num_u32 {
+                    OP_TOALTSTACK
+                }
+                for _ in 0..num_padding_u32 {
+                    {0}
+                }
+                for _ in 0..num_u32 {
+                    OP_FROMALTSTACK
+                }
+            },
+        }
+    }
+}
+
+/// Pads with the default `Blake3Padding::Roll` layout, which the scripts already
+/// committed to in graphs are built with. Use `blake3_var_length_with_padding`
+/// to opt into a smaller layout.
 pub fn blake3_var_length(num_u32: usize) -> Script {
+    blake3_var_length_with_padding(num_u32, Blake3Padding::default())
+}
+
+pub fn blake3_var_length_with_padding(num_u32: usize, padding: Blake3Padding) -> Script {
     assert!(num_u32 <= 512,
             "This blake3 implementation does not support input larger than 512 bytes due to stack limit. \
             Please modify the hashing routine to avoid calling blake3 in this way.");
@@ -312,18 +372,8 @@ pub fn blake3_var_length(num_u32: usize) -> Script {
 
     let script = script! {
 
-        // Add the padding
-        for _ in 0..num_padding_u32 {
-            {0}
-        }
-        //{ push_to_stack(0, num_padding_bytes) }
-
-        // If padded, move all the bytes down
-        if num_padding_u32 != 0 {
-            for _ in 0..num_u32 {
-                { num_u32 + num_padding_u32 - 1 } OP_ROLL
-            }
-        }
+        // Add the padding below the message
+        { padding.script(num_u32, num_padding_u32) }
 
         // the 1st block
         for _ in 0..15{
@@ -624,10 +674,14 @@ pub fn blake3_hash_equalverify() -> Script {
 
 #[cfg(test)]
 mod tests {
+    use crate::bn254::fp254impl::Fp254Impl;
+    use crate::bn254::fq::Fq;
     use crate::hash::blake3_u32::*;
     
     use crate::treepp::{execute_script, script};
-    use crate::u32::u32_std::{u32_equalverify, u32_push, u32_uncompress};
+    use crate::u32::u32_std::{
+        u32_equalverify, u32_fromaltstack, u32_push, u32_toaltstack, u32_uncompress,
+    };
 
     #[test]
     fn test_permute() {
@@ -721,6 +775,57 @@ mod tests {
         println!("max_nb_stack_items = {max_nb_stack_items}");
     }
 
+    // witness sizes of Fq, Fq6 and Fq12 elements in u32
+    fn element_lengths() -> [(&'static str, usize); 3] {
+        let fq_length = Fq::N_LIMBS as usize;
+        [("Fq", fq_length), ("Fq6", fq_length * 6), ("Fq12", fq_length * 12)]
+    }
+
+    #[test]
+    fn test_blake3_var_length_padding_equivalence() {
+        for num_u32 in [1, 9, 16, 17, 54, 100, 108] {
+            let script = script! {
+                for i in 0..num_u32 {
+                    { i }
+                }
+                { blake3_var_length_with_padding(num_u32, Blake3Padding::Roll) }
+                for _ in 0..N_DIGEST_U32_LIMBS {
+                    u32_toaltstack
+                }
+                for i in 0..num_u32 {
+                    { i }
+                }
+                { blake3_var_length_with_padding(num_u32, Blake3Padding::AltStack) }
+                for _ in 0..N_DIGEST_U32_LIMBS {
+                    u32_fromaltstack
+                }
+                blake3_hash_equalverify
+                OP_TRUE
+            };
+            let exec_result = execute_script(script);
+            assert!(exec_result.success, "num_u32: {num_u32}");
+        }
+    }
+
+    #[test]
+    fn test_blake3_var_length_padding_script_size() {
+        for (name, num_u32) in element_lengths() {
+            let roll_size = blake3_var_length_with_padding(num_u32, Blake3Padding::Roll).len();
+            let alt_stack_size =
+                blake3_var_length_with_padding(num_u32, Blake3Padding::AltStack).len();
+            let default_size = blake3_var_length(num_u32).len();
+            assert_eq!(default_size, roll_size, "{name}");
+
+            let smallest_padding = Blake3Padding::smallest_for_length(num_u32);
+            let smallest_size = blake3_var_length_with_padding(num_u32, smallest_padding).len();
+            assert_eq!(smallest_size, roll_size.min(alt_stack_size), "{name}");
+            // parking a message that spans several blocks is strictly smaller
+            if num_u32 > 16 {
+                assert!(alt_stack_size < roll_size, "{name}");
+            }
+        }
+    }
+
     #[test]
     fn test_blake3_var_length_copy() {
         let hex_out = match N_DIGEST_U32_LIMBS {
@@ -1,5 +1,9 @@
-use bitcoin::Witness;
+use bitcoin::{
+    hashes::{sha256, Hash},
+    Witness,
+};
 use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
 
 use crate::signatures::winternitz_hash::WINTERNITZ_VARIABLE_VERIFIER;
 use crate::treepp::{script, Script};
@@ -44,6 +48,20 @@ impl WinternitzSecret {
     }
 }
 
+// Never print the secret key itself, only enough to tell two secrets apart.
+impl Debug for WinternitzSecret {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let fingerprint = sha256::Hash::hash(&self.secret_key);
+        f.debug_struct("WinternitzSecret")
+            .field("secret_key_length", &self.secret_key.len())
+            .field(
+                "fingerprint",
+                &hex::encode(&fingerprint.as_byte_array()[..4]),
+            )
+            .finish()
+    }
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
 pub struct WinternitzPublicKey {
     pub public_key: PublicKey,
@@ -112,6 +130,40 @@ pub fn generate_winternitz_witness(signing_inputs: &WinternitzSigningInputs) ->
     )
 }
 
+/// Recovers the message committed to by a witness produced with `generate_winternitz_witness`.
+/// The witness holds (hash, digit) pairs, starting with the last message digit, followed by
+/// the checksum digits. Returns `None` if the witness is too short or a digit is malformed.
+pub fn winternitz_message_from_witness(
+    witness: &[Vec<u8>],
+    message_size: usize,
+) -> Option<Vec<u8>> {
+    let digit_count = message_size * 2;
+    if witness.len() < digit_count * 2 {
+        return None;
+    }
+
+    let digits = (0..digit_count)
+        .map(|i| {
+            let digit_bytes = &witness[2 * (digit_count - 1 - i) + 1];
+            if digit_bytes.len() > 1
+                || digit_bytes
+                    .first()
+                    .is_some_and(|digit| *digit >> LOG_D != 0)
+            {
+                return None;
+            }
+            Some(digit_bytes.first().copied().unwrap_or(0))
+        })
+        .collect::<Option<Vec<u8>>>()?;
+
+    Some(
+        digits
+            .chunks(2)
+            .map(|pair| pair[0] | (pair[1] << LOG_D))
+            .collect(),
+    )
+}
+
 pub fn winternitz_message_checksig(public_key: &WinternitzPublicKey) -> Script {
     WINTERNITZ_MESSAGE_VERIFIER.checksig_verify(&public_key.parameters, &public_key.public_key)
 }
@@ -171,6 +223,43 @@ mod tests {
         assert!(result.success);
     }
 
+    #[test]
+    fn test_winternitz_message_from_witness() {
+        let message = vec![0x00, 0x7f, 0x80, 0xff, 0x12];
+        let secret = WinternitzSecret::new(message.len());
+
+        let witness = generate_winternitz_witness(&WinternitzSigningInputs {
+            message: &message,
+            signing_key: &secret,
+        })
+        .to_vec();
+
+        assert_eq!(
+            winternitz_message_from_witness(&witness, message.len()),
+            Some(message.clone())
+        );
+        assert_eq!(
+            winternitz_message_from_witness(&witness[..message.len() * 4 - 1], message.len()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_winternitz_secret_debug_is_redacted() {
+        let secret = WinternitzSecret::new(4);
+        let debug = format!("{:?}", secret);
+
+        assert!(debug.contains("secret_key_length: 40"));
+        assert!(!debug.contains(&String::from_utf8(secret.secret_key.clone()).unwrap()));
+        assert!(!debug.contains(&hex::encode(&secret.secret_key)));
+        assert!(!debug.contains(&format!("{:?}", secret.secret_key)));
+        assert_ne!(
+            debug,
+            format!("{:?}", WinternitzSecret::new(4)),
+            "fingerprints of different secrets should differ"
+        );
+    }
+
     #[test]
     fn test_generate_winternitz_secret_length() {
         // Uses an arbitrary message size of 1
@@ -1,8 +1,14 @@
-use std::borrow::Borrow;
+use std::{
+    borrow::Borrow,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
 
-use bitcoin::{Amount, OutPoint, PubkeyHash, PublicKey};
+use bitcoin::{Address, Amount, Network, OutPoint, PubkeyHash, PublicKey, ScriptBuf};
 use serde::{Deserialize, Serialize};
 
+use crate::scripts::generate_pay_to_pubkey_hash_with_inscription_script_address;
+
 use super::{
     base::ChainAdaptor,
     ethereum::{EthereumAdaptor, EthereumInitConfig},
@@ -20,6 +26,79 @@ pub struct PegOutEvent {
     pub tx_hash: Vec<u8>,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum PegOutDestinationError {
+    InvalidAddress(String), // destination could not be parsed as a bitcoin address
+    WrongNetwork(String),   // destination is not valid for the graph network
+    NotPayToPubkeyHash,     // destination does not commit to a public key hash
+    PubkeyHashMismatch,     // destination differs from the event's public key hash
+}
+
+impl Display for PegOutDestinationError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            PegOutDestinationError::InvalidAddress(address) => {
+                write!(f, "Invalid peg-out destination address {address}")
+            }
+            PegOutDestinationError::WrongNetwork(address) => {
+                write!(
+                    f,
+                    "Peg-out destination address {address} is for another network"
+                )
+            }
+            PegOutDestinationError::NotPayToPubkeyHash => {
+                write!(f, "Peg-out destination address is not a P2PKH address")
+            }
+            PegOutDestinationError::PubkeyHashMismatch => {
+                write!(
+                    f,
+                    "Peg-out destination address does not match the withdrawer public key hash"
+                )
+            }
+        }
+    }
+}
+
+impl PegOutEvent {
+    pub fn destination_address(&self, network: Network) -> Result<Address, PegOutDestinationError> {
+        let address = Address::from_str(&self.withdrawer_destination_address)
+            .map_err(|_| {
+                PegOutDestinationError::InvalidAddress(self.withdrawer_destination_address.clone())
+            })?
+            .require_network(network)
+            .map_err(|_| {
+                PegOutDestinationError::WrongNetwork(self.withdrawer_destination_address.clone())
+            })?;
+
+        match address.pubkey_hash() {
+            Some(public_key_hash) if public_key_hash == self.withdrawer_public_key_hash => {
+                Ok(address)
+            }
+            Some(_) => Err(PegOutDestinationError::PubkeyHashMismatch),
+            None => Err(PegOutDestinationError::NotPayToPubkeyHash),
+        }
+    }
+
+    // The peg-out tx does not pay the destination address directly, but a script
+    // spendable by the same key that also inscribes the withdrawer's chain address.
+    pub fn payout_script_pubkey(
+        &self,
+        network: Network,
+    ) -> Result<ScriptBuf, PegOutDestinationError> {
+        let destination_address = self.destination_address(network)?;
+
+        Ok(generate_pay_to_pubkey_hash_with_inscription_script_address(
+            network,
+            &destination_address
+                .pubkey_hash()
+                .expect("Destination address is P2PKH"),
+            self.timestamp,
+            &self.withdrawer_chain_address,
+        )
+        .script_pubkey())
+    }
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Debug)]
 pub struct PegOutBurntEvent {
     pub withdrawer_chain_address: String,
@@ -10,8 +10,8 @@ use std::{
 };
 
 use bitcoin::{
-    absolute::Height, consensus::encode::serialize_hex, Address, Amount, Network, OutPoint,
-    PublicKey, ScriptBuf, Txid, XOnlyPublicKey,
+    absolute::Height, address::NetworkUnchecked, block::Header, consensus::encode::serialize_hex,
+    Address, Amount, Network, OutPoint, PublicKey, ScriptBuf, Txid, XOnlyPublicKey,
 };
 use esplora_client::{AsyncClient, Builder, TxStatus, Utxo};
 
@@ -19,14 +19,20 @@ use crate::{
     constants::DestinationNetwork,
     contexts::base::generate_n_of_n_public_key,
     graphs::{
-        base::{get_tx_statuses, GraphId},
+        base::{get_block_height, get_tx_statuses, GraphId},
         peg_in::{PegInDepositorStatus, PegInVerifierStatus},
-        peg_out::{CommitmentMessageId, PegOutOperatorStatus},
+        peg_out::{
+            AssertChainBroadcastError, ChallengeError, CommitmentMessageId, PegOutOperatorStatus,
+            TimeoutError, DEFAULT_MIN_CONFIRMATIONS,
+        },
     },
     scripts::generate_pay_to_pubkey_script_address,
+    superblock::{get_start_time_block_number, SuperblockCriteria},
 };
 
-use bitvm::signatures::signing_winternitz::WinternitzSecret;
+use bitvm::{
+    chunker::disprove_execution::RawProof, signatures::signing_winternitz::WinternitzSecret,
+};
 
 use super::{
     super::{
@@ -37,7 +43,7 @@ use super::{
         graphs::{
             base::BaseGraph,
             peg_in::{generate_id as peg_in_generate_id, PegInGraph},
-            peg_out::{generate_id as peg_out_generate_id, PegOutGraph},
+            peg_out::{generate_id as peg_out_generate_id, PegOutGraph, PegOutGraphConfig},
         },
         serialization::{serialize, try_deserialize},
         transactions::{
@@ -91,10 +97,15 @@ pub struct BitVMClientPrivateData {
     // Operator public key -> Graph ID -> Message ID -> Winternitz secret
     pub commitment_secrets:
         HashMap<PublicKey, HashMap<String, HashMap<CommitmentMessageId, WinternitzSecret>>>,
+    // Default destination for the challenge, timeout and disprove rewards of all the
+    // peg-out graphs, applied to each graph as it is created or loaded.
+    #[serde(default)]
+    pub reward_address: Option<Address<NetworkUnchecked>>,
 }
 
 pub struct BitVMClient {
     pub esplora: AsyncClient,
+    source_network: Network,
 
     depositor_context: Option<DepositorContext>,
     operator_context: Option<OperatorContext>,
@@ -134,20 +145,18 @@ impl BitVMClient {
 
         let mut operator_context = None;
         if operator_secret.is_some() {
-            operator_context = Some(OperatorContext::new(
-                source_network,
-                operator_secret.unwrap(),
-                n_of_n_public_keys,
-            ));
+            operator_context = Some(
+                OperatorContext::new(source_network, operator_secret.unwrap(), n_of_n_public_keys)
+                    .unwrap_or_else(|err| panic!("{err}")),
+            );
         }
 
         let mut verifier_context = None;
         if verifier_secret.is_some() {
-            verifier_context = Some(VerifierContext::new(
-                source_network,
-                verifier_secret.unwrap(),
-                n_of_n_public_keys,
-            ));
+            verifier_context = Some(
+                VerifierContext::new(source_network, verifier_secret.unwrap(), n_of_n_public_keys)
+                    .unwrap_or_else(|err| panic!("{err}")),
+            );
         }
 
         let mut withdrawer_context = None;
@@ -184,6 +193,7 @@ impl BitVMClient {
             esplora: Builder::new(ESPLORA_URL)
                 .build_async()
                 .expect("Could not build esplora client"),
+            source_network,
 
             depositor_context,
             operator_context,
@@ -564,6 +574,35 @@ impl BitVMClient {
         for graph in peg_out_graphs_to_add.into_iter() {
             self.data.peg_out_graphs.push(graph.clone());
         }
+
+        self.apply_reward_address();
+    }
+
+    // Sets the default reward destination of all the peg-out graphs, see
+    // `PegOutGraph::set_reward_address`. It is kept in the private data, as the
+    // graphs are shared without it.
+    pub fn set_reward_address(&mut self, address: Address<NetworkUnchecked>) -> Result<(), String> {
+        if !address.is_valid_for_network(self.source_network) {
+            return Err(format!(
+                "Reward address is not valid for network {}",
+                self.source_network
+            ));
+        }
+
+        self.private_data.reward_address = Some(address);
+        Self::save_local_private_file(&self.file_path, &serialize(&self.private_data));
+        self.apply_reward_address();
+        Ok(())
+    }
+
+    fn apply_reward_address(&mut self) {
+        if let Some(address) = &self.private_data.reward_address {
+            for peg_out_graph in self.data.peg_out_graphs.iter_mut() {
+                if let Err(err) = peg_out_graph.set_reward_address(address.clone()) {
+                    println!("Error: {}", err);
+                }
+            }
+        }
     }
 
     // fn process(&self) {
@@ -591,6 +630,15 @@ impl BitVMClient {
     //     }
     // }
 
+    // Depth the operator of this client counts a peg-out graph as settled at.
+    fn min_confirmations(&self) -> u32 {
+        self.operator_context
+            .as_ref()
+            .map_or(DEFAULT_MIN_CONFIRMATIONS, |context| {
+                context.min_confirmations
+            })
+    }
+
     pub async fn status(&self) {
         if self.depositor_context.is_some() {
             self.depositor_status().await;
@@ -632,6 +680,7 @@ impl BitVMClient {
         }
 
         let operator_public_key = &self.operator_context.as_ref().unwrap().operator_public_key;
+        let blockchain_height = get_block_height(&self.esplora).await;
         for peg_in_graph in self.data.peg_in_graphs.iter() {
             let peg_out_graph_id = peg_out_generate_id(peg_in_graph, operator_public_key);
             if !peg_out_graphs_by_id.contains_key(&peg_out_graph_id) {
@@ -641,7 +690,13 @@ impl BitVMClient {
                 );
             } else {
                 let peg_out_graph = peg_out_graphs_by_id.get(&peg_out_graph_id).unwrap();
-                let status = peg_out_graph.operator_status(&self.esplora).await;
+                let status = peg_out_graph
+                    .operator_status(
+                        &self.esplora,
+                        Some(blockchain_height),
+                        self.min_confirmations(),
+                    )
+                    .await;
                 println!("Graph id: {} status: {}\n", peg_out_graph.id(), status);
             }
         }
@@ -761,8 +816,15 @@ impl BitVMClient {
 
     pub async fn process_peg_outs(&mut self) {
         let peg_out_graphs = self.get_data().peg_out_graphs.clone();
+        let blockchain_height = get_block_height(&self.esplora).await;
         for peg_out_graph in peg_out_graphs.iter() {
-            let status = peg_out_graph.operator_status(&self.esplora).await;
+            let status = peg_out_graph
+                .operator_status(
+                    &self.esplora,
+                    Some(blockchain_height),
+                    self.min_confirmations(),
+                )
+                .await;
             match status {
                 PegOutOperatorStatus::PegOutStartTimeAvailable => {
                     self.broadcast_start_time(peg_out_graph.id()).await
@@ -774,17 +836,23 @@ impl BitVMClient {
                     self.broadcast_kick_off_1(peg_out_graph.id()).await
                 }
                 PegOutOperatorStatus::PegOutKickOff2Available => {
-                    self.broadcast_kick_off_2(peg_out_graph.id()).await
+                    self.broadcast_kick_off_2_at_height(peg_out_graph.id(), Some(blockchain_height))
+                        .await
                 }
                 // TODO: uncomment after assert tx are done
-                // PegOutOperatorStatus::PegOutAssertAvailable => {
-                //     self.broadcast_assert(peg_out_graph.id()).await
+                // PegOutOperatorStatus::PegOutAssertInitialAvailable => {
+                //     self.broadcast_assert_initial(peg_out_graph.id()).await
+                // }
+                // PegOutOperatorStatus::PegOutAssertFinalAvailable => {
+                //     self.broadcast_assert_final(peg_out_graph.id()).await
                 // }
                 PegOutOperatorStatus::PegOutTake1Available => {
-                    self.broadcast_take_1(peg_out_graph.id()).await
+                    self.broadcast_take_1_at_height(peg_out_graph.id(), Some(blockchain_height))
+                        .await
                 }
                 PegOutOperatorStatus::PegOutTake2Available => {
-                    self.broadcast_take_2(peg_out_graph.id()).await
+                    self.broadcast_take_2_at_height(peg_out_graph.id(), Some(blockchain_height))
+                        .await
                 }
                 _ => {}
             }
@@ -908,8 +976,10 @@ impl BitVMClient {
         let (peg_out_graph, commitment_secrets) = PegOutGraph::new(
             self.operator_context.as_ref().unwrap(),
             peg_in_graph,
-            kickoff_input,
-        );
+            vec![kickoff_input],
+            PegOutGraphConfig::default(),
+        )
+        .unwrap_or_else(|err| panic!("Failed to create peg out graph: {err}"));
 
         self.private_data.commitment_secrets = HashMap::from([(
             *operator_public_key,
@@ -919,11 +989,17 @@ impl BitVMClient {
 
         self.data.peg_out_graphs.push(peg_out_graph);
         peg_in_graph.peg_out_graphs.push(peg_out_graph_id.clone());
+        self.apply_reward_address();
 
         peg_out_graph_id
     }
 
-    pub async fn broadcast_peg_out(&mut self, peg_out_graph_id: &str, input: Input) {
+    pub async fn broadcast_peg_out(
+        &mut self,
+        peg_out_graph_id: &str,
+        input: Input,
+        signal_rbf: bool,
+    ) {
         let peg_out_graph = self
             .data
             .peg_out_graphs
@@ -940,8 +1016,10 @@ impl BitVMClient {
                     &self.esplora,
                     self.operator_context.as_ref().unwrap(),
                     input,
+                    signal_rbf,
                 )
-                .await;
+                .await
+                .unwrap_or_else(|err| panic!("{err}"));
         }
     }
 
@@ -955,7 +1033,11 @@ impl BitVMClient {
             panic!("Invalid graph id");
         }
 
-        peg_out_graph.unwrap().peg_out_confirm(&self.esplora).await;
+        peg_out_graph
+            .unwrap()
+            .peg_out_confirm(&self.esplora)
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
     }
 
     pub async fn broadcast_kick_off_1(&mut self, peg_out_graph_id: &str) {
@@ -981,7 +1063,8 @@ impl BitVMClient {
                         [&self.operator_context.as_ref().unwrap().operator_public_key]
                         [peg_out_graph_id][&CommitmentMessageId::PegOutTxIdDestinationNetwork],
                 )
-                .await;
+                .await
+                .unwrap_or_else(|err| panic!("{err}"));
         }
     }
 
@@ -1005,15 +1088,16 @@ impl BitVMClient {
                         [&self.operator_context.as_ref().unwrap().operator_public_key]
                         [peg_out_graph_id][&CommitmentMessageId::StartTime],
                 )
-                .await;
+                .await
+                .unwrap_or_else(|err| panic!("{err}"));
         }
     }
 
     pub async fn broadcast_start_time_timeout(
         &mut self,
         peg_out_graph_id: &str,
-        output_script_pubkey: ScriptBuf,
-    ) {
+        output_script_pubkey: Option<ScriptBuf>,
+    ) -> Result<(), TimeoutError> {
         let peg_out_graph = self
             .data
             .peg_out_graphs
@@ -1025,11 +1109,20 @@ impl BitVMClient {
 
         peg_out_graph
             .unwrap()
-            .start_time_timeout(&self.esplora, output_script_pubkey)
-            .await;
+            .start_time_timeout(&self.esplora, None, output_script_pubkey)
+            .await
     }
 
     pub async fn broadcast_kick_off_2(&mut self, peg_out_graph_id: &str) {
+        self.broadcast_kick_off_2_at_height(peg_out_graph_id, None)
+            .await;
+    }
+
+    async fn broadcast_kick_off_2_at_height(
+        &mut self,
+        peg_out_graph_id: &str,
+        blockchain_height: Option<u32>,
+    ) {
         let peg_out_graph = self
             .data
             .peg_out_graphs
@@ -1039,10 +1132,12 @@ impl BitVMClient {
             panic!("Invalid graph id");
         }
 
+        let peg_out_graph = peg_out_graph.unwrap();
+        let superblock_params = peg_out_graph.superblock_params();
         peg_out_graph
-            .unwrap()
             .kick_off_2(
                 &self.esplora,
+                blockchain_height,
                 self.operator_context.as_ref().unwrap(),
                 &self.private_data.commitment_secrets
                     [&self.operator_context.as_ref().unwrap().operator_public_key]
@@ -1050,15 +1145,20 @@ impl BitVMClient {
                 &self.private_data.commitment_secrets
                     [&self.operator_context.as_ref().unwrap().operator_public_key]
                     [peg_out_graph_id][&CommitmentMessageId::SuperblockHash],
+                &SuperblockCriteria::new(
+                    &superblock_params,
+                    get_start_time_block_number(&superblock_params),
+                ),
             )
-            .await;
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
     }
 
     pub async fn broadcast_kick_off_timeout(
         &mut self,
         peg_out_graph_id: &str,
-        output_script_pubkey: ScriptBuf,
-    ) {
+        output_script_pubkey: Option<ScriptBuf>,
+    ) -> Result<(), TimeoutError> {
         let peg_out_graph = self
             .data
             .peg_out_graphs
@@ -1070,16 +1170,19 @@ impl BitVMClient {
 
         peg_out_graph
             .unwrap()
-            .kick_off_timeout(&self.esplora, output_script_pubkey)
-            .await;
+            .kick_off_timeout(&self.esplora, None, output_script_pubkey)
+            .await
     }
 
+    // With `signal_rbf` the challenge tx can be fee bumped through
+    // `PegOutGraph::replace_challenge_fee`.
     pub async fn broadcast_challenge(
         &mut self,
         peg_out_graph_id: &str,
         crowdfundng_inputs: &Vec<InputWithScript<'_>>,
-        output_script_pubkey: ScriptBuf,
-    ) {
+        output_script_pubkey: Option<ScriptBuf>,
+        signal_rbf: bool,
+    ) -> Result<(), ChallengeError> {
         let peg_out_graph = self
             .data
             .peg_out_graphs
@@ -1097,8 +1200,9 @@ impl BitVMClient {
                     crowdfundng_inputs,
                     &self.depositor_context.as_ref().unwrap().depositor_keypair,
                     output_script_pubkey,
+                    signal_rbf,
                 )
-                .await;
+                .await
         } else if self.operator_context.is_some() {
             peg_out_graph
                 .unwrap()
@@ -1107,8 +1211,9 @@ impl BitVMClient {
                     crowdfundng_inputs,
                     &self.operator_context.as_ref().unwrap().operator_keypair,
                     output_script_pubkey,
+                    signal_rbf,
                 )
-                .await;
+                .await
         } else if self.verifier_context.is_some() {
             peg_out_graph
                 .unwrap()
@@ -1117,8 +1222,9 @@ impl BitVMClient {
                     crowdfundng_inputs,
                     &self.verifier_context.as_ref().unwrap().verifier_keypair,
                     output_script_pubkey,
+                    signal_rbf,
                 )
-                .await;
+                .await
         } else if self.withdrawer_context.is_some() {
             peg_out_graph
                 .unwrap()
@@ -1127,8 +1233,11 @@ impl BitVMClient {
                     crowdfundng_inputs,
                     &self.withdrawer_context.as_ref().unwrap().withdrawer_keypair,
                     output_script_pubkey,
+                    signal_rbf,
                 )
-                .await;
+                .await
+        } else {
+            Ok(())
         }
     }
 
@@ -1142,7 +1251,11 @@ impl BitVMClient {
             panic!("Invalid graph id");
         }
 
-        peg_out_graph.unwrap().assert_initial(&self.esplora).await;
+        peg_out_graph
+            .unwrap()
+            .assert_initial(&self.esplora, None)
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
     }
 
     pub async fn broadcast_assert_final(&mut self, peg_out_graph_id: &str) {
@@ -1155,14 +1268,43 @@ impl BitVMClient {
             panic!("Invalid graph id");
         }
 
-        peg_out_graph.unwrap().assert_final(&self.esplora).await;
+        peg_out_graph
+            .unwrap()
+            .assert_final(&self.esplora)
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    pub async fn broadcast_assert_chain(
+        &mut self,
+        peg_out_graph_id: &str,
+        proof: &RawProof,
+    ) -> Result<Vec<Txid>, AssertChainBroadcastError> {
+        let peg_out_graph = self
+            .data
+            .peg_out_graphs
+            .iter_mut()
+            .find(|peg_out_graph| peg_out_graph.id().eq(peg_out_graph_id));
+        if peg_out_graph.is_none() {
+            panic!("Invalid graph id");
+        }
+
+        peg_out_graph
+            .unwrap()
+            .broadcast_assert_chain(
+                &self.esplora,
+                &self.private_data.commitment_secrets
+                    [&self.operator_context.as_ref().unwrap().operator_public_key]
+                    [peg_out_graph_id],
+                proof,
+            )
+            .await
     }
 
     pub async fn broadcast_disprove(
         &mut self,
         peg_out_graph_id: &str,
-        input_script_index: u32,
-        output_script_pubkey: ScriptBuf,
+        output_script_pubkey: Option<ScriptBuf>,
     ) {
         let peg_out_graph = self
             .data
@@ -1173,16 +1315,22 @@ impl BitVMClient {
             panic!("Invalid graph id");
         }
 
+        let peg_out_graph = peg_out_graph.unwrap();
+        let commit_witnesses = peg_out_graph
+            .fetch_assert_commit_witnesses(&self.esplora)
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
         peg_out_graph
-            .unwrap()
-            .disprove(&self.esplora, input_script_index, output_script_pubkey)
-            .await;
+            .disprove(&self.esplora, commit_witnesses, output_script_pubkey)
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
     }
 
     pub async fn broadcast_disprove_chain(
         &mut self,
         peg_out_graph_id: &str,
-        output_script_pubkey: ScriptBuf,
+        disprove_superblocks: &[Header],
+        output_script_pubkey: Option<ScriptBuf>,
     ) {
         let peg_out_graph = self
             .data
@@ -1195,11 +1343,21 @@ impl BitVMClient {
 
         peg_out_graph
             .unwrap()
-            .disprove_chain(&self.esplora, output_script_pubkey)
-            .await;
+            .disprove_chain(&self.esplora, disprove_superblocks, output_script_pubkey)
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
     }
 
     pub async fn broadcast_take_1(&mut self, peg_out_graph_id: &str) {
+        self.broadcast_take_1_at_height(peg_out_graph_id, None)
+            .await;
+    }
+
+    async fn broadcast_take_1_at_height(
+        &mut self,
+        peg_out_graph_id: &str,
+        blockchain_height: Option<u32>,
+    ) {
         let peg_out_graph = self
             .data
             .peg_out_graphs
@@ -1209,10 +1367,23 @@ impl BitVMClient {
             panic!("Invalid graph id");
         }
 
-        peg_out_graph.unwrap().take_1(&self.esplora).await;
+        peg_out_graph
+            .unwrap()
+            .take_1(&self.esplora, blockchain_height)
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
     }
 
     pub async fn broadcast_take_2(&mut self, peg_out_graph_id: &str) {
+        self.broadcast_take_2_at_height(peg_out_graph_id, None)
+            .await;
+    }
+
+    async fn broadcast_take_2_at_height(
+        &mut self,
+        peg_out_graph_id: &str,
+        blockchain_height: Option<u32>,
+    ) {
         let peg_out_graph = self
             .data
             .peg_out_graphs
@@ -1225,8 +1396,13 @@ impl BitVMClient {
         if self.operator_context.is_some() {
             peg_out_graph
                 .unwrap()
-                .take_2(&self.esplora, self.operator_context.as_ref().unwrap())
-                .await;
+                .take_2(
+                    &self.esplora,
+                    blockchain_height,
+                    self.operator_context.as_ref().unwrap(),
+                )
+                .await
+                .unwrap_or_else(|err| panic!("{err}"));
         }
     }
 
@@ -1352,6 +1528,7 @@ impl BitVMClient {
                 BitVMClientPrivateData {
                     secret_nonces: HashMap::new(),
                     commitment_secrets: HashMap::new(),
+                    reward_address: None,
                 }
             }
         }
@@ -1496,8 +1673,8 @@ impl ClientCliQuery for BitVMClient {
             Some(async move {
                 match peg_in.depositor_status(&self.esplora).await {
                     PegInDepositorStatus::PegInConfirmComplete => match self.data.peg_out_graphs.iter().find(|peg_out| peg_out.peg_in_graph_id == *peg_in.id()) {
-                        Some(peg_out) => match peg_out.operator_status(&self.esplora).await {
-                            PegOutOperatorStatus::PegOutWait => Some(json!({
+                        Some(peg_out) => match peg_out.operator_status(&self.esplora, None, self.min_confirmations()).await {
+                            PegOutOperatorStatus::PegOutWait(_) => Some(json!({
                                 "graph_id": peg_in.id(),
                                 "amount": peg_in.peg_in_confirm_transaction.prev_outs()[0].value.to_sat(),
                                 "source_outpoint": {
@@ -1604,9 +1781,8 @@ impl ClientCliQuery for BitVMClient {
                     false
                 })
                 .map(|graph| async {
-                    let (tx_json_value, tx_status_result) = match &graph.peg_out_transaction {
-                        Some(tx) => {
-                            let txid = tx.tx().compute_txid();
+                    let (tx_json_value, tx_status_result) = match graph.peg_out_txid() {
+                        Some(txid) => {
                             let tx_status_result = self.esplora.get_tx_status(&txid).await;
                             let tx_status = tx_status_result.as_ref().unwrap_or(&TxStatus {
                                 confirmed: false,
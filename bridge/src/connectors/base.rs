@@ -1,6 +1,50 @@
 use bitcoin::{taproot::TaprootSpendInfo, Address, ScriptBuf, Sequence, TxIn, Witness};
+use bitvm::signatures::signing_winternitz::{WinternitzPublicKey, WinternitzSecret};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
 
-use super::super::transactions::base::Input;
+use super::super::{graphs::peg_out::CommitmentMessageId, transactions::base::Input};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WrongCommitmentSecret {
+    pub message_id: CommitmentMessageId,
+}
+
+impl Display for WrongCommitmentSecret {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "No commitment secret for {:?} that matches the connector's public key",
+            self.message_id
+        )
+    }
+}
+
+// A witness signed with a secret from another graph would fail the connector's commitment script.
+pub fn verify_commitment_secret(
+    commitment_public_keys: &HashMap<CommitmentMessageId, WinternitzPublicKey>,
+    message_id: CommitmentMessageId,
+    secret: &WinternitzSecret,
+) -> Result<(), WrongCommitmentSecret> {
+    match commitment_public_keys.get(&message_id) {
+        Some(public_key) => verify_commitment_public_key(public_key, message_id, secret),
+        None => Err(WrongCommitmentSecret { message_id }),
+    }
+}
+
+pub fn verify_commitment_public_key(
+    commitment_public_key: &WinternitzPublicKey,
+    message_id: CommitmentMessageId,
+    secret: &WinternitzSecret,
+) -> Result<(), WrongCommitmentSecret> {
+    if *commitment_public_key != WinternitzPublicKey::from(secret) {
+        return Err(WrongCommitmentSecret { message_id });
+    }
+
+    Ok(())
+}
 
 pub fn generate_default_tx_in(input: &Input) -> TxIn {
     TxIn {
@@ -52,4 +96,72 @@ pub trait TaprootConnector {
     fn generate_taproot_spend_info(&self) -> TaprootSpendInfo;
 
     fn generate_taproot_address(&self) -> Address;
+
+    // Connector leaves are arbitrary tapscripts, which descriptors cannot express,
+    // so the descriptor pins the tweaked output key. That is all a wallet needs to
+    // watch the connector output.
+    fn descriptor(&self) -> String {
+        let output_key = self.generate_taproot_spend_info().output_key();
+        add_descriptor_checksum(&format!("rawtr({})", output_key.to_inner()))
+    }
+}
+
+const DESCRIPTOR_INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const DESCRIPTOR_CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn descriptor_polymod(c: u64, value: u64) -> u64 {
+    const GENERATOR: [u64; 5] = [
+        0xf5dee51989,
+        0xa9fdca3312,
+        0x1bab10e32d,
+        0x3706b1677a,
+        0x644d626ffd,
+    ];
+
+    let c0 = c >> 35;
+    let mut c = ((c & 0x7ffffffff) << 5) ^ value;
+    for (i, generator) in GENERATOR.iter().enumerate() {
+        if (c0 >> i) & 1 == 1 {
+            c ^= generator;
+        }
+    }
+    c
+}
+
+// Descriptor checksum as specified in BIP-380. Panics on characters outside
+// the descriptor character set.
+pub fn descriptor_checksum(descriptor: &str) -> String {
+    let mut c = 1;
+    let mut class = 0;
+    let mut class_count = 0;
+    for ch in descriptor.chars() {
+        let position = DESCRIPTOR_INPUT_CHARSET
+            .find(ch)
+            .unwrap_or_else(|| panic!("Invalid descriptor character {ch:?}"))
+            as u64;
+        c = descriptor_polymod(c, position & 31);
+        class = class * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = descriptor_polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = descriptor_polymod(c, class);
+    }
+    for _ in 0..8 {
+        c = descriptor_polymod(c, 0);
+    }
+    c ^= 1;
+
+    (0..8)
+        .map(|j| DESCRIPTOR_CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char)
+        .collect()
+}
+
+pub fn add_descriptor_checksum(descriptor: &str) -> String {
+    format!("{descriptor}#{}", descriptor_checksum(descriptor))
 }
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     graphs::peg_out::CommitmentMessageId,
-    superblock::{SUPERBLOCK_HASH_MESSAGE_LENGTH, SUPERBLOCK_MESSAGE_LENGTH},
+    superblock::{SuperblockPolicy, SUPERBLOCK_HASH_MESSAGE_LENGTH},
 };
 
 use bitvm::signatures::signing_winternitz::{
@@ -36,6 +36,8 @@ pub struct Connector1 {
     pub num_blocks_timelock_leaf_0: u32,
     pub num_blocks_timelock_leaf_1: u32,
     pub num_blocks_timelock_leaf_2: u32,
+    #[serde(default)]
+    pub superblock_policy: SuperblockPolicy,
 }
 
 impl Connector1 {
@@ -44,6 +46,7 @@ impl Connector1 {
         operator_taproot_public_key: &XOnlyPublicKey,
         n_of_n_taproot_public_key: &XOnlyPublicKey,
         commitment_public_keys: &HashMap<CommitmentMessageId, WinternitzPublicKey>,
+        superblock_policy: SuperblockPolicy,
     ) -> Self {
         Connector1 {
             network,
@@ -56,6 +59,7 @@ impl Connector1 {
                 NUM_BLOCKS_PER_2_WEEKS + NUM_BLOCKS_PER_DAY,
             ),
             num_blocks_timelock_leaf_2: num_blocks_per_network(network, NUM_BLOCKS_PER_6_HOURS),
+            superblock_policy,
         }
     }
 
@@ -66,7 +70,7 @@ impl Connector1 {
 
         script! {
             { winternitz_message_checksig_verify(superblock_hash_public_key, SUPERBLOCK_HASH_MESSAGE_LENGTH * 2) }
-            { winternitz_message_checksig_verify(superblock_public_key, SUPERBLOCK_MESSAGE_LENGTH * 2) }
+            { winternitz_message_checksig_verify(superblock_public_key, self.superblock_policy.message_length() * 2) }
             { self.num_blocks_timelock_leaf_0 }
             OP_CSV
             OP_DROP
@@ -1,6 +1,8 @@
-use std::collections::BTreeMap;
+use std::{cell::OnceCell, collections::BTreeMap};
 
 use crate::graphs::peg_out::CommitmentMessageId;
+#[cfg(feature = "minimal-graph")]
+use crate::graphs::peg_out::MINIMAL_GROTH16_VARIABLES;
 
 use bitvm::{
     chunker::{
@@ -8,7 +10,10 @@ use bitvm::{
         chunk_groth16_verifier::groth16_verify_to_segments,
         common::RawWitness,
         disprove_execution::{disprove_exec, RawProof},
+        elements::ElementError,
+        segment::Segment,
     },
+    execute_raw_script_with_inputs,
     signatures::signing_winternitz::WinternitzPublicKey,
     treepp::script,
 };
@@ -23,7 +28,13 @@ use num_traits::ToPrimitive;
 use secp256k1::SECP256K1;
 use serde::{Deserialize, Serialize};
 
-use super::{super::transactions::base::Input, base::*};
+use super::{
+    super::{
+        serialization::{deserialize_map_from_pairs, serialize_map_as_pairs},
+        transactions::base::Input,
+    },
+    base::*,
+};
 
 // Specialized for assert leaves currently.
 pub type LockScript = fn(index: u32) -> ScriptBuf;
@@ -35,12 +46,49 @@ pub struct DisproveLeaf {
     pub unlock: UnlockWitness,
 }
 
+// Chunker segments the assert leaves are built from, generated on first use. They
+// only depend on the vk, so connectors of graphs with the same vk can share them.
+pub struct AssertSegments {
+    vk: VerifyingKey<ark_bn254::Bn254>,
+    segments: OnceCell<Vec<Segment>>,
+}
+
+impl AssertSegments {
+    pub fn new(vk: &VerifyingKey<ark_bn254::Bn254>) -> Self {
+        AssertSegments {
+            vk: vk.clone(),
+            segments: OnceCell::new(),
+        }
+    }
+
+    pub fn vk(&self) -> &VerifyingKey<ark_bn254::Bn254> { &self.vk }
+
+    fn segments(&self) -> &[Segment] {
+        self.segments.get_or_init(|| {
+            let default_proof = RawProof::default(); // mock a default proof to generate scripts
+
+            // the leaves only depend on the vk, the proof and public inputs are placeholders
+            groth16_verify_to_segments(
+                &mut BridgeAssigner::new_variable_tracer(),
+                &default_proof.public,
+                &default_proof.proof,
+                &self.vk,
+            )
+            .expect("Failed to build the assert segments for the verifying key")
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct ConnectorC {
     pub network: Network,
     pub operator_taproot_public_key: XOnlyPublicKey,
     lock_scripts: Vec<ScriptBuf>,
     // unlock_witnesses: Vec<UnlockWitnessData>,
+    #[serde(
+        serialize_with = "serialize_map_as_pairs",
+        deserialize_with = "deserialize_map_from_pairs"
+    )]
     commitment_public_keys: BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
 }
 
@@ -49,8 +97,23 @@ impl ConnectorC {
         network: Network,
         operator_taproot_public_key: &XOnlyPublicKey,
         commitment_public_keys: &BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
+        vk: &VerifyingKey<ark_bn254::Bn254>,
     ) -> Self {
-        let leaves = generate_assert_leaves(commitment_public_keys);
+        Self::new_with_segments(
+            network,
+            operator_taproot_public_key,
+            commitment_public_keys,
+            &AssertSegments::new(vk),
+        )
+    }
+
+    pub fn new_with_segments(
+        network: Network,
+        operator_taproot_public_key: &XOnlyPublicKey,
+        commitment_public_keys: &BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
+        assert_segments: &AssertSegments,
+    ) -> Self {
+        let leaves = generate_assert_leaves(commitment_public_keys, assert_segments);
 
         ConnectorC {
             network,
@@ -61,12 +124,16 @@ impl ConnectorC {
         }
     }
 
+    pub fn commitment_public_keys(&self) -> &BTreeMap<CommitmentMessageId, WinternitzPublicKey> {
+        &self.commitment_public_keys
+    }
+
     pub fn generate_disprove_witness(
         &self,
         commit_1_witness: Vec<RawWitness>,
         commit_2_witness: Vec<RawWitness>,
         vk: VerifyingKey<ark_bn254::Bn254>,
-    ) -> Option<(usize, RawWitness)> {
+    ) -> Result<Option<(usize, RawWitness)>, ElementError> {
         let pks = self
             .commitment_public_keys
             .clone()
@@ -85,6 +152,18 @@ impl ConnectorC {
         // merge commit1 and commit2
         disprove_exec(&mut assigner, vec![commit_1_witness, commit_2_witness], vk)
     }
+
+    // Runs the leaf against the witness locally, as the disprove tx is only
+    // accepted if the witness makes the leaf succeed.
+    pub fn verify_disprove_witness(&self, script_index: u32, witness: &[Vec<u8>]) -> bool {
+        let index = script_index.to_usize().unwrap();
+        match self.lock_scripts.get(index) {
+            Some(lock_script) => {
+                execute_raw_script_with_inputs(lock_script.to_bytes(), witness.to_vec()).success
+            }
+            None => false,
+        }
+    }
 }
 
 impl TaprootConnector for ConnectorC {
@@ -140,7 +219,16 @@ fn disprove_leaf() -> DisproveLeaf {
 
 fn generate_assert_leaves(
     commits_public_key: &BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
+    assert_segments: &AssertSegments,
 ) -> Vec<ScriptBuf> {
+    // a minimal graph cannot run the chunker, stub leaves keep the connector spendable
+    #[cfg(feature = "minimal-graph")]
+    if is_minimal_commitment(commits_public_key) {
+        return (0..commits_public_key.len() as u32)
+            .map(disprove_leaf().lock)
+            .collect();
+    }
+
     // hash map to btree map
     let pks = commits_public_key
         .clone()
@@ -155,19 +243,26 @@ fn generate_assert_leaves(
             )
         })
         .collect();
-    let mut bridge_assigner = BridgeAssigner::new_watcher(pks);
-    let default_proof = RawProof::default(); // mock a default proof to generate scripts
-
-    let segments = groth16_verify_to_segments(
-        &mut bridge_assigner,
-        &default_proof.public,
-        &default_proof.proof,
-        &default_proof.vk,
-    );
+    let bridge_assigner = BridgeAssigner::new_watcher(pks);
 
     let mut locks = Vec::with_capacity(1000);
-    for segment in segments {
+    for segment in assert_segments.segments() {
         locks.push(segment.script(&bridge_assigner).compile());
     }
     locks
 }
+
+#[cfg(feature = "minimal-graph")]
+fn is_minimal_commitment(
+    commits_public_key: &BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
+) -> bool {
+    commits_public_key.len() == MINIMAL_GROTH16_VARIABLES.len()
+        && commits_public_key
+            .keys()
+            .all(|message_id| match message_id {
+                CommitmentMessageId::Groth16IntermediateValues((name, _)) => {
+                    MINIMAL_GROTH16_VARIABLES.contains(&name.as_str())
+                }
+                _ => false,
+            })
+}
@@ -1,5 +1,9 @@
 use super::{
-    super::{scripts::*, transactions::base::Input},
+    super::{
+        scripts::*,
+        serialization::{deserialize_map_from_pairs, serialize_map_as_pairs},
+        transactions::base::Input,
+    },
     base::*,
 };
 use crate::graphs::peg_out::CommitmentMessageId;
@@ -19,6 +23,10 @@ use std::collections::BTreeMap;
 pub struct ConnectorE {
     pub network: Network,
     pub operator_public_key: PublicKey,
+    #[serde(
+        serialize_with = "serialize_map_as_pairs",
+        deserialize_with = "deserialize_map_from_pairs"
+    )]
     pub commitment_public_keys: BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
 }
 
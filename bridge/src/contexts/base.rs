@@ -1,9 +1,39 @@
 use bitcoin::{
-    key::Keypair, secp256k1::PublicKey as Secp256k1PublicKey, Network, PrivateKey, PublicKey,
-    XOnlyPublicKey,
+    key::Keypair,
+    secp256k1::{PublicKey as Secp256k1PublicKey, SecretKey},
+    Network, PrivateKey, PublicKey, XOnlyPublicKey,
 };
 use musig2::{secp::Point, KeyAggContext};
 use secp256k1::SECP256K1;
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+// Number of leading zero bytes that make a secret key a small scalar, i.e. below 2^64
+const WEAK_SECRET_KEY_ZERO_BYTES: usize = 24;
+// Public keys among the first multiples of the generator have trivially known secret keys
+const WEAK_PUBLIC_KEY_MULTIPLES: u32 = 256;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum WeakKey {
+    InvalidSecretKey,                // zero, out of range or not hex encoded
+    SmallSecretKey,                  // the secret key or its negation is a small scalar
+    SmallPublicKey { index: usize }, // n-of-n public key is a small multiple of the generator
+}
+
+impl Display for WeakKey {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            WeakKey::InvalidSecretKey => write!(f, "Secret key is not a valid scalar"),
+            WeakKey::SmallSecretKey => write!(f, "Secret key is a small scalar"),
+            WeakKey::SmallPublicKey { index } => write!(
+                f,
+                "n-of-n public key {index} is a small multiple of the generator"
+            ),
+        }
+    }
+}
 
 pub trait BaseContext {
     fn network(&self) -> Network;
@@ -20,6 +50,38 @@ pub fn generate_keys_from_secret(network: Network, secret: &str) -> (Keypair, Pu
     (keypair, public_key)
 }
 
+// Cheap sanity check of the keys a context is built from. An uninitialized buffer
+// or a test key would otherwise build a graph that anyone can spend from.
+pub fn check_keys(secret: &str, n_of_n_public_keys: &[PublicKey]) -> Result<(), WeakKey> {
+    let secret_key = SecretKey::from_str(secret).map_err(|_| WeakKey::InvalidSecretKey)?;
+    let is_small = |secret_key: &SecretKey| {
+        secret_key.secret_bytes()[..WEAK_SECRET_KEY_ZERO_BYTES]
+            .iter()
+            .all(|byte| *byte == 0)
+    };
+    if is_small(&secret_key) || is_small(&secret_key.negate()) {
+        return Err(WeakKey::SmallSecretKey);
+    }
+
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let generator = SecretKey::from_slice(&one).unwrap().public_key(SECP256K1);
+    let mut multiple = generator;
+    for _ in 0..WEAK_PUBLIC_KEY_MULTIPLES {
+        // comparing x-only keys covers the negated multiples as well
+        let (x_only_multiple, _) = multiple.x_only_public_key();
+        if let Some(index) = n_of_n_public_keys
+            .iter()
+            .position(|public_key| public_key.inner.x_only_public_key().0 == x_only_multiple)
+        {
+            return Err(WeakKey::SmallPublicKey { index });
+        }
+        multiple = multiple.combine(&generator).unwrap();
+    }
+
+    Ok(())
+}
+
 pub fn generate_n_of_n_public_key(n_of_n_public_keys: &[PublicKey]) -> (PublicKey, XOnlyPublicKey) {
     let public_keys: Vec<Point> = n_of_n_public_keys
         .iter()
@@ -1,6 +1,15 @@
+use std::sync::Arc;
+
 use bitcoin::{key::Keypair, Network, PublicKey, XOnlyPublicKey};
 
-use super::base::{generate_keys_from_secret, generate_n_of_n_public_key, BaseContext};
+use crate::graphs::{
+    observer::{GraphObserver, NoopObserver},
+    peg_out::DEFAULT_MIN_CONFIRMATIONS,
+};
+
+use super::base::{
+    check_keys, generate_keys_from_secret, generate_n_of_n_public_key, BaseContext, WeakKey,
+};
 
 pub struct OperatorContext {
     pub network: Network,
@@ -12,6 +21,9 @@ pub struct OperatorContext {
     pub n_of_n_public_keys: Vec<PublicKey>,
     pub n_of_n_public_key: PublicKey,
     pub n_of_n_taproot_public_key: XOnlyPublicKey,
+
+    pub observer: Arc<dyn GraphObserver>, // told about the graphs driven with this context
+    pub min_confirmations: u32,           // depth at which the operator counts a graph as settled
 }
 
 impl BaseContext for OperatorContext {
@@ -22,12 +34,18 @@ impl BaseContext for OperatorContext {
 }
 
 impl OperatorContext {
-    pub fn new(network: Network, operator_secret: &str, n_of_n_public_keys: &[PublicKey]) -> Self {
+    pub fn new(
+        network: Network,
+        operator_secret: &str,
+        n_of_n_public_keys: &[PublicKey],
+    ) -> Result<Self, WeakKey> {
+        check_keys(operator_secret, n_of_n_public_keys)?;
+
         let (keypair, public_key) = generate_keys_from_secret(network, operator_secret);
         let (n_of_n_public_key, n_of_n_taproot_public_key) =
             generate_n_of_n_public_key(n_of_n_public_keys);
 
-        OperatorContext {
+        Ok(OperatorContext {
             network,
 
             operator_keypair: keypair,
@@ -37,6 +55,9 @@ impl OperatorContext {
             n_of_n_public_keys: n_of_n_public_keys.to_owned(),
             n_of_n_public_key,
             n_of_n_taproot_public_key,
-        }
+
+            observer: Arc::new(NoopObserver),
+            min_confirmations: DEFAULT_MIN_CONFIRMATIONS,
+        })
     }
 }
@@ -1,6 +1,8 @@
 use bitcoin::{key::Keypair, Network, PublicKey, XOnlyPublicKey};
 
-use super::base::{generate_keys_from_secret, generate_n_of_n_public_key, BaseContext};
+use super::base::{
+    check_keys, generate_keys_from_secret, generate_n_of_n_public_key, BaseContext, WeakKey,
+};
 
 pub struct VerifierContext {
     pub network: Network,
@@ -21,12 +23,18 @@ impl BaseContext for VerifierContext {
 }
 
 impl VerifierContext {
-    pub fn new(network: Network, verifier_secret: &str, n_of_n_public_keys: &[PublicKey]) -> Self {
+    pub fn new(
+        network: Network,
+        verifier_secret: &str,
+        n_of_n_public_keys: &[PublicKey],
+    ) -> Result<Self, WeakKey> {
+        check_keys(verifier_secret, n_of_n_public_keys)?;
+
         let (keypair, public_key) = generate_keys_from_secret(network, verifier_secret);
         let (n_of_n_public_key, n_of_n_taproot_public_key) =
             generate_n_of_n_public_key(n_of_n_public_keys);
 
-        VerifierContext {
+        Ok(VerifierContext {
             network,
 
             verifier_keypair: keypair,
@@ -35,6 +43,6 @@ impl VerifierContext {
             n_of_n_public_keys: n_of_n_public_keys.to_owned(),
             n_of_n_public_key,
             n_of_n_taproot_public_key,
-        }
+        })
     }
 }
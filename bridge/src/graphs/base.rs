@@ -1,9 +1,15 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    future::Future,
+    time::{Duration, Instant},
+};
 
 use bitcoin::{Network, Transaction, Txid};
 use esplora_client::{AsyncClient, Error, TxStatus};
 use futures::future::join_all;
 use musig2::SecNonce;
+use tokio::time::sleep;
 
 use crate::contexts::verifier::VerifierContext;
 
@@ -68,6 +74,66 @@ pub async fn get_block_height(client: &AsyncClient) -> u32 {
     blockchain_height_result.unwrap()
 }
 
+// Reuses the height from a status evaluation when the caller has one, so that
+// the status and the following action are decided against the same block.
+pub async fn get_block_height_if_none(client: &AsyncClient, blockchain_height: Option<u32>) -> u32 {
+    match blockchain_height {
+        Some(blockchain_height) => blockchain_height,
+        None => get_block_height(client).await,
+    }
+}
+
+// Esplora can transiently report a tx as confirmed before it reports the block
+// it was confirmed in. Only a confirmed status with a block height has a
+// confirmation height, so timelocks are never measured from a guessed height.
+pub fn confirmation_height(tx_status: &Result<TxStatus, Error>) -> Option<u32> {
+    tx_status
+        .as_ref()
+        .ok()
+        .filter(|status| status.confirmed)
+        .and_then(|status| status.block_height)
+}
+
+// Blocks from the one a tx was confirmed in up to the tip, both included. 0 while
+// it is unconfirmed or its confirmation height is not known yet.
+pub fn tx_confirmations(tx_status: &Result<TxStatus, Error>, tip_height: u32) -> u32 {
+    confirmation_height(tx_status).map_or(0, |block_height| {
+        (tip_height + 1).saturating_sub(block_height)
+    })
+}
+
+pub async fn confirmations(client: &AsyncClient, txid: Txid, tip_height: u32) -> u32 {
+    tx_confirmations(&client.get_tx_status(&txid).await, tip_height)
+}
+
+// A confirmed tx without a confirmation height is not waiting on anything, its
+// status has to be fetched again before timelocks on it can be evaluated.
+pub fn is_confirmed_without_height(tx_status: &Result<TxStatus, Error>) -> bool {
+    tx_status
+        .as_ref()
+        .is_ok_and(|status| status.confirmed && status.block_height.is_none())
+}
+
+pub fn is_timelock_elapsed(
+    tx_status: &Result<TxStatus, Error>,
+    num_blocks_timelock: u32,
+    blockchain_height: u32,
+) -> bool {
+    confirmation_height(tx_status)
+        .is_some_and(|block_height| block_height + num_blocks_timelock <= blockchain_height)
+}
+
+// Blocks left until the timelock on the outputs of a confirmed tx elapses, `None`
+// while the tx has no confirmation height.
+pub fn timelock_remaining_blocks(
+    tx_status: &Result<TxStatus, Error>,
+    num_blocks_timelock: u32,
+    blockchain_height: u32,
+) -> Option<u32> {
+    confirmation_height(tx_status)
+        .map(|block_height| (block_height + num_blocks_timelock).saturating_sub(blockchain_height))
+}
+
 pub async fn verify_if_not_mined(client: &AsyncClient, txid: Txid) {
     if is_confirmed(client, txid).await {
         panic!("Transaction already mined!");
@@ -82,22 +148,72 @@ pub async fn is_confirmed(client: &AsyncClient, txid: Txid) -> bool {
 }
 
 pub async fn broadcast_and_verify(client: &AsyncClient, transaction: &Transaction) {
+    try_broadcast_and_verify(client, transaction)
+        .await
+        .unwrap_or_else(|err| panic!("{err}"));
+}
+
+// Like `broadcast_and_verify`, but leaves a failed broadcast to the caller.
+pub async fn try_broadcast_and_verify(
+    client: &AsyncClient,
+    transaction: &Transaction,
+) -> Result<(), String> {
     let txid = transaction.compute_txid();
 
     if let Ok(Some(_)) = client.get_tx(&txid).await {
         println!("Tx already submitted.");
-        return;
+        return Ok(());
     }
 
     let tx_result = client.broadcast(transaction).await;
 
-    if tx_result.is_ok() || is_confirmed(client, txid).await {
+    let is_confirmed = client
+        .get_tx_status(&txid)
+        .await
+        .is_ok_and(|status| status.confirmed);
+    if tx_result.is_ok() || is_confirmed {
         println!("Tx mined successfully.");
+        Ok(())
     } else {
-        panic!("Error occurred {:?}", tx_result);
+        Err(format!("Error occurred {:?}", tx_result))
     }
 }
 
 pub async fn get_tx_statuses(client: &AsyncClient, txids: &[Txid]) -> Vec<Result<TxStatus, Error>> {
     join_all(txids.iter().map(|txid| client.get_tx_status(txid))).await
 }
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Timeout;
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Deadline passed before the status was reached")
+    }
+}
+
+// Polls a status until `target` matches it. The status is polled one last time
+// at the deadline, so a status reached while sleeping is not reported as late.
+pub async fn wait_for_status<S, F, Fut>(
+    mut poll_status: F,
+    target: fn(&S) -> bool,
+    poll_interval: Duration,
+    deadline: Instant,
+) -> Result<S, Timeout>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = S>,
+{
+    loop {
+        let status = poll_status().await;
+        if target(&status) {
+            return Ok(status);
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(Timeout);
+        }
+        sleep(poll_interval.min(deadline - now)).await;
+    }
+}
@@ -0,0 +1,85 @@
+use bitcoin::Txid;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// The txs of a peg-out graph, plus the peg-in confirm tx it spends from.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+pub enum TxRole {
+    PegInConfirm,
+    PegOutConfirm,
+    KickOff1,
+    StartTime,
+    StartTimeTimeout,
+    KickOff2,
+    KickOffTimeout,
+    Challenge,
+    Take1,
+    AssertInitial,
+    AssertCommit1,
+    AssertCommit2,
+    AssertFinal,
+    Take2,
+    Disprove,
+    DisproveChain,
+}
+
+impl TxRole {
+    pub fn tx_name(&self) -> &'static str {
+        match self {
+            TxRole::PegInConfirm => "peg_in_confirm",
+            TxRole::PegOutConfirm => "peg_out_confirm",
+            TxRole::KickOff1 => "kick_off_1",
+            TxRole::StartTime => "start_time",
+            TxRole::StartTimeTimeout => "start_time_timeout",
+            TxRole::KickOff2 => "kick_off_2",
+            TxRole::KickOffTimeout => "kick_off_timeout",
+            TxRole::Challenge => "challenge",
+            TxRole::Take1 => "take_1",
+            TxRole::AssertInitial => "assert_initial",
+            TxRole::AssertCommit1 => "assert_commit_1",
+            TxRole::AssertCommit2 => "assert_commit_2",
+            TxRole::AssertFinal => "assert_final",
+            TxRole::Take2 => "take_2",
+            TxRole::Disprove => "disprove",
+            TxRole::DisproveChain => "disprove_chain",
+        }
+    }
+
+    // Txs completed by whoever broadcasts them, with further inputs and outputs or
+    // the destination of the reward, so the tx on chain does not reproduce the txid.
+    pub fn is_completed_on_broadcast(&self) -> bool {
+        matches!(
+            self,
+            TxRole::Challenge
+                | TxRole::StartTimeTimeout
+                | TxRole::KickOffTimeout
+                | TxRole::Disprove
+                | TxRole::DisproveChain
+        )
+    }
+}
+
+// Input `to_input` of `to` spends output `from_vout` of `from`.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Copy)]
+pub struct TxEdge {
+    pub from: TxRole,
+    pub from_vout: u32,
+    pub to: TxRole,
+    pub to_input: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct TxDag {
+    pub nodes: BTreeMap<TxRole, Txid>,
+    pub edges: Vec<TxEdge>, // ordered by spending tx role, then input
+}
+
+impl TxDag {
+    pub fn edges_into(&self, to: TxRole) -> impl Iterator<Item = &TxEdge> {
+        self.edges.iter().filter(move |edge| edge.to == to)
+    }
+
+    pub fn edges_from(&self, from: TxRole) -> impl Iterator<Item = &TxEdge> {
+        self.edges.iter().filter(move |edge| edge.from == from)
+    }
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use esplora_client::{convert_fee_rate, AsyncClient};
+
+// Fee rates for the graph txs that are not presigned. Presigned txs pay the fee
+// they were signed with, see `PegOutGraph::fixed_fee_txs`.
+#[async_trait]
+pub trait FeeEstimator {
+    // sat/vB for a tx to confirm within `target_blocks`
+    async fn fee_rate(&self, target_blocks: u16) -> Result<f64, String>;
+}
+
+pub struct EsploraFeeEstimator<'a> {
+    client: &'a AsyncClient,
+}
+
+impl<'a> EsploraFeeEstimator<'a> {
+    pub fn new(client: &'a AsyncClient) -> Self { Self { client } }
+}
+
+#[async_trait]
+impl FeeEstimator for EsploraFeeEstimator<'_> {
+    async fn fee_rate(&self, target_blocks: u16) -> Result<f64, String> {
+        let estimates = self
+            .client
+            .get_fee_estimates()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        // the estimate of the closest target that is not later than the one asked for
+        convert_fee_rate(target_blocks.into(), estimates)
+            .ok_or_else(|| format!("No fee estimate for a target of {target_blocks} blocks"))
+    }
+}
@@ -1,3 +1,9 @@
 pub mod base;
+pub mod dag;
+pub mod fee_estimator;
+pub mod nonce_store;
+pub mod observer;
 pub mod peg_in;
 pub mod peg_out;
+pub mod status_cache;
+pub mod topology;
@@ -0,0 +1,300 @@
+use bitcoin::{hashes::Hash, PublicKey, Txid};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use musig2::SecNonce;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use super::peg_out::NonceBlobError;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum SecNonceStoreError {
+    Io(String),
+    NotFound(String),     // tx input without a stored nonce
+    AlreadyUsed(String),  // tx input whose nonce has been loaded before
+    WrongInput(String),   // tx input holding the nonce of another tx input
+    Corrupted(String),    // tx input whose stored file is not a readable nonce
+    Blob(NonceBlobError), // the loaded nonces can no longer be used
+}
+
+impl Display for SecNonceStoreError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            SecNonceStoreError::Io(err) => write!(f, "Failed to access secret nonces: {err}"),
+            SecNonceStoreError::NotFound(input) => {
+                write!(f, "No secret nonce stored for {input}")
+            }
+            SecNonceStoreError::AlreadyUsed(input) => {
+                write!(f, "Secret nonce for {input} has already been loaded")
+            }
+            SecNonceStoreError::WrongInput(input) => {
+                write!(
+                    f,
+                    "Secret nonce stored for {input} was made for another input"
+                )
+            }
+            SecNonceStoreError::Corrupted(input) => {
+                write!(f, "Stored secret nonce for {input} is unreadable")
+            }
+            SecNonceStoreError::Blob(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+// Secret nonces of one verifier, kept from pushing the public nonces until signing.
+// Each nonce is tagged with the graph tx input it was made for and `load` hands it
+// out only once, so a nonce can never sign a second message.
+pub trait SecNonceStore {
+    fn save(
+        &self,
+        graph_id: &str,
+        txid: &Txid,
+        input_index: usize,
+        secret_nonce: &SecNonce,
+    ) -> Result<(), SecNonceStoreError>;
+
+    // Takes the nonce out of the store, loading it again fails with `AlreadyUsed`.
+    fn load(
+        &self,
+        graph_id: &str,
+        txid: &Txid,
+        input_index: usize,
+    ) -> Result<SecNonce, SecNonceStoreError>;
+
+    // Reads the nonce like `load` but leaves it in the store, so that a set of
+    // nonces can be checked before any of them is used up.
+    fn peek(
+        &self,
+        graph_id: &str,
+        txid: &Txid,
+        input_index: usize,
+    ) -> Result<SecNonce, SecNonceStoreError>;
+}
+
+// What a set of sealed secret nonces belongs to within its graph. Part of the
+// associated data, so that nonces sealed for one cannot be opened as another's.
+pub(crate) enum SealedNonces<'a> {
+    // All the nonces of a verifier, see `PegOutGraph::export_secret_nonces`
+    Verifier(&'a PublicKey),
+    // The nonce of a single tx input, see `FileSecNonceStore`
+    Input(&'a Txid, usize),
+}
+
+fn sealing_aad(graph_id: &str, sealed: SealedNonces) -> Vec<u8> {
+    let (tag, owner) = match sealed {
+        SealedNonces::Verifier(verifier_public_key) => (0u8, verifier_public_key.to_bytes()),
+        SealedNonces::Input(txid, input_index) => (
+            1,
+            [txid.as_byte_array().as_slice(), &(input_index as u64).to_le_bytes()].concat(),
+        ),
+    };
+
+    [
+        [tag].as_slice(),
+        &(graph_id.len() as u64).to_le_bytes(),
+        graph_id.as_bytes(),
+        &owner,
+    ]
+    .concat()
+}
+
+// Encrypts secret nonces under a 32 byte key, returns the random encryption nonce
+// and the ciphertext.
+pub(crate) fn seal<T: Serialize>(
+    key: &[u8; 32],
+    graph_id: &str,
+    sealed: SealedNonces,
+    secret_nonces: &T,
+) -> ([u8; 12], Vec<u8>) {
+    let nonce: [u8; 12] = rand::random();
+    let plaintext = bincode::serialize(secret_nonces).expect("Failed to serialize secret nonces");
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: &sealing_aad(graph_id, sealed),
+            },
+        )
+        .expect("Failed to encrypt secret nonces");
+
+    (nonce, ciphertext)
+}
+
+// Decrypts secret nonces encrypted by `seal`. None for another key, graph or
+// owner, or a ciphertext that has been tampered with.
+pub(crate) fn open<T: DeserializeOwned>(
+    key: &[u8; 32],
+    graph_id: &str,
+    sealed: SealedNonces,
+    nonce: &[u8; 12],
+    ciphertext: &[u8],
+) -> Option<T> {
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &sealing_aad(graph_id, sealed),
+            },
+        )
+        .ok()?;
+
+    bincode::deserialize(&plaintext).ok()
+}
+
+// A stored nonce, sealed with its tx input as associated data so that the tag
+// cannot be changed without failing decryption.
+#[derive(Serialize, Deserialize)]
+struct StoredSecNonce {
+    graph_id: String,
+    txid: Txid,
+    input_index: usize,
+    nonce: [u8; 12],
+    ciphertext: Vec<u8>,
+}
+
+fn input_name(graph_id: &str, txid: &Txid, input_index: usize) -> String {
+    format!("input {input_index} of tx {txid} in graph {graph_id}")
+}
+
+// One file per tx input in `<dir>/`, encrypted under a 32 byte key. A loaded nonce
+// is moved aside to a `.used` file before it is returned.
+pub struct FileSecNonceStore {
+    dir: PathBuf,
+    key: [u8; 32],
+}
+
+impl FileSecNonceStore {
+    pub fn new(dir: impl Into<PathBuf>, key: &[u8; 32]) -> Self {
+        Self {
+            dir: dir.into(),
+            key: *key,
+        }
+    }
+
+    pub fn nonce_path(&self, graph_id: &str, txid: &Txid, input_index: usize) -> PathBuf {
+        self.dir.join(format!("{graph_id}_{txid}_{input_index}"))
+    }
+
+    fn missing_error(&self, path: &Path, input: String) -> SecNonceStoreError {
+        if path.with_extension("used").exists() {
+            SecNonceStoreError::AlreadyUsed(input)
+        } else {
+            SecNonceStoreError::NotFound(input)
+        }
+    }
+
+    fn read(
+        &self,
+        path: &Path,
+        graph_id: &str,
+        txid: &Txid,
+        input_index: usize,
+    ) -> Result<SecNonce, SecNonceStoreError> {
+        let input = input_name(graph_id, txid, input_index);
+        let bytes = fs::read(path).map_err(io_error)?;
+        let stored: StoredSecNonce = bincode::deserialize(&bytes)
+            .map_err(|_| SecNonceStoreError::Corrupted(input.clone()))?;
+        if stored.graph_id != graph_id || stored.txid != *txid || stored.input_index != input_index
+        {
+            return Err(SecNonceStoreError::WrongInput(input));
+        }
+
+        open(
+            &self.key,
+            graph_id,
+            SealedNonces::Input(txid, input_index),
+            &stored.nonce,
+            &stored.ciphertext,
+        )
+        .ok_or(SecNonceStoreError::Corrupted(input))
+    }
+}
+
+fn io_error(err: std::io::Error) -> SecNonceStoreError { SecNonceStoreError::Io(err.to_string()) }
+
+impl SecNonceStore for FileSecNonceStore {
+    fn save(
+        &self,
+        graph_id: &str,
+        txid: &Txid,
+        input_index: usize,
+        secret_nonce: &SecNonce,
+    ) -> Result<(), SecNonceStoreError> {
+        fs::create_dir_all(&self.dir).map_err(io_error)?;
+
+        let (nonce, ciphertext) = seal(
+            &self.key,
+            graph_id,
+            SealedNonces::Input(txid, input_index),
+            secret_nonce,
+        );
+        let stored = StoredSecNonce {
+            graph_id: graph_id.to_string(),
+            txid: *txid,
+            input_index,
+            nonce,
+            ciphertext,
+        };
+
+        // written aside and renamed, so that a crash never leaves a partial nonce
+        let path = self.nonce_path(graph_id, txid, input_index);
+        let tmp_path = path.with_extension("tmp");
+        fs::write(
+            &tmp_path,
+            bincode::serialize(&stored).expect("Failed to serialize secret nonce"),
+        )
+        .map_err(io_error)?;
+        fs::rename(&tmp_path, &path).map_err(io_error)?;
+
+        // a fresh nonce for the input, the one loaded before stays used
+        match fs::remove_file(path.with_extension("used")) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(io_error(err)),
+            _ => Ok(()),
+        }
+    }
+
+    fn load(
+        &self,
+        graph_id: &str,
+        txid: &Txid,
+        input_index: usize,
+    ) -> Result<SecNonce, SecNonceStoreError> {
+        let path = self.nonce_path(graph_id, txid, input_index);
+        let used_path = path.with_extension("used");
+
+        // marked as used before it is read, so that it is handed out at most once
+        // even if the process dies in between
+        match fs::rename(&path, &used_path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                return Err(self.missing_error(&path, input_name(graph_id, txid, input_index)));
+            }
+            Err(err) => return Err(io_error(err)),
+        }
+
+        self.read(&used_path, graph_id, txid, input_index)
+    }
+
+    fn peek(
+        &self,
+        graph_id: &str,
+        txid: &Txid,
+        input_index: usize,
+    ) -> Result<SecNonce, SecNonceStoreError> {
+        let path = self.nonce_path(graph_id, txid, input_index);
+        if !path.exists() {
+            return Err(self.missing_error(&path, input_name(graph_id, txid, input_index)));
+        }
+
+        self.read(&path, graph_id, txid, input_index)
+    }
+}
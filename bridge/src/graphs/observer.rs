@@ -0,0 +1,47 @@
+use bitcoin::Txid;
+
+use super::base::GraphId;
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum GraphEvent {
+    Broadcast {
+        graph_id: GraphId,
+        tx_name: &'static str,
+        txid: Txid,
+    },
+    ConfirmationDetected {
+        graph_id: GraphId,
+        tx_name: &'static str,
+        block_height: u32,
+    },
+    StatusChanged {
+        graph_id: GraphId,
+        status: String, // as displayed to the operator
+    },
+    ActionFailed {
+        graph_id: GraphId,
+        tx_name: &'static str,
+        error: String,
+    },
+}
+
+// Receives the events of the graphs an operator drives, e.g. to export them to a
+// monitoring system. Called inline, so it should hand events off rather than block.
+// Events are only emitted by `PegOutGraph::guarded_action` and
+// `PegOutGraph::observe_operator_status`, actions run directly are not reported.
+pub trait GraphObserver: Send + Sync {
+    fn on_event(&self, event: GraphEvent);
+}
+
+pub struct NoopObserver;
+
+impl GraphObserver for NoopObserver {
+    fn on_event(&self, _: GraphEvent) {}
+}
+
+// What an observer has been told about a graph, so that only changes are reported.
+#[derive(Default)]
+pub struct ObservedGraph {
+    pub(crate) confirmed_txs: Vec<&'static str>,
+    pub(crate) status: Option<String>,
+}
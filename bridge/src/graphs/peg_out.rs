@@ -1,30 +1,52 @@
+use ark_bn254::Bn254;
+use ark_groth16::VerifyingKey;
 use bitcoin::{
+    address::NetworkUnchecked,
+    block::Header,
     hashes::Hash,
     hex::{Case::Upper, DisplayHex},
     key::Keypair,
-    Amount, Network, OutPoint, PublicKey, ScriptBuf, Txid, XOnlyPublicKey,
+    opcodes::all::{OP_CHECKSIG, OP_CHECKSIGADD, OP_CHECKSIGVERIFY},
+    script::Instruction,
+    Address, Amount, Network, OutPoint, Psbt, PublicKey, ScriptBuf, Transaction, TxOut, Txid,
+    VarInt, XOnlyPublicKey,
 };
 use esplora_client::{AsyncClient, Error, TxStatus};
-use musig2::SecNonce;
+use musig2::{AggNonce, SecNonce};
 use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::{Display, Formatter, Result as FmtResult},
+    iter,
+    ops::Range,
+    sync::OnceLock,
+    time::{Duration, Instant},
 };
 
 use crate::{
     connectors::{
-        connector_d::ConnectorD, connector_e::ConnectorE, connector_f_1::ConnectorF1,
+        base::{verify_commitment_public_key, verify_commitment_secret, WrongCommitmentSecret},
+        connector_d::ConnectorD,
+        connector_e::ConnectorE,
+        connector_f_1::ConnectorF1,
         connector_f_2::ConnectorF2,
     },
     constants::{
         DESTINATION_NETWORK_TXID_LENGTH, SOURCE_NETWORK_TXID_LENGTH, START_TIME_MESSAGE_LENGTH,
     },
+    scripts::generate_pay_to_pubkey_script_address,
+    serialization::{
+        deserialize_canonical, serialize_binary, serialize_canonical, try_deserialize_binary,
+    },
     superblock::{
-        find_superblock, get_start_time_block_number, get_superblock_hash_message,
-        get_superblock_message, SUPERBLOCK_HASH_MESSAGE_LENGTH, SUPERBLOCK_MESSAGE_LENGTH,
+        fetch_superblock_candidates, find_superblocks, get_start_time_block_number,
+        get_superblocks_from_message, get_superblocks_hash_message, get_superblocks_message,
+        is_superblock_claim_disproved, is_superblock_in_window, NoSuperblockFound,
+        SuperblockCriteria, SuperblockError, SuperblockParams, SuperblockPolicy,
+        SUPERBLOCK_HASH_MESSAGE_LENGTH,
     },
     transactions::{
         assert_transactions::{
@@ -33,46 +55,66 @@ use crate::{
             assert_final::AssertFinalTransaction,
             assert_initial::AssertInitialTransaction,
             utils::{
-                groth16_commitment_secrets_to_public_keys, merge_to_connector_c_commits_public_key,
+                derive_groth16_connector_keys, sign_assert_tx_with_groth16_proof,
                 AssertCommit1ConnectorsE, AssertCommit2ConnectorsE, AssertCommitConnectorsF,
+                GrothKeySource,
             },
         },
-        pre_signed_musig2::PreSignedMusig2Transaction,
+        pre_signed_musig2::{
+            commits_to_output, FinalizeError, PartialSigs, PreSignedMusig2Transaction,
+        },
     },
 };
 
+#[cfg(feature = "minimal-graph")]
+use bitvm::chunker::common::variable_name_to_size;
 use bitvm::chunker::{
     assigner::BridgeAssigner,
-    common::BLAKE3_HASH_LENGTH,
+    common::{RawWitness, BLAKE3_HASH_LENGTH},
     disprove_execution::{disprove_exec, RawProof},
+    elements::ElementError,
 };
 use bitvm::signatures::signing_winternitz::{
-    WinternitzPublicKey, WinternitzSecret, WinternitzSigningInputs,
+    winternitz_message_from_witness, WinternitzPublicKey, WinternitzSecret, WinternitzSigningInputs,
 };
 
 use super::{
     super::{
-        client::chain::chain::PegOutEvent,
+        client::chain::chain::{PegOutDestinationError, PegOutEvent},
         connectors::{
-            connector_0::Connector0, connector_1::Connector1, connector_2::Connector2,
-            connector_3::Connector3, connector_4::Connector4, connector_5::Connector5,
-            connector_6::Connector6, connector_a::ConnectorA, connector_b::ConnectorB,
-            connector_c::ConnectorC,
+            base::TaprootConnector,
+            connector_0::Connector0,
+            connector_1::Connector1,
+            connector_2::Connector2,
+            connector_3::Connector3,
+            connector_4::Connector4,
+            connector_5::Connector5,
+            connector_6::Connector6,
+            connector_a::ConnectorA,
+            connector_b::ConnectorB,
+            connector_c::{AssertSegments, ConnectorC},
+        },
+        contexts::{
+            base::generate_n_of_n_public_key, operator::OperatorContext,
+            verifier::VerifierContext,
         },
-        contexts::{operator::OperatorContext, verifier::VerifierContext},
         transactions::{
             base::{
-                validate_transaction, verify_public_nonces_for_tx, BaseTransaction, Input,
+                fee_divergence, merge_musig2_partial_sigs, transaction_fee, validate_transaction,
+                verify_public_nonces_for_tx, BaseTransaction, FeeDivergence, Input,
                 InputWithScript,
             },
-            challenge::ChallengeTransaction,
+            challenge::{ChallengeFundingError, ChallengeTransaction},
             disprove::DisproveTransaction,
             disprove_chain::DisproveChainTransaction,
+            funding::{generate_funding_psbt, WalletUtxo},
             kick_off_1::KickOff1Transaction,
             kick_off_2::KickOff2Transaction,
             kick_off_timeout::KickOffTimeoutTransaction,
             peg_out::PegOutTransaction,
-            peg_out_confirm::PegOutConfirmTransaction,
+            peg_out_confirm::{
+                peg_out_confirm_output_amount, PegOutConfirmAmountError, PegOutConfirmTransaction,
+            },
             pre_signed::PreSignedTransaction,
             start_time::StartTimeTransaction,
             start_time_timeout::StartTimeTimeoutTransaction,
@@ -81,18 +123,56 @@ use super::{
         },
     },
     base::{
-        broadcast_and_verify, get_block_height, verify_if_not_mined, BaseGraph, GraphId,
-        GRAPH_VERSION,
+        broadcast_and_verify, confirmation_height, get_block_height, get_block_height_if_none,
+        get_tx_statuses, is_confirmed_without_height, is_timelock_elapsed,
+        timelock_remaining_blocks, try_broadcast_and_verify, tx_confirmations, verify_if_not_mined,
+        wait_for_status, BaseGraph, GraphId, Timeout, FEE_AMOUNT, GRAPH_VERSION,
     },
+    dag::{TxDag, TxEdge, TxRole},
+    fee_estimator::{EsploraFeeEstimator, FeeEstimator},
+    nonce_store::{open, seal, SealedNonces, SecNonceStore, SecNonceStoreError},
+    observer::{GraphEvent, GraphObserver, ObservedGraph},
     peg_in::PegInGraph,
+    status_cache::StatusCache,
+    topology,
 };
 
 pub type PegOutId = GraphId;
 
+// Why no action is available for a peg-out graph at the moment.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum WaitReason {
+    TimelockPending { remaining_blocks: u32 },
+    AwaitingConfirmation { txid: Txid },
+    // confirmed, but not deep enough to rule out a reorg
+    AwaitingDepth { txid: Txid, confirmations: u32 },
+    AwaitingCounterparty, // another party has to broadcast the next tx
+    AwaitingPresign,      // verifiers have not presigned the graph yet
+}
+
+impl Display for WaitReason {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            WaitReason::TimelockPending { remaining_blocks } => {
+                write!(f, "timelock elapses in {remaining_blocks} blocks")
+            }
+            WaitReason::AwaitingConfirmation { txid } => {
+                write!(f, "tx {txid} not confirmed yet")
+            }
+            WaitReason::AwaitingDepth {
+                txid,
+                confirmations,
+            } => write!(f, "tx {txid} has only {confirmations} confirmations"),
+            WaitReason::AwaitingCounterparty => write!(f, "waiting for another party to act"),
+            WaitReason::AwaitingPresign => write!(f, "graph not presigned yet"),
+        }
+    }
+}
+
 pub enum PegOutWithdrawerStatus {
-    PegOutNotStarted, // peg-out transaction not created yet
-    PegOutWait,       // peg-out not confirmed yet, wait
-    PegOutComplete,   // peg-out complete
+    PegOutNotStarted,       // peg-out transaction not created yet
+    PegOutWait(WaitReason), // peg-out not confirmed yet, wait
+    PegOutComplete,         // peg-out complete
 }
 
 impl Display for PegOutWithdrawerStatus {
@@ -101,7 +181,9 @@ impl Display for PegOutWithdrawerStatus {
             PegOutWithdrawerStatus::PegOutNotStarted => {
                 write!(f, "Peg-out available. Request peg-out?")
             }
-            PegOutWithdrawerStatus::PegOutWait => write!(f, "No action available. Wait..."),
+            PegOutWithdrawerStatus::PegOutWait(reason) => {
+                write!(f, "No action available, {reason}. Wait...")
+            }
             PegOutWithdrawerStatus::PegOutComplete => write!(f, "Peg-out complete. Done."),
         }
     }
@@ -110,7 +192,8 @@ impl Display for PegOutWithdrawerStatus {
 pub enum PegOutVerifierStatus {
     PegOutPresign,            // should presign peg-out graph
     PegOutComplete,           // peg-out complete
-    PegOutWait,               // no action required, wait
+    PegOutWait(WaitReason),   // no action required, wait
+    PegOutRefetch,            // kick-off 1 confirmed without a block height yet, fetch again
     PegOutChallengeAvailable, // can call challenge
     PegOutStartTimeTimeoutAvailable,
     PegOutKickOffTimeoutAvailable,
@@ -128,7 +211,12 @@ impl Display for PegOutVerifierStatus {
             PegOutVerifierStatus::PegOutComplete => {
                 write!(f, "Peg-out complete, reimbursement succeded. Done.")
             }
-            PegOutVerifierStatus::PegOutWait => write!(f, "No action available. Wait..."),
+            PegOutVerifierStatus::PegOutWait(reason) => {
+                write!(f, "No action available, {reason}. Wait...")
+            }
+            PegOutVerifierStatus::PegOutRefetch => {
+                write!(f, "Confirmation height not reported yet. Refetch status...")
+            }
             PegOutVerifierStatus::PegOutChallengeAvailable => {
                 write!(
                   f,
@@ -160,9 +248,31 @@ impl Display for PegOutVerifierStatus {
     }
 }
 
+// Coarse lifecycle of a peg-out graph, for monitoring rather than deciding on actions.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, PartialOrd, Ord)]
+pub enum GraphPhase {
+    Setup,    // graph being presigned, or nothing confirmed yet
+    Funded,   // withdrawer paid out by the operator, reimbursement not started
+    InFlight, // kick-off 1 confirmed, reimbursement under way
+    Disputed, // challenge confirmed
+    Settled,  // reimbursement taken, or the operator timed out or was disproved
+}
+
+impl Display for GraphPhase {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            GraphPhase::Setup => write!(f, "Setup"),
+            GraphPhase::Funded => write!(f, "Funded"),
+            GraphPhase::InFlight => write!(f, "In flight"),
+            GraphPhase::Disputed => write!(f, "Disputed"),
+            GraphPhase::Settled => write!(f, "Settled"),
+        }
+    }
+}
+
 pub enum PegOutOperatorStatus {
-    // TODO: add assert initial and assert final
-    PegOutWait,
+    PegOutWait(WaitReason),
+    PegOutRefetch,     // confirmed without a block height yet, fetch again
     PegOutComplete,    // peg-out complete
     PegOutFailed,      // timeouts or disproves executed
     PegOutStartPegOut, // should execute peg-out tx
@@ -170,7 +280,8 @@ pub enum PegOutOperatorStatus {
     PegOutKickOff1Available,
     PegOutStartTimeAvailable,
     PegOutKickOff2Available,
-    PegOutAssertAvailable,
+    PegOutAssertInitialAvailable, // challenged and kick-off 2 timelock elapsed
+    PegOutAssertFinalAvailable,   // assert initial confirmed
     PegOutTake1Available,
     PegOutTake2Available,
 }
@@ -178,7 +289,12 @@ pub enum PegOutOperatorStatus {
 impl Display for PegOutOperatorStatus {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         match self {
-            PegOutOperatorStatus::PegOutWait => write!(f, "No action available. Wait..."),
+            PegOutOperatorStatus::PegOutWait(reason) => {
+                write!(f, "No action available, {reason}. Wait...")
+            }
+            PegOutOperatorStatus::PegOutRefetch => {
+                write!(f, "Confirmation height not reported yet. Refetch status...")
+            }
             PegOutOperatorStatus::PegOutComplete => {
                 write!(f, "Peg-out complete, reimbursement succeded. Done.")
             }
@@ -209,8 +325,14 @@ impl Display for PegOutOperatorStatus {
             PegOutOperatorStatus::PegOutKickOff2Available => {
                 write!(f, "Start time confirmed. Broadcast kick-off 2 transaction?")
             }
-            PegOutOperatorStatus::PegOutAssertAvailable => {
-                write!(f, "Dispute raised. Broadcast assert transaction?")
+            PegOutOperatorStatus::PegOutAssertInitialAvailable => {
+                write!(f, "Dispute raised. Broadcast assert-initial transaction?")
+            }
+            PegOutOperatorStatus::PegOutAssertFinalAvailable => {
+                write!(
+                    f,
+                    "Assert-initial confirmed. Broadcast assert-commit and assert-final transactions?"
+                )
             }
             PegOutOperatorStatus::PegOutTake1Available => write!(
                 f,
@@ -224,6 +346,70 @@ impl Display for PegOutOperatorStatus {
     }
 }
 
+// The three role statuses derived from one snapshot of the graph txs, so that
+// they never disagree with one another.
+pub struct GraphStatus {
+    pub operator: PegOutOperatorStatus,
+    pub verifier: PegOutVerifierStatus,
+    pub withdrawer: PegOutWithdrawerStatus,
+    pub block_height: u32,
+}
+
+// Statuses of the graph txs in the order `PegOutGraph::get_peg_out_statuses` fetches
+// them, the peg-out tx status being `None` until the peg-out tx is created.
+type PegOutStatuses = (
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Option<Result<TxStatus, Error>>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+    Result<TxStatus, Error>,
+);
+
+// The statuses of the graph txs by tx name, without the peg-out tx.
+fn graph_statuses_by_name(
+    (
+        assert_initial_status,
+        assert_final_status,
+        challenge_status,
+        disprove_chain_status,
+        disprove_status,
+        peg_out_confirm_status,
+        kick_off_1_status,
+        kick_off_2_status,
+        kick_off_timeout_status,
+        _,
+        start_time_timeout_status,
+        start_time_status,
+        take_1_status,
+        take_2_status,
+    ): PegOutStatuses,
+) -> HashMap<&'static str, Result<TxStatus, Error>> {
+    HashMap::from([
+        (TxRole::AssertInitial.tx_name(), assert_initial_status),
+        (TxRole::AssertFinal.tx_name(), assert_final_status),
+        (TxRole::Challenge.tx_name(), challenge_status),
+        (TxRole::DisproveChain.tx_name(), disprove_chain_status),
+        (TxRole::Disprove.tx_name(), disprove_status),
+        (TxRole::PegOutConfirm.tx_name(), peg_out_confirm_status),
+        (TxRole::KickOff1.tx_name(), kick_off_1_status),
+        (TxRole::KickOff2.tx_name(), kick_off_2_status),
+        (TxRole::KickOffTimeout.tx_name(), kick_off_timeout_status),
+        (TxRole::StartTimeTimeout.tx_name(), start_time_timeout_status),
+        (TxRole::StartTime.tx_name(), start_time_status),
+        (TxRole::Take1.tx_name(), take_1_status),
+        (TxRole::Take2.tx_name(), take_2_status),
+    ])
+}
+
 struct PegOutConnectors {
     connector_0: Connector0,
     connector_1: Connector1,
@@ -241,6 +427,19 @@ struct PegOutConnectors {
     assert_commit_connectors_f: AssertCommitConnectorsF,
 }
 
+// Ids and keys a peg-out graph is built for, taken from the operator context when
+// the graph is constructed and from the graph itself when it is validated.
+struct PegOutGraphKeys {
+    network: Network,
+    id: String,
+    peg_in_graph_id: String,
+    n_of_n_public_key: PublicKey,
+    n_of_n_taproot_public_key: XOnlyPublicKey,
+    n_of_n_public_keys: Vec<PublicKey>,
+    operator_public_key: PublicKey,
+    operator_taproot_public_key: XOnlyPublicKey,
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Hash, Clone, PartialOrd, Ord, Debug)]
 pub enum CommitmentMessageId {
     PegOutTxIdSourceNetwork,
@@ -252,9 +451,58 @@ pub enum CommitmentMessageId {
     Groth16IntermediateValues((String, usize)),
 }
 
+// Groth16 intermediate values committed in a minimal graph, i.e. a graph with
+// the full transaction wiring but only a handful of assert commitments.
+#[cfg(feature = "minimal-graph")]
+pub const MINIMAL_GROTH16_VARIABLES: [&str; 2] = ["F_p4_init", "scalar_1"];
+
+// Stand-ins used by the graph constructors: the default challenge amount, and the
+// disprove leaf until it is configurable. See `reject_placeholders`.
+const PLACEHOLDER_CROWDFUNDING_AMOUNT: Amount = Amount::ONE_BTC;
+const PLACEHOLDER_DISPROVE_SCRIPT_INDEX: u32 = 1;
+
+const UNTAGGED_ID_VERSIONS: [&str; 1] = ["0.1"];
+
+// Depth a take, disprove or timeout tx needs before the graph counts as settled,
+// unless the caller of a status check asks for more. A local policy of each party,
+// so it is passed to the status checks rather than kept in the graph.
+pub const DEFAULT_MIN_CONFIRMATIONS: u32 = 1;
+// Older graph versions that `PegOutGraph::migrate_from` can bring up to date.
+const MIGRATABLE_GRAPH_VERSIONS: [&str; 0] = [];
+const ID_HASH_TAG: &str = "BitVM/peg-out-graph-id";
+
+// Witness items assumed for inputs that are not finalized yet, with their length
+// prefix: a schnorr signature with sighash byte, and the control block of a leaf
+// one level below the taproot internal key.
+const ESTIMATED_SIGNATURE_WITNESS_SIZE: usize = 1 + 65;
+const ESTIMATED_CONTROL_BLOCK_WITNESS_SIZE: usize = 1 + 33 + 32;
+
 impl CommitmentMessageId {
+    pub fn generate_commitment_secrets(
+        superblock_policy: SuperblockPolicy,
+    ) -> HashMap<CommitmentMessageId, WinternitzSecret> {
+        // maybe variable cache is more efficient
+        let all_variables = BridgeAssigner::default().all_intermediate_variable();
+
+        Self::generate_commitment_secrets_for_variables(superblock_policy, all_variables)
+    }
+
+    // Only for testing the graph flow, the commitments cannot prove or disprove anything.
+    #[cfg(feature = "minimal-graph")]
+    pub fn generate_minimal_commitment_secrets(
+        superblock_policy: SuperblockPolicy,
+    ) -> HashMap<CommitmentMessageId, WinternitzSecret> {
+        Self::generate_commitment_secrets_for_variables(
+            superblock_policy,
+            MINIMAL_GROTH16_VARIABLES.map(|name| (name.to_string(), variable_name_to_size(name))),
+        )
+    }
+
     // btree map is a copy of chunker related commitments
-    pub fn generate_commitment_secrets() -> HashMap<CommitmentMessageId, WinternitzSecret> {
+    fn generate_commitment_secrets_for_variables(
+        superblock_policy: SuperblockPolicy,
+        groth16_variables: impl IntoIterator<Item = (String, usize)>,
+    ) -> HashMap<CommitmentMessageId, WinternitzSecret> {
         let mut commitment_map = HashMap::from([
             (
                 CommitmentMessageId::PegOutTxIdSourceNetwork,
@@ -270,7 +518,7 @@ impl CommitmentMessageId {
             ),
             (
                 CommitmentMessageId::Superblock,
-                WinternitzSecret::new(SUPERBLOCK_MESSAGE_LENGTH),
+                WinternitzSecret::new(superblock_policy.message_length()),
             ),
             (
                 CommitmentMessageId::SuperblockHash,
@@ -278,11 +526,7 @@ impl CommitmentMessageId {
             ),
         ]);
 
-        // maybe variable cache is more efficient
-        let all_variables = BridgeAssigner::default().all_intermediate_variable();
-        // split variable to different connectors
-
-        for (v, size) in all_variables {
+        for (v, size) in groth16_variables {
             commitment_map.insert(
                 CommitmentMessageId::Groth16IntermediateValues((v, size)),
                 WinternitzSecret::new(size),
@@ -293,881 +537,2015 @@ impl CommitmentMessageId {
     }
 }
 
-#[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
-pub struct PegOutGraph {
-    version: String,
-    network: Network,
-    id: String,
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum GraphConstructionError {
+    InvalidVout(String),   // name of the vout that does not fit into u32
+    InvalidAmount(String), // name of the amount that could not be constructed
+    NetworkMismatch { operator: Network, peg_in: Network },
+    MissingInput(String), // name of the tx built without any funding input
+    InvalidSuperblockPolicy(SuperblockError),
+}
 
-    // state: State,
-    // n_of_n_pre_signing_state: PreSigningState,
-    n_of_n_presigned: bool,
-    n_of_n_public_key: PublicKey,
-    n_of_n_taproot_public_key: XOnlyPublicKey,
+impl Display for GraphConstructionError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            GraphConstructionError::InvalidVout(field) => {
+                write!(f, "Invalid vout for {field}: does not fit into u32")
+            }
+            GraphConstructionError::InvalidAmount(field) => write!(f, "Invalid amount for {field}"),
+            GraphConstructionError::MissingInput(tx_name) => {
+                write!(f, "No funding input given for {tx_name} tx")
+            }
+            GraphConstructionError::InvalidSuperblockPolicy(err) => {
+                write!(f, "Invalid superblock policy: {err}")
+            }
+            GraphConstructionError::NetworkMismatch { operator, peg_in } => {
+                write!(
+                    f,
+                    "Operator network {operator} does not match peg-in graph network {peg_in}"
+                )
+            }
+        }
+    }
+}
 
-    pub peg_in_graph_id: String,
-    peg_in_confirm_txid: Txid,
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum RecoveryError {
+    PegOutConfirmNotFound, // no confirmed tx pays to connector 6 of the graph
+    UnknownTx(Txid),       // tx on chain spends no output any graph tx spends
+    Client(String),
+    Construction(GraphConstructionError),
+    TxidMismatch {
+        tx_name: &'static str,
+        onchain_txid: Txid,
+        recovered_txid: Txid,
+    },
+}
 
-    // Note that only the connectors that are used with message commitments are
-    // required to be here. They carry the Winternitz public keys, which need
-    // to be pushed to remote data store. The remaining connectors can be
-    // constructed dynamically.
-    connector_0: Connector0,
-    connector_1: Connector1,
-    connector_2: Connector2,
-    connector_3: Connector3,
-    connector_4: Connector4,
-    connector_5: Connector5,
-    connector_6: Connector6,
-    connector_a: ConnectorA,
-    connector_b: ConnectorB,
-    connector_c: ConnectorC,
-    connector_d: ConnectorD,
-    connector_e_1: AssertCommit1ConnectorsE,
-    connector_e_2: AssertCommit2ConnectorsE,
-    connector_f_1: ConnectorF1,
-    connector_f_2: ConnectorF2,
+impl Display for RecoveryError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            RecoveryError::PegOutConfirmNotFound => {
+                write!(f, "No confirmed peg-out-confirm tx found for the graph")
+            }
+            RecoveryError::UnknownTx(txid) => {
+                write!(f, "Tx {txid} on chain does not belong to the graph")
+            }
+            RecoveryError::Client(err) => write!(f, "Failed to query the chain: {err}"),
+            RecoveryError::Construction(err) => write!(f, "Failed to rebuild the graph: {err}"),
+            RecoveryError::TxidMismatch {
+                tx_name,
+                onchain_txid,
+                recovered_txid,
+            } => write!(
+                f,
+                "Recovered {tx_name} tx {recovered_txid} does not match {onchain_txid} on chain"
+            ),
+        }
+    }
+}
 
-    peg_out_confirm_transaction: PegOutConfirmTransaction,
-    assert_initial_transaction: AssertInitialTransaction,
-    assert_final_transaction: AssertFinalTransaction,
-    challenge_transaction: ChallengeTransaction,
-    disprove_chain_transaction: DisproveChainTransaction,
-    disprove_transaction: DisproveTransaction,
-    kick_off_1_transaction: KickOff1Transaction,
-    kick_off_2_transaction: KickOff2Transaction,
-    kick_off_timeout_transaction: KickOffTimeoutTransaction,
-    start_time_transaction: StartTimeTransaction,
-    start_time_timeout_transaction: StartTimeTimeoutTransaction,
-    take_1_transaction: Take1Transaction,
-    take_2_transaction: Take2Transaction,
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum GraphInvariantError {
+    // input spends neither a graph tx output nor a prerequisite output
+    UnknownParent {
+        tx_name: &'static str,
+        outpoint: OutPoint,
+    },
+    // prev out stored for signing differs from the parent tx output
+    PrevOutMismatch {
+        tx_name: &'static str,
+        input_index: usize,
+    },
+    ValueNotConserved {
+        tx_name: &'static str,
+        input_amount: Amount,
+        output_amount: Amount,
+    },
+}
 
-    operator_public_key: PublicKey,
-    operator_taproot_public_key: XOnlyPublicKey,
+impl Display for GraphInvariantError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            GraphInvariantError::UnknownParent { tx_name, outpoint } => {
+                write!(f, "Input {outpoint} of {tx_name} tx has no known parent")
+            }
+            GraphInvariantError::PrevOutMismatch {
+                tx_name,
+                input_index,
+            } => write!(
+                f,
+                "Prev out {input_index} of {tx_name} tx does not match its parent output"
+            ),
+            GraphInvariantError::ValueNotConserved {
+                tx_name,
+                input_amount,
+                output_amount,
+            } => write!(
+                f,
+                "Outputs of {tx_name} tx ({output_amount}) exceed its inputs ({input_amount})"
+            ),
+        }
+    }
+}
 
-    pub peg_out_chain_event: Option<PegOutEvent>,
-    pub peg_out_transaction: Option<PegOutTransaction>,
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ValidationError {
+    PegInGraphIdMismatch {
+        peg_in_graph_id: String,
+        expected: String,
+    },
+    PegInConfirmTxidMismatch {
+        peg_in_confirm_txid: Txid,
+        expected: Txid,
+    },
+    // take tx input that should spend connector 0 of the peg-in confirm tx
+    TakeInputMismatch {
+        tx_name: &'static str,
+        outpoint: OutPoint,
+    },
 }
 
-impl BaseGraph for PegOutGraph {
-    fn network(&self) -> Network { self.network }
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ValidationError::PegInGraphIdMismatch {
+                peg_in_graph_id,
+                expected,
+            } => write!(
+                f,
+                "Graph belongs to peg-in graph {peg_in_graph_id}, not {expected}"
+            ),
+            ValidationError::PegInConfirmTxidMismatch {
+                peg_in_confirm_txid,
+                expected,
+            } => write!(
+                f,
+                "Graph refers to peg-in confirm tx {peg_in_confirm_txid}, not {expected}"
+            ),
+            ValidationError::TakeInputMismatch { tx_name, outpoint } => write!(
+                f,
+                "{tx_name} tx spends {outpoint} instead of the peg-in confirm output"
+            ),
+        }
+    }
+}
 
-    fn id(&self) -> &String { &self.id }
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PegOutTxidChanged {
+    pub signed_txid: Txid,
+    pub current_txid: Txid,
+}
 
-    fn verifier_sign(
-        &mut self,
-        verifier_context: &VerifierContext,
-        secret_nonces: &HashMap<Txid, HashMap<usize, SecNonce>>,
-    ) {
-        self.assert_initial_transaction.pre_sign(
-            verifier_context,
-            &self.connector_b,
-            &secret_nonces[&self.assert_initial_transaction.tx().compute_txid()],
-        );
-        self.assert_final_transaction.pre_sign(
-            verifier_context,
-            &self.connector_d,
-            &secret_nonces[&self.assert_final_transaction.tx().compute_txid()],
-        );
-        self.disprove_chain_transaction.pre_sign(
-            verifier_context,
-            &self.connector_b,
-            &secret_nonces[&self.disprove_chain_transaction.tx().compute_txid()],
-        );
-        self.disprove_transaction.pre_sign(
-            verifier_context,
-            &self.connector_5,
-            &secret_nonces[&self.disprove_transaction.tx().compute_txid()],
-        );
-        self.kick_off_timeout_transaction.pre_sign(
-            verifier_context,
-            &self.connector_1,
-            &secret_nonces[&self.kick_off_timeout_transaction.tx().compute_txid()],
-        );
-        self.start_time_timeout_transaction.pre_sign(
-            verifier_context,
-            &self.connector_1,
-            &self.connector_2,
-            &secret_nonces[&self.start_time_timeout_transaction.tx().compute_txid()],
-        );
-        self.take_1_transaction.pre_sign(
-            verifier_context,
-            &self.connector_0,
-            &self.connector_b,
-            &secret_nonces[&self.take_1_transaction.tx().compute_txid()],
-        );
-        self.take_2_transaction.pre_sign(
-            verifier_context,
-            &self.connector_0,
-            &self.connector_5,
-            &secret_nonces[&self.take_2_transaction.tx().compute_txid()],
-        );
+impl Display for PegOutTxidChanged {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "Kick-off 1 was broadcast with peg-out txid {}, but the peg-out txid is now {}",
+            self.signed_txid, self.current_txid
+        )
+    }
+}
 
-        self.n_of_n_presigned = true; // TODO: set to true after collecting all n of n signatures
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum KickOff1Error {
+    PegOutTxidChanged(PegOutTxidChanged),
+    WrongCommitmentSecret(WrongCommitmentSecret),
+    Action(ActionError),
+}
+
+impl Display for KickOff1Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            KickOff1Error::PegOutTxidChanged(err) => write!(f, "{err}"),
+            KickOff1Error::WrongCommitmentSecret(err) => write!(f, "{err}"),
+            KickOff1Error::Action(err) => write!(f, "{err}"),
+        }
     }
+}
 
-    fn push_verifier_nonces(
-        &mut self,
-        verifier_context: &VerifierContext,
-    ) -> HashMap<Txid, HashMap<usize, SecNonce>> {
-        self.all_presigned_txs_mut()
-            .map(|tx_wrapper| {
-                (
-                    tx_wrapper.tx().compute_txid(),
-                    tx_wrapper.push_nonces(verifier_context),
-                )
-            })
-            .collect()
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum KickOff2Error {
+    NoSuperblockFound(NoSuperblockFound),
+    WrongCommitmentSecret(WrongCommitmentSecret),
+    Action(ActionError),
+}
+
+impl Display for KickOff2Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            KickOff2Error::NoSuperblockFound(err) => write!(f, "{err}"),
+            KickOff2Error::WrongCommitmentSecret(err) => write!(f, "{err}"),
+            KickOff2Error::Action(err) => write!(f, "{err}"),
+        }
     }
 }
 
-impl PegOutGraph {
-    pub fn new(
-        context: &OperatorContext,
-        peg_in_graph: &PegInGraph,
-        peg_out_confirm_input: Input,
-    ) -> (Self, HashMap<CommitmentMessageId, WinternitzSecret>) {
-        let peg_in_confirm_transaction = peg_in_graph.peg_in_confirm_transaction_ref();
-        let peg_in_confirm_txid = peg_in_confirm_transaction.tx().compute_txid();
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum StartTimeError {
+    WrongCommitmentSecret(WrongCommitmentSecret),
+    Action(ActionError),
+}
 
-        let commitment_secrets = CommitmentMessageId::generate_commitment_secrets();
-        let connector_1_commitment_public_keys = HashMap::from([
-            (
-                CommitmentMessageId::Superblock,
-                WinternitzPublicKey::from(&commitment_secrets[&CommitmentMessageId::Superblock]),
-            ),
-            (
-                CommitmentMessageId::SuperblockHash,
-                WinternitzPublicKey::from(
-                    &commitment_secrets[&CommitmentMessageId::SuperblockHash],
-                ),
-            ),
-        ]);
-        let connector_2_commitment_public_keys = HashMap::from([(
-            CommitmentMessageId::StartTime,
-            WinternitzPublicKey::from(&commitment_secrets[&CommitmentMessageId::StartTime]),
-        )]);
-        let connector_6_commitment_public_keys = HashMap::from([
-            (
-                CommitmentMessageId::PegOutTxIdSourceNetwork,
-                WinternitzPublicKey::from(
-                    &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
-                ),
-            ),
-            (
-                CommitmentMessageId::PegOutTxIdDestinationNetwork,
-                WinternitzPublicKey::from(
-                    &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
-                ),
-            ),
-        ]);
+impl Display for StartTimeError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            StartTimeError::WrongCommitmentSecret(err) => write!(f, "{err}"),
+            StartTimeError::Action(err) => write!(f, "{err}"),
+        }
+    }
+}
 
-        let (connector_e1_commitment_public_keys, connector_e2_commitment_public_keys) =
-            groth16_commitment_secrets_to_public_keys(&commitment_secrets);
+// The operator signature of take 2 does not spend the connector c output, e.g.
+// because the connector c of the graph was built for another operator key.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Take2SigningFailed;
 
-        let connectors = Self::create_new_connectors(
-            context.network,
-            &context.n_of_n_taproot_public_key,
-            &context.operator_taproot_public_key,
-            &context.operator_public_key,
-            &connector_1_commitment_public_keys,
-            &connector_2_commitment_public_keys,
-            &connector_6_commitment_public_keys,
-            &connector_e1_commitment_public_keys,
-            &connector_e2_commitment_public_keys,
-        );
+impl Display for Take2SigningFailed {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Take 2 signature does not spend the connector c output")
+    }
+}
 
-        let peg_out_confirm_transaction =
-            PegOutConfirmTransaction::new(context, &connectors.connector_6, peg_out_confirm_input);
-        let peg_out_confirm_txid = peg_out_confirm_transaction.tx().compute_txid();
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Take2Error {
+    SigningFailed(Take2SigningFailed),
+    Action(ActionError),
+}
 
-        let kick_off_1_vout_0 = 0;
-        let kick_off_1_transaction = KickOff1Transaction::new(
-            context,
-            &connectors.connector_1,
-            &connectors.connector_2,
-            &connectors.connector_6,
-            Input {
-                outpoint: OutPoint {
-                    txid: peg_out_confirm_txid,
-                    vout: kick_off_1_vout_0.to_u32().unwrap(),
-                },
-                amount: peg_out_confirm_transaction.tx().output[kick_off_1_vout_0].value,
-            },
-        );
-        let kick_off_1_txid = kick_off_1_transaction.tx().compute_txid();
+impl Display for Take2Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Take2Error::SigningFailed(err) => write!(f, "{err}"),
+            Take2Error::Action(err) => write!(f, "{err}"),
+        }
+    }
+}
 
-        let start_time_vout_0 = 2;
-        let start_time_transaction = StartTimeTransaction::new(
-            context,
-            &connectors.connector_2,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: start_time_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[start_time_vout_0].value,
-            },
-        );
+// The disprove witness does not satisfy the connector c leaf it spends, the disprove
+// tx would be rejected.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct InvalidDisproveWitness {
+    pub script_index: u32,
+}
 
-        let start_time_timeout_vout_0 = 2;
-        let start_time_timeout_vout_1 = 1;
-        let start_time_timeout_transaction = StartTimeTimeoutTransaction::new(
-            context,
-            &connectors.connector_1,
-            &connectors.connector_2,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: start_time_timeout_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[start_time_timeout_vout_0].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: start_time_timeout_vout_1.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[start_time_timeout_vout_1].value,
-            },
-        );
+impl Display for InvalidDisproveWitness {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "Disprove witness does not satisfy connector c leaf {}",
+            self.script_index
+        )
+    }
+}
 
-        let kick_off_2_vout_0 = 1;
-        let kick_off_2_transaction = KickOff2Transaction::new(
-            context,
-            &connectors.connector_1,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: kick_off_2_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[kick_off_2_vout_0].value,
-            },
-        );
-        let kick_off_2_txid = kick_off_2_transaction.tx().compute_txid();
+// Neither the action nor the graph names the output script the reward is paid to.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct MissingRewardScript;
 
-        let kick_off_timeout_vout_0 = 1;
-        let kick_off_timeout_transaction = KickOffTimeoutTransaction::new(
-            context,
-            &connectors.connector_1,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: kick_off_timeout_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[kick_off_timeout_vout_0].value,
-            },
-        );
+impl Display for MissingRewardScript {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "No reward output script provided and no default set")
+    }
+}
 
-        let input_amount_crowdfunding = Amount::from_btc(1.0).unwrap(); // TODO replace placeholder
-        let challenge_vout_0 = 0;
-        let challenge_transaction = ChallengeTransaction::new(
-            context,
-            &connectors.connector_a,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: challenge_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[challenge_vout_0].value,
-            },
-            input_amount_crowdfunding,
-        );
+// Error of the start time timeout and kick-off timeout actions.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum TimeoutError {
+    MissingRewardScript(MissingRewardScript),
+    Action(ActionError),
+}
 
-        let take_1_vout_0 = 0;
-        let take_1_vout_1 = 0;
-        let take_1_vout_2 = 0;
-        let take_1_vout_3 = 1;
-        let take_1_transaction = Take1Transaction::new(
-            context,
-            &connectors.connector_0,
-            &connectors.connector_3,
-            &connectors.connector_a,
-            &connectors.connector_b,
-            Input {
-                outpoint: OutPoint {
-                    txid: peg_in_confirm_txid,
-                    vout: take_1_vout_0.to_u32().unwrap(),
-                },
-                amount: peg_in_confirm_transaction.tx().output[take_1_vout_0].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: take_1_vout_1.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[take_1_vout_1].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_2_txid,
-                    vout: take_1_vout_2.to_u32().unwrap(),
-                },
-                amount: kick_off_2_transaction.tx().output[take_1_vout_2].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_2_txid,
-                    vout: take_1_vout_3.to_u32().unwrap(),
-                },
-                amount: kick_off_2_transaction.tx().output[take_1_vout_3].value,
-            },
-        );
+impl Display for TimeoutError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            TimeoutError::MissingRewardScript(err) => write!(f, "{err}"),
+            TimeoutError::Action(err) => write!(f, "{err}"),
+        }
+    }
+}
 
-        // assert initial
-        let assert_initial_vout_0 = 1;
-        let assert_initial_transaction = AssertInitialTransaction::new(
-            &connectors.connector_b,
-            &connectors.connector_d,
-            &connectors.assert_commit_connectors_e_1,
-            &connectors.assert_commit_connectors_e_2,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_2_txid,
-                    vout: assert_initial_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_2_transaction.tx().output[assert_initial_vout_0].value,
-            },
-        );
-        let assert_initial_txid = assert_initial_transaction.tx().compute_txid();
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ChallengeError {
+    MissingRewardScript(MissingRewardScript),
+    Funding(ChallengeFundingError),
+    Action(ActionError),
+}
 
-        // assert commit txs
-        let mut vout_base = 1;
-        let assert_commit1_transaction = AssertCommit1Transaction::new(
-            &connectors.assert_commit_connectors_e_1,
-            &connectors.assert_commit_connectors_f.connector_f_1,
-            (0..connectors.assert_commit_connectors_e_1.connectors_num())
-                .map(|idx| Input {
-                    outpoint: OutPoint {
-                        txid: assert_initial_transaction.tx().compute_txid(),
-                        vout: (idx + vout_base).to_u32().unwrap(),
-                    },
-                    amount: assert_initial_transaction.tx().output[idx + vout_base].value,
-                })
-                .collect(),
-        );
+impl Display for ChallengeError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ChallengeError::MissingRewardScript(err) => write!(f, "{err}"),
+            ChallengeError::Funding(err) => write!(f, "{err}"),
+            ChallengeError::Action(err) => write!(f, "{err}"),
+        }
+    }
+}
 
-        vout_base += connectors.assert_commit_connectors_e_1.connectors_num();
+// The assert chain was only partly published, `broadcast` holds the txids that
+// are in the mempool or mined, in chain order.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AssertChainBroadcastError {
+    pub broadcast: Vec<Txid>,
+    pub failed_tx: &'static str,
+    pub error: String,
+}
 
-        let assert_commit2_transaction = AssertCommit2Transaction::new(
-            &connectors.assert_commit_connectors_e_2,
-            &connectors.assert_commit_connectors_f.connector_f_2,
-            (0..connectors.assert_commit_connectors_e_2.connectors_num())
-                .map(|idx| Input {
-                    outpoint: OutPoint {
-                        txid: assert_initial_transaction.tx().compute_txid(),
-                        vout: (idx + vout_base).to_u32().unwrap(),
-                    },
-                    amount: assert_initial_transaction.tx().output[idx + vout_base].value,
-                })
-                .collect(),
-        );
+impl Display for AssertChainBroadcastError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "Failed to broadcast {} after {} assert chain txs: {}",
+            self.failed_tx,
+            self.broadcast.len(),
+            self.error
+        )
+    }
+}
 
-        // assert final
-        let assert_final_vout_0 = 0;
-        let assert_final_vout_1 = 0;
-        let assert_final_vout_2 = 0;
-        let assert_final_transaction = AssertFinalTransaction::new(
-            context,
-            &connectors.connector_4,
-            &connectors.connector_5,
-            &connectors.connector_c,
-            &connectors.connector_d,
-            &connectors.assert_commit_connectors_f,
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_initial_txid,
-                    vout: assert_final_vout_0.to_u32().unwrap(),
-                },
-                amount: assert_initial_transaction.tx().output[assert_final_vout_0].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_commit1_transaction.tx().compute_txid(),
-                    vout: assert_final_vout_1.to_u32().unwrap(),
-                },
-                amount: assert_commit1_transaction.tx().output[assert_final_vout_1].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_commit2_transaction.tx().compute_txid(),
-                    vout: assert_final_vout_2.to_u32().unwrap(),
-                },
-                amount: assert_commit2_transaction.tx().output[assert_final_vout_2].value,
-            },
-        );
-        let assert_final_txid = assert_final_transaction.tx().compute_txid();
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum AssertCommitWitnessError {
+    NotFound(Txid),
+    Esplora(String),
+    NotAssertCommitTx(Txid), // does not spend the assert commit outputs of the graph
+    MalformedWitness { txid: Txid, input_index: usize }, // not a script path spend
+}
 
-        let take_2_vout_0 = 0;
-        let take_2_vout_1 = 0;
-        let take_2_vout_2 = 1;
-        let take_2_vout_3 = 2;
-        let take_2_transaction = Take2Transaction::new(
-            context,
-            &connectors.connector_0,
-            &connectors.connector_4,
-            &connectors.connector_5,
-            &connectors.connector_c,
-            Input {
-                outpoint: OutPoint {
-                    txid: peg_in_confirm_txid,
-                    vout: take_2_vout_0.to_u32().unwrap(),
-                },
-                amount: peg_in_confirm_transaction.tx().output[take_2_vout_0].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: take_2_vout_1.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[take_2_vout_1].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: take_2_vout_2.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[take_2_vout_2].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: take_2_vout_3.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[take_2_vout_3].value,
-            },
-        );
+impl Display for AssertCommitWitnessError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            AssertCommitWitnessError::NotFound(txid) => {
+                write!(f, "Assert commit tx {txid} not found")
+            }
+            AssertCommitWitnessError::Esplora(err) => {
+                write!(f, "Failed to fetch assert commit tx: {err}")
+            }
+            AssertCommitWitnessError::NotAssertCommitTx(txid) => {
+                write!(f, "Tx {txid} is not an assert commit tx of the graph")
+            }
+            AssertCommitWitnessError::MalformedWitness { txid, input_index } => write!(
+                f,
+                "Input {input_index} of assert commit tx {txid} has no commitment witness"
+            ),
+        }
+    }
+}
 
-        let script_index = 1; // TODO replace placeholder
-        let disprove_vout_0 = 1;
-        let disprove_vout_1 = 2;
-        let disprove_transaction = DisproveTransaction::new(
-            context,
-            &connectors.connector_5,
-            &connectors.connector_c,
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: disprove_vout_0.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[disprove_vout_0].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: disprove_vout_1.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[disprove_vout_1].value,
-            },
-            script_index,
-        );
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum DisproveError {
+    AssertionsValid, // no connector c leaf is satisfied by the committed values
+    InvalidDisproveWitness(InvalidDisproveWitness),
+    MissingRewardScript(MissingRewardScript),
+    Segments(ElementError), // the verifier segments cannot be rebuilt from the commits
+    Action(ActionError),
+}
 
-        let disprove_chain_vout_0 = 1;
-        let disprove_chain_transaction = DisproveChainTransaction::new(
-            context,
-            &connectors.connector_b,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_2_txid,
-                    vout: disprove_chain_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_2_transaction.tx().output[disprove_chain_vout_0].value,
-            },
-        );
+impl Display for DisproveError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            DisproveError::AssertionsValid => {
+                write!(f, "Committed assertions are valid, nothing to disprove")
+            }
+            DisproveError::InvalidDisproveWitness(err) => write!(f, "{err}"),
+            DisproveError::MissingRewardScript(err) => write!(f, "{err}"),
+            DisproveError::Segments(err) => {
+                write!(f, "Failed to rebuild the verifier segments: {err:?}")
+            }
+            DisproveError::Action(err) => write!(f, "{err}"),
+        }
+    }
+}
 
-        (
-            PegOutGraph {
-                version: GRAPH_VERSION.to_string(),
-                network: context.network,
-                id: generate_id(peg_in_graph, &context.operator_public_key),
-                n_of_n_presigned: false,
-                n_of_n_public_key: context.n_of_n_public_key,
-                n_of_n_taproot_public_key: context.n_of_n_taproot_public_key,
-                peg_in_graph_id: peg_in_graph.id().clone(),
-                peg_in_confirm_txid,
-                connector_0: connectors.connector_0,
-                connector_1: connectors.connector_1,
-                connector_2: connectors.connector_2,
-                connector_3: connectors.connector_3,
-                connector_4: connectors.connector_4,
-                connector_5: connectors.connector_5,
-                connector_6: connectors.connector_6,
-                connector_a: connectors.connector_a,
-                connector_b: connectors.connector_b,
-                connector_c: connectors.connector_c,
-                connector_d: connectors.connector_d,
-                connector_e_1: connectors.assert_commit_connectors_e_1,
-                connector_e_2: connectors.assert_commit_connectors_e_2,
-                connector_f_1: connectors.assert_commit_connectors_f.connector_f_1,
-                connector_f_2: connectors.assert_commit_connectors_f.connector_f_2,
-                peg_out_confirm_transaction,
-                assert_initial_transaction,
-                assert_final_transaction,
-                challenge_transaction,
-                disprove_chain_transaction,
-                disprove_transaction,
-                kick_off_1_transaction,
-                kick_off_2_transaction,
-                kick_off_timeout_transaction,
-                start_time_transaction,
-                start_time_timeout_transaction,
-                take_1_transaction,
-                take_2_transaction,
-                operator_public_key: context.operator_public_key,
-                operator_taproot_public_key: context.operator_taproot_public_key,
-                peg_out_chain_event: None,
-                peg_out_transaction: None,
-            },
-            commitment_secrets,
-        )
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum GraphVersionError {
+    Missing,             // no version field
+    Unsupported(String), // neither the current version nor one that can be migrated
+    Malformed(String),   // not a graph of its version
+}
+
+impl Display for GraphVersionError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            GraphVersionError::Missing => write!(f, "Graph has no version"),
+            GraphVersionError::Unsupported(version) => write!(
+                f,
+                "Unsupported graph version {version}, expected {GRAPH_VERSION}"
+            ),
+            GraphVersionError::Malformed(err) => write!(f, "Failed to parse graph: {err}"),
+        }
     }
+}
 
-    pub fn new_for_validation(&self) -> Self {
-        let peg_in_confirm_txid = self.take_1_transaction.tx().input[0].previous_output.txid; // Self-referencing
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ReconcileOutcome {
+    Unchanged,
+    // stored event replaced, `None` if the event was reorged out
+    Updated(Option<PegOutEvent>),
+    // kick-off 1 committed to the tx hash of the stored event
+    CommittedToStaleEvent { current_event: Option<PegOutEvent> },
+    NotUnique, // more than one L2 event matches the graph
+}
 
-        let connectors = Self::create_new_connectors(
-            self.network,
-            &self.n_of_n_taproot_public_key,
-            &self.operator_taproot_public_key,
-            &self.operator_public_key,
-            &self.connector_1.commitment_public_keys,
-            &self.connector_2.commitment_public_keys,
-            &self.connector_6.commitment_public_keys,
-            &self.connector_e_1.commitment_public_keys(),
-            &self.connector_e_2.commitment_public_keys(),
-        );
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ChallengeReason {
+    // kick-off 1 commits to another tx than the graph's peg-out tx
+    SourceTxidMismatch {
+        committed_txid: Txid,
+        peg_out_txid: Txid,
+    },
+    // the committed peg-out tx never paid out the withdrawer
+    PegOutNotConfirmed(Txid),
+    // the committed start time is not between kick-off 1 and the start time tx
+    StartTimeOutOfRange {
+        start_time: u32,
+        kick_off_1_height: u32,
+        start_time_height: u32,
+    },
+    // a committed superblock is not on the main chain within the measurement period
+    SuperblockOutsideWindow,
+}
 
-        let peg_out_confirm_vout_0 = 0;
-        let peg_out_confirm_transaction = PegOutConfirmTransaction::new_for_validation(
-            self.network,
-            &self.operator_public_key,
-            &connectors.connector_6,
-            Input {
-                outpoint: self.peg_out_confirm_transaction.tx().input[peg_out_confirm_vout_0]
-                    .previous_output, // Self-referencing
-                amount: self.peg_out_confirm_transaction.prev_outs()[peg_out_confirm_vout_0].value, // Self-referencing
-            },
-        );
+impl Display for ChallengeReason {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ChallengeReason::SourceTxidMismatch {
+                committed_txid,
+                peg_out_txid,
+            } => write!(
+                f,
+                "Kick-off 1 commits to peg-out txid {committed_txid}, but the peg-out txid is {peg_out_txid}"
+            ),
+            ChallengeReason::PegOutNotConfirmed(txid) => {
+                write!(f, "Committed peg-out tx {txid} has not been confirmed")
+            }
+            ChallengeReason::StartTimeOutOfRange {
+                start_time,
+                kick_off_1_height,
+                start_time_height,
+            } => write!(
+                f,
+                "Committed start time {start_time} is not between kick-off 1 height {kick_off_1_height} and start time tx height {start_time_height}"
+            ),
+            ChallengeReason::SuperblockOutsideWindow => {
+                write!(f, "Committed superblock is outside of the measurement period")
+            }
+        }
+    }
+}
 
-        let kick_off_1_vout_0 = 0;
-        let kick_off_1_transaction = KickOff1Transaction::new_for_validation(
-            self.network,
-            &self.operator_taproot_public_key,
-            &self.n_of_n_taproot_public_key,
-            &connectors.connector_1,
-            &connectors.connector_2,
-            &connectors.connector_6,
-            Input {
-                outpoint: self.kick_off_1_transaction.tx().input[kick_off_1_vout_0].previous_output, // Self-referencing
-                amount: self.kick_off_1_transaction.prev_outs()[kick_off_1_vout_0].value, // Self-referencing
-            },
-        );
-        let kick_off_1_txid = kick_off_1_transaction.tx().compute_txid();
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ChallengeRecommendation {
+    Challenge { reason: ChallengeReason },
+    NoAction,
+}
 
-        let start_time_vout_0 = 2;
-        let start_time_transaction = StartTimeTransaction::new_for_validation(
-            self.network,
-            &self.operator_public_key,
-            &connectors.connector_2,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: start_time_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[start_time_vout_0].value,
-            },
-        );
+// What a verifier observed of the operator's commitments once kick-off 1 is
+// confirmed. Commitments that are not on chain yet are left as `None`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChallengeEvidence {
+    pub kick_off_1_height: u32,
+    pub committed_source_txid: Txid,
+    pub is_committed_source_tx_confirmed: bool,
+    pub committed_start_time: Option<u32>,
+    pub start_time_height: Option<u32>,
+    pub is_superblock_in_window: Option<bool>,
+}
 
-        let start_time_timeout_vout_0 = 2;
-        let start_time_timeout_vout_1 = 1;
-        let start_time_timeout_transaction = StartTimeTimeoutTransaction::new_for_validation(
-            self.network,
-            &connectors.connector_1,
-            &connectors.connector_2,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: start_time_timeout_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[start_time_timeout_vout_0].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: start_time_timeout_vout_1.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[start_time_timeout_vout_1].value,
-            },
-        );
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TimelockInfo {
+    pub tx_name: &'static str, // transaction that can only be broadcast once the timelock elapses
+    pub connector_name: &'static str, // connector whose output carries the timelock
+    pub leaf_index: Option<u32>, // taproot leaf, None for non-taproot connectors
+    pub num_blocks: u32,
+}
 
-        let kick_off_2_vout_0 = 1;
-        let kick_off_2_transaction = KickOff2Transaction::new_for_validation(
-            self.network,
-            &self.operator_public_key,
-            &self.n_of_n_taproot_public_key,
-            &connectors.connector_1,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: kick_off_2_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[kick_off_2_vout_0].value,
-            },
-        );
-        let kick_off_2_txid = kick_off_2_transaction.tx().compute_txid();
+// The timelock an operator graph is blocked on, for showing how long the wait is.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PendingTimelock {
+    pub connector: String, // connector whose output carries the timelock
+    pub blocks_remaining: u32,
+    pub tx_role: TxRole, // tx that can be broadcast once the timelock elapses
+}
 
-        let kick_off_timeout_vout_0 = 1;
-        let kick_off_timeout_transaction = KickOffTimeoutTransaction::new_for_validation(
-            self.network,
-            &connectors.connector_1,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: kick_off_timeout_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[kick_off_timeout_vout_0].value,
-            },
-        );
+// What the peg-out tx pays the withdrawer, derived from the peg-out event alone.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WithdrawerExpectation {
+    pub destination_address: Address, // P2PKH address holding the withdrawer's key
+    pub payout_script_pubkey: ScriptBuf, // output script the peg-out tx pays to
+    pub amount: Amount,               // burnt amount less the peg-out tx fee
+}
 
-        let input_amount_crowdfunding = Amount::from_btc(1.0).unwrap(); // TODO replace placeholder
-        let challenge_vout_0 = 0;
-        let challenge_transaction = ChallengeTransaction::new_for_validation(
-            self.network,
-            &self.operator_public_key,
-            &self.connector_a,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: challenge_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[challenge_vout_0].value,
-            },
-            input_amount_crowdfunding,
-        );
+// What the operator risks by not broadcasting a tx in time.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum DeadlineConsequence {
+    StartTimeTimeout, // start time timeout burns the kick-off 1 funds
+    KickOffTimeout,   // kick-off timeout burns the kick-off 1 funds
+    // the challenge blocks take 1, only asserting leads to a payout
+    ChallengeUnanswered,
+    // every block the take is delayed gives challengers and disprovers more time
+    TakeContested,
+}
 
-        let take_1_vout_0 = 0;
-        let take_1_vout_1 = 0;
-        let take_1_vout_2 = 0;
-        let take_1_vout_3 = 1;
-        let take_1_transaction = Take1Transaction::new_for_validation(
-            self.network,
-            &self.operator_public_key,
-            &connectors.connector_0,
-            &connectors.connector_3,
-            &connectors.connector_a,
-            &connectors.connector_b,
-            Input {
-                outpoint: OutPoint {
-                    txid: peg_in_confirm_txid,
-                    vout: take_1_vout_0.to_u32().unwrap(),
-                },
-                amount: self.take_1_transaction.prev_outs()[take_1_vout_0].value, // Self-referencing
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_1_txid,
-                    vout: take_1_vout_1.to_u32().unwrap(),
-                },
-                amount: kick_off_1_transaction.tx().output[take_1_vout_1].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_2_txid,
-                    vout: take_1_vout_2.to_u32().unwrap(),
-                },
-                amount: kick_off_2_transaction.tx().output[take_1_vout_2].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_2_txid,
-                    vout: take_1_vout_3.to_u32().unwrap(),
-                },
-                amount: kick_off_2_transaction.tx().output[take_1_vout_3].value,
-            },
-        );
+// A pending operator obligation. For the timeouts, `height` is the first block at
+// which the counterparty can punish the operator. For assert and take it is the
+// first block at which the operator can act and should do so.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Deadline {
+    pub tx_name: &'static str,
+    pub height: u32,
+    pub consequence: DeadlineConsequence,
+}
 
-        // assert initial
-        let assert_initial_vout_0 = 1;
-        let assert_initial_transaction = AssertInitialTransaction::new_for_validation(
-            &connectors.connector_b,
-            &connectors.connector_d,
-            &connectors.assert_commit_connectors_e_1,
-            &connectors.assert_commit_connectors_e_2,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_2_txid,
-                    vout: assert_initial_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_2_transaction.tx().output[assert_initial_vout_0].value,
-            },
-        );
-        let assert_initial_txid = assert_initial_transaction.tx().compute_txid();
+// Fees and amounts of a challenged peg-out, read from the graph. The operator
+// answers the challenge with the assert txs and is reimbursed by take 2, the
+// verifiers crowdfund the challenge and disprove a faulty assert.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct DisputePathCost {
+    pub assert_initial_fee: Amount,
+    pub assert_commit_fees: Amount, // assert commit 1 and 2
+    pub assert_final_fee: Amount,
+    pub take_2_fee: Amount,
+    pub take_2_payout: Amount,     // reimburses the operator
+    pub challenge_funding: Amount, // crowdfunded by the verifiers
+    pub challenge_fee: Amount,
+    pub disprove_fee: Amount,
+}
 
-        // assert commit txs
-        let mut vout_base = 1;
-        let assert_commit_1_transaction = AssertCommit1Transaction::new_for_validation(
-            &connectors.assert_commit_connectors_e_1,
-            &connectors.assert_commit_connectors_f.connector_f_1,
-            (0..connectors.assert_commit_connectors_e_1.connectors_num())
-                .map(|idx| Input {
-                    outpoint: OutPoint {
-                        txid: assert_initial_transaction.tx().compute_txid(),
-                        vout: (idx + vout_base).to_u32().unwrap(),
-                    },
-                    amount: assert_initial_transaction.tx().output[idx + vout_base].value,
-                })
-                .collect(),
-        );
+impl DisputePathCost {
+    // Fees the operator spends from answering the challenge to take 2.
+    pub fn operator_cost(&self) -> Amount {
+        self.assert_initial_fee + self.assert_commit_fees + self.assert_final_fee + self.take_2_fee
+    }
 
-        vout_base += connectors.assert_commit_connectors_e_1.connectors_num();
+    // The crowdfunded challenge and the fees of the txs on the verifiers' path.
+    pub fn verifier_cost(&self) -> Amount {
+        self.challenge_funding + self.challenge_fee + self.disprove_fee
+    }
+}
 
-        let assert_commit_2_transaction = AssertCommit2Transaction::new_for_validation(
-            &connectors.assert_commit_connectors_e_2,
-            &connectors.assert_commit_connectors_f.connector_f_2,
-            (0..connectors.assert_commit_connectors_e_2.connectors_num())
-                .map(|idx| Input {
-                    outpoint: OutPoint {
-                        txid: assert_initial_transaction.tx().compute_txid(),
-                        vout: (idx + vout_base).to_u32().unwrap(),
-                    },
-                    amount: assert_initial_transaction.tx().output[idx + vout_base].value,
-                })
-                .collect(),
-        );
+// Upper bounds of the buckets of `FleetMetrics::deadline_histogram` in blocks left
+// until the deadline: an hour, six hours, a day and a week.
+pub const DEADLINE_HISTOGRAM_BUCKETS: [u32; 4] = [6, 36, 144, 1008];
+
+// Summary of the peg-out graphs of an operator, for a dashboard. A graph only
+// counts towards the histogram with its soonest deadline.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct FleetMetrics {
+    pub phase_counts: BTreeMap<GraphPhase, usize>,
+    pub failed: usize, // settled by a timeout or a disprove
+    // take 1 payouts of the graphs the operator has paid out for and awaits
+    pub awaiting_reimbursement: Amount,
+    pub dispute_costs: Amount,     // operator cost of the disputed graphs
+    pub imminent_deadlines: usize, // within the first histogram bucket
+    // graphs by bucket, the ones past the last bucket under `u32::MAX`
+    pub deadline_histogram: BTreeMap<u32, usize>,
+}
 
-        // assert final
-        let assert_final_vout_0 = 0;
-        let assert_final_vout_1 = 0;
-        let assert_final_vout_2 = 0;
-        let assert_final_vout_3 = 0;
-        let assert_final_vout_4 = 0;
-        let assert_final_vout_5 = 0;
-        let assert_final_transaction = AssertFinalTransaction::new_for_validation(
-            &connectors.connector_4,
-            &connectors.connector_5,
-            &connectors.connector_c,
-            &connectors.connector_d,
-            &connectors.assert_commit_connectors_f,
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_initial_txid,
-                    vout: assert_final_vout_0.to_u32().unwrap(),
-                },
-                amount: assert_initial_transaction.tx().output[assert_final_vout_0].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_commit_1_transaction.tx().compute_txid(),
-                    vout: assert_final_vout_1.to_u32().unwrap(),
-                },
-                amount: assert_commit_1_transaction.tx().output[assert_final_vout_1].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_commit_2_transaction.tx().compute_txid(),
-                    vout: assert_final_vout_2.to_u32().unwrap(),
-                },
-                amount: assert_commit_2_transaction.tx().output[assert_final_vout_2].value,
-            },
-        );
-        let assert_final_txid = assert_final_transaction.tx().compute_txid();
+// Assert-commit connector set holding a Groth16 intermediate value commitment.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+pub enum ConnectorRef {
+    E1, // spent by assert commit 1
+    E2, // spent by assert commit 2
+}
 
-        let take_2_vout_0 = 0;
-        let take_2_vout_1 = 0;
-        let take_2_vout_2 = 1;
-        let take_2_vout_3 = 2;
-        let take_2_transaction = Take2Transaction::new_for_validation(
-            self.network,
-            &self.operator_public_key,
-            &connectors.connector_0,
-            &connectors.connector_4,
-            &connectors.connector_5,
-            &connectors.connector_c,
-            Input {
-                outpoint: OutPoint {
-                    txid: peg_in_confirm_txid,
-                    vout: take_2_vout_0.to_u32().unwrap(),
-                },
-                amount: self.take_2_transaction.prev_outs()[take_2_vout_0].value, // Self-referencing
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: take_2_vout_1.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[take_2_vout_1].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: take_2_vout_2.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[take_2_vout_2].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: take_2_vout_3.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[take_2_vout_3].value,
-            },
-        );
+// A verifier's nonces and signatures for a graph, exchanged instead of the
+// whole graph and applied with `PegOutGraph::apply_bundle`.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct SignatureBundle {
+    pub graph_id: GraphId,
+    pub per_tx: BTreeMap<Txid, PartialSigs>,
+}
 
-        let script_index = 1; // TODO replace placeholder
-        let disprove_vout_0 = 1;
-        let disprove_vout_1 = 2;
-        let disprove_transaction = DisproveTransaction::new_for_validation(
-            self.network,
-            &self.connector_5,
-            &self.connector_c,
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: disprove_vout_0.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[disprove_vout_0].value,
-            },
-            Input {
-                outpoint: OutPoint {
-                    txid: assert_final_txid,
-                    vout: disprove_vout_1.to_u32().unwrap(),
-                },
-                amount: assert_final_transaction.tx().output[disprove_vout_1].value,
-            },
-            script_index,
-        );
+// A verifier's secret nonces for a graph, encrypted so that they can be kept
+// across restarts between pushing the nonces and signing. Restore them with
+// `PegOutGraph::import_secret_nonces`.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct EncryptedNonceBlob {
+    pub graph_id: GraphId,
+    pub verifier_public_key: PublicKey,
+    nonce: [u8; 12],
+    ciphertext: Vec<u8>,
+}
 
-        let disprove_chain_vout_0 = 1;
-        let disprove_chain_transaction = DisproveChainTransaction::new_for_validation(
-            self.network,
-            &self.connector_b,
-            Input {
-                outpoint: OutPoint {
-                    txid: kick_off_2_txid,
-                    vout: disprove_chain_vout_0.to_u32().unwrap(),
-                },
-                amount: kick_off_2_transaction.tx().output[disprove_chain_vout_0].value,
-            },
-        );
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum NonceBlobError {
+    GraphMismatch,       // blob of another graph or verifier
+    Decryption,          // wrong key, or the blob has been tampered with
+    StaleNonces(Txid),   // the graph holds public nonces of another nonce round
+    AlreadySigned(Txid), // the nonces have already been used for signing
+}
 
-        PegOutGraph {
-            version: GRAPH_VERSION.to_string(),
-            network: self.network,
-            id: self.id.clone(),
-            n_of_n_presigned: false,
-            n_of_n_public_key: self.n_of_n_public_key,
-            n_of_n_taproot_public_key: self.n_of_n_taproot_public_key,
-            peg_in_graph_id: self.peg_in_graph_id.clone(),
-            peg_in_confirm_txid,
-            connector_0: connectors.connector_0,
-            connector_1: connectors.connector_1,
-            connector_2: connectors.connector_2,
-            connector_3: connectors.connector_3,
-            connector_4: connectors.connector_4,
-            connector_5: connectors.connector_5,
-            connector_6: connectors.connector_6,
-            connector_a: connectors.connector_a,
-            connector_b: connectors.connector_b,
+impl Display for NonceBlobError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            NonceBlobError::GraphMismatch => {
+                write!(
+                    f,
+                    "Secret nonces were exported for another graph or verifier"
+                )
+            }
+            NonceBlobError::Decryption => write!(f, "Failed to decrypt secret nonces"),
+            NonceBlobError::StaleNonces(txid) => write!(
+                f,
+                "Secret nonces for tx {txid} do not match the public nonces in the graph"
+            ),
+            NonceBlobError::AlreadySigned(txid) => {
+                write!(
+                    f,
+                    "Secret nonces for tx {txid} have already been used to sign"
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum TakeKind {
+    Take1,
+    Take2,
+}
+
+// Why `new_for_validation` cannot rebuild a graph from its own keys and inputs.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ValidationRebuildError {
+    MalformedTxs(Vec<&'static str>), // see `PegOutGraph::structural_completeness`
+    MissingCommitmentKey(CommitmentMessageId),
+    KeyMismatch(KeyError),
+    PegOutConfirmAmount(PegOutConfirmAmountError),
+    Construction(GraphConstructionError),
+}
+
+impl Display for ValidationRebuildError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ValidationRebuildError::MalformedTxs(tx_names) => {
+                write!(f, "Missing or orphaned txs: {}", tx_names.join(", "))
+            }
+            ValidationRebuildError::MissingCommitmentKey(message_id) => {
+                write!(f, "No commitment public key for {message_id:?}")
+            }
+            ValidationRebuildError::KeyMismatch(err) => write!(f, "{err}"),
+            ValidationRebuildError::PegOutConfirmAmount(err) => write!(f, "{err}"),
+            ValidationRebuildError::Construction(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum KeyError {
+    NofNTaprootKeyMismatch,
+    OperatorTaprootKeyMismatch,
+    MissingNofNPublicKeys,
+    NofNPublicKeysMismatch,
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            KeyError::NofNTaprootKeyMismatch => {
+                write!(
+                    f,
+                    "N-of-n taproot public key does not match n-of-n public key"
+                )
+            }
+            KeyError::OperatorTaprootKeyMismatch => {
+                write!(
+                    f,
+                    "Operator taproot public key does not match operator public key"
+                )
+            }
+            KeyError::MissingNofNPublicKeys => write!(f, "No individual n-of-n public keys"),
+            KeyError::NofNPublicKeysMismatch => {
+                write!(
+                    f,
+                    "Individual n-of-n public keys do not aggregate to the n-of-n public key"
+                )
+            }
+        }
+    }
+}
+
+// Operator actions that broadcast a graph transaction, see `PegOutGraph::guarded_action`.
+pub enum OperatorAction<'a> {
+    PegOutConfirm,
+    KickOff1 {
+        source_network_txid_commitment_secret: &'a WinternitzSecret,
+        destination_network_txid_commitment_secret: &'a WinternitzSecret,
+    },
+    StartTime {
+        start_time_commitment_secret: &'a WinternitzSecret,
+    },
+    KickOff2 {
+        blockchain_height: Option<u32>,
+        superblock_commitment_secret: &'a WinternitzSecret,
+        superblock_hash_commitment_secret: &'a WinternitzSecret,
+        superblock_criteria: &'a SuperblockCriteria,
+    },
+    AssertInitial {
+        blockchain_height: Option<u32>,
+    },
+    AssertFinal,
+    Take1 {
+        blockchain_height: Option<u32>,
+    },
+    Take2 {
+        blockchain_height: Option<u32>,
+    },
+}
+
+impl OperatorAction<'_> {
+    pub fn kind(&self) -> ActionKind {
+        match self {
+            OperatorAction::PegOutConfirm => ActionKind::PegOutConfirm,
+            OperatorAction::KickOff1 { .. } => ActionKind::KickOff1,
+            OperatorAction::StartTime { .. } => ActionKind::StartTime,
+            OperatorAction::KickOff2 { .. } => ActionKind::KickOff2,
+            OperatorAction::AssertInitial { .. } => ActionKind::AssertInitial,
+            OperatorAction::AssertFinal => ActionKind::AssertFinal,
+            OperatorAction::Take1 { .. } => ActionKind::Take1,
+            OperatorAction::Take2 { .. } => ActionKind::Take2,
+        }
+    }
+
+    pub fn tx_name(&self) -> &'static str { self.kind().tx_name() }
+
+    pub fn tx_role(&self) -> TxRole { self.kind().tx_role() }
+}
+
+// An operator action without its arguments, used to look up what it needs before running it.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ActionKind {
+    PegOutConfirm,
+    KickOff1,
+    StartTime,
+    KickOff2,
+    AssertInitial,
+    AssertFinal,
+    Take1,
+    Take2,
+}
+
+impl ActionKind {
+    pub fn tx_name(&self) -> &'static str {
+        match self {
+            ActionKind::PegOutConfirm => "peg_out_confirm",
+            ActionKind::KickOff1 => "kick_off_1",
+            ActionKind::StartTime => "start_time",
+            ActionKind::KickOff2 => "kick_off_2",
+            ActionKind::AssertInitial => "assert_initial",
+            ActionKind::AssertFinal => "assert_final",
+            ActionKind::Take1 => "take_1",
+            ActionKind::Take2 => "take_2",
+        }
+    }
+
+    pub fn tx_role(&self) -> TxRole {
+        match self {
+            ActionKind::PegOutConfirm => TxRole::PegOutConfirm,
+            ActionKind::KickOff1 => TxRole::KickOff1,
+            ActionKind::StartTime => TxRole::StartTime,
+            ActionKind::KickOff2 => TxRole::KickOff2,
+            ActionKind::AssertInitial => TxRole::AssertInitial,
+            ActionKind::AssertFinal => TxRole::AssertFinal,
+            ActionKind::Take1 => TxRole::Take1,
+            ActionKind::Take2 => TxRole::Take2,
+        }
+    }
+}
+
+// The commitment secrets an operator action signs with, see `PegOutGraph::secrets_for`.
+#[derive(Eq, PartialEq, Clone, Copy)]
+pub enum ActionSecrets<'a> {
+    None,
+    KickOff1 {
+        source_network_txid_commitment_secret: &'a WinternitzSecret,
+        destination_network_txid_commitment_secret: &'a WinternitzSecret,
+    },
+    StartTime {
+        start_time_commitment_secret: &'a WinternitzSecret,
+    },
+    KickOff2 {
+        superblock_commitment_secret: &'a WinternitzSecret,
+        superblock_hash_commitment_secret: &'a WinternitzSecret,
+    },
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MissingSecret(pub CommitmentMessageId);
+
+impl Display for MissingSecret {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Missing commitment secret for {:?}", self.0)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum GuardedActionError {
+    InvalidGraph(GraphId),
+    PegOutTxidChanged(PegOutTxidChanged),
+    Action(ActionError),
+    NoSuperblockFound(NoSuperblockFound),
+    Take2SigningFailed(Take2SigningFailed),
+    WrongCommitmentSecret(WrongCommitmentSecret),
+    Challenge(ChallengeError),
+}
+
+impl Display for GuardedActionError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            GuardedActionError::InvalidGraph(id) => {
+                write!(f, "Graph {id} failed validation, refusing to broadcast")
+            }
+            GuardedActionError::PegOutTxidChanged(err) => write!(f, "{err}"),
+            GuardedActionError::Action(err) => write!(f, "{err}"),
+            GuardedActionError::NoSuperblockFound(err) => write!(f, "{err}"),
+            GuardedActionError::Take2SigningFailed(err) => write!(f, "{err}"),
+            GuardedActionError::WrongCommitmentSecret(err) => write!(f, "{err}"),
+            GuardedActionError::Challenge(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ActionInProgress {
+    pub conflicting: TxRole, // tx already being broadcast from the same output
+}
+
+impl Display for ActionInProgress {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "Conflicting {} tx has already been broadcast from this graph",
+            self.conflicting.tx_name()
+        )
+    }
+}
+
+// The reason an action did not broadcast its tx, besides the errors specific to
+// the action.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ActionError {
+    InProgress(ActionInProgress),
+    AlreadyMined(Txid),
+    NotReady(&'static str), // the txs the action follows are unconfirmed or still timelocked
+    Esplora(String),
+    BroadcastFailed(String),
+}
+
+impl Display for ActionError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ActionError::InProgress(err) => write!(f, "{err}"),
+            ActionError::AlreadyMined(txid) => write!(f, "Tx {txid} is already mined"),
+            ActionError::NotReady(reason) => write!(f, "{reason}"),
+            ActionError::Esplora(err) => write!(f, "Esplora request failed: {err}"),
+            ActionError::BroadcastFailed(err) => write!(f, "Failed to broadcast tx: {err}"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PayoutCommitted {
+    pub tx_name: &'static str,
+    pub input_index: usize, // presigned input whose signature commits to the payout
+}
+
+impl Display for PayoutCommitted {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "Payout of {} tx is fixed by the presigned signature of input {}",
+            self.tx_name, self.input_index
+        )
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum PayoutOverrideError {
+    NoPayout(&'static str), // name of the tx without an output paying the operator
+    Committed(PayoutCommitted),
+}
+
+impl Display for PayoutOverrideError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            PayoutOverrideError::NoPayout(tx_name) => {
+                write!(f, "No operator payout in {tx_name} tx")
+            }
+            PayoutOverrideError::Committed(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum PegOutConfirmError {
+    OutputCountMismatch { expected: usize, onchain: usize },
+    OutputMismatch(usize), // vout of the first output that differs from the graph
+    InputMismatch(usize),  // index of the first graph input the tx does not spend
+    ClientError(String),
+}
+
+impl Display for PegOutConfirmError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            PegOutConfirmError::OutputCountMismatch { expected, onchain } => {
+                write!(
+                    f,
+                    "On-chain peg-out confirm tx has {onchain} outputs, graph expects {expected}"
+                )
+            }
+            PegOutConfirmError::OutputMismatch(vout) => {
+                write!(
+                    f,
+                    "On-chain peg-out confirm output {vout} does not match the graph"
+                )
+            }
+            PegOutConfirmError::InputMismatch(input_index) => {
+                write!(
+                    f,
+                    "On-chain peg-out confirm tx does not spend input {input_index} of the graph"
+                )
+            }
+            PegOutConfirmError::ClientError(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Liveness {
+    Alive,
+    Stalled { reason: String }, // cannot progress until a prerequisite confirms
+    Dead { reason: String },    // a prerequisite was spent outside of the graph
+}
+
+// An output the graph depends on but does not create itself.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PrerequisiteOutput {
+    pub name: &'static str,
+    pub outpoint: OutPoint,
+    pub graph_spending_txids: Vec<Txid>, // graph transactions allowed to spend the output
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PrerequisiteOutputStatus {
+    pub outpoint: OutPoint,
+    pub is_funding_tx_confirmed: bool,
+    pub spending_txid: Option<Txid>,
+}
+
+// Validation of a peg-out graph within a batch of graphs built on the same peg-in.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct ValidationReport {
+    pub is_peg_in_confirmed: bool,
+    pub is_peg_in_valid: bool,
+    pub spends_peg_in: bool, // both take txs pay out of the peg-in confirm output
+    pub is_valid: bool,      // result of `PegOutGraph::validate`
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.is_peg_in_confirmed && self.is_peg_in_valid && self.spends_peg_in && self.is_valid
+    }
+}
+
+fn checked_vout(vout: usize, field: &str) -> Result<u32, GraphConstructionError> {
+    vout.to_u32()
+        .ok_or_else(|| GraphConstructionError::InvalidVout(field.to_string()))
+}
+
+// Txids of the graph transactions by tx role, of the rebuilt assert commit txs
+// and of the peg-out tx, computed on first use. Not part of the graph's
+// identity, so two graphs compare equal regardless of the cache.
+#[derive(Clone, Default)]
+struct TxidCache {
+    roles: OnceLock<BTreeMap<TxRole, Txid>>,
+    assert_commits: OnceLock<(Txid, Txid)>,
+    peg_out: OnceLock<Option<Txid>>,
+}
+
+impl PartialEq for TxidCache {
+    fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl Eq for TxidCache {}
+
+// Whether `validate()` passed on the graph in its current state. Like the txid
+// cache not part of the graph's identity, so a validated graph still compares
+// equal to its stored copy.
+#[derive(Clone, Default)]
+struct ValidationFlag(bool);
+
+impl PartialEq for ValidationFlag {
+    fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl Eq for ValidationFlag {}
+
+// Graph outputs spent by the txs broadcast from this process, by tx role. Only
+// a local interlock against conflicting actions, so it is never persisted and
+// not part of the graph's identity either.
+#[derive(Clone, Default)]
+struct ActionLock(HashMap<OutPoint, TxRole>);
+
+impl PartialEq for ActionLock {
+    fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl Eq for ActionLock {}
+
+// Settings of a peg-out graph that are neither taken from the peg-in graph nor
+// from the operator context. Only set through the `with_*` methods, so that
+// `placeholder_challenge_amount` always tracks `challenge_amount`.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Debug)]
+pub struct PegOutGraphConfig {
+    challenge_amount: Amount, // crowdfunded by the challengers, matches the operator stake
+    #[serde(default)]
+    take_anchors: bool, // anchor output on take 1 and take 2 to bump their fee through CPFP
+    // Key of the proof system the operator asserts proofs of, the disprove leaves of
+    // connector c are built from it.
+    #[serde(
+        default = "default_verifying_key",
+        serialize_with = "serialize_canonical",
+        deserialize_with = "deserialize_canonical"
+    )]
+    verifying_key: VerifyingKey<Bn254>,
+    // Whether `challenge_amount` is still the default stand-in, see `reject_placeholders`
+    #[serde(default = "default_placeholder_challenge_amount")]
+    placeholder_challenge_amount: bool,
+}
+
+impl Default for PegOutGraphConfig {
+    fn default() -> Self {
+        PegOutGraphConfig {
+            challenge_amount: PLACEHOLDER_CROWDFUNDING_AMOUNT,
+            take_anchors: false,
+            verifying_key: default_verifying_key(),
+            placeholder_challenge_amount: true,
+        }
+    }
+}
+
+impl PegOutGraphConfig {
+    pub fn with_challenge_amount(self, challenge_amount: Amount) -> Self {
+        PegOutGraphConfig {
+            challenge_amount,
+            placeholder_challenge_amount: false,
+            ..self
+        }
+    }
+
+    pub fn with_take_anchors(self, take_anchors: bool) -> Self {
+        PegOutGraphConfig {
+            take_anchors,
+            ..self
+        }
+    }
+
+    pub fn with_verifying_key(self, verifying_key: VerifyingKey<Bn254>) -> Self {
+        PegOutGraphConfig {
+            verifying_key,
+            ..self
+        }
+    }
+
+    pub fn challenge_amount(&self) -> Amount { self.challenge_amount }
+
+    pub fn take_anchors(&self) -> bool { self.take_anchors }
+
+    pub fn verifying_key(&self) -> &VerifyingKey<Bn254> { &self.verifying_key }
+
+    pub fn is_placeholder_challenge_amount(&self) -> bool { self.placeholder_challenge_amount }
+}
+
+// Graphs stored before the flag was kept may carry the placeholder, they are
+// treated as if they do.
+fn default_placeholder_challenge_amount() -> bool { true }
+
+// Graphs stored before the key was kept were built from the default proof's key.
+fn default_verifying_key() -> VerifyingKey<Bn254> { RawProof::default().vk }
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct PegOutGraph {
+    version: String,
+    network: Network,
+    id: String,
+
+    // state: State,
+    // n_of_n_pre_signing_state: PreSigningState,
+    n_of_n_presigned: bool,
+    n_of_n_public_key: PublicKey,
+    n_of_n_taproot_public_key: XOnlyPublicKey,
+    // Individual verifier keys in aggregation order, empty for graphs stored before
+    // they were kept here.
+    #[serde(default)]
+    n_of_n_public_keys: Vec<PublicKey>,
+
+    pub peg_in_graph_id: String,
+    peg_in_confirm_txid: Txid,
+
+    // Note that only the connectors that are used with message commitments are
+    // required to be here. They carry the Winternitz public keys, which need
+    // to be pushed to remote data store. The remaining connectors can be
+    // constructed dynamically.
+    connector_0: Connector0,
+    connector_1: Connector1,
+    connector_2: Connector2,
+    connector_3: Connector3,
+    connector_4: Connector4,
+    connector_5: Connector5,
+    connector_6: Connector6,
+    connector_a: ConnectorA,
+    connector_b: ConnectorB,
+    connector_c: ConnectorC,
+    connector_d: ConnectorD,
+    connector_e_1: AssertCommit1ConnectorsE,
+    connector_e_2: AssertCommit2ConnectorsE,
+    connector_f_1: ConnectorF1,
+    connector_f_2: ConnectorF2,
+
+    peg_out_confirm_transaction: PegOutConfirmTransaction,
+    assert_initial_transaction: AssertInitialTransaction,
+    assert_final_transaction: AssertFinalTransaction,
+    challenge_transaction: ChallengeTransaction,
+    disprove_chain_transaction: DisproveChainTransaction,
+    disprove_transaction: DisproveTransaction,
+    kick_off_1_transaction: KickOff1Transaction,
+    kick_off_2_transaction: KickOff2Transaction,
+    kick_off_timeout_transaction: KickOffTimeoutTransaction,
+    start_time_transaction: StartTimeTransaction,
+    start_time_timeout_transaction: StartTimeTimeoutTransaction,
+    take_1_transaction: Take1Transaction,
+    take_2_transaction: Take2Transaction,
+
+    operator_public_key: PublicKey,
+    operator_taproot_public_key: XOnlyPublicKey,
+
+    pub peg_out_chain_event: Option<PegOutEvent>,
+    peg_out_transaction: Option<PegOutTransaction>,
+
+    // Default destination for the challenge, timeout and disprove rewards,
+    // used when no output script is given at broadcast time. Never persisted, so
+    // that a graph merged from another party never pays out to its address;
+    // `BitVMClient` sets it again from its private data whenever it loads the graph.
+    #[serde(skip)]
+    reward_script_pubkey: Option<ScriptBuf>,
+
+    // Peg-out txid committed to in the kick-off 1 signature
+    #[serde(default)]
+    kick_off_1_peg_out_txid: Option<Txid>,
+
+    // Id of the graph in the operator's own records, e.g. a ticket or ledger entry.
+    // Stored with the graph, but neither part of the graph id nor validated.
+    #[serde(default)]
+    external_ref: Option<String>,
+
+    // Kept for `new_for_validation`, graphs stored before it was default to 1 BTC
+    #[serde(default)]
+    config: PegOutGraphConfig,
+
+    // Set once `validate()` passed, never persisted so that a graph loaded from
+    // storage is always validated again before the first guarded action.
+    #[serde(skip)]
+    is_validated: ValidationFlag,
+
+    #[serde(skip)]
+    txids: TxidCache,
+
+    #[serde(skip)]
+    action_lock: ActionLock,
+}
+
+impl BaseGraph for PegOutGraph {
+    fn network(&self) -> Network { self.network }
+
+    fn id(&self) -> &String { &self.id }
+
+    fn verifier_sign(
+        &mut self,
+        verifier_context: &VerifierContext,
+        secret_nonces: &HashMap<Txid, HashMap<usize, SecNonce>>,
+    ) {
+        self.assert_initial_transaction.pre_sign(
+            verifier_context,
+            &self.connector_b,
+            &secret_nonces[&self.txid(TxRole::AssertInitial)],
+        );
+        self.assert_final_transaction.pre_sign(
+            verifier_context,
+            &self.connector_d,
+            &secret_nonces[&self.txid(TxRole::AssertFinal)],
+        );
+        self.disprove_chain_transaction.pre_sign(
+            verifier_context,
+            &self.connector_b,
+            &secret_nonces[&self.txid(TxRole::DisproveChain)],
+        );
+        self.disprove_transaction.pre_sign(
+            verifier_context,
+            &self.connector_5,
+            &secret_nonces[&self.txid(TxRole::Disprove)],
+        );
+        self.kick_off_timeout_transaction.pre_sign(
+            verifier_context,
+            &self.connector_1,
+            &secret_nonces[&self.txid(TxRole::KickOffTimeout)],
+        );
+        self.start_time_timeout_transaction.pre_sign(
+            verifier_context,
+            &self.connector_1,
+            &self.connector_2,
+            &secret_nonces[&self.txid(TxRole::StartTimeTimeout)],
+        );
+        self.take_1_transaction.pre_sign(
+            verifier_context,
+            &self.connector_0,
+            &self.connector_b,
+            &secret_nonces[&self.txid(TxRole::Take1)],
+        );
+        self.take_2_transaction.pre_sign(
+            verifier_context,
+            &self.connector_0,
+            &self.connector_5,
+            &secret_nonces[&self.txid(TxRole::Take2)],
+        );
+
+        self.n_of_n_presigned = true; // TODO: set to true after collecting all n of n signatures
+    }
+
+    fn push_verifier_nonces(
+        &mut self,
+        verifier_context: &VerifierContext,
+    ) -> HashMap<Txid, HashMap<usize, SecNonce>> {
+        self.all_presigned_txs_mut()
+            .map(|tx_wrapper| {
+                (
+                    tx_wrapper.tx().compute_txid(),
+                    tx_wrapper.push_nonces(verifier_context),
+                )
+            })
+            .collect()
+    }
+}
+
+impl PegOutGraph {
+    pub fn new(
+        context: &OperatorContext,
+        peg_in_graph: &PegInGraph,
+        peg_out_confirm_inputs: Vec<Input>,
+        config: PegOutGraphConfig,
+    ) -> Result<(Self, HashMap<CommitmentMessageId, WinternitzSecret>), GraphConstructionError>
+    {
+        Self::new_with_superblock_policy(
+            context,
+            peg_in_graph,
+            peg_out_confirm_inputs,
+            config,
+            SuperblockPolicy::default(),
+        )
+    }
+
+    pub fn new_with_superblock_policy(
+        context: &OperatorContext,
+        peg_in_graph: &PegInGraph,
+        peg_out_confirm_inputs: Vec<Input>,
+        config: PegOutGraphConfig,
+        superblock_policy: SuperblockPolicy,
+    ) -> Result<(Self, HashMap<CommitmentMessageId, WinternitzSecret>), GraphConstructionError>
+    {
+        if context.network != peg_in_graph.network() {
+            return Err(GraphConstructionError::NetworkMismatch {
+                operator: context.network,
+                peg_in: peg_in_graph.network(),
+            });
+        }
+
+        Self::new_with_commitment_secrets(
+            context,
+            peg_in_graph,
+            peg_out_confirm_inputs,
+            config,
+            superblock_policy,
+            CommitmentMessageId::generate_commitment_secrets(superblock_policy),
+        )
+    }
+
+    // Builds a structurally complete graph that commits to `MINIMAL_GROTH16_VARIABLES`
+    // only, which is orders of magnitude cheaper than a full graph. Never use it for
+    // an actual peg-out, the assert commitments cannot be disproved.
+    #[cfg(feature = "minimal-graph")]
+    pub fn new_minimal(
+        context: &OperatorContext,
+        peg_in_graph: &PegInGraph,
+        peg_out_confirm_inputs: Vec<Input>,
+    ) -> Result<(Self, HashMap<CommitmentMessageId, WinternitzSecret>), GraphConstructionError>
+    {
+        if context.network != peg_in_graph.network() {
+            return Err(GraphConstructionError::NetworkMismatch {
+                operator: context.network,
+                peg_in: peg_in_graph.network(),
+            });
+        }
+
+        let superblock_policy = SuperblockPolicy::default();
+        Self::new_with_commitment_secrets(
+            context,
+            peg_in_graph,
+            peg_out_confirm_inputs,
+            PegOutGraphConfig::default(),
+            superblock_policy,
+            CommitmentMessageId::generate_minimal_commitment_secrets(superblock_policy),
+        )
+    }
+
+    // Rebuilds the graph of an operator who lost it from the config it was built
+    // with, the commitment secrets and the peg-out-confirm tx on chain, which is
+    // found through its connector 6 output. Use
+    // `recover_from_chain_with_superblock_policy` for graphs built with another
+    // superblock policy.
+    pub async fn recover_from_chain(
+        client: &AsyncClient,
+        peg_in_graph: &PegInGraph,
+        context: &OperatorContext,
+        config: &PegOutGraphConfig,
+        commitment_secrets: HashMap<CommitmentMessageId, WinternitzSecret>,
+    ) -> Result<Self, RecoveryError> {
+        Self::recover_from_chain_with_superblock_policy(
+            client,
+            peg_in_graph,
+            context,
+            config,
+            commitment_secrets,
+            SuperblockPolicy::default(),
+        )
+        .await
+    }
+
+    pub async fn recover_from_chain_with_superblock_policy(
+        client: &AsyncClient,
+        peg_in_graph: &PegInGraph,
+        context: &OperatorContext,
+        config: &PegOutGraphConfig,
+        commitment_secrets: HashMap<CommitmentMessageId, WinternitzSecret>,
+        superblock_policy: SuperblockPolicy,
+    ) -> Result<Self, RecoveryError> {
+        let connector_6 = Connector6::new(
+            context.network,
+            &context.operator_taproot_public_key,
+            &Self::connector_6_commitment_public_keys(&commitment_secrets),
+        );
+        let connector_6_script_pubkey = connector_6.generate_taproot_address().script_pubkey();
+
+        let candidates = client
+            .scripthash_txs(&connector_6_script_pubkey, None)
+            .await
+            .map_err(|err| RecoveryError::Client(err.to_string()))?;
+        let mut peg_out_confirm_tx = None;
+        for candidate in candidates
+            .iter()
+            .filter(|candidate| candidate.status.confirmed)
+        {
+            let tx = client
+                .get_tx(&candidate.txid)
+                .await
+                .map_err(|err| RecoveryError::Client(err.to_string()))?;
+            if let Some(tx) = tx.filter(|tx| {
+                tx.output
+                    .first()
+                    .is_some_and(|output| output.script_pubkey == connector_6_script_pubkey)
+            }) {
+                peg_out_confirm_tx = Some(tx);
+                break;
+            }
+        }
+        let peg_out_confirm_tx = peg_out_confirm_tx.ok_or(RecoveryError::PegOutConfirmNotFound)?;
+
+        let mut prev_outs = vec![];
+        for input in &peg_out_confirm_tx.input {
+            let outpoint = input.previous_output;
+            let parent_tx = client
+                .get_tx(&outpoint.txid)
+                .await
+                .map_err(|err| RecoveryError::Client(err.to_string()))?
+                .ok_or_else(|| RecoveryError::Client(format!("Tx {} not found", outpoint.txid)))?;
+            prev_outs.push(parent_tx.output[outpoint.vout as usize].clone());
+        }
+
+        let peg_out_graph = Self::recover_from_txs(
+            peg_in_graph,
+            context,
+            config,
+            commitment_secrets,
+            superblock_policy,
+            &peg_out_confirm_tx,
+            &prev_outs,
+            &[],
+        )?;
+
+        // whatever spent an output of a graph tx on chain has to be a graph tx too
+        let dag = peg_out_graph.transaction_dag();
+        let mut spent_outpoints: Vec<OutPoint> = dag
+            .edges
+            .iter()
+            .filter(|edge| edge.from != TxRole::PegInConfirm)
+            .map(|edge| OutPoint {
+                txid: dag.nodes[&edge.from],
+                vout: edge.from_vout,
+            })
+            .collect();
+        spent_outpoints.sort();
+        spent_outpoints.dedup();
+
+        let mut onchain_txs: Vec<Transaction> = vec![];
+        for outpoint in spent_outpoints {
+            let spending_txid = client
+                .get_output_status(&outpoint.txid, outpoint.vout.into())
+                .await
+                .map_err(|err| RecoveryError::Client(err.to_string()))?
+                .and_then(|output_status| output_status.txid);
+            let Some(spending_txid) = spending_txid else {
+                continue;
+            };
+            if onchain_txs
+                .iter()
+                .any(|tx| tx.compute_txid() == spending_txid)
+            {
+                continue;
+            }
+            let tx = client
+                .get_tx(&spending_txid)
+                .await
+                .map_err(|err| RecoveryError::Client(err.to_string()))?
+                .ok_or_else(|| RecoveryError::Client(format!("Tx {spending_txid} not found")))?;
+            onchain_txs.push(tx);
+        }
+        peg_out_graph.verify_onchain_txs(&onchain_txs)?;
+
+        Ok(peg_out_graph)
+    }
+
+    // Rebuilds the graph funded by `peg_out_confirm_tx` from the config it was built
+    // with and checks it reproduces the peg-out-confirm tx and `onchain_txs`, the
+    // other graph txs seen on chain.
+    #[allow(clippy::too_many_arguments)]
+    pub fn recover_from_txs(
+        peg_in_graph: &PegInGraph,
+        context: &OperatorContext,
+        config: &PegOutGraphConfig,
+        commitment_secrets: HashMap<CommitmentMessageId, WinternitzSecret>,
+        superblock_policy: SuperblockPolicy,
+        peg_out_confirm_tx: &Transaction,
+        peg_out_confirm_prev_outs: &[TxOut],
+        onchain_txs: &[Transaction],
+    ) -> Result<Self, RecoveryError> {
+        if context.network != peg_in_graph.network() {
+            return Err(RecoveryError::Construction(
+                GraphConstructionError::NetworkMismatch {
+                    operator: context.network,
+                    peg_in: peg_in_graph.network(),
+                },
+            ));
+        }
+
+        let peg_out_confirm_inputs = peg_out_confirm_tx
+            .input
+            .iter()
+            .zip(peg_out_confirm_prev_outs)
+            .map(|(tx_in, prev_out)| Input {
+                outpoint: tx_in.previous_output,
+                amount: prev_out.value,
+            })
+            .collect();
+        let (peg_out_graph, _) = Self::new_with_commitment_secrets(
+            context,
+            peg_in_graph,
+            peg_out_confirm_inputs,
+            config.clone(),
+            superblock_policy,
+            commitment_secrets,
+        )
+        .map_err(RecoveryError::Construction)?;
+
+        peg_out_graph.verify_onchain_txs(iter::once(peg_out_confirm_tx).chain(onchain_txs))?;
+
+        Ok(peg_out_graph)
+    }
+
+    // Matches every tx on chain with the graph txs spending any of the same outputs,
+    // conflicting graph txs spend the same ones. One of them has to be reproduced,
+    // by txid or, if completed on broadcast, by what it commits to.
+    fn verify_onchain_txs<'a>(
+        &self,
+        onchain_txs: impl IntoIterator<Item = &'a Transaction>,
+    ) -> Result<(), RecoveryError> {
+        let role_txs = self.role_transactions();
+        for onchain_tx in onchain_txs {
+            let onchain_txid = onchain_tx.compute_txid();
+            let candidates: Vec<&(TxRole, Transaction)> = role_txs
+                .iter()
+                .filter(|(_, tx)| {
+                    tx.input.iter().any(|tx_in| {
+                        onchain_tx.input.iter().any(|onchain_tx_in| {
+                            onchain_tx_in.previous_output == tx_in.previous_output
+                        })
+                    })
+                })
+                .collect();
+            let Some((role, tx)) = candidates.first() else {
+                return Err(RecoveryError::UnknownTx(onchain_txid));
+            };
+
+            let is_reproduced = candidates.iter().any(|(role, tx)| {
+                if role.is_completed_on_broadcast() {
+                    completes_tx(onchain_tx, tx)
+                } else {
+                    tx.compute_txid() == onchain_txid
+                }
+            });
+            if !is_reproduced {
+                return Err(RecoveryError::TxidMismatch {
+                    tx_name: role.tx_name(),
+                    onchain_txid,
+                    recovered_txid: tx.compute_txid(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn connector_6_commitment_public_keys(
+        commitment_secrets: &HashMap<CommitmentMessageId, WinternitzSecret>,
+    ) -> HashMap<CommitmentMessageId, WinternitzPublicKey> {
+        HashMap::from([
+            (
+                CommitmentMessageId::PegOutTxIdSourceNetwork,
+                WinternitzPublicKey::from(
+                    &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
+                ),
+            ),
+            (
+                CommitmentMessageId::PegOutTxIdDestinationNetwork,
+                WinternitzPublicKey::from(
+                    &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
+                ),
+            ),
+        ])
+    }
+
+    fn new_with_commitment_secrets(
+        context: &OperatorContext,
+        peg_in_graph: &PegInGraph,
+        peg_out_confirm_inputs: Vec<Input>,
+        config: PegOutGraphConfig,
+        superblock_policy: SuperblockPolicy,
+        commitment_secrets: HashMap<CommitmentMessageId, WinternitzSecret>,
+    ) -> Result<(Self, HashMap<CommitmentMessageId, WinternitzSecret>), GraphConstructionError>
+    {
+        if peg_out_confirm_inputs.is_empty() {
+            return Err(GraphConstructionError::MissingInput(
+                "peg_out_confirm".to_string(),
+            ));
+        }
+        superblock_policy
+            .validate()
+            .map_err(GraphConstructionError::InvalidSuperblockPolicy)?;
+
+        let peg_in_confirm_transaction = peg_in_graph.peg_in_confirm_transaction_ref();
+        let peg_in_confirm_txid = peg_in_confirm_transaction.tx().compute_txid();
+
+        let connector_1_commitment_public_keys = HashMap::from([
+            (
+                CommitmentMessageId::Superblock,
+                WinternitzPublicKey::from(&commitment_secrets[&CommitmentMessageId::Superblock]),
+            ),
+            (
+                CommitmentMessageId::SuperblockHash,
+                WinternitzPublicKey::from(
+                    &commitment_secrets[&CommitmentMessageId::SuperblockHash],
+                ),
+            ),
+        ]);
+        let connector_2_commitment_public_keys = HashMap::from([(
+            CommitmentMessageId::StartTime,
+            WinternitzPublicKey::from(&commitment_secrets[&CommitmentMessageId::StartTime]),
+        )]);
+        let connector_6_commitment_public_keys =
+            Self::connector_6_commitment_public_keys(&commitment_secrets);
+
+        let connectors = Self::create_new_connectors(
+            context.network,
+            &context.n_of_n_taproot_public_key,
+            &context.operator_taproot_public_key,
+            &context.operator_public_key,
+            &connector_1_commitment_public_keys,
+            superblock_policy,
+            &connector_2_commitment_public_keys,
+            &connector_6_commitment_public_keys,
+            GrothKeySource::Secrets(&commitment_secrets),
+            &AssertSegments::new(&config.verifying_key),
+        );
+
+        let peg_out_confirm_transaction =
+            PegOutConfirmTransaction::new(context, &connectors.connector_6, peg_out_confirm_inputs)
+                .map_err(|_| {
+                    GraphConstructionError::InvalidAmount("peg_out_confirm".to_string())
+                })?;
+
+        let peg_out_graph = Self::build_graph(
+            PegOutGraphKeys {
+                network: context.network,
+                id: generate_id(peg_in_graph, &context.operator_public_key),
+                peg_in_graph_id: peg_in_graph.id().clone(),
+                n_of_n_public_key: context.n_of_n_public_key,
+                n_of_n_taproot_public_key: context.n_of_n_taproot_public_key,
+                n_of_n_public_keys: context.n_of_n_public_keys.clone(),
+                operator_public_key: context.operator_public_key,
+                operator_taproot_public_key: context.operator_taproot_public_key,
+            },
+            Some(context),
+            connectors,
+            peg_in_confirm_txid,
+            peg_in_confirm_transaction.tx().output[topology::TAKE_1_VOUT_0].value,
+            peg_out_confirm_transaction,
+            config,
+        )?;
+
+        Ok((peg_out_graph, commitment_secrets))
+    }
+
+    // Wires the graph txs onto the connectors. Shared by construction and
+    // validation so that both build the same graph, only with a `signer` are the
+    // operator inputs signed.
+    fn build_graph(
+        keys: PegOutGraphKeys,
+        signer: Option<&OperatorContext>,
+        connectors: PegOutConnectors,
+        peg_in_confirm_txid: Txid,
+        peg_in_confirm_amount: Amount, // of connector 0, spent by both take txs
+        peg_out_confirm_transaction: PegOutConfirmTransaction,
+        config: PegOutGraphConfig,
+    ) -> Result<Self, GraphConstructionError> {
+        let peg_out_confirm_txid = peg_out_confirm_transaction.tx().compute_txid();
+
+        let kick_off_1_transaction = KickOff1Transaction::new_for_validation(
+            keys.network,
+            &keys.operator_taproot_public_key,
+            &keys.n_of_n_taproot_public_key,
+            &connectors.connector_1,
+            &connectors.connector_2,
+            &connectors.connector_6,
+            Input {
+                outpoint: OutPoint {
+                    txid: peg_out_confirm_txid,
+                    vout: checked_vout(topology::KICK_OFF_1_VOUT_0, "kick_off_1_vout_0")?,
+                },
+                amount: peg_out_confirm_transaction.tx().output[topology::KICK_OFF_1_VOUT_0].value,
+            },
+        );
+        let kick_off_1_txid = kick_off_1_transaction.tx().compute_txid();
+
+        let start_time_transaction = StartTimeTransaction::new_for_validation(
+            keys.network,
+            &keys.operator_public_key,
+            &connectors.connector_2,
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_1_txid,
+                    vout: checked_vout(topology::START_TIME_VOUT_0, "start_time_vout_0")?,
+                },
+                amount: kick_off_1_transaction.tx().output[topology::START_TIME_VOUT_0].value,
+            },
+        );
+
+        let start_time_timeout_transaction = StartTimeTimeoutTransaction::new_for_validation(
+            keys.network,
+            &connectors.connector_1,
+            &connectors.connector_2,
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_1_txid,
+                    vout: checked_vout(
+                        topology::START_TIME_TIMEOUT_VOUT_0,
+                        "start_time_timeout_vout_0",
+                    )?,
+                },
+                amount: kick_off_1_transaction.tx().output[topology::START_TIME_TIMEOUT_VOUT_0]
+                    .value,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_1_txid,
+                    vout: checked_vout(
+                        topology::START_TIME_TIMEOUT_VOUT_1,
+                        "start_time_timeout_vout_1",
+                    )?,
+                },
+                amount: kick_off_1_transaction.tx().output[topology::START_TIME_TIMEOUT_VOUT_1]
+                    .value,
+            },
+        );
+
+        let kick_off_2_transaction = KickOff2Transaction::new_for_validation(
+            keys.network,
+            &keys.operator_public_key,
+            &keys.n_of_n_taproot_public_key,
+            &connectors.connector_1,
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_1_txid,
+                    vout: checked_vout(topology::KICK_OFF_2_VOUT_0, "kick_off_2_vout_0")?,
+                },
+                amount: kick_off_1_transaction.tx().output[topology::KICK_OFF_2_VOUT_0].value,
+            },
+        );
+        let kick_off_2_txid = kick_off_2_transaction.tx().compute_txid();
+
+        let kick_off_timeout_transaction = KickOffTimeoutTransaction::new_for_validation(
+            keys.network,
+            &connectors.connector_1,
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_1_txid,
+                    vout: checked_vout(
+                        topology::KICK_OFF_TIMEOUT_VOUT_0,
+                        "kick_off_timeout_vout_0",
+                    )?,
+                },
+                amount: kick_off_1_transaction.tx().output[topology::KICK_OFF_TIMEOUT_VOUT_0].value,
+            },
+        );
+
+        let challenge_input_0 = Input {
+            outpoint: OutPoint {
+                txid: kick_off_1_txid,
+                vout: checked_vout(topology::CHALLENGE_VOUT_0, "challenge_vout_0")?,
+            },
+            amount: kick_off_1_transaction.tx().output[topology::CHALLENGE_VOUT_0].value,
+        };
+        let challenge_transaction = match signer {
+            Some(context) => ChallengeTransaction::new(
+                context,
+                &connectors.connector_a,
+                challenge_input_0,
+                config.challenge_amount,
+            ),
+            None => ChallengeTransaction::new_for_validation(
+                keys.network,
+                &keys.operator_public_key,
+                &connectors.connector_a,
+                challenge_input_0,
+                config.challenge_amount,
+            ),
+        };
+
+        let take_1_inputs = [
+            Input {
+                outpoint: OutPoint {
+                    txid: peg_in_confirm_txid,
+                    vout: checked_vout(topology::TAKE_1_VOUT_0, "take_1_vout_0")?,
+                },
+                amount: peg_in_confirm_amount,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_1_txid,
+                    vout: checked_vout(topology::TAKE_1_VOUT_1, "take_1_vout_1")?,
+                },
+                amount: kick_off_1_transaction.tx().output[topology::TAKE_1_VOUT_1].value,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_2_txid,
+                    vout: checked_vout(topology::TAKE_1_VOUT_2, "take_1_vout_2")?,
+                },
+                amount: kick_off_2_transaction.tx().output[topology::TAKE_1_VOUT_2].value,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_2_txid,
+                    vout: checked_vout(topology::TAKE_1_VOUT_3, "take_1_vout_3")?,
+                },
+                amount: kick_off_2_transaction.tx().output[topology::TAKE_1_VOUT_3].value,
+            },
+        ];
+        let [take_1_input_0, take_1_input_1, take_1_input_2, take_1_input_3] = take_1_inputs;
+        let take_1_transaction = match signer {
+            Some(context) => Take1Transaction::new(
+                context,
+                &connectors.connector_0,
+                &connectors.connector_3,
+                &connectors.connector_a,
+                &connectors.connector_b,
+                take_1_input_0,
+                take_1_input_1,
+                take_1_input_2,
+                take_1_input_3,
+                config.take_anchors,
+            ),
+            None => Take1Transaction::new_for_validation(
+                keys.network,
+                &keys.operator_public_key,
+                &connectors.connector_0,
+                &connectors.connector_3,
+                &connectors.connector_a,
+                &connectors.connector_b,
+                take_1_input_0,
+                take_1_input_1,
+                take_1_input_2,
+                take_1_input_3,
+                config.take_anchors,
+            ),
+        };
+
+        // assert initial
+        let assert_initial_transaction = AssertInitialTransaction::new_for_validation(
+            &connectors.connector_b,
+            &connectors.connector_d,
+            &connectors.assert_commit_connectors_e_1,
+            &connectors.assert_commit_connectors_e_2,
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_2_txid,
+                    vout: checked_vout(topology::ASSERT_INITIAL_VOUT_0, "assert_initial_vout_0")?,
+                },
+                amount: kick_off_2_transaction.tx().output[topology::ASSERT_INITIAL_VOUT_0].value,
+            },
+        );
+        let assert_initial_txid = assert_initial_transaction.tx().compute_txid();
+
+        // assert commit txs
+        let (assert_commit1_transaction, assert_commit2_transaction) =
+            Self::build_assert_commit_transactions(
+                &assert_initial_transaction,
+                &connectors.assert_commit_connectors_e_1,
+                &connectors.assert_commit_connectors_f.connector_f_1,
+                &connectors.assert_commit_connectors_e_2,
+                &connectors.assert_commit_connectors_f.connector_f_2,
+            )?;
+
+        // assert final
+        let assert_final_inputs = [
+            Input {
+                outpoint: OutPoint {
+                    txid: assert_initial_txid,
+                    vout: checked_vout(topology::ASSERT_FINAL_VOUT_0, "assert_final_vout_0")?,
+                },
+                amount: assert_initial_transaction.tx().output[topology::ASSERT_FINAL_VOUT_0].value,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: assert_commit1_transaction.tx().compute_txid(),
+                    vout: checked_vout(topology::ASSERT_FINAL_VOUT_1, "assert_final_vout_1")?,
+                },
+                amount: assert_commit1_transaction.tx().output[topology::ASSERT_FINAL_VOUT_1].value,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: assert_commit2_transaction.tx().compute_txid(),
+                    vout: checked_vout(topology::ASSERT_FINAL_VOUT_2, "assert_final_vout_2")?,
+                },
+                amount: assert_commit2_transaction.tx().output[topology::ASSERT_FINAL_VOUT_2].value,
+            },
+        ];
+        let [assert_final_input_0, assert_final_input_1, assert_final_input_2] =
+            assert_final_inputs;
+        let assert_final_transaction = match signer {
+            Some(context) => AssertFinalTransaction::new(
+                context,
+                &connectors.connector_4,
+                &connectors.connector_5,
+                &connectors.connector_c,
+                &connectors.connector_d,
+                &connectors.assert_commit_connectors_f,
+                assert_final_input_0,
+                assert_final_input_1,
+                assert_final_input_2,
+            ),
+            None => AssertFinalTransaction::new_for_validation(
+                &connectors.connector_4,
+                &connectors.connector_5,
+                &connectors.connector_c,
+                &connectors.connector_d,
+                &connectors.assert_commit_connectors_f,
+                assert_final_input_0,
+                assert_final_input_1,
+                assert_final_input_2,
+            ),
+        };
+        let assert_final_txid = assert_final_transaction.tx().compute_txid();
+
+        let take_2_inputs = [
+            Input {
+                outpoint: OutPoint {
+                    txid: peg_in_confirm_txid,
+                    vout: checked_vout(topology::TAKE_2_VOUT_0, "take_2_vout_0")?,
+                },
+                amount: peg_in_confirm_amount,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: assert_final_txid,
+                    vout: checked_vout(topology::TAKE_2_VOUT_1, "take_2_vout_1")?,
+                },
+                amount: assert_final_transaction.tx().output[topology::TAKE_2_VOUT_1].value,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: assert_final_txid,
+                    vout: checked_vout(topology::TAKE_2_VOUT_2, "take_2_vout_2")?,
+                },
+                amount: assert_final_transaction.tx().output[topology::TAKE_2_VOUT_2].value,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: assert_final_txid,
+                    vout: checked_vout(topology::TAKE_2_VOUT_3, "take_2_vout_3")?,
+                },
+                amount: assert_final_transaction.tx().output[topology::TAKE_2_VOUT_3].value,
+            },
+        ];
+        let [take_2_input_0, take_2_input_1, take_2_input_2, take_2_input_3] = take_2_inputs;
+        let take_2_transaction = match signer {
+            Some(context) => Take2Transaction::new(
+                context,
+                &connectors.connector_0,
+                &connectors.connector_4,
+                &connectors.connector_5,
+                &connectors.connector_c,
+                take_2_input_0,
+                take_2_input_1,
+                take_2_input_2,
+                take_2_input_3,
+                config.take_anchors,
+            ),
+            None => Take2Transaction::new_for_validation(
+                keys.network,
+                &keys.operator_public_key,
+                &connectors.connector_0,
+                &connectors.connector_4,
+                &connectors.connector_5,
+                &connectors.connector_c,
+                take_2_input_0,
+                take_2_input_1,
+                take_2_input_2,
+                take_2_input_3,
+                config.take_anchors,
+            ),
+        };
+
+        let script_index = PLACEHOLDER_DISPROVE_SCRIPT_INDEX; // TODO replace placeholder
+        let disprove_transaction = DisproveTransaction::new_for_validation(
+            keys.network,
+            &connectors.connector_5,
+            &connectors.connector_c,
+            Input {
+                outpoint: OutPoint {
+                    txid: assert_final_txid,
+                    vout: checked_vout(topology::DISPROVE_VOUT_0, "disprove_vout_0")?,
+                },
+                amount: assert_final_transaction.tx().output[topology::DISPROVE_VOUT_0].value,
+            },
+            Input {
+                outpoint: OutPoint {
+                    txid: assert_final_txid,
+                    vout: checked_vout(topology::DISPROVE_VOUT_1, "disprove_vout_1")?,
+                },
+                amount: assert_final_transaction.tx().output[topology::DISPROVE_VOUT_1].value,
+            },
+            script_index,
+        );
+
+        let disprove_chain_transaction = DisproveChainTransaction::new_for_validation(
+            keys.network,
+            &connectors.connector_b,
+            Input {
+                outpoint: OutPoint {
+                    txid: kick_off_2_txid,
+                    vout: checked_vout(topology::DISPROVE_CHAIN_VOUT_0, "disprove_chain_vout_0")?,
+                },
+                amount: kick_off_2_transaction.tx().output[topology::DISPROVE_CHAIN_VOUT_0].value,
+            },
+        );
+
+        Ok(PegOutGraph {
+            version: GRAPH_VERSION.to_string(),
+            network: keys.network,
+            id: keys.id,
+            n_of_n_presigned: false,
+            n_of_n_public_key: keys.n_of_n_public_key,
+            n_of_n_taproot_public_key: keys.n_of_n_taproot_public_key,
+            n_of_n_public_keys: keys.n_of_n_public_keys,
+            peg_in_graph_id: keys.peg_in_graph_id,
+            peg_in_confirm_txid,
+            connector_0: connectors.connector_0,
+            connector_1: connectors.connector_1,
+            connector_2: connectors.connector_2,
+            connector_3: connectors.connector_3,
+            connector_4: connectors.connector_4,
+            connector_5: connectors.connector_5,
+            connector_6: connectors.connector_6,
+            connector_a: connectors.connector_a,
+            connector_b: connectors.connector_b,
             connector_c: connectors.connector_c,
             connector_d: connectors.connector_d,
             connector_e_1: connectors.assert_commit_connectors_e_1,
@@ -1187,759 +2565,3466 @@ impl PegOutGraph {
             start_time_timeout_transaction,
             take_1_transaction,
             take_2_transaction,
-            operator_public_key: self.operator_public_key,
-            operator_taproot_public_key: self.operator_taproot_public_key,
+            operator_public_key: keys.operator_public_key,
+            operator_taproot_public_key: keys.operator_taproot_public_key,
             peg_out_chain_event: None,
             peg_out_transaction: None,
+            reward_script_pubkey: None,
+            kick_off_1_peg_out_txid: None,
+            external_ref: None,
+            config,
+            is_validated: ValidationFlag::default(),
+            txids: TxidCache::default(),
+            action_lock: ActionLock::default(),
+        })
+    }
+
+    fn peg_out_confirm_inputs(&self) -> Vec<Input> {
+        self.peg_out_confirm_transaction
+            .tx()
+            .input
+            .iter()
+            .zip(self.peg_out_confirm_transaction.prev_outs())
+            .map(|(tx_in, prev_out)| Input {
+                outpoint: tx_in.previous_output, // Self-referencing
+                amount: prev_out.value,          // Self-referencing
+            })
+            .collect()
+    }
+
+    pub fn new_for_validation(&self) -> Result<Self, ValidationRebuildError> {
+        self.new_for_validation_with_segments(&AssertSegments::new(&self.config.verifying_key))
+    }
+
+    // `assert_segments` must be those of the vk in the graph config.
+    fn new_for_validation_with_segments(
+        &self,
+        assert_segments: &AssertSegments,
+    ) -> Result<Self, ValidationRebuildError> {
+        self.check_validation_shape()?;
+        self.validate_key_consistency()
+            .map_err(ValidationRebuildError::KeyMismatch)?;
+
+        let peg_in_confirm_txid = self.take_1_transaction.tx().input[0].previous_output.txid; // Self-referencing
+
+        let connectors = Self::create_new_connectors(
+            self.network,
+            &self.n_of_n_taproot_public_key,
+            &self.operator_taproot_public_key,
+            &self.operator_public_key,
+            &self.connector_1.commitment_public_keys,
+            self.connector_1.superblock_policy,
+            &self.connector_2.commitment_public_keys,
+            &self.connector_6.commitment_public_keys,
+            GrothKeySource::PublicKeys(
+                &self.connector_e_1.commitment_public_keys(),
+                &self.connector_e_2.commitment_public_keys(),
+            ),
+            assert_segments,
+        );
+
+        let peg_out_confirm_transaction = PegOutConfirmTransaction::new_for_validation(
+            self.network,
+            &self.operator_public_key,
+            &connectors.connector_6,
+            self.peg_out_confirm_inputs(),
+        )
+        .map_err(ValidationRebuildError::PegOutConfirmAmount)?;
+
+        Self::build_graph(
+            PegOutGraphKeys {
+                network: self.network,
+                id: self.id.clone(),
+                peg_in_graph_id: self.peg_in_graph_id.clone(),
+                n_of_n_public_key: self.n_of_n_public_key,
+                n_of_n_taproot_public_key: self.n_of_n_taproot_public_key,
+                n_of_n_public_keys: self.n_of_n_public_keys.clone(),
+                operator_public_key: self.operator_public_key,
+                operator_taproot_public_key: self.operator_taproot_public_key,
+            },
+            None,
+            connectors,
+            peg_in_confirm_txid,
+            self.take_1_transaction.prev_outs()[0].value, // Self-referencing
+            peg_out_confirm_transaction,
+            self.config.clone(),
+        )
+        .map_err(ValidationRebuildError::Construction)
+    }
+
+    // The rebuild reads the inputs of the received txs and the commitment keys of
+    // the received connectors, which must be there.
+    fn check_validation_shape(&self) -> Result<(), ValidationRebuildError> {
+        self.structural_completeness()
+            .map_err(ValidationRebuildError::MalformedTxs)?;
+
+        let required_keys = [
+            (
+                &self.connector_1.commitment_public_keys,
+                CommitmentMessageId::Superblock,
+            ),
+            (
+                &self.connector_1.commitment_public_keys,
+                CommitmentMessageId::SuperblockHash,
+            ),
+            (
+                &self.connector_2.commitment_public_keys,
+                CommitmentMessageId::StartTime,
+            ),
+            (
+                &self.connector_6.commitment_public_keys,
+                CommitmentMessageId::PegOutTxIdSourceNetwork,
+            ),
+            (
+                &self.connector_6.commitment_public_keys,
+                CommitmentMessageId::PegOutTxIdDestinationNetwork,
+            ),
+        ];
+        for (public_keys, message_id) in required_keys {
+            if !public_keys.contains_key(&message_id) {
+                return Err(ValidationRebuildError::MissingCommitmentKey(message_id));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn verifier_status(
+        &self,
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+        min_confirmations: u32,
+    ) -> PegOutVerifierStatus {
+        if self.n_of_n_presigned {
+            let statuses = self.get_peg_out_statuses(client).await;
+            let blockchain_height = get_block_height_if_none(client, blockchain_height).await;
+
+            self.verifier_status_from_statuses(&statuses, blockchain_height, min_confirmations)
+        } else {
+            PegOutVerifierStatus::PegOutPresign
+        }
+    }
+
+    // Like `verifier_status`, with the tx statuses looked up in `cache` first.
+    pub async fn verifier_status_cached(
+        &self,
+        client: &AsyncClient,
+        cache: &mut StatusCache,
+        blockchain_height: Option<u32>,
+        min_confirmations: u32,
+    ) -> PegOutVerifierStatus {
+        if self.n_of_n_presigned {
+            let statuses = self.get_peg_out_statuses_cached(client, cache).await;
+            let blockchain_height = get_block_height_if_none(client, blockchain_height).await;
+
+            self.verifier_status_from_statuses(&statuses, blockchain_height, min_confirmations)
+        } else {
+            PegOutVerifierStatus::PegOutPresign
+        }
+    }
+
+    fn verifier_status_from_statuses(
+        &self,
+        (
+            assert_initial_status,
+            assert_final_status,
+            challenge_status,
+            disprove_chain_status,
+            disprove_status,
+            _,
+            kick_off_1_status,
+            kick_off_2_status,
+            kick_off_timeout_status,
+            _,
+            start_time_timeout_status,
+            start_time_status,
+            take_1_status,
+            take_2_status,
+        ): &PegOutStatuses,
+        blockchain_height: u32,
+        min_confirmations: u32,
+    ) -> PegOutVerifierStatus {
+        if kick_off_2_status
+            .as_ref()
+            .is_ok_and(|status| status.confirmed)
+        {
+            if let Some(settlement) = self.settlement(
+                [
+                    (TxRole::Take1, take_1_status),
+                    (TxRole::Take2, take_2_status),
+                ],
+                blockchain_height,
+                min_confirmations,
+            ) {
+                settlement.map_or_else(PegOutVerifierStatus::PegOutWait, |_| {
+                    PegOutVerifierStatus::PegOutComplete
+                })
+            } else if let Some(settlement) = self.settlement(
+                [
+                    (TxRole::Disprove, disprove_status),
+                    (TxRole::DisproveChain, disprove_chain_status),
+                ],
+                blockchain_height,
+                min_confirmations,
+            ) {
+                settlement.map_or_else(PegOutVerifierStatus::PegOutWait, |_| {
+                    PegOutVerifierStatus::PegOutFailed // TODO: can be also `PegOutVerifierStatus::PegOutComplete`
+                })
+            } else if assert_final_status
+                .as_ref()
+                .is_ok_and(|status| status.confirmed)
+            {
+                PegOutVerifierStatus::PegOutDisproveAvailable
+            } else {
+                PegOutVerifierStatus::PegOutDisproveChainAvailable
+            }
+        } else if kick_off_1_status
+            .as_ref()
+            .is_ok_and(|status| status.confirmed)
+        {
+            if let Some(settlement) = self.settlement(
+                [
+                    (TxRole::StartTimeTimeout, start_time_timeout_status),
+                    (TxRole::KickOffTimeout, kick_off_timeout_status),
+                ],
+                blockchain_height,
+                min_confirmations,
+            ) {
+                settlement.map_or_else(PegOutVerifierStatus::PegOutWait, |_| {
+                    PegOutVerifierStatus::PegOutFailed // TODO: can be also `PegOutVerifierStatus::PegOutComplete`
+                })
+            } else if is_confirmed_without_height(kick_off_1_status) {
+                PegOutVerifierStatus::PegOutRefetch
+            } else if start_time_status
+                .as_ref()
+                .is_ok_and(|status| !status.confirmed)
+            {
+                if confirmation_height(kick_off_1_status).is_some_and(|block_height| {
+                    block_height + self.connector_1.num_blocks_timelock_leaf_2 > blockchain_height
+                }) {
+                    PegOutVerifierStatus::PegOutStartTimeTimeoutAvailable
+                } else {
+                    PegOutVerifierStatus::PegOutWait(WaitReason::AwaitingCounterparty)
+                }
+            } else if confirmation_height(kick_off_1_status).is_some_and(|block_height| {
+                block_height + self.connector_1.num_blocks_timelock_leaf_1 > blockchain_height
+            }) {
+                PegOutVerifierStatus::PegOutKickOffTimeoutAvailable
+            } else if challenge_status
+                .as_ref()
+                .is_ok_and(|status| !status.confirmed)
+            {
+                PegOutVerifierStatus::PegOutChallengeAvailable
+            } else {
+                PegOutVerifierStatus::PegOutWait(WaitReason::AwaitingCounterparty)
+            }
+        } else {
+            PegOutVerifierStatus::PegOutWait(WaitReason::AwaitingCounterparty)
+        }
+    }
+
+    pub async fn operator_status(
+        &self,
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+        min_confirmations: u32,
+    ) -> PegOutOperatorStatus {
+        if self.n_of_n_presigned && self.is_peg_out_initiated() {
+            let statuses = self.get_peg_out_statuses(client).await;
+            let blockchain_height = get_block_height_if_none(client, blockchain_height).await;
+
+            return self.operator_status_from_statuses(
+                &statuses,
+                blockchain_height,
+                min_confirmations,
+            );
+        }
+
+        PegOutOperatorStatus::PegOutWait(self.setup_wait_reason())
+    }
+
+    // Like `operator_status`, with the tx statuses looked up in `cache` first.
+    pub async fn operator_status_cached(
+        &self,
+        client: &AsyncClient,
+        cache: &mut StatusCache,
+        blockchain_height: Option<u32>,
+        min_confirmations: u32,
+    ) -> PegOutOperatorStatus {
+        if self.n_of_n_presigned && self.is_peg_out_initiated() {
+            let statuses = self.get_peg_out_statuses_cached(client, cache).await;
+            let blockchain_height = get_block_height_if_none(client, blockchain_height).await;
+
+            return self.operator_status_from_statuses(
+                &statuses,
+                blockchain_height,
+                min_confirmations,
+            );
+        }
+
+        PegOutOperatorStatus::PegOutWait(self.setup_wait_reason())
+    }
+
+    fn operator_status_from_statuses(
+        &self,
+        (
+            assert_initial_status,
+            assert_final_status,
+            challenge_status,
+            disprove_chain_status,
+            disprove_status,
+            peg_out_confirm_status,
+            kick_off_1_status,
+            kick_off_2_status,
+            kick_off_timeout_status,
+            peg_out_status,
+            start_time_timeout_status,
+            start_time_status,
+            take_1_status,
+            take_2_status,
+        ): &PegOutStatuses,
+        blockchain_height: u32,
+        min_confirmations: u32,
+    ) -> PegOutOperatorStatus {
+        self.interpret_operator_status(
+            assert_initial_status,
+            assert_final_status,
+            challenge_status,
+            disprove_chain_status,
+            disprove_status,
+            peg_out_confirm_status,
+            kick_off_1_status,
+            kick_off_2_status,
+            kick_off_timeout_status,
+            peg_out_status.as_ref(),
+            start_time_timeout_status,
+            start_time_status,
+            take_1_status,
+            take_2_status,
+            blockchain_height,
+            min_confirmations,
+        )
+    }
+
+    // All three role statuses from a single fetch of the graph tx statuses.
+    pub async fn full_status(&self, client: &AsyncClient, min_confirmations: u32) -> GraphStatus {
+        let statuses = self.get_peg_out_statuses(client).await;
+        let block_height = get_block_height(client).await;
+
+        GraphStatus {
+            operator: if self.n_of_n_presigned && self.is_peg_out_initiated() {
+                self.operator_status_from_statuses(&statuses, block_height, min_confirmations)
+            } else {
+                PegOutOperatorStatus::PegOutWait(self.setup_wait_reason())
+            },
+            verifier: if self.n_of_n_presigned {
+                self.verifier_status_from_statuses(&statuses, block_height, min_confirmations)
+            } else {
+                PegOutVerifierStatus::PegOutPresign
+            },
+            withdrawer: self.interpret_withdrawer_status(statuses.9.as_ref()),
+            block_height,
+        }
+    }
+
+    // Each poll is a single `operator_status` pass, the matching status is returned.
+    pub async fn wait_for_operator_status(
+        &self,
+        client: &AsyncClient,
+        target: fn(&PegOutOperatorStatus) -> bool,
+        poll_interval: Duration,
+        deadline: Instant,
+        min_confirmations: u32,
+    ) -> Result<PegOutOperatorStatus, Timeout> {
+        wait_for_status(
+            || self.operator_status(client, None, min_confirmations),
+            target,
+            poll_interval,
+            deadline,
+        )
+        .await
+    }
+
+    pub async fn phase(&self, client: &AsyncClient) -> GraphPhase {
+        if !self.n_of_n_presigned {
+            return GraphPhase::Setup;
+        }
+
+        let (
+            assert_initial_status,
+            assert_final_status,
+            challenge_status,
+            disprove_chain_status,
+            disprove_status,
+            peg_out_confirm_status,
+            kick_off_1_status,
+            kick_off_2_status,
+            kick_off_timeout_status,
+            peg_out_status,
+            start_time_timeout_status,
+            start_time_status,
+            take_1_status,
+            take_2_status,
+        ) = Self::get_peg_out_statuses(self, client).await;
+
+        self.interpret_phase(
+            &assert_initial_status,
+            &assert_final_status,
+            &challenge_status,
+            &disprove_chain_status,
+            &disprove_status,
+            &peg_out_confirm_status,
+            &kick_off_1_status,
+            &kick_off_2_status,
+            &kick_off_timeout_status,
+            peg_out_status.as_ref(),
+            &start_time_timeout_status,
+            &start_time_status,
+            &take_1_status,
+            &take_2_status,
+        )
+    }
+
+    // Each phase only depends on which txs are confirmed, checked from the last
+    // phase backwards, so every combination maps to exactly one phase.
+    #[allow(clippy::too_many_arguments)]
+    pub fn interpret_phase(
+        &self,
+        assert_initial_status: &Result<TxStatus, Error>,
+        assert_final_status: &Result<TxStatus, Error>,
+        challenge_status: &Result<TxStatus, Error>,
+        disprove_chain_status: &Result<TxStatus, Error>,
+        disprove_status: &Result<TxStatus, Error>,
+        peg_out_confirm_status: &Result<TxStatus, Error>,
+        kick_off_1_status: &Result<TxStatus, Error>,
+        kick_off_2_status: &Result<TxStatus, Error>,
+        kick_off_timeout_status: &Result<TxStatus, Error>,
+        peg_out_status: Option<&Result<TxStatus, Error>>,
+        start_time_timeout_status: &Result<TxStatus, Error>,
+        start_time_status: &Result<TxStatus, Error>,
+        take_1_status: &Result<TxStatus, Error>,
+        take_2_status: &Result<TxStatus, Error>,
+    ) -> GraphPhase {
+        let is_confirmed =
+            |status: &Result<TxStatus, Error>| status.as_ref().is_ok_and(|status| status.confirmed);
+
+        if [
+            take_1_status,
+            take_2_status,
+            disprove_chain_status,
+            disprove_status,
+            kick_off_timeout_status,
+            start_time_timeout_status,
+        ]
+        .into_iter()
+        .any(is_confirmed)
+        {
+            GraphPhase::Settled
+        } else if [challenge_status, assert_initial_status, assert_final_status]
+            .into_iter()
+            .any(is_confirmed)
+        {
+            GraphPhase::Disputed
+        } else if [kick_off_1_status, start_time_status, kick_off_2_status]
+            .into_iter()
+            .any(is_confirmed)
+        {
+            GraphPhase::InFlight
+        } else if is_confirmed(peg_out_confirm_status) || peg_out_status.is_some_and(is_confirmed) {
+            GraphPhase::Funded
+        } else {
+            GraphPhase::Setup
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn interpret_operator_status(
+        &self,
+        assert_initial_status: &Result<TxStatus, Error>,
+        assert_final_status: &Result<TxStatus, Error>,
+        challenge_status: &Result<TxStatus, Error>,
+        disprove_chain_status: &Result<TxStatus, Error>,
+        disprove_status: &Result<TxStatus, Error>,
+        peg_out_confirm_status: &Result<TxStatus, Error>,
+        kick_off_1_status: &Result<TxStatus, Error>,
+        kick_off_2_status: &Result<TxStatus, Error>,
+        kick_off_timeout_status: &Result<TxStatus, Error>,
+        peg_out_status: Option<&Result<TxStatus, Error>>,
+        start_time_timeout_status: &Result<TxStatus, Error>,
+        start_time_status: &Result<TxStatus, Error>,
+        take_1_status: &Result<TxStatus, Error>,
+        take_2_status: &Result<TxStatus, Error>,
+        blockchain_height: u32,
+        min_confirmations: u32,
+    ) -> PegOutOperatorStatus {
+        if self.n_of_n_presigned && self.is_peg_out_initiated() {
+            if peg_out_status.is_some_and(|status| status.as_ref().unwrap().confirmed) {
+                if kick_off_2_status
+                    .as_ref()
+                    .is_ok_and(|status| status.confirmed)
+                {
+                    if let Some(settlement) = self.settlement(
+                        [
+                            (TxRole::Take1, take_1_status),
+                            (TxRole::Take2, take_2_status),
+                        ],
+                        blockchain_height,
+                        min_confirmations,
+                    ) {
+                        return settlement.map_or_else(PegOutOperatorStatus::PegOutWait, |_| {
+                            PegOutOperatorStatus::PegOutComplete
+                        });
+                    } else if let Some(settlement) = self.settlement(
+                        [
+                            (TxRole::DisproveChain, disprove_chain_status),
+                            (TxRole::Disprove, disprove_status),
+                        ],
+                        blockchain_height,
+                        min_confirmations,
+                    ) {
+                        return settlement.map_or_else(PegOutOperatorStatus::PegOutWait, |_| {
+                            PegOutOperatorStatus::PegOutFailed // TODO: can be also `PegOutOperatorStatus::PegOutComplete`
+                        });
+                    } else if challenge_status
+                        .as_ref()
+                        .is_ok_and(|status| status.confirmed)
+                    {
+                        if assert_final_status
+                            .as_ref()
+                            .is_ok_and(|status| status.confirmed)
+                        {
+                            if is_timelock_elapsed(
+                                assert_final_status,
+                                self.connector_4.num_blocks_timelock,
+                                blockchain_height,
+                            ) {
+                                return PegOutOperatorStatus::PegOutTake2Available;
+                            } else {
+                                return Self::timelock_wait_status(
+                                    assert_final_status,
+                                    self.connector_4.num_blocks_timelock,
+                                    blockchain_height,
+                                );
+                            }
+                        } else if assert_initial_status
+                            .as_ref()
+                            .is_ok_and(|status| status.confirmed)
+                        {
+                            return PegOutOperatorStatus::PegOutAssertFinalAvailable;
+                        } else if is_timelock_elapsed(
+                            kick_off_2_status,
+                            self.connector_b.num_blocks_timelock_1,
+                            blockchain_height,
+                        ) {
+                            return PegOutOperatorStatus::PegOutAssertInitialAvailable;
+                        } else {
+                            return Self::timelock_wait_status(
+                                kick_off_2_status,
+                                self.connector_b.num_blocks_timelock_1,
+                                blockchain_height,
+                            );
+                        }
+                    } else if is_timelock_elapsed(
+                        kick_off_2_status,
+                        self.connector_3.num_blocks_timelock,
+                        blockchain_height,
+                    ) {
+                        return PegOutOperatorStatus::PegOutTake1Available;
+                    } else {
+                        return Self::timelock_wait_status(
+                            kick_off_2_status,
+                            self.connector_3.num_blocks_timelock,
+                            blockchain_height,
+                        );
+                    }
+                } else if kick_off_1_status
+                    .as_ref()
+                    .is_ok_and(|status| status.confirmed)
+                {
+                    if let Some(settlement) = self.settlement(
+                        [
+                            (TxRole::StartTimeTimeout, start_time_timeout_status),
+                            (TxRole::KickOffTimeout, kick_off_timeout_status),
+                        ],
+                        blockchain_height,
+                        min_confirmations,
+                    ) {
+                        return settlement.map_or_else(PegOutOperatorStatus::PegOutWait, |_| {
+                            PegOutOperatorStatus::PegOutFailed // TODO: can be also `PegOutOperatorStatus::PegOutComplete`
+                        });
+                    } else if start_time_status
+                        .as_ref()
+                        .is_ok_and(|status| status.confirmed)
+                    {
+                        if is_timelock_elapsed(
+                            kick_off_1_status,
+                            self.connector_1.num_blocks_timelock_leaf_0,
+                            blockchain_height,
+                        ) {
+                            return PegOutOperatorStatus::PegOutKickOff2Available;
+                        } else {
+                            return Self::timelock_wait_status(
+                                kick_off_1_status,
+                                self.connector_1.num_blocks_timelock_leaf_0,
+                                blockchain_height,
+                            );
+                        }
+                    } else {
+                        return PegOutOperatorStatus::PegOutStartTimeAvailable;
+                    }
+                } else if peg_out_confirm_status
+                    .as_ref()
+                    .is_ok_and(|status| status.confirmed)
+                {
+                    return PegOutOperatorStatus::PegOutKickOff1Available;
+                } else {
+                    return PegOutOperatorStatus::PegOutPegOutConfirmAvailable;
+                }
+            } else {
+                return PegOutOperatorStatus::PegOutStartPegOut;
+            }
+        }
+
+        PegOutOperatorStatus::PegOutWait(self.setup_wait_reason())
+    }
+
+    pub async fn pending_timelock(&self, client: &AsyncClient) -> Option<PendingTimelock> {
+        let (
+            assert_initial_status,
+            assert_final_status,
+            challenge_status,
+            disprove_chain_status,
+            disprove_status,
+            peg_out_confirm_status,
+            kick_off_1_status,
+            kick_off_2_status,
+            kick_off_timeout_status,
+            peg_out_status,
+            start_time_timeout_status,
+            start_time_status,
+            take_1_status,
+            take_2_status,
+        ) = self.get_peg_out_statuses(client).await;
+        let blockchain_height = get_block_height(client).await;
+
+        self.interpret_pending_timelock(
+            &assert_initial_status,
+            &assert_final_status,
+            &challenge_status,
+            &disprove_chain_status,
+            &disprove_status,
+            &peg_out_confirm_status,
+            &kick_off_1_status,
+            &kick_off_2_status,
+            &kick_off_timeout_status,
+            peg_out_status.as_ref(),
+            &start_time_timeout_status,
+            &start_time_status,
+            &take_1_status,
+            &take_2_status,
+            blockchain_height,
+        )
+    }
+
+    // `None` unless `interpret_operator_status` waits on a timelock. Which one it
+    // waits on follows from the last confirmed tx on the path to the take txs.
+    #[allow(clippy::too_many_arguments)]
+    pub fn interpret_pending_timelock(
+        &self,
+        assert_initial_status: &Result<TxStatus, Error>,
+        assert_final_status: &Result<TxStatus, Error>,
+        challenge_status: &Result<TxStatus, Error>,
+        disprove_chain_status: &Result<TxStatus, Error>,
+        disprove_status: &Result<TxStatus, Error>,
+        peg_out_confirm_status: &Result<TxStatus, Error>,
+        kick_off_1_status: &Result<TxStatus, Error>,
+        kick_off_2_status: &Result<TxStatus, Error>,
+        kick_off_timeout_status: &Result<TxStatus, Error>,
+        peg_out_status: Option<&Result<TxStatus, Error>>,
+        start_time_timeout_status: &Result<TxStatus, Error>,
+        start_time_status: &Result<TxStatus, Error>,
+        take_1_status: &Result<TxStatus, Error>,
+        take_2_status: &Result<TxStatus, Error>,
+        blockchain_height: u32,
+    ) -> Option<PendingTimelock> {
+        let PegOutOperatorStatus::PegOutWait(WaitReason::TimelockPending { remaining_blocks }) =
+            self.interpret_operator_status(
+                assert_initial_status,
+                assert_final_status,
+                challenge_status,
+                disprove_chain_status,
+                disprove_status,
+                peg_out_confirm_status,
+                kick_off_1_status,
+                kick_off_2_status,
+                kick_off_timeout_status,
+                peg_out_status,
+                start_time_timeout_status,
+                start_time_status,
+                take_1_status,
+                take_2_status,
+                blockchain_height,
+                // the depth of a terminal tx does not change which timelock is pending
+                DEFAULT_MIN_CONFIRMATIONS,
+            )
+        else {
+            return None;
+        };
+
+        let is_confirmed =
+            |status: &Result<TxStatus, Error>| status.as_ref().is_ok_and(|status| status.confirmed);
+        let tx_role = if is_confirmed(assert_final_status) {
+            TxRole::Take2
+        } else if is_confirmed(challenge_status) {
+            TxRole::AssertInitial
+        } else if is_confirmed(kick_off_2_status) {
+            TxRole::Take1
+        } else {
+            TxRole::KickOff2
+        };
+        let timelock = self
+            .timelocks()
+            .into_iter()
+            .find(|timelock| timelock.tx_name == tx_role.tx_name())
+            .expect("Every timelock wait is listed in the graph timelocks");
+
+        Some(PendingTimelock {
+            connector: timelock.connector_name.to_string(),
+            blocks_remaining: remaining_blocks,
+            tx_role,
+        })
+    }
+
+    // `None` while neither of the terminal `txs` is confirmed. A confirmed one only
+    // settles the graph once it is `min_confirmations` deep, until then a reorg
+    // could still undo it.
+    fn settlement(
+        &self,
+        txs: [(TxRole, &Result<TxStatus, Error>); 2],
+        blockchain_height: u32,
+        min_confirmations: u32,
+    ) -> Option<Result<(), WaitReason>> {
+        let (role, tx_status) = txs
+            .into_iter()
+            .filter(|(_, tx_status)| tx_status.as_ref().is_ok_and(|status| status.confirmed))
+            .max_by_key(|(_, tx_status)| tx_confirmations(tx_status, blockchain_height))?;
+
+        let confirmations = tx_confirmations(tx_status, blockchain_height);
+        // being confirmed at all is enough, whether or not the height is known
+        if min_confirmations <= 1 || confirmations >= min_confirmations {
+            Some(Ok(()))
+        } else {
+            Some(Err(WaitReason::AwaitingDepth {
+                txid: self.txid(role),
+                confirmations,
+            }))
+        }
+    }
+
+    // The operator can only act once the graph is presigned and the withdrawer
+    // has initiated the peg-out.
+    fn setup_wait_reason(&self) -> WaitReason {
+        if self.n_of_n_presigned {
+            WaitReason::AwaitingCounterparty
+        } else {
+            WaitReason::AwaitingPresign
+        }
+    }
+
+    // A timelock measured from a tx that is confirmed without a block height
+    // cannot be evaluated yet. Waiting on it would stall until the status is
+    // fetched again anyway, so ask for that explicitly.
+    fn timelock_wait_status(
+        tx_status: &Result<TxStatus, Error>,
+        num_blocks_timelock: u32,
+        blockchain_height: u32,
+    ) -> PegOutOperatorStatus {
+        match timelock_remaining_blocks(tx_status, num_blocks_timelock, blockchain_height) {
+            Some(remaining_blocks) => {
+                PegOutOperatorStatus::PegOutWait(WaitReason::TimelockPending { remaining_blocks })
+            }
+            None => PegOutOperatorStatus::PegOutRefetch,
+        }
+    }
+
+    pub fn interpret_withdrawer_status(
+        &self,
+        peg_out_status: Option<&Result<TxStatus, Error>>,
+    ) -> PegOutWithdrawerStatus {
+        if let Some(peg_out_status) = peg_out_status {
+            if peg_out_status.as_ref().is_ok_and(|status| status.confirmed) {
+                PegOutWithdrawerStatus::PegOutComplete
+            } else {
+                PegOutWithdrawerStatus::PegOutWait(match self.peg_out_txid() {
+                    Some(txid) => WaitReason::AwaitingConfirmation { txid },
+                    None => WaitReason::AwaitingCounterparty,
+                })
+            }
+        } else {
+            PegOutWithdrawerStatus::PegOutNotStarted
+        }
+    }
+
+    pub async fn withdrawer_status(&self, client: &AsyncClient) -> PegOutWithdrawerStatus {
+        let peg_out_status = match self.peg_out_txid() {
+            Some(peg_out_txid) => Some(client.get_tx_status(&peg_out_txid).await),
+            None => None,
+        };
+        self.interpret_withdrawer_status(peg_out_status.as_ref())
+    }
+
+    // Fails if the withdrawer's destination does not fit the network of the graph.
+    // With `signal_rbf` the operator can replace the peg-out tx until it confirms.
+    pub async fn peg_out(
+        &mut self,
+        client: &AsyncClient,
+        context: &OperatorContext,
+        input: Input,
+        signal_rbf: bool,
+    ) -> Result<(), PegOutDestinationError> {
+        if !self.is_peg_out_initiated() {
+            panic!("Peg out not initiated on L2 chain");
+        }
+
+        if let Some(txid) = self.peg_out_txid() {
+            verify_if_not_mined(client, txid).await;
+        } else {
+            let event = self.peg_out_chain_event.as_ref().unwrap();
+            let tx = PegOutTransaction::new_with_rbf(context, event, input, signal_rbf)?;
+            self.set_peg_out_transaction(tx);
+        }
+
+        let peg_out_tx = self.peg_out_transaction.as_ref().unwrap().finalize();
+
+        broadcast_and_verify(client, &peg_out_tx).await;
+
+        Ok(())
+    }
+
+    pub async fn peg_out_confirm(&mut self, client: &AsyncClient) -> Result<(), ActionError> {
+        self.verify_not_mined(client, TxRole::PegOutConfirm).await?;
+
+        if let Some(peg_out_txid) = self.peg_out_txid() {
+            let peg_out_status = client.get_tx_status(&peg_out_txid).await;
+
+            if peg_out_status.is_ok_and(|status| status.confirmed) {
+                // complete peg-out-confirm tx
+                let peg_out_confirm_tx = self.peg_out_confirm_transaction.finalize();
+
+                // broadcast peg-out-confirm tx
+                self.broadcast_action(client, TxRole::PegOutConfirm, &peg_out_confirm_tx)
+                    .await
+            } else {
+                Err(ActionError::NotReady("Peg-out tx has not been confirmed"))
+            }
+        } else {
+            Err(ActionError::NotReady("Peg-out tx has not been created"))
+        }
+    }
+
+    pub async fn kick_off_1(
+        &mut self,
+        client: &AsyncClient,
+        context: &OperatorContext,
+        source_network_txid_commitment_secret: &WinternitzSecret,
+        destination_network_txid_commitment_secret: &WinternitzSecret,
+    ) -> Result<(), KickOff1Error> {
+        self.verify_action_secrets(ActionSecrets::KickOff1 {
+            source_network_txid_commitment_secret,
+            destination_network_txid_commitment_secret,
+        })
+        .map_err(KickOff1Error::WrongCommitmentSecret)?;
+
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let is_kick_off_1_broadcast = matches!(client.get_tx(&kick_off_1_txid).await, Ok(Some(_)));
+        self.verify_kick_off_1_peg_out_txid(is_kick_off_1_broadcast)
+            .map_err(KickOff1Error::PegOutTxidChanged)?;
+
+        self.verify_not_mined(client, TxRole::KickOff1)
+            .await
+            .map_err(KickOff1Error::Action)?;
+
+        let peg_out_confirm_txid = self.txid(TxRole::PegOutConfirm);
+        let peg_out_confirm_status = client.get_tx_status(&peg_out_confirm_txid).await;
+
+        if peg_out_confirm_status.is_ok_and(|status| status.confirmed) {
+            // complete kick-off 1 tx
+            self.sign_kick_off_1(
+                context,
+                source_network_txid_commitment_secret,
+                destination_network_txid_commitment_secret,
+            )
+            .map_err(KickOff1Error::WrongCommitmentSecret)?;
+            let kick_off_1_tx = self.kick_off_1_transaction.finalize();
+
+            // broadcast kick-off 1 tx
+            self.broadcast_action(client, TxRole::KickOff1, &kick_off_1_tx)
+                .await
+                .map_err(KickOff1Error::Action)
+        } else {
+            Err(KickOff1Error::Action(ActionError::NotReady(
+                "Peg-out-confirm tx has not been confirmed",
+            )))
+        }
+    }
+
+    // (Re-)signs kick-off 1 with the commitment to the current peg-out txid.
+    pub fn sign_kick_off_1(
+        &mut self,
+        context: &OperatorContext,
+        source_network_txid_commitment_secret: &WinternitzSecret,
+        destination_network_txid_commitment_secret: &WinternitzSecret,
+    ) -> Result<(), WrongCommitmentSecret> {
+        let peg_out_txid = self.peg_out_txid().unwrap();
+        let source_network_txid_inputs = WinternitzSigningInputs {
+            message: peg_out_txid.as_byte_array(),
+            signing_key: source_network_txid_commitment_secret,
+        };
+        let destination_network_txid_inputs = WinternitzSigningInputs {
+            message: self
+                .peg_out_chain_event
+                .as_ref()
+                .unwrap()
+                .tx_hash
+                .as_slice(),
+            signing_key: destination_network_txid_commitment_secret,
+        };
+        self.kick_off_1_transaction.sign(
+            context,
+            &self.connector_6,
+            &source_network_txid_inputs,
+            &destination_network_txid_inputs,
+        )?;
+        self.kick_off_1_peg_out_txid = Some(peg_out_txid);
+        Ok(())
+    }
+
+    // Kick-off 1 commits to the peg-out txid. Once it is broadcast, a rebuilt
+    // peg-out tx (e.g. after a fee bump) no longer matches the commitment.
+    pub fn verify_kick_off_1_peg_out_txid(
+        &self,
+        is_kick_off_1_broadcast: bool,
+    ) -> Result<(), PegOutTxidChanged> {
+        match (self.kick_off_1_peg_out_txid, self.peg_out_txid()) {
+            (Some(signed_txid), Some(current_txid)) => {
+                if is_kick_off_1_broadcast && signed_txid != current_txid {
+                    Err(PegOutTxidChanged {
+                        signed_txid,
+                        current_txid,
+                    })
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // With `signal_rbf` the challenge tx can be fee bumped through
+    // `replace_challenge_fee` until it confirms.
+    pub async fn challenge(
+        &mut self,
+        client: &AsyncClient,
+        crowdfundng_inputs: &Vec<InputWithScript<'_>>,
+        keypair: &Keypair,
+        output_script_pubkey: Option<ScriptBuf>,
+        signal_rbf: bool,
+    ) -> Result<(), ChallengeError> {
+        let output_script_pubkey = self
+            .resolve_reward_script_pubkey(output_script_pubkey)
+            .map_err(ChallengeError::MissingRewardScript)?;
+        self.challenge_transaction
+            .validate_challenge_funding(crowdfundng_inputs)
+            .map_err(ChallengeError::Funding)?;
+        self.verify_not_mined(client, TxRole::Challenge)
+            .await
+            .map_err(ChallengeError::Action)?;
+
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+
+        if kick_off_1_status.is_ok_and(|status| status.confirmed) {
+            // complete challenge tx
+            self.rebuild_tx(|graph| {
+                graph.challenge_transaction.add_inputs_and_output_with_rbf(
+                    crowdfundng_inputs,
+                    keypair,
+                    output_script_pubkey,
+                    signal_rbf,
+                )
+            })
+            .map_err(ChallengeError::Funding)?;
+            let challenge_tx = self.challenge_transaction.finalize();
+
+            // broadcast challenge tx
+            self.broadcast_action(client, TxRole::Challenge, &challenge_tx)
+                .await
+                .map_err(ChallengeError::Action)
+        } else {
+            Err(ChallengeError::Action(ActionError::NotReady(
+                "Kick-off 1 tx has not been confirmed",
+            )))
+        }
+    }
+
+    // Replaces the broadcast challenge tx while it is unconfirmed with one paying
+    // `new_fee_rate` sat/vB, taken out of the refund output. The inputs and keypair
+    // are the ones the challenge tx was funded with.
+    pub async fn replace_challenge_fee(
+        &mut self,
+        client: &AsyncClient,
+        crowdfundng_inputs: &Vec<InputWithScript<'_>>,
+        keypair: &Keypair,
+        new_fee_rate: f64,
+    ) -> Result<(), ChallengeError> {
+        self.verify_not_mined(client, TxRole::Challenge)
+            .await
+            .map_err(ChallengeError::Action)?;
+
+        self.rebuild_tx(|graph| {
+            graph
+                .challenge_transaction
+                .bump_fee(crowdfundng_inputs, keypair, new_fee_rate)
+        })
+        .map_err(ChallengeError::Funding)?;
+        let challenge_tx = self.challenge_transaction.finalize();
+
+        // the replacement spends the outputs the replaced tx has locked
+        self.end_action(TxRole::Challenge);
+        self.broadcast_action(client, TxRole::Challenge, &challenge_tx)
+            .await
+            .map_err(ChallengeError::Action)
+    }
+
+    pub async fn start_time(
+        &mut self,
+        client: &AsyncClient,
+        context: &OperatorContext,
+        start_time_commitment_secret: &WinternitzSecret,
+    ) -> Result<(), StartTimeError> {
+        self.verify_action_secrets(ActionSecrets::StartTime {
+            start_time_commitment_secret,
+        })
+        .map_err(StartTimeError::WrongCommitmentSecret)?;
+        self.verify_not_mined(client, TxRole::StartTime)
+            .await
+            .map_err(StartTimeError::Action)?;
+
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+
+        if kick_off_1_status.is_ok_and(|status| status.confirmed) {
+            // sign start time tx
+            let start_time_block = get_start_time_block_number(&self.superblock_params());
+            self.rebuild_tx(|graph| {
+                graph.start_time_transaction.sign(
+                    context,
+                    &graph.connector_2,
+                    start_time_block,
+                    start_time_commitment_secret,
+                )
+            })
+            .map_err(StartTimeError::WrongCommitmentSecret)?;
+
+            // complete start time tx
+            let start_time_tx = self.start_time_transaction.finalize();
+
+            // broadcast start time tx
+            self.broadcast_action(client, TxRole::StartTime, &start_time_tx)
+                .await
+                .map_err(StartTimeError::Action)
+        } else {
+            Err(StartTimeError::Action(ActionError::NotReady(
+                "Kick-off 1 tx has not been confirmed",
+            )))
+        }
+    }
+
+    pub async fn start_time_timeout(
+        &mut self,
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+        output_script_pubkey: Option<ScriptBuf>,
+    ) -> Result<(), TimeoutError> {
+        self.verify_not_mined(client, TxRole::StartTimeTimeout)
+            .await
+            .map_err(TimeoutError::Action)?;
+        let output_script_pubkey = self
+            .resolve_reward_script_pubkey(output_script_pubkey)
+            .map_err(TimeoutError::MissingRewardScript)?;
+
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+
+        let blockchain_height = Self::action_block_height(client, blockchain_height)
+            .await
+            .map_err(TimeoutError::Action)?;
+
+        if kick_off_1_status
+            .as_ref()
+            .is_ok_and(|status| status.confirmed)
+        {
+            if is_timelock_elapsed(
+                &kick_off_1_status,
+                self.connector_1.num_blocks_timelock_leaf_2,
+                blockchain_height,
+            ) {
+                // complete start time timeout tx
+                self.rebuild_tx(|graph| {
+                    graph
+                        .start_time_timeout_transaction
+                        .add_output(output_script_pubkey)
+                });
+                let start_time_timeout_tx = self.start_time_timeout_transaction.finalize();
+
+                // broadcast start time timeout tx
+                self.broadcast_action(client, TxRole::StartTimeTimeout, &start_time_timeout_tx)
+                    .await
+                    .map_err(TimeoutError::Action)
+            } else {
+                Err(TimeoutError::Action(ActionError::NotReady(
+                    "Kick-off 1 timelock has not elapsed",
+                )))
+            }
+        } else {
+            Err(TimeoutError::Action(ActionError::NotReady(
+                "Kick-off 1 tx has not been confirmed",
+            )))
+        }
+    }
+
+    pub async fn kick_off_2(
+        &mut self,
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+        context: &OperatorContext,
+        superblock_commitment_secret: &WinternitzSecret,
+        superblock_hash_commitment_secret: &WinternitzSecret,
+        superblock_criteria: &SuperblockCriteria,
+    ) -> Result<(), KickOff2Error> {
+        self.verify_action_secrets(ActionSecrets::KickOff2 {
+            superblock_commitment_secret,
+            superblock_hash_commitment_secret,
+        })
+        .map_err(KickOff2Error::WrongCommitmentSecret)?;
+        self.verify_not_mined(client, TxRole::KickOff2)
+            .await
+            .map_err(KickOff2Error::Action)?;
+
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+
+        let blockchain_height = Self::action_block_height(client, blockchain_height)
+            .await
+            .map_err(KickOff2Error::Action)?;
+
+        if kick_off_1_status
+            .as_ref()
+            .is_ok_and(|status| status.confirmed)
+        {
+            if is_timelock_elapsed(
+                &kick_off_1_status,
+                self.connector_1.num_blocks_timelock_leaf_0,
+                blockchain_height,
+            ) {
+                // complete kick-off 2 tx
+                let superblock_candidates =
+                    fetch_superblock_candidates(client, superblock_criteria).await;
+                let superblock_headers = find_superblocks(
+                    self.connector_1.superblock_policy,
+                    &superblock_candidates,
+                    superblock_criteria,
+                )
+                .map_err(KickOff2Error::NoSuperblockFound)?;
+                let superblock_hash_message = get_superblocks_hash_message(&superblock_headers)
+                    .map_err(|_| KickOff2Error::NoSuperblockFound(NoSuperblockFound))?;
+                self.kick_off_2_transaction
+                    .sign(
+                        context,
+                        &self.connector_1,
+                        &WinternitzSigningInputs {
+                            message: &get_superblocks_message(&superblock_headers),
+                            signing_key: superblock_commitment_secret,
+                        },
+                        &WinternitzSigningInputs {
+                            message: &superblock_hash_message,
+                            signing_key: superblock_hash_commitment_secret,
+                        },
+                    )
+                    .map_err(KickOff2Error::WrongCommitmentSecret)?;
+                let kick_off_2_tx = self.kick_off_2_transaction.finalize();
+
+                // broadcast kick-off 2 tx
+                self.broadcast_action(client, TxRole::KickOff2, &kick_off_2_tx)
+                    .await
+                    .map_err(KickOff2Error::Action)
+            } else {
+                Err(KickOff2Error::Action(ActionError::NotReady(
+                    "Kick-off 1 timelock has not elapsed",
+                )))
+            }
+        } else {
+            Err(KickOff2Error::Action(ActionError::NotReady(
+                "Kick-off 1 tx has not been confirmed",
+            )))
+        }
+    }
+
+    pub async fn kick_off_timeout(
+        &mut self,
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+        output_script_pubkey: Option<ScriptBuf>,
+    ) -> Result<(), TimeoutError> {
+        self.verify_not_mined(client, TxRole::KickOffTimeout)
+            .await
+            .map_err(TimeoutError::Action)?;
+        let output_script_pubkey = self
+            .resolve_reward_script_pubkey(output_script_pubkey)
+            .map_err(TimeoutError::MissingRewardScript)?;
+
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+
+        let blockchain_height = Self::action_block_height(client, blockchain_height)
+            .await
+            .map_err(TimeoutError::Action)?;
+
+        if kick_off_1_status
+            .as_ref()
+            .is_ok_and(|status| status.confirmed)
+        {
+            if is_timelock_elapsed(
+                &kick_off_1_status,
+                self.connector_1.num_blocks_timelock_leaf_1,
+                blockchain_height,
+            ) {
+                // complete kick-off timeout tx
+                self.rebuild_tx(|graph| {
+                    graph
+                        .kick_off_timeout_transaction
+                        .add_output(output_script_pubkey)
+                });
+                let kick_off_timeout_tx = self.kick_off_timeout_transaction.finalize();
+
+                // broadcast kick-off timeout tx
+                self.broadcast_action(client, TxRole::KickOffTimeout, &kick_off_timeout_tx)
+                    .await
+                    .map_err(TimeoutError::Action)
+            } else {
+                Err(TimeoutError::Action(ActionError::NotReady(
+                    "Kick-off 1 timelock has not elapsed",
+                )))
+            }
+        } else {
+            Err(TimeoutError::Action(ActionError::NotReady(
+                "Kick-off 1 tx has not been confirmed",
+            )))
+        }
+    }
+
+    pub async fn assert_initial(
+        &mut self,
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+    ) -> Result<(), ActionError> {
+        self.verify_not_mined(client, TxRole::AssertInitial).await?;
+
+        let kick_off_2_txid = self.txid(TxRole::KickOff2);
+        let kick_off_2_status = client.get_tx_status(&kick_off_2_txid).await;
+
+        let blockchain_height = Self::action_block_height(client, blockchain_height).await?;
+
+        if kick_off_2_status
+            .as_ref()
+            .is_ok_and(|status| status.confirmed)
+        {
+            if is_timelock_elapsed(
+                &kick_off_2_status,
+                self.connector_b.num_blocks_timelock_1,
+                blockchain_height,
+            ) {
+                // complete assert initial tx
+                let assert_initial_tx = self.assert_initial_transaction.finalize();
+
+                // broadcast assert initial tx
+                self.broadcast_action(client, TxRole::AssertInitial, &assert_initial_tx)
+                    .await
+            } else {
+                Err(ActionError::NotReady("Kick-off 2 timelock has not elapsed"))
+            }
+        } else {
+            Err(ActionError::NotReady(
+                "Kick-off 2 tx has not been confirmed",
+            ))
+        }
+    }
+
+    pub async fn assert_final(&mut self, client: &AsyncClient) -> Result<(), ActionError> {
+        self.verify_not_mined(client, TxRole::AssertFinal).await?;
+
+        let assert_initial_txid = self.txid(TxRole::AssertInitial);
+        let assert_initial_status = client.get_tx_status(&assert_initial_txid).await;
+
+        if assert_initial_status
+            .as_ref()
+            .is_ok_and(|status| status.confirmed)
+        {
+            // complete assert final tx
+            let assert_final_tx = self.assert_final_transaction.finalize();
+
+            // broadcast assert final tx
+            self.broadcast_action(client, TxRole::AssertFinal, &assert_final_tx)
+                .await
+        } else {
+            Err(ActionError::NotReady(
+                "Assert-initial tx has not been confirmed",
+            ))
+        }
+    }
+
+    // Publishes assert initial, both assert commits and assert final in one go, so
+    // that the operator does not wait on confirmations in between. Txs that are
+    // already known to esplora are skipped, a failed call can be retried.
+    pub async fn broadcast_assert_chain(
+        &mut self,
+        client: &AsyncClient,
+        commitment_secrets: &HashMap<CommitmentMessageId, WinternitzSecret>,
+        proof: &RawProof,
+    ) -> Result<Vec<Txid>, AssertChainBroadcastError> {
+        verify_if_not_mined(client, self.txid(TxRole::AssertFinal)).await;
+
+        let (assert_commit_1_tx, assert_commit_2_tx) =
+            self.assert_commit_transactions(commitment_secrets, proof);
+        let assert_chain = [
+            (TxRole::AssertInitial, self.assert_initial_transaction.finalize()),
+            (TxRole::AssertCommit1, assert_commit_1_tx),
+            (TxRole::AssertCommit2, assert_commit_2_tx),
+            (TxRole::AssertFinal, self.assert_final_transaction.finalize()),
+        ];
+
+        self.begin_action(TxRole::AssertInitial)
+            .unwrap_or_else(|err| panic!("{err}"));
+        self.begin_action(TxRole::AssertFinal)
+            .unwrap_or_else(|err| panic!("{err}"));
+
+        // esplora has no package relay, the txs go out one by one in chain order
+        let mut broadcast = vec![];
+        for (role, tx) in assert_chain {
+            let txid = self.txid(role);
+            if !matches!(client.get_tx(&txid).await, Ok(Some(_))) {
+                if let Err(err) = client.broadcast(&tx).await {
+                    if broadcast.is_empty() {
+                        self.end_action(TxRole::AssertInitial);
+                    }
+                    self.end_action(TxRole::AssertFinal);
+                    return Err(AssertChainBroadcastError {
+                        broadcast,
+                        failed_tx: role.tx_name(),
+                        error: format!("{err:?}"),
+                    });
+                }
+            }
+            broadcast.push(txid);
+        }
+
+        Ok(broadcast)
+    }
+
+    // The assert commit txs are not kept in the graph, they are rebuilt from the
+    // assert initial outputs and signed with the operator's Groth16 commitments.
+    pub fn assert_commit_transactions(
+        &self,
+        commitment_secrets: &HashMap<CommitmentMessageId, WinternitzSecret>,
+        proof: &RawProof,
+    ) -> (Transaction, Transaction) {
+        let (mut assert_commit_1, mut assert_commit_2) = self.unsigned_assert_commit_transactions();
+        let (witness_for_commit1, witness_for_commit2) =
+            sign_assert_tx_with_groth16_proof(commitment_secrets, proof);
+        assert_commit_1.sign(&self.connector_e_1, witness_for_commit1);
+        assert_commit_2.sign(&self.connector_e_2, witness_for_commit2);
+
+        let assert_commit_1_tx = assert_commit_1.finalize();
+        let assert_commit_2_tx = assert_commit_2.finalize();
+        let assert_final_inputs = &self.assert_final_transaction.tx().input;
+        assert_eq!(
+            assert_final_inputs[1].previous_output.txid,
+            assert_commit_1_tx.compute_txid(),
+            "Rebuilt assert commit 1 tx is not the one assert final spends"
+        );
+        assert_eq!(
+            assert_final_inputs[2].previous_output.txid,
+            assert_commit_2_tx.compute_txid(),
+            "Rebuilt assert commit 2 tx is not the one assert final spends"
+        );
+
+        (assert_commit_1_tx, assert_commit_2_tx)
+    }
+
+    fn unsigned_assert_commit_transactions(
+        &self,
+    ) -> (AssertCommit1Transaction, AssertCommit2Transaction) {
+        Self::build_assert_commit_transactions(
+            &self.assert_initial_transaction,
+            &self.connector_e_1,
+            &self.connector_f_1,
+            &self.connector_e_2,
+            &self.connector_f_2,
+        )
+        .unwrap_or_else(|err| panic!("Cannot rebuild assert commit txs: {err}"))
+    }
+
+    // Assert commit 1 spends the assert initial outputs from `ASSERT_COMMIT_VOUT_BASE`
+    // on, assert commit 2 the ones that follow.
+    fn build_assert_commit_transactions(
+        assert_initial_transaction: &AssertInitialTransaction,
+        connectors_e_1: &AssertCommit1ConnectorsE,
+        connector_f_1: &ConnectorF1,
+        connectors_e_2: &AssertCommit2ConnectorsE,
+        connector_f_2: &ConnectorF2,
+    ) -> Result<(AssertCommit1Transaction, AssertCommit2Transaction), GraphConstructionError> {
+        let assert_initial_txid = assert_initial_transaction.tx().compute_txid();
+        let assert_initial_outputs = &assert_initial_transaction.tx().output;
+        let inputs =
+            |vouts: Range<usize>, field: &str| -> Result<Vec<Input>, GraphConstructionError> {
+                vouts
+                    .map(|vout| {
+                        Ok(Input {
+                            outpoint: OutPoint {
+                                txid: assert_initial_txid,
+                                vout: checked_vout(vout, field)?,
+                            },
+                            amount: assert_initial_outputs[vout].value,
+                        })
+                    })
+                    .collect()
+            };
+        let commit_1_end = topology::ASSERT_COMMIT_VOUT_BASE + connectors_e_1.connectors_num();
+        let commit_2_end = commit_1_end + connectors_e_2.connectors_num();
+
+        Ok((
+            AssertCommit1Transaction::new_for_validation(
+                connectors_e_1,
+                connector_f_1,
+                inputs(
+                    topology::ASSERT_COMMIT_VOUT_BASE..commit_1_end,
+                    "assert_commit1_vout",
+                )?,
+            ),
+            AssertCommit2Transaction::new_for_validation(
+                connectors_e_2,
+                connector_f_2,
+                inputs(commit_1_end..commit_2_end, "assert_commit2_vout")?,
+            ),
+        ))
+    }
+
+    // The Groth16 intermediate values the operator committed to, read back from the
+    // assert commit txs that assert final spends.
+    pub async fn fetch_assert_commit_witnesses(
+        &self,
+        client: &AsyncClient,
+    ) -> Result<(Vec<RawWitness>, Vec<RawWitness>), AssertCommitWitnessError> {
+        let assert_final_inputs = &self.assert_final_transaction.tx().input;
+        let mut assert_commit_txs = vec![];
+        for input in &assert_final_inputs[1..3] {
+            let txid = input.previous_output.txid;
+            match client.get_tx(&txid).await {
+                Ok(Some(tx)) => assert_commit_txs.push(tx),
+                Ok(None) => return Err(AssertCommitWitnessError::NotFound(txid)),
+                Err(err) => return Err(AssertCommitWitnessError::Esplora(format!("{err:?}"))),
+            }
+        }
+
+        self.assert_commit_witnesses(&assert_commit_txs[0], &assert_commit_txs[1])
+    }
+
+    pub fn assert_commit_witnesses(
+        &self,
+        assert_commit_1_tx: &Transaction,
+        assert_commit_2_tx: &Transaction,
+    ) -> Result<(Vec<RawWitness>, Vec<RawWitness>), AssertCommitWitnessError> {
+        let assert_final_inputs = &self.assert_final_transaction.tx().input;
+        let commit_witnesses = |tx: &Transaction, assert_final_vin: usize| {
+            let txid = tx.compute_txid();
+            if assert_final_inputs[assert_final_vin].previous_output.txid != txid {
+                return Err(AssertCommitWitnessError::NotAssertCommitTx(txid));
+            }
+
+            // a script path spend ends with the leaf script and its control block
+            tx.input
+                .iter()
+                .enumerate()
+                .map(|(input_index, input)| {
+                    let witness = input.witness.to_vec();
+                    match witness.len().checked_sub(2) {
+                        Some(len) if len > 0 => Ok(witness[..len].to_vec()),
+                        _ => Err(AssertCommitWitnessError::MalformedWitness { txid, input_index }),
+                    }
+                })
+                .collect::<Result<Vec<RawWitness>, _>>()
+        };
+
+        Ok((
+            commit_witnesses(assert_commit_1_tx, 1)?,
+            commit_witnesses(assert_commit_2_tx, 2)?,
+        ))
+    }
+
+    // Finds the connector c leaf the committed values fail and the witness that
+    // satisfies it.
+    pub fn generate_disprove_witness(
+        &self,
+        commit_witnesses: (Vec<RawWitness>, Vec<RawWitness>),
+    ) -> Result<(u32, RawWitness), DisproveError> {
+        let (commit_1_witness, commit_2_witness) = commit_witnesses;
+        let (script_index, disprove_witness) = self
+            .connector_c
+            .generate_disprove_witness(
+                commit_1_witness,
+                commit_2_witness,
+                self.verifying_key().clone(),
+            )
+            .map_err(DisproveError::Segments)?
+            .ok_or(DisproveError::AssertionsValid)?;
+        let script_index = script_index as u32;
+        if !self
+            .connector_c
+            .verify_disprove_witness(script_index, &disprove_witness)
+        {
+            return Err(DisproveError::InvalidDisproveWitness(
+                InvalidDisproveWitness { script_index },
+            ));
+        }
+
+        Ok((script_index, disprove_witness))
+    }
+
+    pub async fn disprove(
+        &mut self,
+        client: &AsyncClient,
+        commit_witnesses: (Vec<RawWitness>, Vec<RawWitness>),
+        output_script_pubkey: Option<ScriptBuf>,
+    ) -> Result<(), DisproveError> {
+        self.verify_not_mined(client, TxRole::Disprove)
+            .await
+            .map_err(DisproveError::Action)?;
+        let output_script_pubkey = self
+            .resolve_reward_script_pubkey(output_script_pubkey)
+            .map_err(DisproveError::MissingRewardScript)?;
+
+        let assert_final_txid = self.txid(TxRole::AssertFinal);
+        let assert_final_status = client.get_tx_status(&assert_final_txid).await;
+
+        if assert_final_status.is_ok_and(|status| status.confirmed) {
+            let (input_script_index, disprove_witness) =
+                self.generate_disprove_witness(commit_witnesses)?;
+
+            // complete disprove tx
+            self.rebuild_tx(|graph| {
+                graph.disprove_transaction.add_input_output(
+                    &graph.connector_c,
+                    input_script_index,
+                    disprove_witness,
+                    output_script_pubkey,
+                )
+            });
+            let disprove_tx = self.disprove_transaction.finalize();
+
+            // broadcast disprove tx
+            self.broadcast_action(client, TxRole::Disprove, &disprove_tx)
+                .await
+                .map_err(DisproveError::Action)
+        } else {
+            Err(DisproveError::Action(ActionError::NotReady(
+                "Assert tx has not been confirmed",
+            )))
+        }
+    }
+
+    pub async fn disprove_chain(
+        &mut self,
+        client: &AsyncClient,
+        disprove_superblocks: &[Header],
+        output_script_pubkey: Option<ScriptBuf>,
+    ) -> Result<(), DisproveError> {
+        self.verify_not_mined(client, TxRole::DisproveChain)
+            .await
+            .map_err(DisproveError::Action)?;
+        let output_script_pubkey = self
+            .resolve_reward_script_pubkey(output_script_pubkey)
+            .map_err(DisproveError::MissingRewardScript)?;
+
+        let kick_off_2_txid = self.txid(TxRole::KickOff2);
+        let kick_off_2_status = client.get_tx_status(&kick_off_2_txid).await;
+
+        if kick_off_2_status.is_ok_and(|status| status.confirmed) {
+            let kick_off_2_tx = client
+                .get_tx(&kick_off_2_txid)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| panic!("Kick-off 2 tx {kick_off_2_txid} could not be fetched!"));
+            // an unreadable commitment counts as an invalid claim
+            let committed_superblocks = self
+                .get_committed_superblocks(&kick_off_2_tx)
+                .unwrap_or_default();
+            if !self
+                .is_superblock_commitment_disproved(&committed_superblocks, disprove_superblocks)
+            {
+                panic!("Superblock commitment is not disproved by the given superblocks!");
+            }
+
+            // complete disprove chain tx
+            self.rebuild_tx(|graph| {
+                graph
+                    .disprove_chain_transaction
+                    .add_output(output_script_pubkey)
+            });
+            let disprove_chain_tx = self.disprove_chain_transaction.finalize();
+
+            // broadcast disprove chain tx
+            self.broadcast_action(client, TxRole::DisproveChain, &disprove_chain_tx)
+                .await
+                .map_err(DisproveError::Action)
+        } else {
+            Err(DisproveError::Action(ActionError::NotReady(
+                "Kick-off 2 tx has not been confirmed",
+            )))
+        }
+    }
+
+    pub async fn take_1(
+        &mut self,
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+    ) -> Result<(), ActionError> {
+        self.verify_not_mined(client, TxRole::Take1).await?;
+        self.verify_not_mined(client, TxRole::Challenge).await?;
+        self.verify_not_mined(client, TxRole::AssertFinal).await?;
+        self.verify_not_mined(client, TxRole::DisproveChain).await?;
+
+        let peg_in_confirm_status = client.get_tx_status(&self.peg_in_confirm_txid).await;
+
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+
+        let kick_off_2_txid = self.txid(TxRole::KickOff2);
+        let kick_off_2_status = client.get_tx_status(&kick_off_2_txid).await;
+
+        let blockchain_height = Self::action_block_height(client, blockchain_height).await?;
+
+        if peg_in_confirm_status.is_ok_and(|status| status.confirmed)
+            && kick_off_1_status
+                .as_ref()
+                .is_ok_and(|status| status.confirmed)
+            && kick_off_2_status
+                .as_ref()
+                .is_ok_and(|status| status.confirmed)
+        {
+            if is_timelock_elapsed(
+                &kick_off_2_status,
+                self.connector_3.num_blocks_timelock,
+                blockchain_height,
+            ) {
+                // complete take 1 tx
+                let take_1_tx = self.take_1_transaction.finalize();
+
+                // broadcast take 1 tx
+                self.broadcast_action(client, TxRole::Take1, &take_1_tx)
+                    .await
+            } else {
+                Err(ActionError::NotReady(
+                    "Kick-off 2 tx timelock has not elapsed",
+                ))
+            }
+        } else {
+            Err(ActionError::NotReady(
+                "Peg-in confirm tx, kick-off 1 and kick-off 2 tx have not been confirmed",
+            ))
+        }
+    }
+
+    pub async fn take_2(
+        &mut self,
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+        context: &OperatorContext,
+    ) -> Result<(), Take2Error> {
+        self.verify_not_mined(client, TxRole::Take2)
+            .await
+            .map_err(Take2Error::Action)?;
+        self.verify_not_mined(client, TxRole::Take1)
+            .await
+            .map_err(Take2Error::Action)?;
+        self.verify_not_mined(client, TxRole::Disprove)
+            .await
+            .map_err(Take2Error::Action)?;
+
+        let peg_in_confirm_status = client.get_tx_status(&self.peg_in_confirm_txid).await;
+
+        let assert_final_txid = self.txid(TxRole::AssertFinal);
+        let assert_final_status = client.get_tx_status(&assert_final_txid).await;
+
+        let blockchain_height = Self::action_block_height(client, blockchain_height)
+            .await
+            .map_err(Take2Error::Action)?;
+
+        if peg_in_confirm_status.is_ok_and(|status| status.confirmed)
+            && assert_final_status
+                .as_ref()
+                .is_ok_and(|status| status.confirmed)
+        {
+            if is_timelock_elapsed(
+                &assert_final_status,
+                self.connector_4.num_blocks_timelock,
+                blockchain_height,
+            ) {
+                // complete take 2 tx, the graph is left unsigned if the signature
+                // would not be accepted
+                let mut take_2_transaction = self.take_2_transaction.clone();
+                take_2_transaction.sign(context, &self.connector_c);
+                if !take_2_transaction.is_input_3_signed() {
+                    return Err(Take2Error::SigningFailed(Take2SigningFailed));
+                }
+                self.take_2_transaction = take_2_transaction;
+                let take_2_tx = self.take_2_transaction.finalize();
+
+                // broadcast take 2 tx
+                self.broadcast_action(client, TxRole::Take2, &take_2_tx)
+                    .await
+                    .map_err(Take2Error::Action)
+            } else {
+                Err(Take2Error::Action(ActionError::NotReady(
+                    "Assert tx timelock has not elapsed",
+                )))
+            }
+        } else {
+            Err(Take2Error::Action(ActionError::NotReady(
+                "Peg-in confirm tx and assert tx have not been confirmed",
+            )))
+        }
+    }
+
+    // Validates the graph once, before the first action taken on it.
+    pub fn ensure_validated(&mut self) -> Result<(), GuardedActionError> {
+        if !self.is_validated.0 {
+            if !self.validate() {
+                return Err(GuardedActionError::InvalidGraph(self.id.clone()));
+            }
+            self.is_validated.0 = true;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_validated(&self) -> bool { self.is_validated.0 }
+
+    // Runs an operator action only if the graph passed validation, so that a graph
+    // loaded from (possibly tampered) storage is never broadcast from unchecked.
+    // The only entry point that reports the broadcast or failure of an action to
+    // the observer of the context, the action methods it calls report nothing. An
+    // unconfirmed prerequisite, a failed esplora request and a rejected broadcast
+    // are all returned as errors, and reported as `GraphEvent::ActionFailed`.
+    pub async fn guarded_action(
+        &mut self,
+        action: OperatorAction<'_>,
+        client: &AsyncClient,
+        context: &OperatorContext,
+    ) -> Result<(), GuardedActionError> {
+        let role = action.tx_role();
+        let tx_name = role.tx_name();
+        let result = self.run_guarded_action(action, client, context).await;
+        context.observer.on_event(match &result {
+            Ok(()) => GraphEvent::Broadcast {
+                graph_id: self.id.clone(),
+                tx_name,
+                txid: self.txid(role),
+            },
+            Err(err) => GraphEvent::ActionFailed {
+                graph_id: self.id.clone(),
+                tx_name,
+                error: err.to_string(),
+            },
+        });
+
+        result
+    }
+
+    async fn run_guarded_action(
+        &mut self,
+        action: OperatorAction<'_>,
+        client: &AsyncClient,
+        context: &OperatorContext,
+    ) -> Result<(), GuardedActionError> {
+        self.ensure_validated()?;
+        self.check_action(action.tx_role())
+            .map_err(|err| GuardedActionError::Action(ActionError::InProgress(err)))?;
+
+        match action {
+            OperatorAction::PegOutConfirm => self
+                .peg_out_confirm(client)
+                .await
+                .map_err(GuardedActionError::Action)?,
+            OperatorAction::KickOff1 {
+                source_network_txid_commitment_secret,
+                destination_network_txid_commitment_secret,
+            } => self
+                .kick_off_1(
+                    client,
+                    context,
+                    source_network_txid_commitment_secret,
+                    destination_network_txid_commitment_secret,
+                )
+                .await
+                .map_err(|err| match err {
+                    KickOff1Error::PegOutTxidChanged(err) => {
+                        GuardedActionError::PegOutTxidChanged(err)
+                    }
+                    KickOff1Error::WrongCommitmentSecret(err) => {
+                        GuardedActionError::WrongCommitmentSecret(err)
+                    }
+                    KickOff1Error::Action(err) => GuardedActionError::Action(err),
+                })?,
+            OperatorAction::StartTime {
+                start_time_commitment_secret,
+            } => self
+                .start_time(client, context, start_time_commitment_secret)
+                .await
+                .map_err(|err| match err {
+                    StartTimeError::WrongCommitmentSecret(err) => {
+                        GuardedActionError::WrongCommitmentSecret(err)
+                    }
+                    StartTimeError::Action(err) => GuardedActionError::Action(err),
+                })?,
+            OperatorAction::KickOff2 {
+                blockchain_height,
+                superblock_commitment_secret,
+                superblock_hash_commitment_secret,
+                superblock_criteria,
+            } => self
+                .kick_off_2(
+                    client,
+                    blockchain_height,
+                    context,
+                    superblock_commitment_secret,
+                    superblock_hash_commitment_secret,
+                    superblock_criteria,
+                )
+                .await
+                .map_err(|err| match err {
+                    KickOff2Error::NoSuperblockFound(err) => {
+                        GuardedActionError::NoSuperblockFound(err)
+                    }
+                    KickOff2Error::WrongCommitmentSecret(err) => {
+                        GuardedActionError::WrongCommitmentSecret(err)
+                    }
+                    KickOff2Error::Action(err) => GuardedActionError::Action(err),
+                })?,
+            OperatorAction::AssertInitial { blockchain_height } => self
+                .assert_initial(client, blockchain_height)
+                .await
+                .map_err(GuardedActionError::Action)?,
+            OperatorAction::AssertFinal => self
+                .assert_final(client)
+                .await
+                .map_err(GuardedActionError::Action)?,
+            OperatorAction::Take1 { blockchain_height } => self
+                .take_1(client, blockchain_height)
+                .await
+                .map_err(GuardedActionError::Action)?,
+            OperatorAction::Take2 { blockchain_height } => self
+                .take_2(client, blockchain_height, context)
+                .await
+                .map_err(|err| match err {
+                    Take2Error::SigningFailed(err) => GuardedActionError::Take2SigningFailed(err),
+                    Take2Error::Action(err) => GuardedActionError::Action(err),
+                })?,
+        }
+
+        Ok(())
+    }
+
+    // The challenger's counterpart of `guarded_action`, see `challenge`.
+    pub async fn guarded_challenge(
+        &mut self,
+        client: &AsyncClient,
+        crowdfundng_inputs: &Vec<InputWithScript<'_>>,
+        keypair: &Keypair,
+        output_script_pubkey: Option<ScriptBuf>,
+        signal_rbf: bool,
+    ) -> Result<(), GuardedActionError> {
+        self.ensure_validated()?;
+        self.check_action(TxRole::Challenge)
+            .map_err(|err| GuardedActionError::Action(ActionError::InProgress(err)))?;
+
+        self.challenge(
+            client,
+            crowdfundng_inputs,
+            keypair,
+            output_script_pubkey,
+            signal_rbf,
+        )
+        .await
+        .map_err(GuardedActionError::Challenge)
+    }
+
+    pub fn secrets_for<'a>(
+        &self,
+        action: ActionKind,
+        secrets: &'a HashMap<CommitmentMessageId, WinternitzSecret>,
+    ) -> Result<ActionSecrets<'a>, MissingSecret> {
+        let get = |id: CommitmentMessageId| secrets.get(&id).ok_or(MissingSecret(id));
+
+        match action {
+            ActionKind::KickOff1 => Ok(ActionSecrets::KickOff1 {
+                source_network_txid_commitment_secret: get(
+                    CommitmentMessageId::PegOutTxIdSourceNetwork,
+                )?,
+                destination_network_txid_commitment_secret: get(
+                    CommitmentMessageId::PegOutTxIdDestinationNetwork,
+                )?,
+            }),
+            ActionKind::StartTime => Ok(ActionSecrets::StartTime {
+                start_time_commitment_secret: get(CommitmentMessageId::StartTime)?,
+            }),
+            ActionKind::KickOff2 => Ok(ActionSecrets::KickOff2 {
+                superblock_commitment_secret: get(CommitmentMessageId::Superblock)?,
+                superblock_hash_commitment_secret: get(CommitmentMessageId::SuperblockHash)?,
+            }),
+            ActionKind::PegOutConfirm
+            | ActionKind::AssertInitial
+            | ActionKind::AssertFinal
+            | ActionKind::Take1
+            | ActionKind::Take2 => Ok(ActionSecrets::None),
+        }
+    }
+
+    // Checks the secrets against the connector an action signs for, so a secret
+    // of another graph is rejected before anything is fetched or signed.
+    pub fn verify_action_secrets(
+        &self,
+        secrets: ActionSecrets,
+    ) -> Result<(), WrongCommitmentSecret> {
+        match secrets {
+            ActionSecrets::None => Ok(()),
+            ActionSecrets::KickOff1 {
+                source_network_txid_commitment_secret,
+                destination_network_txid_commitment_secret,
+            } => {
+                verify_commitment_secret(
+                    &self.connector_6.commitment_public_keys,
+                    CommitmentMessageId::PegOutTxIdSourceNetwork,
+                    source_network_txid_commitment_secret,
+                )?;
+                verify_commitment_secret(
+                    &self.connector_6.commitment_public_keys,
+                    CommitmentMessageId::PegOutTxIdDestinationNetwork,
+                    destination_network_txid_commitment_secret,
+                )
+            }
+            ActionSecrets::StartTime {
+                start_time_commitment_secret,
+            } => verify_commitment_secret(
+                &self.connector_2.commitment_public_keys,
+                CommitmentMessageId::StartTime,
+                start_time_commitment_secret,
+            ),
+            ActionSecrets::KickOff2 {
+                superblock_commitment_secret,
+                superblock_hash_commitment_secret,
+            } => {
+                verify_commitment_secret(
+                    &self.connector_1.commitment_public_keys,
+                    CommitmentMessageId::Superblock,
+                    superblock_commitment_secret,
+                )?;
+                verify_commitment_secret(
+                    &self.connector_1.commitment_public_keys,
+                    CommitmentMessageId::SuperblockHash,
+                    superblock_hash_commitment_secret,
+                )
+            }
+        }
+    }
+
+    pub fn set_reward_address(&mut self, address: Address<NetworkUnchecked>) -> Result<(), String> {
+        match address.require_network(self.network) {
+            Ok(address) => {
+                self.reward_script_pubkey = Some(address.script_pubkey());
+                Ok(())
+            }
+            Err(err) => Err(format!(
+                "Reward address is not valid for network {}: {}",
+                self.network, err
+            )),
+        }
+    }
+
+    pub fn reward_script_pubkey(&self) -> Option<&ScriptBuf> { self.reward_script_pubkey.as_ref() }
+
+    pub fn set_external_ref(&mut self, external_ref: Option<String>) {
+        self.external_ref = external_ref;
+    }
+
+    pub fn external_ref(&self) -> Option<&str> { self.external_ref.as_deref() }
+
+    pub fn config(&self) -> &PegOutGraphConfig { &self.config }
+
+    pub fn verifying_key(&self) -> &VerifyingKey<Bn254> { &self.config.verifying_key }
+
+    pub fn resolve_reward_script_pubkey(
+        &self,
+        output_script_pubkey: Option<ScriptBuf>,
+    ) -> Result<ScriptBuf, MissingRewardScript> {
+        output_script_pubkey
+            .or_else(|| self.reward_script_pubkey.clone())
+            .ok_or(MissingRewardScript)
+    }
+
+    pub fn timelocks(&self) -> Vec<TimelockInfo> {
+        vec![
+            TimelockInfo {
+                tx_name: "kick_off_2",
+                connector_name: "connector_1",
+                leaf_index: Some(0),
+                num_blocks: self.connector_1.num_blocks_timelock_leaf_0,
+            },
+            TimelockInfo {
+                tx_name: "kick_off_timeout",
+                connector_name: "connector_1",
+                leaf_index: Some(1),
+                num_blocks: self.connector_1.num_blocks_timelock_leaf_1,
+            },
+            TimelockInfo {
+                tx_name: "start_time_timeout",
+                connector_name: "connector_1",
+                leaf_index: Some(2),
+                num_blocks: self.connector_1.num_blocks_timelock_leaf_2,
+            },
+            TimelockInfo {
+                tx_name: "take_1",
+                connector_name: "connector_3",
+                leaf_index: None,
+                num_blocks: self.connector_3.num_blocks_timelock,
+            },
+            TimelockInfo {
+                tx_name: "take_2",
+                connector_name: "connector_4",
+                leaf_index: None,
+                num_blocks: self.connector_4.num_blocks_timelock,
+            },
+            TimelockInfo {
+                tx_name: "assert_initial",
+                connector_name: "connector_b",
+                leaf_index: Some(1),
+                num_blocks: self.connector_b.num_blocks_timelock_1,
+            },
+        ]
+    }
+
+    // Maps every committed Groth16 intermediate value to the E connector set and
+    // the index of the connector (i.e. the assert-commit input) whose leaf 0 commits it.
+    pub fn commitment_assignment(&self) -> BTreeMap<String, (ConnectorRef, usize)> {
+        let e1 = self
+            .connector_e_1
+            .connectors_e
+            .iter()
+            .enumerate()
+            .map(|(index, connector)| (ConnectorRef::E1, index, connector));
+        let e2 = self
+            .connector_e_2
+            .connectors_e
+            .iter()
+            .enumerate()
+            .map(|(index, connector)| (ConnectorRef::E2, index, connector));
+
+        e1.chain(e2)
+            .flat_map(|(connector_ref, index, connector)| {
+                connector
+                    .commitment_public_keys
+                    .keys()
+                    .filter_map(move |message_id| match message_id {
+                        CommitmentMessageId::Groth16IntermediateValues((name, _)) => {
+                            Some((name.clone(), (connector_ref, index)))
+                        }
+                        _ => None,
+                    })
+            })
+            .collect()
+    }
+
+    // Output descriptors of the taproot connectors, for importing the graph into a
+    // watch-only wallet. E connectors are keyed by set and assert-commit input index.
+    pub fn descriptors(&self) -> BTreeMap<String, String> {
+        let connectors: [(&str, &dyn TaprootConnector); 9] = [
+            ("connector_0", &self.connector_0),
+            ("connector_1", &self.connector_1),
+            ("connector_2", &self.connector_2),
+            ("connector_5", &self.connector_5),
+            ("connector_6", &self.connector_6),
+            ("connector_a", &self.connector_a),
+            ("connector_b", &self.connector_b),
+            ("connector_c", &self.connector_c),
+            ("connector_d", &self.connector_d),
+        ];
+        let e1 = self
+            .connector_e_1
+            .connectors_e
+            .iter()
+            .enumerate()
+            .map(|(index, connector)| (format!("connector_e_1_{index}"), connector.descriptor()));
+        let e2 = self
+            .connector_e_2
+            .connectors_e
+            .iter()
+            .enumerate()
+            .map(|(index, connector)| (format!("connector_e_2_{index}"), connector.descriptor()));
+
+        connectors
+            .into_iter()
+            .map(|(name, connector)| (name.to_string(), connector.descriptor()))
+            .chain(e1)
+            .chain(e2)
+            .collect()
+    }
+
+    // Every connector that carries Winternitz public keys, i.e. the ones that have
+    // to be pushed to the remote data store. Named like in `descriptors`.
+    pub fn commitment_connectors(
+        &self,
+    ) -> Vec<(String, BTreeMap<&CommitmentMessageId, &WinternitzPublicKey>)> {
+        let connectors = [
+            ("connector_1", &self.connector_1.commitment_public_keys),
+            ("connector_2", &self.connector_2.commitment_public_keys),
+            ("connector_6", &self.connector_6.commitment_public_keys),
+        ]
+        .map(|(name, public_keys)| (name.to_string(), public_keys.iter().collect()));
+        let e1 = self
+            .connector_e_1
+            .connectors_e
+            .iter()
+            .enumerate()
+            .map(|(index, connector)| {
+                (
+                    format!("connector_e_1_{index}"),
+                    connector.commitment_public_keys.iter().collect(),
+                )
+            });
+        let e2 = self
+            .connector_e_2
+            .connectors_e
+            .iter()
+            .enumerate()
+            .map(|(index, connector)| {
+                (
+                    format!("connector_e_2_{index}"),
+                    connector.commitment_public_keys.iter().collect(),
+                )
+            });
+
+        connectors.into_iter().chain(e1).chain(e2).collect()
+    }
+
+    pub fn all_winternitz_public_keys(&self) -> HashMap<CommitmentMessageId, WinternitzPublicKey> {
+        self.commitment_connectors()
+            .into_iter()
+            .flat_map(|(_, public_keys)| public_keys)
+            .map(|(message_id, public_key)| (message_id.clone(), public_key.clone()))
+            .collect()
+    }
+
+    pub fn superblock_policy(&self) -> SuperblockPolicy { self.connector_1.superblock_policy }
+
+    pub fn superblock_params(&self) -> SuperblockParams {
+        SuperblockParams::for_network(self.network)
+    }
+
+    // Checks the superblock set committed in kick-off 2 against a heavier set
+    // found by a verifier, as evaluated for disprove chain.
+    pub fn is_superblock_commitment_disproved(
+        &self,
+        committed_superblocks: &[Header],
+        disprove_superblocks: &[Header],
+    ) -> bool {
+        is_superblock_claim_disproved(
+            self.connector_1.superblock_policy,
+            committed_superblocks,
+            disprove_superblocks,
+        )
+    }
+
+    // Amount the crowdfunding inputs of the challenge tx must add up to.
+    pub fn required_challenge_funding(&self) -> Amount {
+        self.challenge_transaction.required_challenge_funding()
+    }
+
+    // Amount the operator is reimbursed with by the given take transaction,
+    // i.e. the outputs paying to the operator after the take fee.
+    pub fn take_payout(&self, which: TakeKind) -> Amount {
+        let take_tx = match which {
+            TakeKind::Take1 => self.take_1_transaction.tx(),
+            TakeKind::Take2 => self.take_2_transaction.tx(),
+        };
+        let operator_script_pubkey =
+            generate_pay_to_pubkey_script_address(self.network, &self.operator_public_key)
+                .script_pubkey();
+
+        take_tx
+            .output
+            .iter()
+            .filter(|output| output.script_pubkey == operator_script_pubkey)
+            .map(|output| output.value)
+            .sum()
+    }
+
+    // Redirects the operator payout of a take tx to another address the operator
+    // controls, e.g. after the operator key has been compromised. The payout can
+    // only be changed if none of the n-of-n signatures presigned for the take tx
+    // commit to it. Operator inputs of the returned tx have to be signed again.
+    pub fn take_payout_override(
+        &self,
+        which: TakeKind,
+        new_operator_address: &Address,
+    ) -> Result<Transaction, PayoutOverrideError> {
+        let (tx_name, take_tx): (&'static str, &dyn PreSignedMusig2Transaction) = match which {
+            TakeKind::Take1 => ("take_1", &self.take_1_transaction),
+            TakeKind::Take2 => ("take_2", &self.take_2_transaction),
+        };
+        let operator_script_pubkey =
+            generate_pay_to_pubkey_script_address(self.network, &self.operator_public_key)
+                .script_pubkey();
+
+        override_payout(
+            tx_name,
+            take_tx,
+            &operator_script_pubkey,
+            new_operator_address.script_pubkey(),
+        )
+    }
+
+    // Rebuilds the PSBT of the funding tx the graph was created with, e.g. to sign
+    // it once the graph is presigned. The funding tx has to be built first with
+    // `generate_funding_psbt`, its leading outputs being the peg-out confirm inputs
+    // in order. `utxos` and `change` have to be the ones it was built from, else
+    // the PSBT pays outpoints the graph does not spend and an error is returned.
+    pub fn funding_psbt(&self, utxos: &[WalletUtxo], change: Address) -> Result<Psbt, String> {
+        let peg_out_confirm_tx = self.peg_out_confirm_transaction.tx();
+        let funding_txid = peg_out_confirm_tx.input[0].previous_output.txid;
+        for (input_index, tx_in) in peg_out_confirm_tx.input.iter().enumerate() {
+            if tx_in.previous_output != OutPoint::new(funding_txid, input_index as u32) {
+                return Err(format!(
+                    "Peg-out confirm input {input_index} is not output {input_index} of the funding tx"
+                ));
+            }
+        }
+
+        let psbt = generate_funding_psbt(
+            self.peg_out_confirm_transaction.prev_outs().clone(),
+            utxos,
+            change,
+        )?;
+        let psbt_txid = psbt.unsigned_tx.compute_txid();
+        if psbt_txid != funding_txid {
+            return Err(format!(
+                "Funding tx {psbt_txid} is not the graph's funding tx {funding_txid}"
+            ));
+        }
+
+        Ok(psbt)
+    }
+
+    pub fn n_of_n_taproot_public_key(&self) -> &XOnlyPublicKey { &self.n_of_n_taproot_public_key }
+
+    // Checked in the order of `commitment_connectors`, the first missing or wrong
+    // secret is returned.
+    pub fn verify_commitment_secrets(
+        &self,
+        secrets: &HashMap<CommitmentMessageId, WinternitzSecret>,
+    ) -> Result<(), WrongCommitmentSecret> {
+        for (_, public_keys) in self.commitment_connectors() {
+            for (message_id, public_key) in public_keys {
+                let secret = secrets
+                    .get(message_id)
+                    .ok_or_else(|| WrongCommitmentSecret {
+                        message_id: message_id.clone(),
+                    })?;
+                verify_commitment_public_key(public_key, message_id.clone(), secret)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Connectors are built from the x-only keys while presigning uses the full
+    // keys, so both must describe the same key. The partial signatures are checked
+    // against the individual keys, which must aggregate to the n-of-n key.
+    pub fn validate_key_consistency(&self) -> Result<(), KeyError> {
+        if self.n_of_n_taproot_public_key != XOnlyPublicKey::from(self.n_of_n_public_key) {
+            return Err(KeyError::NofNTaprootKeyMismatch);
+        }
+        if self.n_of_n_public_keys.is_empty() {
+            return Err(KeyError::MissingNofNPublicKeys);
+        }
+        if generate_n_of_n_public_key(&self.n_of_n_public_keys).0 != self.n_of_n_public_key {
+            return Err(KeyError::NofNPublicKeysMismatch);
+        }
+        if self.operator_taproot_public_key != XOnlyPublicKey::from(self.operator_public_key) {
+            return Err(KeyError::OperatorTaprootKeyMismatch);
+        }
+
+        Ok(())
+    }
+
+    // The start time commitment follows the operator's schnorr signature in the
+    // start time witness.
+    pub fn get_committed_start_time(&self, start_time_tx: &Transaction) -> Option<u32> {
+        let witness = start_time_tx.input.first()?.witness.to_vec();
+        let message =
+            winternitz_message_from_witness(witness.get(1..)?, START_TIME_MESSAGE_LENGTH)?;
+
+        Some(u32::from_le_bytes(message.try_into().ok()?))
+    }
+
+    // The superblock commitment follows the operator's schnorr signature in the
+    // kick-off 2 witness, ahead of the superblock hash commitment.
+    pub fn get_committed_superblocks(&self, kick_off_2_tx: &Transaction) -> Option<Vec<Header>> {
+        let witness = kick_off_2_tx.input.first()?.witness.to_vec();
+        let message = winternitz_message_from_witness(
+            witness.get(1..)?,
+            self.connector_1.superblock_policy.message_length(),
+        )?;
+
+        get_superblocks_from_message(&message)
+    }
+
+    // The source network txid commitment follows the operator's schnorr signature in
+    // the kick-off 1 witness. The message is sized for SOURCE_NETWORK_TXID_LENGTH
+    // bytes, the txid itself fills the first 32 of them.
+    pub fn get_committed_source_network_txid(&self, kick_off_1_tx: &Transaction) -> Option<Txid> {
+        let witness = kick_off_1_tx.input.first()?.witness.to_vec();
+        let message =
+            winternitz_message_from_witness(witness.get(1..)?, SOURCE_NETWORK_TXID_LENGTH)?;
+
+        Some(Txid::from_byte_array(
+            message.get(..Txid::LEN)?.try_into().ok()?,
+        ))
+    }
+
+    // Kick-off 1 is only honest if it commits to the peg-out tx that actually paid
+    // out the withdrawer on the source network.
+    pub async fn verify_committed_source_txid(&self, client: &AsyncClient) -> Result<bool, String> {
+        let kick_off_1_tx = client
+            .get_tx(&self.txid(TxRole::KickOff1))
+            .await
+            .map_err(|err| format!("Failed to get kick-off 1 tx: {err:?}"))?
+            .ok_or("Kick-off 1 tx has not been broadcast")?;
+        let peg_out_txid = self
+            .peg_out_txid()
+            .ok_or("Peg-out tx has not been created")?;
+        let peg_out_status = client.get_tx_status(&peg_out_txid).await;
+
+        self.interpret_committed_source_txid(&kick_off_1_tx, &peg_out_status)
+    }
+
+    pub fn interpret_committed_source_txid(
+        &self,
+        kick_off_1_tx: &Transaction,
+        peg_out_status: &Result<TxStatus, Error>,
+    ) -> Result<bool, String> {
+        let committed_txid = self
+            .get_committed_source_network_txid(kick_off_1_tx)
+            .ok_or("Failed to decode committed source network txid")?;
+        let peg_out_txid = self
+            .peg_out_txid()
+            .ok_or("Peg-out tx has not been created")?;
+        if committed_txid != peg_out_txid {
+            return Ok(false);
+        }
+
+        peg_out_status
+            .as_ref()
+            .map(|status| status.confirmed)
+            .map_err(|err| format!("Failed to get peg-out tx status: {err:?}"))
+    }
+
+    // The operator could broadcast a different tx spending the same funding output,
+    // so look the tx up through the funding output rather than by the graph's txid.
+    // Nothing is checked while the funding output is still unspent.
+    pub async fn verify_peg_out_confirm_onchain(
+        &self,
+        client: &AsyncClient,
+    ) -> Result<(), PegOutConfirmError> {
+        // every funding input may have been spent, and each spender has to be
+        // the peg-out confirm tx of this graph
+        let mut spending_txids: Vec<Txid> = Vec::new();
+        for tx_in in &self.peg_out_confirm_transaction.tx().input {
+            let funding_outpoint = tx_in.previous_output;
+            let spending_txid = client
+                .get_output_status(&funding_outpoint.txid, funding_outpoint.vout.into())
+                .await
+                .map_err(|err| {
+                    PegOutConfirmError::ClientError(format!(
+                        "Failed to get peg-out confirm funding output status: {err:?}"
+                    ))
+                })?
+                .and_then(|output_status| output_status.txid);
+            if let Some(txid) = spending_txid {
+                if !spending_txids.contains(&txid) {
+                    spending_txids.push(txid);
+                }
+            }
+        }
+
+        for txid in spending_txids {
+            let onchain_tx = client
+                .get_tx(&txid)
+                .await
+                .map_err(|err| {
+                    PegOutConfirmError::ClientError(format!(
+                        "Failed to get peg-out confirm tx: {err:?}"
+                    ))
+                })?
+                .ok_or(PegOutConfirmError::ClientError(
+                    "Peg-out confirm tx not found".to_string(),
+                ))?;
+            self.compare_peg_out_confirm_inputs(&onchain_tx)?;
+            self.compare_peg_out_confirm_outputs(&onchain_tx)?;
         }
+
+        Ok(())
     }
 
-    pub async fn verifier_status(&self, client: &AsyncClient) -> PegOutVerifierStatus {
-        if self.n_of_n_presigned {
-            let (
-                assert_initial_status,
-                assert_final_status,
-                challenge_status,
-                disprove_chain_status,
-                disprove_status,
-                _,
-                kick_off_1_status,
-                kick_off_2_status,
-                kick_off_timeout_status,
-                _,
-                start_time_timeout_status,
-                start_time_status,
-                take_1_status,
-                take_2_status,
-            ) = Self::get_peg_out_statuses(self, client).await;
-            let blockchain_height = get_block_height(client).await;
+    pub fn compare_peg_out_confirm_inputs(
+        &self,
+        onchain_tx: &Transaction,
+    ) -> Result<(), PegOutConfirmError> {
+        match self
+            .peg_out_confirm_transaction
+            .tx()
+            .input
+            .iter()
+            .position(|expected| {
+                !onchain_tx
+                    .input
+                    .iter()
+                    .any(|onchain| onchain.previous_output == expected.previous_output)
+            }) {
+            Some(input_index) => Err(PegOutConfirmError::InputMismatch(input_index)),
+            None => Ok(()),
+        }
+    }
 
-            if kick_off_2_status
-                .as_ref()
-                .is_ok_and(|status| status.confirmed)
-            {
-                if take_1_status.as_ref().is_ok_and(|status| status.confirmed)
-                    || take_2_status.as_ref().is_ok_and(|status| status.confirmed)
-                {
-                    PegOutVerifierStatus::PegOutComplete
-                } else if disprove_status
-                    .as_ref()
-                    .is_ok_and(|status| status.confirmed)
-                    || disprove_chain_status
-                        .as_ref()
-                        .is_ok_and(|status| status.confirmed)
-                {
-                    return PegOutVerifierStatus::PegOutFailed; // TODO: can be also `PegOutVerifierStatus::PegOutComplete`
-                } else if assert_final_status
-                    .as_ref()
-                    .is_ok_and(|status| status.confirmed)
-                {
-                    return PegOutVerifierStatus::PegOutDisproveAvailable;
-                } else {
-                    return PegOutVerifierStatus::PegOutDisproveChainAvailable;
-                }
-            } else if kick_off_1_status
-                .as_ref()
-                .is_ok_and(|status| status.confirmed)
-            {
-                if start_time_timeout_status
-                    .as_ref()
-                    .is_ok_and(|status| status.confirmed)
-                    || kick_off_timeout_status
-                        .as_ref()
-                        .is_ok_and(|status| status.confirmed)
-                {
-                    return PegOutVerifierStatus::PegOutFailed; // TODO: can be also `PegOutVerifierStatus::PegOutComplete`
-                } else if start_time_status
-                    .as_ref()
-                    .is_ok_and(|status| !status.confirmed)
-                {
-                    if kick_off_1_status
-                        .as_ref()
-                        .unwrap()
-                        .block_height
-                        .is_some_and(|block_height| {
-                            block_height + self.connector_1.num_blocks_timelock_leaf_2
-                                > blockchain_height
-                        })
-                    {
-                        return PegOutVerifierStatus::PegOutStartTimeTimeoutAvailable;
-                    } else {
-                        return PegOutVerifierStatus::PegOutWait;
-                    }
-                } else if kick_off_1_status
-                    .as_ref()
-                    .unwrap()
-                    .block_height
-                    .is_some_and(|block_height| {
-                        block_height + self.connector_1.num_blocks_timelock_leaf_1
-                            > blockchain_height
-                    })
-                {
-                    return PegOutVerifierStatus::PegOutKickOffTimeoutAvailable;
-                } else if challenge_status
-                    .as_ref()
-                    .is_ok_and(|status| !status.confirmed)
-                {
-                    return PegOutVerifierStatus::PegOutChallengeAvailable;
-                } else {
-                    return PegOutVerifierStatus::PegOutWait;
-                }
-            } else {
-                return PegOutVerifierStatus::PegOutWait;
-            }
-        } else {
-            PegOutVerifierStatus::PegOutPresign
+    pub fn compare_peg_out_confirm_outputs(
+        &self,
+        onchain_tx: &Transaction,
+    ) -> Result<(), PegOutConfirmError> {
+        let expected_outputs = &self.peg_out_confirm_transaction.tx().output;
+        if onchain_tx.output.len() != expected_outputs.len() {
+            return Err(PegOutConfirmError::OutputCountMismatch {
+                expected: expected_outputs.len(),
+                onchain: onchain_tx.output.len(),
+            });
+        }
+
+        match expected_outputs
+            .iter()
+            .zip(&onchain_tx.output)
+            .position(|(expected, onchain)| expected != onchain)
+        {
+            Some(vout) => Err(PegOutConfirmError::OutputMismatch(vout)),
+            None => Ok(()),
         }
     }
 
-    pub async fn operator_status(&self, client: &AsyncClient) -> PegOutOperatorStatus {
-        if self.n_of_n_presigned && self.is_peg_out_initiated() {
-            let (
-                assert_initial_status,
-                assert_final_status,
-                challenge_status,
-                disprove_chain_status,
-                disprove_status,
-                peg_out_confirm_status,
-                kick_off_1_status,
-                kick_off_2_status,
-                kick_off_timeout_status,
-                peg_out_status,
-                start_time_timeout_status,
-                start_time_status,
-                take_1_status,
-                take_2_status,
-            ) = Self::get_peg_out_statuses(self, client).await;
-            let blockchain_height = get_block_height(client).await;
+    // The start time tx sets its lock time when signed, so look it up as the
+    // spender of its kick-off 1 output instead of by the presigned txid.
+    async fn get_broadcast_start_time_tx(
+        &self,
+        client: &AsyncClient,
+    ) -> Result<Transaction, String> {
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let start_time_vout = self.start_time_transaction.tx().input[0]
+            .previous_output
+            .vout;
+        let start_time_txid = client
+            .get_output_status(&kick_off_1_txid, start_time_vout.into())
+            .await
+            .map_err(|err| format!("Failed to get kick-off 1 output status: {err:?}"))?
+            .and_then(|output_status| output_status.txid)
+            .ok_or("Start time tx has not been broadcast")?;
+        client
+            .get_tx(&start_time_txid)
+            .await
+            .map_err(|err| format!("Failed to get start time tx: {err:?}"))?
+            .ok_or("Start time tx not found".to_string())
+    }
 
-            if peg_out_status.is_some_and(|status| status.unwrap().confirmed) {
-                if kick_off_2_status
-                    .as_ref()
-                    .is_ok_and(|status| status.confirmed)
-                {
-                    if take_1_status.as_ref().is_ok_and(|status| status.confirmed)
-                        || take_2_status.as_ref().is_ok_and(|status| status.confirmed)
-                    {
-                        return PegOutOperatorStatus::PegOutComplete;
-                    } else if disprove_chain_status
-                        .as_ref()
-                        .is_ok_and(|status| status.confirmed)
-                        || disprove_status
-                            .as_ref()
-                            .is_ok_and(|status| status.confirmed)
-                    {
-                        return PegOutOperatorStatus::PegOutFailed; // TODO: can be also `PegOutOperatorStatus::PegOutComplete`
-                    } else if challenge_status.is_ok_and(|status| status.confirmed) {
-                        if assert_final_status
-                            .as_ref()
-                            .is_ok_and(|status| status.confirmed)
-                        {
-                            if assert_final_status
-                                .as_ref()
-                                .unwrap()
-                                .block_height
-                                .is_some_and(|block_height| {
-                                    block_height + self.connector_4.num_blocks_timelock
-                                        <= blockchain_height
-                                })
-                            {
-                                return PegOutOperatorStatus::PegOutTake2Available;
-                            } else {
-                                return PegOutOperatorStatus::PegOutWait;
-                            }
-                        } else if kick_off_2_status
-                            .as_ref()
-                            .unwrap()
-                            .block_height
-                            .is_some_and(|block_height| {
-                                block_height + self.connector_b.num_blocks_timelock_1
-                                    <= blockchain_height
-                            })
-                        {
-                            return PegOutOperatorStatus::PegOutAssertAvailable;
-                        } else {
-                            return PegOutOperatorStatus::PegOutWait;
-                        }
-                    } else if kick_off_2_status
-                        .as_ref()
-                        .unwrap()
-                        .block_height
-                        .is_some_and(|block_height| {
-                            block_height + self.connector_3.num_blocks_timelock <= blockchain_height
-                        })
-                    {
-                        return PegOutOperatorStatus::PegOutTake1Available;
-                    } else {
-                        return PegOutOperatorStatus::PegOutWait;
-                    }
-                } else if kick_off_1_status
-                    .as_ref()
-                    .is_ok_and(|status| status.confirmed)
-                {
-                    if start_time_timeout_status
-                        .as_ref()
-                        .is_ok_and(|status| status.confirmed)
-                        || kick_off_timeout_status
-                            .as_ref()
-                            .is_ok_and(|status| status.confirmed)
-                    {
-                        return PegOutOperatorStatus::PegOutFailed; // TODO: can be also `PegOutOperatorStatus::PegOutComplete`
-                    } else if start_time_status
-                        .as_ref()
-                        .is_ok_and(|status| status.confirmed)
-                    {
-                        if kick_off_1_status
-                            .as_ref()
-                            .unwrap()
-                            .block_height
-                            .is_some_and(|block_height| {
-                                block_height + self.connector_1.num_blocks_timelock_leaf_0
-                                    <= blockchain_height
-                            })
-                        {
-                            return PegOutOperatorStatus::PegOutKickOff2Available;
-                        } else {
-                            return PegOutOperatorStatus::PegOutWait;
-                        }
-                    } else {
-                        return PegOutOperatorStatus::PegOutStartTimeAvailable;
+    pub async fn verify_superblock_window(&self, client: &AsyncClient) -> Result<bool, String> {
+        let start_time_tx = self.get_broadcast_start_time_tx(client).await?;
+        let start_time = self
+            .get_committed_start_time(&start_time_tx)
+            .ok_or("Failed to decode committed start time")?;
+
+        let kick_off_2_txid = self.txid(TxRole::KickOff2);
+        let kick_off_2_tx = client
+            .get_tx(&kick_off_2_txid)
+            .await
+            .map_err(|err| format!("Failed to get kick-off 2 tx: {err:?}"))?
+            .ok_or("Kick-off 2 tx has not been broadcast")?;
+        let superblocks = self
+            .get_committed_superblocks(&kick_off_2_tx)
+            .ok_or("Failed to decode committed superblock")?;
+
+        let superblock_params = self.superblock_params();
+        for superblock in superblocks {
+            let block_status = client
+                .get_block_status(&superblock.block_hash())
+                .await
+                .map_err(|err| format!("Failed to get superblock status: {err:?}"))?;
+            match block_status.height {
+                Some(height) if block_status.in_best_chain => {
+                    if !is_superblock_in_window(&superblock_params, start_time, height) {
+                        return Ok(false);
                     }
-                } else if peg_out_confirm_status
-                    .as_ref()
-                    .is_ok_and(|status| status.confirmed)
-                {
-                    return PegOutOperatorStatus::PegOutKickOff1Available;
-                } else {
-                    return PegOutOperatorStatus::PegOutPegOutConfirmAvailable;
                 }
-            } else {
-                return PegOutOperatorStatus::PegOutStartPegOut;
+                // a committed superblock that is not on the main chain cannot be in the window
+                _ => return Ok(false),
             }
         }
 
-        PegOutOperatorStatus::PegOutWait
+        Ok(true)
     }
 
-    pub fn interpret_withdrawer_status(
+    // Once kick-off 1 is confirmed, a verifier decides whether to challenge by
+    // checking everything the operator committed to so far. Failing to gather the
+    // evidence is not a reason to stay quiet, the error is left to the caller.
+    pub async fn should_challenge(
         &self,
-        peg_out_status: Option<&Result<TxStatus, Error>>,
-    ) -> PegOutWithdrawerStatus {
-        if let Some(peg_out_status) = peg_out_status {
-            if peg_out_status.as_ref().is_ok_and(|status| status.confirmed) {
-                PegOutWithdrawerStatus::PegOutComplete
-            } else {
-                PegOutWithdrawerStatus::PegOutWait
-            }
-        } else {
-            PegOutWithdrawerStatus::PegOutNotStarted
+        client: &AsyncClient,
+    ) -> Result<ChallengeRecommendation, String> {
+        // the challenge and take 1 txs both spend connector A, once it is spent
+        // there is nothing left to decide
+        let connector_a_outpoint = self.challenge_transaction.tx().input[0].previous_output;
+        let is_connector_a_spent = client
+            .get_output_status(&connector_a_outpoint.txid, connector_a_outpoint.vout.into())
+            .await
+            .map_err(|err| format!("Failed to get connector A output status: {err:?}"))?
+            .is_some_and(|status| status.spent);
+        if is_connector_a_spent {
+            return Ok(ChallengeRecommendation::NoAction);
         }
+
+        let evidence = self.get_challenge_evidence(client).await?;
+
+        Ok(self.interpret_challenge_evidence(&evidence))
     }
 
-    pub async fn withdrawer_status(&self, client: &AsyncClient) -> PegOutWithdrawerStatus {
-        let peg_out_status = match self.peg_out_transaction {
-            Some(_) => {
-                let peg_out_txid = self
-                    .peg_out_transaction
-                    .as_ref()
-                    .unwrap()
-                    .tx()
-                    .compute_txid();
-                let peg_out_status = client.get_tx_status(&peg_out_txid).await;
-                Some(peg_out_status)
-            }
-            None => None,
-        };
-        self.interpret_withdrawer_status(peg_out_status.as_ref())
+    pub async fn get_challenge_evidence(
+        &self,
+        client: &AsyncClient,
+    ) -> Result<ChallengeEvidence, String> {
+        let kick_off_1_txid = self.txid(TxRole::KickOff1);
+        let kick_off_1_height = client
+            .get_tx_status(&kick_off_1_txid)
+            .await
+            .map_err(|err| format!("Failed to get kick-off 1 tx status: {err:?}"))?
+            .block_height
+            .ok_or("Kick-off 1 tx has not been confirmed")?;
+        let kick_off_1_tx = client
+            .get_tx(&kick_off_1_txid)
+            .await
+            .map_err(|err| format!("Failed to get kick-off 1 tx: {err:?}"))?
+            .ok_or("Kick-off 1 tx not found")?;
+        let committed_source_txid = self
+            .get_committed_source_network_txid(&kick_off_1_tx)
+            .ok_or("Failed to decode committed source network txid")?;
+        let is_committed_source_tx_confirmed = client
+            .get_tx_status(&committed_source_txid)
+            .await
+            .map_err(|err| format!("Failed to get committed peg-out tx status: {err:?}"))?
+            .confirmed;
+
+        let (committed_start_time, start_time_height) =
+            match self.get_broadcast_start_time_tx(client).await {
+                Ok(start_time_tx) => {
+                    let start_time_height = client
+                        .get_tx_status(&start_time_tx.compute_txid())
+                        .await
+                        .map_err(|err| format!("Failed to get start time tx status: {err:?}"))?
+                        .block_height;
+                    (
+                        self.get_committed_start_time(&start_time_tx),
+                        start_time_height,
+                    )
+                }
+                Err(_) => (None, None),
+            };
+
+        Ok(ChallengeEvidence {
+            kick_off_1_height,
+            committed_source_txid,
+            is_committed_source_tx_confirmed,
+            committed_start_time,
+            start_time_height,
+            is_superblock_in_window: self.verify_superblock_window(client).await.ok(),
+        })
     }
 
-    pub async fn peg_out(&mut self, client: &AsyncClient, context: &OperatorContext, input: Input) {
-        if !self.is_peg_out_initiated() {
-            panic!("Peg out not initiated on L2 chain");
+    pub fn interpret_challenge_evidence(
+        &self,
+        evidence: &ChallengeEvidence,
+    ) -> ChallengeRecommendation {
+        let challenge = |reason| ChallengeRecommendation::Challenge { reason };
+
+        if let Some(peg_out_txid) = self.peg_out_txid() {
+            if evidence.committed_source_txid != peg_out_txid {
+                return challenge(ChallengeReason::SourceTxidMismatch {
+                    committed_txid: evidence.committed_source_txid,
+                    peg_out_txid,
+                });
+            }
+        }
+        // peg-out confirm, and so kick-off 1, can only follow a confirmed peg-out tx
+        if !evidence.is_committed_source_tx_confirmed {
+            return challenge(ChallengeReason::PegOutNotConfirmed(
+                evidence.committed_source_txid,
+            ));
+        }
+        if let (Some(start_time), Some(start_time_height)) =
+            (evidence.committed_start_time, evidence.start_time_height)
+        {
+            if start_time < evidence.kick_off_1_height || start_time > start_time_height {
+                return challenge(ChallengeReason::StartTimeOutOfRange {
+                    start_time,
+                    kick_off_1_height: evidence.kick_off_1_height,
+                    start_time_height,
+                });
+            }
+        }
+        if evidence.is_superblock_in_window == Some(false) {
+            return challenge(ChallengeReason::SuperblockOutsideWindow);
         }
 
-        if self.peg_out_transaction.is_some() {
-            let txid = self
-                .peg_out_transaction
-                .as_ref()
-                .unwrap()
-                .tx()
-                .compute_txid();
-            verify_if_not_mined(client, txid).await;
+        ChallengeRecommendation::NoAction
+    }
+
+    // JSON counterpart of `from_bincode`. Serde itself ignores the version, a graph
+    // of another version would deserialize into a subtly wrong graph.
+    pub fn deserialize_checked(data: &str) -> Result<Self, GraphVersionError> {
+        let value: Value = serde_json::from_str(data)
+            .map_err(|err| GraphVersionError::Malformed(err.to_string()))?;
+        let version = value
+            .get("version")
+            .and_then(Value::as_str)
+            .ok_or(GraphVersionError::Missing)?
+            .to_string();
+
+        let value = if version == GRAPH_VERSION {
+            value
+        } else if MIGRATABLE_GRAPH_VERSIONS.contains(&version.as_str()) {
+            Self::migrate_from(&version, value)
         } else {
-            let event = self.peg_out_chain_event.as_ref().unwrap();
-            let tx = PegOutTransaction::new(context, event, input);
-            self.peg_out_transaction = Some(tx);
-        }
+            return Err(GraphVersionError::Unsupported(version));
+        };
 
-        let peg_out_tx = self.peg_out_transaction.as_ref().unwrap().finalize();
+        serde_json::from_value(value).map_err(|err| GraphVersionError::Malformed(err.to_string()))
+    }
 
-        broadcast_and_verify(client, &peg_out_tx).await;
+    // Rewrites the JSON of a graph of `old_version` into that of `GRAPH_VERSION`.
+    // Every version in `MIGRATABLE_GRAPH_VERSIONS` needs an arm here.
+    pub fn migrate_from(old_version: &str, mut value: Value) -> Value {
+        match old_version {
+            GRAPH_VERSION => {}
+            _ => panic!("No migration from graph version {old_version}"),
+        }
+        value["version"] = Value::from(GRAPH_VERSION);
+
+        value
     }
 
-    pub async fn peg_out_confirm(&mut self, client: &AsyncClient) {
-        verify_if_not_mined(client, self.peg_out_confirm_transaction.tx().compute_txid()).await;
+    // Avoids the hex encoding of every tx, key and commitment that JSON needs.
+    // Use `serialization::serialize` for readable output.
+    pub fn to_bincode(&self) -> Vec<u8> { serialize_binary(GRAPH_VERSION, self) }
 
-        if self.peg_out_transaction.as_ref().is_some() {
-            let peg_out_txid = self
-                .peg_out_transaction
-                .as_ref()
-                .unwrap()
-                .tx()
-                .compute_txid();
-            let peg_out_status = client.get_tx_status(&peg_out_txid).await;
+    pub fn from_bincode(data: &[u8]) -> Result<Self, String> {
+        try_deserialize_binary(GRAPH_VERSION, data)
+    }
 
-            if peg_out_status.is_ok_and(|status| status.confirmed) {
-                // complete peg-out-confirm tx
-                let peg_out_confirm_tx = self.peg_out_confirm_transaction.finalize();
+    // The txs held by the graph. The peg-out tx is left out, it is set from
+    // outside of the graph.
+    pub fn txids(&self) -> &BTreeMap<TxRole, Txid> {
+        self.txids.roles.get_or_init(|| self.compute_txids())
+    }
 
-                // broadcast peg-out-confirm tx
-                broadcast_and_verify(client, &peg_out_confirm_tx).await;
-            } else {
-                panic!("Peg-out tx has not been confirmed!");
-            }
-        } else {
-            panic!("Peg-out tx has not been created!");
+    fn compute_txids(&self) -> BTreeMap<TxRole, Txid> {
+        self.all_role_txs()
+            .into_iter()
+            .map(|(role, tx)| (role, tx.tx().compute_txid()))
+            .collect()
+    }
+
+    // The assert commit txs are not held by the graph and are rebuilt unsigned.
+    fn txid(&self, role: TxRole) -> Txid {
+        match role {
+            TxRole::PegInConfirm => self.peg_in_confirm_txid,
+            TxRole::AssertCommit1 => self.assert_commit_txids().0,
+            TxRole::AssertCommit2 => self.assert_commit_txids().1,
+            _ => self.txids()[&role],
         }
     }
 
-    pub async fn kick_off_1(
-        &mut self,
-        client: &AsyncClient,
-        context: &OperatorContext,
-        source_network_txid_commitment_secret: &WinternitzSecret,
-        destination_network_txid_commitment_secret: &WinternitzSecret,
-    ) {
-        verify_if_not_mined(client, self.kick_off_1_transaction.tx().compute_txid()).await;
+    fn assert_commit_txids(&self) -> (Txid, Txid) {
+        *self.txids.assert_commits.get_or_init(|| {
+            let (assert_commit_1, assert_commit_2) = self.unsigned_assert_commit_transactions();
+            (
+                assert_commit_1.tx().compute_txid(),
+                assert_commit_2.tx().compute_txid(),
+            )
+        })
+    }
 
-        let peg_out_confirm_txid = self.peg_out_confirm_transaction.tx().compute_txid();
-        let peg_out_confirm_status = client.get_tx_status(&peg_out_confirm_txid).await;
+    pub fn peg_out_transaction(&self) -> Option<&PegOutTransaction> {
+        self.peg_out_transaction.as_ref()
+    }
 
-        if peg_out_confirm_status.is_ok_and(|status| status.confirmed) {
-            // complete kick-off 1 tx
-            let pegout_txid = self
-                .peg_out_transaction
+    pub fn peg_out_txid(&self) -> Option<Txid> {
+        *self.txids.peg_out.get_or_init(|| {
+            self.peg_out_transaction
                 .as_ref()
-                .unwrap()
-                .tx()
-                .compute_txid()
-                .as_byte_array()
-                .to_owned();
-            let source_network_txid_inputs = WinternitzSigningInputs {
-                message: &pegout_txid,
-                signing_key: source_network_txid_commitment_secret,
-            };
-            let destination_network_txid_inputs = WinternitzSigningInputs {
-                message: self
-                    .peg_out_chain_event
-                    .as_ref()
-                    .unwrap()
-                    .tx_hash
-                    .as_slice(),
-                signing_key: destination_network_txid_commitment_secret,
-            };
-            self.kick_off_1_transaction.sign(
-                context,
-                &self.connector_6,
-                &source_network_txid_inputs,
-                &destination_network_txid_inputs,
-            );
-            let kick_off_1_tx = self.kick_off_1_transaction.finalize();
+                .map(|peg_out_tx| peg_out_tx.tx().compute_txid())
+        })
+    }
 
-            // broadcast kick-off 1 tx
-            broadcast_and_verify(client, &kick_off_1_tx).await;
-        } else {
-            panic!("Peg-out-confirm tx has not been confirmed!");
-        }
+    // The graph has to be validated again with the new peg-out tx.
+    pub fn set_peg_out_transaction(&mut self, peg_out_transaction: PegOutTransaction) {
+        self.rebuild_tx(|graph| graph.peg_out_transaction = Some(peg_out_transaction));
+        self.is_validated.0 = false;
     }
 
-    pub async fn challenge(
-        &mut self,
-        client: &AsyncClient,
-        crowdfundng_inputs: &Vec<InputWithScript<'_>>,
-        keypair: &Keypair,
-        output_script_pubkey: ScriptBuf,
-    ) {
-        verify_if_not_mined(client, self.challenge_transaction.tx().compute_txid()).await;
+    // Graph txs changed after construction are changed through here, so that the
+    // cached txids are dropped together with the tx they were computed from.
+    fn rebuild_tx<T>(&mut self, rebuild: impl FnOnce(&mut Self) -> T) -> T {
+        let result = rebuild(self);
+        self.txids = TxidCache::default();
+        result
+    }
 
-        let kick_off_1_txid = self.kick_off_1_transaction.tx().compute_txid();
-        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+    fn all_role_txs(&self) -> Vec<(TxRole, &dyn PreSignedTransaction)> {
+        vec![
+            (TxRole::AssertInitial, &self.assert_initial_transaction),
+            (TxRole::AssertFinal, &self.assert_final_transaction),
+            (TxRole::Challenge, &self.challenge_transaction),
+            (TxRole::DisproveChain, &self.disprove_chain_transaction),
+            (TxRole::Disprove, &self.disprove_transaction),
+            (TxRole::PegOutConfirm, &self.peg_out_confirm_transaction),
+            (TxRole::KickOff1, &self.kick_off_1_transaction),
+            (TxRole::KickOff2, &self.kick_off_2_transaction),
+            (TxRole::KickOffTimeout, &self.kick_off_timeout_transaction),
+            (
+                TxRole::StartTimeTimeout,
+                &self.start_time_timeout_transaction,
+            ),
+            (TxRole::StartTime, &self.start_time_transaction),
+            (TxRole::Take1, &self.take_1_transaction),
+            (TxRole::Take2, &self.take_2_transaction),
+        ]
+    }
 
-        if kick_off_1_status.is_ok_and(|status| status.confirmed) {
-            // complete challenge tx
-            self.challenge_transaction.add_inputs_and_output(
-                crowdfundng_inputs,
-                keypair,
-                output_script_pubkey,
-            );
-            let challenge_tx = self.challenge_transaction.finalize();
+    fn all_named_txs(&self) -> Vec<(&'static str, &dyn PreSignedTransaction)> {
+        self.all_role_txs()
+            .into_iter()
+            .map(|(role, tx)| (role.tx_name(), tx))
+            .collect()
+    }
 
-            // broadcast challenge tx
-            broadcast_and_verify(client, &challenge_tx).await;
-        } else {
-            panic!("Kick-off 1 tx has not been confirmed!");
+    // Every graph tx by tx role. The assert commit txs are rebuilt unsigned, their
+    // txids do not depend on the witness.
+    fn role_transactions(&self) -> Vec<(TxRole, Transaction)> {
+        let (assert_commit_1, assert_commit_2) = self.unsigned_assert_commit_transactions();
+        let txs: Vec<(TxRole, &Transaction)> = vec![
+            (TxRole::PegOutConfirm, self.peg_out_confirm_transaction.tx()),
+            (TxRole::KickOff1, self.kick_off_1_transaction.tx()),
+            (TxRole::StartTime, self.start_time_transaction.tx()),
+            (
+                TxRole::StartTimeTimeout,
+                self.start_time_timeout_transaction.tx(),
+            ),
+            (TxRole::KickOff2, self.kick_off_2_transaction.tx()),
+            (
+                TxRole::KickOffTimeout,
+                self.kick_off_timeout_transaction.tx(),
+            ),
+            (TxRole::Challenge, self.challenge_transaction.tx()),
+            (TxRole::Take1, self.take_1_transaction.tx()),
+            (TxRole::AssertInitial, self.assert_initial_transaction.tx()),
+            (TxRole::AssertCommit1, assert_commit_1.tx()),
+            (TxRole::AssertCommit2, assert_commit_2.tx()),
+            (TxRole::AssertFinal, self.assert_final_transaction.tx()),
+            (TxRole::Take2, self.take_2_transaction.tx()),
+            (TxRole::Disprove, self.disprove_transaction.tx()),
+            (TxRole::DisproveChain, self.disprove_chain_transaction.tx()),
+        ];
+
+        txs.into_iter()
+            .map(|(role, tx)| (role, tx.clone()))
+            .collect()
+    }
+
+    // Which graph tx spends which output, read from the built txs.
+    pub fn transaction_dag(&self) -> TxDag {
+        let txs = self.role_transactions();
+
+        let nodes: BTreeMap<TxRole, Txid> = txs
+            .iter()
+            .map(|(role, _)| (*role, self.txid(*role)))
+            .chain([(TxRole::PegInConfirm, self.peg_in_confirm_txid)])
+            .collect();
+        let roles_by_txid: HashMap<Txid, TxRole> =
+            nodes.iter().map(|(role, txid)| (*txid, *role)).collect();
+
+        let mut edges = vec![];
+        for (role, tx) in &txs {
+            for (input_index, input) in tx.input.iter().enumerate() {
+                if let Some(from) = roles_by_txid.get(&input.previous_output.txid) {
+                    edges.push(TxEdge {
+                        from: *from,
+                        from_vout: input.previous_output.vout,
+                        to: *role,
+                        to_input: input_index.to_u32().unwrap(),
+                    });
+                }
+            }
         }
+
+        TxDag { nodes, edges }
     }
 
-    pub async fn start_time(
-        &mut self,
-        client: &AsyncClient,
-        context: &OperatorContext,
-        start_time_commitment_secret: &WinternitzSecret,
-    ) {
-        verify_if_not_mined(client, self.start_time_transaction.tx().compute_txid()).await;
+    // Every outpoint spent by a graph tx, tagged with the tx spending it, for a
+    // watch-only wallet to import. Conflicting txs spend the same outpoint, so an
+    // outpoint is listed once per tx spending it.
+    pub fn watched_outpoints(&self) -> Vec<(OutPoint, &'static str)> {
+        let peg_out_tx = self
+            .peg_out_transaction
+            .as_ref()
+            .map(|tx| ("peg_out", tx as &dyn PreSignedTransaction));
+
+        self.all_named_txs()
+            .into_iter()
+            .chain(peg_out_tx)
+            .flat_map(|(tx_name, tx)| {
+                tx.tx()
+                    .input
+                    .iter()
+                    .map(move |input| (input.previous_output, tx_name))
+            })
+            .collect()
+    }
 
-        let kick_off_1_txid = self.kick_off_1_transaction.tx().compute_txid();
-        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+    // Roles without a tx held by the graph, i.e. the peg-in confirm and the assert
+    // commit txs, spend nothing the graph could lock.
+    fn spent_outpoints(&self, role: TxRole) -> Vec<OutPoint> {
+        self.all_named_txs()
+            .into_iter()
+            .find(|(tx_name, _)| *tx_name == role.tx_name())
+            .map_or(vec![], |(_, tx)| {
+                tx.tx()
+                    .input
+                    .iter()
+                    .map(|input| input.previous_output)
+                    .collect()
+            })
+    }
 
-        if kick_off_1_status.is_ok_and(|status| status.confirmed) {
-            // sign start time tx
-            self.start_time_transaction.sign(
-                context,
-                &self.connector_2,
-                get_start_time_block_number(),
-                start_time_commitment_secret,
-            );
+    // Run ahead of `validate` so that a graph stored by another version, or a
+    // corrupted one, is reported by the names of the txs at fault. A tx counts as
+    // missing when it has no inputs or outputs or lacks the prev out of an input,
+    // and as orphaned when it does not descend from the peg-out confirm tx.
+    pub fn structural_completeness(&self) -> Result<(), Vec<&'static str>> {
+        let txs = self.all_named_txs();
+        let mut faulty_txs: Vec<&'static str> = txs
+            .iter()
+            .filter(|(_, tx)| {
+                tx.tx().input.is_empty()
+                    || tx.tx().output.is_empty()
+                    || tx.prev_outs().len() != tx.tx().input.len()
+            })
+            .map(|(tx_name, _)| *tx_name)
+            .collect();
+
+        let mut reachable_txs = vec!["peg_out_confirm"];
+        let mut reachable_txids = vec![self.peg_out_confirm_transaction.tx().compute_txid()];
+        loop {
+            let reachable_count = reachable_txs.len();
+            for (tx_name, tx) in &txs {
+                if !reachable_txs.contains(tx_name)
+                    && tx
+                        .tx()
+                        .input
+                        .iter()
+                        .any(|input| reachable_txids.contains(&input.previous_output.txid))
+                {
+                    reachable_txs.push(*tx_name);
+                    reachable_txids.push(tx.tx().compute_txid());
+                }
+            }
+            if reachable_txs.len() == reachable_count {
+                break;
+            }
+        }
 
-            // complete start time tx
-            let start_time_tx = self.start_time_transaction.finalize();
+        for (tx_name, _) in &txs {
+            if !reachable_txs.contains(tx_name) && !faulty_txs.contains(tx_name) {
+                faulty_txs.push(*tx_name);
+            }
+        }
 
-            // broadcast start time tx
-            broadcast_and_verify(client, &start_time_tx).await;
+        if faulty_txs.is_empty() {
+            Ok(())
         } else {
-            panic!("Kick-off 1 tx has not been confirmed!");
+            Err(faulty_txs)
         }
     }
 
-    pub async fn start_time_timeout(
-        &mut self,
-        client: &AsyncClient,
-        output_script_pubkey: ScriptBuf,
-    ) {
-        verify_if_not_mined(
-            client,
-            self.start_time_timeout_transaction.tx().compute_txid(),
-        )
-        .await;
+    // What the id of the graph is the hash of, see `generate_id_for_version`.
+    pub fn id_preimage(&self) -> String {
+        generate_id_preimage(&self.peg_in_graph_id, &self.operator_public_key)
+    }
 
-        let kick_off_1_txid = self.kick_off_1_transaction.tx().compute_txid();
-        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+    // Preflight guard for mainnet, where a graph built with the placeholders of
+    // the constructors must not be used. Returns the txs still carrying one.
+    pub fn reject_placeholders(&self) -> Result<(), Vec<&'static str>> {
+        if self.network != Network::Bitcoin {
+            return Ok(());
+        }
 
-        let blockchain_height = get_block_height(client).await;
+        let mut placeholder_txs = vec![];
+        if self.config.placeholder_challenge_amount {
+            placeholder_txs.push("challenge");
+        }
+        // the disprove leaf is not configurable yet, every graph spends the placeholder one
+        placeholder_txs.push("disprove");
 
-        if kick_off_1_status
-            .as_ref()
-            .is_ok_and(|status| status.confirmed)
-        {
-            if kick_off_1_status
-                .as_ref()
-                .unwrap()
-                .block_height
-                .is_some_and(|block_height| {
-                    block_height + self.connector_1.num_blocks_timelock_leaf_2 <= blockchain_height
-                })
-            {
-                // complete start time timeout tx
-                self.start_time_timeout_transaction
-                    .add_output(output_script_pubkey);
-                let start_time_timeout_tx = self.start_time_timeout_transaction.finalize();
+        Err(placeholder_txs)
+    }
 
-                // broadcast start time timeout tx
-                broadcast_and_verify(client, &start_time_timeout_tx).await;
-            } else {
-                panic!("Kick-off 1 timelock has not elapsed!");
+    // Every input must spend an output of another graph tx, whose output is what
+    // the input was signed against, or one of the prerequisite outputs.
+    pub fn check_linkage(&self) -> Vec<GraphInvariantError> {
+        let txs = self.all_role_txs();
+        let prerequisites: Vec<OutPoint> = self
+            .prerequisite_outputs()
+            .iter()
+            .map(|prerequisite| prerequisite.outpoint)
+            .collect();
+
+        let mut errors = vec![];
+        for &(role, tx) in &txs {
+            let tx_name = role.tx_name();
+            for (input_index, input) in tx.tx().input.iter().enumerate() {
+                let outpoint = input.previous_output;
+                let parent_output = txs
+                    .iter()
+                    .find(|(parent_role, _)| self.txid(*parent_role) == outpoint.txid)
+                    .and_then(|(_, parent)| parent.tx().output.get(outpoint.vout as usize));
+
+                match parent_output {
+                    Some(parent_output) => {
+                        if tx.prev_outs().get(input_index) != Some(parent_output) {
+                            errors.push(GraphInvariantError::PrevOutMismatch {
+                                tx_name,
+                                input_index,
+                            });
+                        }
+                    }
+                    None if prerequisites.contains(&outpoint) => {}
+                    None => errors.push(GraphInvariantError::UnknownParent { tx_name, outpoint }),
+                }
             }
-        } else {
-            panic!("Kick-off 1 tx has not been confirmed!");
         }
+
+        errors
     }
 
-    pub async fn kick_off_2(
-        &mut self,
-        client: &AsyncClient,
-        context: &OperatorContext,
-        superblock_commitment_secret: &WinternitzSecret,
-        superblock_hash_commitment_secret: &WinternitzSecret,
-    ) {
-        verify_if_not_mined(client, self.kick_off_2_transaction.tx().compute_txid()).await;
+    // The challenge tx is crowdfunded at broadcast time, so until then its inputs
+    // are counted with the funding it is going to require.
+    fn graph_input_amount(&self, tx_name: &str, tx: &dyn PreSignedTransaction) -> Amount {
+        let input_amount: Amount = tx.prev_outs().iter().map(|prev_out| prev_out.value).sum();
+        if tx_name == "challenge" && tx.tx().input.len() == 1 {
+            input_amount + self.required_challenge_funding()
+        } else {
+            input_amount
+        }
+    }
 
-        let kick_off_1_txid = self.kick_off_1_transaction.tx().compute_txid();
-        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+    pub fn check_value_conservation(&self) -> Vec<GraphInvariantError> {
+        self.all_named_txs()
+            .into_iter()
+            .filter_map(|(tx_name, tx)| {
+                let input_amount = self.graph_input_amount(tx_name, tx);
+                let output_amount: Amount = tx.tx().output.iter().map(|output| output.value).sum();
+
+                (output_amount > input_amount).then_some(GraphInvariantError::ValueNotConserved {
+                    tx_name,
+                    input_amount,
+                    output_amount,
+                })
+            })
+            .collect()
+    }
 
-        let blockchain_height = get_block_height(client).await;
+    // Returns the graph txs whose fee rate in sat/vB is below the given minimum
+    // relay fee rate, with their fee rate. See `estimated_vsize` for the size.
+    pub fn check_min_relay(&self, min_relay_sat_per_vb: f64) -> Vec<(String, f64)> {
+        self.all_named_txs()
+            .into_iter()
+            .filter_map(|(tx_name, tx)| {
+                let output_amount: Amount = tx.tx().output.iter().map(|output| output.value).sum();
+                let fee = self
+                    .graph_input_amount(tx_name, tx)
+                    .checked_sub(output_amount)
+                    .unwrap_or(Amount::ZERO);
+                let fee_rate = fee.to_sat() as f64 / estimated_vsize(tx) as f64;
+
+                (fee_rate < min_relay_sat_per_vb).then(|| (tx_name.to_string(), fee_rate))
+            })
+            .collect()
+    }
 
-        if kick_off_1_status
-            .as_ref()
-            .is_ok_and(|status| status.confirmed)
+    // Fails if another tx spending one of the outputs of the given tx has been
+    // broadcast from this graph. Retrying the same tx is always allowed.
+    pub fn check_action(&self, role: TxRole) -> Result<(), ActionInProgress> {
+        match self
+            .spent_outpoints(role)
+            .iter()
+            .filter_map(|outpoint| self.action_lock.0.get(outpoint))
+            .find(|locked_by| **locked_by != role)
         {
-            if kick_off_1_status
-                .as_ref()
-                .unwrap()
-                .block_height
-                .is_some_and(|block_height| {
-                    block_height + self.connector_1.num_blocks_timelock_leaf_0 <= blockchain_height
-                })
-            {
-                // complete kick-off 2 tx
-                let superblock_header = find_superblock();
-                self.kick_off_2_transaction.sign(
-                    context,
-                    &self.connector_1,
-                    &WinternitzSigningInputs {
-                        message: &get_superblock_message(&superblock_header),
-                        signing_key: superblock_commitment_secret,
-                    },
-                    &WinternitzSigningInputs {
-                        message: &get_superblock_hash_message(&superblock_header),
-                        signing_key: superblock_hash_commitment_secret,
-                    },
-                );
-                let kick_off_2_tx = self.kick_off_2_transaction.finalize();
+            Some(conflicting) => Err(ActionInProgress {
+                conflicting: *conflicting,
+            }),
+            None => Ok(()),
+        }
+    }
 
-                // broadcast kick-off 2 tx
-                broadcast_and_verify(client, &kick_off_2_tx).await;
-            } else {
-                panic!("Kick-off 1 timelock has not elapsed!");
-            }
-        } else {
-            panic!("Kick-off 1 tx has not been confirmed!");
+    // Locks the outputs spent by the given tx until `end_action` is called for it.
+    pub fn begin_action(&mut self, role: TxRole) -> Result<(), ActionInProgress> {
+        self.check_action(role)?;
+        for outpoint in self.spent_outpoints(role) {
+            self.action_lock.0.insert(outpoint, role);
         }
+
+        Ok(())
     }
 
-    pub async fn kick_off_timeout(
+    // Releases the outputs locked by the given tx, e.g. once its broadcast failed
+    // or it was replaced.
+    pub fn end_action(&mut self, role: TxRole) {
+        self.action_lock.0.retain(|_, locked_by| *locked_by != role);
+    }
+
+    // An action is resolved once its tx is confirmed, or once another tx spends
+    // one of the outputs it locked. Either way the lock has nothing left to guard.
+    pub fn release_resolved_actions(
         &mut self,
-        client: &AsyncClient,
-        output_script_pubkey: ScriptBuf,
+        confirmed_txids: &[Txid],
+        spending_txids: &HashMap<OutPoint, Txid>,
     ) {
-        verify_if_not_mined(
-            client,
-            self.kick_off_timeout_transaction.tx().compute_txid(),
-        )
-        .await;
-
-        let kick_off_1_txid = self.kick_off_1_transaction.tx().compute_txid();
-        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
-
-        let blockchain_height = get_block_height(client).await;
+        let resolved_roles: Vec<TxRole> = self
+            .action_lock
+            .0
+            .iter()
+            .filter(|(outpoint, role)| {
+                let txid = self.txid(*role);
+                confirmed_txids.contains(&txid)
+                    || spending_txids
+                        .get(outpoint)
+                        .is_some_and(|spending_txid| *spending_txid != txid)
+            })
+            .map(|(_, role)| *role)
+            .collect();
+        for role in resolved_roles {
+            self.end_action(role);
+        }
+    }
 
-        if kick_off_1_status
-            .as_ref()
-            .is_ok_and(|status| status.confirmed)
-        {
-            if kick_off_1_status
-                .as_ref()
-                .unwrap()
-                .block_height
-                .is_some_and(|block_height| {
-                    block_height + self.connector_1.num_blocks_timelock_leaf_1 <= blockchain_height
-                })
+    async fn fetch_resolved_actions(&mut self, client: &AsyncClient) {
+        let mut locked_roles: Vec<TxRole> = self.action_lock.0.values().copied().collect();
+        locked_roles.sort();
+        locked_roles.dedup();
+
+        let mut confirmed_txids = vec![];
+        for role in locked_roles {
+            let txid = self.txid(role);
+            if client
+                .get_tx_status(&txid)
+                .await
+                .is_ok_and(|status| status.confirmed)
             {
-                // complete kick-off timeout tx
-                let kick_off_timeout_tx = self.kick_off_timeout_transaction.finalize();
-
-                // broadcast kick-off timeout tx
-                self.kick_off_timeout_transaction
-                    .add_output(output_script_pubkey);
-                broadcast_and_verify(client, &kick_off_timeout_tx).await;
-            } else {
-                panic!("Kick-off 1 timelock has not elapsed!");
+                confirmed_txids.push(txid);
             }
-        } else {
-            panic!("Kick-off 1 tx has not been confirmed!");
         }
-    }
-
-    pub async fn assert_initial(&mut self, client: &AsyncClient) {
-        verify_if_not_mined(client, self.assert_initial_transaction.tx().compute_txid()).await;
 
-        let kick_off_2_txid = self.kick_off_2_transaction.tx().compute_txid();
-        let kick_off_2_status = client.get_tx_status(&kick_off_2_txid).await;
-
-        let blockchain_height = get_block_height(client).await;
-
-        if kick_off_2_status
-            .as_ref()
-            .is_ok_and(|status| status.confirmed)
-        {
-            if kick_off_2_status
-                .as_ref()
-                .unwrap()
-                .block_height
-                .is_some_and(|block_height| {
-                    block_height + self.connector_b.num_blocks_timelock_1 <= blockchain_height
-                })
+        let mut spending_txids = HashMap::new();
+        for outpoint in self.action_lock.0.keys() {
+            if let Ok(Some(output_status)) = client
+                .get_output_status(&outpoint.txid, outpoint.vout.into())
+                .await
             {
-                // complete assert initial tx
-                let assert_initial_tx = self.assert_initial_transaction.finalize();
-
-                // broadcast assert initial tx
-                broadcast_and_verify(client, &assert_initial_tx).await;
-            } else {
-                panic!("Kick-off 2 timelock has not elapsed!");
+                if let Some(spending_txid) = output_status.txid {
+                    spending_txids.insert(*outpoint, spending_txid);
+                }
             }
-        } else {
-            panic!("Kick-off 2 tx has not been confirmed!");
         }
-    }
-
-    pub async fn assert_final(&mut self, client: &AsyncClient) {
-        verify_if_not_mined(client, self.assert_final_transaction.tx().compute_txid()).await;
 
-        let assert_initial_txid = self.assert_initial_transaction.tx().compute_txid();
-        let assert_initial_status = client.get_tx_status(&assert_initial_txid).await;
+        self.release_resolved_actions(&confirmed_txids, &spending_txids);
+    }
 
-        if assert_initial_status
-            .as_ref()
-            .is_ok_and(|status| status.confirmed)
-        {
-            // complete assert final tx
-            let assert_final_tx = self.assert_final_transaction.finalize();
+    // Like `verify_if_not_mined`, but fails the action instead of panicking.
+    async fn verify_not_mined(
+        &self,
+        client: &AsyncClient,
+        role: TxRole,
+    ) -> Result<(), ActionError> {
+        let txid = self.txid(role);
+        match client.get_tx_status(&txid).await {
+            Ok(status) if status.confirmed => Err(ActionError::AlreadyMined(txid)),
+            Ok(_) => Ok(()),
+            Err(err) => Err(ActionError::Esplora(format!("{err:?}"))),
+        }
+    }
 
-            // broadcast assert final tx
-            broadcast_and_verify(client, &assert_final_tx).await;
-        } else {
-            panic!("Assert-initial tx has not been confirmed!");
+    // Like `get_block_height_if_none`, but fails the action instead of panicking.
+    async fn action_block_height(
+        client: &AsyncClient,
+        blockchain_height: Option<u32>,
+    ) -> Result<u32, ActionError> {
+        match blockchain_height {
+            Some(blockchain_height) => Ok(blockchain_height),
+            None => client
+                .get_height()
+                .await
+                .map_err(|err| ActionError::Esplora(format!("{err:?}"))),
         }
     }
 
-    pub async fn disprove(
+    // Broadcasts the tx of an action under its lock. Locks of actions that have
+    // been resolved meanwhile are released first, and the lock of this action is
+    // released again if the broadcast fails.
+    async fn broadcast_action(
         &mut self,
         client: &AsyncClient,
-        input_script_index: u32,
-        output_script_pubkey: ScriptBuf,
-    ) {
-        verify_if_not_mined(client, self.disprove_transaction.tx().compute_txid()).await;
-
-        let assert_final_txid = self.assert_final_transaction.tx().compute_txid();
-        let assert_final_status = client.get_tx_status(&assert_final_txid).await;
+        role: TxRole,
+        tx: &Transaction,
+    ) -> Result<(), ActionError> {
+        self.fetch_resolved_actions(client).await;
+        self.begin_action(role).map_err(ActionError::InProgress)?;
+
+        if let Err(err) = try_broadcast_and_verify(client, tx).await {
+            self.end_action(role);
+            return Err(ActionError::BroadcastFailed(err));
+        }
 
-        if assert_final_status.is_ok_and(|status| status.confirmed) {
-            // decide if broadcast disprove instead of unwrap directly.
-            // TODO: store and read vk
-            // TODO: get commit transaction witness from network?
-            let (input_script_index, disprove_witness) = self
-                .connector_c
-                .generate_disprove_witness(vec![], vec![], RawProof::default().vk)
-                .unwrap();
+        Ok(())
+    }
 
-            // complete disprove tx
-            self.disprove_transaction.add_input_output(
-                &self.connector_c,
-                input_script_index as u32,
-                disprove_witness,
-                output_script_pubkey,
-            );
-            let disprove_tx = self.disprove_transaction.finalize();
+    pub fn prerequisite_outputs(&self) -> Vec<PrerequisiteOutput> {
+        let peg_out_confirm_funding =
+            self.peg_out_confirm_transaction
+                .tx()
+                .input
+                .iter()
+                .map(|tx_in| PrerequisiteOutput {
+                    name: "peg-out confirm funding",
+                    outpoint: tx_in.previous_output,
+                    graph_spending_txids: vec![self.txid(TxRole::PegOutConfirm)],
+                });
+
+        peg_out_confirm_funding
+            .chain([PrerequisiteOutput {
+                name: "peg-in confirm",
+                outpoint: self.take_1_transaction.tx().input[0].previous_output,
+                graph_spending_txids: vec![self.txid(TxRole::Take1), self.txid(TxRole::Take2)],
+            }])
+            .collect()
+    }
 
-            // broadcast disprove tx
-            broadcast_and_verify(client, &disprove_tx).await;
-        } else {
-            panic!("Assert tx has not been confirmed!");
+    pub async fn liveness(&self, client: &AsyncClient) -> Liveness {
+        let mut statuses = vec![];
+        for prerequisite in self.prerequisite_outputs() {
+            let outpoint = prerequisite.outpoint;
+            let funding_tx_status = client.get_tx_status(&outpoint.txid).await;
+            let output_status = client
+                .get_output_status(&outpoint.txid, outpoint.vout.into())
+                .await;
+
+            statuses.push(PrerequisiteOutputStatus {
+                outpoint,
+                is_funding_tx_confirmed: funding_tx_status.is_ok_and(|status| status.confirmed),
+                spending_txid: output_status
+                    .ok()
+                    .flatten()
+                    .and_then(|output_status| output_status.txid),
+            });
         }
-    }
 
-    pub async fn disprove_chain(&mut self, client: &AsyncClient, output_script_pubkey: ScriptBuf) {
-        verify_if_not_mined(client, self.disprove_chain_transaction.tx().compute_txid()).await;
+        self.interpret_liveness(&statuses)
+    }
 
-        let kick_off_2_txid = self.kick_off_2_transaction.tx().compute_txid();
-        let kick_off_2_status = client.get_tx_status(&kick_off_2_txid).await;
+    // A prerequisite spent by a conflicting tx can never be spent by the graph again,
+    // whereas an unconfirmed one may still confirm later.
+    pub fn interpret_liveness(&self, statuses: &[PrerequisiteOutputStatus]) -> Liveness {
+        let prerequisites = self.prerequisite_outputs();
+        let find_status = |prerequisite: &PrerequisiteOutput| {
+            statuses
+                .iter()
+                .find(|status| status.outpoint == prerequisite.outpoint)
+        };
 
-        if kick_off_2_status.is_ok_and(|status| status.confirmed) {
-            // complete disprove chain tx
-            self.disprove_chain_transaction
-                .add_output(output_script_pubkey);
-            let disprove_chain_tx = self.disprove_chain_transaction.finalize();
+        for prerequisite in &prerequisites {
+            if let Some(spending_txid) =
+                find_status(prerequisite).and_then(|status| status.spending_txid)
+            {
+                if !prerequisite.graph_spending_txids.contains(&spending_txid) {
+                    return Liveness::Dead {
+                        reason: format!(
+                            "{} output {} was spent by conflicting tx {}",
+                            prerequisite.name, prerequisite.outpoint, spending_txid
+                        ),
+                    };
+                }
+            }
+        }
 
-            // broadcast disprove chain tx
-            broadcast_and_verify(client, &disprove_chain_tx).await;
-        } else {
-            panic!("Kick-off 2 tx has not been confirmed!");
+        for prerequisite in &prerequisites {
+            if !find_status(prerequisite).is_some_and(|status| status.is_funding_tx_confirmed) {
+                return Liveness::Stalled {
+                    reason: format!(
+                        "{} output {} has not been confirmed",
+                        prerequisite.name, prerequisite.outpoint
+                    ),
+                };
+            }
         }
+
+        Liveness::Alive
     }
 
-    pub async fn take_1(&mut self, client: &AsyncClient) {
-        verify_if_not_mined(client, self.take_1_transaction.tx().compute_txid()).await;
-        verify_if_not_mined(client, self.challenge_transaction.tx().compute_txid()).await;
-        verify_if_not_mined(client, self.assert_final_transaction.tx().compute_txid()).await;
-        verify_if_not_mined(client, self.disprove_chain_transaction.tx().compute_txid()).await;
+    pub async fn operator_deadlines(&self, client: &AsyncClient) -> Vec<Deadline> {
+        let statuses = self.get_peg_out_statuses(client).await;
 
-        let peg_in_confirm_status = client.get_tx_status(&self.peg_in_confirm_txid).await;
+        self.interpret_operator_deadlines(&graph_statuses_by_name(statuses))
+    }
 
-        let kick_off_1_txid = self.kick_off_1_transaction.tx().compute_txid();
-        let kick_off_1_status = client.get_tx_status(&kick_off_1_txid).await;
+    // Single pass over the graph txs, reporting to the observer of the context the
+    // confirmations and the status change since `observed`.
+    pub async fn observe_operator_status(
+        &self,
+        client: &AsyncClient,
+        context: &OperatorContext,
+        observed: &mut ObservedGraph,
+    ) -> PegOutOperatorStatus {
+        let statuses = self.get_peg_out_statuses(client).await;
 
-        let kick_off_2_txid = self.kick_off_2_transaction.tx().compute_txid();
-        let kick_off_2_status = client.get_tx_status(&kick_off_2_txid).await;
+        let status = if self.n_of_n_presigned && self.is_peg_out_initiated() {
+            let blockchain_height = get_block_height(client).await;
+            self.operator_status_from_statuses(
+                &statuses,
+                blockchain_height,
+                context.min_confirmations,
+            )
+        } else {
+            PegOutOperatorStatus::PegOutWait(self.setup_wait_reason())
+        };
+        self.report_operator_status(
+            context.observer.as_ref(),
+            observed,
+            &graph_statuses_by_name(statuses),
+            &status,
+        );
 
-        let blockchain_height = get_block_height(client).await;
+        status
+    }
 
-        if peg_in_confirm_status.is_ok_and(|status| status.confirmed)
-            && kick_off_1_status
-                .as_ref()
-                .is_ok_and(|status| status.confirmed)
-            && kick_off_2_status
-                .as_ref()
-                .is_ok_and(|status| status.confirmed)
-        {
-            if kick_off_2_status
-                .unwrap()
-                .block_height
-                .is_some_and(|block_height| {
-                    block_height + self.connector_3.num_blocks_timelock <= blockchain_height
-                })
-            {
-                // complete take 1 tx
-                let take_1_tx = self.take_1_transaction.finalize();
+    // Confirmations are reported once the tx has a block height, in the order the
+    // txs were confirmed in.
+    pub fn report_operator_status(
+        &self,
+        observer: &dyn GraphObserver,
+        observed: &mut ObservedGraph,
+        statuses: &HashMap<&str, Result<TxStatus, Error>>,
+        status: &PegOutOperatorStatus,
+    ) {
+        let mut confirmations: Vec<(u32, &'static str)> = self
+            .all_named_txs()
+            .into_iter()
+            .filter(|(tx_name, _)| !observed.confirmed_txs.contains(tx_name))
+            .filter_map(|(tx_name, _)| {
+                statuses
+                    .get(tx_name)
+                    .and_then(confirmation_height)
+                    .map(|block_height| (block_height, tx_name))
+            })
+            .collect();
+        confirmations.sort_by_key(|(block_height, _)| *block_height);
+
+        for (block_height, tx_name) in confirmations {
+            observed.confirmed_txs.push(tx_name);
+            observer.on_event(GraphEvent::ConfirmationDetected {
+                graph_id: self.id.clone(),
+                tx_name,
+                block_height,
+            });
+        }
 
-                // broadcast take 1 tx
-                broadcast_and_verify(client, &take_1_tx).await;
-            } else {
-                panic!("Kick-off 2 tx timelock has not elapsed!");
-            }
-        } else {
-            panic!("Peg-in confirm tx, kick-off 1 and kick-off 2 tx have not been confirmed!");
+        let status = status.to_string();
+        if observed.status.as_ref() != Some(&status) {
+            observed.status = Some(status.clone());
+            observer.on_event(GraphEvent::StatusChanged {
+                graph_id: self.id.clone(),
+                status,
+            });
         }
     }
 
-    pub async fn take_2(&mut self, client: &AsyncClient, context: &OperatorContext) {
-        verify_if_not_mined(client, self.take_2_transaction.tx().compute_txid()).await;
-        verify_if_not_mined(client, self.take_1_transaction.tx().compute_txid()).await;
-        verify_if_not_mined(client, self.disprove_transaction.tx().compute_txid()).await;
+    // Deadlines sorted by height, soonest first. Txs missing from `statuses` count
+    // as unconfirmed, and no deadline is derived from a tx without a block height.
+    pub fn interpret_operator_deadlines(
+        &self,
+        statuses: &HashMap<&str, Result<TxStatus, Error>>,
+    ) -> Vec<Deadline> {
+        let height = |tx_name: &str| statuses.get(tx_name).and_then(confirmation_height);
+        let is_confirmed = |tx_name: &str| {
+            statuses
+                .get(tx_name)
+                .is_some_and(|status| status.as_ref().is_ok_and(|status| status.confirmed))
+        };
+        let deadline = |tx_name, height, consequence| Deadline {
+            tx_name,
+            height,
+            consequence,
+        };
 
-        let peg_in_confirm_status = client.get_tx_status(&self.peg_in_confirm_txid).await;
+        let mut deadlines = vec![];
+        let is_settled = [
+            "kick_off_timeout",
+            "start_time_timeout",
+            "disprove_chain",
+            "disprove",
+            "take_1",
+            "take_2",
+        ]
+        .into_iter()
+        .any(is_confirmed);
+        if is_settled {
+            return deadlines;
+        }
+        let Some(kick_off_1_height) = height("kick_off_1") else {
+            return deadlines;
+        };
 
-        let assert_final_txid = self.assert_final_transaction.tx().compute_txid();
-        let assert_final_status = client.get_tx_status(&assert_final_txid).await;
+        if !is_confirmed("kick_off_2") {
+            if !is_confirmed("start_time") {
+                deadlines.push(deadline(
+                    "start_time",
+                    kick_off_1_height + self.connector_1.num_blocks_timelock_leaf_2,
+                    DeadlineConsequence::StartTimeTimeout,
+                ));
+            }
+            deadlines.push(deadline(
+                "kick_off_2",
+                kick_off_1_height + self.connector_1.num_blocks_timelock_leaf_1,
+                DeadlineConsequence::KickOffTimeout,
+            ));
+        } else if let Some(kick_off_2_height) = height("kick_off_2") {
+            if !is_confirmed("challenge") {
+                deadlines.push(deadline(
+                    "take_1",
+                    kick_off_2_height + self.connector_3.num_blocks_timelock,
+                    DeadlineConsequence::TakeContested,
+                ));
+            } else if !is_confirmed("assert_initial") {
+                deadlines.push(deadline(
+                    "assert_initial",
+                    kick_off_2_height + self.connector_b.num_blocks_timelock_1,
+                    DeadlineConsequence::ChallengeUnanswered,
+                ));
+            } else if let Some(assert_final_height) = height("assert_final") {
+                deadlines.push(deadline(
+                    "take_2",
+                    assert_final_height + self.connector_4.num_blocks_timelock,
+                    DeadlineConsequence::TakeContested,
+                ));
+            }
+        }
 
-        let blockchain_height = get_block_height(client).await;
+        deadlines.sort_by_key(|deadline| deadline.height);
+        deadlines
+    }
 
-        if peg_in_confirm_status.is_ok_and(|status| status.confirmed)
-            && assert_final_status
-                .as_ref()
-                .is_ok_and(|status| status.confirmed)
-        {
-            if assert_final_status
-                .unwrap()
-                .block_height
-                .is_some_and(|block_height| {
-                    block_height + self.connector_4.num_blocks_timelock <= blockchain_height
-                })
-            {
-                // complete take 2 tx
-                self.take_2_transaction.sign(context, &self.connector_c);
-                let take_2_tx = self.take_2_transaction.finalize();
+    pub fn is_peg_out_initiated(&self) -> bool { self.peg_out_chain_event.is_some() }
+
+    // None until a peg-out event is attached. Lets the withdrawer check the payout
+    // before the operator creates the peg-out tx.
+    pub fn withdrawer_expectation(
+        &self,
+    ) -> Option<Result<WithdrawerExpectation, PegOutDestinationError>> {
+        let event = self.peg_out_chain_event.as_ref()?;
+
+        Some(
+            event
+                .destination_address(self.network)
+                .and_then(|destination_address| {
+                    Ok(WithdrawerExpectation {
+                        destination_address,
+                        payout_script_pubkey: event.payout_script_pubkey(self.network)?,
+                        amount: event
+                            .amount
+                            .checked_sub(Amount::from_sat(FEE_AMOUNT))
+                            .unwrap_or(Amount::ZERO),
+                    })
+                }),
+        )
+    }
 
-                // broadcast take 2 tx
-                broadcast_and_verify(client, &take_2_tx).await;
-            } else {
-                panic!("Assert tx timelock has not elapsed!");
-            }
-        } else {
-            panic!("Peg-in confirm tx and assert tx have not been confirmed!");
-        }
+    fn is_matching_peg_out_event(&self, event: &PegOutEvent) -> bool {
+        self.peg_in_confirm_txid.eq(&event.source_outpoint.txid)
+            && self.operator_public_key.eq(&event.operator_public_key)
     }
 
-    pub fn is_peg_out_initiated(&self) -> bool { self.peg_out_chain_event.is_some() }
+    // An L2 reorg can replace the event the peg-out was initiated with. Before
+    // kick-off 1 commits to the event's tx hash the stored event follows the L2
+    // chain, afterwards a changed tx hash leaves the operator with a commitment
+    // to an event that no longer exists.
+    pub fn reconcile_peg_out_event(&mut self, new_events: &[PegOutEvent]) -> ReconcileOutcome {
+        let matching_events: Vec<&PegOutEvent> = new_events
+            .iter()
+            .filter(|event| self.is_matching_peg_out_event(event))
+            .collect();
+        let current_event = match matching_events[..] {
+            [] => None,
+            [event] => Some(event.clone()),
+            _ => return ReconcileOutcome::NotUnique,
+        };
+        if current_event == self.peg_out_chain_event {
+            return ReconcileOutcome::Unchanged;
+        }
+
+        let is_kick_off_1_signed = self.kick_off_1_peg_out_txid.is_some();
+        let committed_tx_hash = self
+            .peg_out_chain_event
+            .as_ref()
+            .map(|event| &event.tx_hash);
+        if is_kick_off_1_signed
+            && current_event.as_ref().map(|event| &event.tx_hash) != committed_tx_hash
+        {
+            return ReconcileOutcome::CommittedToStaleEvent { current_event };
+        }
+
+        self.peg_out_chain_event = current_event.clone();
+        self.is_validated.0 = false;
+        ReconcileOutcome::Updated(current_event)
+    }
 
     pub async fn match_and_set_peg_out_event(
         &mut self,
@@ -1948,9 +6033,7 @@ impl PegOutGraph {
         let mut events: Vec<PegOutEvent> = Vec::new();
         let mut ids: Vec<usize> = Vec::new();
         for (i, event) in all_events.iter().enumerate() {
-            if self.peg_in_confirm_txid.eq(&event.source_outpoint.txid)
-                && self.operator_public_key.eq(&event.operator_public_key)
-            {
+            if self.is_matching_peg_out_event(event) {
                 events.push(event.clone());
                 ids.push(i);
             }
@@ -1963,98 +6046,83 @@ impl PegOutGraph {
             0 => Ok(None),
             1 => {
                 self.peg_out_chain_event = Some(events[0].clone());
+                self.is_validated.0 = false;
                 Ok(Some(events[0].clone()))
             }
             _ => Err(String::from("Event from L2 chain is not unique")),
         }
     }
 
-    async fn get_peg_out_statuses(
+    async fn get_peg_out_statuses(&self, client: &AsyncClient) -> PegOutStatuses {
+        // one request per tx, all in flight at once
+        let tx_statuses = get_tx_statuses(client, &self.status_txids()).await;
+        self.peg_out_statuses_from(tx_statuses)
+    }
+
+    async fn get_peg_out_statuses_cached(
         &self,
         client: &AsyncClient,
-    ) -> (
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Option<Result<TxStatus, Error>>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-        Result<TxStatus, Error>,
-    ) {
-        let assert_initial_status = client
-            .get_tx_status(&self.assert_initial_transaction.tx().compute_txid())
-            .await;
-
-        let assert_final_status = client
-            .get_tx_status(&self.assert_final_transaction.tx().compute_txid())
-            .await;
-
-        let challenge_status = client
-            .get_tx_status(&self.challenge_transaction.tx().compute_txid())
-            .await;
-
-        let disprove_chain_status = client
-            .get_tx_status(&self.disprove_chain_transaction.tx().compute_txid())
-            .await;
-
-        let disprove_status = client
-            .get_tx_status(&self.disprove_transaction.tx().compute_txid())
-            .await;
-
-        let peg_out_confirm_status = client
-            .get_tx_status(&self.peg_out_confirm_transaction.tx().compute_txid())
-            .await;
-
-        let kick_off_1_status = client
-            .get_tx_status(&self.kick_off_1_transaction.tx().compute_txid())
-            .await;
-
-        let kick_off_2_status = client
-            .get_tx_status(&self.kick_off_2_transaction.tx().compute_txid())
-            .await;
-
-        let kick_off_timeout_status = client
-            .get_tx_status(&self.kick_off_timeout_transaction.tx().compute_txid())
-            .await;
-
-        let mut peg_out_status: Option<Result<TxStatus, Error>> = None;
-        if self.peg_out_transaction.is_some() {
-            peg_out_status = Some(
-                client
-                    .get_tx_status(
-                        &self
-                            .peg_out_transaction
-                            .as_ref()
-                            .unwrap()
-                            .tx()
-                            .compute_txid(),
-                    )
-                    .await,
-            );
-        }
+        cache: &mut StatusCache,
+    ) -> PegOutStatuses {
+        let tx_statuses = cache.statuses(client, &self.status_txids()).await;
+        self.peg_out_statuses_from(tx_statuses)
+    }
 
-        let start_time_timeout_status = client
-            .get_tx_status(&self.start_time_timeout_transaction.tx().compute_txid())
-            .await;
+    // The txids whose statuses make up `PegOutStatuses`, the peg-out tx last if known.
+    fn status_txids(&self) -> Vec<Txid> {
+        self.named_status_txids()
+            .into_iter()
+            .map(|(_, txid)| txid)
+            .collect()
+    }
 
-        let start_time_status = client
-            .get_tx_status(&self.start_time_transaction.tx().compute_txid())
-            .await;
+    fn named_status_txids(&self) -> Vec<(&'static str, Txid)> {
+        let peg_out_txid = self
+            .peg_out_txid()
+            .map(|peg_out_txid| ("peg_out", peg_out_txid));
+        [
+            TxRole::AssertInitial,
+            TxRole::AssertFinal,
+            TxRole::Challenge,
+            TxRole::DisproveChain,
+            TxRole::Disprove,
+            TxRole::PegOutConfirm,
+            TxRole::KickOff1,
+            TxRole::KickOff2,
+            TxRole::KickOffTimeout,
+            TxRole::StartTimeTimeout,
+            TxRole::StartTime,
+            TxRole::Take1,
+            TxRole::Take2,
+        ]
+        .into_iter()
+        .map(|role| (role.tx_name(), self.txid(role)))
+        .chain(peg_out_txid)
+        .collect()
+    }
 
-        let take_1_status = client
-            .get_tx_status(&self.take_1_transaction.tx().compute_txid())
-            .await;
+    fn peg_out_statuses_from(&self, tx_statuses: Vec<Result<TxStatus, Error>>) -> PegOutStatuses {
+        let mut tx_statuses = tx_statuses.into_iter();
 
-        let take_2_status = client
-            .get_tx_status(&self.take_2_transaction.tx().compute_txid())
-            .await;
+        let peg_out_status = self
+            .peg_out_transaction
+            .as_ref()
+            .and_then(|_| tx_statuses.next_back());
+        // statuses come back in the order of their txids
+        let mut next_status = || tx_statuses.next().unwrap();
+        let assert_initial_status = next_status();
+        let assert_final_status = next_status();
+        let challenge_status = next_status();
+        let disprove_chain_status = next_status();
+        let disprove_status = next_status();
+        let peg_out_confirm_status = next_status();
+        let kick_off_1_status = next_status();
+        let kick_off_2_status = next_status();
+        let kick_off_timeout_status = next_status();
+        let start_time_timeout_status = next_status();
+        let start_time_status = next_status();
+        let take_1_status = next_status();
+        let take_2_status = next_status();
 
         (
             assert_initial_status,
@@ -2074,86 +6142,219 @@ impl PegOutGraph {
         )
     }
 
-    pub fn validate(&self) -> bool {
-        let mut ret_val = true;
-        let peg_out_graph = self.new_for_validation();
-        if !validate_transaction(
-            self.assert_initial_transaction.tx(),
-            peg_out_graph.assert_initial_transaction.tx(),
-        ) {
-            ret_val = false;
-        }
-        if !validate_transaction(
-            self.assert_final_transaction.tx(),
-            peg_out_graph.assert_final_transaction.tx(),
-        ) {
-            ret_val = false;
-        }
-        if !validate_transaction(
-            self.challenge_transaction.tx(),
-            peg_out_graph.challenge_transaction.tx(),
-        ) {
-            ret_val = false;
-        }
-        if !validate_transaction(
-            self.disprove_chain_transaction.tx(),
-            peg_out_graph.disprove_chain_transaction.tx(),
-        ) {
-            ret_val = false;
-        }
-        if !validate_transaction(
-            self.disprove_transaction.tx(),
-            peg_out_graph.disprove_transaction.tx(),
-        ) {
-            ret_val = false;
+    // Txs whose only difference from the rebuilt graph is the fee they pay
+    pub fn fee_divergences(
+        &self,
+    ) -> Result<Vec<(&'static str, FeeDivergence)>, ValidationRebuildError> {
+        let peg_out_graph = self.new_for_validation()?;
+
+        Ok(self
+            .all_named_txs()
+            .into_iter()
+            .zip(peg_out_graph.all_named_txs())
+            .filter_map(|((tx_name, tx), (_, comparison_tx))| {
+                fee_divergence(tx, comparison_tx).map(|divergence| (tx_name, divergence))
+            })
+            .collect())
+    }
+
+    // Txs that can pay the fee rate of the moment: neither presigned nor spent by
+    // another graph tx, which would commit to their txid. The peg-out tx is funded
+    // by the operator alone.
+    pub fn fee_adjustable_txs(&self) -> Vec<&'static str> {
+        let presigned_tx_names: Vec<&str> = self
+            .all_named_presigned_txs()
+            .into_iter()
+            .map(|(tx_name, _)| tx_name)
+            .collect();
+        let spent_txids: HashSet<Txid> = self
+            .all_named_txs()
+            .into_iter()
+            .flat_map(|(_, tx)| tx.tx().input.iter().map(|input| input.previous_output.txid))
+            .collect();
+
+        let mut tx_names = vec!["peg_out"];
+        tx_names.extend(
+            self.all_role_txs()
+                .into_iter()
+                .map(|(role, _)| role)
+                .filter(|role| {
+                    !presigned_tx_names.contains(&role.tx_name())
+                        && !spent_txids.contains(&self.txid(*role))
+                })
+                .map(|role| role.tx_name()),
+        );
+        tx_names
+    }
+
+    // Txs paying the fee the graph was built with, whatever the fee market.
+    pub fn fixed_fee_txs(&self) -> Vec<&'static str> {
+        let fee_adjustable_txs = self.fee_adjustable_txs();
+        self.all_named_txs()
+            .into_iter()
+            .map(|(tx_name, _)| tx_name)
+            .filter(|tx_name| !fee_adjustable_txs.contains(tx_name))
+            .collect()
+    }
+
+    // Only applies to `fee_adjustable_txs`, the others cannot be bumped this way.
+    // Fails if the estimates cannot be fetched or have no fitting target.
+    pub async fn recommended_fee_rate(
+        &self,
+        client: &AsyncClient,
+        target_blocks: u16,
+    ) -> Result<f64, String> {
+        EsploraFeeEstimator::new(client)
+            .fee_rate(target_blocks)
+            .await
+    }
+
+    // The assert commit txs are not kept in the graph, their fees are what they
+    // spend from assert initial less what they pass on to assert final.
+    pub fn dispute_path_cost(&self) -> DisputePathCost {
+        let fee = |tx_name: &str, tx: &dyn PreSignedTransaction| {
+            transaction_fee(tx)
+                .unwrap_or_else(|| panic!("{tx_name} tx pays out more than it spends"))
+        };
+
+        let assert_initial_outputs = &self.assert_initial_transaction.tx().output;
+        let assert_final_prev_outs = self.assert_final_transaction.prev_outs();
+        let commit_1_end = topology::ASSERT_COMMIT_VOUT_BASE + self.connector_e_1.connectors_num();
+        let commit_2_end = commit_1_end + self.connector_e_2.connectors_num();
+        let assert_commit_fee = |tx_name: &str, outputs: &[TxOut], assert_final_vin: usize| {
+            outputs
+                .iter()
+                .map(|output| output.value)
+                .sum::<Amount>()
+                .checked_sub(assert_final_prev_outs[assert_final_vin].value)
+                .unwrap_or_else(|| panic!("{tx_name} tx pays out more than it spends"))
+        };
+        let assert_commit_fees = assert_commit_fee(
+            "assert_commit_1",
+            &assert_initial_outputs[topology::ASSERT_COMMIT_VOUT_BASE..commit_1_end],
+            1,
+        ) + assert_commit_fee(
+            "assert_commit_2",
+            &assert_initial_outputs[commit_1_end..commit_2_end],
+            2,
+        );
+
+        let challenge_funding = self.challenge_transaction.required_challenge_funding();
+        let challenge_fee = (self.challenge_transaction.prev_outs()[0].value + challenge_funding)
+            .checked_sub(self.challenge_transaction.tx().output[0].value)
+            .unwrap_or_else(|| panic!("challenge tx pays out more than it spends"));
+
+        DisputePathCost {
+            assert_initial_fee: fee("assert_initial", &self.assert_initial_transaction),
+            assert_commit_fees,
+            assert_final_fee: fee("assert_final", &self.assert_final_transaction),
+            take_2_fee: fee("take_2", &self.take_2_transaction),
+            take_2_payout: self
+                .take_2_transaction
+                .tx()
+                .output
+                .iter()
+                .map(|output| output.value)
+                .sum(),
+            challenge_funding,
+            challenge_fee,
+            disprove_fee: fee("disprove", &self.disprove_transaction),
         }
-        if !validate_transaction(
-            self.peg_out_confirm_transaction.tx(),
-            peg_out_graph.peg_out_confirm_transaction.tx(),
-        ) {
-            ret_val = false;
+    }
+
+    // `validate` only checks the graph against itself, this checks that it belongs
+    // to `peg_in_graph`, for graphs received separately from their peg-in graph.
+    pub fn validate_against_peg_in(
+        &self,
+        peg_in_graph: &PegInGraph,
+    ) -> Result<(), ValidationError> {
+        if self.peg_in_graph_id != *peg_in_graph.id() {
+            return Err(ValidationError::PegInGraphIdMismatch {
+                peg_in_graph_id: self.peg_in_graph_id.clone(),
+                expected: peg_in_graph.id().clone(),
+            });
         }
-        if !validate_transaction(
-            self.kick_off_1_transaction.tx(),
-            peg_out_graph.kick_off_1_transaction.tx(),
-        ) {
-            ret_val = false;
+
+        let peg_in_confirm_tx = peg_in_graph.peg_in_confirm_transaction_ref().tx();
+        let peg_in_confirm_txid = peg_in_confirm_tx.compute_txid();
+        if self.peg_in_confirm_txid != peg_in_confirm_txid {
+            return Err(ValidationError::PegInConfirmTxidMismatch {
+                peg_in_confirm_txid: self.peg_in_confirm_txid,
+                expected: peg_in_confirm_txid,
+            });
         }
-        if !validate_transaction(
-            self.kick_off_2_transaction.tx(),
-            peg_out_graph.kick_off_2_transaction.tx(),
-        ) {
-            ret_val = false;
+
+        let take_txs: [(&'static str, &dyn PreSignedTransaction, usize); 2] = [
+            ("take_1", &self.take_1_transaction, topology::TAKE_1_VOUT_0),
+            ("take_2", &self.take_2_transaction, topology::TAKE_2_VOUT_0),
+        ];
+        for (tx_name, take_tx, vout) in take_txs {
+            let outpoint = take_tx.tx().input[0].previous_output;
+            if outpoint != OutPoint::new(peg_in_confirm_txid, vout as u32)
+                || take_tx.prev_outs()[0] != peg_in_confirm_tx.output[vout]
+            {
+                return Err(ValidationError::TakeInputMismatch { tx_name, outpoint });
+            }
         }
-        if !validate_transaction(
-            self.kick_off_timeout_transaction.tx(),
-            peg_out_graph.kick_off_timeout_transaction.tx(),
-        ) {
-            ret_val = false;
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> bool {
+        self.validate_with_segments(&AssertSegments::new(&self.config.verifying_key))
+    }
+
+    // `assert_segments` must be those of the vk in the graph config.
+    fn validate_with_segments(&self, assert_segments: &AssertSegments) -> bool {
+        if let Err(tx_names) = self.structural_completeness() {
+            println!(
+                "Missing or orphaned txs on graph: {} txs: {}",
+                self.id,
+                tx_names.join(", ")
+            );
+            return false;
         }
-        if !validate_transaction(
-            self.start_time_transaction.tx(),
-            peg_out_graph.start_time_transaction.tx(),
-        ) {
-            ret_val = false;
+        if let Err(err) = self.validate_key_consistency() {
+            println!("Key mismatch on graph: {} error: {}", self.id, err);
+            return false;
         }
-        if !validate_transaction(
-            self.start_time_timeout_transaction.tx(),
-            peg_out_graph.start_time_timeout_transaction.tx(),
-        ) {
-            ret_val = false;
+        if let Err(err) = peg_out_confirm_output_amount(&self.peg_out_confirm_inputs()) {
+            println!(
+                "Invalid peg-out confirm amount on graph: {} error: {}",
+                self.id, err
+            );
+            return false;
         }
-        if !validate_transaction(
-            self.take_1_transaction.tx(),
-            peg_out_graph.take_1_transaction.tx(),
-        ) {
+
+        let mut ret_val = true;
+        let peg_out_graph = match self.new_for_validation_with_segments(assert_segments) {
+            Ok(peg_out_graph) => peg_out_graph,
+            Err(err) => {
+                println!("Cannot rebuild graph: {} error: {}", self.id, err);
+                return false;
+            }
+        };
+        if self.connector_c != peg_out_graph.connector_c {
+            println!(
+                "Connector c of graph: {} was not built from its verifying key",
+                self.id
+            );
             ret_val = false;
         }
-        if !validate_transaction(
-            self.take_2_transaction.tx(),
-            peg_out_graph.take_2_transaction.tx(),
-        ) {
-            ret_val = false;
+        for ((tx_name, tx), (_, comparison_tx)) in self
+            .all_named_txs()
+            .into_iter()
+            .zip(peg_out_graph.all_named_txs())
+        {
+            if !validate_transaction(tx.tx(), comparison_tx.tx()) {
+                if let Some(divergence) = fee_divergence(tx, comparison_tx) {
+                    println!(
+                        "Fee mismatch on {} tx of graph: {} {}",
+                        tx_name, self.id, divergence
+                    );
+                }
+                ret_val = false;
+            }
         }
 
         if !verify_public_nonces_for_tx(&self.assert_initial_transaction) {
@@ -2184,39 +6385,129 @@ impl PegOutGraph {
             ret_val = false;
         }
 
+        if let Err(errors) = self.dry_finalize_all() {
+            for (tx_name, err) in errors {
+                println!(
+                    "Cannot finalize {} tx on graph: {} error: {}",
+                    tx_name, self.id, err
+                );
+            }
+            ret_val = false;
+        }
+
         ret_val
     }
 
     pub fn merge(&mut self, source_peg_out_graph: &PegOutGraph) {
-        self.assert_initial_transaction
-            .merge(&source_peg_out_graph.assert_initial_transaction);
+        self.is_validated.0 = false;
 
-        self.assert_final_transaction
-            .merge(&source_peg_out_graph.assert_final_transaction);
+        // inputs and outputs added at broadcast time change the txids
+        self.rebuild_tx(|graph| {
+            graph
+                .assert_initial_transaction
+                .merge(&source_peg_out_graph.assert_initial_transaction);
 
-        self.challenge_transaction
-            .merge(&source_peg_out_graph.challenge_transaction);
+            graph
+                .assert_final_transaction
+                .merge(&source_peg_out_graph.assert_final_transaction);
+
+            graph
+                .challenge_transaction
+                .merge(&source_peg_out_graph.challenge_transaction);
+
+            graph
+                .disprove_chain_transaction
+                .merge(&source_peg_out_graph.disprove_chain_transaction);
+
+            graph
+                .disprove_transaction
+                .merge(&source_peg_out_graph.disprove_transaction);
+
+            graph
+                .kick_off_timeout_transaction
+                .merge(&source_peg_out_graph.kick_off_timeout_transaction);
+
+            graph
+                .start_time_transaction
+                .merge(&source_peg_out_graph.start_time_transaction);
 
-        self.disprove_chain_transaction
-            .merge(&source_peg_out_graph.disprove_chain_transaction);
+            graph
+                .start_time_timeout_transaction
+                .merge(&source_peg_out_graph.start_time_timeout_transaction);
 
-        self.disprove_transaction
-            .merge(&source_peg_out_graph.disprove_transaction);
+            graph
+                .take_1_transaction
+                .merge(&source_peg_out_graph.take_1_transaction);
+
+            graph
+                .take_2_transaction
+                .merge(&source_peg_out_graph.take_2_transaction);
+        });
+    }
+
+    // Aggregated nonces of the presigned inputs that verifiers submitted nonces for,
+    // for a coordinator to check the aggregation before anyone signs.
+    pub fn aggregated_nonces(&self) -> HashMap<Txid, HashMap<usize, AggNonce>> {
+        self.all_presigned_txs()
+            .map(|tx| {
+                (
+                    tx.tx().compute_txid(),
+                    tx.verifier_inputs()
+                        .into_iter()
+                        .filter_map(|input_index| {
+                            tx.aggregated_nonce(input_index)
+                                .map(|aggregated_nonce| (input_index, aggregated_nonce))
+                        })
+                        .collect::<HashMap<usize, AggNonce>>(),
+                )
+            })
+            .filter(|(_, aggregated_nonces)| !aggregated_nonces.is_empty())
+            .collect()
+    }
 
-        self.kick_off_timeout_transaction
-            .merge(&source_peg_out_graph.kick_off_timeout_transaction);
+    pub fn signature_bundle(&self, verifier_public_key: &PublicKey) -> SignatureBundle {
+        SignatureBundle {
+            graph_id: self.id.clone(),
+            per_tx: self
+                .all_presigned_txs()
+                .map(|tx| {
+                    (
+                        tx.tx().compute_txid(),
+                        PartialSigs::from_tx(tx, verifier_public_key),
+                    )
+                })
+                .filter(|(_, partial_sigs)| !partial_sigs.is_empty())
+                .collect(),
+        }
+    }
 
-        self.start_time_transaction
-            .merge(&source_peg_out_graph.start_time_transaction);
+    pub fn apply_bundle(&mut self, bundle: &SignatureBundle) -> Result<(), String> {
+        if bundle.graph_id != self.id {
+            return Err(format!(
+                "Signature bundle for graph {} does not match graph {}",
+                bundle.graph_id, self.id
+            ));
+        }
 
-        self.start_time_timeout_transaction
-            .merge(&source_peg_out_graph.start_time_timeout_transaction);
+        let txids: Vec<Txid> = self
+            .all_presigned_txs()
+            .map(|tx| tx.tx().compute_txid())
+            .collect();
+        if let Some(txid) = bundle.per_tx.keys().find(|txid| !txids.contains(txid)) {
+            return Err(format!(
+                "Signature bundle contains unknown tx {} for graph {}",
+                txid, self.id
+            ));
+        }
 
-        self.take_1_transaction
-            .merge(&source_peg_out_graph.take_1_transaction);
+        for tx in self.all_presigned_txs_mut() {
+            if let Some(partial_sigs) = bundle.per_tx.get(&tx.tx().compute_txid()) {
+                merge_musig2_partial_sigs(tx, partial_sigs);
+            }
+        }
+        self.is_validated.0 = false;
 
-        self.take_2_transaction
-            .merge(&source_peg_out_graph.take_2_transaction);
+        Ok(())
     }
 
     fn create_new_connectors(
@@ -2225,21 +6516,25 @@ impl PegOutGraph {
         operator_taproot_public_key: &XOnlyPublicKey,
         operator_public_key: &PublicKey,
         connector_1_commitment_public_keys: &HashMap<CommitmentMessageId, WinternitzPublicKey>,
+        superblock_policy: SuperblockPolicy,
         connector_2_commitment_public_keys: &HashMap<CommitmentMessageId, WinternitzPublicKey>,
         connector_6_commitment_public_keys: &HashMap<CommitmentMessageId, WinternitzPublicKey>,
-        connector_e1_commitment_public_keys: &Vec<
-            BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
-        >,
-        connector_e2_commitment_public_keys: &Vec<
-            BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
-        >,
+        groth16_key_source: GrothKeySource,
+        assert_segments: &AssertSegments,
     ) -> PegOutConnectors {
+        let (
+            connector_e1_commitment_public_keys,
+            connector_e2_commitment_public_keys,
+            connector_c_commitment_public_keys,
+        ) = derive_groth16_connector_keys(groth16_key_source);
+
         let connector_0 = Connector0::new(network, n_of_n_taproot_public_key);
         let connector_1 = Connector1::new(
             network,
             operator_taproot_public_key,
             n_of_n_taproot_public_key,
             connector_1_commitment_public_keys,
+            superblock_policy,
         );
         let connector_2 = Connector2::new(
             network,
@@ -2263,13 +6558,11 @@ impl PegOutGraph {
         let connector_b = ConnectorB::new(network, n_of_n_taproot_public_key);
 
         // connector c pks = connector e1 pks + connector e2 pks
-        let connector_c = ConnectorC::new(
+        let connector_c = ConnectorC::new_with_segments(
             network,
             operator_taproot_public_key,
-            &merge_to_connector_c_commits_public_key(
-                connector_e1_commitment_public_keys,
-                connector_e2_commitment_public_keys,
-            ),
+            &connector_c_commitment_public_keys,
+            assert_segments,
         );
         let connector_d = ConnectorD::new(network, n_of_n_taproot_public_key);
 
@@ -2311,17 +6604,20 @@ impl PegOutGraph {
     }
 
     fn all_presigned_txs(&self) -> impl Iterator<Item = &dyn PreSignedMusig2Transaction> {
-        let all_txs: Vec<&dyn PreSignedMusig2Transaction> = vec![
-            &self.assert_initial_transaction,
-            &self.assert_final_transaction,
-            &self.disprove_chain_transaction,
-            &self.disprove_transaction,
-            &self.kick_off_timeout_transaction,
-            &self.start_time_timeout_transaction,
-            &self.take_1_transaction,
-            &self.take_2_transaction,
-        ];
-        all_txs.into_iter()
+        self.all_named_presigned_txs().into_iter().map(|(_, tx)| tx)
+    }
+
+    fn all_named_presigned_txs(&self) -> Vec<(&'static str, &dyn PreSignedMusig2Transaction)> {
+        vec![
+            ("assert_initial", &self.assert_initial_transaction),
+            ("assert_final", &self.assert_final_transaction),
+            ("disprove_chain", &self.disprove_chain_transaction),
+            ("disprove", &self.disprove_transaction),
+            ("kick_off_timeout", &self.kick_off_timeout_transaction),
+            ("start_time_timeout", &self.start_time_timeout_transaction),
+            ("take_1", &self.take_1_transaction),
+            ("take_2", &self.take_2_transaction),
+        ]
     }
 
     fn all_presigned_txs_mut(
@@ -2340,6 +6636,175 @@ impl PegOutGraph {
         all_txs.into_iter()
     }
 
+    // Encrypts the secret nonces returned by `push_verifier_nonces` under a 32 byte
+    // key. The blob is bound to this graph and verifier.
+    pub fn export_secret_nonces(
+        &self,
+        context: &VerifierContext,
+        secret_nonces: &HashMap<Txid, HashMap<usize, SecNonce>>,
+        key: &[u8; 32],
+    ) -> EncryptedNonceBlob {
+        let (nonce, ciphertext) = seal(
+            key,
+            &self.id,
+            SealedNonces::Verifier(&context.verifier_public_key),
+            secret_nonces,
+        );
+
+        EncryptedNonceBlob {
+            graph_id: self.id.clone(),
+            verifier_public_key: context.verifier_public_key,
+            nonce,
+            ciphertext,
+        }
+    }
+
+    // Decrypts secret nonces exported with `export_secret_nonces`. A musig2 nonce
+    // must never sign twice, so the nonces are only returned if the public nonces
+    // in the graph are still the ones they were generated with, i.e. the nonce
+    // round has not been restarted, and the verifier has not signed with them yet.
+    pub fn import_secret_nonces(
+        &self,
+        context: &VerifierContext,
+        blob: &EncryptedNonceBlob,
+        key: &[u8; 32],
+    ) -> Result<HashMap<Txid, HashMap<usize, SecNonce>>, NonceBlobError> {
+        let verifier_public_key = context.verifier_public_key;
+        if blob.graph_id != self.id || blob.verifier_public_key != verifier_public_key {
+            return Err(NonceBlobError::GraphMismatch);
+        }
+
+        let secret_nonces: HashMap<Txid, HashMap<usize, SecNonce>> = open(
+            key,
+            &self.id,
+            SealedNonces::Verifier(&verifier_public_key),
+            &blob.nonce,
+            &blob.ciphertext,
+        )
+        .ok_or(NonceBlobError::Decryption)?;
+        self.check_secret_nonces(&verifier_public_key, &secret_nonces)?;
+
+        Ok(secret_nonces)
+    }
+
+    // Secret nonces are only usable while the graph holds the public nonces they were
+    // generated with and the verifier has not signed with them yet.
+    fn check_secret_nonces(
+        &self,
+        verifier_public_key: &PublicKey,
+        secret_nonces: &HashMap<Txid, HashMap<usize, SecNonce>>,
+    ) -> Result<(), NonceBlobError> {
+        for tx in self.all_presigned_txs() {
+            let txid = tx.tx().compute_txid();
+            if tx
+                .musig2_signatures()
+                .values()
+                .any(|signatures| signatures.contains_key(verifier_public_key))
+            {
+                return Err(NonceBlobError::AlreadySigned(txid));
+            }
+
+            let is_current_round = secret_nonces.get(&txid).is_some_and(|tx_secret_nonces| {
+                tx.verifier_inputs().iter().all(|input_index| {
+                    match (
+                        tx_secret_nonces.get(input_index),
+                        tx.musig2_nonces()
+                            .get(input_index)
+                            .and_then(|public_nonces| public_nonces.get(verifier_public_key)),
+                    ) {
+                        (Some(secret_nonce), Some(public_nonce)) => {
+                            secret_nonce.public_nonce() == *public_nonce
+                        }
+                        _ => false,
+                    }
+                })
+            });
+            if !is_current_round {
+                return Err(NonceBlobError::StaleNonces(txid));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Pushes the verifier's nonces like `push_verifier_nonces` and saves each secret
+    // nonce to `store` before returning. If they cannot all be saved, the graph is
+    // left with the nonces it held before, so no public nonce is ever shared without
+    // its secret nonce surviving a restart.
+    pub fn push_verifier_nonces_persisted(
+        &mut self,
+        context: &VerifierContext,
+        store: &dyn SecNonceStore,
+    ) -> Result<HashMap<Txid, HashMap<usize, SecNonce>>, SecNonceStoreError> {
+        let previous_nonces = self
+            .all_presigned_txs()
+            .map(|tx| {
+                (
+                    tx.musig2_nonces().clone(),
+                    tx.musig2_nonce_signatures().clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let secret_nonces = self.push_verifier_nonces(context);
+        let saved = secret_nonces
+            .iter()
+            .try_for_each(|(txid, tx_secret_nonces)| {
+                tx_secret_nonces
+                    .iter()
+                    .try_for_each(|(input_index, secret_nonce)| {
+                        store.save(&self.id, txid, *input_index, secret_nonce)
+                    })
+            });
+        if let Err(err) = saved {
+            for (tx, (nonces, nonce_signatures)) in
+                self.all_presigned_txs_mut().zip(previous_nonces)
+            {
+                *tx.musig2_nonces_mut() = nonces;
+                *tx.musig2_nonce_signatures_mut() = nonce_signatures;
+            }
+            return Err(err);
+        }
+
+        Ok(secret_nonces)
+    }
+
+    // Loads the secret nonces saved by `push_verifier_nonces_persisted` after a
+    // restart. They are taken out of `store`, so a second load fails, and only
+    // returned if they can still be signed with, see `import_secret_nonces`. None
+    // is taken out before all of them have been read and checked, so a missing or
+    // stale nonce leaves the others in the store.
+    pub fn load_verifier_nonces(
+        &self,
+        context: &VerifierContext,
+        store: &dyn SecNonceStore,
+    ) -> Result<HashMap<Txid, HashMap<usize, SecNonce>>, SecNonceStoreError> {
+        let mut secret_nonces = HashMap::new();
+        for tx in self.all_presigned_txs() {
+            let txid = tx.tx().compute_txid();
+            let tx_secret_nonces = tx
+                .verifier_inputs()
+                .into_iter()
+                .map(|input_index| Ok((input_index, store.peek(&self.id, &txid, input_index)?)))
+                .collect::<Result<HashMap<_, _>, SecNonceStoreError>>()?;
+            secret_nonces.insert(txid, tx_secret_nonces);
+        }
+
+        self.check_secret_nonces(&context.verifier_public_key, &secret_nonces)
+            .map_err(SecNonceStoreError::Blob)?;
+
+        secret_nonces
+            .iter()
+            .map(|(txid, tx_secret_nonces)| {
+                let loaded = tx_secret_nonces
+                    .keys()
+                    .map(|&index| Ok((index, store.load(&self.id, txid, index)?)))
+                    .collect::<Result<HashMap<_, _>, SecNonceStoreError>>()?;
+                Ok((*txid, loaded))
+            })
+            .collect()
+    }
+
     pub fn has_all_nonces_of(&self, context: &VerifierContext) -> bool {
         self.all_presigned_txs()
             .all(|x| x.has_nonces_for(context.verifier_public_key))
@@ -2356,12 +6821,364 @@ impl PegOutGraph {
         self.all_presigned_txs()
             .all(|x| x.has_all_signatures(verifier_pubkeys))
     }
+
+    // Verifiers of `verifier_pubkeys` yet to push their nonces, by pre-signed tx.
+    // Txs with all the nonces are left out, so the map is empty once all are in.
+    pub fn missing_nonces(&self, verifier_pubkeys: &[PublicKey]) -> HashMap<Txid, Vec<PublicKey>> {
+        self.missing_verifiers(verifier_pubkeys, |tx, pubkey| tx.has_nonces_for(pubkey))
+    }
+    pub fn missing_signatures(
+        &self,
+        verifier_pubkeys: &[PublicKey],
+    ) -> HashMap<Txid, Vec<PublicKey>> {
+        self.missing_verifiers(verifier_pubkeys, |tx, pubkey| tx.has_signatures_for(pubkey))
+    }
+
+    fn missing_verifiers(
+        &self,
+        verifier_pubkeys: &[PublicKey],
+        has_contributed: impl Fn(&dyn PreSignedMusig2Transaction, PublicKey) -> bool,
+    ) -> HashMap<Txid, Vec<PublicKey>> {
+        self.all_presigned_txs()
+            .filter_map(|tx| {
+                let missing: Vec<PublicKey> = verifier_pubkeys
+                    .iter()
+                    .filter(|pubkey| !has_contributed(tx, **pubkey))
+                    .copied()
+                    .collect();
+                (!missing.is_empty()).then(|| (tx.tx().compute_txid(), missing))
+            })
+            .collect()
+    }
+
+    // Aggregates the collected partial signatures of every pre-signed tx, so that
+    // signatures which are present but invalid are caught before they are needed.
+    // Graphs stored without the individual n-of-n keys cannot be checked, so every
+    // tx of them fails.
+    pub fn dry_finalize_all(&self) -> Result<(), Vec<(String, FinalizeError)>> {
+        if self.n_of_n_public_keys.is_empty() {
+            return Err(self
+                .all_named_presigned_txs()
+                .into_iter()
+                .map(|(tx_name, _)| (tx_name.to_string(), FinalizeError::MissingPublicKeys))
+                .collect());
+        }
+
+        let errors: Vec<(String, FinalizeError)> = self
+            .all_named_presigned_txs()
+            .into_iter()
+            .filter_map(|(tx_name, tx)| {
+                tx.dry_finalize(&self.n_of_n_public_keys)
+                    .err()
+                    .map(|err| (tx_name.to_string(), err))
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+// Whether `onchain_tx` is `tx` completed on broadcast. Inputs and outputs may be
+// added and the later outputs may pay elsewhere, the spent outputs, the first
+// output and the amounts are kept.
+fn completes_tx(onchain_tx: &Transaction, tx: &Transaction) -> bool {
+    onchain_tx.input.len() >= tx.input.len()
+        && onchain_tx
+            .input
+            .iter()
+            .zip(&tx.input)
+            .all(|(onchain_tx_in, tx_in)| onchain_tx_in.previous_output == tx_in.previous_output)
+        && onchain_tx.output.len() >= tx.output.len()
+        && onchain_tx.output.first() == tx.output.first()
+        && onchain_tx
+            .output
+            .iter()
+            .zip(&tx.output)
+            .all(|(onchain_output, output)| onchain_output.value == output.value)
+}
+
+// The vsize of the tx once all of its inputs carry a witness. Inputs already
+// finalized count with their witness, the others are estimated from the script
+// they spend: a signature for each signature check, the script and, for taproot,
+// its control block. Winternitz commitments are only known once the tx is signed
+// and are left out of the estimate.
+pub fn estimated_vsize(tx: &dyn PreSignedTransaction) -> u64 {
+    let mut weight = tx.tx().weight().to_wu();
+    if tx.tx().input.iter().all(|input| input.witness.is_empty()) {
+        // segwit marker and flag, and the item count of every witness
+        weight += 2 + tx.tx().input.len() as u64;
+    }
+
+    for ((input, prev_out), prev_script) in tx
+        .tx()
+        .input
+        .iter()
+        .zip(tx.prev_outs())
+        .zip(tx.prev_scripts())
+    {
+        if !input.witness.is_empty() {
+            continue;
+        }
+
+        let signature_count = prev_script
+            .instructions()
+            .filter(|instruction| {
+                matches!(instruction, Ok(Instruction::Op(op))
+                    if [OP_CHECKSIG, OP_CHECKSIGVERIFY, OP_CHECKSIGADD].contains(op))
+            })
+            .count();
+        let mut witness_size = signature_count * ESTIMATED_SIGNATURE_WITNESS_SIZE
+            + VarInt(prev_script.len() as u64).size()
+            + prev_script.len();
+        if prev_out.script_pubkey.is_p2tr() {
+            witness_size += ESTIMATED_CONTROL_BLOCK_WITNESS_SIZE;
+        }
+        weight += witness_size as u64;
+    }
+
+    weight.div_ceil(4)
 }
 
 pub fn generate_id(peg_in_graph: &PegInGraph, operator_public_key: &PublicKey) -> String {
+    generate_id_for_version(
+        GRAPH_VERSION,
+        &generate_id_preimage(peg_in_graph.id(), operator_public_key),
+    )
+}
+
+pub fn generate_id_preimage(peg_in_graph_id: &str, operator_public_key: &PublicKey) -> String {
+    peg_in_graph_id.to_string() + &operator_public_key.to_string()
+}
+
+// Ids of the versions in `UNTAGGED_ID_VERSIONS` are the plain SHA256 of the
+// preimage and must stay so, graphs are stored and looked up by them. Any later
+// version hashes with a tag of its own, so ids of different schemes never collide.
+pub fn generate_id_for_version(version: &str, preimage: &str) -> String {
     let mut hasher = Sha256::new();
 
-    hasher.update(peg_in_graph.id().to_string() + &operator_public_key.to_string());
+    if !UNTAGGED_ID_VERSIONS.contains(&version) {
+        let tag_hash = Sha256::digest(format!("{ID_HASH_TAG}/{version}"));
+        hasher.update(tag_hash);
+        hasher.update(tag_hash);
+    }
+    hasher.update(preimage);
 
     hasher.finalize().to_hex_string(Upper)
 }
+
+// Copy of the presigned tx with its output to `payout_script_pubkey` paying
+// `new_script_pubkey` instead, see `PegOutGraph::take_payout_override`.
+pub fn override_payout(
+    tx_name: &'static str,
+    presigned_tx: &dyn PreSignedMusig2Transaction,
+    payout_script_pubkey: &ScriptBuf,
+    new_script_pubkey: ScriptBuf,
+) -> Result<Transaction, PayoutOverrideError> {
+    let payout_index = presigned_tx
+        .tx()
+        .output
+        .iter()
+        .position(|output| output.script_pubkey == *payout_script_pubkey)
+        .ok_or(PayoutOverrideError::NoPayout(tx_name))?;
+
+    let verifier_inputs = presigned_tx.verifier_inputs();
+    if let Some(&input_index) = verifier_inputs.iter().find(|&&input_index| {
+        commits_to_output(
+            presigned_tx.sighash_type(input_index),
+            input_index,
+            payout_index,
+        )
+    }) {
+        return Err(PayoutOverrideError::Committed(PayoutCommitted {
+            tx_name,
+            input_index,
+        }));
+    }
+
+    let mut tx = presigned_tx.tx().clone();
+    tx.output[payout_index].script_pubkey = new_script_pubkey;
+    for (input_index, input) in tx.input.iter_mut().enumerate() {
+        if !verifier_inputs.contains(&input_index) {
+            input.witness.clear();
+        }
+    }
+
+    Ok(tx)
+}
+
+pub async fn aggregate_metrics(
+    peg_out_graphs: &[&PegOutGraph],
+    client: &AsyncClient,
+) -> FleetMetrics {
+    let blockchain_height = get_block_height(client).await;
+
+    // the txs of all graphs are fetched at once, then split up again per graph
+    let graph_txids: Vec<Vec<(&str, Txid)>> = peg_out_graphs
+        .iter()
+        .map(|peg_out_graph| peg_out_graph.named_status_txids())
+        .collect();
+    let txids: Vec<Txid> = graph_txids
+        .iter()
+        .flatten()
+        .map(|(_, txid)| *txid)
+        .collect();
+    let mut tx_statuses = get_tx_statuses(client, &txids).await.into_iter();
+
+    let graph_statuses: Vec<(&PegOutGraph, HashMap<&str, Result<TxStatus, Error>>)> =
+        peg_out_graphs
+            .iter()
+            .zip(graph_txids)
+            .map(|(peg_out_graph, txids)| {
+                let statuses = txids
+                    .into_iter()
+                    .map(|(tx_name, _)| (tx_name, tx_statuses.next().unwrap()))
+                    .collect();
+                (*peg_out_graph, statuses)
+            })
+            .collect();
+
+    interpret_aggregate_metrics(&graph_statuses, blockchain_height)
+}
+
+// `statuses` hold the txs of the graph keyed by their tx name, plus the peg-out tx
+// under `peg_out` once it is set. A tx without a status counts as not broadcast.
+pub fn interpret_aggregate_metrics(
+    graph_statuses: &[(&PegOutGraph, HashMap<&str, Result<TxStatus, Error>>)],
+    blockchain_height: u32,
+) -> FleetMetrics {
+    let mut metrics = FleetMetrics::default();
+    let not_broadcast = Ok(TxStatus {
+        confirmed: false,
+        block_height: None,
+        block_hash: None,
+        block_time: None,
+    });
+
+    for (peg_out_graph, statuses) in graph_statuses {
+        let status = |tx_name: &str| statuses.get(tx_name).unwrap_or(&not_broadcast);
+        let is_confirmed = |tx_name: &str| {
+            status(tx_name)
+                .as_ref()
+                .is_ok_and(|status| status.confirmed)
+        };
+
+        let phase = if peg_out_graph.n_of_n_presigned {
+            peg_out_graph.interpret_phase(
+                status("assert_initial"),
+                status("assert_final"),
+                status("challenge"),
+                status("disprove_chain"),
+                status("disprove"),
+                status("peg_out_confirm"),
+                status("kick_off_1"),
+                status("kick_off_2"),
+                status("kick_off_timeout"),
+                statuses.get("peg_out"),
+                status("start_time_timeout"),
+                status("start_time"),
+                status("take_1"),
+                status("take_2"),
+            )
+        } else {
+            GraphPhase::Setup
+        };
+        *metrics.phase_counts.entry(phase).or_default() += 1;
+
+        match phase {
+            GraphPhase::Setup => {}
+            GraphPhase::Funded | GraphPhase::InFlight | GraphPhase::Disputed => {
+                metrics.awaiting_reimbursement += peg_out_graph.take_payout(TakeKind::Take1);
+                if phase == GraphPhase::Disputed {
+                    metrics.dispute_costs += peg_out_graph.dispute_path_cost().operator_cost();
+                }
+
+                if let Some(deadline) = peg_out_graph.interpret_operator_deadlines(statuses).first()
+                {
+                    let blocks_left = deadline.height.saturating_sub(blockchain_height);
+                    let bucket = DEADLINE_HISTOGRAM_BUCKETS
+                        .into_iter()
+                        .find(|bucket| blocks_left <= *bucket)
+                        .unwrap_or(u32::MAX);
+                    *metrics.deadline_histogram.entry(bucket).or_default() += 1;
+                    if bucket == DEADLINE_HISTOGRAM_BUCKETS[0] {
+                        metrics.imminent_deadlines += 1;
+                    }
+                }
+            }
+            GraphPhase::Settled => {
+                if [
+                    "kick_off_timeout",
+                    "start_time_timeout",
+                    "disprove_chain",
+                    "disprove",
+                ]
+                .into_iter()
+                .any(is_confirmed)
+                {
+                    metrics.failed += 1;
+                }
+            }
+        }
+    }
+
+    metrics
+}
+
+pub async fn validate_graphs(
+    peg_in_graph: &PegInGraph,
+    peg_out_graphs: &[PegOutGraph],
+    client: &AsyncClient,
+) -> Vec<(String, ValidationReport)> {
+    let peg_in_confirm_txid = peg_in_graph.peg_in_confirm_transaction.tx().compute_txid();
+    let is_peg_in_confirmed = client
+        .get_tx_status(&peg_in_confirm_txid)
+        .await
+        .is_ok_and(|status| status.confirmed);
+
+    interpret_validate_graphs(peg_in_graph, peg_out_graphs, is_peg_in_confirmed)
+}
+
+// The peg-in graph and its confirm output are checked once for the whole batch,
+// each peg-out graph is then only checked against that shared baseline. The
+// chunker segments connector c is rebuilt from are generated once per vk.
+pub fn interpret_validate_graphs(
+    peg_in_graph: &PegInGraph,
+    peg_out_graphs: &[PegOutGraph],
+    is_peg_in_confirmed: bool,
+) -> Vec<(String, ValidationReport)> {
+    let is_peg_in_valid = peg_in_graph.validate();
+    let mut assert_segments: Vec<AssertSegments> = vec![];
+
+    peg_out_graphs
+        .iter()
+        .map(|peg_out_graph| {
+            let verifying_key = &peg_out_graph.config.verifying_key;
+            let index = match assert_segments
+                .iter()
+                .position(|segments| segments.vk() == verifying_key)
+            {
+                Some(index) => index,
+                None => {
+                    assert_segments.push(AssertSegments::new(verifying_key));
+                    assert_segments.len() - 1
+                }
+            };
+
+            let spends_peg_in = peg_out_graph.validate_against_peg_in(peg_in_graph).is_ok();
+
+            (
+                peg_out_graph.id.clone(),
+                ValidationReport {
+                    is_peg_in_confirmed,
+                    is_peg_in_valid,
+                    spends_peg_in,
+                    is_valid: peg_out_graph.validate_with_segments(&assert_segments[index]),
+                },
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use bitcoin::{BlockHash, Txid};
+use esplora_client::{AsyncClient, Error, TxStatus};
+use futures::future::join_all;
+use std::{
+    collections::{HashMap, HashSet},
+    slice,
+    time::{Duration, Instant},
+};
+
+pub const DEFAULT_STATUS_TTL: Duration = Duration::from_secs(10);
+
+// Where `StatusCache` gets the statuses it does not have from.
+#[async_trait]
+pub trait TxStatusSource: Sync {
+    async fn tx_status(&self, txid: &Txid) -> Result<TxStatus, Error>;
+
+    // hash of the block at `block_height` on the current best chain
+    async fn block_hash(&self, block_height: u32) -> Result<BlockHash, Error>;
+}
+
+#[async_trait]
+impl TxStatusSource for AsyncClient {
+    async fn tx_status(&self, txid: &Txid) -> Result<TxStatus, Error> {
+        self.get_tx_status(txid).await
+    }
+
+    async fn block_hash(&self, block_height: u32) -> Result<BlockHash, Error> {
+        self.get_block_hash(block_height).await
+    }
+}
+
+// Memoizes tx statuses across polls. A confirmed status is kept for as long as
+// the block it was confirmed in is on the best chain, which is rechecked once
+// per `ttl` for each block, so a reorg drops it. Anything else is kept for
+// `ttl`. Failed lookups are never cached, and a block that cannot be rechecked
+// counts as reorged out.
+pub struct StatusCache {
+    ttl: Duration,
+    confirmed: HashMap<Txid, TxStatus>,
+    unconfirmed: HashMap<Txid, (TxStatus, Instant)>,
+    // when each block that anchors a confirmed status was last seen on the best chain
+    anchors: HashMap<BlockHash, Instant>,
+}
+
+impl Default for StatusCache {
+    fn default() -> Self { Self::new(DEFAULT_STATUS_TTL) }
+}
+
+impl StatusCache {
+    pub fn new(ttl: Duration) -> Self {
+        StatusCache {
+            ttl,
+            confirmed: HashMap::new(),
+            unconfirmed: HashMap::new(),
+            anchors: HashMap::new(),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration { self.ttl }
+
+    // Returned in the order of `txids`. The anchor blocks to recheck and then the
+    // statuses missing from the cache are each looked up all at once.
+    pub async fn statuses(
+        &mut self,
+        source: &dyn TxStatusSource,
+        txids: &[Txid],
+    ) -> Vec<Result<TxStatus, Error>> {
+        self.recheck_anchors(source, txids).await;
+
+        let cached: Vec<Option<TxStatus>> = txids.iter().map(|txid| self.cached(txid)).collect();
+        let mut fetched = join_all(
+            txids
+                .iter()
+                .zip(&cached)
+                .filter(|(_, cached)| cached.is_none())
+                .map(|(txid, _)| source.tx_status(txid)),
+        )
+        .await
+        .into_iter();
+
+        txids
+            .iter()
+            .zip(cached)
+            .map(|(txid, cached)| match cached {
+                Some(status) => Ok(status),
+                None => {
+                    let status = fetched.next().unwrap();
+                    if let Ok(status) = &status {
+                        self.insert(*txid, status.clone());
+                    }
+                    status
+                }
+            })
+            .collect()
+    }
+
+    pub async fn status(
+        &mut self,
+        source: &dyn TxStatusSource,
+        txid: &Txid,
+    ) -> Result<TxStatus, Error> {
+        self.statuses(source, slice::from_ref(txid))
+            .await
+            .pop()
+            .unwrap()
+    }
+
+    pub fn invalidate(&mut self, txid: &Txid) {
+        self.confirmed.remove(txid);
+        self.unconfirmed.remove(txid);
+    }
+
+    fn insert(&mut self, txid: Txid, status: TxStatus) {
+        self.unconfirmed.remove(&txid);
+        match status.block_hash {
+            // without the block it cannot be told whether a reorg dropped it
+            Some(block_hash) if status.confirmed && status.block_height.is_some() => {
+                self.anchors.insert(block_hash, Instant::now());
+                self.confirmed.insert(txid, status);
+            }
+            _ => {
+                self.unconfirmed.insert(txid, (status, Instant::now()));
+            }
+        }
+    }
+
+    // Status of `txid` as long as it is still valid, given the anchors were rechecked.
+    fn cached(&self, txid: &Txid) -> Option<TxStatus> {
+        if let Some(status) = self.confirmed.get(txid) {
+            return Some(status.clone());
+        }
+        self.unconfirmed
+            .get(txid)
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < self.ttl)
+            .map(|(status, _)| status.clone())
+    }
+
+    // Rechecks the blocks anchoring the confirmed statuses of `txids` that were last
+    // seen on the best chain more than `ttl` ago, each block once.
+    async fn recheck_anchors(&mut self, source: &dyn TxStatusSource, txids: &[Txid]) {
+        let stale_anchors: Vec<(BlockHash, u32)> = txids
+            .iter()
+            .filter_map(|txid| self.confirmed.get(txid))
+            .filter_map(|status| Some((status.block_hash?, status.block_height?)))
+            .filter(|(block_hash, _)| {
+                !self
+                    .anchors
+                    .get(block_hash)
+                    .is_some_and(|checked_at| checked_at.elapsed() < self.ttl)
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let best_block_hashes = join_all(
+            stale_anchors
+                .iter()
+                .map(|(_, block_height)| source.block_hash(*block_height)),
+        )
+        .await;
+
+        for ((block_hash, _), best_block_hash) in stale_anchors.into_iter().zip(best_block_hashes) {
+            if best_block_hash.is_ok_and(|best_block_hash| best_block_hash == block_hash) {
+                self.anchors.insert(block_hash, Instant::now());
+            } else {
+                // reorged out, whatever was confirmed in that block has to be looked up again
+                self.anchors.remove(&block_hash);
+                self.confirmed
+                    .retain(|_, status| status.block_hash != Some(block_hash));
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Output indexes the peg-out graph txs spend, wired in `PegOutGraph::build_graph`
+// which both `PegOutGraph::new` and `PegOutGraph::new_for_validation` go through.
+// `<TX>_VOUT_<N>` is the output of the parent tx spent by input N of <TX>.
+
+pub const KICK_OFF_1_VOUT_0: usize = 0; // peg-out confirm, connector 6
+
+pub const START_TIME_VOUT_0: usize = 2; // kick-off 1, connector 2
+
+pub const START_TIME_TIMEOUT_VOUT_0: usize = 2; // kick-off 1, connector 2
+pub const START_TIME_TIMEOUT_VOUT_1: usize = 1; // kick-off 1, connector 1
+
+pub const KICK_OFF_2_VOUT_0: usize = 1; // kick-off 1, connector 1
+
+pub const KICK_OFF_TIMEOUT_VOUT_0: usize = 1; // kick-off 1, connector 1
+
+pub const CHALLENGE_VOUT_0: usize = 0; // kick-off 1, connector a
+
+pub const TAKE_1_VOUT_0: usize = 0; // peg-in confirm, connector 0
+pub const TAKE_1_VOUT_1: usize = 0; // kick-off 1, connector a
+pub const TAKE_1_VOUT_2: usize = 0; // kick-off 2, connector 3
+pub const TAKE_1_VOUT_3: usize = 1; // kick-off 2, connector b
+
+pub const ASSERT_INITIAL_VOUT_0: usize = 1; // kick-off 2, connector b
+
+// First output of assert initial spent by assert commit 1. Assert commit 2 spends
+// the outputs that follow the ones of assert commit 1.
+pub const ASSERT_COMMIT_VOUT_BASE: usize = 1; // assert initial, connectors e
+
+pub const ASSERT_FINAL_VOUT_0: usize = 0; // assert initial, connector d
+pub const ASSERT_FINAL_VOUT_1: usize = 0; // assert commit 1, connector f 1
+pub const ASSERT_FINAL_VOUT_2: usize = 0; // assert commit 2, connector f 2
+
+pub const TAKE_2_VOUT_0: usize = 0; // peg-in confirm, connector 0
+pub const TAKE_2_VOUT_1: usize = 0; // assert final, connector 4
+pub const TAKE_2_VOUT_2: usize = 1; // assert final, connector 5
+pub const TAKE_2_VOUT_3: usize = 2; // assert final, connector c
+
+pub const DISPROVE_VOUT_0: usize = 1; // assert final, connector 5
+pub const DISPROVE_VOUT_1: usize = 2; // assert final, connector c
+
+pub const DISPROVE_CHAIN_VOUT_0: usize = 1; // kick-off 2, connector b
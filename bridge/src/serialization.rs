@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 
 pub fn serialize(object: &impl Serialize) -> String { serde_json::to_string(object).unwrap() }
 
@@ -18,3 +19,78 @@ where
         Err(err) => Err(format!("Failed to parse json: {}", err)),
     }
 }
+
+// Compact binary counterpart of the JSON functions above, for transport and
+// storage. The version is written ahead of the object, so data of another
+// version is rejected before decoding the object itself.
+pub fn serialize_binary(version: &str, object: &impl Serialize) -> Vec<u8> {
+    bincode::serialize(&(version, object)).unwrap()
+}
+
+pub fn try_deserialize_binary<T>(version: &str, data: &[u8]) -> Result<T, String>
+where
+    T: DeserializeOwned,
+{
+    let mut reader = data;
+    let data_version: String = bincode::deserialize_from(&mut reader)
+        .map_err(|err| format!("Failed to parse binary data version: {}", err))?;
+    if data_version != version {
+        return Err(format!(
+            "Unsupported binary data version {}, expected {}",
+            data_version, version
+        ));
+    }
+
+    bincode::deserialize_from(&mut reader)
+        .map_err(|err| format!("Failed to parse binary data: {}", err))
+}
+
+// JSON map keys have to be strings, which keys like
+// `CommitmentMessageId::Groth16IntermediateValues` are not. Use with
+// `serialize_with`/`deserialize_with` to store such a map as a sequence of key
+// value pairs instead. Bincode encodes both forms the same way.
+pub fn serialize_map_as_pairs<'a, M, K, V, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+where
+    &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    K: Serialize + 'a,
+    V: Serialize + 'a,
+    S: Serializer,
+{
+    serializer.collect_seq(map)
+}
+
+pub fn deserialize_map_from_pairs<'de, M, K, V, D>(deserializer: D) -> Result<M, D::Error>
+where
+    M: FromIterator<(K, V)>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Ok(Vec::<(K, V)>::deserialize(deserializer)?
+        .into_iter()
+        .collect())
+}
+
+// Arkworks types like `VerifyingKey` only implement arkworks' own serialization.
+// Use with `serialize_with`/`deserialize_with` to store them as their compressed
+// bytes.
+pub fn serialize_canonical<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: CanonicalSerialize,
+    S: Serializer,
+{
+    let mut bytes = vec![];
+    value
+        .serialize_compressed(&mut bytes)
+        .map_err(serde::ser::Error::custom)?;
+    serializer.serialize_bytes(&bytes)
+}
+
+pub fn deserialize_canonical<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: CanonicalDeserialize,
+    D: Deserializer<'de>,
+{
+    let bytes = Vec::<u8>::deserialize(deserializer)?;
+    T::deserialize_compressed(&*bytes).map_err(serde::de::Error::custom)
+}
@@ -1,22 +1,382 @@
-use std::mem::size_of;
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    mem::size_of,
+    ops::RangeInclusive,
+    time::Instant,
+};
 
-use bitcoin::{block::Header, consensus::encode::serialize, hashes::Hash, BlockHash};
+use bitcoin::{
+    block::Header,
+    consensus::encode::{deserialize, serialize},
+    hashes::Hash,
+    pow::Work,
+    BlockHash, Network, Target,
+};
+use esplora_client::AsyncClient;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{NUM_BLOCKS_PER_2_WEEKS, NUM_BLOCKS_PER_DAY};
+
+// Chain dependent superblock settings, so that a chain with faster blocks than
+// mainnet can use a shorter measurement period.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct SuperblockParams {
+    pub start_time_block_number: u32,
+    pub window_blocks: u32, // length of the measurement period ∆C following the start time
+    pub min_work: Work,     // weight a block needs to qualify as a superblock
+}
+
+impl SuperblockParams {
+    pub fn for_network(network: Network) -> Self {
+        if network == Network::Bitcoin {
+            SuperblockParams {
+                start_time_block_number: 161249,
+                window_blocks: NUM_BLOCKS_PER_2_WEEKS,
+                min_work: Work::from_be_bytes([0; 32]),
+            }
+        } else {
+            SuperblockParams {
+                start_time_block_number: 0,
+                window_blocks: NUM_BLOCKS_PER_DAY,
+                min_work: Work::from_be_bytes([0; 32]),
+            }
+        }
+    }
+}
 
 /*
   TODO: Implement selecting a block that marks the start of a superblock measurement period
   that lasts for the period ∆C (e.g. 2000 blocks), during which the operator must observe
   all blocks on the main chain and identify the heaviest superblock SB.
 */
-pub fn get_start_time_block_number() -> u32 { 161249 }
+pub fn get_start_time_block_number(params: &SuperblockParams) -> u32 {
+    params.start_time_block_number
+}
+
+pub fn is_superblock_in_window(
+    params: &SuperblockParams,
+    start_time_block_number: u32,
+    superblock_height: u32,
+) -> bool {
+    superblock_height >= start_time_block_number
+        && superblock_height - start_time_block_number <= params.window_blocks
+}
 
-pub fn find_superblock() -> Header { todo!() }
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub enum SuperblockPolicy {
+    // commit to the heaviest superblock of the measurement period
+    #[default]
+    Single,
+    // commit to a chain of consecutive blocks, weighted by their aggregated weight
+    Chain(usize),
+}
+
+impl SuperblockPolicy {
+    pub fn chain(count: usize) -> Result<Self, SuperblockError> {
+        let policy = SuperblockPolicy::Chain(count);
+        policy.validate()?;
+
+        Ok(policy)
+    }
+
+    // A chain policy has to commit to at least one superblock.
+    pub fn validate(&self) -> Result<(), SuperblockError> {
+        match self.superblock_count() {
+            0 => Err(SuperblockError::NoSuperblocks),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn superblock_count(&self) -> usize {
+        match self {
+            SuperblockPolicy::Single => 1,
+            SuperblockPolicy::Chain(count) => *count,
+        }
+    }
+
+    pub fn message_length(&self) -> usize { self.superblock_count() * SUPERBLOCK_MESSAGE_LENGTH }
+}
+
+// What qualifies as a superblock and how long to look for one.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SuperblockCriteria {
+    pub min_weight: Work,
+    pub search_deadline: Option<Instant>, // candidates are not looked at past this
+    pub candidate_range: RangeInclusive<u32>, // block heights
+}
+
+impl SuperblockCriteria {
+    // Any block of the measurement period following the start time that meets the
+    // minimum work of `params` qualifies.
+    pub fn new(params: &SuperblockParams, start_time_block_number: u32) -> Self {
+        SuperblockCriteria {
+            min_weight: params.min_work,
+            search_deadline: None,
+            candidate_range: start_time_block_number
+                ..=start_time_block_number + params.window_blocks,
+        }
+    }
+
+    fn is_past_deadline(&self) -> bool {
+        self.search_deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct NoSuperblockFound;
+
+impl Display for NoSuperblockFound {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "No block meets the superblock criteria")
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum SuperblockError {
+    InsufficientWork,       // the header does not meet its own proof-of-work target
+    OutsideWindow,          // the header is not a block of the measurement window
+    NotHeaviest(BlockHash), // a block of the window is heavier
+    NotFound,               // no block of the range could be fetched
+    NoSuperblocks,          // the policy or the superblock set covers no superblock
+}
+
+impl Display for SuperblockError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            SuperblockError::InsufficientWork => {
+                write!(f, "Superblock does not meet its proof-of-work target")
+            }
+            SuperblockError::OutsideWindow => {
+                write!(f, "Superblock is outside the measurement window")
+            }
+            SuperblockError::NotHeaviest(block_hash) => {
+                write!(f, "Block {block_hash} is heavier than the superblock")
+            }
+            SuperblockError::NotFound => write!(f, "No block found in the superblock range"),
+            SuperblockError::NoSuperblocks => write!(f, "No superblock to commit to"),
+        }
+    }
+}
+
+// Checks a claimed superblock against the headers of the measurement window
+// following the committed start time. The window is taken as the observed best
+// chain, its headers are not validated again.
+pub fn validate_superblock(header: &Header, window: &[Header]) -> Result<(), SuperblockError> {
+    header
+        .validate_pow(header.target())
+        .map_err(|_| SuperblockError::InsufficientWork)?;
+
+    let block_hash = header.block_hash();
+    if !window
+        .iter()
+        .any(|window_header| window_header.block_hash() == block_hash)
+    {
+        return Err(SuperblockError::OutsideWindow);
+    }
+
+    let weight = get_superblock_weight(header);
+    match window
+        .iter()
+        .find(|window_header| get_superblock_weight(window_header) > weight)
+    {
+        Some(heavier) => Err(SuperblockError::NotHeaviest(heavier.block_hash())),
+        None => Ok(()),
+    }
+}
+
+// Headers of the blocks in the candidate range, by height. Stops at the chain tip
+// or at the search deadline, whichever comes first.
+pub async fn fetch_superblock_candidates(
+    client: &AsyncClient,
+    criteria: &SuperblockCriteria,
+) -> Vec<(u32, Header)> {
+    let mut candidates = vec![];
+    for height in criteria.candidate_range.clone() {
+        if criteria.is_past_deadline() {
+            break;
+        }
+        let Ok(block_hash) = client.get_block_hash(height).await else {
+            break;
+        };
+        match client.get_header_by_hash(&block_hash).await {
+            Ok(header) => candidates.push((height, header)),
+            Err(_) => break,
+        }
+    }
+
+    candidates
+}
+
+// The heaviest block between `start_height` and `end_height` inclusive. Like
+// `fetch_superblock_candidates`, the scan stops at the chain tip.
+pub async fn find_superblock_from_esplora(
+    client: &AsyncClient,
+    start_height: u32,
+    end_height: u32,
+) -> Result<Header, SuperblockError> {
+    let criteria = SuperblockCriteria {
+        min_weight: Work::from_be_bytes([0; 32]),
+        search_deadline: None,
+        candidate_range: start_height..=end_height,
+    };
+    let candidates = fetch_superblock_candidates(client, &criteria).await;
+
+    find_superblock(&candidates, &criteria).map_err(|_| SuperblockError::NotFound)
+}
+
+// The heaviest candidate within the range that meets the minimum weight. Ties go
+// to the lower block.
+pub fn find_superblock(
+    candidates: &[(u32, Header)],
+    criteria: &SuperblockCriteria,
+) -> Result<Header, NoSuperblockFound> {
+    let mut superblock: Option<(Work, Header)> = None;
+    for (height, header) in candidates {
+        if criteria.is_past_deadline() {
+            break;
+        }
+        if !criteria.candidate_range.contains(height) {
+            continue;
+        }
+
+        let weight = get_superblock_weight(header);
+        if weight >= criteria.min_weight
+            && superblock.is_none_or(|(heaviest_weight, _)| weight > heaviest_weight)
+        {
+            superblock = Some((weight, *header));
+        }
+    }
+
+    superblock
+        .map(|(_, header)| header)
+        .ok_or(NoSuperblockFound)
+}
+
+pub fn find_superblocks(
+    policy: SuperblockPolicy,
+    candidates: &[(u32, Header)],
+    criteria: &SuperblockCriteria,
+) -> Result<Vec<Header>, NoSuperblockFound> {
+    match policy {
+        SuperblockPolicy::Single => Ok(vec![find_superblock(candidates, criteria)?]),
+        SuperblockPolicy::Chain(count) => find_superblock_chain(candidates, criteria, count),
+    }
+}
+
+// The heaviest run of `count` consecutive candidates within the range, each
+// extending the previous one. Runs are compared by their aggregated weight, which
+// has to meet the minimum weight. Ties go to the lower run.
+fn find_superblock_chain(
+    candidates: &[(u32, Header)],
+    criteria: &SuperblockCriteria,
+    count: usize,
+) -> Result<Vec<Header>, NoSuperblockFound> {
+    if count == 0 {
+        return Err(NoSuperblockFound);
+    }
+
+    let mut in_range = vec![];
+    for (height, header) in candidates {
+        if criteria.is_past_deadline() {
+            break;
+        }
+        if criteria.candidate_range.contains(height) {
+            in_range.push((*height, *header));
+        }
+    }
+
+    let mut superblocks: Option<(Work, &[(u32, Header)])> = None;
+    for run in in_range.windows(count) {
+        let is_chain = run.windows(2).all(|pair| {
+            pair[1].0 == pair[0].0 + 1 && pair[1].1.prev_blockhash == pair[0].1.block_hash()
+        });
+        if !is_chain {
+            continue;
+        }
+
+        let weight = run
+            .iter()
+            .fold(Work::from_be_bytes([0; 32]), |total, (_, header)| {
+                total + get_superblock_weight(header)
+            });
+        if weight >= criteria.min_weight
+            && superblocks.is_none_or(|(heaviest_weight, _)| weight > heaviest_weight)
+        {
+            superblocks = Some((weight, run));
+        }
+    }
+
+    superblocks
+        .map(|(_, run)| run.iter().map(|(_, header)| *header).collect())
+        .ok_or(NoSuperblockFound)
+}
 
 pub fn get_superblock_message(sb: &Header) -> Vec<u8> { serialize(sb) }
 
+// For a single superblock this is identical to `get_superblock_message`.
+pub fn get_superblocks_message(sbs: &[Header]) -> Vec<u8> {
+    sbs.iter().flat_map(get_superblock_message).collect()
+}
+
 pub const SUPERBLOCK_MESSAGE_LENGTH: usize = size_of::<Header>();
 
+pub fn get_superblocks_from_message(message: &[u8]) -> Option<Vec<Header>> {
+    if message.is_empty() || message.len() % SUPERBLOCK_MESSAGE_LENGTH != 0 {
+        return None;
+    }
+
+    message
+        .chunks(SUPERBLOCK_MESSAGE_LENGTH)
+        .map(|chunk| deserialize(chunk).ok())
+        .collect()
+}
+
 pub fn get_superblock_hash_message(sb: &Header) -> Vec<u8> {
     sb.block_hash().as_byte_array().into()
 }
 
+// The hash commitment always covers the last header of the committed set.
+pub fn get_superblocks_hash_message(sbs: &[Header]) -> Result<Vec<u8>, SuperblockError> {
+    sbs.last()
+        .map(get_superblock_hash_message)
+        .ok_or(SuperblockError::NoSuperblocks)
+}
+
 pub const SUPERBLOCK_HASH_MESSAGE_LENGTH: usize = size_of::<BlockHash>();
+
+// The weight of a superblock is the work its hash would have proven as a target,
+// i.e. the lower the block hash, the heavier the superblock.
+pub fn get_superblock_weight(sb: &Header) -> Work {
+    Target::from_le_bytes(sb.block_hash().to_byte_array()).to_work()
+}
+
+pub fn get_superblocks_weight(sbs: &[Header]) -> Option<Work> {
+    sbs.iter()
+        .map(get_superblock_weight)
+        .reduce(|total, weight| total + weight)
+}
+
+pub fn is_superblock_set_valid(policy: SuperblockPolicy, sbs: &[Header]) -> bool {
+    sbs.len() == policy.superblock_count()
+        && sbs
+            .windows(2)
+            .all(|pair| pair[1].prev_blockhash == pair[0].block_hash())
+}
+
+// A claimed superblock set is disproved by a valid set of the same policy that is
+// strictly heavier in aggregate.
+pub fn is_superblock_claim_disproved(
+    policy: SuperblockPolicy,
+    claimed_sbs: &[Header],
+    disprove_sbs: &[Header],
+) -> bool {
+    if !is_superblock_set_valid(policy, disprove_sbs) {
+        return false;
+    }
+    if !is_superblock_set_valid(policy, claimed_sbs) {
+        return true;
+    }
+
+    get_superblocks_weight(disprove_sbs) > get_superblocks_weight(claimed_sbs)
+}
@@ -0,0 +1,94 @@
+use bitcoin::{
+    absolute, Address, Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
+    WitnessProgram, WitnessVersion,
+};
+
+use super::{
+    super::graphs::base::DUST_AMOUNT,
+    base::{transaction_fee, Input},
+    pre_signed::PreSignedTransaction,
+};
+
+// Pay-to-anchor outputs are spent by anyone with an empty witness. 240 sats is
+// their dust limit, so the anchor relays without the ephemeral dust policy.
+pub const ANCHOR_AMOUNT: Amount = Amount::from_sat(240);
+
+// Funding input witness and segwit marker of the child, assuming a P2WPKH funding input
+const FUNDING_WITNESS_VSIZE: u64 = 28;
+
+fn generate_anchor_script() -> ScriptBuf {
+    let program =
+        WitnessProgram::new(WitnessVersion::V1, &[0x4e, 0x73]).expect("Invalid anchor program");
+    ScriptBuf::new_witness_program(&program)
+}
+
+pub fn generate_anchor_output() -> TxOut {
+    TxOut {
+        value: ANCHOR_AMOUNT,
+        script_pubkey: generate_anchor_script(),
+    }
+}
+
+pub fn anchor_vout(tx: &Transaction) -> Option<usize> {
+    let anchor_script = generate_anchor_script();
+    tx.output
+        .iter()
+        .position(|output| output.script_pubkey == anchor_script)
+}
+
+// Builds the child spending the anchor of `parent` and `funding_input`, paying for
+// the package of both to reach `fee_rate` (sat/vB). The funding input is left for
+// its wallet to sign, the change above dust is paid to `change_address`.
+pub fn generate_cpfp_child(
+    parent: &dyn PreSignedTransaction,
+    funding_input: Input,
+    fee_rate: f64,
+    change_address: &Address,
+) -> Result<Transaction, String> {
+    let anchor_vout = anchor_vout(parent.tx()).ok_or("Transaction has no anchor output")?;
+    let parent_fee = transaction_fee(parent).ok_or("Transaction pays out more than it spends")?;
+
+    let tx_in = |previous_output| TxIn {
+        previous_output,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::default(),
+    };
+    let mut child = Transaction {
+        version: bitcoin::transaction::Version(2),
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![
+            tx_in(OutPoint::new(
+                parent.tx().compute_txid(),
+                anchor_vout as u32,
+            )),
+            tx_in(funding_input.outpoint),
+        ],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: change_address.script_pubkey(),
+        }],
+    };
+
+    let child_vsize = child.vsize() as u64 + FUNDING_WITNESS_VSIZE;
+    let package_vsize = parent.tx().vsize() as u64 + child_vsize;
+    let package_fee = Amount::from_sat((fee_rate * package_vsize as f64).ceil() as u64);
+    // the child pays at least the minimum relay fee on its own
+    let child_fee = package_fee
+        .checked_sub(parent_fee)
+        .unwrap_or(Amount::ZERO)
+        .max(Amount::from_sat(child_vsize));
+
+    let change = (ANCHOR_AMOUNT + funding_input.amount)
+        .checked_sub(child_fee)
+        .filter(|change| *change >= Amount::from_sat(DUST_AMOUNT))
+        .ok_or_else(|| {
+            format!(
+                "Funding input of {} too low to pay a fee of {child_fee}",
+                funding_input.amount
+            )
+        })?;
+    child.output[0].value = change;
+
+    Ok(child)
+}
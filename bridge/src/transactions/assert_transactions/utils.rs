@@ -96,7 +96,8 @@ pub fn sign_assert_tx_with_groth16_proof(
     let mut bridge_assigner = BridgeAssigner::new_operator(commitment_secrets);
 
     let segments =
-        groth16_verify_to_segments(&mut bridge_assigner, &proof.public, &proof.proof, &proof.vk);
+        groth16_verify_to_segments(&mut bridge_assigner, &proof.public, &proof.proof, &proof.vk)
+            .expect("Failed to build the assert segments for the proof");
 
     let mut elements = BTreeMap::new();
     for segment in segments {
@@ -146,19 +147,31 @@ pub fn groth16_commitment_secrets_to_public_keys(
     Vec<BTreeMap<CommitmentMessageId, WinternitzPublicKey>>,
     Vec<BTreeMap<CommitmentMessageId, WinternitzPublicKey>>,
 ) {
-    // hash map to btree map
-    let commitment_secrets: BTreeMap<CommitmentMessageId, WinternitzSecret> =
-        commitment_secrets.clone().into_iter().collect();
+    split_groth16_public_keys(
+        commitment_secrets
+            .iter()
+            .map(|(message_id, secret)| (message_id.clone(), WinternitzPublicKey::from(secret)))
+            .collect(),
+    )
+}
 
+// Distributes the Groth16 intermediate value keys over the E connectors of the two
+// assert-commit transactions, in message id order.
+fn split_groth16_public_keys(
+    commitment_public_keys: BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
+) -> (
+    Vec<BTreeMap<CommitmentMessageId, WinternitzPublicKey>>,
+    Vec<BTreeMap<CommitmentMessageId, WinternitzPublicKey>>,
+) {
     // see the unit test: assigner.rs/test_commitment_size
     let commitments_of_connector = 1;
     let connectors_e_of_transaction = 700;
     let mut connector_e1_commitment_public_keys = vec![];
     let mut connector_e2_commitment_public_keys = vec![];
 
-    for (message_id, secret) in commitment_secrets.iter() {
+    for (message_id, public_key) in commitment_public_keys.into_iter() {
         match message_id {
-            CommitmentMessageId::Groth16IntermediateValues((name, size)) => {
+            CommitmentMessageId::Groth16IntermediateValues(_) => {
                 let pushing_keys =
                     if connector_e1_commitment_public_keys.len() < connectors_e_of_transaction {
                         &mut connector_e1_commitment_public_keys
@@ -166,10 +179,7 @@ pub fn groth16_commitment_secrets_to_public_keys(
                         &mut connector_e2_commitment_public_keys
                     };
 
-                pushing_keys.push(BTreeMap::from([(
-                    message_id.clone(),
-                    WinternitzPublicKey::from(secret),
-                )]));
+                pushing_keys.push(BTreeMap::from([(message_id, public_key)]));
             }
             _ => {}
         }
@@ -200,6 +210,54 @@ pub fn merge_to_connector_c_commits_public_key(
     }
     connector_c_commitment_public_keys
 }
+
+/// Where the Groth16 commitment public keys of a peg-out graph come from.
+pub enum GrothKeySource<'a> {
+    /// The operator's commitment secrets, when a graph is built.
+    Secrets(&'a HashMap<CommitmentMessageId, WinternitzSecret>),
+    /// The keys stored in the E connectors of the two assert-commit transactions,
+    /// when a graph is rebuilt for validation.
+    PublicKeys(
+        &'a Vec<BTreeMap<CommitmentMessageId, WinternitzPublicKey>>,
+        &'a Vec<BTreeMap<CommitmentMessageId, WinternitzPublicKey>>,
+    ),
+}
+
+/// Derives the E connector keys of both assert-commit transactions and the merged
+/// connector C keys. Both sources go through the same split and merge, so a graph
+/// rebuilt from its stored keys commits to the same connector C as the original.
+pub fn derive_groth16_connector_keys(
+    source: GrothKeySource,
+) -> (
+    Vec<BTreeMap<CommitmentMessageId, WinternitzPublicKey>>,
+    Vec<BTreeMap<CommitmentMessageId, WinternitzPublicKey>>,
+    BTreeMap<CommitmentMessageId, WinternitzPublicKey>,
+) {
+    let (connector_e1_commitment_public_keys, connector_e2_commitment_public_keys) =
+        match source {
+            GrothKeySource::Secrets(commitment_secrets) => {
+                groth16_commitment_secrets_to_public_keys(commitment_secrets)
+            }
+            GrothKeySource::PublicKeys(
+                connector_e1_commitment_public_keys,
+                connector_e2_commitment_public_keys,
+            ) => split_groth16_public_keys(merge_to_connector_c_commits_public_key(
+                connector_e1_commitment_public_keys,
+                connector_e2_commitment_public_keys,
+            )),
+        };
+    let connector_c_commitment_public_keys = merge_to_connector_c_commits_public_key(
+        &connector_e1_commitment_public_keys,
+        &connector_e2_commitment_public_keys,
+    );
+
+    (
+        connector_e1_commitment_public_keys,
+        connector_e2_commitment_public_keys,
+        connector_c_commitment_public_keys,
+    )
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct AssertCommitConnectorsF {
     pub connector_f_1: ConnectorF1,
@@ -1,12 +1,15 @@
-use bitcoin::{Amount, OutPoint, PublicKey, Script, Transaction, Txid, XOnlyPublicKey};
-use core::cmp;
+use bitcoin::{Amount, OutPoint, PublicKey, Script, Sequence, Transaction, Txid, XOnlyPublicKey};
+use core::{
+    cmp,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
 use itertools::Itertools;
 use musig2::{secp256k1::schnorr::Signature, PubNonce};
 use std::collections::HashMap;
 
 use super::{
     pre_signed::PreSignedTransaction,
-    pre_signed_musig2::{verify_public_nonce, PreSignedMusig2Transaction},
+    pre_signed_musig2::{verify_public_nonce, PartialSigs, PreSignedMusig2Transaction},
 };
 
 pub struct Input {
@@ -20,6 +23,16 @@ pub struct InputWithScript<'a> {
     pub script: &'a Script,
 }
 
+// nSequence of an input that a single party funds and signs, as opposed to a
+// connector input, whose sequence is fixed by the graph.
+pub fn party_input_sequence(signal_rbf: bool) -> Sequence {
+    if signal_rbf {
+        Sequence::ENABLE_RBF_NO_LOCKTIME
+    } else {
+        Sequence::MAX
+    }
+}
+
 pub trait BaseTransaction {
     // fn initialize(&mut self, context: &dyn BaseContext);
 
@@ -79,6 +92,23 @@ pub fn merge_musig2_nonces_and_signatures(
     merge_hash_maps(signatures, source_transaction.musig2_signatures().clone());
 }
 
+pub fn merge_musig2_partial_sigs(
+    destination_transaction: &mut dyn PreSignedMusig2Transaction,
+    partial_sigs: &PartialSigs,
+) {
+    let nonces = destination_transaction.musig2_nonces_mut();
+    merge_hash_maps(nonces, partial_sigs.musig2_nonces.clone());
+
+    let nonce_signatures = destination_transaction.musig2_nonce_signatures_mut();
+    merge_hash_maps(
+        nonce_signatures,
+        partial_sigs.musig2_nonce_signatures.clone(),
+    );
+
+    let signatures = destination_transaction.musig2_signatures_mut();
+    merge_hash_maps(signatures, partial_sigs.musig2_signatures.clone());
+}
+
 // merge the nonce/signature hashmaps. We can't just do a.extend(b) since that would just overwrite the inner
 // hashmap rather than merging it
 fn merge_hash_maps<T: Clone>(
@@ -132,6 +162,66 @@ pub fn validate_transaction(
     true
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeDivergence {
+    pub expected_fee: Amount,
+    pub actual_fee: Amount,
+}
+
+impl Display for FeeDivergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "verifier expected fee {}, operator used {}",
+            self.expected_fee, self.actual_fee
+        )
+    }
+}
+
+// Some if both txs spend the same outputs into the same scripts and only their output amounts,
+// and therefore their fees, differ.
+pub fn fee_divergence(
+    transaction: &dyn PreSignedTransaction,
+    comparison_transaction: &dyn PreSignedTransaction,
+) -> Option<FeeDivergence> {
+    let tx = transaction.tx();
+    let comparison_tx = comparison_transaction.tx();
+    if tx.input.len() != comparison_tx.input.len()
+        || tx.output.len() != comparison_tx.output.len()
+        || transaction.prev_outs() != comparison_transaction.prev_outs()
+    {
+        return None;
+    }
+
+    let inputs_match = tx.input.iter().zip(&comparison_tx.input).all(|(a, b)| {
+        a.previous_output == b.previous_output
+            && a.script_sig == b.script_sig
+            && a.sequence == b.sequence
+    });
+    let scripts_match = tx
+        .output
+        .iter()
+        .zip(&comparison_tx.output)
+        .all(|(a, b)| a.script_pubkey == b.script_pubkey);
+    if !inputs_match || !scripts_match {
+        return None;
+    }
+
+    let expected_fee = transaction_fee(comparison_transaction)?;
+    let actual_fee = transaction_fee(transaction)?;
+    (expected_fee != actual_fee).then_some(FeeDivergence {
+        expected_fee,
+        actual_fee,
+    })
+}
+
+pub(crate) fn transaction_fee(transaction: &dyn PreSignedTransaction) -> Option<Amount> {
+    let input_amount: Amount = transaction.prev_outs().iter().map(|out| out.value).sum();
+    let output_amount: Amount = transaction.tx().output.iter().map(|out| out.value).sum();
+
+    input_amount.checked_sub(output_amount)
+}
+
 fn verify_public_nonces(
     all_nonces: &HashMap<usize, HashMap<PublicKey, PubNonce>>,
     all_sigs: &HashMap<usize, HashMap<PublicKey, Signature>>,
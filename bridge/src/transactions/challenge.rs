@@ -1,15 +1,18 @@
 use bitcoin::{
-    absolute, consensus, key::Keypair, Amount, Network, PublicKey, ScriptBuf, Sequence,
-    TapSighashType, Transaction, TxIn, TxOut, Witness,
+    absolute, consensus, key::Keypair, Amount, Network, PublicKey, ScriptBuf, TapSighashType,
+    Transaction, TxIn, TxOut, Witness,
 };
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
 
 use super::{
     super::{
         connectors::{base::*, connector_a::ConnectorA},
         contexts::operator::OperatorContext,
-        graphs::base::FEE_AMOUNT,
+        graphs::base::{DUST_AMOUNT, FEE_AMOUNT},
         scripts::*,
     },
     base::*,
@@ -17,6 +20,42 @@ use super::{
     signing::populate_p2wsh_witness,
 };
 
+// Bitcoin Core's default, in sat/vB. A replacement has to pay at least this much
+// on top of the fee of the tx it replaces (BIP125 rule 4).
+const INCREMENTAL_RELAY_FEE_RATE: u64 = 1;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ChallengeFundingError {
+    InsufficientChallengeFunding { have: Amount, need: Amount },
+    DustRefund { refund: Amount }, // refund output would be below the dust limit
+    FeeNotIncreased { min_fee: Amount, new_fee: Amount }, // below the BIP125 minimum
+}
+
+impl Display for ChallengeFundingError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ChallengeFundingError::InsufficientChallengeFunding { have, need } => {
+                write!(
+                    f,
+                    "Total input amount {have} too low, {need} required. Add additional input."
+                )
+            }
+            ChallengeFundingError::DustRefund { refund } => {
+                write!(
+                    f,
+                    "Refund of {refund} is below the dust limit. Adjust the inputs."
+                )
+            }
+            ChallengeFundingError::FeeNotIncreased { min_fee, new_fee } => {
+                write!(
+                    f,
+                    "New fee {new_fee} is below {min_fee}, the current fee plus the incremental relay fee"
+                )
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct ChallengeTransaction {
     #[serde(with = "consensus::serde::With::<consensus::serde::Hex>")]
@@ -108,58 +147,144 @@ impl ChallengeTransaction {
         );
     }
 
+    // The tx fee is already deducted from output 0, so the crowdfunding inputs
+    // only need to cover the crowdfunding amount itself.
+    pub fn required_challenge_funding(&self) -> Amount { self.input_amount_crowdfunding }
+
+    pub fn validate_challenge_funding(
+        &self,
+        inputs: &[InputWithScript],
+    ) -> Result<Option<Amount>, ChallengeFundingError> {
+        let total_input_amount: Amount = inputs.iter().map(|input| input.amount).sum();
+        let required_amount = self.required_challenge_funding();
+
+        match total_input_amount.cmp(&required_amount) {
+            Ordering::Less => Err(ChallengeFundingError::InsufficientChallengeFunding {
+                have: total_input_amount,
+                need: required_amount,
+            }),
+            Ordering::Greater => {
+                let refund = total_input_amount - required_amount;
+                if refund < Amount::from_sat(DUST_AMOUNT) {
+                    Err(ChallengeFundingError::DustRefund { refund })
+                } else {
+                    Ok(Some(refund))
+                }
+            }
+            Ordering::Equal => Ok(None),
+        }
+    }
+
     // allows for aggregating multiple inputs and one refund output
     pub fn add_inputs_and_output(
         &mut self,
         inputs: &Vec<InputWithScript>,
         keypair: &Keypair,
         output_script_pubkey: ScriptBuf,
-    ) {
+    ) -> Result<(), ChallengeFundingError> {
+        self.add_inputs_and_output_with_rbf(inputs, keypair, output_script_pubkey, false)
+    }
+
+    // With `signal_rbf` the crowdfunding inputs opt the tx into replace-by-fee, see
+    // `bump_fee`. The operator's input keeps the sequence it was signed with.
+    pub fn add_inputs_and_output_with_rbf(
+        &mut self,
+        inputs: &Vec<InputWithScript>,
+        keypair: &Keypair,
+        output_script_pubkey: ScriptBuf,
+        signal_rbf: bool,
+    ) -> Result<(), ChallengeFundingError> {
         if self.tx.input.len() > 1 {
             panic!("Cannot add any more inputs or outputs.");
         }
 
-        // check total input amount
-        let mut total_input_amount = Amount::from_sat(0);
-        for input in inputs {
-            total_input_amount += input.amount;
-        }
-        match total_input_amount.cmp(&self.input_amount_crowdfunding) {
-            Ordering::Less => panic!("Total input amount too low. Add additional input."),
-            Ordering::Greater => {
-                // add refund output
-                let _output = TxOut {
-                    value: total_input_amount - self.input_amount_crowdfunding,
-                    script_pubkey: output_script_pubkey,
-                };
-                self.tx.output.push(_output);
-            }
-            Ordering::Equal => {}
+        // check total input amount before signing any of the inputs
+        if let Some(refund) = self.validate_challenge_funding(inputs)? {
+            // add refund output
+            let _output = TxOut {
+                value: refund,
+                script_pubkey: output_script_pubkey,
+            };
+            self.tx.output.push(_output);
         }
 
         // add crowdfunding inputs
-        let sighash_type = bitcoin::EcdsaSighashType::AllPlusAnyoneCanPay;
-        let mut input_index = self.tx.input.len();
         for input in inputs {
             let _input = TxIn {
                 previous_output: input.outpoint,
                 script_sig: ScriptBuf::new(),
-                sequence: Sequence::MAX,
+                sequence: party_input_sequence(signal_rbf),
                 witness: Witness::default(),
             };
             self.tx.input.push(_input);
+        }
+        self.sign_crowdfunding_inputs(inputs, keypair);
 
-            // add witness
+        Ok(())
+    }
+
+    // Takes the fee increase to `fee_rate` sat/vB out of the refund output and signs
+    // the crowdfunding inputs again. The operator's signature only commits to output
+    // 0, so it stays valid. The replacement keeps the inputs and outputs, so its vsize
+    // is the one of the current tx.
+    pub fn bump_fee(
+        &mut self,
+        inputs: &Vec<InputWithScript>,
+        keypair: &Keypair,
+        fee_rate: f64,
+    ) -> Result<(), ChallengeFundingError> {
+        if self.tx.input.len() < 2 {
+            panic!("Missing input. Call add_inputs_and_output before bumping the fee");
+        }
+        if !inputs
+            .iter()
+            .map(|input| input.outpoint)
+            .eq(self.tx.input[1..].iter().map(|tx_in| tx_in.previous_output))
+        {
+            panic!("Crowdfunding inputs do not match the inputs of the challenge tx");
+        }
+
+        let input_amount =
+            self.prev_outs[0].value + inputs.iter().map(|input| input.amount).sum::<Amount>();
+        let output_amount: Amount = self.tx.output.iter().map(|output| output.value).sum();
+        let fee = input_amount - output_amount;
+        let vsize = self.tx.vsize() as u64;
+        let new_fee = Amount::from_sat((fee_rate * vsize as f64).ceil() as u64);
+        let min_fee = fee + Amount::from_sat(INCREMENTAL_RELAY_FEE_RATE * vsize);
+        if new_fee < min_fee {
+            return Err(ChallengeFundingError::FeeNotIncreased { min_fee, new_fee });
+        }
+
+        let refund = self
+            .tx
+            .output
+            .get(1)
+            .map_or(Amount::ZERO, |output| output.value);
+        let new_refund = refund.checked_sub(new_fee - fee).unwrap_or(Amount::ZERO);
+        if new_refund < Amount::from_sat(DUST_AMOUNT) {
+            return Err(ChallengeFundingError::DustRefund { refund: new_refund });
+        }
+        self.tx.output[1].value = new_refund;
+
+        for tx_in in &mut self.tx.input[1..] {
+            tx_in.witness.clear();
+        }
+        self.sign_crowdfunding_inputs(inputs, keypair);
+
+        Ok(())
+    }
+
+    fn sign_crowdfunding_inputs(&mut self, inputs: &[InputWithScript], keypair: &Keypair) {
+        let sighash_type = bitcoin::EcdsaSighashType::AllPlusAnyoneCanPay;
+        for (input_index, input) in (1..).zip(inputs) {
             populate_p2wsh_witness(
                 &mut self.tx,
                 input_index,
                 sighash_type,
                 input.script,
                 input.amount,
-                &vec![&keypair],
+                &vec![keypair],
             );
-
-            input_index += 1;
         }
     }
 
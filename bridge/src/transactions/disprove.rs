@@ -50,6 +50,10 @@ impl PreSignedTransaction for DisproveTransaction {
     fn prev_scripts(&self) -> &Vec<ScriptBuf> {
         &self.prev_scripts
     }
+
+    fn sighash_type(&self, _input_index: usize) -> TapSighashType {
+        TapSighashType::Single
+    }
 }
 
 impl PreSignedMusig2Transaction for DisproveTransaction {
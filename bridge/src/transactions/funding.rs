@@ -0,0 +1,81 @@
+use bitcoin::{
+    absolute, Address, Amount, OutPoint, Psbt, ScriptBuf, Sequence, Transaction, TxIn, TxOut,
+    Witness,
+};
+
+use super::super::graphs::base::{DUST_AMOUNT, FEE_AMOUNT};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WalletUtxo {
+    pub outpoint: OutPoint,
+    pub txout: TxOut,
+}
+
+// Builds an unsigned transaction paying `funding_outputs` as its leading outputs,
+// funded from `utxos` in the given order, with any change paid to `change`. Change
+// below dust is an error rather than a silent addition to the fee. The PSBT
+// carries the witness utxos needed by the wallet to sign.
+pub fn generate_funding_psbt(
+    funding_outputs: Vec<TxOut>,
+    utxos: &[WalletUtxo],
+    change: Address,
+) -> Result<Psbt, String> {
+    let required_amount = funding_outputs
+        .iter()
+        .map(|funding_output| funding_output.value)
+        .sum::<Amount>()
+        + Amount::from_sat(FEE_AMOUNT);
+
+    let mut selected_utxos: Vec<&WalletUtxo> = Vec::new();
+    let mut selected_amount = Amount::ZERO;
+    for utxo in utxos {
+        if selected_amount >= required_amount {
+            break;
+        }
+        selected_utxos.push(utxo);
+        selected_amount += utxo.txout.value;
+    }
+    if selected_amount < required_amount {
+        return Err(format!(
+            "Insufficient funds: {} available, {} required",
+            selected_amount, required_amount
+        ));
+    }
+
+    let mut output = funding_outputs;
+    let change_amount = selected_amount - required_amount;
+    if change_amount >= Amount::from_sat(DUST_AMOUNT) {
+        output.push(TxOut {
+            value: change_amount,
+            script_pubkey: change.script_pubkey(),
+        });
+    } else if change_amount > Amount::ZERO {
+        return Err(format!(
+            "Change of {} is below dust, select other utxos or pay it to a funding output",
+            change_amount
+        ));
+    }
+
+    let tx = Transaction {
+        version: bitcoin::transaction::Version(2),
+        lock_time: absolute::LockTime::ZERO,
+        input: selected_utxos
+            .iter()
+            .map(|utxo| TxIn {
+                previous_output: utxo.outpoint,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::MAX,
+                witness: Witness::default(),
+            })
+            .collect(),
+        output,
+    };
+
+    let mut psbt = Psbt::from_unsigned_tx(tx)
+        .map_err(|err| format!("Failed to create funding psbt: {err}"))?;
+    for (input, utxo) in psbt.inputs.iter_mut().zip(selected_utxos) {
+        input.witness_utxo = Some(utxo.txout.clone());
+    }
+
+    Ok(psbt)
+}
@@ -11,7 +11,10 @@ use super::{
             connector_a::ConnectorA,
         },
         contexts::operator::OperatorContext,
-        graphs::base::{DUST_AMOUNT, FEE_AMOUNT, MESSAGE_COMMITMENT_FEE_AMOUNT},
+        graphs::{
+            base::{DUST_AMOUNT, FEE_AMOUNT, MESSAGE_COMMITMENT_FEE_AMOUNT},
+            peg_out::CommitmentMessageId,
+        },
     },
     base::*,
     pre_signed::*,
@@ -153,13 +156,25 @@ impl KickOff1Transaction {
         connector_6: &Connector6,
         source_network_txid_inputs: &WinternitzSigningInputs,
         destination_network_txid_inputs: &WinternitzSigningInputs,
-    ) {
+    ) -> Result<(), WrongCommitmentSecret> {
+        verify_commitment_secret(
+            &connector_6.commitment_public_keys,
+            CommitmentMessageId::PegOutTxIdSourceNetwork,
+            source_network_txid_inputs.signing_key,
+        )?;
+        verify_commitment_secret(
+            &connector_6.commitment_public_keys,
+            CommitmentMessageId::PegOutTxIdDestinationNetwork,
+            destination_network_txid_inputs.signing_key,
+        )?;
+
         self.sign_input_0(
             context,
             connector_6,
             source_network_txid_inputs,
             destination_network_txid_inputs,
         );
+        Ok(())
     }
 }
 
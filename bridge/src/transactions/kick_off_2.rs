@@ -4,13 +4,15 @@ use bitcoin::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::connectors::base::{P2wshConnector, TaprootConnector};
+use crate::connectors::base::{
+    verify_commitment_secret, P2wshConnector, TaprootConnector, WrongCommitmentSecret,
+};
 
 use super::{
     super::{
         connectors::{connector_1::Connector1, connector_3::Connector3, connector_b::ConnectorB},
         contexts::operator::OperatorContext,
-        graphs::base::DUST_AMOUNT,
+        graphs::{base::DUST_AMOUNT, peg_out::CommitmentMessageId},
     },
     base::*,
     pre_signed::*,
@@ -131,13 +133,25 @@ impl KickOff2Transaction {
         connector_1: &Connector1,
         superblock_signing_inputs: &WinternitzSigningInputs,
         superblock_hash_signing_inputs: &WinternitzSigningInputs,
-    ) {
+    ) -> Result<(), WrongCommitmentSecret> {
+        verify_commitment_secret(
+            &connector_1.commitment_public_keys,
+            CommitmentMessageId::Superblock,
+            superblock_signing_inputs.signing_key,
+        )?;
+        verify_commitment_secret(
+            &connector_1.commitment_public_keys,
+            CommitmentMessageId::SuperblockHash,
+            superblock_hash_signing_inputs.signing_key,
+        )?;
+
         self.sign_input_0(
             context,
             connector_1,
             superblock_signing_inputs,
             superblock_hash_signing_inputs,
         );
+        Ok(())
     }
 }
 
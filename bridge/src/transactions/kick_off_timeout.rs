@@ -39,6 +39,8 @@ impl PreSignedTransaction for KickOffTimeoutTransaction {
     fn prev_outs(&self) -> &Vec<TxOut> { &self.prev_outs }
 
     fn prev_scripts(&self) -> &Vec<ScriptBuf> { &self.prev_scripts }
+
+    fn sighash_type(&self, _input_index: usize) -> TapSighashType { TapSighashType::Single }
 }
 
 impl PreSignedMusig2Transaction for KickOffTimeoutTransaction {
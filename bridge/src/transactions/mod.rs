@@ -1,9 +1,11 @@
+pub mod anchor;
 pub mod assert;
 pub mod assert_transactions;
 pub mod base;
 pub mod challenge;
 pub mod disprove;
 pub mod disprove_chain;
+pub mod funding;
 pub mod kick_off_1;
 pub mod kick_off_2;
 pub mod kick_off_timeout;
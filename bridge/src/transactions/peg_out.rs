@@ -1,10 +1,10 @@
 use bitcoin::{
-    absolute, consensus, Amount, EcdsaSighashType, Network, PublicKey, ScriptBuf, Sequence,
-    Transaction, TxIn, TxOut, Witness,
+    absolute, consensus, Amount, EcdsaSighashType, Network, PublicKey, ScriptBuf, Transaction,
+    TxIn, TxOut, Witness,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::client::chain::chain::PegOutEvent;
+use crate::client::chain::chain::{PegOutDestinationError, PegOutEvent};
 
 use super::{
     super::{contexts::operator::OperatorContext, graphs::base::FEE_AMOUNT, scripts::*},
@@ -32,17 +32,32 @@ impl PreSignedTransaction for PegOutTransaction {
 }
 
 impl PegOutTransaction {
-    pub fn new(context: &OperatorContext, peg_out_event: &PegOutEvent, input_0: Input) -> Self {
+    pub fn new(
+        context: &OperatorContext,
+        peg_out_event: &PegOutEvent,
+        input_0: Input,
+    ) -> Result<Self, PegOutDestinationError> {
+        Self::new_with_rbf(context, peg_out_event, input_0, false)
+    }
+
+    // With `signal_rbf` the operator can replace the tx while it is unconfirmed.
+    pub fn new_with_rbf(
+        context: &OperatorContext,
+        peg_out_event: &PegOutEvent,
+        input_0: Input,
+        signal_rbf: bool,
+    ) -> Result<Self, PegOutDestinationError> {
         let mut this = Self::new_for_validation(
             context.network,
             &context.operator_public_key,
             peg_out_event,
             input_0,
-        );
+        )?;
+        this.tx.input[0].sequence = party_input_sequence(signal_rbf);
 
         this.sign_input_0(context);
 
-        this
+        Ok(this)
     }
 
     pub fn new_for_validation(
@@ -50,11 +65,11 @@ impl PegOutTransaction {
         operator_public_key: &PublicKey,
         peg_out_event: &PegOutEvent,
         input_0: Input,
-    ) -> Self {
+    ) -> Result<Self, PegOutDestinationError> {
         let _input_0 = TxIn {
             previous_output: input_0.outpoint,
             script_sig: ScriptBuf::new(),
-            sequence: Sequence::MAX,
+            sequence: party_input_sequence(false),
             witness: Witness::default(),
         };
 
@@ -62,16 +77,10 @@ impl PegOutTransaction {
 
         let _output_0 = TxOut {
             value: total_output_amount,
-            script_pubkey: generate_pay_to_pubkey_hash_with_inscription_script_address(
-                network,
-                &peg_out_event.withdrawer_public_key_hash,
-                peg_out_event.timestamp,
-                &peg_out_event.withdrawer_chain_address,
-            )
-            .script_pubkey(),
+            script_pubkey: peg_out_event.payout_script_pubkey(network)?,
         };
 
-        PegOutTransaction {
+        Ok(PegOutTransaction {
             tx: Transaction {
                 version: bitcoin::transaction::Version(2),
                 lock_time: absolute::LockTime::ZERO,
@@ -84,7 +93,7 @@ impl PegOutTransaction {
                     .script_pubkey(),
             }],
             prev_scripts: vec![generate_pay_to_pubkey_script(operator_public_key)],
-        }
+        })
     }
 
     fn sign_input_0(&mut self, context: &OperatorContext) {
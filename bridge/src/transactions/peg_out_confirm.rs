@@ -3,6 +3,7 @@ use bitcoin::{
     TxOut,
 };
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
 
 use super::{
     super::{
@@ -15,6 +16,40 @@ use super::{
     pre_signed::*,
 };
 
+// Fee paid for every input besides the first, which is covered by FEE_AMOUNT.
+pub const ADDITIONAL_INPUT_FEE_AMOUNT: u64 = 2_000;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum PegOutConfirmAmountError {
+    InsufficientInputAmount { input_amount: Amount, fee: Amount },
+}
+
+impl Display for PegOutConfirmAmountError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            PegOutConfirmAmountError::InsufficientInputAmount { input_amount, fee } => write!(
+                f,
+                "Peg-out confirm inputs of {input_amount} do not cover the fee of {fee}"
+            ),
+        }
+    }
+}
+
+pub fn peg_out_confirm_fee(input_count: usize) -> Amount {
+    Amount::from_sat(
+        FEE_AMOUNT + input_count.saturating_sub(1) as u64 * ADDITIONAL_INPUT_FEE_AMOUNT,
+    )
+}
+
+// Amount left for the connector 6 output once the fee for all inputs is paid.
+pub fn peg_out_confirm_output_amount(inputs: &[Input]) -> Result<Amount, PegOutConfirmAmountError> {
+    let input_amount: Amount = inputs.iter().map(|input| input.amount).sum();
+    let fee = peg_out_confirm_fee(inputs.len());
+    input_amount
+        .checked_sub(fee)
+        .ok_or(PegOutConfirmAmountError::InsufficientInputAmount { input_amount, fee })
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct PegOutConfirmTransaction {
     #[serde(with = "consensus::serde::With::<consensus::serde::Hex>")]
@@ -35,52 +70,67 @@ impl PreSignedTransaction for PegOutConfirmTransaction {
 }
 
 impl PegOutConfirmTransaction {
-    pub fn new(context: &OperatorContext, connector_6: &Connector6, input_0: Input) -> Self {
+    pub fn new(
+        context: &OperatorContext,
+        connector_6: &Connector6,
+        inputs: Vec<Input>,
+    ) -> Result<Self, PegOutConfirmAmountError> {
         let mut this = Self::new_for_validation(
             context.network,
             &context.operator_public_key,
             connector_6,
-            input_0,
-        );
+            inputs,
+        )?;
 
-        this.sign_input_0(context);
+        for input_index in 0..this.tx.input.len() {
+            this.sign_input(context, input_index);
+        }
 
-        this
+        Ok(this)
     }
 
+    // All inputs are paid by the operator and merged into the single connector 6
+    // output, e.g. to fund a graph from several smaller UTXOs. There is no change,
+    // the fee grows with the number of inputs.
     pub fn new_for_validation(
         network: Network,
         operator_public_key: &PublicKey,
         connector_6: &Connector6,
-        input_0: Input,
-    ) -> Self {
-        let _input_0 = generate_default_tx_in(&input_0);
-
-        let total_output_amount = input_0.amount - Amount::from_sat(FEE_AMOUNT);
+        inputs: Vec<Input>,
+    ) -> Result<Self, PegOutConfirmAmountError> {
+        let total_output_amount = peg_out_confirm_output_amount(&inputs)?;
 
         let _output_0 = TxOut {
             value: total_output_amount,
             script_pubkey: connector_6.generate_taproot_address().script_pubkey(),
         };
 
-        PegOutConfirmTransaction {
+        Ok(PegOutConfirmTransaction {
             tx: Transaction {
                 version: bitcoin::transaction::Version(2),
                 lock_time: absolute::LockTime::ZERO,
-                input: vec![_input_0],
+                input: inputs.iter().map(generate_default_tx_in).collect(),
                 output: vec![_output_0],
             },
-            prev_outs: vec![TxOut {
-                value: input_0.amount,
-                script_pubkey: generate_pay_to_pubkey_script_address(network, operator_public_key)
+            prev_outs: inputs
+                .iter()
+                .map(|input| TxOut {
+                    value: input.amount,
+                    script_pubkey: generate_pay_to_pubkey_script_address(
+                        network,
+                        operator_public_key,
+                    )
                     .script_pubkey(),
-            }],
-            prev_scripts: vec![generate_pay_to_pubkey_script(operator_public_key)],
-        }
+                })
+                .collect(),
+            prev_scripts: inputs
+                .iter()
+                .map(|_| generate_pay_to_pubkey_script(operator_public_key))
+                .collect(),
+        })
     }
 
-    fn sign_input_0(&mut self, context: &OperatorContext) {
-        let input_index = 0;
+    fn sign_input(&mut self, context: &OperatorContext, input_index: usize) {
         pre_sign_p2wsh_input(
             self,
             input_index,
@@ -1,12 +1,13 @@
 use bitcoin::{
-    key::Keypair, taproot::TaprootSpendInfo, EcdsaSighashType, PublicKey, ScriptBuf,
+    hashes::Hash, key::Keypair, taproot::TaprootSpendInfo, EcdsaSighashType, PublicKey, ScriptBuf,
     TapSighashType, Transaction, TxOut,
 };
 
 use super::{
     super::contexts::base::BaseContext,
     signing::{
-        populate_p2wpkh_witness, populate_p2wsh_witness, populate_taproot_input_witness_default,
+        generate_taproot_leaf_sighash, populate_p2wpkh_witness, populate_p2wsh_witness,
+        populate_taproot_input_witness_default,
     },
 };
 
@@ -15,6 +16,29 @@ pub trait PreSignedTransaction {
     fn tx_mut(&mut self) -> &mut Transaction;
     fn prev_outs(&self) -> &Vec<TxOut>;
     fn prev_scripts(&self) -> &Vec<ScriptBuf>;
+    fn sighash_type(&self, _input_index: usize) -> TapSighashType { TapSighashType::All }
+    // The exact message each taproot input signs for the leaf in `prev_scripts`, so
+    // the graph's txs can be signed by an external signer (e.g. an HSM). Inputs
+    // spending segwit v0 outputs are signed with ECDSA and are not listed.
+    fn sighashes(&self) -> Vec<(usize, TapSighashType, [u8; 32])> {
+        (0..self.tx().input.len())
+            .filter(|&input_index| {
+                self.prev_outs()[input_index].script_pubkey.is_p2tr()
+                    && input_index < self.prev_scripts().len()
+            })
+            .map(|input_index| {
+                let sighash_type = self.sighash_type(input_index);
+                let sighash = generate_taproot_leaf_sighash(
+                    self.tx(),
+                    self.prev_outs(),
+                    input_index,
+                    sighash_type,
+                    &self.prev_scripts()[input_index],
+                );
+                (input_index, sighash_type, sighash.to_byte_array())
+            })
+            .collect()
+    }
 }
 
 pub fn pre_sign_p2wsh_input<T: PreSignedTransaction>(
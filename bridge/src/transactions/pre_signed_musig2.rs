@@ -6,17 +6,21 @@ use bitcoin::{
 use musig2::{
     secp::MaybeScalar,
     secp256k1::{schnorr::Signature, Message},
-    BinaryEncoding, PartialSignature, PubNonce, SecNonce,
+    AggNonce, BinaryEncoding, PartialSignature, PubNonce, SecNonce,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
 };
-use std::collections::HashMap;
 
 use super::{
     super::contexts::{base::BaseContext, verifier::VerifierContext},
     pre_signed::PreSignedTransaction,
     signing::push_taproot_leaf_script_and_control_block_to_witness,
     signing_musig2::{
-        generate_aggregated_nonce, generate_nonce, generate_taproot_aggregated_signature,
-        generate_taproot_partial_signature,
+        aggregate_taproot_partial_signatures, generate_aggregated_nonce, generate_nonce,
+        generate_taproot_aggregated_signature, generate_taproot_partial_signature,
     },
 };
 
@@ -31,6 +35,14 @@ pub trait PreSignedMusig2Transaction: PreSignedTransaction {
         &mut self,
     ) -> &mut HashMap<usize, HashMap<PublicKey, PartialSignature>>;
     fn verifier_inputs(&self) -> Vec<usize>;
+    // Sum of the public nonces submitted for the input so far, which is the
+    // aggregated nonce the input is signed with once all verifiers submitted theirs.
+    fn aggregated_nonce(&self, input_index: usize) -> Option<AggNonce> {
+        self.musig2_nonces()
+            .get(&input_index)
+            .filter(|nonces| !nonces.is_empty())
+            .map(|nonces| generate_aggregated_nonce(&nonces.values().cloned().collect()))
+    }
     fn has_nonces_for(&self, verifier_pubkey: PublicKey) -> bool {
         self.has_all_nonces(&[verifier_pubkey])
     }
@@ -53,6 +65,56 @@ pub trait PreSignedMusig2Transaction: PreSignedTransaction {
             })
         })
     }
+    // Aggregates the partial signatures of every fully signed verifier input without
+    // touching the tx, inputs still waiting for signatures are skipped.
+    fn dry_finalize(&self, n_of_n_public_keys: &[PublicKey]) -> Result<(), FinalizeError> {
+        for input_index in self.verifier_inputs() {
+            let Some(musig2_signatures) = self.musig2_signatures().get(&input_index) else {
+                continue;
+            };
+            if !n_of_n_public_keys
+                .iter()
+                .all(|pubkey| musig2_signatures.contains_key(pubkey))
+            {
+                continue;
+            }
+
+            let musig2_nonces: Vec<PubNonce> = match self.musig2_nonces().get(&input_index) {
+                Some(nonces)
+                    if n_of_n_public_keys
+                        .iter()
+                        .all(|pubkey| nonces.contains_key(pubkey)) =>
+                {
+                    n_of_n_public_keys
+                        .iter()
+                        .map(|pubkey| nonces[pubkey].clone())
+                        .collect()
+                }
+                _ => return Err(FinalizeError::MissingNonces(input_index)),
+            };
+            let partial_signatures: Vec<PartialSignature> = n_of_n_public_keys
+                .iter()
+                .map(|pubkey| musig2_signatures[pubkey])
+                .collect();
+
+            let signature = aggregate_taproot_partial_signatures(
+                n_of_n_public_keys,
+                self.tx(),
+                &generate_aggregated_nonce(&musig2_nonces),
+                input_index,
+                self.prev_outs(),
+                &self.prev_scripts()[input_index],
+                self.sighash_type(input_index),
+                partial_signatures,
+            );
+            if signature.is_err() {
+                return Err(FinalizeError::InvalidSignature(input_index));
+            }
+        }
+
+        Ok(())
+    }
+
     fn push_nonces(&mut self, context: &VerifierContext) -> HashMap<usize, SecNonce> {
         self.verifier_inputs()
             .iter()
@@ -92,6 +154,91 @@ pub trait PreSignedMusig2Transaction: PreSignedTransaction {
     }
 }
 
+// Whether a signature on the given input with the given sighash type commits to
+// the output at the given index, i.e. whether the output is fixed once signed.
+pub fn commits_to_output(
+    sighash_type: TapSighashType,
+    input_index: usize,
+    output_index: usize,
+) -> bool {
+    match sighash_type {
+        TapSighashType::Default | TapSighashType::All | TapSighashType::AllPlusAnyoneCanPay => true,
+        TapSighashType::Single | TapSighashType::SinglePlusAnyoneCanPay => {
+            input_index == output_index
+        }
+        TapSighashType::None | TapSighashType::NonePlusAnyoneCanPay => false,
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum FinalizeError {
+    MissingNonces(usize), // input index signed by all verifiers, but missing nonces
+    InvalidSignature(usize), // input index whose partial signatures fail to aggregate
+    MissingPublicKeys,    // the individual n-of-n keys to aggregate with are unknown
+}
+
+impl Display for FinalizeError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            FinalizeError::MissingNonces(input_index) => {
+                write!(f, "Missing musig2 nonces for input {input_index}")
+            }
+            FinalizeError::InvalidSignature(input_index) => write!(
+                f,
+                "Partial signatures for input {input_index} do not aggregate to a valid signature"
+            ),
+            FinalizeError::MissingPublicKeys => {
+                write!(f, "Missing individual n-of-n public keys to aggregate with")
+            }
+        }
+    }
+}
+
+// Nonces, nonce signatures and partial signatures a single verifier contributed
+// to a transaction, indexed by input like in `PreSignedMusig2Transaction`.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+pub struct PartialSigs {
+    pub musig2_nonces: HashMap<usize, HashMap<PublicKey, PubNonce>>,
+    pub musig2_nonce_signatures: HashMap<usize, HashMap<PublicKey, Signature>>,
+    pub musig2_signatures: HashMap<usize, HashMap<PublicKey, PartialSignature>>,
+}
+
+impl PartialSigs {
+    pub fn from_tx(tx: &dyn PreSignedMusig2Transaction, verifier_pubkey: &PublicKey) -> Self {
+        PartialSigs {
+            musig2_nonces: filter_by_pubkey(tx.musig2_nonces(), verifier_pubkey),
+            musig2_nonce_signatures: filter_by_pubkey(
+                tx.musig2_nonce_signatures(),
+                verifier_pubkey,
+            ),
+            musig2_signatures: filter_by_pubkey(tx.musig2_signatures(), verifier_pubkey),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.musig2_nonces.is_empty()
+            && self.musig2_nonce_signatures.is_empty()
+            && self.musig2_signatures.is_empty()
+    }
+}
+
+fn filter_by_pubkey<T: Clone>(
+    values: &HashMap<usize, HashMap<PublicKey, T>>,
+    verifier_pubkey: &PublicKey,
+) -> HashMap<usize, HashMap<PublicKey, T>> {
+    values
+        .iter()
+        .filter_map(|(input_index, values_by_pubkey)| {
+            values_by_pubkey.get(verifier_pubkey).map(|value| {
+                (
+                    *input_index,
+                    HashMap::from([(*verifier_pubkey, value.clone())]),
+                )
+            })
+        })
+        .collect()
+}
+
 pub fn get_nonce_message(nonce: &PubNonce) -> Message {
     let nonce_hash = sha256::Hash::hash(nonce.to_bytes().as_slice());
     Message::from_digest_slice(nonce_hash.as_ref()).expect("Failed to create nonce message")
@@ -122,13 +269,15 @@ pub fn pre_sign_musig2_taproot_input<T: PreSignedTransaction + PreSignedMusig2Tr
 
     let prev_outs = &tx.prev_outs().clone();
     let script = &tx.prev_scripts()[input_index].clone();
-    let musig2_nonces = &tx.musig2_nonces()[&input_index].values().cloned().collect();
+    let aggregated_nonce = tx
+        .aggregated_nonce(input_index)
+        .expect("No nonces to sign with");
 
     let partial_signature = generate_taproot_partial_signature(
         context,
         tx.tx_mut(),
         secret_nonce,
-        &generate_aggregated_nonce(musig2_nonces),
+        &aggregated_nonce,
         input_index,
         prev_outs,
         script,
@@ -159,8 +308,9 @@ pub fn finalize_musig2_taproot_input<T: PreSignedTransaction + PreSignedMusig2Tr
 
     let prev_outs = &tx.prev_outs().clone();
     let script = &tx.prev_scripts()[input_index].clone();
-    let musig2_nonces: &Vec<PubNonce> =
-        &tx.musig2_nonces()[&input_index].values().cloned().collect();
+    let aggregated_nonce = tx
+        .aggregated_nonce(input_index)
+        .expect("No nonces to finalize with");
     let musig2_signatures: Vec<MaybeScalar> = tx.musig2_signatures()[&input_index]
         .values()
         .map(|&partial_signature| PartialSignature::from(partial_signature))
@@ -171,7 +321,7 @@ pub fn finalize_musig2_taproot_input<T: PreSignedTransaction + PreSignedMusig2Tr
     let signature = generate_taproot_aggregated_signature(
         context,
         tx_mut,
-        &generate_aggregated_nonce(musig2_nonces),
+        &aggregated_nonce,
         input_index,
         prev_outs,
         script,
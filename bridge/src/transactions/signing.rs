@@ -3,8 +3,8 @@ use bitcoin::{
     secp256k1::Message,
     sighash::{Prevouts, SighashCache},
     taproot::{LeafVersion, TaprootSpendInfo},
-    Amount, EcdsaSighashType, PublicKey, Script, ScriptBuf, TapLeafHash, TapSighashType,
-    Transaction, TxOut,
+    Amount, EcdsaSighashType, PublicKey, Script, ScriptBuf, TapLeafHash, TapSighash,
+    TapSighashType, Transaction, TxOut, XOnlyPublicKey,
 };
 use secp256k1::SECP256K1;
 
@@ -161,17 +161,18 @@ pub fn populate_p2wpkh_witness(
     push_p2wpkh_public_key_to_witness(tx, input_index, public_key);
 }
 
-pub fn generate_taproot_leaf_schnorr_signature(
-    tx: &mut Transaction,
+// Script path sighash of a taproot input, shared by the single key and the MuSig2
+// signers. Inputs signed with ANYONECANPAY only commit to their own prevout.
+pub fn generate_taproot_leaf_sighash(
+    tx: &Transaction,
     prev_outs: &[TxOut],
     input_index: usize,
     sighash_type: TapSighashType,
     script: &Script,
-    keypair: &Keypair,
-) -> bitcoin::taproot::Signature {
+) -> TapSighash {
     let leaf_hash = TapLeafHash::from_script(script, LeafVersion::TapScript);
 
-    let sighash = if sighash_type == TapSighashType::AllPlusAnyoneCanPay
+    if sighash_type == TapSighashType::AllPlusAnyoneCanPay
         || sighash_type == TapSighashType::SinglePlusAnyoneCanPay
         || sighash_type == TapSighashType::NonePlusAnyoneCanPay
     {
@@ -192,7 +193,18 @@ pub fn generate_taproot_leaf_schnorr_signature(
                 sighash_type,
             )
             .expect("Failed to construct sighash")
-    };
+    }
+}
+
+pub fn generate_taproot_leaf_schnorr_signature(
+    tx: &mut Transaction,
+    prev_outs: &[TxOut],
+    input_index: usize,
+    sighash_type: TapSighashType,
+    script: &Script,
+    keypair: &Keypair,
+) -> bitcoin::taproot::Signature {
+    let sighash = generate_taproot_leaf_sighash(tx, prev_outs, input_index, sighash_type, script);
 
     // If secp256k1 is updated to 0.30.0, the following line can be replaced with
     // let signature = keypair.sign_schnorr_no_aux_rand(&Message::from(sighash));
@@ -341,6 +353,42 @@ fn generate_p2tr_key_spend_schnorr_signature(
     }
 }
 
+// Checks the key spend of a taproot input against the output key of the output
+// it spends, the way a node would before accepting the tx.
+pub fn verify_p2tr_key_spend_witness(
+    tx: &Transaction,
+    input_index: usize,
+    prev_outs: &[TxOut],
+) -> bool {
+    let witness = &tx.input[input_index].witness;
+    let script_pubkey = &prev_outs[input_index].script_pubkey;
+    if witness.len() != 1 || !script_pubkey.is_p2tr() {
+        return false;
+    }
+    let (Ok(signature), Ok(output_key)) = (
+        bitcoin::taproot::Signature::from_slice(&witness[0]),
+        XOnlyPublicKey::from_slice(&script_pubkey.as_bytes()[2..]),
+    ) else {
+        return false;
+    };
+
+    let prevouts = match signature.sighash_type {
+        TapSighashType::AllPlusAnyoneCanPay
+        | TapSighashType::SinglePlusAnyoneCanPay
+        | TapSighashType::NonePlusAnyoneCanPay => {
+            Prevouts::One(input_index, &prev_outs[input_index])
+        }
+        _ => Prevouts::All(prev_outs),
+    };
+    SighashCache::new(tx)
+        .taproot_key_spend_signature_hash(input_index, &prevouts, signature.sighash_type)
+        .is_ok_and(|sighash| {
+            SECP256K1
+                .verify_schnorr(&signature.signature, &Message::from(sighash), &output_key)
+                .is_ok()
+        })
+}
+
 pub fn populate_p2tr_key_spend_witness(
     tx: &mut Transaction,
     input_index: usize,
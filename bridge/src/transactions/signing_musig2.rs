@@ -1,9 +1,5 @@
 #![allow(clippy::too_many_arguments)]
-use bitcoin::{
-    sighash::{Prevouts, SighashCache},
-    taproot::LeafVersion,
-    Script, TapLeafHash, TapSighashType, Transaction, TxOut,
-};
+use bitcoin::{PublicKey, Script, TapSighashType, Transaction, TxOut};
 use musig2::{
     aggregate_partial_signatures,
     errors::{SigningError, VerifyError},
@@ -11,7 +7,10 @@ use musig2::{
     sign_partial, AggNonce, KeyAggContext, LiftedSignature, PartialSignature, PubNonce, SecNonce,
 };
 
-use super::super::contexts::{base::BaseContext, verifier::VerifierContext};
+use super::{
+    super::contexts::{base::BaseContext, verifier::VerifierContext},
+    signing::generate_taproot_leaf_sighash,
+};
 
 pub fn generate_nonce() -> SecNonce { SecNonce::build(&mut rand::rngs::OsRng).build() }
 
@@ -35,15 +34,7 @@ pub fn generate_taproot_partial_signature(
     ); // TODO: The tests will reveal whether this conversion works as expected.
     let key_agg_ctx = KeyAggContext::new(pubkeys).unwrap();
 
-    let leaf_hash = TapLeafHash::from_script(script, LeafVersion::TapScript);
-    let sighash = SighashCache::new(tx)
-        .taproot_script_spend_signature_hash(
-            input_index,
-            &Prevouts::All(prevouts), // TODO: add Prevouts::One
-            leaf_hash,
-            sighash_type,
-        )
-        .expect("Failed to construct sighash");
+    let sighash = generate_taproot_leaf_sighash(tx, prevouts, input_index, sighash_type, script);
 
     sign_partial(
         &key_agg_ctx,
@@ -63,24 +54,39 @@ pub fn generate_taproot_aggregated_signature(
     script: &Script,
     sighash_type: TapSighashType,
     partial_signatures: Vec<PartialSignature>,
+) -> Result<LiftedSignature, VerifyError> {
+    aggregate_taproot_partial_signatures(
+        context.n_of_n_public_keys(),
+        tx,
+        aggregated_nonce,
+        input_index,
+        prevouts,
+        script,
+        sighash_type,
+        partial_signatures,
+    )
+}
+
+// Fails if the aggregated signature does not verify against the n-of-n key.
+pub fn aggregate_taproot_partial_signatures(
+    n_of_n_public_keys: &[PublicKey],
+    tx: &Transaction,
+    aggregated_nonce: &AggNonce,
+    input_index: usize,
+    prevouts: &[TxOut],
+    script: &Script,
+    sighash_type: TapSighashType,
+    partial_signatures: Vec<PartialSignature>,
 ) -> Result<LiftedSignature, VerifyError> {
     let pubkeys: Vec<Point> = Vec::from_iter(
-        context
-            .n_of_n_public_keys()
+        n_of_n_public_keys
             .iter()
             .map(|&public_key| public_key.inner.into()),
     );
     let key_agg_ctx = KeyAggContext::new(pubkeys).unwrap();
 
-    let leaf_hash = TapLeafHash::from_script(script, LeafVersion::TapScript);
-    let sighash_cache = SighashCache::new(tx)
-        .taproot_script_spend_signature_hash(
-            input_index,
-            &Prevouts::All(prevouts), // TODO: add Prevouts::One
-            leaf_hash,
-            sighash_type,
-        )
-        .expect("Failed to construct sighash");
+    let sighash_cache =
+        generate_taproot_leaf_sighash(tx, prevouts, input_index, sighash_type, script);
 
     aggregate_partial_signatures(
         &key_agg_ctx,
@@ -5,10 +5,17 @@ use musig2::{secp256k1::schnorr::Signature, PartialSignature, PubNonce};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::connectors::{base::TaprootConnector, connector_2::Connector2};
+use crate::connectors::{
+    base::{verify_commitment_secret, TaprootConnector, WrongCommitmentSecret},
+    connector_2::Connector2,
+};
 
 use super::{
-    super::{contexts::operator::OperatorContext, graphs::base::FEE_AMOUNT, scripts::*},
+    super::{
+        contexts::operator::OperatorContext,
+        graphs::{base::FEE_AMOUNT, peg_out::CommitmentMessageId},
+        scripts::*,
+    },
     base::*,
     pre_signed::*,
     pre_signed_musig2::*,
@@ -152,7 +159,13 @@ impl StartTimeTransaction {
         connector_2: &Connector2,
         start_time_block_number: u32,
         start_time_commitment_secret: &WinternitzSecret,
-    ) {
+    ) -> Result<(), WrongCommitmentSecret> {
+        verify_commitment_secret(
+            &connector_2.commitment_public_keys,
+            CommitmentMessageId::StartTime,
+            start_time_commitment_secret,
+        )?;
+
         self.tx_mut().lock_time = absolute::LockTime::from_height(start_time_block_number)
             .expect("Failed to set lock time from block.");
         self.sign_input_0(
@@ -163,6 +176,7 @@ impl StartTimeTransaction {
                 signing_key: start_time_commitment_secret,
             },
         );
+        Ok(())
     }
 
     pub fn merge(&mut self, burn: &StartTimeTransaction) {
@@ -39,6 +39,13 @@ impl PreSignedTransaction for StartTimeTimeoutTransaction {
     fn prev_outs(&self) -> &Vec<TxOut> { &self.prev_outs }
 
     fn prev_scripts(&self) -> &Vec<ScriptBuf> { &self.prev_scripts }
+
+    fn sighash_type(&self, input_index: usize) -> TapSighashType {
+        match input_index {
+            1 => TapSighashType::None,
+            _ => TapSighashType::Single,
+        }
+    }
 }
 
 impl PreSignedMusig2Transaction for StartTimeTimeoutTransaction {
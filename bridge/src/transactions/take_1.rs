@@ -1,6 +1,6 @@
 use bitcoin::{
-    absolute, consensus, Amount, EcdsaSighashType, Network, PublicKey, ScriptBuf, TapSighashType,
-    Transaction, TxOut,
+    absolute, consensus, Address, Amount, EcdsaSighashType, Network, PublicKey, ScriptBuf,
+    TapSighashType, Transaction, TxOut,
 };
 use musig2::{secp256k1::schnorr::Signature, PartialSignature, PubNonce, SecNonce};
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,7 @@ use super::{
         graphs::base::FEE_AMOUNT,
         scripts::*,
     },
+    anchor::{generate_anchor_output, generate_cpfp_child, ANCHOR_AMOUNT},
     base::*,
     pre_signed::*,
     pre_signed_musig2::*,
@@ -80,6 +81,7 @@ impl Take1Transaction {
         input_1: Input,
         input_2: Input,
         input_3: Input,
+        with_anchor: bool,
     ) -> Self {
         let mut this = Self::new_for_validation(
             context.network,
@@ -92,6 +94,7 @@ impl Take1Transaction {
             input_1,
             input_2,
             input_3,
+            with_anchor,
         );
 
         this.sign_input_1(context, connector_a);
@@ -112,6 +115,7 @@ impl Take1Transaction {
         input_1: Input,
         input_2: Input,
         input_3: Input,
+        with_anchor: bool,
     ) -> Self {
         let input_0_leaf = 0;
         let _input_0 = connector_0.generate_taproot_leaf_tx_in(input_0_leaf, &input_0);
@@ -124,21 +128,30 @@ impl Take1Transaction {
         let input_3_leaf = 0;
         let _input_3 = connector_b.generate_taproot_leaf_tx_in(input_3_leaf, &input_3);
 
-        let total_output_amount = input_0.amount + input_1.amount + input_2.amount + input_3.amount
-            - Amount::from_sat(FEE_AMOUNT);
+        let mut total_output_amount =
+            input_0.amount + input_1.amount + input_2.amount + input_3.amount
+                - Amount::from_sat(FEE_AMOUNT);
+        if with_anchor {
+            total_output_amount -= ANCHOR_AMOUNT;
+        }
 
         let _output_0 = TxOut {
             value: total_output_amount,
             script_pubkey: generate_pay_to_pubkey_script_address(network, operator_public_key)
                 .script_pubkey(),
         };
+        let mut outputs = vec![_output_0];
+        if with_anchor {
+            // presigned like the other outputs, so that all verifiers agree on it
+            outputs.push(generate_anchor_output());
+        }
 
         Take1Transaction {
             tx: Transaction {
                 version: bitcoin::transaction::Version(2),
                 lock_time: absolute::LockTime::ZERO,
                 input: vec![_input_0, _input_1, _input_2, _input_3],
-                output: vec![_output_0],
+                output: outputs,
             },
             prev_outs: vec![
                 TxOut {
@@ -269,6 +282,16 @@ impl Take1Transaction {
         self.sign_input_3(context, connector_b, &secret_nonces[&input_index]);
     }
 
+    // Child paying for this tx through its anchor output, see `generate_cpfp_child`.
+    pub fn build_cpfp_child(
+        &self,
+        funding_input: Input,
+        fee_rate: f64,
+        change_address: &Address,
+    ) -> Result<Transaction, String> {
+        generate_cpfp_child(self, funding_input, fee_rate, change_address)
+    }
+
     pub fn merge(&mut self, take_1: &Take1Transaction) {
         merge_transactions(&mut self.tx, &take_1.tx);
         merge_musig2_nonces_and_signatures(self, take_1);
@@ -41,15 +41,17 @@ async fn test_merge_add_new_input_and_output() {
     .script_pubkey();
     let input_script =
         generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key);
-    source_challenge_tx.add_inputs_and_output(
-        &vec![InputWithScript {
-            outpoint,
-            amount: amount * 2,
-            script: &input_script,
-        }],
-        &config.depositor_context.depositor_keypair,
-        refund_script.clone(),
-    );
+    source_challenge_tx
+        .add_inputs_and_output(
+            &vec![InputWithScript {
+                outpoint,
+                amount: amount * 2,
+                script: &input_script,
+            }],
+            &config.depositor_context.depositor_keypair,
+            refund_script.clone(),
+        )
+        .unwrap();
 
     let input_length_before = destination_challenge_tx.tx().input.len();
     let output_length_before = destination_challenge_tx.tx().output.len();
@@ -0,0 +1,147 @@
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+use bitcoin::{OutPoint, Txid};
+use bridge::{
+    graphs::{
+        base::is_timelock_elapsed,
+        dag::TxRole,
+        peg_out::{
+            ActionError, CommitmentMessageId, KickOff2Error, PegOutOperatorStatus, WaitReason,
+            DEFAULT_MIN_CONFIRMATIONS,
+        },
+    },
+    superblock::{get_start_time_block_number, NoSuperblockFound, SuperblockCriteria},
+};
+use esplora_client::TxStatus;
+
+use crate::bridge::{
+    mock::esplora::MockEsplora,
+    peg_out_graph::utils::{set_stub_peg_out, setup_and_create_stub_graphs},
+};
+
+const KICK_OFF_1_HEIGHT: u32 = 100;
+
+#[test]
+fn test_timelock_elapsed_depends_only_on_given_height() {
+    let num_blocks_timelock = 10;
+    let tx_status = Ok(TxStatus {
+        confirmed: true,
+        block_height: Some(100),
+        block_hash: None,
+        block_time: None,
+    });
+
+    let stale_height = 109;
+    let fresh_height = 110;
+
+    // status and action see the same branch as long as they share the height
+    assert!(!is_timelock_elapsed(
+        &tx_status,
+        num_blocks_timelock,
+        stale_height
+    ));
+    assert!(is_timelock_elapsed(
+        &tx_status,
+        num_blocks_timelock,
+        fresh_height
+    ));
+}
+
+#[test]
+fn test_timelock_not_elapsed_for_unconfirmed_tx() {
+    let tx_status = Ok(TxStatus {
+        confirmed: false,
+        block_height: None,
+        block_hash: None,
+        block_time: None,
+    });
+
+    assert!(!is_timelock_elapsed(&tx_status, 0, u32::MAX));
+}
+
+#[tokio::test]
+async fn test_operator_status_and_action_agree_at_timelock_boundary() {
+    let (config, _, mut peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    let txids = peg_out_graph.txids();
+    let esplora = MockEsplora::start_with_confirmations(
+        1,
+        Duration::ZERO,
+        HashMap::from([
+            (peg_out_graph.peg_out_txid().unwrap(), KICK_OFF_1_HEIGHT - 2),
+            (txids[&TxRole::PegOutConfirm], KICK_OFF_1_HEIGHT - 1),
+            (txids[&TxRole::KickOff1], KICK_OFF_1_HEIGHT),
+            (txids[&TxRole::StartTime], KICK_OFF_1_HEIGHT + 1),
+        ]),
+    )
+    .await;
+    let client = esplora.client();
+    let superblock_params = peg_out_graph.superblock_params();
+    let superblock_criteria = SuperblockCriteria::new(
+        &superblock_params,
+        get_start_time_block_number(&superblock_params),
+    );
+
+    let fresh_height = KICK_OFF_1_HEIGHT + config.connector_1.num_blocks_timelock_leaf_0;
+    let stale_height = fresh_height - 1;
+
+    // one block short of the kick-off 1 timelock, both hold back kick-off 2
+    assert_eq!(
+        peg_out_graph
+            .operator_status(&client, Some(stale_height), DEFAULT_MIN_CONFIRMATIONS)
+            .await,
+        PegOutOperatorStatus::PegOutWait(WaitReason::TimelockPending {
+            remaining_blocks: 1
+        })
+    );
+    assert_eq!(
+        peg_out_graph
+            .kick_off_2(
+                &client,
+                Some(stale_height),
+                &config.operator_context,
+                &commitment_secrets[&CommitmentMessageId::Superblock],
+                &commitment_secrets[&CommitmentMessageId::SuperblockHash],
+                &superblock_criteria,
+            )
+            .await,
+        Err(KickOff2Error::Action(ActionError::NotReady(
+            "Kick-off 1 timelock has not elapsed"
+        )))
+    );
+
+    // at the boundary both go ahead, the mock has no blocks to find a superblock in
+    assert_eq!(
+        peg_out_graph
+            .operator_status(&client, Some(fresh_height), DEFAULT_MIN_CONFIRMATIONS)
+            .await,
+        PegOutOperatorStatus::PegOutKickOff2Available
+    );
+    assert_eq!(
+        peg_out_graph
+            .kick_off_2(
+                &client,
+                Some(fresh_height),
+                &config.operator_context,
+                &commitment_secrets[&CommitmentMessageId::Superblock],
+                &commitment_secrets[&CommitmentMessageId::SuperblockHash],
+                &superblock_criteria,
+            )
+            .await,
+        Err(KickOff2Error::NoSuperblockFound(NoSuperblockFound))
+    );
+}
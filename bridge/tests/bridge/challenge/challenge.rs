@@ -66,22 +66,24 @@ async fn test_challenge_tx() {
         input_amount_crowdfunding_total,
     );
 
-    challenge_tx.add_inputs_and_output(
-        &vec![
-            InputWithScript {
-                outpoint: crowdfunding_outpoints[0],
-                amount: amount_1,
-                script: &generate_pay_to_pubkey_script(crowdfunding_public_key),
-            },
-            InputWithScript {
-                outpoint: crowdfunding_outpoints[1],
-                amount: amount_1,
-                script: &generate_pay_to_pubkey_script(crowdfunding_public_key),
-            },
-        ],
-        crowdfunding_keypair,
-        refund_address.script_pubkey(),
-    );
+    challenge_tx
+        .add_inputs_and_output(
+            &vec![
+                InputWithScript {
+                    outpoint: crowdfunding_outpoints[0],
+                    amount: amount_1,
+                    script: &generate_pay_to_pubkey_script(crowdfunding_public_key),
+                },
+                InputWithScript {
+                    outpoint: crowdfunding_outpoints[1],
+                    amount: amount_1,
+                    script: &generate_pay_to_pubkey_script(crowdfunding_public_key),
+                },
+            ],
+            crowdfunding_keypair,
+            refund_address.script_pubkey(),
+        )
+        .unwrap();
 
     let tx = challenge_tx.finalize();
     println!("Script Path Spend Transaction: {:?}\n", tx);
@@ -109,3 +111,87 @@ async fn test_challenge_tx() {
         amount_1 * 2 - input_amount_crowdfunding_total
     );
 }
+
+#[tokio::test]
+async fn test_challenge_tx_fee_replacement() {
+    let config = setup_test().await;
+
+    let crowdfunding_keypair = &config.depositor_context.depositor_keypair;
+    let crowdfunding_public_key = &config.depositor_context.depositor_public_key;
+
+    let faucet = Faucet::new(FaucetType::EsploraRegtest);
+    let amount_0 = Amount::from_sat(DUST_AMOUNT);
+    let connector_a_address = config.connector_a.generate_taproot_address();
+    let input_amount_crowdfunding_total = Amount::from_sat(INITIAL_AMOUNT);
+    let amount_1 = Amount::from_sat(INITIAL_AMOUNT * 2);
+    let crowdfunding_address = generate_pay_to_pubkey_script_address(
+        config.depositor_context.network,
+        crowdfunding_public_key,
+    );
+    faucet
+        .fund_inputs(
+            &config.client_0,
+            &vec![
+                (&connector_a_address, amount_0),
+                (&crowdfunding_address, amount_1),
+            ],
+        )
+        .await
+        .wait()
+        .await;
+
+    let outpoint_0 = generate_stub_outpoint(&config.client_0, &connector_a_address, amount_0).await;
+    let outpoint_1 =
+        generate_stub_outpoint(&config.client_0, &crowdfunding_address, amount_1).await;
+
+    let mut challenge_tx = ChallengeTransaction::new(
+        &config.operator_context,
+        &config.connector_a,
+        Input {
+            outpoint: outpoint_0,
+            amount: amount_0,
+        },
+        input_amount_crowdfunding_total,
+    );
+    let crowdfunding_script = generate_pay_to_pubkey_script(crowdfunding_public_key);
+    let crowdfunding_inputs = vec![InputWithScript {
+        outpoint: outpoint_1,
+        amount: amount_1,
+        script: &crowdfunding_script,
+    }];
+    challenge_tx
+        .add_inputs_and_output_with_rbf(
+            &crowdfunding_inputs,
+            crowdfunding_keypair,
+            crowdfunding_address.script_pubkey(),
+            true,
+        )
+        .unwrap();
+
+    let tx = challenge_tx.finalize();
+    assert!(config.client_0.esplora.broadcast(&tx).await.is_ok());
+
+    challenge_tx
+        .bump_fee(&crowdfunding_inputs, crowdfunding_keypair, 100.0)
+        .unwrap();
+    let replacement_tx = challenge_tx.finalize();
+    let result = config.client_0.esplora.broadcast(&replacement_tx).await;
+    println!("Broadcast result: {:?}\n", result);
+    assert!(result.is_ok());
+
+    // the replacement evicted the original from the mempool
+    assert!(config
+        .client_0
+        .esplora
+        .get_tx(&tx.compute_txid())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(config
+        .client_0
+        .esplora
+        .get_tx(&replacement_tx.compute_txid())
+        .await
+        .unwrap()
+        .is_some());
+}
@@ -0,0 +1,270 @@
+use std::str::FromStr;
+
+use bitcoin::{Amount, OutPoint, Sequence, Txid};
+
+use bridge::{
+    graphs::{
+        base::{DUST_AMOUNT, INITIAL_AMOUNT},
+        peg_out::{ChallengeError, GuardedActionError},
+    },
+    scripts::{generate_pay_to_pubkey_script, generate_pay_to_pubkey_script_address},
+    transactions::{
+        base::{Input, InputWithScript},
+        challenge::{ChallengeFundingError, ChallengeTransaction},
+        pre_signed::PreSignedTransaction,
+    },
+};
+
+use crate::bridge::{
+    peg_out_graph::utils::setup_and_create_stub_graphs,
+    setup::{setup_test, SetupConfig},
+};
+
+fn create_stub_challenge_tx(config: &SetupConfig) -> ChallengeTransaction {
+    ChallengeTransaction::new(
+        &config.operator_context,
+        &config.connector_a,
+        Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "0e6719ac074b0e3cac76d057643506faa1c266b322aa9cf4c6f635fe63b14327",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            amount: Amount::from_sat(DUST_AMOUNT),
+        },
+        Amount::from_sat(INITIAL_AMOUNT),
+    )
+}
+
+#[tokio::test]
+async fn test_challenge_funding_insufficient() {
+    let config = setup_test().await;
+    let mut challenge_tx = create_stub_challenge_tx(&config);
+    let input_script =
+        generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key);
+    let refund_script = generate_pay_to_pubkey_script_address(
+        config.depositor_context.network,
+        &config.depositor_context.depositor_public_key,
+    )
+    .script_pubkey();
+
+    let need = challenge_tx.required_challenge_funding();
+    assert_eq!(need, Amount::from_sat(INITIAL_AMOUNT));
+
+    let have = need - Amount::from_sat(1);
+    let inputs = vec![InputWithScript {
+        outpoint: OutPoint {
+            txid: Txid::from_str(
+                "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+        amount: have,
+        script: &input_script,
+    }];
+
+    assert_eq!(
+        challenge_tx.add_inputs_and_output(
+            &inputs,
+            &config.depositor_context.depositor_keypair,
+            refund_script
+        ),
+        Err(ChallengeFundingError::InsufficientChallengeFunding { have, need })
+    );
+    // nothing was added to the tx
+    assert_eq!(challenge_tx.tx().input.len(), 1);
+    assert_eq!(challenge_tx.tx().output.len(), 1);
+}
+
+#[tokio::test]
+async fn test_graph_challenge_funding_insufficient() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let input_script =
+        generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key);
+    let refund_script = generate_pay_to_pubkey_script_address(
+        config.depositor_context.network,
+        &config.depositor_context.depositor_public_key,
+    )
+    .script_pubkey();
+
+    let need = peg_out_graph.required_challenge_funding();
+    let have = need - Amount::from_sat(1);
+    let inputs = vec![InputWithScript {
+        outpoint: OutPoint {
+            txid: Txid::from_str(
+                "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+        amount: have,
+        script: &input_script,
+    }];
+
+    // fails before any request is sent to the esplora client
+    let result = peg_out_graph
+        .guarded_challenge(
+            &config.client_0.esplora,
+            &inputs,
+            &config.depositor_context.depositor_keypair,
+            Some(refund_script),
+            false,
+        )
+        .await;
+    assert_eq!(
+        result,
+        Err(GuardedActionError::Challenge(ChallengeError::Funding(
+            ChallengeFundingError::InsufficientChallengeFunding { have, need }
+        )))
+    );
+}
+
+#[tokio::test]
+async fn test_challenge_funding_dust_refund() {
+    let config = setup_test().await;
+    let challenge_tx = create_stub_challenge_tx(&config);
+    let input_script =
+        generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key);
+
+    let refund = Amount::from_sat(DUST_AMOUNT - 1);
+    let inputs = vec![InputWithScript {
+        outpoint: OutPoint {
+            txid: Txid::from_str(
+                "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+        amount: challenge_tx.required_challenge_funding() + refund,
+        script: &input_script,
+    }];
+
+    assert_eq!(
+        challenge_tx.validate_challenge_funding(&inputs),
+        Err(ChallengeFundingError::DustRefund { refund })
+    );
+}
+
+#[tokio::test]
+async fn test_challenge_funding_over_funded() {
+    let config = setup_test().await;
+    let mut challenge_tx = create_stub_challenge_tx(&config);
+    let input_script =
+        generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key);
+    let refund_script = generate_pay_to_pubkey_script_address(
+        config.depositor_context.network,
+        &config.depositor_context.depositor_public_key,
+    )
+    .script_pubkey();
+
+    // two inputs that exceed the crowdfunding total
+    let amount = Amount::from_sat(INITIAL_AMOUNT * 2 / 3);
+    let inputs: Vec<InputWithScript> = (0..2)
+        .map(|vout| InputWithScript {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+                )
+                .unwrap(),
+                vout,
+            },
+            amount,
+            script: &input_script,
+        })
+        .collect();
+
+    assert_eq!(
+        challenge_tx.add_inputs_and_output(
+            &inputs,
+            &config.depositor_context.depositor_keypair,
+            refund_script.clone()
+        ),
+        Ok(())
+    );
+    assert_eq!(challenge_tx.tx().input.len(), 3);
+    assert_eq!(challenge_tx.tx().output.len(), 2);
+    assert_eq!(challenge_tx.tx().output[1].script_pubkey, refund_script);
+    assert_eq!(
+        challenge_tx.tx().output[1].value,
+        amount * 2 - challenge_tx.required_challenge_funding()
+    );
+}
+
+#[tokio::test]
+async fn test_challenge_funding_fee_bump() {
+    let config = setup_test().await;
+    let mut challenge_tx = create_stub_challenge_tx(&config);
+    let keypair = &config.depositor_context.depositor_keypair;
+    let input_script =
+        generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key);
+    let refund_script = generate_pay_to_pubkey_script_address(
+        config.depositor_context.network,
+        &config.depositor_context.depositor_public_key,
+    )
+    .script_pubkey();
+
+    let amount = Amount::from_sat(INITIAL_AMOUNT * 2 / 3);
+    let inputs: Vec<InputWithScript> = (0..2)
+        .map(|vout| InputWithScript {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+                )
+                .unwrap(),
+                vout,
+            },
+            amount,
+            script: &input_script,
+        })
+        .collect();
+    challenge_tx
+        .add_inputs_and_output_with_rbf(&inputs, keypair, refund_script, true)
+        .unwrap();
+
+    // only the crowdfunding inputs signal rbf, the operator's input is presigned
+    let tx = challenge_tx.tx().clone();
+    assert_eq!(tx.input[0].sequence, Sequence::MAX);
+    assert!(tx.input[1..]
+        .iter()
+        .all(|tx_in| tx_in.sequence == Sequence::ENABLE_RBF_NO_LOCKTIME));
+    assert!(tx.is_explicitly_rbf());
+
+    assert!(matches!(
+        challenge_tx.bump_fee(&inputs, keypair, 0.1),
+        Err(ChallengeFundingError::FeeNotIncreased { .. })
+    ));
+    assert_eq!(challenge_tx.tx(), &tx);
+
+    challenge_tx.bump_fee(&inputs, keypair, 100.0).unwrap();
+    let replacement_tx = challenge_tx.tx().clone();
+    assert_ne!(replacement_tx.compute_txid(), tx.compute_txid());
+    // same inputs, only the crowdfunding ones are signed again
+    assert!(replacement_tx
+        .input
+        .iter()
+        .zip(&tx.input)
+        .all(
+            |(replacement_tx_in, tx_in)| replacement_tx_in.previous_output == tx_in.previous_output
+                && replacement_tx_in.sequence == tx_in.sequence
+        ));
+    assert_eq!(replacement_tx.input[0].witness, tx.input[0].witness);
+    assert_eq!(replacement_tx.output[0], tx.output[0]);
+    assert!(replacement_tx.output[1].value < tx.output[1].value);
+
+    // a higher fee that does not cover the incremental relay fee is not enough
+    assert!(matches!(
+        challenge_tx.bump_fee(&inputs, keypair, 100.5),
+        Err(ChallengeFundingError::FeeNotIncreased { .. })
+    ));
+    assert_eq!(challenge_tx.tx(), &replacement_tx);
+    challenge_tx.bump_fee(&inputs, keypair, 102.0).unwrap();
+
+    // the whole refund cannot be spent on fees
+    assert!(matches!(
+        challenge_tx.bump_fee(&inputs, keypair, 1_000_000.0),
+        Err(ChallengeFundingError::DustRefund { .. })
+    ));
+}
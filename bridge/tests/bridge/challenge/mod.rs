@@ -1 +1,2 @@
 pub mod challenge;
+pub mod challenge_funding;
@@ -1,13 +1,14 @@
 use std::str::FromStr;
 
-use bitcoin::{Amount, OutPoint, Txid};
+use bitcoin::{Amount, Network, OutPoint, Txid};
 use bridge::{
     client::client::{BitVMClient, BitVMClientPublicData},
     graphs::{
-        base::{FEE_AMOUNT, INITIAL_AMOUNT},
+        base::{BaseGraph, FEE_AMOUNT, INITIAL_AMOUNT},
         peg_in::PegInGraph,
-        peg_out::PegOutGraph,
+        peg_out::{PegOutGraph, PegOutGraphConfig},
     },
+    scripts::generate_pay_to_pubkey_script_address,
     transactions::base::Input,
 };
 
@@ -47,6 +48,61 @@ async fn test_merge_add_new_graph() {
     assert_eq!(merged_data.peg_out_graphs.len(), 2);
 }
 
+#[tokio::test]
+async fn test_merge_applies_reward_address() {
+    let (mut client, new_peg_in_graph, new_peg_out_graph) = setup_and_create_graphs().await;
+    let network = new_peg_out_graph.network();
+    let reward_address = generate_pay_to_pubkey_script_address(
+        network,
+        &new_peg_in_graph.depositor_public_key,
+    );
+
+    let other_network = if network == Network::Bitcoin {
+        Network::Testnet
+    } else {
+        Network::Bitcoin
+    };
+    let wrong_address = generate_pay_to_pubkey_script_address(
+        other_network,
+        &new_peg_in_graph.depositor_public_key,
+    );
+    assert!(client
+        .set_reward_address(wrong_address.as_unchecked().clone())
+        .is_err());
+
+    client
+        .set_reward_address(reward_address.as_unchecked().clone())
+        .unwrap();
+    let existing_peg_out_graph = &client.get_data().peg_out_graphs[0];
+    assert_eq!(
+        existing_peg_out_graph.reward_script_pubkey(),
+        Some(&reward_address.script_pubkey())
+    );
+
+    // a graph loaded from shared data comes without the reward address
+    let received_peg_out_graph: PegOutGraph =
+        serde_json::from_value(serde_json::to_value(&new_peg_out_graph).unwrap()).unwrap();
+    assert!(received_peg_out_graph.reward_script_pubkey().is_none());
+
+    let data = client.get_data();
+    client.merge_data(BitVMClientPublicData {
+        version: data.version + 1,
+        peg_in_graphs: vec![new_peg_in_graph],
+        peg_out_graphs: vec![received_peg_out_graph],
+    });
+
+    let merged_peg_out_graph = client
+        .get_data()
+        .peg_out_graphs
+        .iter()
+        .find(|graph| graph.id() == new_peg_out_graph.id())
+        .unwrap();
+    assert_eq!(
+        merged_peg_out_graph.resolve_reward_script_pubkey(None),
+        Ok(reward_address.script_pubkey())
+    );
+}
+
 async fn setup_and_create_graphs() -> (BitVMClient, PegInGraph, PegOutGraph) {
     let mut config = setup_test().await;
 
@@ -94,11 +150,13 @@ async fn setup_and_create_graphs() -> (BitVMClient, PegInGraph, PegOutGraph) {
     let (new_peg_out_graph, _) = PegOutGraph::new(
         &config.operator_context,
         &new_peg_in_graph,
-        Input {
+        vec![Input {
             outpoint: peg_out_outpoint,
             amount,
-        },
-    );
+        }],
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
 
     (config.client_0, new_peg_in_graph, new_peg_out_graph)
 }
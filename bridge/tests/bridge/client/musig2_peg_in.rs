@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use bitcoin::Amount;
 
 use bridge::{
@@ -13,7 +11,7 @@ use tokio::time::sleep;
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::TX_WAIT_TIME,
+    helper::get_mine_wait_time,
 };
 
 use super::super::{helper::generate_stub_outpoint, setup::setup_test};
@@ -101,7 +99,7 @@ async fn test_musig2_peg_in() {
     println!("Operator: Reading state from remote...");
     depositor_operator_verifier_0_client.sync().await;
 
-    let timeout = Duration::from_secs(TX_WAIT_TIME);
+    let timeout = get_mine_wait_time();
     println!(
         "Waiting {:?} for peg-in deposit transaction to be mined...",
         timeout
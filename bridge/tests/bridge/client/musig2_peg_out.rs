@@ -1,6 +1,5 @@
-use std::time::Duration;
-
 use bitcoin::{Address, Amount, OutPoint};
+use bitvm::chunker::disprove_execution::RawProof;
 use bridge::{
     client::{
         chain::chain::{Chain, PegOutEvent},
@@ -25,7 +24,10 @@ use tokio::time::sleep;
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::{find_peg_in_graph_by_peg_out, generate_stub_outpoint, TX_WAIT_TIME},
+    helper::{
+        find_peg_in_graph_by_peg_out, generate_stub_outpoint, get_mine_wait_time,
+        get_superblock_header,
+    },
     mock::chain::mock::MockAdaptor,
     setup::setup_test,
 };
@@ -111,6 +113,61 @@ async fn test_musig2_peg_out_take_2() {
         .await;
 }
 
+#[tokio::test]
+#[serial]
+async fn test_musig2_peg_out_assert_chain() {
+    let (
+        mut depositor_operator_verifier_0_client,
+        _,
+        peg_out_graph_id,
+        depositor_context,
+        withdrawer_evm_address,
+        withdrawer_context,
+        operator_context,
+    ) = create_peg_out_graph().await;
+    simulate_peg_out_from_l2(
+        &mut depositor_operator_verifier_0_client,
+        &peg_out_graph_id,
+        &operator_context,
+        &withdrawer_evm_address,
+        &withdrawer_context,
+    )
+    .await;
+
+    let with_kick_off_2_tx = true;
+    let with_challenge_tx = false;
+    let with_assert_tx = false;
+    broadcast_transactions_from_peg_out_graph(
+        &mut depositor_operator_verifier_0_client,
+        &peg_out_graph_id,
+        &depositor_context,
+        with_kick_off_2_tx,
+        with_challenge_tx,
+        with_assert_tx,
+    )
+    .await;
+
+    eprintln!("Broadcasting assert chain...");
+    depositor_operator_verifier_0_client.sync().await;
+    let txids = depositor_operator_verifier_0_client
+        .broadcast_assert_chain(&peg_out_graph_id, &RawProof::default())
+        .await
+        .unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(txids.len(), 4);
+
+    println!("Waiting for peg-out assert chain txs...");
+    sleep(get_mine_wait_time()).await;
+
+    for txid in txids {
+        let tx_status = depositor_operator_verifier_0_client
+            .esplora
+            .get_tx_status(&txid)
+            .await
+            .unwrap();
+        assert!(tx_status.confirmed, "{txid} was not mined");
+    }
+}
+
 #[tokio::test]
 #[serial]
 async fn test_musig2_start_time_timeout() {
@@ -149,9 +206,12 @@ async fn test_musig2_start_time_timeout() {
     depositor_operator_verifier_0_client
         .broadcast_start_time_timeout(
             &peg_out_graph_id,
-            generate_pay_to_pubkey_script(&depositor_context.depositor_public_key),
+            Some(generate_pay_to_pubkey_script(
+                &depositor_context.depositor_public_key,
+            )),
         )
-        .await;
+        .await
+        .unwrap();
 }
 
 #[tokio::test]
@@ -192,9 +252,12 @@ async fn test_musig2_kick_off_timeout() {
     depositor_operator_verifier_0_client
         .broadcast_kick_off_timeout(
             &peg_out_graph_id,
-            generate_pay_to_pubkey_script(&depositor_context.depositor_public_key),
+            Some(generate_pay_to_pubkey_script(
+                &depositor_context.depositor_public_key,
+            )),
         )
-        .await;
+        .await
+        .unwrap();
 }
 
 #[tokio::test]
@@ -235,8 +298,9 @@ async fn test_musig2_peg_out_disprove_with_challenge() {
     depositor_operator_verifier_0_client
         .broadcast_disprove(
             &peg_out_graph_id,
-            1,
-            generate_pay_to_pubkey_script(&depositor_context.depositor_public_key),
+            Some(generate_pay_to_pubkey_script(
+                &depositor_context.depositor_public_key,
+            )),
         )
         .await;
 }
@@ -279,7 +343,11 @@ async fn test_musig2_peg_out_disprove_chain_with_challenge() {
     depositor_operator_verifier_0_client
         .broadcast_disprove_chain(
             &peg_out_graph_id,
-            generate_pay_to_pubkey_script(&depositor_context.depositor_public_key),
+            // a mainnet block outweighs any regtest block the operator committed to
+            &[get_superblock_header()],
+            Some(generate_pay_to_pubkey_script(
+                &depositor_context.depositor_public_key,
+            )),
         )
         .await;
 }
@@ -320,20 +388,20 @@ async fn broadcast_transactions_from_peg_out_graph(
 
     // Wait for peg-in deposit transaction to be mined
     println!("Waiting for peg-out kick-off tx...");
-    sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+    sleep(get_mine_wait_time()).await;
 
     if with_kick_off_2_tx {
         eprintln!("Broadcasting start time...");
         client.broadcast_start_time(&peg_out_graph_id).await;
 
         println!("Waiting for peg-out start time tx...");
-        sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+        sleep(get_mine_wait_time()).await;
 
         eprintln!("Broadcasting kick-off 2...");
         client.broadcast_kick_off_2(&peg_out_graph_id).await;
 
         println!("Waiting for peg-out kick-off 2 tx...");
-        sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+        sleep(get_mine_wait_time()).await;
     }
 
     if with_challenge_tx {
@@ -365,12 +433,16 @@ async fn broadcast_transactions_from_peg_out_graph(
             .broadcast_challenge(
                 &peg_out_graph_id,
                 &vec![challenge_crowdfunding_input],
-                generate_pay_to_pubkey_script(&depositor_context.depositor_public_key),
+                Some(generate_pay_to_pubkey_script(
+                    &depositor_context.depositor_public_key,
+                )),
+                true,
             )
-            .await;
+            .await
+            .unwrap();
 
         println!("Waiting for peg-out challenge tx...");
-        sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+        sleep(get_mine_wait_time()).await;
     }
 
     // TODO: uncomment after assert txs are done
@@ -418,7 +490,7 @@ async fn create_peg_out_graph() -> (
         .fund_inputs(&depositor_operator_verifier_0_client, &funding_inputs)
         .await;
     println!("Waiting for funding inputs tx...");
-    sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+    sleep(get_mine_wait_time()).await;
 
     let kick_off_outpoint = generate_stub_outpoint(
         &depositor_operator_verifier_0_client,
@@ -517,7 +589,7 @@ async fn create_peg_in_graph(
 
     // Wait for peg-in deposit transaction to be mined
     println!("Waiting for peg-in deposit tx...");
-    sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+    sleep(get_mine_wait_time()).await;
 
     client_0.sync().await;
     client_0.broadcast_peg_in_confirm(&graph_id).await;
@@ -595,16 +667,18 @@ async fn simulate_peg_out_from_l2(
     };
 
     eprintln!("Broadcasting peg out...");
-    client.broadcast_peg_out(&peg_out_graph_id, input).await;
+    client
+        .broadcast_peg_out(&peg_out_graph_id, input, false)
+        .await;
 
     // Wait for peg-out transaction to be mined
     println!("Waiting for peg-out tx...");
-    sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+    sleep(get_mine_wait_time()).await;
 
     eprintln!("Broadcasting peg out confirm...");
     client.broadcast_peg_out_confirm(&peg_out_graph_id).await;
 
     // Wait for peg-out confirm transaction to be mined
     println!("Waiting for peg-out confirm tx...");
-    sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+    sleep(get_mine_wait_time()).await;
 }
@@ -7,7 +7,7 @@ use bridge::{
     graphs::{
         base::{FEE_AMOUNT, INITIAL_AMOUNT},
         peg_in::PegInGraph,
-        peg_out::PegOutGraph,
+        peg_out::{PegOutGraph, PegOutGraphConfig},
     },
     scripts::generate_burn_script,
     transactions::{base::Input, pre_signed::PreSignedTransaction},
@@ -24,6 +24,19 @@ async fn test_validate_success() {
     assert!(is_data_valid);
 }
 
+#[tokio::test]
+async fn test_validate_graph_stored_without_n_of_n_public_keys() {
+    let (mut data, _) = setup_and_create_graphs().await;
+
+    let mut json = serde_json::to_value(&data.peg_out_graphs[0]).unwrap();
+    json.as_object_mut().unwrap().remove("n_of_n_public_keys");
+    data.peg_out_graphs[0] = serde_json::from_value(json).unwrap();
+
+    let is_data_valid = BitVMClient::validate_data(&data);
+
+    assert!(is_data_valid);
+}
+
 #[tokio::test]
 async fn test_validate_invalid_previous_output() {
     let (mut data, peg_in_outpoint) = setup_and_create_graphs().await;
@@ -126,11 +139,13 @@ async fn setup_and_create_graphs() -> (BitVMClientPublicData, OutPoint) {
     let (peg_out_graph, _) = PegOutGraph::new(
         &config.operator_context,
         &peg_in_graph_0,
-        Input {
+        vec![Input {
             outpoint: peg_out_outpoint,
             amount: amount_0,
-        },
-    );
+        }],
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
 
     let data = BitVMClientPublicData {
         version: 1,
@@ -0,0 +1 @@
+pub mod weak_key;
@@ -0,0 +1,75 @@
+use bitcoin::{Network, PublicKey};
+
+use bridge::{
+    contexts::{
+        base::{generate_keys_from_secret, WeakKey},
+        operator::OperatorContext,
+        verifier::VerifierContext,
+    },
+    graphs::base::{OPERATOR_SECRET, VERIFIER_0_SECRET, VERIFIER_1_SECRET},
+};
+
+const ZERO_SECRET: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const ONE_SECRET: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+const SMALL_SECRET: &str = "00000000000000000000000000000000000000000000000000000000deadbeef";
+// curve order minus one, i.e. the negation of one
+const MINUS_ONE_SECRET: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140";
+const MINUS_TWO_SECRET: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd036413f";
+
+fn public_key(secret: &str) -> PublicKey { generate_keys_from_secret(Network::Testnet, secret).1 }
+
+fn n_of_n_public_keys() -> Vec<PublicKey> {
+    vec![public_key(VERIFIER_0_SECRET), public_key(VERIFIER_1_SECRET)]
+}
+
+#[test]
+fn test_weak_secret_keys() {
+    let n_of_n_public_keys = n_of_n_public_keys();
+
+    for (secret, err) in [
+        (ZERO_SECRET, WeakKey::InvalidSecretKey),
+        ("not a secret key", WeakKey::InvalidSecretKey),
+        (ONE_SECRET, WeakKey::SmallSecretKey),
+        (SMALL_SECRET, WeakKey::SmallSecretKey),
+        (MINUS_ONE_SECRET, WeakKey::SmallSecretKey),
+    ] {
+        assert_eq!(
+            OperatorContext::new(Network::Testnet, secret, &n_of_n_public_keys).err(),
+            Some(err)
+        );
+        assert_eq!(
+            VerifierContext::new(Network::Testnet, secret, &n_of_n_public_keys).err(),
+            Some(err)
+        );
+    }
+}
+
+#[test]
+fn test_weak_n_of_n_public_keys() {
+    let mut n_of_n_public_keys = n_of_n_public_keys();
+    n_of_n_public_keys.push(public_key(ONE_SECRET));
+    assert_eq!(
+        OperatorContext::new(Network::Testnet, OPERATOR_SECRET, &n_of_n_public_keys).err(),
+        Some(WeakKey::SmallPublicKey { index: 2 })
+    );
+
+    // the negation of a small multiple is just as weak
+    n_of_n_public_keys[2] = public_key(MINUS_TWO_SECRET);
+    assert_eq!(
+        VerifierContext::new(Network::Testnet, VERIFIER_0_SECRET, &n_of_n_public_keys).err(),
+        Some(WeakKey::SmallPublicKey { index: 2 })
+    );
+}
+
+#[test]
+fn test_valid_keys() {
+    let n_of_n_public_keys = n_of_n_public_keys();
+
+    let operator_context =
+        OperatorContext::new(Network::Testnet, OPERATOR_SECRET, &n_of_n_public_keys).unwrap();
+    assert_eq!(
+        operator_context.operator_public_key,
+        public_key(OPERATOR_SECRET)
+    );
+    assert!(VerifierContext::new(Network::Testnet, VERIFIER_1_SECRET, &n_of_n_public_keys).is_ok());
+}
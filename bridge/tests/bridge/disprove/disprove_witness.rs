@@ -0,0 +1,51 @@
+#[cfg(feature = "minimal-graph")]
+use bitvm::chunker::disprove_execution::RawProof;
+#[cfg(feature = "minimal-graph")]
+use bridge::{
+    connectors::connector_c::ConnectorC,
+    graphs::peg_out::{CommitmentMessageId, MINIMAL_GROTH16_VARIABLES},
+};
+
+use super::super::setup::setup_test;
+
+#[tokio::test]
+async fn test_invalid_disprove_witness() {
+    let config = setup_test().await;
+
+    assert!(!config.connector_c.verify_disprove_witness(0, &[]));
+    assert!(!config
+        .connector_c
+        .verify_disprove_witness(0, &[vec![0; 32], vec![0; 32]]));
+    // no such leaf
+    assert!(!config.connector_c.verify_disprove_witness(u32::MAX, &[]));
+}
+
+// The leaves of a minimal graph are stubs that can be unlocked without a proof.
+#[cfg(feature = "minimal-graph")]
+#[tokio::test]
+async fn test_valid_disprove_witness() {
+    let config = setup_test().await;
+
+    let minimal_commitment_public_keys = config
+        .connector_c
+        .commitment_public_keys()
+        .iter()
+        .filter(|(message_id, _)| match message_id {
+            CommitmentMessageId::Groth16IntermediateValues((name, _)) => {
+                MINIMAL_GROTH16_VARIABLES.contains(&name.as_str())
+            }
+            _ => false,
+        })
+        .map(|(message_id, public_key)| (message_id.clone(), public_key.clone()))
+        .collect();
+    let connector_c = ConnectorC::new(
+        config.connector_c.network,
+        &config.operator_context.operator_taproot_public_key,
+        &minimal_commitment_public_keys,
+        &RawProof::default().vk,
+    );
+
+    assert!(connector_c.verify_disprove_witness(0, &[b"SECRET_0".to_vec()]));
+    assert!(connector_c.verify_disprove_witness(1, &[b"SECRET_1".to_vec()]));
+    assert!(!connector_c.verify_disprove_witness(0, &[b"SECRET_1".to_vec()]));
+}
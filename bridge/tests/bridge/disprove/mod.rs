@@ -1 +1,2 @@
 pub mod disprove;
+pub mod disprove_witness;
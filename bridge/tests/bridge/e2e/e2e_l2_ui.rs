@@ -1,9 +1,8 @@
 use futures::StreamExt;
-use std::time::Duration;
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::{find_peg_out_graph, generate_stub_outpoint, TX_WAIT_TIME},
+    helper::{find_peg_out_graph, generate_stub_outpoint, get_mine_wait_time},
     setup::setup_test,
 };
 use bitcoin::{Address, Amount};
@@ -13,7 +12,7 @@ use bridge::{
     contexts::{depositor::DepositorContext, operator::OperatorContext},
     graphs::{
         base::{BaseGraph, FEE_AMOUNT, INITIAL_AMOUNT},
-        peg_out::PegOutOperatorStatus,
+        peg_out::{PegOutOperatorStatus, DEFAULT_MIN_CONFIRMATIONS},
     },
     scripts::generate_pay_to_pubkey_script_address,
     transactions::base::Input,
@@ -35,7 +34,9 @@ async fn test_e2e_0_simulate_complete_peg_in() {
         .build_async()
         .expect("Could not build esplora client");
     let peg_out_graph = find_peg_out_graph(&operator_client, peg_out_graph_id.as_str()).unwrap();
-    let status = peg_out_graph.operator_status(&esplora).await;
+    let status = peg_out_graph
+        .operator_status(&esplora, None, DEFAULT_MIN_CONFIRMATIONS)
+        .await;
     println!(">>>>> Graph id: {} status: {}", peg_out_graph.id(), status);
     println!("Peg in completed, please proceed to initate peg out in UI.");
 }
@@ -58,7 +59,9 @@ async fn test_e2e_1_simulate_peg_out() {
     let peg_out_graph_result = futures::stream::iter(peg_out_graphs)
         .filter(|g| {
             Box::pin(async {
-                let status = g.operator_status(&esplora).await;
+                let status = g
+                    .operator_status(&esplora, None, DEFAULT_MIN_CONFIRMATIONS)
+                    .await;
                 println!(">>>>> Graph id: {} status: {}", g.id(), status);
                 match status {
                     PegOutOperatorStatus::PegOutStartPegOut => true,
@@ -108,22 +111,17 @@ async fn test_e2e_1_simulate_peg_out() {
     };
     eprintln!("Broadcasting peg out...");
     operator_client
-        .broadcast_peg_out(&peg_out_graph.id(), input)
+        .broadcast_peg_out(&peg_out_graph.id(), input, false)
         .await;
 
     // Wait for peg-out transaction to be mined
     println!("Waiting for peg-out tx...");
-    sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+    sleep(get_mine_wait_time()).await;
 
     operator_client.flush().await;
 
     let synced_peg_out_graph = find_peg_out_graph(&operator_client, peg_out_graph.id()).unwrap();
-    let peg_out_txid = synced_peg_out_graph
-        .peg_out_transaction
-        .as_ref()
-        .unwrap()
-        .tx()
-        .compute_txid();
+    let peg_out_txid = synced_peg_out_graph.peg_out_txid().unwrap();
     println!(
         "Peg out tx [{}] broadcasted, please proceed to burnEBTC in L2.",
         peg_out_txid
@@ -180,7 +178,7 @@ async fn create_graph() -> (
         .fund_inputs(&depositor_operator_verifier_0_client, &funding_inputs)
         .await;
     println!("Waiting for funding inputs tx...");
-    sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+    sleep(get_mine_wait_time()).await;
 
     let kick_off_outpoint = generate_stub_outpoint(
         &depositor_operator_verifier_0_client,
@@ -283,7 +281,7 @@ async fn create_peg_in_graph(
 
     // Wait for peg-in deposit transaction to be mined
     println!("Waiting for peg-in deposit tx...");
-    sleep(Duration::from_secs(TX_WAIT_TIME)).await;
+    sleep(get_mine_wait_time()).await;
 
     client_0.sync().await;
     client_0.broadcast_peg_in_confirm(&graph_id).await;
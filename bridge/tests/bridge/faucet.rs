@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, process::Command, time::Duration};
 use tokio::time::sleep;
 
-use crate::bridge::helper::{ESPLORA_FUNDING_URL, TX_WAIT_TIME};
+use crate::bridge::helper::{get_mine_wait_time, ESPLORA_FUNDING_URL};
 
 const ESPLORA_RETRIES: usize = 5;
 const ESPLORA_RETRY_WAIT_TIME: u64 = 10;
@@ -48,7 +48,7 @@ impl Faucet {
     }
 
     pub async fn wait(&self) {
-        let timeout = Duration::from_secs(TX_WAIT_TIME);
+        let timeout = get_mine_wait_time();
         println!("Waiting {:?} for funding inputs tx...", timeout);
         sleep(timeout).await;
     }
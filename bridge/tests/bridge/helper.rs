@@ -1,20 +1,103 @@
-use std::str::FromStr;
+use std::{
+    env,
+    process::Command,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 use bitcoin::{
     block::{Header, Version},
-    Address, Amount, BlockHash, CompactTarget, OutPoint, TxMerkleNode,
+    consensus::encode::serialize_hex,
+    Address, Amount, BlockHash, CompactTarget, OutPoint, Transaction, TxMerkleNode, Txid,
 };
+use esplora_client::AsyncClient;
+use tokio::time::sleep;
 
 use bridge::{
     client::client::BitVMClient,
     graphs::{base::BaseGraph, peg_in::PegInGraph, peg_out::PegOutGraph},
 };
 
-pub const TX_WAIT_TIME: u64 = 45; // in seconds
+pub const TX_WAIT_TIME: u64 = 45; // in seconds, overridden by TEST_MINE_WAIT_VAR
+pub const TEST_MINE_WAIT_VAR: &str = "TEST_MINE_WAIT"; // e.g. a few seconds on an instant regtest
+pub const TX_POLL_INTERVAL: u64 = 1; // in seconds
 pub const ESPLORA_FUNDING_URL: &str = "https://esploraapi53d3659b.devnet-annapurna.stratabtc.org/";
 pub const ESPLORA_RETRIES: usize = 3;
 pub const ESPLORA_RETRY_WAIT_TIME: u64 = 5;
 
+pub fn get_mine_wait_time() -> Duration {
+    let seconds = env::var(TEST_MINE_WAIT_VAR)
+        .ok()
+        .and_then(|wait_time| wait_time.parse().ok())
+        .unwrap_or(TX_WAIT_TIME);
+    Duration::from_secs(seconds)
+}
+
+pub async fn wait_for_confirmation(client: &AsyncClient, txid: Txid) {
+    let wait_time = get_mine_wait_time();
+    let deadline = Instant::now() + wait_time;
+    println!("Waiting up to {:?} for tx {txid} to be mined...", wait_time);
+    while !client
+        .get_tx_status(&txid)
+        .await
+        .is_ok_and(|status| status.confirmed)
+    {
+        if Instant::now() >= deadline {
+            panic!("Tx {txid} has not been mined within {:?}", wait_time);
+        }
+        sleep(Duration::from_secs(TX_POLL_INTERVAL)).await;
+    }
+}
+
+// Unconfirmed inputs and relative timelocks that have not elapsed yet only show
+// up as broadcast errors, so keep retrying until the tx is accepted.
+pub async fn broadcast_when_spendable(
+    client: &AsyncClient,
+    tx: &Transaction,
+) -> Result<(), esplora_client::Error> {
+    let wait_time = get_mine_wait_time();
+    let deadline = Instant::now() + wait_time;
+    println!(
+        "Broadcasting tx {} once spendable, waiting up to {:?}...",
+        tx.compute_txid(),
+        wait_time
+    );
+    loop {
+        let result = client.broadcast(tx).await;
+        if result.is_ok() || Instant::now() >= deadline {
+            return result;
+        }
+        sleep(Duration::from_secs(TX_POLL_INTERVAL)).await;
+    }
+}
+
+// Esplora has no package relay, so packages are submitted to the bitcoind of the
+// esplora regtest container. Returns the result of `submitpackage`.
+pub fn submit_package_by_cli(txs: &[&Transaction]) -> serde_json::Value {
+    let output = Command::new("/usr/local/bin/docker")
+        .args(["ps", "-q", "--filter", "ancestor=blockstream/esplora"])
+        .output()
+        .expect("failed to list docker containers");
+    let container = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let raw_txs: Vec<String> = txs.iter().map(|tx| serialize_hex(*tx)).collect();
+    let package = serde_json::to_string(&raw_txs).unwrap();
+
+    let output = Command::new("/usr/local/bin/docker")
+        .args([
+            "exec",
+            container.as_str(),
+            "/srv/explorer/bitcoin/bin/bitcoin-cli",
+            "-conf=/data/.bitcoin.conf",
+            "-datadir=/data/bitcoin",
+            "submitpackage",
+            package.as_str(),
+        ])
+        .output()
+        .expect("failed to execute bitcoin-cli");
+    serde_json::from_slice(&output.stdout)
+        .unwrap_or_else(|_| panic!("submitpackage failed: {:?}", output))
+}
+
 pub async fn generate_stub_outpoint(
     client: &BitVMClient,
     funding_utxo_address: &Address,
@@ -4,7 +4,7 @@ use bitcoin::{Amount, OutPoint};
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::generate_stub_outpoint,
+    helper::{broadcast_when_spendable, generate_stub_outpoint},
     setup::{setup_test, SetupConfig},
 };
 use bridge::{
@@ -190,13 +190,7 @@ async fn test_peg_in_time_lock_surpassed() {
     let refund_txid = peg_in_refund_tx.compute_txid();
 
     // mine peg-in refund
-    let refund_wait_timeout = Duration::from_secs(60);
-    println!(
-        "Waiting \x1b[37;41m{:?}\x1b[0m before broadcasting peg in refund tx...",
-        refund_wait_timeout
-    );
-    sleep(refund_wait_timeout).await; // TODO: check if this can be refactored to drop waiting
-    let refund_result = config.client_0.esplora.broadcast(&peg_in_refund_tx).await;
+    let refund_result = broadcast_when_spendable(&config.client_0.esplora, &peg_in_refund_tx).await;
     assert!(refund_result.is_ok());
 
     // depositor balance
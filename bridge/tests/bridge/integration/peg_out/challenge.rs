@@ -86,11 +86,13 @@ async fn test_challenge_success() {
         challenge_kick_off_input,
         challenge_input_amount,
     );
-    challenge.add_inputs_and_output(
-        &vec![challenge_crowdfunding_input],
-        &config.depositor_context.depositor_keypair,
-        generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key),
-    ); // add crowdfunding input
+    challenge
+        .add_inputs_and_output(
+            &vec![challenge_crowdfunding_input],
+            &config.depositor_context.depositor_keypair,
+            generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key),
+        )
+        .unwrap(); // add crowdfunding input
     let challenge_tx = challenge.finalize();
     let challenge_txid = challenge_tx.compute_txid();
 
@@ -266,6 +266,7 @@ async fn test_disprove_success() {
             witness_for_commit2,
             wrong_proof.vk.clone(),
         )
+        .unwrap()
         .unwrap();
     // let script_index = 1;
 
@@ -1,6 +1,3 @@
-use std::time::Duration;
-use tokio::time::sleep;
-
 use bitcoin::{Address, Amount, OutPoint};
 use bridge::{
     connectors::base::TaprootConnector,
@@ -15,7 +12,7 @@ use bridge::{
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::verify_funding_inputs,
+    helper::{broadcast_when_spendable, verify_funding_inputs},
     integration::peg_out::utils::create_and_mine_kick_off_2_tx,
     setup::setup_test,
 };
@@ -89,13 +86,8 @@ async fn test_disprove_chain_success() {
     let disprove_chain_txid = disprove_chain_tx.compute_txid();
 
     // mine disprove chain
-    let disprove_chain_wait_timeout = Duration::from_secs(20);
-    println!(
-        "Waiting \x1b[37;41m{:?}\x1b[0m before broadcasting disprove chain tx...",
-        disprove_chain_wait_timeout
-    );
-    sleep(disprove_chain_wait_timeout).await;
-    let disprove_chain_result = config.client_0.esplora.broadcast(&disprove_chain_tx).await;
+    let disprove_chain_result =
+        broadcast_when_spendable(&config.client_0.esplora, &disprove_chain_tx).await;
     println!("disprove chain result: {:?}", disprove_chain_result);
     assert!(disprove_chain_result.is_ok());
 
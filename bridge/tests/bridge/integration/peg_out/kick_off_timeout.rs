@@ -1,6 +1,3 @@
-use std::time::Duration;
-use tokio::time::sleep;
-
 use bitcoin::{Amount, OutPoint};
 use bridge::{
     connectors::{base::TaprootConnector, connector_1::Connector1},
@@ -15,6 +12,7 @@ use bridge::{
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
+    helper::broadcast_when_spendable,
     integration::peg_out::utils::create_and_mine_kick_off_1_tx,
     setup::setup_test,
 };
@@ -71,6 +69,7 @@ async fn test_kick_off_timeout_success() {
         &config.operator_context.operator_taproot_public_key, // Verifiers get this via remote storage.
         &config.verifier_0_context.n_of_n_taproot_public_key,
         &config.connector_1.commitment_public_keys, // Verifiers get this via remote storage.
+        config.connector_1.superblock_policy,
     );
     kick_off_timeout.pre_sign(
         &config.verifier_0_context,
@@ -82,6 +81,7 @@ async fn test_kick_off_timeout_success() {
         &config.operator_context.operator_taproot_public_key,
         &config.verifier_0_context.n_of_n_taproot_public_key,
         &config.connector_1.commitment_public_keys,
+        config.connector_1.superblock_policy,
     );
     kick_off_timeout.pre_sign(
         &config.verifier_1_context,
@@ -99,17 +99,8 @@ async fn test_kick_off_timeout_success() {
     let kick_off_timeout_txid = kick_off_timeout_tx.compute_txid();
 
     // mine kick-off timeout
-    let kick_off_timeout_wait_timeout = Duration::from_secs(20);
-    println!(
-        "Waiting \x1b[37;41m{:?}\x1b[0m before broadcasting kick off timeout tx...",
-        kick_off_timeout_wait_timeout
-    );
-    sleep(kick_off_timeout_wait_timeout).await;
-    let kick_off_timeout_result = config
-        .client_0
-        .esplora
-        .broadcast(&kick_off_timeout_tx)
-        .await;
+    let kick_off_timeout_result =
+        broadcast_when_spendable(&config.client_0.esplora, &kick_off_timeout_tx).await;
     println!("Kick-off timeout result: {kick_off_timeout_result:?}");
     assert!(kick_off_timeout_result.is_ok());
 
@@ -72,7 +72,7 @@ async fn test_peg_out_success() {
         amount: operator_input_amount,
     };
 
-    let peg_out = PegOutTransaction::new(&config.operator_context, &stub_event, input);
+    let peg_out = PegOutTransaction::new(&config.operator_context, &stub_event, input).unwrap();
 
     let peg_out_tx = peg_out.finalize();
     let peg_out_txid = peg_out_tx.compute_txid();
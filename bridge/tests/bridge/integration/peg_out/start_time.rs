@@ -1,14 +1,11 @@
-use std::time::Duration;
-use tokio::time::sleep;
-
-use bitcoin::{Address, Amount, OutPoint};
+use bitcoin::{Address, Amount, Network, OutPoint};
 use bridge::{
     connectors::base::TaprootConnector,
     graphs::{
         base::{DUST_AMOUNT, FEE_AMOUNT, INITIAL_AMOUNT, MESSAGE_COMMITMENT_FEE_AMOUNT},
         peg_out::CommitmentMessageId,
     },
-    superblock::get_start_time_block_number,
+    superblock::{get_start_time_block_number, SuperblockParams},
     transactions::{
         base::{BaseTransaction, Input},
         start_time::StartTimeTransaction,
@@ -17,7 +14,7 @@ use bridge::{
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::verify_funding_inputs,
+    helper::{broadcast_when_spendable, verify_funding_inputs},
     integration::peg_out::utils::create_and_mine_kick_off_1_tx,
     setup::setup_test,
 };
@@ -71,23 +68,20 @@ async fn test_start_time_success() {
         start_time_input_0,
     );
 
-    start_time.sign(
-        &config.operator_context,
-        &config.connector_2,
-        get_start_time_block_number(),
-        &config.commitment_secrets[&CommitmentMessageId::StartTime],
-    );
+    start_time
+        .sign(
+            &config.operator_context,
+            &config.connector_2,
+            get_start_time_block_number(&SuperblockParams::for_network(Network::Regtest)),
+            &config.commitment_secrets[&CommitmentMessageId::StartTime],
+        )
+        .unwrap();
 
     let start_time_tx = start_time.finalize();
 
     // mine start time
-    let start_time_wait_timeout = Duration::from_secs(20);
-    println!(
-        "Waiting \x1b[37;41m{:?}\x1b[0m before broadcasting start time tx...",
-        start_time_wait_timeout
-    );
-    sleep(start_time_wait_timeout).await;
-    let start_time_result = config.client_0.esplora.broadcast(&start_time_tx).await;
+    let start_time_result =
+        broadcast_when_spendable(&config.client_0.esplora, &start_time_tx).await;
     println!("Broadcast result: {:?}\n", start_time_result);
     assert!(start_time_result.is_ok());
 }
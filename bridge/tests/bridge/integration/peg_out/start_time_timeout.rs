@@ -1,6 +1,3 @@
-use std::time::Duration;
-use tokio::time::sleep;
-
 use bitcoin::{Address, Amount, OutPoint};
 use bridge::{
     connectors::base::TaprootConnector,
@@ -15,7 +12,7 @@ use bridge::{
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::verify_funding_inputs,
+    helper::{broadcast_when_spendable, verify_funding_inputs},
     integration::peg_out::utils::create_and_mine_kick_off_1_tx,
     setup::setup_test,
 };
@@ -104,17 +101,8 @@ async fn test_start_time_timeout_success() {
     let start_time_timeout_txid = start_time_timeout_tx.compute_txid();
 
     // mine start time timeout
-    let start_time_timeout_wait_timeout = Duration::from_secs(20);
-    println!(
-        "Waiting \x1b[37;41m{:?}\x1b[0m before broadcasting start time timeout tx...",
-        start_time_timeout_wait_timeout
-    );
-    sleep(start_time_timeout_wait_timeout).await;
-    let start_time_timeout_result = config
-        .client_0
-        .esplora
-        .broadcast(&start_time_timeout_tx)
-        .await;
+    let start_time_timeout_result =
+        broadcast_when_spendable(&config.client_0.esplora, &start_time_timeout_tx).await;
     println!("Start time timeout result: {:?}", start_time_timeout_result);
     assert!(start_time_timeout_result.is_ok());
 
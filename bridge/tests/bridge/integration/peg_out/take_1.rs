@@ -1,6 +1,5 @@
-use std::time::Duration;
-
 use bitcoin::{Address, Amount, OutPoint};
+use bitvm::signatures::signing_winternitz::WinternitzSigningInputs;
 use bridge::{
     connectors::base::TaprootConnector,
     graphs::{
@@ -16,12 +15,10 @@ use bridge::{
         take_1::Take1Transaction,
     },
 };
-use bitvm::signatures::signing_winternitz::WinternitzSigningInputs;
-use tokio::time::sleep;
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::{get_superblock_header, verify_funding_inputs},
+    helper::{broadcast_when_spendable, get_superblock_header, verify_funding_inputs},
     integration::peg_out::utils::{
         create_and_mine_kick_off_1_tx, create_and_mine_peg_in_confirm_tx,
     },
@@ -94,29 +91,26 @@ async fn test_take_1_success() {
         kick_off_2_input_0,
     );
     let superblock_header = get_superblock_header();
-    kick_off_2.sign(
-        &config.operator_context,
-        &config.connector_1,
-        &WinternitzSigningInputs {
-            message: &get_superblock_message(&superblock_header),
-            signing_key: &config.commitment_secrets[&CommitmentMessageId::Superblock],
-        },
-        &WinternitzSigningInputs {
-            message: &get_superblock_hash_message(&superblock_header),
-            signing_key: &config.commitment_secrets[&CommitmentMessageId::SuperblockHash],
-        },
-    );
+    kick_off_2
+        .sign(
+            &config.operator_context,
+            &config.connector_1,
+            &WinternitzSigningInputs {
+                message: &get_superblock_message(&superblock_header),
+                signing_key: &config.commitment_secrets[&CommitmentMessageId::Superblock],
+            },
+            &WinternitzSigningInputs {
+                message: &get_superblock_hash_message(&superblock_header),
+                signing_key: &config.commitment_secrets[&CommitmentMessageId::SuperblockHash],
+            },
+        )
+        .unwrap();
     let kick_off_2_tx = kick_off_2.finalize();
     let kick_off_2_txid = kick_off_2_tx.compute_txid();
 
     // mine kick-off 2
-    let kick_off_2_wait_timeout = Duration::from_secs(20);
-    println!(
-        "Waiting \x1b[37;41m{:?}\x1b[0m before broadcasting kick-off 2 tx...",
-        kick_off_2_wait_timeout
-    );
-    sleep(kick_off_2_wait_timeout).await;
-    let kick_off_2_result = config.client_0.esplora.broadcast(&kick_off_2_tx).await;
+    let kick_off_2_result =
+        broadcast_when_spendable(&config.client_0.esplora, &kick_off_2_tx).await;
     println!("Broadcast result: {:?}\n", kick_off_2_result);
     assert!(kick_off_2_result.is_ok());
 
@@ -164,6 +158,7 @@ async fn test_take_1_success() {
         take_1_input_1,
         take_1_input_2,
         take_1_input_3,
+        false,
     );
 
     let secret_nonces_0 = take_1.push_nonces(&config.verifier_0_context);
@@ -186,13 +181,7 @@ async fn test_take_1_success() {
     let take_1_txid = take_1_tx.compute_txid();
 
     // mine take 1
-    let take_1_wait_timeout = Duration::from_secs(20);
-    println!(
-        "Waiting \x1b[37;41m{:?}\x1b[0m before broadcasting take 1 tx...",
-        take_1_wait_timeout
-    );
-    sleep(take_1_wait_timeout).await;
-    let take_1_result = config.client_0.esplora.broadcast(&take_1_tx).await;
+    let take_1_result = broadcast_when_spendable(&config.client_0.esplora, &take_1_tx).await;
     println!("Broadcast result: {:?}\n", take_1_result);
     assert!(take_1_result.is_ok());
 
@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use bitcoin::{Address, Amount, OutPoint};
 use bridge::{
     connectors::base::TaprootConnector,
@@ -11,11 +9,10 @@ use bridge::{
         take_2::Take2Transaction,
     },
 };
-use tokio::time::sleep;
 
 use crate::bridge::{
     faucet::{Faucet, FaucetType},
-    helper::verify_funding_inputs,
+    helper::{broadcast_when_spendable, verify_funding_inputs},
     integration::peg_out::utils::{create_and_mine_assert_tx, create_and_mine_peg_in_confirm_tx},
     setup::setup_test,
 };
@@ -114,6 +111,7 @@ async fn test_take_2_success() {
         take_2_input_1,
         take_2_input_2,
         take_2_input_3,
+        false,
     );
 
     let secret_nonces_0 = take_2.push_nonces(&config.verifier_0_context);
@@ -138,13 +136,7 @@ async fn test_take_2_success() {
     let take_2_txid = take_2_tx.compute_txid();
 
     // mine take 2
-    let take_2_wait_timeout = Duration::from_secs(20);
-    println!(
-        "Waiting \x1b[37;41m{:?}\x1b[0m before broadcasting take 2 tx...",
-        take_2_wait_timeout
-    );
-    sleep(take_2_wait_timeout).await;
-    let take_2_result = config.client_0.esplora.broadcast(&take_2_tx).await;
+    let take_2_result = broadcast_when_spendable(&config.client_0.esplora, &take_2_tx).await;
     println!("Broadcast result: {:?}\n", take_2_result);
     assert!(take_2_result.is_ok());
 
@@ -22,7 +22,7 @@ use bridge::{
 };
 use bitvm::signatures::signing_winternitz::{WinternitzSecret, WinternitzSigningInputs};
 
-use crate::bridge::helper::{generate_stub_outpoint, get_superblock_header};
+use crate::bridge::helper::{generate_stub_outpoint, get_superblock_header, wait_for_confirmation};
 
 pub async fn create_and_mine_kick_off_1_tx(
     client: &BitVMClient,
@@ -58,12 +58,14 @@ pub async fn create_and_mine_kick_off_1_tx(
         message: ethereum_txid.as_bytes(),
         signing_key: &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
     };
-    kick_off_1.sign(
-        &operator_context,
-        &connector_6,
-        &source_network_txid_digits,
-        &destination_network_txid_digits,
-    );
+    kick_off_1
+        .sign(
+            &operator_context,
+            &connector_6,
+            &source_network_txid_digits,
+            &destination_network_txid_digits,
+        )
+        .unwrap();
 
     let kick_off_1_tx = kick_off_1.finalize();
     let kick_off_1_txid = kick_off_1_tx.compute_txid();
@@ -72,6 +74,7 @@ pub async fn create_and_mine_kick_off_1_tx(
     let kick_off_1_result = client.esplora.broadcast(&kick_off_1_tx).await;
     println!("Kick-off 1 result: {kick_off_1_result:?}");
     assert!(kick_off_1_result.is_ok());
+    wait_for_confirmation(&client.esplora, kick_off_1_txid).await;
 
     (kick_off_1_tx, kick_off_1_txid)
 }
@@ -93,18 +96,20 @@ pub async fn create_and_mine_kick_off_2_tx(
     let mut kick_off_2 =
         KickOff2Transaction::new(&operator_context, &connector_1, kick_off_2_input);
     let superblock_header = get_superblock_header();
-    kick_off_2.sign(
-        &operator_context,
-        &connector_1,
-        &WinternitzSigningInputs {
-            message: &get_superblock_message(&superblock_header),
-            signing_key: &commitment_secrets[&CommitmentMessageId::Superblock],
-        },
-        &WinternitzSigningInputs {
-            message: &get_superblock_hash_message(&superblock_header),
-            signing_key: &commitment_secrets[&CommitmentMessageId::SuperblockHash],
-        },
-    );
+    kick_off_2
+        .sign(
+            &operator_context,
+            &connector_1,
+            &WinternitzSigningInputs {
+                message: &get_superblock_message(&superblock_header),
+                signing_key: &commitment_secrets[&CommitmentMessageId::Superblock],
+            },
+            &WinternitzSigningInputs {
+                message: &get_superblock_hash_message(&superblock_header),
+                signing_key: &commitment_secrets[&CommitmentMessageId::SuperblockHash],
+            },
+        )
+        .unwrap();
     let kick_off_2_tx = kick_off_2.finalize();
     let kick_off_2_txid = kick_off_2_tx.compute_txid();
 
@@ -115,6 +120,7 @@ pub async fn create_and_mine_kick_off_2_tx(
         "error: {:?}",
         kick_off_2_result.err()
     );
+    wait_for_confirmation(&client.esplora, kick_off_2_txid).await;
 
     (kick_off_2_tx, kick_off_2_txid)
 }
@@ -157,6 +163,7 @@ pub async fn create_and_mine_assert_tx(
     // mine assert tx
     let assert_result = client.esplora.broadcast(&assert_tx).await;
     assert!(assert_result.is_ok());
+    wait_for_confirmation(&client.esplora, assert_txid).await;
 
     (assert_tx, assert_txid)
 }
@@ -193,6 +200,7 @@ pub async fn create_and_mine_peg_in_confirm_tx(
     // mine peg-in confirm
     let confirm_result = client.esplora.broadcast(&peg_in_confirm_tx).await;
     assert!(confirm_result.is_ok());
+    wait_for_confirmation(&client.esplora, peg_in_confirm_txid).await;
 
     (peg_in_confirm_tx, peg_in_confirm_txid)
 }
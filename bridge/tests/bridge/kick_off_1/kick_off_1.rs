@@ -59,12 +59,14 @@ async fn test_kick_off_1_tx() {
         message: ethereum_txid.as_bytes(),
         signing_key: &config.commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
     };
-    kick_off_1_tx.sign(
-        &config.operator_context,
-        &config.connector_6,
-        &source_network_txid_digits,
-        &destination_network_txid_digits,
-    );
+    kick_off_1_tx
+        .sign(
+            &config.operator_context,
+            &config.connector_6,
+            &source_network_txid_digits,
+            &destination_network_txid_digits,
+        )
+        .unwrap();
 
     let tx = kick_off_1_tx.finalize();
     println!("Script Path Spend Transaction: {:?}\n", tx);
@@ -43,18 +43,20 @@ async fn test_kick_off_2_tx() {
     );
 
     let superblock_header = get_superblock_header();
-    kick_off_2_tx.sign(
-        &config.operator_context,
-        &config.connector_1,
-        &WinternitzSigningInputs {
-            message: &get_superblock_message(&superblock_header),
-            signing_key: &config.commitment_secrets[&CommitmentMessageId::Superblock],
-        },
-        &WinternitzSigningInputs {
-            message: &get_superblock_hash_message(&superblock_header),
-            signing_key: &config.commitment_secrets[&CommitmentMessageId::SuperblockHash],
-        },
-    );
+    kick_off_2_tx
+        .sign(
+            &config.operator_context,
+            &config.connector_1,
+            &WinternitzSigningInputs {
+                message: &get_superblock_message(&superblock_header),
+                signing_key: &config.commitment_secrets[&CommitmentMessageId::Superblock],
+            },
+            &WinternitzSigningInputs {
+                message: &get_superblock_hash_message(&superblock_header),
+                signing_key: &config.commitment_secrets[&CommitmentMessageId::SuperblockHash],
+            },
+        )
+        .unwrap();
 
     let tx = kick_off_2_tx.finalize();
     // println!("Script Path Spend Transaction: {:?}\n", tx);
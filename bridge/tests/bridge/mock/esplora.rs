@@ -0,0 +1,152 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use bitcoin::Txid;
+use esplora_client::{AsyncClient, Builder};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    time::sleep,
+};
+
+pub const MOCK_ESPLORA_HEIGHT: u32 = 100;
+pub const MOCK_FEE_ESTIMATES: [(u16, f64); 3] = [(1, 25.0), (6, 10.0), (144, 1.5)]; // target blocks, sat/vB
+
+// Esplora server answering every tx status request with an unconfirmed status,
+// unless the tx was given a confirmation height, at a tip height of
+// `MOCK_ESPLORA_HEIGHT` and with `MOCK_FEE_ESTIMATES`.
+// Responses are held back until `expected_in_flight` requests are pending, or
+// for `hold` at most, so that requests sent one after the other show up as a
+// concurrency of one.
+pub struct MockEsplora {
+    url: String,
+    requests: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+#[derive(Clone)]
+struct Counters {
+    requests: Arc<AtomicUsize>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+impl MockEsplora {
+    pub async fn start(expected_in_flight: usize, hold: Duration) -> Self {
+        Self::start_with_confirmations(expected_in_flight, hold, HashMap::new()).await
+    }
+
+    pub async fn start_with_confirmations(
+        expected_in_flight: usize,
+        hold: Duration,
+        confirmation_heights: HashMap<Txid, u32>,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let counters = Counters {
+            requests: Arc::new(AtomicUsize::new(0)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max_in_flight: Arc::new(AtomicUsize::new(0)),
+        };
+
+        let server_counters = counters.clone();
+        let confirmation_heights = Arc::new(confirmation_heights);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(respond(
+                    stream,
+                    server_counters.clone(),
+                    confirmation_heights.clone(),
+                    expected_in_flight,
+                    hold,
+                ));
+            }
+        });
+
+        Self {
+            url,
+            requests: counters.requests,
+            max_in_flight: counters.max_in_flight,
+        }
+    }
+
+    pub fn client(&self) -> AsyncClient { Builder::new(&self.url).build_async().unwrap() }
+
+    pub fn requests(&self) -> usize { self.requests.load(Ordering::SeqCst) }
+
+    pub fn max_in_flight(&self) -> usize { self.max_in_flight.load(Ordering::SeqCst) }
+}
+
+async fn respond(
+    mut stream: TcpStream,
+    counters: Counters,
+    confirmation_heights: Arc<HashMap<Txid, u32>>,
+    expected_in_flight: usize,
+    hold: Duration,
+) {
+    // GET requests only, the request ends with its headers
+    let mut request = vec![];
+    let mut buffer = [0u8; 1024];
+    while !request.ends_with(b"\r\n\r\n") {
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => request.extend_from_slice(&buffer[..n]),
+        }
+    }
+    let request = String::from_utf8_lossy(&request);
+    let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+    counters.requests.fetch_add(1, Ordering::SeqCst);
+    let in_flight = counters.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+    counters
+        .max_in_flight
+        .fetch_max(in_flight, Ordering::SeqCst);
+
+    let held_since = Instant::now();
+    while counters.max_in_flight.load(Ordering::SeqCst) < expected_in_flight
+        && held_since.elapsed() < hold
+    {
+        sleep(Duration::from_millis(1)).await;
+    }
+
+    let status_txid = path
+        .strip_prefix("/tx/")
+        .and_then(|path| path.strip_suffix("/status"));
+    let body = if let Some(txid) = status_txid {
+        let block_height = Txid::from_str(txid)
+            .ok()
+            .and_then(|txid| confirmation_heights.get(&txid));
+        Some(match block_height {
+            Some(block_height) => format!(r#"{{"confirmed":true,"block_height":{block_height}}}"#),
+            None => r#"{"confirmed":false}"#.to_string(),
+        })
+    } else if path == "/blocks/tip/height" {
+        Some(MOCK_ESPLORA_HEIGHT.to_string())
+    } else if path == "/fee-estimates" {
+        let estimates: HashMap<String, f64> = MOCK_FEE_ESTIMATES
+            .iter()
+            .map(|(target_blocks, fee_rate)| (target_blocks.to_string(), *fee_rate))
+            .collect();
+        Some(serde_json::to_string(&estimates).unwrap())
+    } else {
+        None
+    };
+    let response = match body {
+        Some(body) => format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        ),
+        None => {
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+        }
+    };
+    counters.in_flight.fetch_sub(1, Ordering::SeqCst);
+    let _ = stream.write_all(response.as_bytes()).await;
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use bitcoin::{relative, OutPoint, Transaction, Txid};
+use esplora_client::{Error, TxStatus};
+
+use bridge::{
+    graphs::peg_out::{PegOutGraph, PegOutOperatorStatus, DEFAULT_MIN_CONFIRMATIONS},
+    transactions::pre_signed::PreSignedTransaction,
+};
+
+// Stands in for bitcoind and esplora when driving a graph through its states.
+// Scripts and signatures are not checked, only that a tx does not conflict with
+// another one and that its relative timelocks have elapsed. Outputs of txs the
+// chain does not know about count as confirmed long ago, so funding inputs and
+// txs built outside of a graph need no setup.
+pub struct InMemoryChain {
+    height: u32,
+    mempool: Vec<Transaction>,
+    confirmation_heights: HashMap<Txid, u32>,
+    spent_outpoints: HashMap<OutPoint, Txid>,
+}
+
+impl Default for InMemoryChain {
+    fn default() -> Self { Self::new() }
+}
+
+impl InMemoryChain {
+    pub fn new() -> Self {
+        Self {
+            height: 0,
+            mempool: vec![],
+            confirmation_heights: HashMap::new(),
+            spent_outpoints: HashMap::new(),
+        }
+    }
+
+    pub fn height(&self) -> u32 { self.height }
+
+    pub fn submit(&mut self, tx: &Transaction) -> Result<Txid, String> {
+        let txid = tx.compute_txid();
+        if self.confirmation_heights.contains_key(&txid)
+            || self.mempool.iter().any(|tx| tx.compute_txid() == txid)
+        {
+            return Err(format!("Tx {txid} already submitted"));
+        }
+
+        for input in &tx.input {
+            if let Some(spending_txid) = self.spent_outpoints.get(&input.previous_output) {
+                return Err(format!(
+                    "Tx {txid} conflicts with {spending_txid} on {}",
+                    input.previous_output
+                ));
+            }
+
+            // same as bitcoind, the timelock must be met by the next block
+            if let Some(relative::LockTime::Blocks(num_blocks)) =
+                input.sequence.to_relative_lock_time()
+            {
+                let parent_txid = input.previous_output.txid;
+                let is_parent_in_mempool = self
+                    .mempool
+                    .iter()
+                    .any(|tx| tx.compute_txid() == parent_txid);
+                let is_timelock_elapsed = match self.confirmation_heights.get(&parent_txid) {
+                    Some(parent_height) => {
+                        self.height + 1 >= parent_height + num_blocks.value() as u32
+                    }
+                    None => !is_parent_in_mempool || num_blocks.value() == 0,
+                };
+                if !is_timelock_elapsed {
+                    return Err(format!(
+                        "Tx {txid} spends {} before its timelock of {} blocks",
+                        input.previous_output,
+                        num_blocks.value()
+                    ));
+                }
+            }
+        }
+
+        for input in &tx.input {
+            self.spent_outpoints.insert(input.previous_output, txid);
+        }
+        self.mempool.push(tx.clone());
+
+        Ok(txid)
+    }
+
+    // The first mined block includes the whole mempool.
+    pub fn mine(&mut self, n_blocks: u32) {
+        if n_blocks == 0 {
+            return;
+        }
+        self.height += 1;
+        for tx in self.mempool.drain(..) {
+            self.confirmation_heights
+                .insert(tx.compute_txid(), self.height);
+        }
+        self.height += n_blocks - 1;
+    }
+
+    pub fn tx_status(&self, txid: &Txid) -> Result<TxStatus, Error> {
+        match self.confirmation_heights.get(txid) {
+            Some(block_height) => Ok(TxStatus {
+                confirmed: true,
+                block_height: Some(*block_height),
+                block_hash: None,
+                block_time: None,
+            }),
+            None if self.mempool.iter().any(|tx| tx.compute_txid() == *txid) => Ok(TxStatus {
+                confirmed: false,
+                block_height: None,
+                block_hash: None,
+                block_time: None,
+            }),
+            None => Err(Error::TransactionNotFound(*txid)),
+        }
+    }
+
+    pub fn spending_txid(&self, outpoint: &OutPoint) -> Option<Txid> {
+        self.spent_outpoints.get(outpoint).copied()
+    }
+
+    // Keyed like the statuses `PegOutGraph::interpret_operator_deadlines` takes.
+    // The peg-out tx is not part of the graph txids, see `PegOutGraph::txids`.
+    pub fn graph_statuses(
+        &self,
+        peg_out_graph: &PegOutGraph,
+    ) -> HashMap<&'static str, Result<TxStatus, Error>> {
+        peg_out_graph
+            .txids()
+            .iter()
+            .map(|(role, txid)| (role.tx_name(), self.tx_status(txid)))
+            .collect()
+    }
+
+    // What `PegOutGraph::operator_status` would return against this chain.
+    pub fn operator_status(&self, peg_out_graph: &PegOutGraph) -> PegOutOperatorStatus {
+        let statuses = self.graph_statuses(peg_out_graph);
+        let peg_out_status = peg_out_graph
+            .peg_out_txid()
+            .map(|peg_out_txid| self.tx_status(&peg_out_txid));
+
+        peg_out_graph.interpret_operator_status(
+            &statuses["assert_initial"],
+            &statuses["assert_final"],
+            &statuses["challenge"],
+            &statuses["disprove_chain"],
+            &statuses["disprove"],
+            &statuses["peg_out_confirm"],
+            &statuses["kick_off_1"],
+            &statuses["kick_off_2"],
+            &statuses["kick_off_timeout"],
+            peg_out_status.as_ref(),
+            &statuses["start_time_timeout"],
+            &statuses["start_time"],
+            &statuses["take_1"],
+            &statuses["take_2"],
+            self.height,
+            DEFAULT_MIN_CONFIRMATIONS,
+        )
+    }
+}
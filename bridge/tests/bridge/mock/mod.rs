@@ -1 +1,3 @@
 pub mod chain;
+pub mod esplora;
+pub mod in_memory_chain;
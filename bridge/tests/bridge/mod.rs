@@ -2,6 +2,7 @@ pub mod assert;
 pub mod base;
 pub mod challenge;
 pub mod client;
+pub mod contexts;
 pub mod data_store;
 pub mod disprove;
 pub mod disprove_chain;
@@ -14,10 +15,12 @@ pub mod kick_off_2;
 pub mod kick_off_timeout;
 pub mod mock;
 pub mod peg_in;
+pub mod peg_out_graph;
 pub mod serialization;
 pub mod setup;
 pub mod start_time;
 pub mod start_time_timeout;
+pub mod superblock;
 pub mod take_1;
 pub mod take_2;
 pub mod validate;
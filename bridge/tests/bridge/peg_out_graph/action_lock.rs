@@ -0,0 +1,146 @@
+use std::{collections::HashMap, time::Duration};
+
+use bitcoin::{hashes::Hash, OutPoint, Txid};
+use bridge::{
+    graphs::{
+        dag::TxRole,
+        peg_out::{ActionError, ActionInProgress, ChallengeError, PegOutGraph},
+    },
+    scripts::{generate_pay_to_pubkey_script, generate_pay_to_pubkey_script_address},
+    serialization::{deserialize, serialize},
+    transactions::{base::InputWithScript, challenge::ChallengeTransaction},
+};
+use serde_json::Value;
+
+use crate::bridge::mock::esplora::MockEsplora;
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_conflicting_actions() {
+    let (_, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // take 2 and disprove both spend the connector 5 and connector C outputs of assert final
+    assert!(peg_out_graph.begin_action(TxRole::Take2).is_ok());
+    assert_eq!(
+        peg_out_graph.check_action(TxRole::Disprove),
+        Err(ActionInProgress {
+            conflicting: TxRole::Take2
+        })
+    );
+    assert_eq!(
+        peg_out_graph.begin_action(TxRole::Disprove),
+        Err(ActionInProgress {
+            conflicting: TxRole::Take2
+        })
+    );
+
+    // retrying the same action and unrelated actions are not affected
+    assert!(peg_out_graph.begin_action(TxRole::Take2).is_ok());
+    assert!(peg_out_graph.begin_action(TxRole::Challenge).is_ok());
+    assert_eq!(
+        peg_out_graph.check_action(TxRole::Take1),
+        Err(ActionInProgress {
+            conflicting: TxRole::Challenge
+        })
+    );
+
+    // once take 2 is resolved, disprove can go ahead and locks take 2 out in turn
+    peg_out_graph.end_action(TxRole::Take2);
+    assert!(peg_out_graph.begin_action(TxRole::Disprove).is_ok());
+    assert_eq!(
+        peg_out_graph.check_action(TxRole::Take2),
+        Err(ActionInProgress {
+            conflicting: TxRole::Disprove
+        })
+    );
+
+    // roles without a graph tx have nothing to lock
+    assert!(peg_out_graph.begin_action(TxRole::AssertCommit1).is_ok());
+}
+
+#[tokio::test]
+async fn test_resolved_actions_released() {
+    let (_, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let take_2_txid = peg_out_graph.txids()[&TxRole::Take2];
+    let disprove_txid = peg_out_graph.txids()[&TxRole::Disprove];
+    let dag = peg_out_graph.transaction_dag();
+    let take_2_edge = dag.edges_into(TxRole::Take2).last().unwrap();
+    let take_2_outpoint = OutPoint {
+        txid: dag.nodes[&take_2_edge.from],
+        vout: take_2_edge.from_vout,
+    };
+
+    // spent by the locked tx itself, nothing is resolved yet
+    peg_out_graph.begin_action(TxRole::Take2).unwrap();
+    peg_out_graph.release_resolved_actions(&[], &HashMap::from([(take_2_outpoint, take_2_txid)]));
+    assert!(peg_out_graph.check_action(TxRole::Disprove).is_err());
+
+    // the conflicting tx got in first
+    peg_out_graph
+        .release_resolved_actions(&[], &HashMap::from([(take_2_outpoint, Txid::all_zeros())]));
+    assert!(peg_out_graph.check_action(TxRole::Disprove).is_ok());
+
+    // a confirmed tx no longer needs its lock
+    peg_out_graph.begin_action(TxRole::Disprove).unwrap();
+    peg_out_graph.release_resolved_actions(&[disprove_txid], &HashMap::new());
+    assert!(peg_out_graph.check_action(TxRole::Take2).is_ok());
+}
+
+#[tokio::test]
+async fn test_action_lock_is_not_persisted() {
+    let (_, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    peg_out_graph.begin_action(TxRole::Take2).unwrap();
+    let json = serde_json::to_string(&peg_out_graph).unwrap();
+    let deserialized_peg_out_graph: PegOutGraph = serde_json::from_str(&json).unwrap();
+
+    assert!(deserialized_peg_out_graph
+        .check_action(TxRole::Disprove)
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_action_returns_action_in_progress() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let esplora = MockEsplora::start(1, Duration::ZERO).await;
+    let keypair = &config.depositor_context.depositor_keypair;
+    let input_script =
+        generate_pay_to_pubkey_script(&config.depositor_context.depositor_public_key);
+    let refund_script = generate_pay_to_pubkey_script_address(
+        config.depositor_context.network,
+        &config.depositor_context.depositor_public_key,
+    )
+    .script_pubkey();
+    let inputs = vec![InputWithScript {
+        outpoint: OutPoint {
+            txid: Txid::all_zeros(),
+            vout: 0,
+        },
+        amount: peg_out_graph.required_challenge_funding() * 2,
+        script: &input_script,
+    }];
+
+    // a graph whose challenge tx was funded, the bump does not depend on any confirmation
+    let mut json: Value = serde_json::from_str(&serialize(&peg_out_graph)).unwrap();
+    let mut challenge_tx: ChallengeTransaction =
+        serde_json::from_value(json["challenge_transaction"].clone()).unwrap();
+    challenge_tx
+        .add_inputs_and_output_with_rbf(&inputs, keypair, refund_script, true)
+        .unwrap();
+    json["challenge_transaction"] = serde_json::to_value(&challenge_tx).unwrap();
+    let mut peg_out_graph = deserialize::<PegOutGraph>(&json.to_string());
+
+    // take 1 spends the connector A output of kick-off 1 as well
+    peg_out_graph.begin_action(TxRole::Take1).unwrap();
+    assert_eq!(
+        peg_out_graph
+            .replace_challenge_fee(&esplora.client(), &inputs, keypair, 100.0)
+            .await,
+        Err(ChallengeError::Action(ActionError::InProgress(
+            ActionInProgress {
+                conflicting: TxRole::Take1
+            }
+        )))
+    );
+}
@@ -0,0 +1,110 @@
+use std::ptr;
+
+use bridge::graphs::peg_out::{ActionKind, ActionSecrets, CommitmentMessageId, MissingSecret};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_secrets_for_kick_off_1() {
+    let (_, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    match peg_out_graph.secrets_for(ActionKind::KickOff1, &commitment_secrets) {
+        Ok(ActionSecrets::KickOff1 {
+            source_network_txid_commitment_secret,
+            destination_network_txid_commitment_secret,
+        }) => {
+            assert!(ptr::eq(
+                source_network_txid_commitment_secret,
+                &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork]
+            ));
+            assert!(ptr::eq(
+                destination_network_txid_commitment_secret,
+                &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork]
+            ));
+        }
+        _ => panic!("Expected kick-off 1 secrets"),
+    }
+}
+
+#[tokio::test]
+async fn test_secrets_for_start_time() {
+    let (_, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    match peg_out_graph.secrets_for(ActionKind::StartTime, &commitment_secrets) {
+        Ok(ActionSecrets::StartTime {
+            start_time_commitment_secret,
+        }) => assert!(ptr::eq(
+            start_time_commitment_secret,
+            &commitment_secrets[&CommitmentMessageId::StartTime]
+        )),
+        _ => panic!("Expected start time secrets"),
+    }
+}
+
+#[tokio::test]
+async fn test_secrets_for_kick_off_2() {
+    let (_, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    match peg_out_graph.secrets_for(ActionKind::KickOff2, &commitment_secrets) {
+        Ok(ActionSecrets::KickOff2 {
+            superblock_commitment_secret,
+            superblock_hash_commitment_secret,
+        }) => {
+            assert!(ptr::eq(
+                superblock_commitment_secret,
+                &commitment_secrets[&CommitmentMessageId::Superblock]
+            ));
+            assert!(ptr::eq(
+                superblock_hash_commitment_secret,
+                &commitment_secrets[&CommitmentMessageId::SuperblockHash]
+            ));
+        }
+        _ => panic!("Expected kick-off 2 secrets"),
+    }
+}
+
+#[tokio::test]
+async fn test_secrets_for_actions_without_commitments() {
+    let (_, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    for action in [
+        ActionKind::PegOutConfirm,
+        ActionKind::AssertInitial,
+        ActionKind::AssertFinal,
+        ActionKind::Take1,
+        ActionKind::Take2,
+    ] {
+        assert!(
+            peg_out_graph.secrets_for(action, &commitment_secrets) == Ok(ActionSecrets::None),
+            "{} should not need commitment secrets",
+            action.tx_name()
+        );
+        assert!(peg_out_graph.secrets_for(action, &Default::default()) == Ok(ActionSecrets::None));
+    }
+}
+
+#[tokio::test]
+async fn test_secrets_for_missing_secret() {
+    let (_, _, peg_out_graph, mut commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    commitment_secrets.remove(&CommitmentMessageId::PegOutTxIdDestinationNetwork);
+    commitment_secrets.remove(&CommitmentMessageId::SuperblockHash);
+
+    assert_eq!(
+        peg_out_graph
+            .secrets_for(ActionKind::KickOff1, &commitment_secrets)
+            .err(),
+        Some(MissingSecret(
+            CommitmentMessageId::PegOutTxIdDestinationNetwork
+        ))
+    );
+    assert_eq!(
+        peg_out_graph
+            .secrets_for(ActionKind::KickOff2, &commitment_secrets)
+            .err(),
+        Some(MissingSecret(CommitmentMessageId::SuperblockHash))
+    );
+    assert!(peg_out_graph
+        .secrets_for(ActionKind::StartTime, &commitment_secrets)
+        .is_ok());
+}
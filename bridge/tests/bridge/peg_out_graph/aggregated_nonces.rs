@@ -0,0 +1,43 @@
+use musig2::AggNonce;
+
+use bridge::{
+    graphs::base::BaseGraph,
+    transactions::{
+        pre_signed::PreSignedTransaction, pre_signed_musig2::PreSignedMusig2Transaction,
+        take_1::Take1Transaction,
+    },
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_aggregated_nonces() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    assert!(peg_out_graph.aggregated_nonces().is_empty());
+
+    let secret_nonces_0 = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    let secret_nonces_1 = peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+
+    let aggregated_nonces = peg_out_graph.aggregated_nonces();
+    assert_eq!(aggregated_nonces.len(), secret_nonces_0.len());
+    for (txid, aggregated_nonces) in &aggregated_nonces {
+        assert_eq!(aggregated_nonces.len(), secret_nonces_0[txid].len());
+        for (input_index, aggregated_nonce) in aggregated_nonces {
+            let manual_aggregated_nonce = AggNonce::sum([
+                secret_nonces_0[txid][input_index].public_nonce(),
+                secret_nonces_1[txid][input_index].public_nonce(),
+            ]);
+            assert_eq!(*aggregated_nonce, manual_aggregated_nonce);
+        }
+    }
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let take_1: Take1Transaction =
+        serde_json::from_value(json["take_1_transaction"].clone()).unwrap();
+    let take_1_txid = take_1.tx().compute_txid();
+    assert_eq!(
+        take_1.aggregated_nonce(0),
+        Some(aggregated_nonces[&take_1_txid][&0].clone())
+    );
+    assert_eq!(take_1.aggregated_nonce(1), None);
+}
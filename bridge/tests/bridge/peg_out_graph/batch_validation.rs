@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use bitcoin::{Amount, OutPoint, Txid};
+
+use bridge::{
+    contexts::operator::OperatorContext,
+    graphs::{
+        base::{BaseGraph, FEE_AMOUNT, INITIAL_AMOUNT, WITHDRAWER_SECRET},
+        peg_in::PegInGraph,
+        peg_out::{interpret_validate_graphs, PegOutGraph, PegOutGraphConfig, ValidationReport},
+    },
+    transactions::base::Input,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_validate_graphs_sharing_peg_in() {
+    let (config, peg_in_graph, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // a second operator building on the same peg-in
+    let other_operator_context = OperatorContext::new(
+        config.operator_context.network,
+        WITHDRAWER_SECRET,
+        &config.operator_context.n_of_n_public_keys,
+    )
+    .unwrap();
+    let (other_peg_out_graph, _) = PegOutGraph::new(
+        &other_operator_context,
+        &peg_in_graph,
+        vec![Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "c2a4cc4bb6d2ec6fe7b1d50ff0b2b5a26af2c85e3ca05a0ccc0c1a49ed8a4bbf",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            amount: Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1),
+        }],
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
+
+    // a graph built on a different peg-in does not belong to the batch
+    let foreign_peg_in_graph = PegInGraph::new(
+        &config.depositor_context,
+        Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "9f2ba4a3e8c0f6fdfcb3f1cbe62aa0ab1f4b5b7f8a6a1dd5e8f5b13a2f8c4e71",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            amount: Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1),
+        },
+        &config.depositor_evm_address,
+    );
+    let (foreign_peg_out_graph, _) = PegOutGraph::new(
+        &config.operator_context,
+        &foreign_peg_in_graph,
+        vec![Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+                )
+                .unwrap(),
+                vout: 1,
+            },
+            amount: Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1),
+        }],
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
+
+    let graphs = vec![
+        peg_out_graph.clone(),
+        other_peg_out_graph.clone(),
+        foreign_peg_out_graph.clone(),
+    ];
+    let reports = interpret_validate_graphs(&peg_in_graph, &graphs, true);
+
+    let valid_report = ValidationReport {
+        is_peg_in_confirmed: true,
+        is_peg_in_valid: true,
+        spends_peg_in: true,
+        is_valid: true,
+    };
+    assert_eq!(
+        reports,
+        vec![
+            (peg_out_graph.id().clone(), valid_report),
+            (other_peg_out_graph.id().clone(), valid_report),
+            (
+                foreign_peg_out_graph.id().clone(),
+                ValidationReport {
+                    spends_peg_in: false,
+                    ..valid_report
+                }
+            ),
+        ]
+    );
+    assert!(reports[0].1.is_ok() && reports[1].1.is_ok());
+    assert!(!reports[2].1.is_ok());
+    assert_ne!(reports[0].0, reports[1].0);
+
+    // graphs are not ok before their peg-in is confirmed
+    let reports = interpret_validate_graphs(&peg_in_graph, &graphs[..1], false);
+    assert!(!reports[0].1.is_peg_in_confirmed);
+    assert!(!reports[0].1.is_ok());
+}
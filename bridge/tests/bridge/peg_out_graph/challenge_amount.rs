@@ -0,0 +1,139 @@
+use std::{collections::HashMap, str::FromStr};
+
+use bitcoin::{consensus::encode::deserialize_hex, Amount, OutPoint, Transaction, TxOut, Txid};
+
+use bitvm::signatures::signing_winternitz::WinternitzSecret;
+
+use bridge::{
+    graphs::{
+        base::{FEE_AMOUNT, INITIAL_AMOUNT},
+        peg_in::PegInGraph,
+        peg_out::{CommitmentMessageId, PegOutGraph, PegOutGraphConfig, RecoveryError},
+    },
+    superblock::SuperblockPolicy,
+    transactions::base::Input,
+};
+
+use super::super::setup::{setup_test, SetupConfig};
+
+#[tokio::test]
+async fn test_challenge_amount() {
+    let config = setup_test().await;
+    let graph_config =
+        PegOutGraphConfig::default().with_challenge_amount(Amount::from_btc(2.5).unwrap());
+    let (_, peg_out_graph, _) = create_graphs(&config, graph_config.clone());
+    assert_eq!(peg_out_graph.config(), &graph_config);
+    assert_ne!(
+        PegOutGraphConfig::default().challenge_amount(),
+        graph_config.challenge_amount()
+    );
+
+    // a verifier rebuilds the graph it received with the same challenge amount
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let received_graph: PegOutGraph = serde_json::from_value(json.clone()).unwrap();
+    assert!(received_graph.validate());
+    assert_eq!(received_graph.new_for_validation().unwrap().config(), &graph_config);
+
+    let mut mismatched_json = json;
+    mismatched_json["config"] = serde_json::to_value(PegOutGraphConfig::default()).unwrap();
+    let mismatched_graph: PegOutGraph = serde_json::from_value(mismatched_json).unwrap();
+    assert!(!mismatched_graph.validate());
+}
+
+#[tokio::test]
+async fn test_recover_graph_with_challenge_amount() {
+    let config = setup_test().await;
+    let graph_config = PegOutGraphConfig::default()
+        .with_challenge_amount(Amount::from_btc(2.5).unwrap())
+        .with_take_anchors(true);
+    let (peg_in_graph, peg_out_graph, commitment_secrets) =
+        create_graphs(&config, graph_config.clone());
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let graph_tx = |tx_name: &str| -> Transaction {
+        deserialize_hex(
+            json[format!("{tx_name}_transaction")]["tx"]
+                .as_str()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+    let prev_outs: Vec<TxOut> = deserialize_hex(
+        json["peg_out_confirm_transaction"]["prev_outs"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+    let onchain_txs = [graph_tx("kick_off_1"), graph_tx("challenge")];
+    let recover = |graph_config: &PegOutGraphConfig| {
+        PegOutGraph::recover_from_txs(
+            &peg_in_graph,
+            &config.operator_context,
+            graph_config,
+            commitment_secrets.clone(),
+            SuperblockPolicy::default(),
+            &graph_tx("peg_out_confirm"),
+            &prev_outs,
+            &onchain_txs,
+        )
+    };
+
+    let recovered_graph = recover(&graph_config).unwrap();
+    assert_eq!(recovered_graph.config(), &graph_config);
+    assert_eq!(
+        recovered_graph.transaction_dag().nodes,
+        peg_out_graph.transaction_dag().nodes
+    );
+
+    // with the default config the challenge tx on chain is not reproduced
+    assert!(matches!(
+        recover(&PegOutGraphConfig::default()),
+        Err(RecoveryError::TxidMismatch {
+            tx_name: "challenge",
+            ..
+        })
+    ));
+}
+
+fn create_graphs(
+    config: &SetupConfig,
+    graph_config: PegOutGraphConfig,
+) -> (
+    PegInGraph,
+    PegOutGraph,
+    HashMap<CommitmentMessageId, WinternitzSecret>,
+) {
+    let amount = Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1);
+    let peg_in_graph = PegInGraph::new(
+        &config.depositor_context,
+        Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "0e6719ac074b0e3cac76d057643506faa1c266b322aa9cf4c6f635fe63b14327",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            amount,
+        },
+        &config.depositor_evm_address,
+    );
+    let (peg_out_graph, commitment_secrets) = PegOutGraph::new(
+        &config.operator_context,
+        &peg_in_graph,
+        vec![Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            amount,
+        }],
+        graph_config,
+    )
+    .unwrap();
+
+    (peg_in_graph, peg_out_graph, commitment_secrets)
+}
@@ -0,0 +1,128 @@
+use std::str::FromStr;
+
+use bitcoin::{consensus::encode::deserialize_hex, OutPoint, Transaction, Txid};
+
+use bridge::{
+    graphs::peg_out::{
+        ChallengeEvidence, ChallengeReason, ChallengeRecommendation, CommitmentMessageId,
+    },
+    transactions::pre_signed::PreSignedTransaction,
+};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+const KICK_OFF_1_HEIGHT: u32 = 100;
+
+fn honest_evidence(committed_source_txid: Txid) -> ChallengeEvidence {
+    ChallengeEvidence {
+        kick_off_1_height: KICK_OFF_1_HEIGHT,
+        committed_source_txid,
+        is_committed_source_tx_confirmed: true,
+        committed_start_time: Some(KICK_OFF_1_HEIGHT + 1),
+        start_time_height: Some(KICK_OFF_1_HEIGHT + 2),
+        is_superblock_in_window: Some(true),
+    }
+}
+
+#[tokio::test]
+async fn test_challenge_recommendation() {
+    let (config, _, mut peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    let peg_out_outpoint = OutPoint {
+        txid: Txid::from_str("8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182")
+            .unwrap(),
+        vout: 0,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, peg_out_outpoint);
+    peg_out_graph
+        .sign_kick_off_1(
+            &config.operator_context,
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
+        )
+        .unwrap();
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let kick_off_1_tx: Transaction =
+        deserialize_hex(json["kick_off_1_transaction"]["tx"].as_str().unwrap()).unwrap();
+    let committed_source_txid = peg_out_graph
+        .get_committed_source_network_txid(&kick_off_1_tx)
+        .unwrap();
+
+    // honest operator, also before start time and kick-off 2 are on chain
+    let evidence = honest_evidence(committed_source_txid);
+    assert_eq!(
+        peg_out_graph.interpret_challenge_evidence(&evidence),
+        ChallengeRecommendation::NoAction
+    );
+    let evidence = ChallengeEvidence {
+        committed_start_time: None,
+        start_time_height: None,
+        is_superblock_in_window: None,
+        ..honest_evidence(committed_source_txid)
+    };
+    assert_eq!(
+        peg_out_graph.interpret_challenge_evidence(&evidence),
+        ChallengeRecommendation::NoAction
+    );
+
+    // committed peg-out tx never confirmed
+    let evidence = ChallengeEvidence {
+        is_committed_source_tx_confirmed: false,
+        ..honest_evidence(committed_source_txid)
+    };
+    assert_eq!(
+        peg_out_graph.interpret_challenge_evidence(&evidence),
+        ChallengeRecommendation::Challenge {
+            reason: ChallengeReason::PegOutNotConfirmed(committed_source_txid)
+        }
+    );
+
+    // start time committed ahead of the start time tx
+    let evidence = ChallengeEvidence {
+        committed_start_time: Some(KICK_OFF_1_HEIGHT + 3),
+        ..honest_evidence(committed_source_txid)
+    };
+    assert_eq!(
+        peg_out_graph.interpret_challenge_evidence(&evidence),
+        ChallengeRecommendation::Challenge {
+            reason: ChallengeReason::StartTimeOutOfRange {
+                start_time: KICK_OFF_1_HEIGHT + 3,
+                kick_off_1_height: KICK_OFF_1_HEIGHT,
+                start_time_height: KICK_OFF_1_HEIGHT + 2,
+            }
+        }
+    );
+
+    // superblock outside of the measurement period
+    let evidence = ChallengeEvidence {
+        is_superblock_in_window: Some(false),
+        ..honest_evidence(committed_source_txid)
+    };
+    assert_eq!(
+        peg_out_graph.interpret_challenge_evidence(&evidence),
+        ChallengeRecommendation::Challenge {
+            reason: ChallengeReason::SuperblockOutsideWindow
+        }
+    );
+
+    // kick-off 1 commits to another peg-out tx than the one paying the withdrawer
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: peg_out_outpoint.txid,
+            vout: 1,
+        },
+    );
+    let peg_out_txid = peg_out_graph.peg_out_txid().unwrap();
+    let evidence = honest_evidence(committed_source_txid);
+    assert_eq!(
+        peg_out_graph.interpret_challenge_evidence(&evidence),
+        ChallengeRecommendation::Challenge {
+            reason: ChallengeReason::SourceTxidMismatch {
+                committed_txid: committed_source_txid,
+                peg_out_txid,
+            }
+        }
+    );
+}
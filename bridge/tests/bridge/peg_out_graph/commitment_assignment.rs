@@ -0,0 +1,40 @@
+use std::collections::{BTreeSet, HashSet};
+
+use bitvm::chunker::assigner::BridgeAssigner;
+
+use bridge::graphs::peg_out::ConnectorRef;
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_commitment_assignment_assigns_each_variable_once() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let assignment = peg_out_graph.commitment_assignment();
+
+    let expected_variables: BTreeSet<String> = BridgeAssigner::default()
+        .all_intermediate_variable()
+        .into_keys()
+        .collect();
+    let assigned_variables: BTreeSet<String> = assignment.keys().cloned().collect();
+    assert_eq!(assigned_variables, expected_variables);
+
+    // each E connector commits a single value, so no two variables share a position
+    let positions: HashSet<(ConnectorRef, usize)> = assignment.values().copied().collect();
+    assert_eq!(positions.len(), assignment.len());
+
+    for connector_ref in [ConnectorRef::E1, ConnectorRef::E2] {
+        let indices: BTreeSet<usize> = positions
+            .iter()
+            .filter(|(assigned_ref, _)| *assigned_ref == connector_ref)
+            .map(|(_, index)| *index)
+            .collect();
+        assert!(
+            indices.iter().copied().eq(0..indices.len()),
+            "{connector_ref:?} connectors are not assigned contiguously"
+        );
+    }
+    assert!(positions
+        .iter()
+        .any(|(connector_ref, _)| *connector_ref == ConnectorRef::E1));
+}
@@ -0,0 +1,28 @@
+use bitvm::signatures::signing_winternitz::WinternitzPublicKey;
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_all_winternitz_public_keys() {
+    let (_, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    let public_keys = peg_out_graph.all_winternitz_public_keys();
+    assert_eq!(public_keys.len(), commitment_secrets.len());
+    for (message_id, secret) in &commitment_secrets {
+        assert!(
+            public_keys.get(message_id) == Some(&WinternitzPublicKey::from(secret)),
+            "missing public key for {message_id:?}"
+        );
+    }
+
+    // every commitment is carried by exactly one connector
+    let num_keys: usize = peg_out_graph
+        .commitment_connectors()
+        .iter()
+        .map(|(_, public_keys)| public_keys.len())
+        .sum();
+    assert_eq!(num_keys, commitment_secrets.len());
+    let connectors = peg_out_graph.commitment_connectors();
+    assert_eq!(connectors[0].0, "connector_1");
+    assert!(connectors.iter().any(|(name, _)| name == "connector_e_2_0"));
+}
@@ -0,0 +1,27 @@
+use bitcoin::hex::DisplayHex;
+
+use bridge::serialization::serialize;
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_commitment_secrets_are_not_leaked() {
+    let (_, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    let json = serialize(&peg_out_graph);
+    let debug = format!("{:?}", commitment_secrets);
+
+    for secret in commitment_secrets.values() {
+        let secret_key: Vec<u8> =
+            serde_json::from_value(serde_json::to_value(secret).unwrap()["secret_key"].clone())
+                .unwrap();
+        let secret_key_string = String::from_utf8(secret_key.clone()).unwrap();
+
+        // the graph is pushed to the remote data store and must only carry public keys
+        assert!(!json.contains(&secret_key_string));
+        assert!(!json.contains(&secret_key.to_lower_hex_string()));
+
+        assert!(!debug.contains(&secret_key_string));
+        assert!(!debug.contains(&format!("{:?}", secret_key)));
+    }
+}
@@ -0,0 +1,40 @@
+use bridge::{connectors::base::WrongCommitmentSecret, graphs::peg_out::CommitmentMessageId};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_verify_commitment_secrets() {
+    let (_, _, peg_out_graph, mut commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    assert_eq!(
+        peg_out_graph.verify_commitment_secrets(&commitment_secrets),
+        Ok(())
+    );
+
+    // both txid commitments have the same size, only the public keys tell them apart
+    let source_secret = commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork].clone();
+    let destination_secret = commitment_secrets
+        .insert(
+            CommitmentMessageId::PegOutTxIdDestinationNetwork,
+            source_secret,
+        )
+        .unwrap();
+    commitment_secrets.insert(
+        CommitmentMessageId::PegOutTxIdSourceNetwork,
+        destination_secret,
+    );
+    assert_eq!(
+        peg_out_graph.verify_commitment_secrets(&commitment_secrets),
+        Err(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::PegOutTxIdSourceNetwork
+        })
+    );
+
+    commitment_secrets.remove(&CommitmentMessageId::StartTime);
+    assert_eq!(
+        peg_out_graph.verify_commitment_secrets(&commitment_secrets),
+        Err(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::StartTime
+        })
+    );
+}
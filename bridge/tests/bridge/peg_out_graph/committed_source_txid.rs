@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use bitcoin::{consensus::encode::deserialize_hex, OutPoint, Transaction, Txid, Witness};
+use esplora_client::{Error, TxStatus};
+
+use bridge::{
+    graphs::peg_out::{CommitmentMessageId, PegOutGraph},
+    transactions::pre_signed::PreSignedTransaction,
+};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+fn kick_off_1_tx(peg_out_graph: &PegOutGraph) -> Transaction {
+    let json = serde_json::to_value(peg_out_graph).unwrap();
+    deserialize_hex(json["kick_off_1_transaction"]["tx"].as_str().unwrap()).unwrap()
+}
+
+fn peg_out_status(confirmed: bool) -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed,
+        block_height: confirmed.then_some(100),
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+#[tokio::test]
+async fn test_committed_source_txid() {
+    let (config, _, mut peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    let peg_out_outpoint = OutPoint {
+        txid: Txid::from_str("8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182")
+            .unwrap(),
+        vout: 0,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, peg_out_outpoint);
+    peg_out_graph
+        .sign_kick_off_1(
+            &config.operator_context,
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
+        )
+        .unwrap();
+    let kick_off_1_tx = kick_off_1_tx(&peg_out_graph);
+    let peg_out_txid = peg_out_graph.peg_out_txid().unwrap();
+
+    assert_eq!(
+        peg_out_graph.get_committed_source_network_txid(&kick_off_1_tx),
+        Some(peg_out_txid)
+    );
+    assert_eq!(
+        peg_out_graph.interpret_committed_source_txid(&kick_off_1_tx, &peg_out_status(true)),
+        Ok(true)
+    );
+    // the committed peg-out tx has to be confirmed on the source network
+    assert_eq!(
+        peg_out_graph.interpret_committed_source_txid(&kick_off_1_tx, &peg_out_status(false)),
+        Ok(false)
+    );
+
+    // the peg-out tx observed on chain differs from the one kick-off 1 commits to
+    let other_peg_out_outpoint = OutPoint {
+        txid: peg_out_outpoint.txid,
+        vout: 1,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, other_peg_out_outpoint);
+    assert_eq!(
+        peg_out_graph.interpret_committed_source_txid(&kick_off_1_tx, &peg_out_status(true)),
+        Ok(false)
+    );
+}
+
+#[tokio::test]
+async fn test_committed_source_txid_without_commitment() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let peg_out_outpoint = OutPoint {
+        txid: Txid::from_str("8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182")
+            .unwrap(),
+        vout: 0,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, peg_out_outpoint);
+
+    // kick-off 1 has not been signed, so there is nothing to decode
+    let mut kick_off_1_tx = kick_off_1_tx(&peg_out_graph);
+    kick_off_1_tx.input[0].witness = Witness::new();
+
+    assert_eq!(
+        peg_out_graph.get_committed_source_network_txid(&kick_off_1_tx),
+        None
+    );
+    assert!(peg_out_graph
+        .interpret_committed_source_txid(&kick_off_1_tx, &peg_out_status(true))
+        .is_err());
+}
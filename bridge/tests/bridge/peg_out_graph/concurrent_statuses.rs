@@ -0,0 +1,37 @@
+use std::{str::FromStr, time::Duration};
+
+use bitcoin::{OutPoint, Txid};
+
+use bridge::graphs::{base::BaseGraph, peg_out::GraphPhase};
+
+use crate::bridge::mock::esplora::MockEsplora;
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+#[tokio::test]
+async fn test_peg_out_statuses_queried_concurrently() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    // the 13 graph txs and the peg-out tx
+    let esplora = MockEsplora::start(14, Duration::from_secs(1)).await;
+    let phase = peg_out_graph.phase(&esplora.client()).await;
+
+    assert_eq!(phase, GraphPhase::Setup);
+    assert_eq!(esplora.requests(), 14);
+    assert_eq!(esplora.max_in_flight(), 14);
+}
@@ -0,0 +1,143 @@
+use std::str::FromStr;
+
+use bitcoin::{OutPoint, Txid};
+use esplora_client::{Error, TxStatus};
+
+use bridge::graphs::{
+    base::{tx_confirmations, BaseGraph},
+    dag::TxRole,
+    peg_out::{PegOutGraph, PegOutOperatorStatus, WaitReason, DEFAULT_MIN_CONFIRMATIONS},
+};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+const TAKE_1_HEIGHT: u32 = 120;
+
+fn confirmed(block_height: u32) -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed: true,
+        block_height: Some(block_height),
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+fn unconfirmed() -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed: false,
+        block_height: None,
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+async fn setup_presigned_peg_out_graph() -> PegOutGraph {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    peg_out_graph
+}
+
+// Status of a graph whose take 1 confirmed at TAKE_1_HEIGHT.
+fn take_1_status(
+    peg_out_graph: &PegOutGraph,
+    blockchain_height: u32,
+    min_confirmations: u32,
+) -> PegOutOperatorStatus {
+    peg_out_graph.interpret_operator_status(
+        &unconfirmed(),
+        &unconfirmed(),
+        &unconfirmed(),
+        &unconfirmed(),
+        &unconfirmed(),
+        &confirmed(TAKE_1_HEIGHT - 20),
+        &confirmed(TAKE_1_HEIGHT - 19),
+        &confirmed(TAKE_1_HEIGHT - 10),
+        &unconfirmed(),
+        Some(&confirmed(TAKE_1_HEIGHT - 21)),
+        &unconfirmed(),
+        &confirmed(TAKE_1_HEIGHT - 18),
+        &confirmed(TAKE_1_HEIGHT),
+        &unconfirmed(),
+        blockchain_height,
+        min_confirmations,
+    )
+}
+
+#[test]
+fn test_tx_confirmations() {
+    assert_eq!(tx_confirmations(&unconfirmed(), TAKE_1_HEIGHT), 0);
+    assert_eq!(
+        tx_confirmations(&confirmed(TAKE_1_HEIGHT), TAKE_1_HEIGHT),
+        1
+    );
+    assert_eq!(
+        tx_confirmations(&confirmed(TAKE_1_HEIGHT), TAKE_1_HEIGHT + 5),
+        6
+    );
+    // a tip that lags behind the status
+    assert_eq!(
+        tx_confirmations(&confirmed(TAKE_1_HEIGHT), TAKE_1_HEIGHT - 1),
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_operator_status_waits_for_min_confirmations() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+
+    let status = take_1_status(&peg_out_graph, TAKE_1_HEIGHT, 6);
+    let take_1_txid = peg_out_graph.txids()[&TxRole::Take1];
+    assert!(matches!(
+        status,
+        PegOutOperatorStatus::PegOutWait(WaitReason::AwaitingDepth {
+            txid,
+            confirmations: 1,
+        }) if txid == take_1_txid
+    ));
+
+    assert!(matches!(
+        take_1_status(&peg_out_graph, TAKE_1_HEIGHT + 4, 6),
+        PegOutOperatorStatus::PegOutWait(WaitReason::AwaitingDepth {
+            confirmations: 5,
+            ..
+        })
+    ));
+    assert!(matches!(
+        take_1_status(&peg_out_graph, TAKE_1_HEIGHT + 5, 6),
+        PegOutOperatorStatus::PegOutComplete
+    ));
+}
+
+#[tokio::test]
+async fn test_operator_status_single_confirmation_by_default() {
+    let (config, _, _, _) = setup_and_create_stub_graphs().await;
+    assert_eq!(
+        config.operator_context.min_confirmations,
+        DEFAULT_MIN_CONFIRMATIONS
+    );
+
+    // the depth is a local policy, it is not part of the graph data
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    assert!(json["config"].get("min_confirmations").is_none());
+
+    assert!(matches!(
+        take_1_status(&peg_out_graph, TAKE_1_HEIGHT, DEFAULT_MIN_CONFIRMATIONS),
+        PegOutOperatorStatus::PegOutComplete
+    ));
+}
@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+use bitcoin::{key::TweakedPublicKey, Address, Network, XOnlyPublicKey};
+
+use bridge::connectors::{
+    base::{add_descriptor_checksum, descriptor_checksum, TaprootConnector},
+    connector_5::Connector5,
+    connector_c::ConnectorC,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+fn descriptor_address(descriptor: &str, network: Network) -> Address {
+    let (body, checksum) = descriptor.split_once('#').unwrap();
+    assert_eq!(descriptor_checksum(body), checksum);
+
+    let output_key = body
+        .strip_prefix("rawtr(")
+        .and_then(|key| key.strip_suffix(')'))
+        .unwrap();
+    Address::p2tr_tweaked(
+        TweakedPublicKey::dangerous_assume_tweaked(XOnlyPublicKey::from_str(output_key).unwrap()),
+        network,
+    )
+}
+
+#[test]
+fn test_descriptor_checksum() {
+    // test vector from BIP-380
+    assert_eq!(
+        add_descriptor_checksum("raw(deadbeef)"),
+        "raw(deadbeef)#89f8spxm"
+    );
+}
+
+#[tokio::test]
+async fn test_descriptors_derive_connector_addresses() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let network = config.operator_context.network;
+
+    let descriptors = peg_out_graph.descriptors();
+    assert!(descriptors.contains_key("connector_e_1_0"));
+    assert!(descriptors.contains_key("connector_e_2_0"));
+
+    let graph = serde_json::to_value(&peg_out_graph).unwrap();
+    let connector_5: Connector5 = serde_json::from_value(graph["connector_5"].clone()).unwrap();
+    let connector_c: ConnectorC = serde_json::from_value(graph["connector_c"].clone()).unwrap();
+
+    for (name, connector) in [
+        ("connector_5", &connector_5 as &dyn TaprootConnector),
+        ("connector_c", &connector_c),
+    ] {
+        assert_eq!(descriptors[name], connector.descriptor());
+        assert_eq!(
+            descriptor_address(&descriptors[name], network),
+            connector.generate_taproot_address()
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use ark_bn254::G1Affine;
+use ark_ff::UniformRand as _;
+use ark_std::test_rng;
+use bitvm::chunker::disprove_execution::RawProof;
+use rand::{RngCore as _, SeedableRng as _};
+
+use bridge::{
+    graphs::peg_out::{AssertCommitWitnessError, DisproveError},
+    transactions::assert_transactions::utils::sign_assert_tx_with_groth16_proof,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_disprove_witness_from_assert_commit_txs() {
+    let (_, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    // the operator commits to a proof that does not verify
+    let mut wrong_proof = RawProof::default();
+    let mut rng = ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+    wrong_proof.proof.a = G1Affine::rand(&mut rng);
+    assert!(!wrong_proof.valid_proof());
+
+    let (assert_commit_1_tx, assert_commit_2_tx) =
+        peg_out_graph.assert_commit_transactions(&commitment_secrets, &wrong_proof);
+    let commit_witnesses = peg_out_graph
+        .assert_commit_witnesses(&assert_commit_1_tx, &assert_commit_2_tx)
+        .unwrap();
+    assert_eq!(
+        commit_witnesses,
+        sign_assert_tx_with_groth16_proof(&commitment_secrets, &wrong_proof)
+    );
+    assert!(peg_out_graph
+        .generate_disprove_witness(commit_witnesses)
+        .is_ok());
+
+    assert_eq!(
+        peg_out_graph.assert_commit_witnesses(&assert_commit_2_tx, &assert_commit_1_tx),
+        Err(AssertCommitWitnessError::NotAssertCommitTx(
+            assert_commit_2_tx.compute_txid()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_disprove_witness_for_valid_assertions() {
+    let (_, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    let proof = RawProof::default();
+    let (assert_commit_1_tx, assert_commit_2_tx) =
+        peg_out_graph.assert_commit_transactions(&commitment_secrets, &proof);
+    let commit_witnesses = peg_out_graph
+        .assert_commit_witnesses(&assert_commit_1_tx, &assert_commit_2_tx)
+        .unwrap();
+    assert_eq!(
+        peg_out_graph.generate_disprove_witness(commit_witnesses),
+        Err(DisproveError::AssertionsValid)
+    );
+}
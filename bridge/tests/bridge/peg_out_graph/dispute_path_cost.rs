@@ -0,0 +1,40 @@
+use bitcoin::{consensus::encode::deserialize_hex, Amount, TxOut};
+
+use bridge::graphs::{base::FEE_AMOUNT, peg_out::DisputePathCost};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_dispute_path_cost() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let cost = peg_out_graph.dispute_path_cost();
+    assert_eq!(
+        cost,
+        DisputePathCost {
+            assert_initial_fee: Amount::from_sat(100 * FEE_AMOUNT),
+            assert_commit_fees: Amount::from_sat(2 * 100 * FEE_AMOUNT),
+            assert_final_fee: Amount::from_sat(FEE_AMOUNT),
+            take_2_fee: Amount::from_sat(FEE_AMOUNT),
+            take_2_payout: cost.take_2_payout,
+            challenge_funding: Amount::from_btc(1.0).unwrap(),
+            challenge_fee: Amount::from_sat(FEE_AMOUNT),
+            disprove_fee: Amount::from_sat(100 * FEE_AMOUNT),
+        }
+    );
+    assert_eq!(cost.operator_cost(), Amount::from_sat(302 * FEE_AMOUNT));
+    assert_eq!(
+        cost.verifier_cost(),
+        Amount::from_btc(1.0).unwrap() + Amount::from_sat(101 * FEE_AMOUNT)
+    );
+
+    // take 2 pays out the peg-in deposit and whatever assert final passes on
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let take_2_prev_outs: Vec<TxOut> =
+        deserialize_hex(json["take_2_transaction"]["prev_outs"].as_str().unwrap()).unwrap();
+    let take_2_input_amount: Amount = take_2_prev_outs.iter().map(|out| out.value).sum();
+    assert_eq!(
+        cost.take_2_payout,
+        take_2_input_amount - Amount::from_sat(FEE_AMOUNT)
+    );
+}
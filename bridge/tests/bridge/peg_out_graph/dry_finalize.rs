@@ -0,0 +1,72 @@
+use bridge::{
+    graphs::{base::BaseGraph, dag::TxRole, peg_out::PegOutGraph},
+    transactions::pre_signed_musig2::FinalizeError,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_dry_finalize_all_detects_corrupted_partial_signature() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let verifier_1_public_key = config.verifier_1_context.verifier_public_key;
+
+    let secret_nonces_0 = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    let secret_nonces_1 = peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+
+    // nothing to finalize before the signing round
+    assert_eq!(peg_out_graph.dry_finalize_all(), Ok(()));
+
+    let mut verifier_0_graph = peg_out_graph.clone();
+    verifier_0_graph.verifier_sign(&config.verifier_0_context, &secret_nonces_0);
+    let mut verifier_1_graph = peg_out_graph.clone();
+    verifier_1_graph.verifier_sign(&config.verifier_1_context, &secret_nonces_1);
+    let mut bundle = verifier_1_graph.signature_bundle(&verifier_1_public_key);
+
+    let mut signed_graph = verifier_0_graph.clone();
+    signed_graph.apply_bundle(&bundle).unwrap();
+    assert!(signed_graph.has_all_signatures(&[
+        config.verifier_0_context.verifier_public_key,
+        verifier_1_public_key
+    ]));
+    assert_eq!(signed_graph.dry_finalize_all(), Ok(()));
+    assert!(signed_graph.validate());
+
+    // a graph stored before the individual n-of-n keys were kept cannot be checked
+    let mut json = serde_json::to_value(&signed_graph).unwrap();
+    json.as_object_mut().unwrap().remove("n_of_n_public_keys");
+    let stored_graph: PegOutGraph = serde_json::from_value(json).unwrap();
+    let errors = stored_graph.dry_finalize_all().unwrap_err();
+    assert!(errors.iter().any(|(tx_name, _)| tx_name == "take_1"));
+    assert!(errors
+        .iter()
+        .all(|(_, err)| *err == FinalizeError::MissingPublicKeys));
+    assert!(!stored_graph.validate());
+
+    // swap verifier 1's partial signatures for the two take 1 inputs
+    let take_1_sigs = &mut bundle
+        .per_tx
+        .get_mut(&peg_out_graph.txids()[&TxRole::Take1])
+        .unwrap()
+        .musig2_signatures;
+    let input_0_sig = take_1_sigs[&0][&verifier_1_public_key];
+    let input_3_sig = take_1_sigs[&3][&verifier_1_public_key];
+    take_1_sigs
+        .get_mut(&0)
+        .unwrap()
+        .insert(verifier_1_public_key, input_3_sig);
+    take_1_sigs
+        .get_mut(&3)
+        .unwrap()
+        .insert(verifier_1_public_key, input_0_sig);
+
+    let mut corrupted_graph = verifier_0_graph.clone();
+    corrupted_graph.apply_bundle(&bundle).unwrap();
+    assert_eq!(
+        corrupted_graph.dry_finalize_all(),
+        Err(vec![(
+            String::from("take_1"),
+            FinalizeError::InvalidSignature(0)
+        )])
+    );
+    assert!(!corrupted_graph.validate());
+}
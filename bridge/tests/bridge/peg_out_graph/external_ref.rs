@@ -0,0 +1,50 @@
+use bridge::graphs::{base::BaseGraph, peg_out::PegOutGraph};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_external_ref() {
+    let (_, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let id = peg_out_graph.id().clone();
+    assert_eq!(peg_out_graph.external_ref(), None);
+
+    peg_out_graph.set_external_ref(Some("ticket-1234".to_string()));
+    assert_eq!(peg_out_graph.external_ref(), Some("ticket-1234"));
+    assert_eq!(peg_out_graph.id(), &id);
+
+    // the ref is stored with the graph
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    assert_eq!(json["external_ref"], serde_json::json!("ticket-1234"));
+    let stored_graph: PegOutGraph = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(stored_graph.external_ref(), Some("ticket-1234"));
+    assert_eq!(stored_graph.id(), &id);
+    assert!(stored_graph == peg_out_graph);
+
+    // graphs stored before external refs were kept load without one
+    let mut legacy_json = json;
+    legacy_json.as_object_mut().unwrap().remove("external_ref");
+    let legacy_graph: PegOutGraph = serde_json::from_value(legacy_json).unwrap();
+    assert_eq!(legacy_graph.external_ref(), None);
+}
+
+#[tokio::test]
+async fn test_external_ref_does_not_affect_validation() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // each party tags the same graph with its own record id
+    let mut operator_graph = peg_out_graph.clone();
+    operator_graph.set_external_ref(Some("operator-ledger-7".to_string()));
+    let mut verifier_graph = peg_out_graph.clone();
+    verifier_graph.set_external_ref(Some("verifier-ticket-42".to_string()));
+
+    assert!(operator_graph.validate());
+    assert!(verifier_graph.validate());
+    // also once the graph is loaded with the ref of the other party
+    let loaded_graph: PegOutGraph =
+        serde_json::from_value(serde_json::to_value(&operator_graph).unwrap()).unwrap();
+    assert_eq!(loaded_graph.external_ref(), Some("operator-ledger-7"));
+    assert!(loaded_graph.validate());
+    assert_eq!(operator_graph.new_for_validation().unwrap().external_ref(), None);
+    assert_eq!(operator_graph.id(), verifier_graph.id());
+    assert_eq!(operator_graph.txids(), verifier_graph.txids());
+}
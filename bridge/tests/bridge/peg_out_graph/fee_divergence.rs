@@ -0,0 +1,86 @@
+use bitcoin::{
+    consensus::encode::{deserialize_hex, serialize_hex},
+    Amount, Transaction, TxOut,
+};
+
+use bridge::{
+    graphs::peg_out::{CommitmentMessageId, PegOutGraph, ValidationRebuildError},
+    transactions::base::FeeDivergence,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+fn with_take_1_tx(peg_out_graph: &PegOutGraph, take_1_tx: &Transaction) -> PegOutGraph {
+    let mut json = serde_json::to_value(peg_out_graph).unwrap();
+    json["take_1_transaction"]["tx"] = serialize_hex(take_1_tx).into();
+
+    serde_json::from_value(json).unwrap()
+}
+
+#[tokio::test]
+async fn test_fee_divergence() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    assert!(peg_out_graph.fee_divergences().unwrap().is_empty());
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let mut take_1_tx: Transaction =
+        deserialize_hex(json["take_1_transaction"]["tx"].as_str().unwrap()).unwrap();
+    let prev_outs: Vec<TxOut> =
+        deserialize_hex(json["take_1_transaction"]["prev_outs"].as_str().unwrap()).unwrap();
+    let input_amount: Amount = prev_outs.iter().map(|out| out.value).sum();
+    let expected_fee = input_amount - take_1_tx.output[0].value;
+
+    // the operator built take 1 with a higher fee than the verifier expects
+    let extra_fee = Amount::from_sat(500);
+    take_1_tx.output[0].value -= extra_fee;
+    let diverging_graph = with_take_1_tx(&peg_out_graph, &take_1_tx);
+
+    assert!(!diverging_graph.validate());
+    assert_eq!(
+        diverging_graph.fee_divergences(),
+        Ok(vec![(
+            "take_1",
+            FeeDivergence {
+                expected_fee,
+                actual_fee: expected_fee + extra_fee,
+            }
+        )])
+    );
+
+    // paying out to another script is not a fee-only difference
+    take_1_tx.output[0].script_pubkey = prev_outs[0].script_pubkey.clone();
+    let diverging_graph = with_take_1_tx(&peg_out_graph, &take_1_tx);
+
+    assert!(!diverging_graph.validate());
+    assert!(diverging_graph.fee_divergences().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_fee_divergence_malformed_graph() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+
+    // take 1 without the inputs the rebuild reads the peg-in confirm txid from
+    let mut take_1_tx: Transaction =
+        deserialize_hex(json["take_1_transaction"]["tx"].as_str().unwrap()).unwrap();
+    take_1_tx.input.clear();
+    let malformed_graph = with_take_1_tx(&peg_out_graph, &take_1_tx);
+    assert_eq!(
+        malformed_graph.fee_divergences(),
+        Err(ValidationRebuildError::MalformedTxs(vec!["take_1"]))
+    );
+    assert!(!malformed_graph.validate());
+
+    // connector 2 without the key of the start time commitment
+    let mut stripped_json = json;
+    stripped_json["connector_2"]["commitment_public_keys"]
+        .as_object_mut()
+        .unwrap()
+        .remove("StartTime");
+    let stripped_graph: PegOutGraph = serde_json::from_value(stripped_json).unwrap();
+    assert_eq!(
+        stripped_graph.fee_divergences(),
+        Err(ValidationRebuildError::MissingCommitmentKey(CommitmentMessageId::StartTime))
+    );
+    assert!(!stripped_graph.validate());
+}
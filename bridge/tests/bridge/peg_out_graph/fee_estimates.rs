@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use bridge::graphs::fee_estimator::{EsploraFeeEstimator, FeeEstimator};
+
+use crate::bridge::mock::esplora::MockEsplora;
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_fee_adjustable_txs() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // kick-off 1 and peg-out confirm are not presigned, but presigned txs spend them
+    assert_eq!(
+        peg_out_graph.fee_adjustable_txs(),
+        vec!["peg_out", "challenge", "start_time"]
+    );
+    let fixed_fee_txs = peg_out_graph.fixed_fee_txs();
+    assert_eq!(fixed_fee_txs.len(), 11);
+    for tx_name in [
+        "peg_out_confirm",
+        "kick_off_1",
+        "kick_off_2",
+        "take_1",
+        "take_2",
+    ] {
+        assert!(fixed_fee_txs.contains(&tx_name), "{tx_name}");
+    }
+}
+
+#[tokio::test]
+async fn test_recommended_fee_rate() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let esplora = MockEsplora::start(1, Duration::ZERO).await;
+    let client = esplora.client();
+
+    // the estimate of the closest target that is not later than the one asked for
+    for (target_blocks, fee_rate) in [(1, 25.0), (3, 25.0), (6, 10.0), (1008, 1.5)] {
+        assert_eq!(
+            peg_out_graph
+                .recommended_fee_rate(&client, target_blocks)
+                .await,
+            Ok(fee_rate),
+            "{target_blocks}"
+        );
+    }
+
+    assert!(EsploraFeeEstimator::new(&client).fee_rate(0).await.is_err());
+    assert!(peg_out_graph
+        .recommended_fee_rate(&client, 0)
+        .await
+        .is_err());
+}
@@ -0,0 +1,123 @@
+use std::{collections::HashMap, str::FromStr};
+
+use bitcoin::{consensus::encode::deserialize_hex, Amount, OutPoint, Transaction, Txid};
+use esplora_client::{Error, TxStatus};
+
+use bridge::{
+    graphs::{
+        base::BaseGraph,
+        peg_out::{interpret_aggregate_metrics, GraphPhase, PegOutGraph, TakeKind},
+    },
+    transactions::pre_signed::PreSignedTransaction,
+};
+
+use crate::bridge::mock::in_memory_chain::InMemoryChain;
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+const HEIGHT: u32 = 20;
+
+fn graph_tx(json: &serde_json::Value, tx_name: &str) -> Transaction {
+    deserialize_hex(
+        json[format!("{tx_name}_transaction")]["tx"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+// Each graph gets a chain of its own, as the stub graphs all share the same txs.
+fn statuses_after(
+    peg_out_graph: &PegOutGraph,
+    tx_names: &[&str],
+) -> HashMap<&'static str, Result<TxStatus, Error>> {
+    let json = serde_json::to_value(peg_out_graph).unwrap();
+    let mut chain = InMemoryChain::new();
+    for tx_name in tx_names {
+        chain.submit(&graph_tx(&json, tx_name)).unwrap();
+        chain.mine(1);
+    }
+    chain.mine(HEIGHT - chain.height());
+
+    let mut statuses = chain.graph_statuses(peg_out_graph);
+    if let Some(peg_out_txid) = peg_out_graph.peg_out_txid() {
+        statuses.insert("peg_out", chain.tx_status(&peg_out_txid));
+    }
+    statuses
+}
+
+#[tokio::test]
+async fn test_fleet_metrics() {
+    let (config, _, unsigned_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut peg_out_graph = unsigned_graph.clone();
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    let in_flight = ["peg_out", "peg_out_confirm", "kick_off_1"];
+    let graph_statuses = vec![
+        (&unsigned_graph, statuses_after(&unsigned_graph, &[])),
+        (&peg_out_graph, statuses_after(&peg_out_graph, &["peg_out"])),
+        (&peg_out_graph, statuses_after(&peg_out_graph, &in_flight)),
+        (
+            &peg_out_graph,
+            statuses_after(
+                &peg_out_graph,
+                &[&in_flight[..], &["start_time", "kick_off_2", "challenge"]].concat(),
+            ),
+        ),
+        (
+            &peg_out_graph,
+            statuses_after(
+                &peg_out_graph,
+                &[&in_flight[..], &["kick_off_timeout"]].concat(),
+            ),
+        ),
+    ];
+
+    let metrics = interpret_aggregate_metrics(&graph_statuses, HEIGHT);
+    for phase in [
+        GraphPhase::Setup,
+        GraphPhase::Funded,
+        GraphPhase::InFlight,
+        GraphPhase::Disputed,
+        GraphPhase::Settled,
+    ] {
+        assert_eq!(metrics.phase_counts[&phase], 1, "{phase}");
+    }
+    assert_eq!(metrics.failed, 1);
+    assert_eq!(
+        metrics.awaiting_reimbursement,
+        peg_out_graph.take_payout(TakeKind::Take1) * 3
+    );
+    assert_eq!(
+        metrics.dispute_costs,
+        peg_out_graph.dispute_path_cost().operator_cost()
+    );
+
+    // timelocks are not enforced off mainnet, both deadlines have passed
+    assert_eq!(metrics.imminent_deadlines, 2);
+    assert_eq!(metrics.deadline_histogram, [(6, 2)].into());
+
+    assert_eq!(
+        interpret_aggregate_metrics(&[], HEIGHT).awaiting_reimbursement,
+        Amount::ZERO
+    );
+
+    // txs without a status count as not broadcast
+    let metrics = interpret_aggregate_metrics(&[(&peg_out_graph, HashMap::new())], HEIGHT);
+    assert_eq!(metrics.phase_counts.values().sum::<usize>(), 1);
+    assert_eq!(metrics.failed, 0);
+}
@@ -0,0 +1,88 @@
+use std::{str::FromStr, time::Duration};
+
+use bitcoin::{OutPoint, Txid};
+
+use bridge::graphs::{base::BaseGraph, peg_out::DEFAULT_MIN_CONFIRMATIONS};
+
+use crate::bridge::mock::esplora::{MockEsplora, MOCK_ESPLORA_HEIGHT};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+#[tokio::test]
+async fn test_full_status() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    let esplora = MockEsplora::start(14, Duration::from_secs(1)).await;
+    let client = esplora.client();
+    let status = peg_out_graph
+        .full_status(&client, DEFAULT_MIN_CONFIRMATIONS)
+        .await;
+
+    // the 13 graph txs, the peg-out tx and the tip height, fetched once
+    assert_eq!(esplora.requests(), 15);
+    assert_eq!(status.block_height, MOCK_ESPLORA_HEIGHT);
+
+    assert_eq!(
+        status.operator.to_string(),
+        peg_out_graph
+            .operator_status(&client, None, DEFAULT_MIN_CONFIRMATIONS)
+            .await
+            .to_string()
+    );
+    assert_eq!(
+        status.verifier.to_string(),
+        peg_out_graph
+            .verifier_status(&client, None, DEFAULT_MIN_CONFIRMATIONS)
+            .await
+            .to_string()
+    );
+    assert_eq!(
+        status.withdrawer.to_string(),
+        peg_out_graph.withdrawer_status(&client).await.to_string()
+    );
+}
+
+#[tokio::test]
+async fn test_full_status_before_presigning() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let esplora = MockEsplora::start(13, Duration::from_secs(1)).await;
+    let client = esplora.client();
+    let status = peg_out_graph
+        .full_status(&client, DEFAULT_MIN_CONFIRMATIONS)
+        .await;
+
+    assert_eq!(
+        status.operator.to_string(),
+        peg_out_graph
+            .operator_status(&client, None, DEFAULT_MIN_CONFIRMATIONS)
+            .await
+            .to_string()
+    );
+    assert_eq!(
+        status.verifier.to_string(),
+        peg_out_graph
+            .verifier_status(&client, None, DEFAULT_MIN_CONFIRMATIONS)
+            .await
+            .to_string()
+    );
+    assert_eq!(
+        status.withdrawer.to_string(),
+        peg_out_graph.withdrawer_status(&client).await.to_string()
+    );
+}
@@ -0,0 +1,206 @@
+use std::str::FromStr;
+
+use bitcoin::{Amount, OutPoint, TxOut, Txid};
+
+use bridge::{
+    graphs::{
+        base::{DUST_AMOUNT, FEE_AMOUNT, INITIAL_AMOUNT},
+        peg_out::{PegOutGraph, PegOutGraphConfig},
+    },
+    scripts::generate_pay_to_pubkey_script_address,
+    transactions::{
+        base::Input,
+        funding::{generate_funding_psbt, WalletUtxo},
+        peg_out_confirm::peg_out_confirm_fee,
+    },
+};
+
+use super::{super::setup::SetupConfig, utils::setup_and_create_stub_graphs};
+
+fn generate_stub_wallet_utxos(config: &SetupConfig, amounts: &[u64]) -> Vec<WalletUtxo> {
+    let script_pubkey = generate_pay_to_pubkey_script_address(
+        config.operator_context.network,
+        &config.operator_context.operator_public_key,
+    )
+    .script_pubkey();
+
+    amounts
+        .iter()
+        .enumerate()
+        .map(|(vout, amount)| WalletUtxo {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+                )
+                .unwrap(),
+                vout: vout as u32,
+            },
+            txout: TxOut {
+                value: Amount::from_sat(*amount),
+                script_pubkey: script_pubkey.clone(),
+            },
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_funding_psbt_pays_peg_out_confirm_input() {
+    let (config, peg_in_graph, _, _) = setup_and_create_stub_graphs().await;
+    let change_address = generate_pay_to_pubkey_script_address(
+        config.operator_context.network,
+        &config.operator_context.operator_public_key,
+    );
+
+    // the funding tx has to be known before the graph that spends it is created
+    let peg_out_confirm_amount = Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT);
+    let peg_out_confirm_output = TxOut {
+        value: peg_out_confirm_amount,
+        script_pubkey: generate_pay_to_pubkey_script_address(
+            config.operator_context.network,
+            &config.operator_context.operator_public_key,
+        )
+        .script_pubkey(),
+    };
+    let utxos = generate_stub_wallet_utxos(&config, &[INITIAL_AMOUNT, INITIAL_AMOUNT]);
+    let psbt = generate_funding_psbt(
+        vec![peg_out_confirm_output.clone()],
+        &utxos,
+        change_address.clone(),
+    )
+    .unwrap();
+    let funding_txid = psbt.unsigned_tx.compute_txid();
+
+    let (peg_out_graph, _) = PegOutGraph::new(
+        &config.operator_context,
+        &peg_in_graph,
+        vec![Input {
+            outpoint: OutPoint {
+                txid: funding_txid,
+                vout: 0,
+            },
+            amount: peg_out_confirm_amount,
+        }],
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
+
+    let graph_psbt = peg_out_graph
+        .funding_psbt(&utxos, change_address.clone())
+        .unwrap();
+    assert_eq!(graph_psbt.unsigned_tx.compute_txid(), funding_txid);
+    assert_eq!(graph_psbt.unsigned_tx.output[0], peg_out_confirm_output);
+
+    // two utxos are needed, the remainder goes to change
+    assert_eq!(graph_psbt.inputs.len(), 2);
+    assert!(graph_psbt
+        .inputs
+        .iter()
+        .zip(&utxos)
+        .all(|(input, utxo)| input.witness_utxo.as_ref() == Some(&utxo.txout)));
+    assert_eq!(
+        graph_psbt.unsigned_tx.output[1].value,
+        Amount::from_sat(INITIAL_AMOUNT * 2)
+            - peg_out_confirm_amount
+            - Amount::from_sat(FEE_AMOUNT)
+    );
+    assert_eq!(
+        graph_psbt.unsigned_tx.output[1].script_pubkey,
+        change_address.script_pubkey()
+    );
+
+    // the same utxos in another order build another funding tx than the one the
+    // graph spends
+    let reordered_utxos: Vec<WalletUtxo> = utxos.iter().rev().cloned().collect();
+    assert!(peg_out_graph
+        .funding_psbt(&reordered_utxos, change_address)
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_funding_psbt_pays_all_peg_out_confirm_inputs() {
+    let (config, peg_in_graph, _, _) = setup_and_create_stub_graphs().await;
+    let change_address = generate_pay_to_pubkey_script_address(
+        config.operator_context.network,
+        &config.operator_context.operator_public_key,
+    );
+
+    let amounts = [
+        INITIAL_AMOUNT / 2,
+        INITIAL_AMOUNT / 2 + peg_out_confirm_fee(2).to_sat(),
+    ];
+    let peg_out_confirm_outputs: Vec<TxOut> = amounts
+        .iter()
+        .map(|amount| TxOut {
+            value: Amount::from_sat(*amount),
+            script_pubkey: change_address.script_pubkey(),
+        })
+        .collect();
+    let utxos = generate_stub_wallet_utxos(&config, &[INITIAL_AMOUNT * 2]);
+    let funding_txid = generate_funding_psbt(
+        peg_out_confirm_outputs.clone(),
+        &utxos,
+        change_address.clone(),
+    )
+    .unwrap()
+    .unsigned_tx
+    .compute_txid();
+
+    let (peg_out_graph, _) = PegOutGraph::new(
+        &config.operator_context,
+        &peg_in_graph,
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(vout, amount)| Input {
+                outpoint: OutPoint::new(funding_txid, vout as u32),
+                amount: Amount::from_sat(*amount),
+            })
+            .collect(),
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
+
+    let graph_psbt = peg_out_graph.funding_psbt(&utxos, change_address).unwrap();
+    assert_eq!(graph_psbt.unsigned_tx.compute_txid(), funding_txid);
+    assert_eq!(
+        graph_psbt.unsigned_tx.output[..amounts.len()],
+        peg_out_confirm_outputs
+    );
+}
+
+#[tokio::test]
+async fn test_funding_psbt_dust_change() {
+    let (config, _, _, _) = setup_and_create_stub_graphs().await;
+    let change_address = generate_pay_to_pubkey_script_address(
+        config.operator_context.network,
+        &config.operator_context.operator_public_key,
+    );
+    let funding_output = TxOut {
+        value: Amount::from_sat(INITIAL_AMOUNT),
+        script_pubkey: change_address.script_pubkey(),
+    };
+
+    // the change would be below dust
+    let utxos = generate_stub_wallet_utxos(&config, &[INITIAL_AMOUNT + FEE_AMOUNT + 1]);
+    assert!(
+        generate_funding_psbt(vec![funding_output.clone()], &utxos, change_address.clone())
+            .is_err()
+    );
+
+    // no change at all
+    let utxos = generate_stub_wallet_utxos(&config, &[INITIAL_AMOUNT + FEE_AMOUNT]);
+    let psbt = generate_funding_psbt(vec![funding_output.clone()], &utxos, change_address).unwrap();
+    assert_eq!(psbt.unsigned_tx.output, vec![funding_output]);
+}
+
+#[tokio::test]
+async fn test_funding_psbt_insufficient_funds() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let change_address = generate_pay_to_pubkey_script_address(
+        config.operator_context.network,
+        &config.operator_context.operator_public_key,
+    );
+
+    let utxos = generate_stub_wallet_utxos(&config, &[DUST_AMOUNT, DUST_AMOUNT]);
+    assert!(peg_out_graph.funding_psbt(&utxos, change_address).is_err());
+}
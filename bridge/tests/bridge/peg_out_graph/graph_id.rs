@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+use bitcoin::PublicKey;
+
+use bridge::graphs::{
+    base::{BaseGraph, GRAPH_VERSION},
+    peg_out::{generate_id, generate_id_for_version, generate_id_preimage},
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+const PEG_IN_GRAPH_ID: &str = "1B5A0C4F3C2E8D9A7B6E5F4D3C2B1A0918273645546372819A8B7C6D5E4F3A2B";
+const OPERATOR_PUBLIC_KEY: &str =
+    "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+#[test]
+fn test_graph_id_is_locked_for_current_version() {
+    let operator_public_key = PublicKey::from_str(OPERATOR_PUBLIC_KEY).unwrap();
+    let preimage = generate_id_preimage(PEG_IN_GRAPH_ID, &operator_public_key);
+    assert_eq!(preimage, format!("{PEG_IN_GRAPH_ID}{OPERATOR_PUBLIC_KEY}"));
+
+    // stored graphs are looked up by these ids, they must never change
+    assert_eq!(GRAPH_VERSION, "0.1");
+    assert_eq!(
+        generate_id_for_version(GRAPH_VERSION, &preimage),
+        "37897A8C682CBB6C80DFC18BAB15A4876BAE38A07DFA0D993D62E99CC7D870F9"
+    );
+    assert_eq!(
+        generate_id_for_version("0.2", &preimage),
+        "583E5726EF6109C6A83C96A3DD0769F789AA90E059C6C6D8E0CC339835AC5EDB"
+    );
+}
+
+#[tokio::test]
+async fn test_graph_id_preimage() {
+    let (config, peg_in_graph, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let operator_public_key = config.operator_context.operator_public_key;
+    assert_eq!(
+        peg_out_graph.id_preimage(),
+        generate_id_preimage(peg_in_graph.id(), &operator_public_key)
+    );
+    assert_eq!(
+        *peg_out_graph.id(),
+        generate_id_for_version(GRAPH_VERSION, &peg_out_graph.id_preimage())
+    );
+    assert_eq!(
+        *peg_out_graph.id(),
+        generate_id(&peg_in_graph, &operator_public_key)
+    );
+}
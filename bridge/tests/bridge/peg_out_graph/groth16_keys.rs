@@ -0,0 +1,38 @@
+use bridge::{
+    connectors::connector_c::ConnectorC,
+    transactions::assert_transactions::utils::{
+        derive_groth16_connector_keys, AssertCommit1ConnectorsE, AssertCommit2ConnectorsE,
+        GrothKeySource,
+    },
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_groth16_connector_keys_from_secrets_and_public_keys_match() {
+    let (config, _, peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let connector_e_1: AssertCommit1ConnectorsE =
+        serde_json::from_value(json["connector_e_1"].clone()).unwrap();
+    let connector_e_2: AssertCommit2ConnectorsE =
+        serde_json::from_value(json["connector_e_2"].clone()).unwrap();
+    let connector_c: ConnectorC = serde_json::from_value(json["connector_c"].clone()).unwrap();
+
+    let from_secrets = derive_groth16_connector_keys(GrothKeySource::Secrets(&commitment_secrets));
+    let from_public_keys = derive_groth16_connector_keys(GrothKeySource::PublicKeys(
+        &connector_e_1.commitment_public_keys(),
+        &connector_e_2.commitment_public_keys(),
+    ));
+    assert!(from_secrets == from_public_keys);
+    assert!(from_secrets.0 == connector_e_1.commitment_public_keys());
+    assert!(from_secrets.1 == connector_e_2.commitment_public_keys());
+
+    let rebuilt_connector_c = ConnectorC::new(
+        config.operator_context.network,
+        &config.operator_context.operator_taproot_public_key,
+        &from_public_keys.2,
+        peg_out_graph.verifying_key(),
+    );
+    assert!(rebuilt_connector_c == connector_c);
+}
@@ -0,0 +1,83 @@
+use bitcoin::{
+    consensus::encode::{deserialize_hex, serialize_hex},
+    Amount, OutPoint, Transaction,
+};
+use serde_json::Value;
+
+use bridge::{
+    graphs::{
+        base::BaseGraph,
+        peg_out::{GuardedActionError, OperatorAction, PegOutGraph},
+    },
+    serialization::{deserialize, serialize},
+    transactions::pre_signed::PreSignedTransaction,
+};
+
+use super::utils::{generate_stub_peg_out_event, setup_and_create_stub_graphs};
+
+#[tokio::test]
+async fn test_ensure_validated_caches_success() {
+    let (_, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    assert!(!peg_out_graph.is_validated());
+    assert_eq!(peg_out_graph.ensure_validated(), Ok(()));
+    assert!(peg_out_graph.is_validated());
+
+    // the cached result is not persisted with the graph
+    let loaded_peg_out_graph = deserialize::<PegOutGraph>(&serialize(&peg_out_graph));
+    assert!(!loaded_peg_out_graph.is_validated());
+    assert!(loaded_peg_out_graph == peg_out_graph);
+}
+
+#[tokio::test]
+async fn test_peg_out_event_clears_validation() {
+    let (config, peg_in_graph, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    assert_eq!(peg_out_graph.ensure_validated(), Ok(()));
+
+    let event = generate_stub_peg_out_event(
+        &config,
+        OutPoint {
+            txid: peg_in_graph.peg_in_confirm_transaction.tx().compute_txid(),
+            vout: 0,
+        },
+    );
+    assert!(peg_out_graph
+        .match_and_set_peg_out_event(&mut vec![event])
+        .await
+        .unwrap()
+        .is_some());
+    assert!(!peg_out_graph.is_validated());
+}
+
+#[tokio::test]
+async fn test_guarded_action_refuses_tampered_graph() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // redirect part of the take 1 payout in the stored graph
+    let mut json: Value = serde_json::from_str(&serialize(&peg_out_graph)).unwrap();
+    let mut take_1_tx: Transaction =
+        deserialize_hex(json["take_1_transaction"]["tx"].as_str().unwrap()).unwrap();
+    take_1_tx.output[0].value -= Amount::from_sat(1);
+    json["take_1_transaction"]["tx"] = Value::String(serialize_hex(&take_1_tx));
+    let mut tampered_peg_out_graph = deserialize::<PegOutGraph>(&json.to_string());
+
+    // fails before any request is sent to the esplora client
+    let result = tampered_peg_out_graph
+        .guarded_action(
+            OperatorAction::Take1 {
+                blockchain_height: None,
+            },
+            &config.client_0.esplora,
+            &config.operator_context,
+        )
+        .await;
+
+    assert_eq!(
+        result,
+        Err(GuardedActionError::InvalidGraph(
+            tampered_peg_out_graph.id().clone()
+        ))
+    );
+    assert!(!tampered_peg_out_graph.is_validated());
+}
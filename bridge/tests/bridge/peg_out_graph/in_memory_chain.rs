@@ -0,0 +1,154 @@
+use std::str::FromStr;
+
+use bitcoin::{
+    absolute, consensus::encode::deserialize_hex, transaction, Amount, OutPoint, ScriptBuf,
+    Sequence, Transaction, TxIn, TxOut, Txid, Witness,
+};
+
+use bridge::graphs::{
+    base::BaseGraph,
+    peg_out::{PegOutGraph, PegOutOperatorStatus},
+};
+
+use crate::bridge::mock::in_memory_chain::InMemoryChain;
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+fn graph_tx(json: &serde_json::Value, tx_name: &str) -> Transaction {
+    deserialize_hex(
+        json[format!("{tx_name}_transaction")]["tx"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+fn timelock(peg_out_graph: &PegOutGraph, tx_name: &str) -> u32 {
+    peg_out_graph
+        .timelocks()
+        .into_iter()
+        .find(|timelock| timelock.tx_name == tx_name)
+        .unwrap()
+        .num_blocks
+}
+
+#[tokio::test]
+async fn test_in_memory_chain_peg_out_to_take_2() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    // the chain does not check witnesses, the unsigned txs spend the same outpoints
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let mut chain = InMemoryChain::new();
+
+    let peg_out_tx = graph_tx(&json, "peg_out");
+    chain.submit(&peg_out_tx).unwrap();
+    chain.mine(1);
+
+    for tx_name in ["peg_out_confirm", "kick_off_1", "start_time"] {
+        chain.submit(&graph_tx(&json, tx_name)).unwrap();
+        chain.mine(1);
+    }
+    chain.mine(timelock(&peg_out_graph, "kick_off_2"));
+    chain.submit(&graph_tx(&json, "kick_off_2")).unwrap();
+    chain.mine(1);
+
+    chain.submit(&graph_tx(&json, "challenge")).unwrap();
+    chain.mine(timelock(&peg_out_graph, "assert_initial").max(1));
+    assert!(matches!(
+        chain.operator_status(&peg_out_graph),
+        PegOutOperatorStatus::PegOutAssertInitialAvailable
+    ));
+
+    chain.submit(&graph_tx(&json, "assert_initial")).unwrap();
+    chain.mine(1);
+    assert!(matches!(
+        chain.operator_status(&peg_out_graph),
+        PegOutOperatorStatus::PegOutAssertFinalAvailable
+    ));
+
+    chain.submit(&graph_tx(&json, "assert_final")).unwrap();
+    chain.mine(1);
+    chain.mine(timelock(&peg_out_graph, "take_2"));
+    assert!(matches!(
+        chain.operator_status(&peg_out_graph),
+        PegOutOperatorStatus::PegOutTake2Available
+    ));
+
+    let take_2_tx = graph_tx(&json, "take_2");
+    chain.submit(&take_2_tx).unwrap();
+    chain.mine(1);
+    assert!(matches!(
+        chain.operator_status(&peg_out_graph),
+        PegOutOperatorStatus::PegOutComplete
+    ));
+    assert!(peg_out_graph
+        .interpret_operator_deadlines(&chain.graph_statuses(&peg_out_graph))
+        .is_empty());
+
+    // take 2 spent the outputs of assert final that disprove needs
+    let disprove_tx = graph_tx(&json, "disprove");
+    assert!(chain.submit(&disprove_tx).is_err());
+    assert_eq!(
+        chain.spending_txid(&disprove_tx.input[0].previous_output),
+        Some(take_2_tx.compute_txid())
+    );
+}
+
+#[test]
+fn test_in_memory_chain_relative_timelock() {
+    let tx = |previous_output, sequence| Transaction {
+        version: transaction::Version(2),
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output,
+            script_sig: ScriptBuf::new(),
+            sequence,
+            witness: Witness::default(),
+        }],
+        output: vec![TxOut {
+            value: Amount::from_sat(1000),
+            script_pubkey: ScriptBuf::new(),
+        }],
+    };
+
+    let mut chain = InMemoryChain::new();
+    let parent_tx = tx(OutPoint::null(), Sequence::MAX);
+    let parent_txid = chain.submit(&parent_tx).unwrap();
+    let child_tx = tx(OutPoint::new(parent_txid, 0), Sequence(3));
+
+    assert!(chain.submit(&child_tx).is_err());
+    chain.mine(1);
+    assert!(chain.tx_status(&parent_txid).unwrap().confirmed);
+    assert!(chain.submit(&child_tx).is_err());
+
+    chain.mine(1);
+    assert!(chain.submit(&child_tx).is_err());
+
+    // the next block is 3 blocks after the parent's
+    chain.mine(1);
+    let child_txid = chain.submit(&child_tx).unwrap();
+    assert!(!chain.tx_status(&child_txid).unwrap().confirmed);
+    chain.mine(1);
+    assert_eq!(
+        chain.tx_status(&child_txid).unwrap().block_height,
+        Some(chain.height())
+    );
+    assert!(chain
+        .submit(&tx(OutPoint::new(parent_txid, 0), Sequence::MAX))
+        .is_err());
+}
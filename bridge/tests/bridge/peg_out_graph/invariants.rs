@@ -0,0 +1,139 @@
+use bitcoin::{secp256k1::SecretKey, Amount, Network, OutPoint, PublicKey, Txid};
+use proptest::prelude::*;
+
+use bridge::{
+    contexts::{
+        base::generate_keys_from_secret, depositor::DepositorContext, operator::OperatorContext,
+    },
+    graphs::{
+        base::{DEPOSITOR_EVM_ADDRESS, FEE_AMOUNT, INITIAL_AMOUNT},
+        peg_in::PegInGraph,
+        peg_out::PegOutGraph,
+    },
+    transactions::base::Input,
+};
+
+// Random but valid inputs to graph construction. Timelocks are protocol
+// constants and not part of the fixture.
+#[derive(Debug, Clone)]
+struct GraphFixture {
+    network: Network,
+    depositor_secret: String,
+    operator_secret: String,
+    verifier_secrets: Vec<String>,
+    peg_in_amount: u64,
+    peg_out_confirm_amounts: Vec<u64>,
+}
+
+fn secret() -> impl Strategy<Value = String> {
+    any::<[u8; 32]>().prop_filter_map("not a valid secret key", |bytes| {
+        SecretKey::from_slice(&bytes)
+            .ok()
+            .map(|secret_key| secret_key.display_secret().to_string())
+    })
+}
+
+fn graph_fixture() -> impl Strategy<Value = GraphFixture> {
+    let min_amount = INITIAL_AMOUNT + FEE_AMOUNT + 1;
+    (
+        prop_oneof![
+            Just(Network::Regtest),
+            Just(Network::Testnet),
+            Just(Network::Bitcoin)
+        ],
+        secret(),
+        secret(),
+        prop::collection::vec(secret(), 1..=3),
+        min_amount..16 * INITIAL_AMOUNT,
+        prop::collection::vec(min_amount..16 * INITIAL_AMOUNT, 1..=3),
+    )
+        .prop_map(
+            |(
+                network,
+                depositor_secret,
+                operator_secret,
+                verifier_secrets,
+                peg_in_amount,
+                peg_out_confirm_amounts,
+            )| GraphFixture {
+                network,
+                depositor_secret,
+                operator_secret,
+                verifier_secrets,
+                peg_in_amount,
+                peg_out_confirm_amounts,
+            },
+        )
+}
+
+fn stub_input(index: u32, amount: u64) -> Input {
+    Input {
+        outpoint: OutPoint {
+            txid: Txid::from_byte_array([index as u8 + 1; 32]),
+            vout: index,
+        },
+        amount: Amount::from_sat(amount),
+    }
+}
+
+fn build_graph(fixture: &GraphFixture) -> PegOutGraph {
+    let n_of_n_public_keys: Vec<PublicKey> = fixture
+        .verifier_secrets
+        .iter()
+        .map(|secret| generate_keys_from_secret(fixture.network, secret).1)
+        .collect();
+
+    let depositor_context = DepositorContext::new(
+        fixture.network,
+        &fixture.depositor_secret,
+        &n_of_n_public_keys,
+    );
+    let operator_context = OperatorContext::new(
+        fixture.network,
+        &fixture.operator_secret,
+        &n_of_n_public_keys,
+    )
+    .unwrap();
+
+    let peg_in_graph = PegInGraph::new(
+        &depositor_context,
+        stub_input(0, fixture.peg_in_amount),
+        DEPOSITOR_EVM_ADDRESS,
+    );
+    let (peg_out_graph, _) = PegOutGraph::new_minimal(
+        &operator_context,
+        &peg_in_graph,
+        fixture
+            .peg_out_confirm_amounts
+            .iter()
+            .enumerate()
+            .map(|(index, amount)| stub_input(index as u32 + 1, *amount))
+            .collect(),
+    )
+    .unwrap();
+
+    peg_out_graph
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn prop_every_input_has_a_parent(fixture in graph_fixture()) {
+        let peg_out_graph = build_graph(&fixture);
+        prop_assert_eq!(peg_out_graph.check_linkage(), vec![]);
+    }
+
+    #[test]
+    fn prop_value_is_conserved(fixture in graph_fixture()) {
+        let peg_out_graph = build_graph(&fixture);
+        prop_assert_eq!(peg_out_graph.check_value_conservation(), vec![]);
+    }
+
+    #[test]
+    fn prop_fresh_graph_is_valid(fixture in graph_fixture()) {
+        let peg_out_graph = build_graph(&fixture);
+        prop_assert!(peg_out_graph.validate());
+        prop_assert_eq!(peg_out_graph.dry_finalize_all(), Ok(()));
+    }
+}
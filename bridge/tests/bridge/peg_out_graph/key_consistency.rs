@@ -0,0 +1,82 @@
+use serde_json::Value;
+
+use bridge::{
+    graphs::peg_out::{KeyError, PegOutGraph, ValidationRebuildError},
+    serialization::{deserialize, serialize},
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_key_consistency_success() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    assert_eq!(peg_out_graph.validate_key_consistency(), Ok(()));
+    assert_eq!(
+        peg_out_graph.n_of_n_taproot_public_key(),
+        &config.operator_context.n_of_n_taproot_public_key
+    );
+}
+
+#[tokio::test]
+async fn test_key_consistency_mismatched_n_of_n_taproot_key() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // swap in a valid x-only key that does not belong to the n-of-n public key
+    let mut json: Value = serde_json::from_str(&serialize(&peg_out_graph)).unwrap();
+    json["n_of_n_taproot_public_key"] =
+        serde_json::to_value(config.operator_context.operator_taproot_public_key).unwrap();
+    let mismatched_peg_out_graph = deserialize::<PegOutGraph>(&json.to_string());
+
+    assert_eq!(
+        mismatched_peg_out_graph.validate_key_consistency(),
+        Err(KeyError::NofNTaprootKeyMismatch)
+    );
+    assert!(!mismatched_peg_out_graph.validate());
+    assert_eq!(
+        mismatched_peg_out_graph.new_for_validation().err(),
+        Some(ValidationRebuildError::KeyMismatch(KeyError::NofNTaprootKeyMismatch))
+    );
+}
+
+#[tokio::test]
+async fn test_key_consistency_mismatched_operator_taproot_key() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut json: Value = serde_json::from_str(&serialize(&peg_out_graph)).unwrap();
+    json["operator_taproot_public_key"] =
+        serde_json::to_value(config.operator_context.n_of_n_taproot_public_key).unwrap();
+    let mismatched_peg_out_graph = deserialize::<PegOutGraph>(&json.to_string());
+
+    assert_eq!(
+        mismatched_peg_out_graph.validate_key_consistency(),
+        Err(KeyError::OperatorTaprootKeyMismatch)
+    );
+    assert!(!mismatched_peg_out_graph.validate());
+}
+
+#[tokio::test]
+async fn test_key_consistency_n_of_n_public_keys() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // cleared, e.g. to skip the finalization dry run
+    let mut json: Value = serde_json::from_str(&serialize(&peg_out_graph)).unwrap();
+    json.as_object_mut().unwrap().remove("n_of_n_public_keys");
+    let stripped_peg_out_graph = deserialize::<PegOutGraph>(&json.to_string());
+    assert_eq!(
+        stripped_peg_out_graph.validate_key_consistency(),
+        Err(KeyError::MissingNofNPublicKeys)
+    );
+    assert!(!stripped_peg_out_graph.validate());
+
+    // keys that aggregate to another n-of-n key
+    let mut json: Value = serde_json::from_str(&serialize(&peg_out_graph)).unwrap();
+    json["n_of_n_public_keys"] =
+        serde_json::to_value(vec![config.operator_context.operator_public_key]).unwrap();
+    let mismatched_peg_out_graph = deserialize::<PegOutGraph>(&json.to_string());
+    assert_eq!(
+        mismatched_peg_out_graph.validate_key_consistency(),
+        Err(KeyError::NofNPublicKeysMismatch)
+    );
+    assert!(!mismatched_peg_out_graph.validate());
+}
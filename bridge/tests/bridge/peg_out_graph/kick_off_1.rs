@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+use bitcoin::{OutPoint, Txid};
+
+use bridge::graphs::peg_out::CommitmentMessageId;
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+#[tokio::test]
+async fn test_kick_off_1_peg_out_txid_change() {
+    let (config, _, mut peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+
+    let peg_out_outpoint = OutPoint {
+        txid: Txid::from_str("8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182")
+            .unwrap(),
+        vout: 0,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, peg_out_outpoint);
+    peg_out_graph
+        .sign_kick_off_1(
+            &config.operator_context,
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
+        )
+        .unwrap();
+    assert!(peg_out_graph.verify_kick_off_1_peg_out_txid(true).is_ok());
+
+    // rebuild the peg-out tx, e.g. after re-funding it from another utxo
+    let rebuilt_peg_out_outpoint = OutPoint {
+        txid: peg_out_outpoint.txid,
+        vout: 1,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, rebuilt_peg_out_outpoint);
+
+    // kick-off 1 not broadcast yet, re-signing is allowed
+    assert!(peg_out_graph.verify_kick_off_1_peg_out_txid(false).is_ok());
+
+    // kick-off 1 already broadcast with the old commitment
+    let err = peg_out_graph
+        .verify_kick_off_1_peg_out_txid(true)
+        .unwrap_err();
+    assert_ne!(err.signed_txid, err.current_txid);
+
+    // re-signing before broadcast picks up the new txid
+    peg_out_graph
+        .sign_kick_off_1(
+            &config.operator_context,
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
+        )
+        .unwrap();
+    assert!(peg_out_graph.verify_kick_off_1_peg_out_txid(true).is_ok());
+}
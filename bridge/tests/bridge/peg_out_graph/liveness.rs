@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+use bitcoin::Txid;
+
+use bridge::graphs::peg_out::{Liveness, PegOutGraph, PrerequisiteOutputStatus};
+
+use super::utils::setup_and_create_stub_graphs;
+
+fn confirmed_and_unspent(peg_out_graph: &PegOutGraph) -> Vec<PrerequisiteOutputStatus> {
+    peg_out_graph
+        .prerequisite_outputs()
+        .iter()
+        .map(|prerequisite| PrerequisiteOutputStatus {
+            outpoint: prerequisite.outpoint,
+            is_funding_tx_confirmed: true,
+            spending_txid: None,
+        })
+        .collect()
+}
+
+fn conflicting_txid() -> Txid {
+    Txid::from_str("3fbd2b4c7e3bba7bb1e6c1e3b0c0e1cc2b82ad8a3e1bb26f4a2b0a2f9d03c1e7").unwrap()
+}
+
+#[tokio::test]
+async fn test_liveness_alive() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut statuses = confirmed_and_unspent(&peg_out_graph);
+    assert_eq!(peg_out_graph.interpret_liveness(&statuses), Liveness::Alive);
+
+    // spent by the graph's own transactions
+    for (status, prerequisite) in statuses
+        .iter_mut()
+        .zip(peg_out_graph.prerequisite_outputs())
+    {
+        status.spending_txid = Some(prerequisite.graph_spending_txids[0]);
+    }
+    assert_eq!(peg_out_graph.interpret_liveness(&statuses), Liveness::Alive);
+}
+
+#[tokio::test]
+async fn test_liveness_stalled_on_unconfirmed_prerequisite() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut statuses = confirmed_and_unspent(&peg_out_graph);
+    statuses[1].is_funding_tx_confirmed = false;
+
+    assert!(matches!(
+        peg_out_graph.interpret_liveness(&statuses),
+        Liveness::Stalled { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_liveness_dead_on_conflicting_funding_spend() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut statuses = confirmed_and_unspent(&peg_out_graph);
+    statuses[0].spending_txid = Some(conflicting_txid());
+
+    let liveness = peg_out_graph.interpret_liveness(&statuses);
+    assert!(
+        matches!(&liveness, Liveness::Dead { reason } if reason.contains(&conflicting_txid().to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_liveness_dead_takes_precedence_over_stalled() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // the peg-in confirm output was taken by another operator's graph
+    let mut statuses = confirmed_and_unspent(&peg_out_graph);
+    statuses[0].is_funding_tx_confirmed = false;
+    statuses[1].spending_txid = Some(conflicting_txid());
+
+    assert!(matches!(
+        peg_out_graph.interpret_liveness(&statuses),
+        Liveness::Dead { .. }
+    ));
+}
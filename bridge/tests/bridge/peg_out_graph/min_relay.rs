@@ -0,0 +1,53 @@
+use bitcoin::{
+    consensus::encode::{deserialize_hex, serialize_hex},
+    Amount, Transaction, TxOut,
+};
+
+use bridge::{
+    graphs::peg_out::{estimated_vsize, PegOutGraph},
+    transactions::take_1::Take1Transaction,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+const MIN_RELAY_SAT_PER_VB: f64 = 1.0;
+
+#[tokio::test]
+async fn test_check_min_relay() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    assert!(peg_out_graph.check_min_relay(0.0).is_empty());
+    assert_eq!(peg_out_graph.check_min_relay(f64::MAX).len(), 13);
+    assert!(!peg_out_graph
+        .check_min_relay(MIN_RELAY_SAT_PER_VB)
+        .iter()
+        .any(|(tx_name, _)| tx_name == "take_1"));
+
+    // leave a single sat for fees in take 1
+    let mut json = serde_json::to_value(&peg_out_graph).unwrap();
+    let mut take_1_tx: Transaction =
+        deserialize_hex(json["take_1_transaction"]["tx"].as_str().unwrap()).unwrap();
+    let prev_outs: Vec<TxOut> =
+        deserialize_hex(json["take_1_transaction"]["prev_outs"].as_str().unwrap()).unwrap();
+    let input_amount: Amount = prev_outs.iter().map(|prev_out| prev_out.value).sum();
+    let other_outputs_amount: Amount = take_1_tx.output[1..]
+        .iter()
+        .map(|output| output.value)
+        .sum();
+    take_1_tx.output[0].value = input_amount - other_outputs_amount - Amount::from_sat(1);
+    json["take_1_transaction"]["tx"] = serialize_hex(&take_1_tx).into();
+    let take_1_transaction: Take1Transaction =
+        serde_json::from_value(json["take_1_transaction"].clone()).unwrap();
+    let under_fee_graph: PegOutGraph = serde_json::from_value(json).unwrap();
+
+    // the unsigned tx lacks the witness it is going to be relayed with
+    let take_1_vsize = estimated_vsize(&take_1_transaction);
+    assert!(take_1_vsize > take_1_tx.vsize() as u64);
+
+    let below_min_relay = under_fee_graph.check_min_relay(MIN_RELAY_SAT_PER_VB);
+    let (_, fee_rate) = below_min_relay
+        .iter()
+        .find(|(tx_name, _)| tx_name == "take_1")
+        .expect("take 1 is below min relay");
+    assert_eq!(*fee_rate, 1.0 / take_1_vsize as f64);
+}
@@ -0,0 +1,68 @@
+use std::{str::FromStr, time::Instant};
+
+use bitcoin::{Amount, OutPoint, Txid};
+
+use bridge::{
+    graphs::{
+        base::{FEE_AMOUNT, INITIAL_AMOUNT},
+        peg_in::PegInGraph,
+        peg_out::{PegOutGraph, MINIMAL_GROTH16_VARIABLES},
+    },
+    transactions::base::Input,
+};
+
+use super::super::setup::setup_test;
+
+#[tokio::test]
+async fn test_minimal_graph_is_valid() {
+    let config = setup_test().await;
+
+    let amount = Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1);
+    let peg_in_graph = PegInGraph::new(
+        &config.depositor_context,
+        Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "0e6719ac074b0e3cac76d057643506faa1c266b322aa9cf4c6f635fe63b14327",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            amount,
+        },
+        &config.depositor_evm_address,
+    );
+
+    let start = Instant::now();
+    let (peg_out_graph, commitment_secrets) = PegOutGraph::new_minimal(
+        &config.operator_context,
+        &peg_in_graph,
+        vec![Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            amount,
+        }],
+    )
+    .unwrap();
+    println!("Minimal graph built in {:?}", start.elapsed());
+
+    let assignment = peg_out_graph.commitment_assignment();
+    assert_eq!(assignment.len(), MINIMAL_GROTH16_VARIABLES.len());
+    assert!(MINIMAL_GROTH16_VARIABLES
+        .iter()
+        .all(|name| assignment.contains_key(*name)));
+    // source/destination txid, start time, superblock and superblock hash
+    assert_eq!(
+        commitment_secrets.len(),
+        MINIMAL_GROTH16_VARIABLES.len() + 5
+    );
+
+    let start = Instant::now();
+    assert!(peg_out_graph.validate());
+    println!("Minimal graph validated in {:?}", start.elapsed());
+}
@@ -0,0 +1,35 @@
+use bridge::graphs::base::BaseGraph;
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_missing_nonces_and_signatures() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let verifier_0_public_key = config.verifier_0_context.verifier_public_key;
+    let verifier_1_public_key = config.verifier_1_context.verifier_public_key;
+    let verifier_pubkeys = [verifier_0_public_key, verifier_1_public_key];
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    let missing_nonces = peg_out_graph.missing_nonces(&verifier_pubkeys);
+    assert_eq!(missing_nonces.len(), secret_nonces.len());
+    for txid in secret_nonces.keys() {
+        assert_eq!(missing_nonces[txid], vec![verifier_1_public_key]);
+    }
+
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    assert!(peg_out_graph.missing_nonces(&verifier_pubkeys).is_empty());
+    assert_eq!(
+        peg_out_graph.missing_signatures(&verifier_pubkeys).len(),
+        secret_nonces.len()
+    );
+
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    let missing_signatures = peg_out_graph.missing_signatures(&verifier_pubkeys);
+    assert_eq!(missing_signatures.len(), secret_nonces.len());
+    for txid in secret_nonces.keys() {
+        assert_eq!(missing_signatures[txid], vec![verifier_1_public_key]);
+    }
+    assert!(peg_out_graph
+        .missing_signatures(&[verifier_0_public_key])
+        .is_empty());
+}
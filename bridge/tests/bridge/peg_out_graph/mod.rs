@@ -0,0 +1,69 @@
+pub mod action_lock;
+pub mod action_secrets;
+pub mod aggregated_nonces;
+pub mod batch_validation;
+pub mod challenge_amount;
+pub mod challenge_recommendation;
+pub mod commitment_assignment;
+pub mod commitment_connectors;
+pub mod commitment_secrets;
+pub mod commitment_secrets_verification;
+pub mod committed_source_txid;
+pub mod concurrent_statuses;
+pub mod confirmation_depth;
+pub mod descriptors;
+pub mod disprove_witness;
+pub mod dispute_path_cost;
+pub mod dry_finalize;
+pub mod external_ref;
+pub mod fee_divergence;
+pub mod fee_estimates;
+pub mod fleet_metrics;
+pub mod full_status;
+pub mod funding_psbt;
+pub mod graph_id;
+pub mod groth16_keys;
+pub mod guarded_action;
+pub mod in_memory_chain;
+#[cfg(feature = "minimal-graph")]
+pub mod invariants;
+pub mod key_consistency;
+pub mod kick_off_1;
+pub mod liveness;
+pub mod min_relay;
+#[cfg(feature = "minimal-graph")]
+pub mod minimal_graph;
+pub mod missing_signatures;
+pub mod network_mismatch;
+pub mod nonce_store;
+pub mod observer;
+pub mod operator_deadlines;
+pub mod operator_status;
+pub mod peg_out_confirm_inputs;
+pub mod peg_out_confirm_onchain;
+pub mod peg_out_destination;
+pub mod pending_timelock;
+pub mod phase;
+pub mod reconcile_peg_out_event;
+pub mod recovery;
+pub mod reject_placeholders;
+pub mod reward;
+pub mod secret_nonces;
+pub mod sighashes;
+pub mod signature_bundle;
+pub mod status_cache;
+pub mod structural_completeness;
+pub mod superblock_window;
+pub mod take_payout;
+pub mod take_payout_override;
+pub mod timelocks;
+pub mod topology;
+pub mod transaction_dag;
+pub mod txids;
+pub mod utils;
+pub mod validate_against_peg_in;
+pub mod verifying_key;
+pub mod wait_for_operator_status;
+pub mod watched_outpoints;
+pub mod withdrawer_expectation;
+pub mod wrong_commitment_secret;
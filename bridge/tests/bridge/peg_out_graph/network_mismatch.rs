@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+use bitcoin::{Amount, Network, OutPoint, Txid};
+
+use bridge::{
+    contexts::operator::OperatorContext,
+    graphs::{
+        base::{BaseGraph, FEE_AMOUNT, INITIAL_AMOUNT, OPERATOR_SECRET},
+        peg_out::{GraphConstructionError, PegOutGraph, PegOutGraphConfig},
+    },
+    transactions::base::Input,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_peg_out_graph_network_mismatch() {
+    let (config, peg_in_graph, _, _) = setup_and_create_stub_graphs().await;
+
+    let mainnet_operator_context = OperatorContext::new(
+        Network::Bitcoin,
+        OPERATOR_SECRET,
+        &config.operator_context.n_of_n_public_keys,
+    )
+    .unwrap();
+    assert_ne!(mainnet_operator_context.network, peg_in_graph.network());
+
+    let result = PegOutGraph::new(
+        &mainnet_operator_context,
+        &peg_in_graph,
+        vec![Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            amount: Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1),
+        }],
+        PegOutGraphConfig::default(),
+    );
+
+    assert_eq!(
+        result.err(),
+        Some(GraphConstructionError::NetworkMismatch {
+            operator: Network::Bitcoin,
+            peg_in: peg_in_graph.network(),
+        })
+    );
+}
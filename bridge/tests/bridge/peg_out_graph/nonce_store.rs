@@ -0,0 +1,246 @@
+use std::{env, fs, path::PathBuf};
+
+use bitcoin::Txid;
+use bridge::graphs::{
+    base::BaseGraph,
+    nonce_store::{FileSecNonceStore, SecNonceStore, SecNonceStoreError},
+    peg_out::NonceBlobError,
+};
+use musig2::SecNonce;
+
+use super::utils::setup_and_create_stub_graphs;
+
+const KEY: [u8; 32] = [7; 32];
+
+fn store_dir() -> PathBuf {
+    env::temp_dir().join(format!("bitvm-nonce-store-{}", rand::random::<u64>()))
+}
+
+struct FailingSecNonceStore;
+
+impl SecNonceStore for FailingSecNonceStore {
+    fn save(&self, _: &str, _: &Txid, _: usize, _: &SecNonce) -> Result<(), SecNonceStoreError> {
+        Err(SecNonceStoreError::Io("disk full".to_string()))
+    }
+
+    fn load(&self, graph_id: &str, _: &Txid, _: usize) -> Result<SecNonce, SecNonceStoreError> {
+        Err(SecNonceStoreError::NotFound(graph_id.to_string()))
+    }
+    fn peek(&self, graph_id: &str, _: &Txid, _: usize) -> Result<SecNonce, SecNonceStoreError> {
+        Err(SecNonceStoreError::NotFound(graph_id.to_string()))
+    }
+}
+
+#[tokio::test]
+async fn test_secret_nonces_reloaded_from_store() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let verifier_0_public_key = config.verifier_0_context.verifier_public_key;
+    let verifier_1_public_key = config.verifier_1_context.verifier_public_key;
+    let dir = store_dir();
+
+    let secret_nonces_0 = peg_out_graph
+        .push_verifier_nonces_persisted(
+            &config.verifier_0_context,
+            &FileSecNonceStore::new(&dir, &KEY),
+        )
+        .unwrap();
+    let secret_nonces_1 = peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+
+    // a new store over the same directory, as after a restart
+    let store = FileSecNonceStore::new(&dir, &KEY);
+    let reloaded_secret_nonces = peg_out_graph
+        .load_verifier_nonces(&config.verifier_0_context, &store)
+        .unwrap();
+    assert_eq!(reloaded_secret_nonces.len(), secret_nonces_0.len());
+    for (txid, tx_secret_nonces) in &secret_nonces_0 {
+        for (input_index, secret_nonce) in tx_secret_nonces {
+            assert_eq!(
+                reloaded_secret_nonces[txid][input_index].public_nonce(),
+                secret_nonce.public_nonce()
+            );
+        }
+    }
+
+    // loading takes the nonces out of the store
+    assert!(matches!(
+        peg_out_graph.load_verifier_nonces(&config.verifier_0_context, &store),
+        Err(SecNonceStoreError::AlreadyUsed(_))
+    ));
+
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &reloaded_secret_nonces);
+    peg_out_graph.verifier_sign(&config.verifier_1_context, &secret_nonces_1);
+    assert!(peg_out_graph.has_all_signatures(&[verifier_0_public_key, verifier_1_public_key]));
+
+    // nonces saved again are refused once the verifier has signed
+    for (txid, tx_secret_nonces) in &reloaded_secret_nonces {
+        for (input_index, secret_nonce) in tx_secret_nonces {
+            store
+                .save(peg_out_graph.id(), txid, *input_index, secret_nonce)
+                .unwrap();
+        }
+    }
+    assert!(matches!(
+        peg_out_graph.load_verifier_nonces(&config.verifier_0_context, &store),
+        Err(SecNonceStoreError::Blob(NonceBlobError::AlreadySigned(_)))
+    ));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_secret_nonce_not_reused_for_another_input() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let dir = store_dir();
+    let store = FileSecNonceStore::new(&dir, &KEY);
+
+    let secret_nonces = peg_out_graph
+        .push_verifier_nonces_persisted(&config.verifier_0_context, &store)
+        .unwrap();
+    let mut inputs = secret_nonces
+        .iter()
+        .flat_map(|(txid, tx_secret_nonces)| tx_secret_nonces.keys().map(|index| (*txid, *index)));
+    let (txid, input_index) = inputs.next().unwrap();
+    let (other_txid, other_input_index) =
+        inputs.find(|(other_txid, _)| *other_txid != txid).unwrap();
+    let graph_id = peg_out_graph.id();
+
+    // the nonce of one input copied over those of another tx and another input
+    let path = store.nonce_path(graph_id, &txid, input_index);
+    fs::copy(
+        &path,
+        store.nonce_path(graph_id, &other_txid, other_input_index),
+    )
+    .unwrap();
+    assert!(matches!(
+        store.load(graph_id, &other_txid, other_input_index),
+        Err(SecNonceStoreError::WrongInput(_))
+    ));
+    fs::copy(&path, store.nonce_path(graph_id, &txid, input_index + 1)).unwrap();
+    assert!(matches!(
+        store.load(graph_id, &txid, input_index + 1),
+        Err(SecNonceStoreError::WrongInput(_))
+    ));
+
+    assert_eq!(
+        store
+            .load(graph_id, &txid, input_index)
+            .unwrap()
+            .public_nonce(),
+        secret_nonces[&txid][&input_index].public_nonce()
+    );
+    assert!(matches!(
+        store.load(graph_id, &txid, input_index),
+        Err(SecNonceStoreError::AlreadyUsed(_))
+    ));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_secret_nonces_bound_to_key_and_round() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let dir = store_dir();
+    let store = FileSecNonceStore::new(&dir, &KEY);
+
+    assert!(matches!(
+        peg_out_graph.load_verifier_nonces(&config.verifier_0_context, &store),
+        Err(SecNonceStoreError::NotFound(_))
+    ));
+
+    let secret_nonces = peg_out_graph
+        .push_verifier_nonces_persisted(&config.verifier_0_context, &store)
+        .unwrap();
+    let mut inputs = secret_nonces
+        .iter()
+        .flat_map(|(txid, tx_secret_nonces)| tx_secret_nonces.keys().map(|index| (*txid, *index)));
+    let (txid, input_index) = inputs.next().unwrap();
+    assert!(matches!(
+        FileSecNonceStore::new(&dir, &[8; 32]).load(peg_out_graph.id(), &txid, input_index),
+        Err(SecNonceStoreError::Corrupted(_))
+    ));
+    let (txid, input_index) = inputs.next().unwrap();
+    fs::write(
+        store.nonce_path(peg_out_graph.id(), &txid, input_index),
+        b"not a nonce",
+    )
+    .unwrap();
+    assert!(matches!(
+        store.load(peg_out_graph.id(), &txid, input_index),
+        Err(SecNonceStoreError::Corrupted(_))
+    ));
+
+    // nonces of a previous round no longer match the public nonces in the graph
+    peg_out_graph
+        .push_verifier_nonces_persisted(&config.verifier_0_context, &store)
+        .unwrap();
+    peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    assert!(matches!(
+        peg_out_graph.load_verifier_nonces(&config.verifier_0_context, &store),
+        Err(SecNonceStoreError::Blob(NonceBlobError::StaleNonces(_)))
+    ));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_nonces_rolled_back_when_not_saved() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let dir = store_dir();
+    let store = FileSecNonceStore::new(&dir, &KEY);
+
+    assert!(matches!(
+        peg_out_graph
+            .push_verifier_nonces_persisted(&config.verifier_0_context, &FailingSecNonceStore),
+        Err(SecNonceStoreError::Io(_))
+    ));
+    assert!(!peg_out_graph.has_all_nonces_of(&config.verifier_0_context));
+
+    // a failed round leaves the nonces of the previous one in place
+    peg_out_graph
+        .push_verifier_nonces_persisted(&config.verifier_0_context, &store)
+        .unwrap();
+    assert!(peg_out_graph
+        .push_verifier_nonces_persisted(&config.verifier_0_context, &FailingSecNonceStore)
+        .is_err());
+    assert!(peg_out_graph
+        .load_verifier_nonces(&config.verifier_0_context, &store)
+        .is_ok());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_secret_nonces_kept_when_not_all_loaded() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let dir = store_dir();
+    let store = FileSecNonceStore::new(&dir, &KEY);
+
+    let secret_nonces = peg_out_graph
+        .push_verifier_nonces_persisted(&config.verifier_0_context, &store)
+        .unwrap();
+    let inputs = secret_nonces
+        .iter()
+        .flat_map(|(txid, tx_secret_nonces)| tx_secret_nonces.keys().map(|index| (*txid, *index)))
+        .collect::<Vec<_>>();
+    let graph_id = peg_out_graph.id();
+
+    // one nonce lost, the others must not be used up by the failed load
+    let (lost_txid, lost_input_index) = inputs[inputs.len() - 1];
+    let lost_path = store.nonce_path(graph_id, &lost_txid, lost_input_index);
+    let lost_nonce = fs::read(&lost_path).unwrap();
+    fs::remove_file(&lost_path).unwrap();
+    assert!(matches!(
+        peg_out_graph.load_verifier_nonces(&config.verifier_0_context, &store),
+        Err(SecNonceStoreError::NotFound(_))
+    ));
+    for (txid, input_index) in &inputs[..inputs.len() - 1] {
+        assert!(store.peek(graph_id, txid, *input_index).is_ok());
+    }
+
+    fs::write(&lost_path, lost_nonce).unwrap();
+    assert!(peg_out_graph
+        .load_verifier_nonces(&config.verifier_0_context, &store)
+        .is_ok());
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,133 @@
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bitcoin::{consensus::encode::deserialize_hex, OutPoint, Transaction, Txid};
+
+use bridge::graphs::{
+    base::BaseGraph,
+    observer::{GraphEvent, GraphObserver, ObservedGraph},
+    peg_out::{ActionError, GuardedActionError, OperatorAction, PegOutOperatorStatus},
+};
+
+use crate::bridge::mock::{esplora::MockEsplora, in_memory_chain::InMemoryChain};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+#[derive(Default)]
+struct RecordingObserver {
+    events: Mutex<Vec<GraphEvent>>,
+}
+
+impl GraphObserver for RecordingObserver {
+    fn on_event(&self, event: GraphEvent) { self.events.lock().unwrap().push(event); }
+}
+
+fn graph_tx(json: &serde_json::Value, tx_name: &str) -> Transaction {
+    deserialize_hex(
+        json[format!("{tx_name}_transaction")]["tx"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_observer_events() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let mut chain = InMemoryChain::new();
+    let observer = RecordingObserver::default();
+    let mut observed = ObservedGraph::default();
+    let mut report = |chain: &InMemoryChain| {
+        peg_out_graph.report_operator_status(
+            &observer,
+            &mut observed,
+            &chain.graph_statuses(&peg_out_graph),
+            &chain.operator_status(&peg_out_graph),
+        )
+    };
+
+    chain.submit(&graph_tx(&json, "peg_out")).unwrap();
+    chain.mine(1);
+    report(&chain);
+    // nothing changed since the last report
+    report(&chain);
+
+    // both confirmed in the same block
+    chain.submit(&graph_tx(&json, "peg_out_confirm")).unwrap();
+    chain.submit(&graph_tx(&json, "kick_off_1")).unwrap();
+    chain.mine(1);
+    report(&chain);
+
+    chain.submit(&graph_tx(&json, "start_time")).unwrap();
+    chain.mine(1);
+    report(&chain);
+
+    let graph_id = peg_out_graph.id().clone();
+    let status_changed = |status: PegOutOperatorStatus| GraphEvent::StatusChanged {
+        graph_id: graph_id.clone(),
+        status: status.to_string(),
+    };
+    let confirmation_detected = |tx_name, block_height| GraphEvent::ConfirmationDetected {
+        graph_id: graph_id.clone(),
+        tx_name,
+        block_height,
+    };
+    assert_eq!(
+        *observer.events.lock().unwrap(),
+        vec![
+            status_changed(PegOutOperatorStatus::PegOutPegOutConfirmAvailable),
+            confirmation_detected("peg_out_confirm", 2),
+            confirmation_detected("kick_off_1", 2),
+            status_changed(PegOutOperatorStatus::PegOutStartTimeAvailable),
+            confirmation_detected("start_time", 3),
+            status_changed(PegOutOperatorStatus::PegOutKickOff2Available),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_guarded_action_failure_reported() {
+    let (mut config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let esplora = MockEsplora::start(1, Duration::ZERO).await;
+    let observer = Arc::new(RecordingObserver::default());
+    config.operator_context.observer = observer.clone();
+
+    // the mock reports every tx as unconfirmed, assert initial included
+    let result = peg_out_graph
+        .guarded_action(
+            OperatorAction::AssertFinal,
+            &esplora.client(),
+            &config.operator_context,
+        )
+        .await;
+    let error = ActionError::NotReady("Assert-initial tx has not been confirmed");
+    assert_eq!(result, Err(GuardedActionError::Action(error.clone())));
+    assert_eq!(
+        *observer.events.lock().unwrap(),
+        vec![GraphEvent::ActionFailed {
+            graph_id: peg_out_graph.id().clone(),
+            tx_name: "assert_final",
+            error: error.to_string(),
+        }]
+    );
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use esplora_client::{Error, TxStatus};
+
+use bridge::graphs::peg_out::{Deadline, DeadlineConsequence, PegOutGraph};
+
+use super::utils::setup_and_create_stub_graphs;
+
+const KICK_OFF_1_HEIGHT: u32 = 100;
+const KICK_OFF_2_HEIGHT: u32 = 300;
+const ASSERT_FINAL_HEIGHT: u32 = 500;
+
+fn confirmed(block_height: u32) -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed: true,
+        block_height: Some(block_height),
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+fn num_blocks(peg_out_graph: &PegOutGraph, tx_name: &str) -> u32 {
+    peg_out_graph
+        .timelocks()
+        .into_iter()
+        .find(|timelock| timelock.tx_name == tx_name)
+        .unwrap()
+        .num_blocks
+}
+
+#[tokio::test]
+async fn test_operator_deadlines() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let mut statuses = HashMap::new();
+    assert_eq!(
+        peg_out_graph.interpret_operator_deadlines(&statuses),
+        vec![]
+    );
+
+    // kick-off 1 starts the clock on start time and kick-off 2
+    statuses.insert("kick_off_1", confirmed(KICK_OFF_1_HEIGHT));
+    assert_eq!(
+        peg_out_graph.interpret_operator_deadlines(&statuses),
+        vec![
+            Deadline {
+                tx_name: "start_time",
+                height: KICK_OFF_1_HEIGHT + num_blocks(&peg_out_graph, "start_time_timeout"),
+                consequence: DeadlineConsequence::StartTimeTimeout,
+            },
+            Deadline {
+                tx_name: "kick_off_2",
+                height: KICK_OFF_1_HEIGHT + num_blocks(&peg_out_graph, "kick_off_timeout"),
+                consequence: DeadlineConsequence::KickOffTimeout,
+            },
+        ]
+    );
+
+    statuses.insert("start_time", confirmed(KICK_OFF_1_HEIGHT + 1));
+    statuses.insert("kick_off_2", confirmed(KICK_OFF_2_HEIGHT));
+    assert_eq!(
+        peg_out_graph.interpret_operator_deadlines(&statuses),
+        vec![Deadline {
+            tx_name: "take_1",
+            height: KICK_OFF_2_HEIGHT + num_blocks(&peg_out_graph, "take_1"),
+            consequence: DeadlineConsequence::TakeContested,
+        }]
+    );
+
+    statuses.insert("challenge", confirmed(KICK_OFF_2_HEIGHT + 1));
+    assert_eq!(
+        peg_out_graph.interpret_operator_deadlines(&statuses),
+        vec![Deadline {
+            tx_name: "assert_initial",
+            height: KICK_OFF_2_HEIGHT + num_blocks(&peg_out_graph, "assert_initial"),
+            consequence: DeadlineConsequence::ChallengeUnanswered,
+        }]
+    );
+
+    statuses.insert("assert_initial", confirmed(ASSERT_FINAL_HEIGHT - 1));
+    statuses.insert("assert_final", confirmed(ASSERT_FINAL_HEIGHT));
+    assert_eq!(
+        peg_out_graph.interpret_operator_deadlines(&statuses),
+        vec![Deadline {
+            tx_name: "take_2",
+            height: ASSERT_FINAL_HEIGHT + num_blocks(&peg_out_graph, "take_2"),
+            consequence: DeadlineConsequence::TakeContested,
+        }]
+    );
+
+    // nothing is left to do once the graph is settled
+    statuses.insert("disprove", confirmed(ASSERT_FINAL_HEIGHT + 1));
+    assert_eq!(
+        peg_out_graph.interpret_operator_deadlines(&statuses),
+        vec![]
+    );
+}
@@ -0,0 +1,208 @@
+use std::str::FromStr;
+
+use bitcoin::{OutPoint, Txid};
+use esplora_client::{Error, TxStatus};
+
+use bridge::graphs::{
+    base::{confirmation_height, is_confirmed_without_height, is_timelock_elapsed, BaseGraph},
+    peg_out::{
+        PegOutGraph, PegOutOperatorStatus, PegOutWithdrawerStatus, WaitReason,
+        DEFAULT_MIN_CONFIRMATIONS,
+    },
+};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+const KICK_OFF_2_HEIGHT: u32 = 100;
+
+fn confirmed(block_height: u32) -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed: true,
+        block_height: Some(block_height),
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+// Esplora can briefly report a tx as confirmed before it knows the block height.
+fn confirmed_without_height() -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed: true,
+        block_height: None,
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+fn unconfirmed() -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed: false,
+        block_height: None,
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+async fn setup_presigned_peg_out_graph() -> PegOutGraph {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+
+    let peg_out_outpoint = OutPoint {
+        txid: Txid::from_str("8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182")
+            .unwrap(),
+        vout: 0,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, peg_out_outpoint);
+
+    peg_out_graph
+}
+
+// Status of a challenged graph whose kick-off 2 confirmed at KICK_OFF_2_HEIGHT.
+fn challenged_status(
+    peg_out_graph: &PegOutGraph,
+    assert_initial_status: Result<TxStatus, Error>,
+    assert_final_status: Result<TxStatus, Error>,
+    blockchain_height: u32,
+) -> PegOutOperatorStatus {
+    peg_out_graph.interpret_operator_status(
+        &assert_initial_status,
+        &assert_final_status,
+        &confirmed(KICK_OFF_2_HEIGHT + 1),
+        &unconfirmed(),
+        &unconfirmed(),
+        &confirmed(KICK_OFF_2_HEIGHT - 3),
+        &confirmed(KICK_OFF_2_HEIGHT - 2),
+        &confirmed(KICK_OFF_2_HEIGHT),
+        &unconfirmed(),
+        Some(&confirmed(KICK_OFF_2_HEIGHT - 4)),
+        &unconfirmed(),
+        &confirmed(KICK_OFF_2_HEIGHT - 1),
+        &unconfirmed(),
+        &unconfirmed(),
+        blockchain_height,
+        DEFAULT_MIN_CONFIRMATIONS,
+    )
+}
+
+#[tokio::test]
+async fn test_operator_status_assert_initial_available() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+
+    let status = challenged_status(
+        &peg_out_graph,
+        unconfirmed(),
+        unconfirmed(),
+        KICK_OFF_2_HEIGHT + 1,
+    );
+    assert!(matches!(
+        status,
+        PegOutOperatorStatus::PegOutAssertInitialAvailable
+    ));
+}
+
+#[tokio::test]
+async fn test_operator_status_assert_initial_timelock_not_elapsed() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+
+    // stale height from before kick-off 2 confirmed
+    let status = challenged_status(
+        &peg_out_graph,
+        unconfirmed(),
+        unconfirmed(),
+        KICK_OFF_2_HEIGHT - 1,
+    );
+    let assert_initial_timelock = peg_out_graph
+        .timelocks()
+        .into_iter()
+        .find(|timelock| timelock.tx_name == "assert_initial")
+        .unwrap()
+        .num_blocks;
+    assert!(matches!(
+        status,
+        PegOutOperatorStatus::PegOutWait(WaitReason::TimelockPending { remaining_blocks })
+            if remaining_blocks == assert_initial_timelock + 1
+    ));
+}
+
+#[tokio::test]
+async fn test_withdrawer_status_awaiting_peg_out_confirmation() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+
+    let status = peg_out_graph.interpret_withdrawer_status(Some(&unconfirmed()));
+    assert!(matches!(
+        status,
+        PegOutWithdrawerStatus::PegOutWait(WaitReason::AwaitingConfirmation { .. })
+    ));
+    assert!(status.to_string().contains("not confirmed yet"));
+}
+
+#[tokio::test]
+async fn test_operator_status_assert_final_available() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+
+    let status = challenged_status(
+        &peg_out_graph,
+        confirmed(KICK_OFF_2_HEIGHT + 2),
+        unconfirmed(),
+        KICK_OFF_2_HEIGHT + 2,
+    );
+    assert!(matches!(
+        status,
+        PegOutOperatorStatus::PegOutAssertFinalAvailable
+    ));
+}
+
+#[tokio::test]
+async fn test_operator_status_take_2_available_after_assert_final() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+
+    let status = challenged_status(
+        &peg_out_graph,
+        confirmed(KICK_OFF_2_HEIGHT + 2),
+        confirmed(KICK_OFF_2_HEIGHT + 3),
+        KICK_OFF_2_HEIGHT + 3,
+    );
+    assert!(matches!(status, PegOutOperatorStatus::PegOutTake2Available));
+}
+
+#[tokio::test]
+async fn test_operator_status_refetch_when_confirmed_without_height() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+
+    let status = challenged_status(
+        &peg_out_graph,
+        confirmed(KICK_OFF_2_HEIGHT + 2),
+        confirmed_without_height(),
+        KICK_OFF_2_HEIGHT + 100,
+    );
+    assert!(matches!(status, PegOutOperatorStatus::PegOutRefetch));
+
+    // with a known height a pending timelock is still a plain wait
+    let status = challenged_status(
+        &peg_out_graph,
+        confirmed(KICK_OFF_2_HEIGHT + 2),
+        confirmed(KICK_OFF_2_HEIGHT + 3),
+        KICK_OFF_2_HEIGHT + 2,
+    );
+    assert!(matches!(
+        status,
+        PegOutOperatorStatus::PegOutWait(WaitReason::TimelockPending { .. })
+    ));
+}
+
+#[test]
+fn test_confirmation_height() {
+    assert_eq!(confirmation_height(&confirmed(7)), Some(7));
+    assert_eq!(confirmation_height(&confirmed_without_height()), None);
+    assert_eq!(confirmation_height(&unconfirmed()), None);
+    assert!(is_confirmed_without_height(&confirmed_without_height()));
+    assert!(!is_confirmed_without_height(&unconfirmed()));
+    assert!(!is_timelock_elapsed(
+        &confirmed_without_height(),
+        0,
+        u32::MAX
+    ));
+}
@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use bitcoin::{consensus::encode::deserialize_hex, Amount, OutPoint, Transaction, Txid};
+
+use bridge::{
+    graphs::{
+        base::{FEE_AMOUNT, INITIAL_AMOUNT},
+        peg_out::{GraphConstructionError, PegOutGraph, PegOutGraphConfig},
+    },
+    transactions::{
+        base::Input,
+        peg_out_confirm::{peg_out_confirm_fee, ADDITIONAL_INPUT_FEE_AMOUNT},
+    },
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+fn tx(peg_out_graph: &PegOutGraph, tx_field: &str) -> Transaction {
+    let json = serde_json::to_value(peg_out_graph).unwrap();
+    deserialize_hex(json[tx_field]["tx"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_peg_out_confirm_funded_from_two_inputs() {
+    let (config, peg_in_graph, _, _) = setup_and_create_stub_graphs().await;
+
+    let funding_txid =
+        Txid::from_str("4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900").unwrap();
+    let inputs = [
+        (0, INITIAL_AMOUNT / 2),
+        (1, INITIAL_AMOUNT / 2 + peg_out_confirm_fee(2).to_sat() + 1),
+    ];
+    let (peg_out_graph, _) = PegOutGraph::new(
+        &config.operator_context,
+        &peg_in_graph,
+        inputs
+            .iter()
+            .map(|(vout, amount)| Input {
+                outpoint: OutPoint {
+                    txid: funding_txid,
+                    vout: *vout,
+                },
+                amount: Amount::from_sat(*amount),
+            })
+            .collect(),
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
+
+    let peg_out_confirm_tx = tx(&peg_out_graph, "peg_out_confirm_transaction");
+    assert_eq!(peg_out_confirm_tx.input.len(), 2);
+    for (tx_in, (vout, _)) in peg_out_confirm_tx.input.iter().zip(inputs) {
+        assert_eq!(tx_in.previous_output, OutPoint::new(funding_txid, vout));
+        assert!(!tx_in.witness.is_empty());
+    }
+    assert_eq!(peg_out_confirm_tx.output.len(), 1);
+    assert_eq!(
+        peg_out_confirm_tx.output[0].value,
+        Amount::from_sat(INITIAL_AMOUNT + 1)
+    );
+
+    // kick-off 1 is funded by the merged output
+    let kick_off_1_tx = tx(&peg_out_graph, "kick_off_1_transaction");
+    assert_eq!(
+        kick_off_1_tx.input[0].previous_output,
+        OutPoint::new(peg_out_confirm_tx.compute_txid(), 0)
+    );
+
+    assert!(peg_out_graph.validate());
+
+    let funding_outpoints: Vec<OutPoint> = peg_out_graph
+        .prerequisite_outputs()
+        .iter()
+        .filter(|prerequisite| prerequisite.name == "peg-out confirm funding")
+        .map(|prerequisite| prerequisite.outpoint)
+        .collect();
+    assert_eq!(
+        funding_outpoints,
+        vec![
+            OutPoint::new(funding_txid, 0),
+            OutPoint::new(funding_txid, 1)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_peg_out_confirm_fee_scales_with_inputs() {
+    let (config, peg_in_graph, _, _) = setup_and_create_stub_graphs().await;
+
+    assert_eq!(peg_out_confirm_fee(1), Amount::from_sat(FEE_AMOUNT));
+    assert_eq!(
+        peg_out_confirm_fee(3),
+        Amount::from_sat(FEE_AMOUNT + 2 * ADDITIONAL_INPUT_FEE_AMOUNT)
+    );
+
+    // the inputs cover the fee of a single input, but not of three
+    let funding_txid =
+        Txid::from_str("4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900").unwrap();
+    let result = PegOutGraph::new(
+        &config.operator_context,
+        &peg_in_graph,
+        (0..3)
+            .map(|vout| Input {
+                outpoint: OutPoint {
+                    txid: funding_txid,
+                    vout,
+                },
+                amount: Amount::from_sat(4_000),
+            })
+            .collect(),
+        PegOutGraphConfig::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(GraphConstructionError::InvalidAmount(field)) if field == "peg_out_confirm"
+    ));
+}
+
+#[tokio::test]
+async fn test_peg_out_confirm_without_inputs() {
+    let (config, peg_in_graph, _, _) = setup_and_create_stub_graphs().await;
+
+    let result = PegOutGraph::new(
+        &config.operator_context,
+        &peg_in_graph,
+        vec![],
+        PegOutGraphConfig::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(GraphConstructionError::MissingInput(tx_name)) if tx_name == "peg_out_confirm"
+    ));
+}
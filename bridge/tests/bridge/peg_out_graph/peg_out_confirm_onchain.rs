@@ -0,0 +1,70 @@
+use bitcoin::{consensus::encode::deserialize_hex, Amount, Transaction};
+use serde_json::Value;
+
+use bridge::{
+    graphs::peg_out::{PegOutConfirmError, PegOutGraph},
+    scripts::generate_burn_script,
+    serialization::serialize,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+// The tx as the operator would broadcast it, taken from the graph's stored copy.
+fn get_graph_peg_out_confirm_tx(peg_out_graph: &PegOutGraph) -> Transaction {
+    let json: Value = serde_json::from_str(&serialize(peg_out_graph)).unwrap();
+    deserialize_hex(json["peg_out_confirm_transaction"]["tx"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_peg_out_confirm_onchain_matches() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let onchain_tx = get_graph_peg_out_confirm_tx(&peg_out_graph);
+
+    assert_eq!(
+        peg_out_graph.compare_peg_out_confirm_inputs(&onchain_tx),
+        Ok(())
+    );
+    assert_eq!(
+        peg_out_graph.compare_peg_out_confirm_outputs(&onchain_tx),
+        Ok(())
+    );
+}
+
+#[tokio::test]
+async fn test_peg_out_confirm_onchain_mismatch() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut different_script_tx = get_graph_peg_out_confirm_tx(&peg_out_graph);
+    different_script_tx.output[0].script_pubkey = generate_burn_script();
+    assert_eq!(
+        peg_out_graph.compare_peg_out_confirm_outputs(&different_script_tx),
+        Err(PegOutConfirmError::OutputMismatch(0))
+    );
+
+    let mut different_amount_tx = get_graph_peg_out_confirm_tx(&peg_out_graph);
+    let last_vout = different_amount_tx.output.len() - 1;
+    different_amount_tx.output[last_vout].value -= Amount::from_sat(1);
+    assert_eq!(
+        peg_out_graph.compare_peg_out_confirm_outputs(&different_amount_tx),
+        Err(PegOutConfirmError::OutputMismatch(last_vout))
+    );
+
+    // a tx with the same outputs that spends only some of the funding inputs
+    let mut missing_input_tx = get_graph_peg_out_confirm_tx(&peg_out_graph);
+    missing_input_tx.input[0].previous_output.vout += 1;
+    assert_eq!(
+        peg_out_graph.compare_peg_out_confirm_inputs(&missing_input_tx),
+        Err(PegOutConfirmError::InputMismatch(0))
+    );
+
+    let mut missing_output_tx = get_graph_peg_out_confirm_tx(&peg_out_graph);
+    missing_output_tx.output.pop();
+    assert_eq!(
+        peg_out_graph.compare_peg_out_confirm_outputs(&missing_output_tx),
+        Err(PegOutConfirmError::OutputCountMismatch {
+            expected: last_vout + 1,
+            onchain: last_vout,
+        })
+    );
+}
@@ -0,0 +1,120 @@
+use std::str::FromStr;
+
+use bitcoin::{Network, OutPoint, Txid};
+
+use bridge::{
+    client::chain::chain::PegOutDestinationError,
+    scripts::{
+        generate_p2pkh_address, generate_p2wpkh_address,
+        generate_pay_to_pubkey_hash_with_inscription_script_address,
+    },
+    transactions::{base::Input, peg_out::PegOutTransaction, pre_signed::PreSignedTransaction},
+};
+
+use super::utils::{generate_stub_peg_out_event, setup_and_create_stub_graphs};
+
+fn stub_outpoint() -> OutPoint {
+    OutPoint {
+        txid: Txid::from_str("0e6719ac074b0e3cac76d057643506faa1c266b322aa9cf4c6f635fe63b14327")
+            .unwrap(),
+        vout: 0,
+    }
+}
+
+#[tokio::test]
+async fn test_peg_out_destination_valid() {
+    let (config, _, _, _) = setup_and_create_stub_graphs().await;
+    let network = config.withdrawer_context.network;
+    let event = generate_stub_peg_out_event(&config, stub_outpoint());
+
+    let address = event.destination_address(network).unwrap();
+    assert_eq!(
+        address,
+        generate_p2pkh_address(network, &config.withdrawer_context.withdrawer_public_key)
+    );
+
+    let payout_script_pubkey = event.payout_script_pubkey(network).unwrap();
+    assert_eq!(
+        payout_script_pubkey,
+        generate_pay_to_pubkey_hash_with_inscription_script_address(
+            network,
+            &config
+                .withdrawer_context
+                .withdrawer_public_key
+                .pubkey_hash(),
+            event.timestamp,
+            &event.withdrawer_chain_address,
+        )
+        .script_pubkey()
+    );
+
+    let peg_out_tx = PegOutTransaction::new(
+        &config.operator_context,
+        &event,
+        Input {
+            outpoint: stub_outpoint(),
+            amount: event.amount,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        peg_out_tx.tx().output[0].script_pubkey,
+        payout_script_pubkey
+    );
+}
+
+#[tokio::test]
+async fn test_peg_out_destination_malformed() {
+    let (config, _, _, _) = setup_and_create_stub_graphs().await;
+    let network = config.withdrawer_context.network;
+    let event = generate_stub_peg_out_event(&config, stub_outpoint());
+
+    let mut invalid_event = event.clone();
+    invalid_event.withdrawer_destination_address = "not an address".to_string();
+    assert_eq!(
+        invalid_event.destination_address(network),
+        Err(PegOutDestinationError::InvalidAddress(
+            "not an address".to_string()
+        ))
+    );
+
+    let mut mainnet_event = event.clone();
+    mainnet_event.withdrawer_destination_address = generate_p2pkh_address(
+        Network::Bitcoin,
+        &config.withdrawer_context.withdrawer_public_key,
+    )
+    .to_string();
+    assert!(matches!(
+        mainnet_event.destination_address(network),
+        Err(PegOutDestinationError::WrongNetwork(_))
+    ));
+
+    let mut segwit_event = event.clone();
+    segwit_event.withdrawer_destination_address =
+        generate_p2wpkh_address(network, &config.withdrawer_context.withdrawer_public_key)
+            .to_string();
+    assert_eq!(
+        segwit_event.destination_address(network),
+        Err(PegOutDestinationError::NotPayToPubkeyHash)
+    );
+
+    let mut mismatched_event = event.clone();
+    mismatched_event.withdrawer_destination_address =
+        generate_p2pkh_address(network, &config.operator_context.operator_public_key).to_string();
+    assert_eq!(
+        mismatched_event.destination_address(network),
+        Err(PegOutDestinationError::PubkeyHashMismatch)
+    );
+    assert!(mismatched_event.payout_script_pubkey(network).is_err());
+    assert!(matches!(
+        PegOutTransaction::new(
+            &config.operator_context,
+            &mismatched_event,
+            Input {
+                outpoint: stub_outpoint(),
+                amount: mismatched_event.amount,
+            },
+        ),
+        Err(PegOutDestinationError::PubkeyHashMismatch)
+    ));
+}
@@ -0,0 +1,156 @@
+use std::str::FromStr;
+
+use bitcoin::{OutPoint, Txid};
+use esplora_client::{Error, TxStatus};
+
+use bridge::graphs::{
+    base::BaseGraph,
+    dag::TxRole,
+    peg_out::{PegOutGraph, PendingTimelock},
+};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+const KICK_OFF_2_HEIGHT: u32 = 100;
+
+fn confirmed(block_height: u32) -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed: true,
+        block_height: Some(block_height),
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+fn unconfirmed() -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed: false,
+        block_height: None,
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+async fn setup_presigned_peg_out_graph() -> PegOutGraph {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    peg_out_graph
+}
+
+fn timelock(peg_out_graph: &PegOutGraph, tx_name: &str) -> u32 {
+    peg_out_graph
+        .timelocks()
+        .into_iter()
+        .find(|timelock| timelock.tx_name == tx_name)
+        .unwrap()
+        .num_blocks
+}
+
+// Pending timelock of a graph whose kick-off 2 confirmed at KICK_OFF_2_HEIGHT.
+fn kicked_off_pending_timelock(
+    peg_out_graph: &PegOutGraph,
+    challenge_status: Result<TxStatus, Error>,
+    assert_initial_status: Result<TxStatus, Error>,
+    assert_final_status: Result<TxStatus, Error>,
+    blockchain_height: u32,
+) -> Option<PendingTimelock> {
+    peg_out_graph.interpret_pending_timelock(
+        &assert_initial_status,
+        &assert_final_status,
+        &challenge_status,
+        &unconfirmed(),
+        &unconfirmed(),
+        &confirmed(KICK_OFF_2_HEIGHT - 3),
+        &confirmed(KICK_OFF_2_HEIGHT - 2),
+        &confirmed(KICK_OFF_2_HEIGHT),
+        &unconfirmed(),
+        Some(&confirmed(KICK_OFF_2_HEIGHT - 4)),
+        &unconfirmed(),
+        &confirmed(KICK_OFF_2_HEIGHT - 1),
+        &unconfirmed(),
+        &unconfirmed(),
+        blockchain_height,
+    )
+}
+
+#[tokio::test]
+async fn test_pending_timelock_connector_3() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+    let take_1_timelock = timelock(&peg_out_graph, "take_1");
+
+    // stale height from before kick-off 2 confirmed
+    assert_eq!(
+        kicked_off_pending_timelock(
+            &peg_out_graph,
+            unconfirmed(),
+            unconfirmed(),
+            unconfirmed(),
+            KICK_OFF_2_HEIGHT - 2,
+        ),
+        Some(PendingTimelock {
+            connector: "connector_3".to_string(),
+            blocks_remaining: take_1_timelock + 2,
+            tx_role: TxRole::Take1,
+        })
+    );
+
+    // take 1 is available
+    assert_eq!(
+        kicked_off_pending_timelock(
+            &peg_out_graph,
+            unconfirmed(),
+            unconfirmed(),
+            unconfirmed(),
+            KICK_OFF_2_HEIGHT + take_1_timelock,
+        ),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_pending_timelock_connector_4() {
+    let peg_out_graph = setup_presigned_peg_out_graph().await;
+    let take_2_timelock = timelock(&peg_out_graph, "take_2");
+
+    assert_eq!(
+        kicked_off_pending_timelock(
+            &peg_out_graph,
+            confirmed(KICK_OFF_2_HEIGHT + 1),
+            confirmed(KICK_OFF_2_HEIGHT + 2),
+            confirmed(KICK_OFF_2_HEIGHT + 3),
+            KICK_OFF_2_HEIGHT + 2,
+        ),
+        Some(PendingTimelock {
+            connector: "connector_4".to_string(),
+            blocks_remaining: take_2_timelock + 1,
+            tx_role: TxRole::Take2,
+        })
+    );
+
+    // assert final can be broadcast right away
+    assert_eq!(
+        kicked_off_pending_timelock(
+            &peg_out_graph,
+            confirmed(KICK_OFF_2_HEIGHT + 1),
+            confirmed(KICK_OFF_2_HEIGHT + 2),
+            unconfirmed(),
+            KICK_OFF_2_HEIGHT + 2,
+        ),
+        None
+    );
+}
@@ -0,0 +1,177 @@
+use esplora_client::{Error, TxStatus};
+
+use bridge::graphs::peg_out::{GraphPhase, PegOutGraph};
+
+use super::utils::setup_and_create_stub_graphs;
+
+fn status(confirmed: bool) -> Result<TxStatus, Error> {
+    Ok(TxStatus {
+        confirmed,
+        block_height: confirmed.then_some(100),
+        block_hash: None,
+        block_time: None,
+    })
+}
+
+// Names of the confirmed txs, in the order of the status arguments.
+const TX_NAMES: [&str; 14] = [
+    "assert_initial",
+    "assert_final",
+    "challenge",
+    "disprove_chain",
+    "disprove",
+    "peg_out_confirm",
+    "kick_off_1",
+    "kick_off_2",
+    "kick_off_timeout",
+    "peg_out",
+    "start_time_timeout",
+    "start_time",
+    "take_1",
+    "take_2",
+];
+
+fn phase(peg_out_graph: &PegOutGraph, confirmed_txs: &[&str]) -> GraphPhase {
+    assert!(confirmed_txs.iter().all(|tx| TX_NAMES.contains(tx)));
+    let s: Vec<Result<TxStatus, Error>> = TX_NAMES
+        .iter()
+        .map(|tx| status(confirmed_txs.contains(tx)))
+        .collect();
+
+    peg_out_graph.interpret_phase(
+        &s[0],
+        &s[1],
+        &s[2],
+        &s[3],
+        &s[4],
+        &s[5],
+        &s[6],
+        &s[7],
+        &s[8],
+        Some(&s[9]),
+        &s[10],
+        &s[11],
+        &s[12],
+        &s[13],
+    )
+}
+
+#[tokio::test]
+async fn test_phase_mapping() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let snapshots: Vec<(&[&str], GraphPhase)> = vec![
+        (&[], GraphPhase::Setup),
+        (&["peg_out"], GraphPhase::Funded),
+        (&["peg_out", "peg_out_confirm"], GraphPhase::Funded),
+        (
+            &["peg_out", "peg_out_confirm", "kick_off_1"],
+            GraphPhase::InFlight,
+        ),
+        (
+            &[
+                "peg_out",
+                "peg_out_confirm",
+                "kick_off_1",
+                "start_time",
+                "kick_off_2",
+            ],
+            GraphPhase::InFlight,
+        ),
+        (
+            &["peg_out", "peg_out_confirm", "kick_off_1", "challenge"],
+            GraphPhase::Disputed,
+        ),
+        (
+            &[
+                "peg_out",
+                "peg_out_confirm",
+                "kick_off_1",
+                "challenge",
+                "start_time",
+                "kick_off_2",
+                "assert_initial",
+                "assert_final",
+            ],
+            GraphPhase::Disputed,
+        ),
+        (
+            &[
+                "peg_out",
+                "peg_out_confirm",
+                "kick_off_1",
+                "start_time",
+                "kick_off_2",
+                "take_1",
+            ],
+            GraphPhase::Settled,
+        ),
+        (
+            &[
+                "peg_out",
+                "peg_out_confirm",
+                "kick_off_1",
+                "challenge",
+                "start_time",
+                "kick_off_2",
+                "assert_initial",
+                "assert_final",
+                "take_2",
+            ],
+            GraphPhase::Settled,
+        ),
+        (
+            &[
+                "peg_out",
+                "peg_out_confirm",
+                "kick_off_1",
+                "kick_off_timeout",
+            ],
+            GraphPhase::Settled,
+        ),
+        (
+            &[
+                "peg_out",
+                "peg_out_confirm",
+                "kick_off_1",
+                "start_time",
+                "kick_off_2",
+                "disprove_chain",
+            ],
+            GraphPhase::Settled,
+        ),
+    ];
+
+    for (confirmed_txs, expected_phase) in snapshots {
+        assert_eq!(
+            phase(&peg_out_graph, confirmed_txs),
+            expected_phase,
+            "confirmed txs: {confirmed_txs:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_phase_without_peg_out_tx() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let unconfirmed = status(false);
+
+    let phase = peg_out_graph.interpret_phase(
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+        None,
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+        &unconfirmed,
+    );
+
+    assert_eq!(phase, GraphPhase::Setup);
+}
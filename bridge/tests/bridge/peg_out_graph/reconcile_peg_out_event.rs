@@ -0,0 +1,107 @@
+use std::str::FromStr;
+
+use bitcoin::{OutPoint, Txid};
+
+use bridge::{
+    graphs::peg_out::{CommitmentMessageId, ReconcileOutcome},
+    transactions::pre_signed::PreSignedTransaction,
+};
+
+use super::utils::{generate_stub_peg_out_event, set_stub_peg_out, setup_and_create_stub_graphs};
+
+#[tokio::test]
+async fn test_reconcile_peg_out_event_before_kick_off() {
+    let (config, peg_in_graph, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let source_outpoint = OutPoint {
+        txid: peg_in_graph.peg_in_confirm_transaction.tx().compute_txid(),
+        vout: 0,
+    };
+    let event = generate_stub_peg_out_event(&config, source_outpoint);
+    assert_eq!(
+        peg_out_graph.reconcile_peg_out_event(&[event.clone()]),
+        ReconcileOutcome::Updated(Some(event.clone()))
+    );
+    assert_eq!(
+        peg_out_graph.reconcile_peg_out_event(&[event.clone()]),
+        ReconcileOutcome::Unchanged
+    );
+
+    // the L2 reorg re-includes the peg-out in another tx
+    let mut reorged_event = event.clone();
+    reorged_event.tx_hash = [1u8; 32].into();
+    assert_eq!(
+        peg_out_graph.reconcile_peg_out_event(&[reorged_event.clone()]),
+        ReconcileOutcome::Updated(Some(reorged_event.clone()))
+    );
+    assert_eq!(
+        peg_out_graph.peg_out_chain_event,
+        Some(reorged_event.clone())
+    );
+
+    assert_eq!(
+        peg_out_graph.reconcile_peg_out_event(&[reorged_event.clone(), reorged_event]),
+        ReconcileOutcome::NotUnique
+    );
+
+    // the L2 reorg drops the peg-out altogether
+    assert_eq!(
+        peg_out_graph.reconcile_peg_out_event(&[]),
+        ReconcileOutcome::Updated(None)
+    );
+    assert!(!peg_out_graph.is_peg_out_initiated());
+}
+
+#[tokio::test]
+async fn test_reconcile_peg_out_event_after_kick_off() {
+    let (config, peg_in_graph, mut peg_out_graph, commitment_secrets) =
+        setup_and_create_stub_graphs().await;
+
+    let peg_out_outpoint = OutPoint {
+        txid: Txid::from_str("8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182")
+            .unwrap(),
+        vout: 0,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, peg_out_outpoint);
+    let event = generate_stub_peg_out_event(
+        &config,
+        OutPoint {
+            txid: peg_in_graph.peg_in_confirm_transaction.tx().compute_txid(),
+            vout: 0,
+        },
+    );
+    peg_out_graph.peg_out_chain_event = Some(event.clone());
+    peg_out_graph
+        .sign_kick_off_1(
+            &config.operator_context,
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
+        )
+        .unwrap();
+
+    // a change that keeps the committed tx hash is still followed
+    let mut retimed_event = event.clone();
+    retimed_event.timestamp += 1;
+    assert_eq!(
+        peg_out_graph.reconcile_peg_out_event(&[retimed_event.clone()]),
+        ReconcileOutcome::Updated(Some(retimed_event.clone()))
+    );
+
+    let mut reorged_event = retimed_event.clone();
+    reorged_event.tx_hash = [1u8; 32].into();
+    assert_eq!(
+        peg_out_graph.reconcile_peg_out_event(&[reorged_event.clone()]),
+        ReconcileOutcome::CommittedToStaleEvent {
+            current_event: Some(reorged_event)
+        }
+    );
+    assert_eq!(
+        peg_out_graph.reconcile_peg_out_event(&[]),
+        ReconcileOutcome::CommittedToStaleEvent {
+            current_event: None
+        }
+    );
+
+    // the committed event is kept for the operator to act on
+    assert_eq!(peg_out_graph.peg_out_chain_event, Some(retimed_event));
+}
@@ -0,0 +1,157 @@
+use bitcoin::{
+    consensus::encode::deserialize_hex, hashes::Hash, Amount, OutPoint, ScriptBuf, Transaction,
+    TxIn, TxOut, Txid,
+};
+use serde_json::Value;
+
+use bridge::{
+    graphs::peg_out::{PegOutGraph, PegOutGraphConfig, RecoveryError},
+    superblock::SuperblockPolicy,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+fn graph_tx(json: &Value, tx_name: &str) -> Transaction {
+    deserialize_hex(
+        json[format!("{tx_name}_transaction")]["tx"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+fn peg_out_confirm_prev_outs(json: &Value) -> Vec<TxOut> {
+    deserialize_hex(
+        json["peg_out_confirm_transaction"]["prev_outs"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_recover_forgotten_graph() {
+    let (config, peg_in_graph, peg_out_graph, commitment_secrets) =
+        setup_and_create_stub_graphs().await;
+
+    // all the operator has left are the txs on chain and the commitment secrets
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let onchain_txs: Vec<Transaction> = ["kick_off_1", "start_time", "kick_off_2", "take_1"]
+        .into_iter()
+        .map(|tx_name| graph_tx(&json, tx_name))
+        .collect();
+
+    let recovered_graph = PegOutGraph::recover_from_txs(
+        &peg_in_graph,
+        &config.operator_context,
+        &PegOutGraphConfig::default(),
+        commitment_secrets,
+        SuperblockPolicy::default(),
+        &graph_tx(&json, "peg_out_confirm"),
+        &peg_out_confirm_prev_outs(&json),
+        &onchain_txs,
+    )
+    .unwrap();
+
+    assert!(recovered_graph == peg_out_graph);
+    assert_eq!(recovered_graph.txids(), peg_out_graph.txids());
+}
+
+#[tokio::test]
+async fn test_recover_graph_before_kick_off_1() {
+    let (config, peg_in_graph, peg_out_graph, commitment_secrets) =
+        setup_and_create_stub_graphs().await;
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+
+    // connector 6 is not spent yet, only peg-out-confirm can be checked
+    let recovered_graph = PegOutGraph::recover_from_txs(
+        &peg_in_graph,
+        &config.operator_context,
+        &PegOutGraphConfig::default(),
+        commitment_secrets,
+        SuperblockPolicy::default(),
+        &graph_tx(&json, "peg_out_confirm"),
+        &peg_out_confirm_prev_outs(&json),
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(recovered_graph.txids(), peg_out_graph.txids());
+}
+
+#[tokio::test]
+async fn test_recover_graph_with_completed_challenge() {
+    let (config, peg_in_graph, peg_out_graph, commitment_secrets) =
+        setup_and_create_stub_graphs().await;
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+
+    // the challenger added a crowdfunding input and a refund output
+    let mut challenge_tx = graph_tx(&json, "challenge");
+    challenge_tx.input.push(TxIn {
+        previous_output: OutPoint {
+            txid: Txid::all_zeros(),
+            vout: 0,
+        },
+        ..TxIn::default()
+    });
+    challenge_tx.output.push(TxOut {
+        value: Amount::from_sat(10_000),
+        script_pubkey: ScriptBuf::new(),
+    });
+
+    let result = PegOutGraph::recover_from_txs(
+        &peg_in_graph,
+        &config.operator_context,
+        &PegOutGraphConfig::default(),
+        commitment_secrets,
+        SuperblockPolicy::default(),
+        &graph_tx(&json, "peg_out_confirm"),
+        &peg_out_confirm_prev_outs(&json),
+        &[graph_tx(&json, "kick_off_1"), challenge_tx],
+    );
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_recover_graph_mismatch() {
+    let (config, peg_in_graph, peg_out_graph, commitment_secrets) =
+        setup_and_create_stub_graphs().await;
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let recover = |onchain_txs: &[Transaction]| {
+        PegOutGraph::recover_from_txs(
+            &peg_in_graph,
+            &config.operator_context,
+            &PegOutGraphConfig::default(),
+            commitment_secrets.clone(),
+            SuperblockPolicy::default(),
+            &graph_tx(&json, "peg_out_confirm"),
+            &peg_out_confirm_prev_outs(&json),
+            onchain_txs,
+        )
+    };
+
+    // connector 1 was spent by a kick-off 2 tx the secrets do not reproduce
+    let mut kick_off_2_tx = graph_tx(&json, "kick_off_2");
+    kick_off_2_tx.output[0].value -= Amount::from_sat(1);
+    let kick_off_2_txid = kick_off_2_tx.compute_txid();
+    assert!(matches!(
+        recover(&[graph_tx(&json, "kick_off_1"), kick_off_2_tx]),
+        Err(RecoveryError::TxidMismatch {
+            tx_name: "kick_off_2",
+            onchain_txid,
+            ..
+        }) if onchain_txid == kick_off_2_txid
+    ));
+
+    // a tx spending nothing of the graph
+    let unknown_tx = Transaction {
+        input: vec![TxIn::default()],
+        ..graph_tx(&json, "kick_off_1")
+    };
+    let unknown_txid = unknown_tx.compute_txid();
+    assert_eq!(
+        recover(&[unknown_tx]).err(),
+        Some(RecoveryError::UnknownTx(unknown_txid))
+    );
+}
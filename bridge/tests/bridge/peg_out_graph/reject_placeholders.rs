@@ -0,0 +1,38 @@
+use bridge::graphs::peg_out::{PegOutGraph, PegOutGraphConfig};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_reject_placeholders() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // placeholders only matter once real funds are at stake
+    assert!(peg_out_graph.reject_placeholders().is_ok());
+
+    let mut json = serde_json::to_value(&peg_out_graph).unwrap();
+    json["network"] = serde_json::json!("bitcoin");
+    let mainnet_graph: PegOutGraph = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(
+        mainnet_graph.reject_placeholders(),
+        Err(vec!["challenge", "disprove"])
+    );
+
+    // a configured challenge amount is accepted even if it equals the default one
+    json["config"] = serde_json::to_value(
+        PegOutGraphConfig::default()
+            .with_challenge_amount(PegOutGraphConfig::default().challenge_amount()),
+    )
+    .unwrap();
+    let mainnet_graph: PegOutGraph = serde_json::from_value(json).unwrap();
+    assert_eq!(mainnet_graph.reject_placeholders(), Err(vec!["disprove"]));
+
+    // so is one set after the other settings
+    let config = PegOutGraphConfig::default()
+        .with_challenge_amount(PegOutGraphConfig::default().challenge_amount())
+        .with_take_anchors(true);
+    assert!(!config.is_placeholder_challenge_amount());
+    assert!(PegOutGraphConfig::default()
+        .with_take_anchors(true)
+        .is_placeholder_challenge_amount());
+}
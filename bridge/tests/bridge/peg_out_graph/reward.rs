@@ -0,0 +1,58 @@
+use bitcoin::Network;
+
+use bridge::{
+    graphs::peg_out::{MissingRewardScript, PegOutGraph},
+    scripts::{generate_burn_script, generate_pay_to_pubkey_script_address},
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_reward_script_pubkey_default_and_override() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    assert!(peg_out_graph.reward_script_pubkey().is_none());
+    assert_eq!(
+        peg_out_graph.resolve_reward_script_pubkey(None),
+        Err(MissingRewardScript)
+    );
+
+    let reward_address = generate_pay_to_pubkey_script_address(
+        config.verifier_0_context.network,
+        &config.verifier_0_context.verifier_public_key,
+    );
+    peg_out_graph
+        .set_reward_address(reward_address.as_unchecked().clone())
+        .unwrap();
+
+    // default is used when no output script is given
+    assert_eq!(
+        peg_out_graph.resolve_reward_script_pubkey(None),
+        Ok(reward_address.script_pubkey())
+    );
+
+    // call-time output script takes precedence over the default
+    let override_script_pubkey = generate_burn_script();
+    assert_eq!(
+        peg_out_graph.resolve_reward_script_pubkey(Some(override_script_pubkey.clone())),
+        Ok(override_script_pubkey)
+    );
+
+    // the default stays with the party that set it
+    let shared_graph: PegOutGraph =
+        serde_json::from_value(serde_json::to_value(&peg_out_graph).unwrap()).unwrap();
+    assert!(shared_graph.reward_script_pubkey().is_none());
+}
+
+#[tokio::test]
+async fn test_reward_address_wrong_network() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mainnet_address = generate_pay_to_pubkey_script_address(
+        Network::Bitcoin,
+        &config.verifier_0_context.verifier_public_key,
+    );
+    assert!(peg_out_graph
+        .set_reward_address(mainnet_address.as_unchecked().clone())
+        .is_err());
+    assert!(peg_out_graph.reward_script_pubkey().is_none());
+}
@@ -0,0 +1,56 @@
+use bridge::graphs::{base::BaseGraph, peg_out::NonceBlobError};
+
+use super::utils::setup_and_create_stub_graphs;
+
+const KEY: [u8; 32] = [7; 32];
+
+#[tokio::test]
+async fn test_secret_nonces_survive_restart() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let verifier_0_public_key = config.verifier_0_context.verifier_public_key;
+    let verifier_1_public_key = config.verifier_1_context.verifier_public_key;
+
+    let secret_nonces_0 = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    let secret_nonces_1 = peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    let blob =
+        peg_out_graph.export_secret_nonces(&config.verifier_0_context, &secret_nonces_0, &KEY);
+    drop(secret_nonces_0);
+
+    assert!(matches!(
+        peg_out_graph.import_secret_nonces(&config.verifier_0_context, &blob, &[8; 32]),
+        Err(NonceBlobError::Decryption)
+    ));
+    assert!(matches!(
+        peg_out_graph.import_secret_nonces(&config.verifier_1_context, &blob, &KEY),
+        Err(NonceBlobError::GraphMismatch)
+    ));
+
+    let restored_secret_nonces = peg_out_graph
+        .import_secret_nonces(&config.verifier_0_context, &blob, &KEY)
+        .unwrap();
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &restored_secret_nonces);
+    peg_out_graph.verifier_sign(&config.verifier_1_context, &secret_nonces_1);
+    assert!(peg_out_graph.has_all_signatures(&[verifier_0_public_key, verifier_1_public_key]));
+    assert_eq!(peg_out_graph.dry_finalize_all(), Ok(()));
+
+    // the nonces must not sign a second time
+    assert!(matches!(
+        peg_out_graph.import_secret_nonces(&config.verifier_0_context, &blob, &KEY),
+        Err(NonceBlobError::AlreadySigned(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_secret_nonces_of_previous_round_rejected() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    let blob = peg_out_graph.export_secret_nonces(&config.verifier_0_context, &secret_nonces, &KEY);
+
+    // a new nonce round replaces the public nonces the old secret nonces belong to
+    peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    assert!(matches!(
+        peg_out_graph.import_secret_nonces(&config.verifier_0_context, &blob, &KEY),
+        Err(NonceBlobError::StaleNonces(_))
+    ));
+}
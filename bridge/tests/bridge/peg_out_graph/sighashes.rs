@@ -0,0 +1,61 @@
+use bitcoin::{secp256k1::Message, TapSighashType};
+use secp256k1::SECP256K1;
+
+use bridge::transactions::{
+    pre_signed::PreSignedTransaction, signing::generate_taproot_leaf_schnorr_signature,
+    start_time_timeout::StartTimeTimeoutTransaction, take_1::Take1Transaction,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_sighashes_match_internal_signer() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let take_1: Take1Transaction =
+        serde_json::from_value(json["take_1_transaction"].clone()).unwrap();
+
+    // input 2 spends the P2WSH connector 3 and is signed with ECDSA
+    let sighashes = take_1.sighashes();
+    let input_indexes: Vec<usize> = sighashes.iter().map(|(index, _, _)| *index).collect();
+    assert_eq!(input_indexes, vec![0, 1, 3]);
+
+    let keypair = &config.operator_context.operator_keypair;
+    let (x_only_public_key, _) = keypair.x_only_public_key();
+    for (input_index, sighash_type, sighash) in sighashes {
+        assert_eq!(sighash_type, TapSighashType::All);
+        let signature = generate_taproot_leaf_schnorr_signature(
+            &mut take_1.tx().clone(),
+            take_1.prev_outs(),
+            input_index,
+            sighash_type,
+            &take_1.prev_scripts()[input_index],
+            keypair,
+        );
+        assert!(SECP256K1
+            .verify_schnorr(
+                &signature.signature,
+                &Message::from_digest(sighash),
+                &x_only_public_key
+            )
+            .is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_sighashes_use_presigned_sighash_types() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let start_time_timeout: StartTimeTimeoutTransaction =
+        serde_json::from_value(json["start_time_timeout_transaction"].clone()).unwrap();
+
+    let sighash_types: Vec<(usize, TapSighashType)> = start_time_timeout
+        .sighashes()
+        .into_iter()
+        .map(|(input_index, sighash_type, _)| (input_index, sighash_type))
+        .collect();
+    assert_eq!(
+        sighash_types,
+        vec![(0, TapSighashType::Single), (1, TapSighashType::None)]
+    );
+}
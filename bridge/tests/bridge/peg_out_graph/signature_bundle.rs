@@ -0,0 +1,60 @@
+use bridge::graphs::base::BaseGraph;
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_apply_bundle_matches_merge() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let verifier_1_public_key = config.verifier_1_context.verifier_public_key;
+
+    // nonce round
+    let mut verifier_0_graph = peg_out_graph.clone();
+    let secret_nonces_0 = verifier_0_graph.push_verifier_nonces(&config.verifier_0_context);
+    let mut verifier_1_graph = peg_out_graph.clone();
+    let secret_nonces_1 = verifier_1_graph.push_verifier_nonces(&config.verifier_1_context);
+
+    let mut merged_graph = verifier_0_graph.clone();
+    merged_graph.merge(&verifier_1_graph);
+    let mut bundled_graph = verifier_0_graph.clone();
+    bundled_graph
+        .apply_bundle(&verifier_1_graph.signature_bundle(&verifier_1_public_key))
+        .unwrap();
+    assert!(merged_graph == bundled_graph);
+    assert!(bundled_graph.has_all_nonces_of(&config.verifier_1_context));
+
+    // signing round
+    let mut verifier_0_graph = merged_graph.clone();
+    verifier_0_graph.verifier_sign(&config.verifier_0_context, &secret_nonces_0);
+    let mut verifier_1_graph = merged_graph.clone();
+    verifier_1_graph.verifier_sign(&config.verifier_1_context, &secret_nonces_1);
+
+    let mut merged_graph = verifier_0_graph.clone();
+    merged_graph.merge(&verifier_1_graph);
+    let mut bundled_graph = verifier_0_graph.clone();
+    bundled_graph
+        .apply_bundle(&verifier_1_graph.signature_bundle(&verifier_1_public_key))
+        .unwrap();
+    assert!(merged_graph == bundled_graph);
+    assert!(bundled_graph.has_all_signatures_of(&config.verifier_1_context));
+}
+
+#[tokio::test]
+async fn test_apply_bundle_rejects_other_graph() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let (_, _, other_peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut verifier_1_graph = peg_out_graph.clone();
+    verifier_1_graph.push_verifier_nonces(&config.verifier_1_context);
+    let mut bundle =
+        verifier_1_graph.signature_bundle(&config.verifier_1_context.verifier_public_key);
+
+    // same peg-in and operator, hence the same id, but fresh commitment keys and so other txids
+    let mut coordinator_graph = other_peg_out_graph.clone();
+    assert!(coordinator_graph.apply_bundle(&bundle).is_err());
+    assert!(coordinator_graph == other_peg_out_graph);
+
+    bundle.graph_id = String::from("unknown");
+    let mut coordinator_graph = peg_out_graph.clone();
+    assert!(coordinator_graph.apply_bundle(&bundle).is_err());
+    assert!(coordinator_graph == peg_out_graph);
+}
@@ -0,0 +1,176 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bitcoin::{hashes::Hash, BlockHash, Txid};
+use esplora_client::{Error, TxStatus};
+
+use bridge::graphs::status_cache::{StatusCache, TxStatusSource};
+
+#[derive(Default)]
+struct MockSource {
+    statuses: Mutex<HashMap<Txid, TxStatus>>,
+    block_hashes: Mutex<HashMap<u32, BlockHash>>,
+    status_lookups: AtomicUsize,
+    pending_lookups: AtomicUsize,
+    max_pending_lookups: AtomicUsize,
+}
+
+impl MockSource {
+    fn confirm(&self, txid: Txid, block_height: u32, block_hash: BlockHash) {
+        self.block_hashes
+            .lock()
+            .unwrap()
+            .insert(block_height, block_hash);
+        self.statuses.lock().unwrap().insert(
+            txid,
+            TxStatus {
+                confirmed: true,
+                block_height: Some(block_height),
+                block_hash: Some(block_hash),
+                block_time: None,
+            },
+        );
+    }
+
+    fn unconfirm(&self, txid: Txid) {
+        self.statuses.lock().unwrap().insert(
+            txid,
+            TxStatus {
+                confirmed: false,
+                block_height: None,
+                block_hash: None,
+                block_time: None,
+            },
+        );
+    }
+
+    fn status_lookups(&self) -> usize { self.status_lookups.load(Ordering::SeqCst) }
+
+    fn max_pending_lookups(&self) -> usize { self.max_pending_lookups.load(Ordering::SeqCst) }
+}
+
+#[async_trait]
+impl TxStatusSource for MockSource {
+    async fn tx_status(&self, txid: &Txid) -> Result<TxStatus, Error> {
+        self.status_lookups.fetch_add(1, Ordering::SeqCst);
+        let pending_lookups = self.pending_lookups.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_pending_lookups
+            .fetch_max(pending_lookups, Ordering::SeqCst);
+        tokio::task::yield_now().await;
+        self.pending_lookups.fetch_sub(1, Ordering::SeqCst);
+
+        self.statuses
+            .lock()
+            .unwrap()
+            .get(txid)
+            .cloned()
+            .ok_or(Error::TransactionNotFound(*txid))
+    }
+
+    async fn block_hash(&self, block_height: u32) -> Result<BlockHash, Error> {
+        Ok(self.block_hashes.lock().unwrap()[&block_height])
+    }
+}
+
+fn txid(byte: u8) -> Txid { Txid::from_byte_array([byte; 32]) }
+
+fn block_hash(byte: u8) -> BlockHash { BlockHash::from_byte_array([byte; 32]) }
+
+#[tokio::test]
+async fn test_status_cache_keeps_confirmed_status() {
+    let source = MockSource::default();
+    source.confirm(txid(1), 100, block_hash(1));
+    let mut cache = StatusCache::new(Duration::from_secs(3600));
+
+    let status = cache.status(&source, &txid(1)).await.unwrap();
+    assert!(status.confirmed);
+    assert_eq!(cache.status(&source, &txid(1)).await.unwrap(), status);
+    assert_eq!(source.status_lookups(), 1);
+
+    // failed lookups are not cached
+    assert!(cache.status(&source, &txid(2)).await.is_err());
+    assert!(cache.status(&source, &txid(2)).await.is_err());
+    assert_eq!(source.status_lookups(), 3);
+}
+
+#[tokio::test]
+async fn test_status_cache_unconfirmed_status_expires() {
+    let source = MockSource::default();
+    source.unconfirm(txid(1));
+    let mut cache = StatusCache::new(Duration::from_secs(3600));
+
+    assert!(!cache.status(&source, &txid(1)).await.unwrap().confirmed);
+    source.confirm(txid(1), 100, block_hash(1));
+    assert!(!cache.status(&source, &txid(1)).await.unwrap().confirmed);
+    assert_eq!(source.status_lookups(), 1);
+
+    let mut cache = StatusCache::new(Duration::ZERO);
+    source.unconfirm(txid(1));
+    assert!(!cache.status(&source, &txid(1)).await.unwrap().confirmed);
+    source.confirm(txid(1), 100, block_hash(1));
+    assert!(cache.status(&source, &txid(1)).await.unwrap().confirmed);
+}
+
+#[tokio::test]
+async fn test_status_cache_reorg_invalidates_confirmation() {
+    let source = MockSource::default();
+    source.confirm(txid(1), 100, block_hash(1));
+    source.confirm(txid(2), 100, block_hash(1));
+    source.confirm(txid(3), 99, block_hash(3));
+    // every lookup rechecks the anchor block
+    let mut cache = StatusCache::new(Duration::ZERO);
+
+    let statuses = cache.statuses(&source, &[txid(1), txid(2), txid(3)]).await;
+    assert!(statuses
+        .iter()
+        .all(|status| status.as_ref().unwrap().confirmed));
+    assert!(cache.status(&source, &txid(1)).await.unwrap().confirmed);
+    assert_eq!(source.status_lookups(), 3);
+
+    // block 100 is orphaned, tx 1 is back in the mempool and tx 2 is mined again
+    source.unconfirm(txid(1));
+    source.confirm(txid(2), 100, block_hash(2));
+
+    let statuses = cache.statuses(&source, &[txid(1), txid(2), txid(3)]).await;
+    assert!(!statuses[0].as_ref().unwrap().confirmed);
+    assert_eq!(
+        statuses[1].as_ref().unwrap().block_hash,
+        Some(block_hash(2))
+    );
+    assert_eq!(
+        statuses[2].as_ref().unwrap().block_hash,
+        Some(block_hash(3))
+    );
+    // block 99 is below the reorg, tx 3 was not looked up again
+    assert_eq!(source.status_lookups(), 5);
+}
+
+#[tokio::test]
+async fn test_status_cache_fetches_misses_concurrently() {
+    let source = MockSource::default();
+    source.confirm(txid(1), 100, block_hash(1));
+    source.unconfirm(txid(2));
+    source.unconfirm(txid(3));
+    let mut cache = StatusCache::new(Duration::from_secs(3600));
+
+    assert!(cache.status(&source, &txid(1)).await.unwrap().confirmed);
+    assert_eq!(source.max_pending_lookups(), 1);
+
+    // only the misses are looked up, all at once
+    let statuses = cache
+        .statuses(&source, &[txid(1), txid(2), txid(3), txid(4)])
+        .await;
+    assert!(statuses[0].as_ref().unwrap().confirmed);
+    assert!(!statuses[1].as_ref().unwrap().confirmed);
+    assert!(!statuses[2].as_ref().unwrap().confirmed);
+    assert!(statuses[3].is_err());
+    assert_eq!(source.status_lookups(), 4);
+    assert_eq!(source.max_pending_lookups(), 3);
+}
@@ -0,0 +1,59 @@
+use bitcoin::{
+    consensus::encode::{deserialize_hex, serialize_hex},
+    hashes::Hash,
+    Transaction, Txid,
+};
+
+use bridge::graphs::peg_out::PegOutGraph;
+
+use super::utils::setup_and_create_stub_graphs;
+
+fn edit_tx(
+    peg_out_graph: &PegOutGraph,
+    field: &str,
+    edit: impl Fn(&mut Transaction),
+) -> PegOutGraph {
+    let mut json = serde_json::to_value(peg_out_graph).unwrap();
+    let mut tx: Transaction = deserialize_hex(json[field]["tx"].as_str().unwrap()).unwrap();
+    edit(&mut tx);
+    json[field]["tx"] = serialize_hex(&tx).into();
+
+    serde_json::from_value(json).unwrap()
+}
+
+#[tokio::test]
+async fn test_structural_completeness() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    assert_eq!(peg_out_graph.structural_completeness(), Ok(()));
+}
+
+#[tokio::test]
+async fn test_structural_completeness_orphaned_tx() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // disprove chain no longer spends kick-off 2
+    let orphaned_graph = edit_tx(&peg_out_graph, "disprove_chain_transaction", |tx| {
+        tx.input[0].previous_output.txid = Txid::all_zeros();
+    });
+
+    assert_eq!(
+        orphaned_graph.structural_completeness(),
+        Err(vec!["disprove_chain"])
+    );
+    assert!(!orphaned_graph.validate());
+}
+
+#[tokio::test]
+async fn test_structural_completeness_missing_tx() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let incomplete_graph = edit_tx(&peg_out_graph, "start_time_transaction", |tx| {
+        tx.output.clear();
+    });
+
+    assert_eq!(
+        incomplete_graph.structural_completeness(),
+        Err(vec!["start_time"])
+    );
+}
@@ -0,0 +1,141 @@
+use std::str::FromStr;
+
+use bitcoin::{Amount, Network, OutPoint, Txid};
+
+use bitvm::signatures::signing_winternitz::WinternitzSigningInputs;
+
+use bridge::{
+    graphs::{base::DUST_AMOUNT, peg_out::CommitmentMessageId},
+    superblock::{
+        get_start_time_block_number, get_superblock_hash_message, get_superblock_message,
+        is_superblock_in_window, SuperblockCriteria, SuperblockParams,
+    },
+    transactions::{
+        base::{BaseTransaction, Input},
+        kick_off_2::KickOff2Transaction,
+        pre_signed::PreSignedTransaction,
+        start_time::StartTimeTransaction,
+    },
+};
+
+use crate::bridge::helper::get_superblock_header;
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_committed_start_time_and_superblock() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let input = || Input {
+        outpoint: OutPoint {
+            txid: Txid::from_str(
+                "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+        amount: Amount::from_sat(DUST_AMOUNT),
+    };
+
+    let mut start_time_tx =
+        StartTimeTransaction::new(&config.operator_context, &config.connector_2, input());
+    start_time_tx
+        .sign(
+            &config.operator_context,
+            &config.connector_2,
+            get_start_time_block_number(&peg_out_graph.superblock_params()),
+            &config.commitment_secrets[&CommitmentMessageId::StartTime],
+        )
+        .unwrap();
+    assert_eq!(
+        peg_out_graph.get_committed_start_time(&start_time_tx.finalize()),
+        Some(get_start_time_block_number(
+            &peg_out_graph.superblock_params()
+        ))
+    );
+
+    let mut kick_off_2_tx =
+        KickOff2Transaction::new(&config.operator_context, &config.connector_1, input());
+    let superblock_header = get_superblock_header();
+    kick_off_2_tx
+        .sign(
+            &config.operator_context,
+            &config.connector_1,
+            &WinternitzSigningInputs {
+                message: &get_superblock_message(&superblock_header),
+                signing_key: &config.commitment_secrets[&CommitmentMessageId::Superblock],
+            },
+            &WinternitzSigningInputs {
+                message: &get_superblock_hash_message(&superblock_header),
+                signing_key: &config.commitment_secrets[&CommitmentMessageId::SuperblockHash],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        peg_out_graph.get_committed_superblocks(&kick_off_2_tx.finalize()),
+        Some(vec![superblock_header])
+    );
+
+    // an unsigned kick-off 2 carries no commitment
+    let unsigned_kick_off_2_tx =
+        KickOff2Transaction::new(&config.operator_context, &config.connector_1, input());
+    assert_eq!(
+        peg_out_graph.get_committed_superblocks(unsigned_kick_off_2_tx.tx()),
+        None
+    );
+}
+
+#[test]
+fn test_superblock_in_window() {
+    let params = SuperblockParams::for_network(Network::Bitcoin);
+    let start_time = get_start_time_block_number(&params);
+
+    assert!(is_superblock_in_window(&params, start_time, start_time));
+    assert!(is_superblock_in_window(&params, start_time, start_time + 1));
+    assert!(is_superblock_in_window(
+        &params,
+        start_time,
+        start_time + params.window_blocks
+    ));
+}
+
+#[test]
+fn test_superblock_out_of_window() {
+    let params = SuperblockParams::for_network(Network::Bitcoin);
+    let start_time = get_start_time_block_number(&params);
+
+    assert!(!is_superblock_in_window(
+        &params,
+        start_time,
+        start_time - 1
+    ));
+    assert!(!is_superblock_in_window(
+        &params,
+        start_time,
+        start_time + params.window_blocks + 1
+    ));
+}
+
+#[test]
+fn test_superblock_params_per_network() {
+    let mainnet_params = SuperblockParams::for_network(Network::Bitcoin);
+    let regtest_params = SuperblockParams::for_network(Network::Regtest);
+    assert_ne!(
+        get_start_time_block_number(&regtest_params),
+        get_start_time_block_number(&mainnet_params)
+    );
+    assert!(regtest_params.window_blocks < mainnet_params.window_blocks);
+
+    // a height past the regtest window is still within the mainnet one
+    let start_time = get_start_time_block_number(&regtest_params);
+    let height = start_time + regtest_params.window_blocks + 1;
+    assert!(!is_superblock_in_window(
+        &regtest_params,
+        start_time,
+        height
+    ));
+    assert!(is_superblock_in_window(&mainnet_params, start_time, height));
+    assert_eq!(
+        SuperblockCriteria::new(&regtest_params, start_time).candidate_range,
+        start_time..=height - 1
+    );
+}
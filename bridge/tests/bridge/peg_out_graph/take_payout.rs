@@ -0,0 +1,39 @@
+use bitcoin::{consensus::encode::deserialize_hex, Amount, Transaction, TxOut};
+use serde_json::Value;
+
+use bridge::{
+    graphs::{
+        base::FEE_AMOUNT,
+        peg_out::{PegOutGraph, TakeKind},
+    },
+    serialization::serialize,
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+fn get_take_tx_and_prev_outs(
+    peg_out_graph: &PegOutGraph,
+    take_transaction: &str,
+) -> (Transaction, Vec<TxOut>) {
+    let json: Value = serde_json::from_str(&serialize(peg_out_graph)).unwrap();
+    let tx = deserialize_hex(json[take_transaction]["tx"].as_str().unwrap()).unwrap();
+    let prev_outs = deserialize_hex(json[take_transaction]["prev_outs"].as_str().unwrap()).unwrap();
+    (tx, prev_outs)
+}
+
+#[tokio::test]
+async fn test_take_payout() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    for (which, take_transaction) in [
+        (TakeKind::Take1, "take_1_transaction"),
+        (TakeKind::Take2, "take_2_transaction"),
+    ] {
+        let (take_tx, prev_outs) = get_take_tx_and_prev_outs(&peg_out_graph, take_transaction);
+        let total_input_amount: Amount = prev_outs.iter().map(|prev_out| prev_out.value).sum();
+
+        let payout = peg_out_graph.take_payout(which);
+        assert_eq!(payout, total_input_amount - Amount::from_sat(FEE_AMOUNT));
+        assert_eq!(payout, take_tx.output[0].value);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use bitcoin::{
+    absolute::LockTime, transaction::Version, Amount, PublicKey, ScriptBuf, TapSighashType,
+    Transaction, TxIn, TxOut, Witness,
+};
+use musig2::{secp256k1::schnorr::Signature, PartialSignature, PubNonce};
+
+use bridge::{
+    graphs::peg_out::{override_payout, PayoutCommitted, PayoutOverrideError, TakeKind},
+    scripts::{generate_burn_script, generate_pay_to_pubkey_script_address},
+    transactions::{
+        pre_signed::PreSignedTransaction,
+        pre_signed_musig2::{commits_to_output, PreSignedMusig2Transaction},
+    },
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+// Presigned tx whose verifier input 1 only signs output 1.
+struct SingleSignedTransaction {
+    tx: Transaction,
+    prev_outs: Vec<TxOut>,
+    prev_scripts: Vec<ScriptBuf>,
+    musig2_nonces: HashMap<usize, HashMap<PublicKey, PubNonce>>,
+    musig2_nonce_signatures: HashMap<usize, HashMap<PublicKey, Signature>>,
+    musig2_signatures: HashMap<usize, HashMap<PublicKey, PartialSignature>>,
+}
+
+impl PreSignedTransaction for SingleSignedTransaction {
+    fn tx(&self) -> &Transaction { &self.tx }
+
+    fn tx_mut(&mut self) -> &mut Transaction { &mut self.tx }
+
+    fn prev_outs(&self) -> &Vec<TxOut> { &self.prev_outs }
+
+    fn prev_scripts(&self) -> &Vec<ScriptBuf> { &self.prev_scripts }
+
+    fn sighash_type(&self, _input_index: usize) -> TapSighashType { TapSighashType::Single }
+}
+
+impl PreSignedMusig2Transaction for SingleSignedTransaction {
+    fn musig2_nonces(&self) -> &HashMap<usize, HashMap<PublicKey, PubNonce>> { &self.musig2_nonces }
+
+    fn musig2_nonces_mut(&mut self) -> &mut HashMap<usize, HashMap<PublicKey, PubNonce>> {
+        &mut self.musig2_nonces
+    }
+
+    fn musig2_nonce_signatures(&self) -> &HashMap<usize, HashMap<PublicKey, Signature>> {
+        &self.musig2_nonce_signatures
+    }
+
+    fn musig2_nonce_signatures_mut(
+        &mut self,
+    ) -> &mut HashMap<usize, HashMap<PublicKey, Signature>> {
+        &mut self.musig2_nonce_signatures
+    }
+
+    fn musig2_signatures(&self) -> &HashMap<usize, HashMap<PublicKey, PartialSignature>> {
+        &self.musig2_signatures
+    }
+
+    fn musig2_signatures_mut(
+        &mut self,
+    ) -> &mut HashMap<usize, HashMap<PublicKey, PartialSignature>> {
+        &mut self.musig2_signatures
+    }
+
+    fn verifier_inputs(&self) -> Vec<usize> { vec![1] }
+}
+
+#[tokio::test]
+async fn test_take_payout_override_rejected_when_presigned() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let new_operator_address = generate_pay_to_pubkey_script_address(
+        config.operator_context.network,
+        &config.depositor_context.depositor_public_key,
+    );
+
+    // the n-of-n signatures on the take txs sign all outputs
+    for (which, tx_name) in [(TakeKind::Take1, "take_1"), (TakeKind::Take2, "take_2")] {
+        assert_eq!(
+            peg_out_graph.take_payout_override(which, &new_operator_address),
+            Err(PayoutOverrideError::Committed(PayoutCommitted {
+                tx_name,
+                input_index: 0,
+            }))
+        );
+    }
+}
+
+#[test]
+fn test_override_payout() {
+    let payout_script_pubkey = ScriptBuf::from_bytes(vec![0x51]);
+    let tx_in = |witness: &[u8]| TxIn {
+        witness: Witness::from_slice(&[witness]),
+        ..TxIn::default()
+    };
+    let tx_out = |script_pubkey: &ScriptBuf| TxOut {
+        value: Amount::from_sat(10_000),
+        script_pubkey: script_pubkey.clone(),
+    };
+    let presigned_tx = SingleSignedTransaction {
+        tx: Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![tx_in(&[1]), tx_in(&[2])],
+            output: vec![
+                tx_out(&payout_script_pubkey),
+                tx_out(&generate_burn_script()),
+            ],
+        },
+        prev_outs: vec![],
+        prev_scripts: vec![],
+        musig2_nonces: HashMap::new(),
+        musig2_nonce_signatures: HashMap::new(),
+        musig2_signatures: HashMap::new(),
+    };
+    let new_script_pubkey = ScriptBuf::from_bytes(vec![0x52]);
+
+    // the verifier signature only commits to output 1, output 0 can be redirected
+    let tx = override_payout(
+        "take_1",
+        &presigned_tx,
+        &payout_script_pubkey,
+        new_script_pubkey.clone(),
+    )
+    .unwrap();
+    assert_eq!(tx.output[0].script_pubkey, new_script_pubkey);
+    assert_eq!(tx.output[1], presigned_tx.tx.output[1]);
+    // only the operator input has to be signed again
+    assert!(tx.input[0].witness.is_empty());
+    assert_eq!(tx.input[1].witness, presigned_tx.tx.input[1].witness);
+
+    assert_eq!(
+        override_payout(
+            "take_1",
+            &presigned_tx,
+            &generate_burn_script(),
+            new_script_pubkey.clone(),
+        ),
+        Err(PayoutOverrideError::Committed(PayoutCommitted {
+            tx_name: "take_1",
+            input_index: 1,
+        }))
+    );
+    assert_eq!(
+        override_payout(
+            "take_1",
+            &presigned_tx,
+            &new_script_pubkey,
+            new_script_pubkey.clone(),
+        ),
+        Err(PayoutOverrideError::NoPayout("take_1"))
+    );
+}
+
+#[test]
+fn test_commits_to_output() {
+    assert!(commits_to_output(TapSighashType::All, 1, 0));
+    assert!(commits_to_output(TapSighashType::Default, 1, 0));
+    assert!(commits_to_output(TapSighashType::Single, 0, 0));
+
+    // a payout not signed by any presigned input can be redirected
+    assert!(!commits_to_output(TapSighashType::Single, 1, 0));
+    assert!(!commits_to_output(
+        TapSighashType::SinglePlusAnyoneCanPay,
+        1,
+        0
+    ));
+    assert!(!commits_to_output(TapSighashType::None, 0, 0));
+    assert!(!commits_to_output(
+        TapSighashType::NonePlusAnyoneCanPay,
+        0,
+        0
+    ));
+}
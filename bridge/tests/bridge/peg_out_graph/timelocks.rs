@@ -0,0 +1,59 @@
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_timelocks_lists_each_timelock_once() {
+    let (config, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let expected = [
+        (
+            "kick_off_2",
+            "connector_1",
+            Some(0),
+            config.connector_1.num_blocks_timelock_leaf_0,
+        ),
+        (
+            "kick_off_timeout",
+            "connector_1",
+            Some(1),
+            config.connector_1.num_blocks_timelock_leaf_1,
+        ),
+        (
+            "start_time_timeout",
+            "connector_1",
+            Some(2),
+            config.connector_1.num_blocks_timelock_leaf_2,
+        ),
+        (
+            "take_1",
+            "connector_3",
+            None,
+            config.connector_3.num_blocks_timelock,
+        ),
+        (
+            "take_2",
+            "connector_4",
+            None,
+            config.connector_4.num_blocks_timelock,
+        ),
+        (
+            "assert_initial",
+            "connector_b",
+            Some(1),
+            config.connector_b.num_blocks_timelock_1,
+        ),
+    ];
+
+    let timelocks = peg_out_graph.timelocks();
+    assert_eq!(timelocks.len(), expected.len());
+    for (tx_name, connector_name, leaf_index, num_blocks) in expected {
+        let matching: Vec<_> = timelocks
+            .iter()
+            .filter(|timelock| {
+                timelock.connector_name == connector_name && timelock.leaf_index == leaf_index
+            })
+            .collect();
+        assert_eq!(matching.len(), 1, "{connector_name} leaf {leaf_index:?}");
+        assert_eq!(matching[0].tx_name, tx_name);
+        assert_eq!(matching[0].num_blocks, num_blocks);
+    }
+}
@@ -0,0 +1,61 @@
+use bitcoin::{consensus::encode::deserialize_hex, OutPoint, Transaction};
+
+use bridge::graphs::topology::{KICK_OFF_2_VOUT_0, TAKE_2_VOUT_3};
+
+use super::utils::setup_and_create_stub_graphs;
+
+const TX_NAMES: [&str; 13] = [
+    "assert_initial",
+    "assert_final",
+    "challenge",
+    "disprove_chain",
+    "disprove",
+    "peg_out_confirm",
+    "kick_off_1",
+    "kick_off_2",
+    "kick_off_timeout",
+    "start_time_timeout",
+    "start_time",
+    "take_1",
+    "take_2",
+];
+
+fn graph_tx(json: &serde_json::Value, tx_name: &str) -> Transaction {
+    deserialize_hex(
+        json[format!("{tx_name}_transaction")]["tx"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_topology_shared_by_constructors() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let validation_graph = peg_out_graph.new_for_validation().unwrap();
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let validation_json = serde_json::to_value(&validation_graph).unwrap();
+    for tx_name in TX_NAMES {
+        let tx = graph_tx(&json, tx_name);
+        let validation_tx = graph_tx(&validation_json, tx_name);
+
+        let outpoints = |tx: &Transaction| -> Vec<OutPoint> {
+            tx.input.iter().map(|input| input.previous_output).collect()
+        };
+        assert_eq!(outpoints(&tx), outpoints(&validation_tx), "{tx_name}");
+        assert_eq!(tx.output.len(), validation_tx.output.len(), "{tx_name}");
+        assert_eq!(tx.compute_txid(), validation_tx.compute_txid(), "{tx_name}");
+    }
+
+    let kick_off_1_txid = graph_tx(&json, "kick_off_1").compute_txid();
+    assert_eq!(
+        graph_tx(&json, "kick_off_2").input[0].previous_output,
+        OutPoint::new(kick_off_1_txid, KICK_OFF_2_VOUT_0 as u32)
+    );
+    let assert_final_txid = graph_tx(&json, "assert_final").compute_txid();
+    assert_eq!(
+        graph_tx(&json, "take_2").input[3].previous_output,
+        OutPoint::new(assert_final_txid, TAKE_2_VOUT_3 as u32)
+    );
+}
@@ -0,0 +1,50 @@
+use bridge::graphs::{
+    dag::{TxEdge, TxRole},
+    topology::{ASSERT_COMMIT_VOUT_BASE, ASSERT_INITIAL_VOUT_0, TAKE_1_VOUT_0, TAKE_2_VOUT_3},
+};
+
+use super::utils::setup_and_create_stub_graphs;
+
+#[tokio::test]
+async fn test_transaction_dag_edges() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let dag = peg_out_graph.transaction_dag();
+
+    for (role, txid) in peg_out_graph.txids() {
+        assert_eq!(dag.nodes[role], *txid, "{role:?}");
+    }
+
+    let edge = |from, from_vout: usize, to, to_input| TxEdge {
+        from,
+        from_vout: from_vout as u32,
+        to,
+        to_input,
+    };
+    assert!(dag.edges.contains(&edge(
+        TxRole::KickOff2,
+        ASSERT_INITIAL_VOUT_0,
+        TxRole::AssertInitial,
+        0
+    )));
+    assert!(dag
+        .edges
+        .contains(&edge(TxRole::PegInConfirm, TAKE_1_VOUT_0, TxRole::Take1, 0)));
+    assert!(dag
+        .edges
+        .contains(&edge(TxRole::AssertFinal, TAKE_2_VOUT_3, TxRole::Take2, 3)));
+    assert!(dag.edges.contains(&edge(
+        TxRole::AssertInitial,
+        ASSERT_COMMIT_VOUT_BASE,
+        TxRole::AssertCommit1,
+        0
+    )));
+    assert!(dag
+        .edges_into(TxRole::AssertFinal)
+        .any(|edge| edge.from == TxRole::AssertCommit2));
+
+    // every tx but the first of the graph and the peg-in confirm tx spends a graph tx
+    for role in dag.nodes.keys() {
+        let is_root = matches!(role, TxRole::PegInConfirm | TxRole::PegOutConfirm);
+        assert_eq!(dag.edges_into(*role).next().is_none(), is_root, "{role:?}");
+    }
+}
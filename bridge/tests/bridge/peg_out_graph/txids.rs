@@ -0,0 +1,94 @@
+use std::str::FromStr;
+
+use bitcoin::{
+    consensus::encode::{deserialize_hex, serialize_hex},
+    Amount, OutPoint, Transaction, TxIn, TxOut, Txid,
+};
+use serde_json::Value;
+
+use bridge::{
+    graphs::{base::DUST_AMOUNT, dag::TxRole, peg_out::PegOutGraph},
+    scripts::generate_burn_script,
+    serialization::{deserialize, serialize},
+};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+#[tokio::test]
+async fn test_cached_txids_match_computed() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    assert_eq!(peg_out_graph.txids().len(), 13);
+    let dag = peg_out_graph.transaction_dag();
+    for (role, txid) in peg_out_graph.txids() {
+        assert_eq!(dag.nodes[role], *txid, "{role:?}");
+    }
+
+    // the cache is rebuilt for a graph loaded from storage
+    let json = serialize(&peg_out_graph);
+    let loaded_peg_out_graph = deserialize::<PegOutGraph>(&json);
+    assert_eq!(loaded_peg_out_graph.txids(), peg_out_graph.txids());
+    assert!(loaded_peg_out_graph == peg_out_graph);
+}
+
+#[tokio::test]
+async fn test_rebuilt_tx_gets_fresh_txid() {
+    let (_, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    let challenge_txid = peg_out_graph.txids()[&TxRole::Challenge];
+
+    // the challenge tx as completed by a challenger, with its crowdfunding added
+    let mut json: Value = serde_json::from_str(&serialize(&peg_out_graph)).unwrap();
+    let mut challenge_tx: Transaction =
+        deserialize_hex(json["challenge_transaction"]["tx"].as_str().unwrap()).unwrap();
+    challenge_tx.input.push(TxIn::default());
+    challenge_tx.output.push(TxOut {
+        value: Amount::from_sat(DUST_AMOUNT),
+        script_pubkey: generate_burn_script(),
+    });
+    json["challenge_transaction"]["tx"] = Value::String(serialize_hex(&challenge_tx));
+    let challenged_peg_out_graph = deserialize::<PegOutGraph>(&json.to_string());
+
+    peg_out_graph.merge(&challenged_peg_out_graph);
+    let rebuilt_challenge_txid = peg_out_graph.txids()[&TxRole::Challenge];
+    assert_ne!(rebuilt_challenge_txid, challenge_txid);
+    assert_eq!(rebuilt_challenge_txid, challenge_tx.compute_txid());
+}
+
+#[tokio::test]
+async fn test_cached_peg_out_txid() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    assert_eq!(peg_out_graph.peg_out_txid(), None);
+
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+    let peg_out_txid = peg_out_graph.peg_out_txid().unwrap();
+    assert_eq!(
+        peg_out_txid,
+        peg_out_graph.peg_out_transaction().unwrap().tx().compute_txid()
+    );
+
+    // a new peg-out tx replaces the cached txid
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: peg_out_txid,
+            vout: 1,
+        },
+    );
+    let new_peg_out_txid = peg_out_graph.peg_out_txid().unwrap();
+    assert_ne!(new_peg_out_txid, peg_out_txid);
+    assert_eq!(
+        new_peg_out_txid,
+        peg_out_graph.peg_out_transaction().unwrap().tx().compute_txid()
+    );
+}
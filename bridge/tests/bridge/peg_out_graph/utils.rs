@@ -0,0 +1,114 @@
+use std::{collections::HashMap, str::FromStr};
+
+use bitcoin::{Amount, OutPoint, Txid};
+
+use bitvm::signatures::signing_winternitz::WinternitzSecret;
+
+use bridge::{
+    client::chain::chain::PegOutEvent,
+    graphs::{
+        base::{FEE_AMOUNT, INITIAL_AMOUNT},
+        peg_in::PegInGraph,
+        peg_out::{CommitmentMessageId, PegOutGraph, PegOutGraphConfig},
+    },
+    scripts::generate_p2pkh_address,
+    transactions::{base::Input, peg_out::PegOutTransaction},
+};
+
+use super::super::setup::{setup_test, SetupConfig};
+
+pub async fn setup_and_create_stub_graphs() -> (
+    SetupConfig,
+    PegInGraph,
+    PegOutGraph,
+    HashMap<CommitmentMessageId, WinternitzSecret>,
+) {
+    setup_and_create_stub_graphs_with_config(PegOutGraphConfig::default()).await
+}
+
+pub async fn setup_and_create_stub_graphs_with_config(
+    graph_config: PegOutGraphConfig,
+) -> (
+    SetupConfig,
+    PegInGraph,
+    PegOutGraph,
+    HashMap<CommitmentMessageId, WinternitzSecret>,
+) {
+    let config = setup_test().await;
+
+    let amount = Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1);
+    let peg_in_outpoint = OutPoint {
+        txid: Txid::from_str("0e6719ac074b0e3cac76d057643506faa1c266b322aa9cf4c6f635fe63b14327")
+            .unwrap(),
+        vout: 0,
+    };
+    let peg_out_confirm_outpoint = OutPoint {
+        txid: Txid::from_str("4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900")
+            .unwrap(),
+        vout: 0,
+    };
+
+    let peg_in_graph = PegInGraph::new(
+        &config.depositor_context,
+        Input {
+            outpoint: peg_in_outpoint,
+            amount,
+        },
+        &config.depositor_evm_address,
+    );
+
+    let (peg_out_graph, commitment_secrets) = PegOutGraph::new(
+        &config.operator_context,
+        &peg_in_graph,
+        vec![Input {
+            outpoint: peg_out_confirm_outpoint,
+            amount,
+        }],
+        graph_config,
+    )
+    .unwrap();
+
+    (config, peg_in_graph, peg_out_graph, commitment_secrets)
+}
+
+pub fn generate_stub_peg_out_event(config: &SetupConfig, source_outpoint: OutPoint) -> PegOutEvent {
+    PegOutEvent {
+        source_outpoint,
+        amount: Amount::from_sat(INITIAL_AMOUNT),
+        timestamp: 1722328130,
+        withdrawer_chain_address: config.withdrawer_evm_address.clone(),
+        withdrawer_destination_address: generate_p2pkh_address(
+            config.withdrawer_context.network,
+            &config.withdrawer_context.withdrawer_public_key,
+        )
+        .to_string(),
+        withdrawer_public_key_hash: config
+            .withdrawer_context
+            .withdrawer_public_key
+            .pubkey_hash(),
+        operator_public_key: config.operator_context.operator_public_key,
+        tx_hash: [0u8; 32].into(),
+    }
+}
+
+// Attaches a peg-out event and a peg-out tx spending the given outpoint, as
+// the operator would after observing the withdrawal on L2.
+pub fn set_stub_peg_out(
+    config: &SetupConfig,
+    peg_out_graph: &mut PegOutGraph,
+    peg_out_outpoint: OutPoint,
+) {
+    let event = generate_stub_peg_out_event(config, peg_out_outpoint);
+    peg_out_graph.set_peg_out_transaction(
+        PegOutTransaction::new(
+            &config.operator_context,
+            &event,
+            Input {
+                outpoint: peg_out_outpoint,
+                amount: Amount::from_sat(INITIAL_AMOUNT),
+            },
+        )
+        .unwrap(),
+    );
+    peg_out_graph.peg_out_chain_event = Some(event);
+}
@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use bitcoin::{
+    consensus::encode::{deserialize_hex, serialize_hex},
+    Amount, OutPoint, Transaction, Txid,
+};
+
+use bridge::{
+    graphs::{
+        base::{BaseGraph, FEE_AMOUNT, INITIAL_AMOUNT},
+        peg_in::PegInGraph,
+        peg_out::{PegOutGraph, ValidationError},
+    },
+    transactions::{base::Input, pre_signed::PreSignedTransaction},
+};
+
+use super::{super::setup::SetupConfig, utils::setup_and_create_stub_graphs};
+
+fn other_peg_in_graph(config: &SetupConfig) -> PegInGraph {
+    PegInGraph::new(
+        &config.depositor_context,
+        Input {
+            outpoint: OutPoint {
+                txid: Txid::from_str(
+                    "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+                )
+                .unwrap(),
+                vout: 1,
+            },
+            amount: Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1),
+        },
+        &config.depositor_evm_address,
+    )
+}
+
+#[tokio::test]
+async fn test_validate_against_peg_in() {
+    let (config, peg_in_graph, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    assert_eq!(peg_out_graph.validate_against_peg_in(&peg_in_graph), Ok(()));
+
+    let other_peg_in_graph = other_peg_in_graph(&config);
+    assert_eq!(
+        peg_out_graph.validate_against_peg_in(&other_peg_in_graph),
+        Err(ValidationError::PegInGraphIdMismatch {
+            peg_in_graph_id: peg_in_graph.id().clone(),
+            expected: other_peg_in_graph.id().clone(),
+        })
+    );
+
+    // a peg-in graph passed off under the id of the one the graph was built for
+    let mut json = serde_json::to_value(&other_peg_in_graph).unwrap();
+    json["id"] = peg_in_graph.id().clone().into();
+    let forged_peg_in_graph: PegInGraph = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        peg_out_graph.validate_against_peg_in(&forged_peg_in_graph),
+        Err(ValidationError::PegInConfirmTxidMismatch {
+            peg_in_confirm_txid: peg_in_graph.peg_in_confirm_transaction.tx().compute_txid(),
+            expected: other_peg_in_graph
+                .peg_in_confirm_transaction
+                .tx()
+                .compute_txid(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_validate_against_peg_in_take_input() {
+    let (_, peg_in_graph, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut json = serde_json::to_value(&peg_out_graph).unwrap();
+    let mut take_2_tx: Transaction =
+        deserialize_hex(json["take_2_transaction"]["tx"].as_str().unwrap()).unwrap();
+    take_2_tx.input[0].previous_output.vout += 1;
+    let outpoint = take_2_tx.input[0].previous_output;
+    json["take_2_transaction"]["tx"] = serialize_hex(&take_2_tx).into();
+    let edited_graph: PegOutGraph = serde_json::from_value(json).unwrap();
+
+    assert_eq!(
+        edited_graph.validate_against_peg_in(&peg_in_graph),
+        Err(ValidationError::TakeInputMismatch {
+            tx_name: "take_2",
+            outpoint,
+        })
+    );
+}
@@ -0,0 +1,114 @@
+use bitcoin::{consensus::encode::deserialize_hex, Transaction, TxOut};
+
+use bitvm::chunker::disprove_execution::RawProof;
+
+use bridge::{
+    graphs::{
+        base::GRAPH_VERSION,
+        peg_out::{PegOutGraph, PegOutGraphConfig, RecoveryError},
+    },
+    serialization::{serialize_binary, try_deserialize_binary},
+    superblock::SuperblockPolicy,
+};
+
+use super::utils::{setup_and_create_stub_graphs, setup_and_create_stub_graphs_with_config};
+
+#[tokio::test]
+async fn test_verifying_key_roundtrip() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    assert_eq!(peg_out_graph.verifying_key(), &RawProof::default().vk);
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let from_json: PegOutGraph = serde_json::from_value(json.clone()).unwrap();
+    let from_binary: PegOutGraph = try_deserialize_binary(
+        GRAPH_VERSION,
+        &serialize_binary(GRAPH_VERSION, &peg_out_graph),
+    )
+    .unwrap();
+    assert_eq!(from_json.verifying_key(), peg_out_graph.verifying_key());
+    assert_eq!(from_binary.verifying_key(), peg_out_graph.verifying_key());
+
+    // graphs stored before the key was kept were built from the default key
+    let mut stored_json = json;
+    stored_json["config"]
+        .as_object_mut()
+        .unwrap()
+        .remove("verifying_key");
+    let stored_graph: PegOutGraph = serde_json::from_value(stored_json).unwrap();
+    assert_eq!(stored_graph.verifying_key(), &RawProof::default().vk);
+}
+
+#[tokio::test]
+async fn test_validate_rejects_other_verifying_key() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let mut verifying_key = RawProof::default().vk;
+    verifying_key.gamma_abc_g1[0] = -verifying_key.gamma_abc_g1[0];
+    let mut json = serde_json::to_value(&peg_out_graph).unwrap();
+    json["config"] =
+        serde_json::to_value(PegOutGraphConfig::default().with_verifying_key(verifying_key))
+            .unwrap();
+
+    // the disprove leaves of connector c no longer match the advertised key
+    let received_graph: PegOutGraph = serde_json::from_value(json).unwrap();
+    assert!(!received_graph.validate());
+}
+
+#[tokio::test]
+async fn test_recover_graph_with_verifying_key() {
+    let mut verifying_key = RawProof::default().vk;
+    verifying_key.gamma_abc_g1[0] = -verifying_key.gamma_abc_g1[0];
+    let graph_config = PegOutGraphConfig::default().with_verifying_key(verifying_key);
+    let (config, peg_in_graph, peg_out_graph, commitment_secrets) =
+        setup_and_create_stub_graphs_with_config(graph_config.clone()).await;
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let graph_tx = |tx_name: &str| -> Transaction {
+        deserialize_hex(
+            json[format!("{tx_name}_transaction")]["tx"]
+                .as_str()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+    let prev_outs: Vec<TxOut> = deserialize_hex(
+        json["peg_out_confirm_transaction"]["prev_outs"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+    let onchain_txs = ["kick_off_1", "kick_off_2", "assert_initial", "assert_final"].map(graph_tx);
+    let recover = |graph_config: &PegOutGraphConfig| {
+        PegOutGraph::recover_from_txs(
+            &peg_in_graph,
+            &config.operator_context,
+            graph_config,
+            commitment_secrets.clone(),
+            SuperblockPolicy::default(),
+            &graph_tx("peg_out_confirm"),
+            &prev_outs,
+            &onchain_txs,
+        )
+    };
+
+    // connector c of the recovered graph is built from the key of the published one
+    let recovered_graph = recover(&graph_config).unwrap();
+    assert_eq!(
+        recovered_graph.verifying_key(),
+        peg_out_graph.verifying_key()
+    );
+    assert_eq!(
+        recovered_graph.transaction_dag().nodes,
+        peg_out_graph.transaction_dag().nodes
+    );
+    assert!(recovered_graph.validate());
+
+    // the assert final tx on chain pays to connector c of the other key
+    assert!(matches!(
+        recover(&PegOutGraphConfig::default()),
+        Err(RecoveryError::TxidMismatch {
+            tx_name: "assert_final",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,104 @@
+use std::{
+    future::ready,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use bitcoin::{consensus::encode::deserialize_hex, OutPoint, Transaction, Txid};
+
+use bridge::graphs::{
+    base::{wait_for_status, BaseGraph, Timeout},
+    peg_out::{PegOutGraph, PegOutOperatorStatus},
+};
+
+use crate::bridge::mock::in_memory_chain::InMemoryChain;
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+fn graph_tx(json: &serde_json::Value, tx_name: &str) -> Transaction {
+    deserialize_hex(
+        json[format!("{tx_name}_transaction")]["tx"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+async fn setup_peg_out_graph() -> (PegOutGraph, InMemoryChain) {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let secret_nonces = peg_out_graph.push_verifier_nonces(&config.verifier_0_context);
+    peg_out_graph.push_verifier_nonces(&config.verifier_1_context);
+    peg_out_graph.verifier_sign(&config.verifier_0_context, &secret_nonces);
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let mut chain = InMemoryChain::new();
+    chain.submit(&graph_tx(&json, "peg_out")).unwrap();
+    chain.mine(1);
+
+    (peg_out_graph, chain)
+}
+
+#[tokio::test]
+async fn test_wait_for_operator_status() {
+    let (peg_out_graph, mut chain) = setup_peg_out_graph().await;
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+
+    // the operator broadcasts the next tx of the graph with every block
+    let mut pending_txs = ["peg_out_confirm", "kick_off_1", "start_time"]
+        .map(|tx_name| graph_tx(&json, tx_name))
+        .into_iter();
+    let status = wait_for_status(
+        || {
+            if let Some(tx) = pending_txs.next() {
+                chain.submit(&tx).unwrap();
+            }
+            chain.mine(1);
+            ready(chain.operator_status(&peg_out_graph))
+        },
+        |status| matches!(status, PegOutOperatorStatus::PegOutKickOff2Available),
+        Duration::from_millis(1),
+        Instant::now() + Duration::from_secs(10),
+    )
+    .await;
+
+    assert!(matches!(
+        status,
+        Ok(PegOutOperatorStatus::PegOutKickOff2Available)
+    ));
+    assert!(pending_txs.next().is_none());
+}
+
+#[tokio::test]
+async fn test_wait_for_operator_status_timeout() {
+    let (peg_out_graph, mut chain) = setup_peg_out_graph().await;
+
+    // nothing is broadcast, the graph stays where it is
+    let status = wait_for_status(
+        || {
+            chain.mine(1);
+            ready(chain.operator_status(&peg_out_graph))
+        },
+        |status| matches!(status, PegOutOperatorStatus::PegOutComplete),
+        Duration::from_millis(1),
+        Instant::now() + Duration::from_millis(20),
+    )
+    .await;
+
+    assert!(matches!(status, Err(Timeout)));
+    assert!(matches!(
+        chain.operator_status(&peg_out_graph),
+        PegOutOperatorStatus::PegOutPegOutConfirmAvailable
+    ));
+}
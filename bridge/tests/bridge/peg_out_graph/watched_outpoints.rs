@@ -0,0 +1,38 @@
+use std::str::FromStr;
+
+use bitcoin::{OutPoint, Txid};
+
+use bridge::transactions::pre_signed::PreSignedTransaction;
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+#[tokio::test]
+async fn test_watched_outpoints() {
+    let (config, peg_in_graph, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    // 3 inputs on assert final, 4 on each take tx, 2 on disprove and start time
+    // timeout and 1 on each of the 8 other txs
+    let watched_outpoints = peg_out_graph.watched_outpoints();
+    assert_eq!(watched_outpoints.len(), 23);
+
+    let peg_in_confirm_outpoint = OutPoint {
+        txid: peg_in_graph.peg_in_confirm_transaction.tx().compute_txid(),
+        vout: 0,
+    };
+    let peg_in_confirm_spenders: Vec<&str> = watched_outpoints
+        .iter()
+        .filter(|(outpoint, _)| *outpoint == peg_in_confirm_outpoint)
+        .map(|(_, tx_name)| *tx_name)
+        .collect();
+    assert_eq!(peg_in_confirm_spenders, vec!["take_1", "take_2"]);
+
+    let peg_out_outpoint = OutPoint {
+        txid: Txid::from_str("8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182")
+            .unwrap(),
+        vout: 0,
+    };
+    set_stub_peg_out(&config, &mut peg_out_graph, peg_out_outpoint);
+    let watched_outpoints = peg_out_graph.watched_outpoints();
+    assert_eq!(watched_outpoints.len(), 24);
+    assert!(watched_outpoints.contains(&(peg_out_outpoint, "peg_out")));
+}
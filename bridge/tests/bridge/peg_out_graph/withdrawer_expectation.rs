@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+use bitcoin::{Amount, OutPoint, Txid};
+
+use bridge::{
+    client::chain::chain::PegOutDestinationError,
+    graphs::{base::FEE_AMOUNT, peg_out::WithdrawerExpectation},
+    scripts::generate_p2pkh_address,
+    transactions::pre_signed::PreSignedTransaction,
+};
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+#[tokio::test]
+async fn test_withdrawer_expectation() {
+    let (config, _, mut peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    assert!(peg_out_graph.withdrawer_expectation().is_none());
+
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+    let event = peg_out_graph.peg_out_chain_event.clone().unwrap();
+    let expectation = peg_out_graph.withdrawer_expectation().unwrap().unwrap();
+    assert_eq!(
+        expectation,
+        WithdrawerExpectation {
+            destination_address: generate_p2pkh_address(
+                config.withdrawer_context.network,
+                &config.withdrawer_context.withdrawer_public_key,
+            ),
+            payout_script_pubkey: event
+                .payout_script_pubkey(config.withdrawer_context.network)
+                .unwrap(),
+            amount: event.amount - Amount::from_sat(FEE_AMOUNT),
+        }
+    );
+
+    // the peg-out tx the operator built pays exactly what was expected
+    let peg_out_output = &peg_out_graph
+        .peg_out_transaction()
+        .unwrap()
+        .tx()
+        .output[0];
+    assert_eq!(
+        peg_out_output.script_pubkey,
+        expectation.payout_script_pubkey
+    );
+    assert_eq!(peg_out_output.value, expectation.amount);
+
+    let mut malformed_event = event;
+    malformed_event.withdrawer_destination_address = "not an address".to_string();
+    peg_out_graph.peg_out_chain_event = Some(malformed_event);
+    assert_eq!(
+        peg_out_graph.withdrawer_expectation(),
+        Some(Err(PegOutDestinationError::InvalidAddress(
+            "not an address".to_string()
+        )))
+    );
+}
@@ -0,0 +1,203 @@
+use std::str::FromStr;
+
+use bitcoin::{consensus::encode::deserialize_hex, Amount, Network, OutPoint, Transaction, Txid};
+
+use bitvm::signatures::signing_winternitz::{WinternitzSecret, WinternitzSigningInputs};
+
+use bridge::{
+    connectors::base::WrongCommitmentSecret,
+    constants::{SOURCE_NETWORK_TXID_LENGTH, START_TIME_MESSAGE_LENGTH},
+    graphs::{
+        base::DUST_AMOUNT,
+        peg_out::{
+            CommitmentMessageId, GuardedActionError, KickOff1Error, KickOff2Error, OperatorAction,
+            StartTimeError,
+        },
+    },
+    superblock::{
+        get_start_time_block_number, get_superblock_message, SuperblockCriteria, SuperblockParams,
+    },
+    transactions::{
+        base::Input, kick_off_2::KickOff2Transaction, start_time::StartTimeTransaction,
+    },
+};
+
+use crate::bridge::helper::get_superblock_header;
+
+use super::utils::{set_stub_peg_out, setup_and_create_stub_graphs};
+
+fn input() -> Input {
+    Input {
+        outpoint: OutPoint {
+            txid: Txid::from_str(
+                "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+        amount: Amount::from_sat(DUST_AMOUNT),
+    }
+}
+
+#[tokio::test]
+async fn test_kick_off_1_wrong_commitment_secret() {
+    let (config, _, mut peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+    set_stub_peg_out(
+        &config,
+        &mut peg_out_graph,
+        OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+    );
+
+    // secret of another graph
+    let other_secret = WinternitzSecret::new(SOURCE_NETWORK_TXID_LENGTH);
+    assert_eq!(
+        peg_out_graph.sign_kick_off_1(
+            &config.operator_context,
+            &other_secret,
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
+        ),
+        Err(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::PegOutTxIdSourceNetwork
+        })
+    );
+    let json = serde_json::to_value(&peg_out_graph).unwrap();
+    let kick_off_1_tx: Transaction =
+        deserialize_hex(json["kick_off_1_transaction"]["tx"].as_str().unwrap()).unwrap();
+    assert!(kick_off_1_tx.input[0].witness.is_empty());
+
+    // secrets swapped between the two commitments
+    assert_eq!(
+        peg_out_graph.sign_kick_off_1(
+            &config.operator_context,
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
+            &commitment_secrets[&CommitmentMessageId::PegOutTxIdSourceNetwork],
+        ),
+        Err(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::PegOutTxIdDestinationNetwork
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_start_time_and_kick_off_2_wrong_commitment_secret() {
+    let (config, _, _, _) = setup_and_create_stub_graphs().await;
+
+    let mut start_time_tx =
+        StartTimeTransaction::new(&config.operator_context, &config.connector_2, input());
+    assert_eq!(
+        start_time_tx.sign(
+            &config.operator_context,
+            &config.connector_2,
+            get_start_time_block_number(&SuperblockParams::for_network(Network::Regtest)),
+            &WinternitzSecret::new(START_TIME_MESSAGE_LENGTH),
+        ),
+        Err(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::StartTime
+        })
+    );
+
+    let mut kick_off_2_tx =
+        KickOff2Transaction::new(&config.operator_context, &config.connector_1, input());
+    let superblock_message = get_superblock_message(&get_superblock_header());
+    let other_secret = WinternitzSecret::new(superblock_message.len());
+    assert_eq!(
+        kick_off_2_tx.sign(
+            &config.operator_context,
+            &config.connector_1,
+            &WinternitzSigningInputs {
+                message: &superblock_message,
+                signing_key: &other_secret,
+            },
+            &WinternitzSigningInputs {
+                message: &superblock_message,
+                signing_key: &config.commitment_secrets[&CommitmentMessageId::SuperblockHash],
+            },
+        ),
+        Err(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::Superblock
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_actions_reject_wrong_commitment_secret() {
+    let (config, _, mut peg_out_graph, commitment_secrets) = setup_and_create_stub_graphs().await;
+    let client = &config.client_0.esplora;
+
+    // fails before any request is sent to the esplora client
+    let other_secret = WinternitzSecret::new(SOURCE_NETWORK_TXID_LENGTH);
+    assert_eq!(
+        peg_out_graph
+            .kick_off_1(
+                client,
+                &config.operator_context,
+                &other_secret,
+                &commitment_secrets[&CommitmentMessageId::PegOutTxIdDestinationNetwork],
+            )
+            .await,
+        Err(KickOff1Error::WrongCommitmentSecret(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::PegOutTxIdSourceNetwork
+        }))
+    );
+
+    assert_eq!(
+        peg_out_graph
+            .start_time(
+                client,
+                &config.operator_context,
+                &WinternitzSecret::new(START_TIME_MESSAGE_LENGTH),
+            )
+            .await,
+        Err(StartTimeError::WrongCommitmentSecret(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::StartTime
+        }))
+    );
+
+    let superblock_params = peg_out_graph.superblock_params();
+    let superblock_criteria = SuperblockCriteria::new(
+        &superblock_params,
+        get_start_time_block_number(&superblock_params),
+    );
+    let superblock_message = get_superblock_message(&get_superblock_header());
+    let other_secret = WinternitzSecret::new(superblock_message.len());
+    assert_eq!(
+        peg_out_graph
+            .kick_off_2(
+                client,
+                None,
+                &config.operator_context,
+                &commitment_secrets[&CommitmentMessageId::Superblock],
+                &other_secret,
+                &superblock_criteria,
+            )
+            .await,
+        Err(KickOff2Error::WrongCommitmentSecret(WrongCommitmentSecret {
+            message_id: CommitmentMessageId::SuperblockHash
+        }))
+    );
+
+    let result = peg_out_graph
+        .guarded_action(
+            OperatorAction::StartTime {
+                start_time_commitment_secret: &commitment_secrets
+                    [&CommitmentMessageId::PegOutTxIdSourceNetwork],
+            },
+            client,
+            &config.operator_context,
+        )
+        .await;
+    assert_eq!(
+        result,
+        Err(GuardedActionError::WrongCommitmentSecret(
+            WrongCommitmentSecret {
+                message_id: CommitmentMessageId::StartTime
+            }
+        ))
+    );
+}
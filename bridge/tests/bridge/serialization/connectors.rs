@@ -0,0 +1,113 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use bridge::{
+    connectors::base::{P2wshConnector, TaprootConnector},
+    graphs::base::GRAPH_VERSION,
+    serialization::{serialize, serialize_binary, try_deserialize, try_deserialize_binary},
+};
+
+use super::super::setup::setup_test;
+
+// Both the JSON and the binary form, as graphs are stored and sent in either.
+fn roundtrips<T>(connector: &T) -> [T; 2]
+where
+    T: Serialize + DeserializeOwned + PartialEq,
+{
+    let from_json: T = try_deserialize(&serialize(connector)).unwrap();
+    let from_binary: T =
+        try_deserialize_binary(GRAPH_VERSION, &serialize_binary(GRAPH_VERSION, connector)).unwrap();
+    assert!(from_json == *connector);
+    assert!(from_binary == *connector);
+
+    [from_json, from_binary]
+}
+
+fn assert_taproot_roundtrip<T>(connector: &T)
+where
+    T: TaprootConnector + Serialize + DeserializeOwned + PartialEq,
+{
+    for deserialized in roundtrips(connector) {
+        assert_eq!(
+            deserialized.generate_taproot_address(),
+            connector.generate_taproot_address()
+        );
+    }
+}
+
+fn assert_p2wsh_roundtrip<T>(connector: &T)
+where
+    T: P2wshConnector + Serialize + DeserializeOwned + PartialEq,
+{
+    for deserialized in roundtrips(connector) {
+        assert_eq!(
+            deserialized.generate_address(),
+            connector.generate_address()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_connector_serialization_roundtrip() {
+    let config = setup_test().await;
+
+    assert_taproot_roundtrip(&config.connector_0);
+    assert_taproot_roundtrip(&config.connector_5);
+    assert_taproot_roundtrip(&config.connector_a);
+    assert_taproot_roundtrip(&config.connector_b);
+    assert_taproot_roundtrip(&config.connector_d);
+    assert_taproot_roundtrip(&config.connector_z);
+    assert_p2wsh_roundtrip(&config.connector_3);
+    assert_p2wsh_roundtrip(&config.connector_4);
+    assert_p2wsh_roundtrip(&config.assert_commit_connectors_f.connector_f_1);
+    assert_p2wsh_roundtrip(&config.assert_commit_connectors_f.connector_f_2);
+
+    assert_taproot_roundtrip(&config.connector_1);
+    assert_taproot_roundtrip(&config.connector_2);
+    assert_taproot_roundtrip(&config.connector_6);
+    for deserialized in roundtrips(&config.connector_1) {
+        assert!(deserialized.commitment_public_keys == config.connector_1.commitment_public_keys);
+    }
+    for deserialized in roundtrips(&config.connector_2) {
+        assert!(deserialized.commitment_public_keys == config.connector_2.commitment_public_keys);
+    }
+    for deserialized in roundtrips(&config.connector_6) {
+        assert!(deserialized.commitment_public_keys == config.connector_6.commitment_public_keys);
+    }
+}
+
+#[tokio::test]
+async fn test_groth16_connector_serialization_roundtrip() {
+    let config = setup_test().await;
+
+    // keyed by Groth16 intermediate values, which JSON cannot use as map keys
+    assert_taproot_roundtrip(&config.connector_c);
+    for deserialized in roundtrips(&config.connector_c) {
+        assert!(
+            deserialized.commitment_public_keys() == config.connector_c.commitment_public_keys()
+        );
+    }
+
+    for connector_e in config
+        .assert_commit_connectors_e_1
+        .connectors_e
+        .iter()
+        .chain(&config.assert_commit_connectors_e_2.connectors_e)
+    {
+        assert_taproot_roundtrip(connector_e);
+        for deserialized in roundtrips(connector_e) {
+            assert!(deserialized.commitment_public_keys == connector_e.commitment_public_keys);
+        }
+    }
+    for deserialized in roundtrips(&config.assert_commit_connectors_e_1) {
+        assert!(
+            deserialized.commitment_public_keys()
+                == config.assert_commit_connectors_e_1.commitment_public_keys()
+        );
+    }
+    for deserialized in roundtrips(&config.assert_commit_connectors_e_2) {
+        assert!(
+            deserialized.commitment_public_keys()
+                == config.assert_commit_connectors_e_2.commitment_public_keys()
+        );
+    }
+}
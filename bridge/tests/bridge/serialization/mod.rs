@@ -1,3 +1,4 @@
 pub mod assert_transaction;
+pub mod connectors;
 pub mod peg_in_graph;
 pub mod peg_out_graph;
@@ -1,19 +1,24 @@
+use std::time::Instant;
+
 use bitcoin::{Address, Amount};
 
 use bridge::{
     graphs::{
-        base::{FEE_AMOUNT, INITIAL_AMOUNT},
+        base::{FEE_AMOUNT, GRAPH_VERSION, INITIAL_AMOUNT},
         peg_in::PegInGraph,
-        peg_out::PegOutGraph,
+        peg_out::{GraphVersionError, PegOutGraph, PegOutGraphConfig},
     },
     scripts::generate_pay_to_pubkey_script_address,
-    serialization::{deserialize, serialize},
+    serialization::{deserialize, serialize, serialize_binary},
     transactions::base::Input,
 };
 
 use crate::bridge::faucet::{Faucet, FaucetType};
 
-use super::super::{helper::generate_stub_outpoint, setup::setup_test};
+use super::super::{
+    helper::generate_stub_outpoint, peg_out_graph::utils::setup_and_create_stub_graphs,
+    setup::setup_test,
+};
 
 #[tokio::test]
 async fn test_peg_out_graph_serialization() {
@@ -54,14 +59,94 @@ async fn test_peg_out_graph_serialization() {
     let (peg_out_graph, _) = PegOutGraph::new(
         &config.operator_context,
         &peg_in_graph,
-        Input {
+        vec![Input {
             outpoint: kick_off_outpoint,
             amount: kick_off_amount,
-        },
-    );
+        }],
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
 
     let json = serialize(&peg_out_graph);
     assert!(!json.is_empty());
     let deserialized_peg_out_graph = deserialize::<PegOutGraph>(&json);
     assert!(peg_out_graph == deserialized_peg_out_graph);
 }
+
+#[tokio::test]
+async fn test_peg_out_graph_binary_serialization() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let start = Instant::now();
+    let json = serialize(&peg_out_graph);
+    let json_serialization_time = start.elapsed();
+    let start = Instant::now();
+    let deserialized_peg_out_graph = deserialize::<PegOutGraph>(&json);
+    let json_deserialization_time = start.elapsed();
+    assert!(peg_out_graph == deserialized_peg_out_graph);
+
+    let start = Instant::now();
+    let binary = peg_out_graph.to_bincode();
+    let binary_serialization_time = start.elapsed();
+    let start = Instant::now();
+    let deserialized_peg_out_graph = PegOutGraph::from_bincode(&binary).unwrap();
+    let binary_deserialization_time = start.elapsed();
+    assert!(peg_out_graph == deserialized_peg_out_graph);
+
+    println!(
+        "JSON: {} bytes, serialized in {:?}, deserialized in {:?}",
+        json.len(),
+        json_serialization_time,
+        json_deserialization_time
+    );
+    println!(
+        "Binary: {} bytes, serialized in {:?}, deserialized in {:?}",
+        binary.len(),
+        binary_serialization_time,
+        binary_deserialization_time
+    );
+    // JSON spells out every byte of the txs, scripts and Winternitz keys in hex or
+    // as a number
+    assert!(binary.len() * 2 < json.len());
+}
+
+#[tokio::test]
+async fn test_peg_out_graph_binary_version_mismatch() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let other_version = format!("{GRAPH_VERSION}.1");
+    let binary = serialize_binary(&other_version, &peg_out_graph);
+    let err = PegOutGraph::from_bincode(&binary).unwrap_err();
+    assert!(err.contains(&other_version));
+
+    let binary = peg_out_graph.to_bincode();
+    assert!(PegOutGraph::from_bincode(&binary[..binary.len() - 1]).is_err());
+}
+
+#[tokio::test]
+async fn test_peg_out_graph_deserialize_checked() {
+    let (_, _, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+
+    let json = serialize(&peg_out_graph);
+    assert!(PegOutGraph::deserialize_checked(&json).unwrap() == peg_out_graph);
+
+    let mut value = serde_json::to_value(&peg_out_graph).unwrap();
+    value["version"] = "9.9".into();
+    assert_eq!(
+        PegOutGraph::deserialize_checked(&value.to_string()).err(),
+        Some(GraphVersionError::Unsupported(String::from("9.9")))
+    );
+    // plain serde does not look at the version
+    assert!(serde_json::from_value::<PegOutGraph>(value.clone()).is_ok());
+
+    value.as_object_mut().unwrap().remove("version");
+    assert_eq!(
+        PegOutGraph::deserialize_checked(&value.to_string()).err(),
+        Some(GraphVersionError::Missing)
+    );
+
+    assert!(matches!(
+        PegOutGraph::deserialize_checked(&json[..json.len() / 2]),
+        Err(GraphVersionError::Malformed(_))
+    ));
+}
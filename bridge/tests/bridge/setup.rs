@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use bitcoin::{Network, PublicKey};
 
 use bitvm::{
-    chunker::assigner::BridgeAssigner,
+    chunker::{assigner::BridgeAssigner, disprove_execution::RawProof},
     signatures::signing_winternitz::{WinternitzPublicKey, WinternitzSecret},
     signatures::winternitz::Parameters,
 };
@@ -32,7 +32,7 @@ use bridge::{
         },
         peg_out::CommitmentMessageId,
     },
-    superblock::{SUPERBLOCK_HASH_MESSAGE_LENGTH, SUPERBLOCK_MESSAGE_LENGTH},
+    superblock::{SuperblockPolicy, SUPERBLOCK_HASH_MESSAGE_LENGTH, SUPERBLOCK_MESSAGE_LENGTH},
     transactions::assert_transactions::utils::{
         groth16_commitment_secrets_to_public_keys, merge_to_connector_c_commits_public_key,
         AssertCommit1ConnectorsE, AssertCommit2ConnectorsE, AssertCommitConnectorsF,
@@ -82,11 +82,11 @@ pub async fn setup_test() -> SetupConfig {
     let depositor_context =
         DepositorContext::new(source_network, DEPOSITOR_SECRET, &n_of_n_public_keys);
     let operator_context =
-        OperatorContext::new(source_network, OPERATOR_SECRET, &n_of_n_public_keys);
+        OperatorContext::new(source_network, OPERATOR_SECRET, &n_of_n_public_keys).unwrap();
     let verifier_0_context =
-        VerifierContext::new(source_network, VERIFIER_0_SECRET, &n_of_n_public_keys);
+        VerifierContext::new(source_network, VERIFIER_0_SECRET, &n_of_n_public_keys).unwrap();
     let verifier_1_context =
-        VerifierContext::new(source_network, VERIFIER_1_SECRET, &n_of_n_public_keys);
+        VerifierContext::new(source_network, VERIFIER_1_SECRET, &n_of_n_public_keys).unwrap();
     let withdrawer_context =
         WithdrawerContext::new(source_network, WITHDRAWER_SECRET, &n_of_n_public_keys);
 
@@ -153,6 +153,7 @@ pub async fn setup_test() -> SetupConfig {
             &connector_e1_commitment_public_keys,
             &connector_e2_commitment_public_keys,
         ),
+        &RawProof::default().vk,
     );
 
     let connector_z = ConnectorZ::new(
@@ -179,6 +180,7 @@ pub async fn setup_test() -> SetupConfig {
                 ),
             ),
         ]),
+        SuperblockPolicy::Single,
     );
     let connector_2 = Connector2::new(
         source_network,
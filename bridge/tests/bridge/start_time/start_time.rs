@@ -1,9 +1,9 @@
-use bitcoin::{consensus::encode::serialize_hex, Amount};
+use bitcoin::{consensus::encode::serialize_hex, Amount, Network};
 
 use bridge::{
     connectors::base::TaprootConnector,
     graphs::{base::DUST_AMOUNT, peg_out::CommitmentMessageId},
-    superblock::get_start_time_block_number,
+    superblock::{get_start_time_block_number, SuperblockParams},
     transactions::{
         base::{BaseTransaction, Input},
         start_time::StartTimeTransaction,
@@ -38,12 +38,14 @@ async fn test_start_time_tx() {
         },
     );
 
-    start_time_tx.sign(
-        &config.operator_context,
-        &config.connector_2,
-        get_start_time_block_number(),
-        &config.commitment_secrets[&CommitmentMessageId::StartTime],
-    );
+    start_time_tx
+        .sign(
+            &config.operator_context,
+            &config.connector_2,
+            get_start_time_block_number(&SuperblockParams::for_network(Network::Regtest)),
+            &config.commitment_secrets[&CommitmentMessageId::StartTime],
+        )
+        .unwrap();
 
     let tx = start_time_tx.finalize();
     println!("Script Path Spend Transaction: {:?}\n", tx);
@@ -0,0 +1,375 @@
+use std::{str::FromStr, time::Instant};
+
+use bitcoin::{block::Header, pow::Work, Amount, CompactTarget, Network, OutPoint, Txid};
+
+use bridge::{
+    graphs::{
+        base::{FEE_AMOUNT, INITIAL_AMOUNT},
+        peg_out::{CommitmentMessageId, GraphConstructionError, PegOutGraph, PegOutGraphConfig},
+    },
+    superblock::{
+        find_superblock, find_superblock_from_esplora, find_superblocks,
+        get_superblock_hash_message, get_superblock_message, get_superblock_weight,
+        get_superblocks_hash_message, get_superblocks_message, get_superblocks_weight,
+        is_superblock_claim_disproved, is_superblock_set_valid, validate_superblock,
+        NoSuperblockFound, SuperblockCriteria, SuperblockError, SuperblockParams, SuperblockPolicy,
+        SUPERBLOCK_MESSAGE_LENGTH,
+    },
+    transactions::base::Input,
+};
+
+use crate::bridge::{
+    helper::get_superblock_header, peg_out_graph::utils::setup_and_create_stub_graphs,
+    setup::setup_test,
+};
+
+fn get_superblock_chain(length: usize) -> Vec<Header> {
+    let mut headers = vec![get_superblock_header()];
+    while headers.len() < length {
+        let mut next = get_superblock_header();
+        next.prev_blockhash = headers.last().unwrap().block_hash();
+        next.time += headers.len() as u32;
+        headers.push(next);
+    }
+    headers
+}
+
+#[test]
+fn test_single_superblock_policy() {
+    let policy = SuperblockPolicy::default();
+    assert_eq!(policy, SuperblockPolicy::Single);
+    assert_eq!(policy.message_length(), SUPERBLOCK_MESSAGE_LENGTH);
+
+    let superblock = get_superblock_header();
+    let superblocks = vec![superblock];
+    assert!(is_superblock_set_valid(policy, &superblocks));
+    assert_eq!(
+        get_superblocks_message(&superblocks),
+        get_superblock_message(&superblock)
+    );
+    assert_eq!(
+        get_superblocks_hash_message(&superblocks),
+        Ok(get_superblock_hash_message(&superblock))
+    );
+
+    let mut other_superblock = get_superblock_header();
+    other_superblock.nonce += 1;
+    let other_is_heavier =
+        get_superblock_weight(&other_superblock) > get_superblock_weight(&superblock);
+    assert_eq!(
+        is_superblock_claim_disproved(policy, &superblocks, &[other_superblock]),
+        other_is_heavier
+    );
+    assert_eq!(
+        is_superblock_claim_disproved(policy, &[other_superblock], &superblocks),
+        !other_is_heavier
+    );
+    assert!(!is_superblock_claim_disproved(
+        policy,
+        &superblocks,
+        &get_superblock_chain(2)
+    ));
+}
+
+#[test]
+fn test_chain_superblock_policy() {
+    let policy = SuperblockPolicy::Chain(3);
+    assert_eq!(policy.message_length(), 3 * SUPERBLOCK_MESSAGE_LENGTH);
+
+    let superblocks = get_superblock_chain(3);
+    assert!(is_superblock_set_valid(policy, &superblocks));
+    assert_eq!(
+        get_superblocks_message(&superblocks).len(),
+        policy.message_length()
+    );
+    assert_eq!(
+        get_superblocks_hash_message(&superblocks),
+        Ok(get_superblock_hash_message(&superblocks[2]))
+    );
+    assert!(get_superblocks_weight(&superblocks).unwrap() > get_superblock_weight(&superblocks[0]));
+
+    // every header of the set must extend the previous one
+    let mut broken_superblocks = superblocks.clone();
+    broken_superblocks[1].prev_blockhash = broken_superblocks[2].block_hash();
+    assert!(!is_superblock_set_valid(policy, &broken_superblocks));
+    assert!(!is_superblock_claim_disproved(
+        policy,
+        &superblocks,
+        &broken_superblocks
+    ));
+
+    // a claim covering fewer superblocks than required is always disproved
+    assert!(is_superblock_claim_disproved(
+        policy,
+        &superblocks[..2],
+        &superblocks
+    ));
+    assert!(!is_superblock_claim_disproved(
+        policy,
+        &superblocks,
+        &superblocks
+    ));
+}
+
+#[test]
+fn test_superblock_policy_without_superblocks() {
+    assert_eq!(
+        SuperblockPolicy::chain(0),
+        Err(SuperblockError::NoSuperblocks)
+    );
+    assert_eq!(SuperblockPolicy::chain(2), Ok(SuperblockPolicy::Chain(2)));
+    assert_eq!(
+        SuperblockPolicy::Chain(0).validate(),
+        Err(SuperblockError::NoSuperblocks)
+    );
+    assert_eq!(SuperblockPolicy::Single.validate(), Ok(()));
+
+    assert_eq!(
+        get_superblocks_hash_message(&[]),
+        Err(SuperblockError::NoSuperblocks)
+    );
+    assert_eq!(
+        find_superblocks(
+            SuperblockPolicy::Chain(0),
+            &get_superblock_candidates(),
+            &SuperblockCriteria::new(&SuperblockParams::for_network(Network::Bitcoin), 100)
+        ),
+        Err(NoSuperblockFound)
+    );
+}
+
+fn get_superblock_candidates() -> Vec<(u32, Header)> {
+    (0..3)
+        .map(|index| {
+            let mut header = get_superblock_header();
+            header.nonce += index;
+            (100 + index, header)
+        })
+        .collect()
+}
+
+#[test]
+fn test_find_superblock_none_qualifies() {
+    let candidates = get_superblock_candidates();
+    let heaviest_weight = candidates
+        .iter()
+        .map(|(_, header)| get_superblock_weight(header))
+        .max()
+        .unwrap();
+
+    let mut one = [0; 32];
+    one[31] = 1;
+    let criteria = SuperblockCriteria {
+        min_weight: heaviest_weight + Work::from_be_bytes(one),
+        search_deadline: None,
+        candidate_range: 100..=102,
+    };
+    assert_eq!(
+        find_superblock(&candidates, &criteria),
+        Err(NoSuperblockFound)
+    );
+
+    let criteria = SuperblockCriteria {
+        candidate_range: 103..=200,
+        ..SuperblockCriteria::new(&SuperblockParams::for_network(Network::Bitcoin), 100)
+    };
+    assert_eq!(
+        find_superblocks(SuperblockPolicy::Single, &candidates, &criteria),
+        Err(NoSuperblockFound)
+    );
+
+    let criteria = SuperblockCriteria {
+        search_deadline: Some(Instant::now()),
+        ..SuperblockCriteria::new(&SuperblockParams::for_network(Network::Bitcoin), 100)
+    };
+    assert_eq!(
+        find_superblock(&candidates, &criteria),
+        Err(NoSuperblockFound)
+    );
+}
+
+#[test]
+fn test_find_superblock_one_qualifies() {
+    let candidates = get_superblock_candidates();
+    let (_, heaviest) = *candidates
+        .iter()
+        .max_by_key(|(_, header)| get_superblock_weight(header))
+        .unwrap();
+
+    let criteria = SuperblockCriteria {
+        min_weight: get_superblock_weight(&heaviest),
+        ..SuperblockCriteria::new(&SuperblockParams::for_network(Network::Bitcoin), 100)
+    };
+    assert_eq!(find_superblock(&candidates, &criteria), Ok(heaviest));
+    assert_eq!(
+        find_superblocks(SuperblockPolicy::Single, &candidates, &criteria),
+        Ok(vec![heaviest])
+    );
+}
+
+#[test]
+fn test_find_superblock_chain() {
+    let chain = get_superblock_chain(4);
+    let candidates = chain
+        .iter()
+        .enumerate()
+        .map(|(index, header)| (100 + index as u32, *header))
+        .collect::<Vec<_>>();
+    let criteria = SuperblockCriteria::new(&SuperblockParams::for_network(Network::Bitcoin), 100);
+
+    let mut heaviest = &chain[..2];
+    for run in chain.windows(2) {
+        if get_superblocks_weight(run) > get_superblocks_weight(heaviest) {
+            heaviest = run;
+        }
+    }
+    assert_eq!(
+        find_superblocks(SuperblockPolicy::Chain(2), &candidates, &criteria),
+        Ok(heaviest.to_vec())
+    );
+    assert_eq!(
+        find_superblocks(SuperblockPolicy::Chain(4), &candidates, &criteria),
+        Ok(chain.clone())
+    );
+    assert_eq!(
+        find_superblocks(SuperblockPolicy::Chain(5), &candidates, &criteria),
+        Err(NoSuperblockFound)
+    );
+
+    // the run must be made of consecutive heights
+    let mut gapped_candidates = candidates.clone();
+    gapped_candidates[2].0 += 10;
+    assert_eq!(
+        find_superblocks(SuperblockPolicy::Chain(4), &gapped_candidates, &criteria),
+        Err(NoSuperblockFound)
+    );
+
+    // the plain candidates do not extend each other
+    assert_eq!(
+        find_superblocks(
+            SuperblockPolicy::Chain(2),
+            &get_superblock_candidates(),
+            &criteria
+        ),
+        Err(NoSuperblockFound)
+    );
+}
+
+#[test]
+fn test_validate_superblock() {
+    let superblock = get_superblock_header();
+    let window = get_superblock_candidates()
+        .into_iter()
+        .map(|(_, header)| header)
+        .collect::<Vec<_>>();
+    assert_eq!(validate_superblock(&superblock, &window[..1]), Ok(()));
+    assert_eq!(
+        validate_superblock(&superblock, &window[1..]),
+        Err(SuperblockError::OutsideWindow)
+    );
+
+    // the other candidates no longer meet the target of the block they were mined as
+    assert_eq!(
+        validate_superblock(&window[1], &window),
+        Err(SuperblockError::InsufficientWork)
+    );
+}
+
+#[test]
+fn test_validate_superblock_not_heaviest() {
+    // a header that only meets the regtest target is lighter than a mainnet block
+    let mut superblock = get_superblock_header();
+    superblock.bits = CompactTarget::from_consensus(0x207fffff);
+    while superblock.validate_pow(superblock.target()).is_err() {
+        superblock.nonce += 1;
+    }
+    let heavier = get_superblock_header();
+    assert!(get_superblock_weight(&heavier) > get_superblock_weight(&superblock));
+
+    assert_eq!(validate_superblock(&superblock, &[superblock]), Ok(()));
+    assert_eq!(
+        validate_superblock(&superblock, &[superblock, heavier]),
+        Err(SuperblockError::NotHeaviest(heavier.block_hash()))
+    );
+    assert_eq!(
+        validate_superblock(&heavier, &[superblock, heavier]),
+        Ok(())
+    );
+}
+
+#[tokio::test]
+async fn test_find_superblock_from_esplora() {
+    let config = setup_test().await;
+    let client = &config.client_0.esplora;
+
+    // the latest blocks mined on regtest
+    let end_height = client.get_height().await.unwrap();
+    let start_height = end_height.saturating_sub(9);
+    let mut headers = vec![];
+    for height in start_height..=end_height {
+        let block_hash = client.get_block_hash(height).await.unwrap();
+        headers.push(client.get_header_by_hash(&block_hash).await.unwrap());
+    }
+    let heaviest = *headers
+        .iter()
+        .max_by_key(|header| get_superblock_weight(header))
+        .unwrap();
+
+    let superblock = find_superblock_from_esplora(client, start_height, end_height)
+        .await
+        .unwrap();
+    assert_eq!(superblock, heaviest);
+    assert!(headers.contains(&superblock));
+
+    assert_eq!(
+        find_superblock_from_esplora(client, end_height + 1, end_height + 10).await,
+        Err(SuperblockError::NotFound)
+    );
+}
+
+#[tokio::test]
+async fn test_peg_out_graph_superblock_policy() {
+    let (config, peg_in_graph, peg_out_graph, _) = setup_and_create_stub_graphs().await;
+    assert_eq!(peg_out_graph.superblock_policy(), SuperblockPolicy::Single);
+
+    let peg_out_confirm_inputs = vec![Input {
+        outpoint: OutPoint {
+            txid: Txid::from_str(
+                "4e254eab8a41f14f56491813a7100cebe305d84edf09488001d9dd3d180a4900",
+            )
+            .unwrap(),
+            vout: 0,
+        },
+        amount: Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT + 1),
+    }];
+    assert!(matches!(
+        PegOutGraph::new_with_superblock_policy(
+            &config.operator_context,
+            &peg_in_graph,
+            peg_out_confirm_inputs.clone(),
+            PegOutGraphConfig::default(),
+            SuperblockPolicy::Chain(0),
+        ),
+        Err(GraphConstructionError::InvalidSuperblockPolicy(
+            SuperblockError::NoSuperblocks
+        ))
+    ));
+
+    let policy = SuperblockPolicy::Chain(2);
+    let (peg_out_graph, commitment_secrets) = PegOutGraph::new_with_superblock_policy(
+        &config.operator_context,
+        &peg_in_graph,
+        peg_out_confirm_inputs,
+        PegOutGraphConfig::default(),
+        policy,
+    )
+    .unwrap();
+
+    assert_eq!(peg_out_graph.superblock_policy(), policy);
+    assert!(peg_out_graph.validate());
+    assert!(commitment_secrets.contains_key(&CommitmentMessageId::Superblock));
+
+    let superblocks = get_superblock_chain(2);
+    assert!(!peg_out_graph.is_superblock_commitment_disproved(&superblocks, &superblocks));
+    assert!(peg_out_graph.is_superblock_commitment_disproved(&superblocks[..1], &superblocks));
+}
@@ -1 +1,2 @@
 pub mod take_1;
+pub mod take_1_anchor;
@@ -69,6 +69,7 @@ async fn test_take_1_tx() {
             outpoint: funding_outpoint3,
             amount: input_value3,
         },
+        false,
     );
 
     let secret_nonces_0 = take_1_tx.push_nonces(&config.verifier_0_context);
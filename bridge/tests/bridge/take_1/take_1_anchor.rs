@@ -0,0 +1,158 @@
+use bitcoin::{Address, Amount, EcdsaSighashType};
+
+use bridge::{
+    connectors::base::{P2wshConnector, TaprootConnector},
+    graphs::base::{DUST_AMOUNT, FEE_AMOUNT, INITIAL_AMOUNT, ONE_HUNDRED},
+    scripts::generate_p2wpkh_address,
+    transactions::{
+        anchor::{anchor_vout, ANCHOR_AMOUNT},
+        base::{BaseTransaction, Input},
+        pre_signed::PreSignedTransaction,
+        pre_signed_musig2::PreSignedMusig2Transaction,
+        signing::populate_p2wpkh_witness,
+        take_1::Take1Transaction,
+    },
+};
+
+use crate::bridge::faucet::{Faucet, FaucetType};
+
+use super::super::{
+    helper::{generate_stub_outpoint, submit_package_by_cli},
+    setup::setup_test,
+};
+
+const MIN_RELAY_SAT_PER_VB: f64 = 1.0;
+
+#[tokio::test]
+async fn test_take_1_anchor_cpfp() {
+    let config = setup_test().await;
+    let faucet = Faucet::new(FaucetType::EsploraRegtest);
+
+    let input_value0 = Amount::from_sat(INITIAL_AMOUNT + FEE_AMOUNT);
+    let funding_utxo_address0 = config.connector_0.generate_taproot_address();
+    let input_value1 = Amount::from_sat(DUST_AMOUNT);
+    let funding_utxo_address1 = config.connector_a.generate_taproot_address();
+    let input_value2 = Amount::from_sat(DUST_AMOUNT);
+    let funding_utxo_address2 = config.connector_3.generate_address();
+    let input_value3 = Amount::from_sat(ONE_HUNDRED * 2 / 100);
+    let funding_utxo_address3 = config.connector_b.generate_taproot_address();
+    let cpfp_value = Amount::from_sat(DUST_AMOUNT * 2);
+    let cpfp_address = generate_p2wpkh_address(
+        config.operator_context.network,
+        &config.operator_context.operator_public_key,
+    );
+    let funding_inputs: Vec<(&Address, Amount)> = vec![
+        (&funding_utxo_address0, input_value0),
+        (&funding_utxo_address1, input_value1),
+        (&funding_utxo_address2, input_value2),
+        (&funding_utxo_address3, input_value3),
+        (&cpfp_address, cpfp_value),
+    ];
+    faucet
+        .fund_inputs(&config.client_0, &funding_inputs)
+        .await
+        .wait()
+        .await;
+
+    let funding_outpoint0 =
+        generate_stub_outpoint(&config.client_0, &funding_utxo_address0, input_value0).await;
+    let funding_outpoint1 =
+        generate_stub_outpoint(&config.client_0, &funding_utxo_address1, input_value1).await;
+    let funding_outpoint2 =
+        generate_stub_outpoint(&config.client_0, &funding_utxo_address2, input_value2).await;
+    let funding_outpoint3 =
+        generate_stub_outpoint(&config.client_0, &funding_utxo_address3, input_value3).await;
+    let cpfp_outpoint = generate_stub_outpoint(&config.client_0, &cpfp_address, cpfp_value).await;
+
+    let mut take_1_tx = Take1Transaction::new(
+        &config.operator_context,
+        &config.connector_0,
+        &config.connector_3,
+        &config.connector_a,
+        &config.connector_b,
+        Input {
+            outpoint: funding_outpoint0,
+            amount: input_value0,
+        },
+        Input {
+            outpoint: funding_outpoint1,
+            amount: input_value1,
+        },
+        Input {
+            outpoint: funding_outpoint2,
+            amount: input_value2,
+        },
+        Input {
+            outpoint: funding_outpoint3,
+            amount: input_value3,
+        },
+        true,
+    );
+    // a parent paying a single sat is below the minimum relay fee rate on its own
+    take_1_tx.tx_mut().output[0].value += Amount::from_sat(FEE_AMOUNT - 1);
+
+    // the anchor is in the tx the verifiers sign
+    let secret_nonces_0 = take_1_tx.push_nonces(&config.verifier_0_context);
+    let secret_nonces_1 = take_1_tx.push_nonces(&config.verifier_1_context);
+    take_1_tx.pre_sign(
+        &config.verifier_0_context,
+        &config.connector_0,
+        &config.connector_b,
+        &secret_nonces_0,
+    );
+    take_1_tx.pre_sign(
+        &config.verifier_1_context,
+        &config.connector_0,
+        &config.connector_b,
+        &secret_nonces_1,
+    );
+
+    let tx = take_1_tx.finalize();
+    let anchor_vout = anchor_vout(&tx).unwrap();
+    assert_eq!(tx.output[anchor_vout].value, ANCHOR_AMOUNT);
+
+    let mut child_tx = take_1_tx
+        .build_cpfp_child(
+            Input {
+                outpoint: cpfp_outpoint,
+                amount: cpfp_value,
+            },
+            5.0,
+            &cpfp_address,
+        )
+        .unwrap();
+    assert_eq!(child_tx.input[0].previous_output.txid, tx.compute_txid());
+    populate_p2wpkh_witness(
+        &config.operator_context,
+        &mut child_tx,
+        1,
+        EcdsaSighashType::All,
+        cpfp_value,
+        &config.operator_context.operator_public_key,
+        &config.operator_context.operator_keypair,
+    );
+
+    // only the child brings the package above the minimum relay fee rate
+    let parent_fee = Amount::from_sat(1);
+    let child_fee = ANCHOR_AMOUNT + cpfp_value - child_tx.output[0].value;
+    let parent_fee_rate = parent_fee.to_sat() as f64 / tx.vsize() as f64;
+    let package_fee_rate = (parent_fee + child_fee).to_sat() as f64
+        / (tx.vsize() + child_tx.vsize()) as f64;
+    assert!(parent_fee_rate < MIN_RELAY_SAT_PER_VB);
+    assert!(package_fee_rate >= MIN_RELAY_SAT_PER_VB);
+
+    assert!(config.client_0.esplora.broadcast(&tx).await.is_err());
+    let result = submit_package_by_cli(&[&tx, &child_tx]);
+    assert_eq!(result["package_msg"], "success", "{result}");
+
+    assert!(take_1_tx
+        .build_cpfp_child(
+            Input {
+                outpoint: cpfp_outpoint,
+                amount: cpfp_value,
+            },
+            1_000.0,
+            &cpfp_address,
+        )
+        .is_err());
+}
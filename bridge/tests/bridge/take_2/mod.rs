@@ -1 +1,2 @@
 pub mod take_2;
+pub mod take_2_signature;
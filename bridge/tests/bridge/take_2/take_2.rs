@@ -72,6 +72,7 @@ async fn test_take_2_tx() {
             outpoint: funding_outpoint3,
             amount: input_value3,
         },
+        false,
     );
 
     let secret_nonces_0 = take_2_tx.push_nonces(&config.verifier_0_context);
@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use bitcoin::{Amount, OutPoint, Txid};
+
+use bridge::{
+    connectors::connector_c::ConnectorC,
+    graphs::base::{DUST_AMOUNT, INITIAL_AMOUNT},
+    transactions::{base::Input, take_2::Take2Transaction},
+};
+
+use super::super::setup::{setup_test, SetupConfig};
+
+fn stub_input(vout: u32, amount: u64) -> Input {
+    Input {
+        outpoint: OutPoint {
+            txid: Txid::from_str(
+                "8b4cce4a1a9522392c095df6416533d89e1e6ac7bdf8ab3c1685426b321ed182",
+            )
+            .unwrap(),
+            vout,
+        },
+        amount: Amount::from_sat(amount),
+    }
+}
+
+fn take_2_tx(config: &SetupConfig, connector_c: &ConnectorC) -> Take2Transaction {
+    Take2Transaction::new(
+        &config.operator_context,
+        &config.connector_0,
+        &config.connector_4,
+        &config.connector_5,
+        connector_c,
+        stub_input(0, INITIAL_AMOUNT),
+        stub_input(1, DUST_AMOUNT),
+        stub_input(2, DUST_AMOUNT),
+        stub_input(3, DUST_AMOUNT),
+        false,
+    )
+}
+
+#[tokio::test]
+async fn test_take_2_signature() {
+    let config = setup_test().await;
+
+    let mut take_2_tx = take_2_tx(&config, &config.connector_c);
+    assert!(!take_2_tx.is_input_3_signed());
+
+    take_2_tx.sign(&config.operator_context, &config.connector_c);
+    assert!(take_2_tx.is_input_3_signed());
+}
+
+#[tokio::test]
+async fn test_take_2_signature_with_broken_connector_c() {
+    let config = setup_test().await;
+
+    // the output is locked to a key other than the operator's
+    let mut connector_c = config.connector_c.clone();
+    connector_c.operator_taproot_public_key = config.operator_context.n_of_n_taproot_public_key;
+    let mut take_2_tx = take_2_tx(&config, &connector_c);
+
+    take_2_tx.sign(&config.operator_context, &connector_c);
+    assert!(!take_2_tx.is_input_3_signed());
+}
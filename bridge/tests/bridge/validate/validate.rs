@@ -6,7 +6,7 @@ use bridge::{
     graphs::{
         base::{FEE_AMOUNT, INITIAL_AMOUNT},
         peg_in::PegInGraph,
-        peg_out::PegOutGraph,
+        peg_out::{PegOutGraph, PegOutGraphConfig},
     },
     scripts::generate_burn_script,
     transactions::{base::Input, pre_signed::PreSignedTransaction},
@@ -117,11 +117,13 @@ async fn setup_and_create_graphs() -> (PegInGraph, PegOutGraph, OutPoint) {
     let (peg_out_graph, _) = PegOutGraph::new(
         &config.operator_context,
         &peg_in_graph,
-        Input {
+        vec![Input {
             outpoint: peg_out_outpoint,
             amount,
-        },
-    );
+        }],
+        PegOutGraphConfig::default(),
+    )
+    .unwrap();
 
     (peg_in_graph, peg_out_graph, peg_in_outpoint)
 }